@@ -0,0 +1,76 @@
+//! Compares single threaded and parallel entropy calculation times.
+//!
+//! Run with `cargo bench --bench entropy`; the size of the input, in megabytes, defaults to 1024 and may be set with
+//! the `BINWALK_BENCH_MB` environment variable.
+use binwalk::entropy::{block_entropy, classify_entropy};
+use std::time::{Duration, Instant};
+
+/// Block size used by `binwalk --entropy` for a 1GB input
+const BLOCK_SIZE: usize = 1024 * 1024 * 1024 / 2048;
+
+/// Times `classify_entropy` and `block_entropy`, respectively, on the specified number of threads
+fn time_entropy(data: &[u8], threads: usize) -> (Duration, Duration) {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .expect("Failed to create thread pool");
+
+    let start = Instant::now();
+    let blocks = pool.install(|| classify_entropy(data, BLOCK_SIZE.min(data.len()), None));
+    let classify_elapsed = start.elapsed();
+
+    assert!(blocks.is_ok());
+
+    let start = Instant::now();
+    let entropy = pool.install(|| block_entropy(data, BLOCK_SIZE));
+    let block_elapsed = start.elapsed();
+
+    assert!(!entropy.is_empty());
+    (classify_elapsed, block_elapsed)
+}
+
+fn main() {
+    let megabytes: usize = std::env::var("BINWALK_BENCH_MB")
+        .ok()
+        .and_then(|mb| mb.parse().ok())
+        .unwrap_or(1024);
+
+    // Pseudo-random data generated with xorshift, so that every block is classified as well
+    let mut state: u64 = 0x2545F4914F6CDD1D;
+    let data: Vec<u8> = (0..megabytes * 1024 * 1024)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 32) as u8
+        })
+        .collect();
+
+    let threads = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+
+    let (classify_single_threaded, block_single_threaded) = time_entropy(&data, 1);
+    let (classify_parallel, block_parallel) = time_entropy(&data, threads);
+
+    for (name, single_threaded, parallel) in [
+        (
+            "classify_entropy",
+            classify_single_threaded,
+            classify_parallel,
+        ),
+        ("block_entropy", block_single_threaded, block_parallel),
+    ] {
+        println!("{name} of {megabytes}MB:");
+        println!("  1 thread:    {single_threaded:.2?}");
+        println!(
+            "  {:<11} {parallel:.2?} ({:.2}x speedup)",
+            format!("{threads} thread(s):"),
+            single_threaded.as_secs_f64() / parallel.as_secs_f64()
+        );
+    }
+
+    if threads == 1 {
+        println!(
+            "  Only one core is available; run on a multi-core machine to measure the parallel speedup"
+        );
+    }
+}
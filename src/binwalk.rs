@@ -1,11 +1,12 @@
 //! Primary Binwalk interface.
 
+use crate::logging::{debug, error, info, warn};
 use aho_corasick::AhoCorasick;
-use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path;
+use std::sync::mpsc;
 use uuid::Uuid;
 
 #[cfg(windows)]
@@ -14,11 +15,15 @@ use std::os::windows;
 #[cfg(unix)]
 use std::os::unix;
 
-use crate::common::{is_offset_safe, read_file};
+use crate::common::{guess_file_type, hex_preview, is_offset_safe, read_file};
 use crate::extractors;
+use crate::logging;
 use crate::magic;
 use crate::signatures;
 
+/// Default number of bytes that trial decodes must decode, see `Binwalk.trial_size`
+pub const DEFAULT_TRIAL_SIZE: usize = 4096;
+
 /// Returned on initialization error
 #[derive(Debug, Default, Clone)]
 pub struct BinwalkError {
@@ -43,6 +48,39 @@ pub struct AnalysisResults {
     /// File extraction results, as returned by Binwalk::extract.
     /// HashMap key is the corresponding SignatureResult.id value in `file_map`.
     pub extractions: HashMap<String, extractors::common::ExtractionResult>,
+    /// Regions of the file not covered by any signature in `file_map`, as returned by Binwalk::find_gaps.
+    /// Only populated if `ExtractionConfig.gap_min_size` is set; any gaps that were carved to disk have their
+    /// extraction results recorded in `extractions`.
+    #[serde(default)]
+    pub gaps: Vec<signatures::common::SignatureResult>,
+    /// MIME type of the file, as guessed by `common::guess_file_type`, if no signatures were found in it.
+    /// Only populated if `Binwalk.file_type_lookup` is enabled.
+    #[serde(default)]
+    pub file_type: Option<String>,
+}
+
+/// A magic byte match, as returned by Binwalk::find_magic
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct MagicMatch {
+    /// Offset of the magic bytes in the file data; this is not necessarily where the matching file starts
+    pub offset: usize,
+    /// Name of the signature that the magic bytes belong to
+    pub name: String,
+}
+
+/// What Binwalk::extract would do with a signature, as returned by Binwalk::plan
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PlannedSignature {
+    /// The signature result, as returned by Binwalk::scan
+    pub signature: signatures::common::SignatureResult,
+    /// Number of signatures that this signature is nested within; 0 if it is not nested
+    pub depth: usize,
+    /// The extraction that would be performed, if the signature would be extracted
+    pub extraction: Option<extractors::common::PlannedExtraction>,
+    /// The reason the signature would not be extracted, if it would not be
+    pub skip_reason: Option<String>,
+    /// True if the extracted files would be analyzed during recursive extraction
+    pub recurse: bool,
 }
 
 /// Analyze files / memory for file signatures
@@ -81,6 +119,20 @@ pub struct Binwalk {
     pub pattern_signature_table: HashMap<usize, signatures::common::Signature>,
     /// Maps signatures to their corresponding extractors
     pub extractor_lookup_table: HashMap<String, Option<extractors::common::Extractor>>,
+    /// Run-time options passed to each extractor execution
+    pub extraction_config: extractors::common::ExtractionConfig,
+    /// If set, log messages generated while scanning and extracting are delivered to this sink rather than to the
+    /// global logger
+    pub log_sink: Option<logging::LogSink>,
+    /// Number of bytes that magic matches for signatures with `Signature.trial_validation` set must successfully decode
+    /// before being validated by the signature parser; 0 disables trial validation. Defaults to `DEFAULT_TRIAL_SIZE`.
+    pub trial_size: usize,
+    /// Number of bytes, starting at each signature's offset, to include in `SignatureResult.preview`; 0 (the default)
+    /// disables previews.
+    pub preview_size: usize,
+    /// If true, guess the MIME type of files in which no signatures are found, and of any unidentified gaps in
+    /// files, from a database of common file magics; see `AnalysisResults.file_type`.
+    pub file_type_lookup: bool,
 }
 
 impl Binwalk {
@@ -137,6 +189,7 @@ impl Binwalk {
         full_search: bool,
     ) -> Result<Binwalk, BinwalkError> {
         let mut new_instance = Binwalk {
+            trial_size: DEFAULT_TRIAL_SIZE,
             ..Default::default()
         };
 
@@ -239,6 +292,73 @@ impl Binwalk {
         Ok(new_instance)
     }
 
+    /// Registers an extractor for the named signature, which is used instead of the signature's compiled in extractor.
+    /// Extractors may also be registered for signatures that have no compiled in extractor, including user-defined
+    /// signatures. See `extractors::common::ExtractorRegistry`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use binwalk::Binwalk;
+    /// use binwalk::extractors::common::{Extractor, ExtractorType};
+    /// use binwalk::extractors::elf::extract_elf_sections;
+    ///
+    /// let binwalker = Binwalk::new();
+    ///
+    /// binwalker.register_extractor(
+    ///     "elf",
+    ///     Extractor {
+    ///         utility: ExtractorType::Internal(extract_elf_sections),
+    ///         ..Default::default()
+    ///     },
+    /// );
+    ///
+    /// assert!(binwalker.extraction_config.extractor_registry.get("elf").is_some());
+    /// ```
+    pub fn register_extractor(
+        &self,
+        signature_name: &str,
+        extractor: extractors::common::Extractor,
+    ) {
+        self.extraction_config
+            .extractor_registry
+            .register_extractor(signature_name, extractor);
+    }
+
+    /// Scan file data for magic signatures, with no file system side effects.
+    ///
+    /// This is the "find" phase of an analysis on its own: the returned signature results describe the offset, name,
+    /// size, confidence, and description of each identified file, but nothing is extracted and nothing is written to
+    /// disk. The `include` and `exclude` signature filters behave as they do for `Binwalk::configure`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_binwalk_rs_scan_only_0() -> Result<(), binwalk::BinwalkError> {
+    /// use binwalk::Binwalk;
+    ///
+    /// let file_data = std::fs::read("tests/inputs/gzip.bin").expect("Unable to read file");
+    ///
+    /// let signature_results = Binwalk::scan_only(&file_data, None, None)?;
+    ///
+    /// assert_eq!(signature_results.len(), 1);
+    /// assert_eq!(signature_results[0].name, "gzip");
+    /// assert_eq!(signature_results[0].offset, 0);
+    /// # Ok(())
+    /// # } _doctest_main_src_binwalk_rs_scan_only_0(); }
+    /// ```
+    #[allow(dead_code)]
+    pub fn scan_only(
+        file_data: &[u8],
+        include: Option<Vec<String>>,
+        exclude: Option<Vec<String>>,
+    ) -> Result<Vec<signatures::common::SignatureResult>, BinwalkError> {
+        // Without a target file or output directory, configuration does not touch the file system
+        let binwalker = Binwalk::configure(None, None, include, exclude, None, false)?;
+
+        Ok(binwalker.scan(file_data))
+    }
+
     /// Scan a file for magic signatures.
     /// Returns a list of validated magic signatures representing the known contents of the file.
     ///
@@ -263,10 +383,19 @@ impl Binwalk {
     pub fn scan(&self, file_data: &[u8]) -> Vec<signatures::common::SignatureResult> {
         const FILE_START_OFFSET: usize = 0;
 
+        let _log_sink = logging::install_sink(self.log_sink.as_ref());
+
         let mut index_adjustment: usize = 0;
         let mut next_valid_offset: usize = 0;
         let mut previous_valid_offset = None;
 
+        // If nested signatures are reported, signatures are not skipped over while scanning
+        let report_nested = self.extraction_config.nested_signatures
+            != extractors::common::NestedSignaturePolicy::Discard;
+
+        // End offsets and IDs of the signatures that may contain the signature currently being validated, outermost first
+        let mut containers: Vec<(usize, String)> = vec![];
+
         let available_data = file_data.len();
 
         // A list of identified signatures, representing a "map" of the file data
@@ -285,11 +414,17 @@ impl Binwalk {
                 let magic_end = magic_start + magic.len();
 
                 if file_data.len() > magic_end && file_data[magic_start..magic_end] == magic {
+                    let _log_offset = logging::offset_context(magic_start);
+
                     debug!(
                         "Found {} short magic match at offset {:#X}",
                         signature.description, magic_start
                     );
 
+                    if !self.trial_decode(signature, file_data, magic_start) {
+                        continue;
+                    }
+
                     if let Ok(mut signature_result) = (signature.parser)(file_data, magic_start) {
                         // Auto populate some signature result fields
                         signature_result_auto_populate(&mut signature_result, signature);
@@ -301,8 +436,11 @@ impl Binwalk {
                             signature_result.name, FILE_START_OFFSET
                         );
 
-                        // Only update the next_valid_offset if confidence is high; these are, after all, short signatures
-                        if signature_result.confidence >= signatures::common::CONFIDENCE_HIGH {
+                        // Only update the next_valid_offset if confidence is high; these are, after all, short signatures.
+                        // If nested signatures are to be reported, the signature's data must be scanned as well.
+                        if signature_result.confidence >= signatures::common::CONFIDENCE_HIGH
+                            && !report_nested
+                        {
                             next_valid_offset = signature_result.offset + signature_result.size;
                         }
 
@@ -352,6 +490,7 @@ impl Binwalk {
             for magic_match in grep.find_overlapping_iter(&file_data[next_valid_offset..]) {
                 // Get the location of the magic bytes inside the file data
                 let magic_offset: usize = next_valid_offset + magic_match.start();
+                let _log_offset = logging::offset_context(magic_offset);
 
                 // Get the signature associated with this magic signature
                 let magic_pattern_index: usize = magic_match.pattern().as_usize();
@@ -366,6 +505,11 @@ impl Binwalk {
                     signature.description, magic_offset
                 );
 
+                // Cheaply weed out false positive matches for signatures with short magic bytes
+                if !self.trial_decode(&signature, file_data, magic_offset) {
+                    continue;
+                }
+
                 /*
                  * Invoke the signature parser to parse and validate the signature.
                  * An error indicates a false positive match for the signature type.
@@ -392,8 +536,11 @@ impl Binwalk {
                         signature_result.name, signature_result.offset
                     );
 
-                    // Only update the next_valid_offset if confidence is at least medium
-                    if signature_result.confidence >= signatures::common::CONFIDENCE_MEDIUM {
+                    // Only update the next_valid_offset if confidence is at least medium, and nested signatures are not
+                    // to be reported
+                    if signature_result.confidence >= signatures::common::CONFIDENCE_MEDIUM
+                        && !report_nested
+                    {
                         // Only update the next_valid offset if the end of the signature reported the size of its contents
                         if signature_result.size > 0 {
                             // This file's signature has a known size, so there's no need to scan inside this file's data.
@@ -477,6 +624,30 @@ impl Binwalk {
 
             // Else, if the offsets don't conflict, make sure this signature doesn't fall inside a previously identified signature's data
             } else if this_signature.offset < next_valid_offset {
+                containers.retain(|(container_end, _)| this_signature.offset < *container_end);
+
+                // Signatures that lie entirely within another signature's data are reported as nested, if so configured
+                if let Some((container_end, container_id)) = containers.last()
+                    && report_nested
+                    && this_signature.offset + this_signature.size <= *container_end
+                {
+                    debug!(
+                        "Signature {} at offset {:#X} is nested within signature {}",
+                        this_signature.name, this_signature.offset, container_id
+                    );
+                    file_map[i].parent = Some(container_id.clone());
+
+                    if this_signature.confidence >= signatures::common::CONFIDENCE_MEDIUM
+                        && this_signature.size > 0
+                    {
+                        containers.push((
+                            this_signature.offset + this_signature.size,
+                            this_signature.id.clone(),
+                        ));
+                    }
+                    continue;
+                }
+
                 debug!(
                     "Signature {} at offset {:#X} contains conflicting data; ignoring",
                     this_signature.name, this_signature.offset
@@ -502,6 +673,7 @@ impl Binwalk {
             // This signature looks OK, update the next_valid_offset to be the end of this signature's data, only if we're fairly confident in the signature
             if this_signature.confidence >= signatures::common::CONFIDENCE_MEDIUM {
                 next_valid_offset = this_signature.offset + this_signature.size;
+                containers = vec![(next_valid_offset, this_signature.id.clone())];
             }
         }
 
@@ -540,6 +712,15 @@ impl Binwalk {
                     }
                 }
 
+                // Nested signatures can not extend beyond the end of the signature that contains them
+                if let Some(parent) = file_map[i]
+                    .parent
+                    .as_ref()
+                    .and_then(|parent_id| file_map.iter().find(|entry| entry.id == *parent_id))
+                {
+                    next_offset = next_offset.min(parent.offset + parent.size);
+                }
+
                 file_map[i].size = next_offset - file_map[i].offset;
                 warn!(
                     "Signature {}:{:#X} size is unknown; assuming size of {:#X} bytes",
@@ -555,11 +736,82 @@ impl Binwalk {
 
         debug!("Found {} valid signatures", file_map.len());
 
+        if self.preview_size > 0 {
+            for signature in file_map.iter_mut() {
+                let preview_end = signature
+                    .offset
+                    .saturating_add(self.preview_size)
+                    .min(file_data.len());
+                signature.preview = file_data
+                    .get(signature.offset..preview_end)
+                    .map(hex_preview);
+            }
+        }
+
         file_map
     }
 
+    /// Quickly find the magic bytes of all known signatures in the file data, without validating or extracting them.
+    ///
+    /// This is much faster than `scan`, but since the matches are not validated, many of them may be false positives,
+    /// and matches inside of other files are not skipped. Useful as a first pass, to decide whether a full analysis
+    /// of a large file is worthwhile. Results are sorted by offset.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use binwalk::Binwalk;
+    ///
+    /// let file_data = std::fs::read("tests/inputs/gzip.bin").expect("Unable to read file");
+    ///
+    /// let binwalker = Binwalk::new();
+    ///
+    /// let magic_matches = binwalker.find_magic(&file_data);
+    ///
+    /// assert_eq!(magic_matches[0].offset, 0);
+    /// assert_eq!(magic_matches[0].name, "gzip");
+    /// ```
+    #[allow(dead_code)]
+    pub fn find_magic(&self, file_data: &[u8]) -> Vec<MagicMatch> {
+        let mut magic_matches: Vec<MagicMatch> = vec![];
+
+        // Short signatures are only matched at their fixed offset from the start of the file
+        for signature in &self.short_signatures {
+            for magic in &signature.magic {
+                let magic_start = signature.magic_offset;
+
+                if file_data.get(magic_start..magic_start + magic.len()) == Some(magic.as_slice()) {
+                    magic_matches.push(MagicMatch {
+                        offset: magic_start,
+                        name: signature.name.clone(),
+                    });
+                }
+            }
+        }
+
+        let grep = AhoCorasick::new(self.patterns.clone()).unwrap();
+
+        for magic_match in grep.find_overlapping_iter(file_data) {
+            let magic_pattern_index: usize = magic_match.pattern().as_usize();
+
+            if let Some(signature) = self.pattern_signature_table.get(&magic_pattern_index) {
+                magic_matches.push(MagicMatch {
+                    offset: magic_match.start(),
+                    name: signature.name.clone(),
+                });
+            }
+        }
+
+        magic_matches.sort();
+        magic_matches.dedup();
+        magic_matches
+    }
+
     /// Extract all extractable signatures found in a file.
     ///
+    /// If `ExtractionConfig.run_deadline` passes, no further extractions are started, and only the extraction results
+    /// gathered so far are returned.
+    ///
     /// ## Example
     ///
     /// ```
@@ -605,30 +857,343 @@ impl Binwalk {
         &self,
         file_data: &[u8],
         file_name: impl Into<String>,
-        file_map: &Vec<signatures::common::SignatureResult>,
+        file_map: &[signatures::common::SignatureResult],
     ) -> HashMap<String, extractors::common::ExtractionResult> {
+        self.extract_each(file_data, &file_name.into(), file_map, |_, _| ())
+    }
+
+    /// Extract all extractable signatures found in a file in a background thread.
+    ///
+    /// Each extraction result is sent over the returned channel as soon as it completes, paired with the ID of the
+    /// signature it was extracted from. The channel is closed once all signatures have been processed.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_binwalk_rs_extract_stream() -> Result<binwalk::Binwalk, binwalk::BinwalkError> {
+    /// use binwalk::Binwalk;
+    ///
+    /// let target_path = std::path::Path::new("tests")
+    ///     .join("inputs")
+    ///     .join("gzip.bin")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let extraction_directory = std::path::Path::new("tests")
+    ///     .join("extractions_stream")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// # std::fs::remove_dir_all(&extraction_directory);
+    /// let binwalker = Binwalk::configure(Some(target_path),
+    ///                                    Some(extraction_directory.clone()),
+    ///                                    None,
+    ///                                    None,
+    ///                                    None,
+    ///                                    false)?;
+    ///
+    /// let file_data = std::fs::read(&binwalker.base_target_file).expect("Unable to read file");
+    ///
+    /// let scan_results = binwalker.scan(&file_data);
+    /// let receiver = binwalker.extract_stream(file_data, &binwalker.base_target_file, scan_results.clone());
+    ///
+    /// for (signature_id, extraction_result) in receiver {
+    ///     assert_eq!(signature_id, scan_results[0].id);
+    ///     assert_eq!(extraction_result.success, true);
+    /// }
+    /// # std::fs::remove_dir_all(&extraction_directory);
+    /// # Ok(binwalker)
+    /// # } _doctest_main_src_binwalk_rs_extract_stream(); }
+    /// ```
+    #[allow(dead_code)]
+    pub fn extract_stream(
+        &self,
+        file_data: Vec<u8>,
+        file_name: impl Into<String>,
+        file_map: Vec<signatures::common::SignatureResult>,
+    ) -> mpsc::Receiver<(String, extractors::common::ExtractionResult)> {
+        let file_path = file_name.into();
+        let binwalker = self.clone();
+        let (sender, receiver) = mpsc::channel();
+
+        logging::spawn(move || {
+            binwalker.extract_each(&file_data, &file_path, &file_map, |signature_id, result| {
+                // The receiver may have been dropped; keep extracting regardless
+                let _ = sender.send((signature_id.to_string(), result.clone()));
+            });
+        });
+
+        receiver
+    }
+
+    /// Extract data at known offsets, bypassing the signature scan.
+    ///
+    /// Each `(offset, size, signature_name)` tuple describes data located by some other means; it is extracted with
+    /// the extractor for the named signature, as defined in magic.rs or registered with `Binwalk::register_extractor`.
+    /// A size of 0 means that the size is unknown, and the data extends to the end of `file_data`.
+    ///
+    /// Returns the synthesized signature results and their extraction results, or an error if any of the provided
+    /// offsets lie outside of `file_data`, or name an unknown signature.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_binwalk_rs_extract_at() -> Result<binwalk::Binwalk, binwalk::BinwalkError> {
+    /// use binwalk::Binwalk;
+    ///
+    /// let target_path = std::path::Path::new("tests")
+    ///     .join("inputs")
+    ///     .join("gzip.bin")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let extraction_directory = std::path::Path::new("tests")
+    ///     .join("extractions_at")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// # std::fs::remove_dir_all(&extraction_directory);
+    /// let binwalker = Binwalk::configure(Some(target_path),
+    ///                                    Some(extraction_directory.clone()),
+    ///                                    None,
+    ///                                    None,
+    ///                                    None,
+    ///                                    false)?;
+    ///
+    /// let file_data = std::fs::read(&binwalker.base_target_file).expect("Unable to read file");
+    ///
+    /// let results = binwalker.extract_at(&file_data, &binwalker.base_target_file, &[(0, 0, "gzip")])?;
+    ///
+    /// assert_eq!(results.file_map.len(), 1);
+    /// assert_eq!(results.extractions[&results.file_map[0].id].success, true);
+    /// # std::fs::remove_dir_all(&extraction_directory);
+    /// # Ok(binwalker)
+    /// # } _doctest_main_src_binwalk_rs_extract_at(); }
+    /// ```
+    #[allow(dead_code)]
+    pub fn extract_at(
+        &self,
+        file_data: &[u8],
+        file_name: impl Into<String>,
+        locations: &[(usize, usize, &str)],
+    ) -> Result<AnalysisResults, BinwalkError> {
         let file_path = file_name.into();
+        let mut file_map: Vec<signatures::common::SignatureResult> = vec![];
+
+        for (offset, size, signature_name) in locations {
+            let size = match size {
+                0 => file_data.len().saturating_sub(*offset),
+                _ => *size,
+            };
+
+            if *offset >= file_data.len()
+                || offset
+                    .checked_add(size)
+                    .is_none_or(|end| end > file_data.len())
+            {
+                return Err(BinwalkError::new(&format!(
+                    "{signature_name} data at {offset:#X} - {:#X} lies outside of the file data",
+                    offset.saturating_add(size)
+                )));
+            }
+
+            // The signature must have an extractor, either compiled in or registered at run time
+            if !self.extractor_lookup_table.contains_key(*signature_name)
+                && self
+                    .extraction_config
+                    .extractor_registry
+                    .get(signature_name)
+                    .is_none()
+            {
+                return Err(BinwalkError::new(&format!(
+                    "Unknown signature '{signature_name}'"
+                )));
+            }
+
+            // Describe the data using the signature definition, if there is one
+            let signature = self
+                .short_signatures
+                .iter()
+                .chain(self.pattern_signature_table.values())
+                .find(|signature| signature.name == *signature_name);
+
+            let signature_result = signatures::common::SignatureResult {
+                offset: *offset,
+                id: Uuid::new_v4().to_string(),
+                size,
+                name: signature_name.to_string(),
+                confidence: signatures::common::CONFIDENCE_HIGH,
+                description: signature.map_or(signature_name.to_string(), |signature| {
+                    signature.description.clone()
+                }),
+                always_display: signature.is_some_and(|signature| signature.always_display),
+                ..Default::default()
+            };
+
+            file_map.push(signature_result);
+        }
+
+        let extractions = self.extract(file_data, &file_path, &file_map);
+
+        Ok(AnalysisResults {
+            file_path,
+            file_map,
+            extractions,
+            ..Default::default()
+        })
+    }
+
+    /// Performs the extraction of all extractable signatures, calling `on_result` with the signature ID and
+    /// extraction result as each extraction completes. Returns all extraction results.
+    fn extract_each(
+        &self,
+        file_data: &[u8],
+        file_path: &str,
+        file_map: &[signatures::common::SignatureResult],
+        mut on_result: impl FnMut(&str, &extractors::common::ExtractionResult),
+    ) -> HashMap<String, extractors::common::ExtractionResult> {
+        let _log_sink = logging::install_sink(self.log_sink.as_ref());
+
+        let file_path = file_path.to_string();
         let mut extraction_results: HashMap<String, extractors::common::ExtractionResult> =
             HashMap::new();
 
+        // Output directory names only need to be unique amongst this file's extractions
+        let extraction_config = extractors::common::ExtractionConfig {
+            output_directories: Default::default(),
+            ..self.extraction_config.clone()
+        };
+
+        // If resuming a previous run, load the previous run's extraction manifest
+        let previous_manifest = if self.extraction_config.resume {
+            extractors::common::read_manifest(&file_path, &self.extraction_config)
+        } else {
+            None
+        };
+
+        // In carve only mode, carve every signature's data to disk rather than running extractors
+        if self.extraction_config.carve_only {
+            for signature in file_map {
+                let _log_offset = logging::offset_context(signature.offset);
+
+                if self.extraction_config.deadline_passed() {
+                    warn!("Run deadline reached, not carving any more data from {file_path}");
+                    break;
+                }
+
+                let carve_result = extractors::common::carve(
+                    file_data,
+                    &file_path,
+                    signature,
+                    &self.extraction_config,
+                );
+                on_result(&signature.id, &carve_result);
+                extraction_results.insert(signature.id.clone(), carve_result);
+                self.update_manifest(&file_path, file_map, &extraction_results);
+
+                if self.extraction_config.output_failure.get().is_some() {
+                    break;
+                }
+            }
+
+            return extraction_results;
+        }
+
         // Spawn extractors for each extractable signature
         for signature in file_map {
+            let _log_offset = logging::offset_context(signature.offset);
+
+            // Once the run deadline has passed, return the results gathered so far
+            if self.extraction_config.deadline_passed() {
+                warn!("Run deadline reached, not extracting any more data from {file_path}");
+                break;
+            }
+
+            // Don't re-run extractions that were already completed by a previous run
+            if let Some(previous_result) = previous_manifest.as_ref().and_then(|manifest| {
+                extractors::common::resume_extraction(
+                    file_data,
+                    &file_path,
+                    signature,
+                    manifest,
+                    &self.extraction_config,
+                )
+            }) {
+                on_result(&signature.id, &previous_result);
+                extraction_results.insert(signature.id.clone(), previous_result);
+                self.update_manifest(&file_path, file_map, &extraction_results);
+                continue;
+            }
+
             // Signatures may opt to not perform extraction; honor this request
             if signature.extraction_declined {
                 continue;
             }
 
+            // Don't waste time extracting low confidence signatures, if so configured
+            if signature.confidence < self.extraction_config.min_confidence {
+                debug!(
+                    "Not extracting {} (ID: {}) at {:#X}: confidence {} is below the minimum of {}",
+                    signature.name,
+                    signature.id,
+                    signature.offset,
+                    signature.confidence,
+                    self.extraction_config.min_confidence
+                );
+                continue;
+            }
+
+            // Nested signatures may not be extracted, depending on the nesting policy
+            if !self.nested_extraction_allowed(signature, file_map) {
+                continue;
+            }
+
             // Get the extractor for this signature
-            let extractor = self.extractor_lookup_table[&signature.name].clone();
+            let extractor = self
+                .extractor_lookup_table
+                .get(&signature.name)
+                .cloned()
+                .flatten();
+
+            // Signatures with no compiled in extractor may still have an extractor registered at run time
+            let registered = self
+                .extraction_config
+                .extractor_registry
+                .get(&signature.name)
+                .is_some();
 
             match &extractor {
-                None => continue,
-                Some(_) => {
+                None if !registered => continue,
+                _ => {
+                    // The user may veto extraction of any signature
+                    if let Some(predicate) = &self.extraction_config.extraction_predicate
+                        && !predicate.allows(signature)
+                    {
+                        info!(
+                            "Extraction of {} data at offset {:#X} skipped: vetoed by the extraction predicate",
+                            signature.name, signature.offset
+                        );
+
+                        let extraction_result = extractors::common::ExtractionResult {
+                            skipped: true,
+                            ..Default::default()
+                        };
+
+                        on_result(&signature.id, &extraction_result);
+                        extraction_results.insert(signature.id.clone(), extraction_result);
+                        continue;
+                    }
+
                     // Run an extraction for this signature
-                    let mut extraction_result =
-                        extractors::common::execute(file_data, &file_path, signature, &extractor);
+                    let mut extraction_result = extractors::common::execute(
+                        file_data,
+                        &file_path,
+                        signature,
+                        &extractor,
+                        &extraction_config,
+                    );
 
-                    if !extraction_result.success {
+                    if !extraction_result.success && !extraction_result.skipped {
                         debug!(
                             "Extraction failed for {} (ID: {}) {:#X} - {:#X}",
                             signature.name, signature.id, signature.offset, signature.size
@@ -641,7 +1206,9 @@ impl Binwalk {
                          * If extraction failed, it could be due to truncated data (signature matching is not perfect ya know!)
                          * In that case, make one more attempt, this time provide the extractor all the data possible.
                          */
-                        if signature.size < available_data {
+                        if signature.size < available_data
+                            && !self.extraction_config.deadline_passed()
+                        {
                             // Create a duplicate signature, but set its reported size to the length of all available data
                             let mut new_signature = signature.clone();
                             new_signature.size = available_data;
@@ -660,12 +1227,29 @@ impl Binwalk {
                                 &file_path,
                                 &new_signature,
                                 &extractor,
+                                &extraction_config,
                             );
                         }
                     }
 
+                    // Data nested within successfully extracted data is found again when recursing into the extracted files
+                    if let Some(parent_id) = &signature.parent
+                        && extraction_results
+                            .get(parent_id)
+                            .is_some_and(|parent| parent.success && !parent.do_not_recurse)
+                    {
+                        extraction_result.do_not_recurse = true;
+                    }
+
                     // Update the HashMap with the result of this extraction attempt
+                    on_result(&signature.id, &extraction_result);
                     extraction_results.insert(signature.id.clone(), extraction_result);
+                    self.update_manifest(&file_path, file_map, &extraction_results);
+
+                    // Nothing more can be extracted once the output file system is full or read-only
+                    if self.extraction_config.output_failure.get().is_some() {
+                        break;
+                    }
                 }
             }
         }
@@ -673,6 +1257,291 @@ impl Binwalk {
         extraction_results
     }
 
+    /// Plans the extraction of all extractable signatures found in a file, without running any external extractors or
+    /// writing anything to disk.
+    ///
+    /// Each signature is planned in the same way that `Binwalk::extract` would extract it: the returned plan lists the
+    /// extractor that would be run and the output directory it would write to, or why the signature would not be
+    /// extracted. Internal extractors are dry run to validate the data and determine its size.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use binwalk::Binwalk;
+    ///
+    /// let target_path = std::path::Path::new("tests")
+    ///     .join("inputs")
+    ///     .join("gzip.bin");
+    ///
+    /// let file_data = std::fs::read(&target_path).expect("Unable to read file");
+    ///
+    /// let binwalker = Binwalk::new();
+    /// let scan_results = binwalker.scan(&file_data);
+    /// let plan = binwalker.plan(&file_data, target_path.display().to_string(), &scan_results);
+    ///
+    /// let extraction = plan[0].extraction.as_ref().unwrap();
+    ///
+    /// assert_eq!(extraction.extractor, "gzip_built_in");
+    /// assert_eq!(extraction.dry_run_success, Some(true));
+    /// assert!(extraction.output_path.ends_with("gzip.bin.extracted/0"));
+    /// assert_eq!(std::path::Path::new(&extraction.output_path).exists(), false);
+    /// ```
+    pub fn plan(
+        &self,
+        file_data: &[u8],
+        file_name: impl Into<String>,
+        file_map: &[signatures::common::SignatureResult],
+    ) -> Vec<PlannedSignature> {
+        let _log_sink = logging::install_sink(self.log_sink.as_ref());
+
+        let file_path = file_name.into();
+        let mut plan: Vec<PlannedSignature> = vec![];
+
+        // As when extracting, output directory names only need to be unique amongst this file's extractions
+        let extraction_config = extractors::common::ExtractionConfig {
+            output_directories: Default::default(),
+            ..self.extraction_config.clone()
+        };
+
+        for signature in file_map {
+            let _log_offset = logging::offset_context(signature.offset);
+
+            let mut planned_signature = PlannedSignature {
+                signature: signature.clone(),
+                depth: nesting_depth(signature, file_map),
+                skip_reason: self.extraction_skip_reason(signature, file_map),
+                ..Default::default()
+            };
+
+            if planned_signature.skip_reason.is_none() {
+                let extractor = self
+                    .extractor_lookup_table
+                    .get(&signature.name)
+                    .cloned()
+                    .flatten();
+
+                match extractors::common::plan_extraction(
+                    file_data,
+                    &file_path,
+                    signature,
+                    &extractor,
+                    &extraction_config,
+                ) {
+                    None => {
+                        planned_signature.skip_reason = Some("extractor excluded".to_string());
+                    }
+                    Some(extraction) => {
+                        // Data nested within successfully extracted data is found again when recursing into the extracted files
+                        let parent_recursed = signature.parent.as_ref().is_some_and(|parent_id| {
+                            plan.iter()
+                                .any(|parent| parent.signature.id == *parent_id && parent.recurse)
+                        });
+
+                        planned_signature.recurse = !extraction.do_not_recurse
+                            && extraction.dry_run_success != Some(false)
+                            && !parent_recursed;
+                        planned_signature.extraction = Some(extraction);
+                    }
+                }
+            }
+
+            plan.push(planned_signature);
+        }
+
+        plan
+    }
+
+    /// Returns the reason that `Binwalk::extract` would not extract the signature, if it would not; mirrors the checks
+    /// made by `extract_each`
+    fn extraction_skip_reason(
+        &self,
+        signature: &signatures::common::SignatureResult,
+        file_map: &[signatures::common::SignatureResult],
+    ) -> Option<String> {
+        // In carve only mode, every signature is carved
+        if self.extraction_config.carve_only {
+            return None;
+        }
+
+        if signature.extraction_declined {
+            return Some("declined by the signature parser".to_string());
+        }
+
+        if signature.confidence < self.extraction_config.min_confidence {
+            return Some(format!(
+                "confidence {} is below the minimum of {}",
+                signature.confidence, self.extraction_config.min_confidence
+            ));
+        }
+
+        if !self.nested_extraction_allowed(signature, file_map) {
+            return Some("not allowed by the nested signature policy".to_string());
+        }
+
+        let has_extractor = self
+            .extractor_lookup_table
+            .get(&signature.name)
+            .is_some_and(|extractor| extractor.is_some())
+            || self
+                .extraction_config
+                .extractor_registry
+                .get(&signature.name)
+                .is_some();
+
+        if !has_extractor {
+            return Some("no extractor".to_string());
+        }
+
+        if let Some(predicate) = &self.extraction_config.extraction_predicate
+            && !predicate.allows(signature)
+        {
+            return Some("vetoed by the extraction predicate".to_string());
+        }
+
+        None
+    }
+
+    /// Returns true if the signature may be extracted according to `ExtractionConfig.nested_signatures`
+    fn nested_extraction_allowed(
+        &self,
+        signature: &signatures::common::SignatureResult,
+        file_map: &[signatures::common::SignatureResult],
+    ) -> bool {
+        let allowed = match self.extraction_config.nested_signatures {
+            extractors::common::NestedSignaturePolicy::Outermost => signature.parent.is_none(),
+            extractors::common::NestedSignaturePolicy::Innermost => !file_map
+                .iter()
+                .any(|other| other.parent.as_ref() == Some(&signature.id)),
+            _ => true,
+        };
+
+        if !allowed {
+            debug!(
+                "Not extracting {} (ID: {}) at {:#X}: not allowed by the nested signature policy",
+                signature.name, signature.id, signature.offset
+            );
+        }
+
+        allowed
+    }
+
+    /// Returns false if the signature requires trial validation and its extractor's trial decoder fails to decode
+    /// the data at the magic match offset; else, returns true
+    fn trial_decode(
+        &self,
+        signature: &signatures::common::Signature,
+        file_data: &[u8],
+        magic_offset: usize,
+    ) -> bool {
+        if !signature.trial_validation || self.trial_size == 0 {
+            return true;
+        }
+
+        let Some(trial_decoder) = signature
+            .extractor
+            .as_ref()
+            .and_then(|extractor| extractor.trial_decoder)
+        else {
+            return true;
+        };
+
+        if trial_decoder(file_data, magic_offset, self.trial_size) {
+            return true;
+        }
+
+        debug!(
+            "{} magic match at offset {:#X} failed trial validation",
+            signature.description, magic_offset
+        );
+
+        false
+    }
+
+    /// Returns the regions of the file data that are not covered by any of the signature results in `file_map`,
+    /// including any data before the first result and after the last. Only regions of at least `min_size` bytes are
+    /// returned.
+    ///
+    /// Each region is reported as a synthetic SignatureResult named `gap`, so that it may be analyzed, carved
+    /// (see `extractors::common::carve_gap`) or entropy scored like any other result.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use binwalk::Binwalk;
+    ///
+    /// let mut file_data = vec![0xFF; 64];
+    /// file_data.extend(std::fs::read("tests/inputs/gzip.bin").expect("Unable to read file"));
+    /// file_data.extend(vec![0xFF; 32]);
+    ///
+    /// let binwalker = Binwalk::new();
+    /// let file_map = binwalker.scan(&file_data);
+    /// let gaps = binwalker.find_gaps(&file_data, &file_map, 1);
+    ///
+    /// assert_eq!(gaps.len(), 2);
+    /// assert_eq!(gaps[0].name, "gap");
+    /// assert_eq!(gaps[0].offset, 0);
+    /// assert_eq!(gaps[0].size, 64);
+    /// assert_eq!(gaps[1].offset + gaps[1].size, file_data.len());
+    /// assert_eq!(gaps[1].size, 32);
+    /// ```
+    pub fn find_gaps(
+        &self,
+        file_data: &[u8],
+        file_map: &[signatures::common::SignatureResult],
+        min_size: usize,
+    ) -> Vec<signatures::common::SignatureResult> {
+        let mut gaps: Vec<signatures::common::SignatureResult> = Vec::new();
+
+        // Signature results may overlap, so track the furthest extent of any result seen so far
+        let mut covered_regions: Vec<(usize, usize)> = file_map
+            .iter()
+            .map(|result| (result.offset, result.offset.saturating_add(result.size)))
+            .collect();
+        covered_regions.sort();
+        covered_regions.push((file_data.len(), file_data.len()));
+
+        let mut last_known_offset: usize = 0;
+
+        for (start, end) in covered_regions {
+            let gap_end = start.min(file_data.len());
+
+            if gap_end > last_known_offset && gap_end - last_known_offset >= min_size.max(1) {
+                let size = gap_end - last_known_offset;
+
+                gaps.push(signatures::common::SignatureResult {
+                    offset: last_known_offset,
+                    id: Uuid::new_v4().to_string(),
+                    size,
+                    name: "gap".to_string(),
+                    description: format!("Unidentified data, {size} bytes"),
+                    ..Default::default()
+                });
+            }
+
+            last_known_offset = last_known_offset.max(end);
+        }
+
+        gaps
+    }
+
+    /// Writes the extraction manifest for a file, if enabled by `ExtractionConfig.write_manifest`.
+    /// Called after each extraction, so that the manifest is up to date should the run be interrupted.
+    fn update_manifest(
+        &self,
+        file_path: &str,
+        file_map: &[signatures::common::SignatureResult],
+        extraction_results: &HashMap<String, extractors::common::ExtractionResult>,
+    ) {
+        if self.extraction_config.write_manifest {
+            let _ = extractors::common::write_manifest(
+                file_path,
+                file_map,
+                extraction_results,
+                &self.extraction_config,
+            );
+        }
+    }
+
     /// Analyze a data buffer and optionally extract the file contents.
     ///
     /// ## Example
@@ -723,6 +1592,8 @@ impl Binwalk {
     ) -> AnalysisResults {
         let file_path = target_file.into();
 
+        let _log_sink = logging::install_sink(self.log_sink.as_ref());
+
         // Return value
         let mut results: AnalysisResults = AnalysisResults {
             file_path: file_path.clone(),
@@ -743,6 +1614,40 @@ impl Binwalk {
             results.extractions = self.extract(file_data, &file_path, &results.file_map);
         }
 
+        // Report, and optionally carve, any unidentified data between the signature results
+        if let Some(gap_min_size) = self.extraction_config.gap_min_size {
+            results.gaps = self.find_gaps(file_data, &results.file_map, gap_min_size);
+
+            if self.file_type_lookup {
+                for gap in results.gaps.iter_mut() {
+                    if let Some(file_type) =
+                        guess_file_type(&file_data[gap.offset..gap.offset + gap.size])
+                    {
+                        gap.description = format!("{}, file type: {file_type}", gap.description);
+                    }
+                }
+            }
+
+            if do_extraction {
+                for gap in &results.gaps {
+                    let _log_offset = logging::offset_context(gap.offset);
+
+                    let carve_result = extractors::common::carve_gap(
+                        file_data,
+                        &file_path,
+                        gap,
+                        &self.extraction_config,
+                    );
+                    results.extractions.insert(gap.id.clone(), carve_result);
+                }
+            }
+        }
+
+        // Files that binwalk has no signatures for may still be of a commonly known type
+        if self.file_type_lookup && results.file_map.is_empty() {
+            results.file_type = guess_file_type(file_data);
+        }
+
         debug!("Analysis end: {file_path}");
 
         results
@@ -917,3 +1822,25 @@ fn signature_result_auto_populate(
     signature_result.name = signature.name.clone();
     signature_result.always_display = signature.always_display;
 }
+
+/// Returns the number of signatures in the file map that the signature is nested within
+fn nesting_depth(
+    signature: &signatures::common::SignatureResult,
+    file_map: &[signatures::common::SignatureResult],
+) -> usize {
+    let mut depth: usize = 0;
+    let mut parent_id = signature.parent.as_ref();
+
+    while let Some(parent) = parent_id.and_then(|id| file_map.iter().find(|s| s.id == *id)) {
+        depth += 1;
+
+        // Guards against malformed file maps in which signatures are nested within each other
+        if depth >= file_map.len() {
+            break;
+        }
+
+        parent_id = parent.parent.as_ref();
+    }
+
+    depth
+}
@@ -7,10 +7,18 @@ pub struct CliArgs {
     #[arg(short = 'L', long)]
     pub list: bool,
 
+    /// List any missing external extraction utilities
+    #[arg(long)]
+    pub dependencies: bool,
+
     /// Read data from standard input
     #[arg(short, long)]
     pub stdin: bool,
 
+    /// If the input file is a single gzip, XZ, or bzip2 compressed stream, decompress it before analysis
+    #[arg(long)]
+    pub decompress_input: bool,
+
     /// Supress normal stdout output
     #[arg(short, long)]
     pub quiet: bool,
@@ -27,22 +35,168 @@ pub struct CliArgs {
     #[arg(short, long)]
     pub carve: bool,
 
+    /// Carve each identified file to the extraction directory, without running any extractors
+    #[arg(long, conflicts_with = "extract")]
+    pub carve_only: bool,
+
+    /// Start files carved by --carve-only and --gaps at the nearest preceding multiple of this many bytes
+    #[arg(long, value_name = "BYTES")]
+    pub carve_align: Option<usize>,
+
+    /// Pad files carved by --carve-only and --gaps with zeros to a multiple of this many bytes
+    #[arg(long, value_name = "BYTES")]
+    pub carve_pad: Option<usize>,
+
+    /// Report unidentified data regions of at least this many bytes, and carve them to gap_<start>_<end>.bin files
+    /// when extracting
+    #[arg(long, value_name = "MIN_SIZE")]
+    pub gaps: Option<usize>,
+
+    /// Only run these extractors (signature or extraction utility names)
+    #[arg(long, value_delimiter = ',', num_args = 1..)]
+    pub include_extractors: Option<Vec<String>>,
+
+    /// Do not run these extractors (signature or extraction utility names); takes precedence over --include-extractors
+    #[arg(long, value_delimiter = ',', num_args = 1..)]
+    pub exclude_extractors: Option<Vec<String>>,
+
+    /// Run external extraction utilities from these paths rather than looking them up in PATH
+    /// (e.g. unsquashfs=/opt/tools/bin/unsquashfs)
+    #[arg(long, value_delimiter = ',', num_args = 1.., value_name = "NAME=PATH")]
+    pub extractor_paths: Option<Vec<String>>,
+
+    /// Load external extractor definitions, by signature name, from a JSON file; these take precedence over the
+    /// built-in extractors
+    #[arg(long, value_name = "FILE")]
+    pub extractor_config: Option<String>,
+
+    /// Give data carved for external extraction utilities these file extensions, by signature name, rather than the
+    /// extractors' default extensions (e.g. gzip=gz)
+    #[arg(long, value_delimiter = ',', num_args = 1.., value_name = "NAME=EXTENSION")]
+    pub carved_extensions: Option<Vec<String>>,
+
+    /// Never leave extracted files executable, and replace extracted symlinks and special files with placeholder files
+    /// (recommended for untrusted data, e.g. in CI pipelines)
+    #[arg(long)]
+    pub quarantine: bool,
+
+    /// Carve the individual sections of ELF files, rather than only reporting the ELF file size
+    #[arg(long)]
+    pub elf_sections: bool,
+
+    /// Recover deleted files from FAT file systems, for forensic use
+    #[arg(long)]
+    pub fat_deleted: bool,
+
+    /// Preserve extended attributes (e.g. file capabilities, SELinux labels) stored in extracted archives and file
+    /// systems; not recommended for untrusted data, and ignored in quarantine mode
+    #[arg(long)]
+    pub xattrs: bool,
+
+    /// Replace extracted symlinks that point outside of these directories (e.g. /bin,/lib) with placeholder files
+    #[arg(long, value_delimiter = ',', num_args = 1.., value_name = "DIR")]
+    pub symlink_allowlist: Option<Vec<String>>,
+
+    /// Write a manifest of all extractions to each extraction directory
+    #[arg(long)]
+    pub manifest: bool,
+
+    /// Write all extracted files to a single tar archive, rather than leaving them in an extraction directory
+    #[arg(long, conflicts_with = "carve")]
+    pub archive: bool,
+
+    /// Resume an interrupted extraction, skipping extractions recorded as complete in the extraction manifest
+    #[arg(long)]
+    pub resume: bool,
+
+    /// Only extract signatures with at least this confidence: 0 (low), 128 (medium) or 250 (high)
+    #[arg(long, default_value_t = 0)]
+    pub min_confidence: u8,
+
+    /// How to handle signatures found inside of other signatures' data: discard them, report them and extract only the
+    /// outermost or innermost signatures, or report and extract both
+    #[arg(long, default_value = "discard", value_parser = ["discard", "outermost", "innermost", "both"])]
+    pub nested: String,
+
+    /// Also hash carved data with this digest algorithm; SHA-256 hashes are always reported
+    #[arg(long, value_parser = ["md5", "sha1", "sha256", "blake3"])]
+    pub digest: Option<String>,
+
+    /// What to do if an extraction's output directory already exists, e.g. from a previous run
+    #[arg(long, default_value = "overwrite", value_parser = ["fail", "skip", "overwrite"])]
+    pub existing: String,
+
+    /// Carve the input files of extraction utilities to a temporary directory in DIR, rather than to the output
+    /// directory; if DIR is not specified, the system temporary directory (TMPDIR) is used
+    #[arg(long, value_name = "DIR", num_args = 0..=1)]
+    pub carve_dir: Option<Option<String>>,
+
+    /// Stop starting new extractions SECONDS seconds after the run starts; results gathered so far are still reported
+    #[arg(long, value_name = "SECONDS")]
+    pub deadline: Option<u64>,
+
+    /// Kill external extractors that are still running when the deadline passes
+    #[arg(long, requires = "deadline")]
+    pub kill_at_deadline: bool,
+
+    /// Keep the output of failed extractions for debugging
+    #[arg(short = 'k', long)]
+    pub keep_failed: bool,
+
+    /// Symlink duplicate extractions to the output of the first identical extraction
+    #[arg(long)]
+    pub dedupe: bool,
+
+    /// Do not recursively scan files extracted from these signatures
+    #[arg(long, value_delimiter = ',', num_args = 1..)]
+    pub no_recurse: Option<Vec<String>>,
+
+    /// Do not recursively scan extracted files with these extensions (e.g. txt) or MIME types (e.g. image/png, image/*)
+    #[arg(long, value_delimiter = ',', num_args = 1..)]
+    pub terminal: Option<Vec<String>>,
+
     /// Recursively scan extracted files
     #[arg(short = 'M', long)]
     pub matryoshka: bool,
 
+    /// Print the extractions that would be performed on the input file, and the output directories they would be written
+    /// to, without extracting anything; use with --nested to include nested signatures
+    #[arg(long, conflicts_with_all = ["carve", "entropy"])]
+    pub plan: bool,
+
     /// Search for all signatures at all offsets
     #[arg(short = 'a', long)]
     pub search_all: bool,
 
+    /// Number of bytes that magic matches for false positive prone signatures (e.g. zlib) must successfully decode
+    /// before being validated; 0 disables trial decoding
+    #[arg(long, default_value_t = crate::binwalk::DEFAULT_TRIAL_SIZE)]
+    pub trial_size: usize,
+
+    /// Display a hex and ASCII preview of the first BYTES bytes of each signature [default: 16]
+    #[arg(long, value_name = "BYTES", num_args = 0..=1, default_missing_value = "16")]
+    pub preview: Option<usize>,
+
+    /// Guess the MIME type of files without any identified signatures, and of unidentified gaps
+    #[arg(long)]
+    pub file_type: bool,
+
     /// Generate an entropy graph with Plotly
     #[arg(short = 'E', long, conflicts_with = "extract")]
     pub entropy: bool,
 
-    /// Save entropy graph as a PNG file
+    /// Save entropy graph as a PNG file (or an SVG file, if the file name ends in .svg)
     #[arg(short, long)]
     pub png: Option<String>,
 
+    /// Entropy analysis block size, in bytes
+    #[arg(long)]
+    pub block_size: Option<usize>,
+
+    /// Entropy analysis block stride, in bytes; a stride smaller than the block size produces overlapping blocks
+    #[arg(long)]
+    pub stride: Option<usize>,
+
     /// Log JSON results to a file ('-' for stdout)
     #[arg(short, long)]
     pub log: Option<String>,
@@ -51,6 +205,10 @@ pub struct CliArgs {
     #[arg(short, long)]
     pub threads: Option<usize>,
 
+    /// Maximum number of external extraction utilities to run at once (default: one per thread)
+    #[arg(long)]
+    pub max_processes: Option<usize>,
+
     /// Do no scan for these signatures
     #[arg(short = 'x', long, value_delimiter = ',', num_args = 1..)]
     pub exclude: Option<Vec<String>>,
@@ -1,6 +1,9 @@
 //! Common Functions
+use crate::logging::{debug, error};
+use bzip2::bufread::BzDecoder;
 use chrono::prelude::DateTime;
-use log::{debug, error};
+use flate2::bufread::GzDecoder;
+use liblzma::bufread::XzDecoder;
 use std::fs::File;
 use std::io::Read;
 
@@ -72,6 +75,88 @@ pub fn read_file(file: impl Into<String>) -> Result<Vec<u8>, std::io::Error> {
     }
 }
 
+/// Decompresses data that consists entirely of a single gzip, XZ, or bzip2 compressed stream.
+///
+/// Returns None if the data does not start with a supported compressed stream, if decompression fails, or if the
+/// compressed stream is followed by anything other than zero padding, as such data is better analyzed as-is.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::common::decompress_input;
+///
+/// let file_data = std::fs::read("tests/inputs/gzip.bin").expect("Failed to read file data");
+/// let decompressed_data = decompress_input(&file_data).expect("Failed to decompress file data");
+///
+/// assert_eq!(decompressed_data.len(), 104);
+/// assert!(decompress_input(b"not compressed").is_none());
+/// ```
+pub fn decompress_input(file_data: &[u8]) -> Option<Vec<u8>> {
+    const GZIP_MAGIC: &[u8] = b"\x1F\x8B\x08";
+    const XZ_MAGIC: &[u8] = b"\xFD7zXZ\x00";
+    const BZIP2_MAGIC: &[u8] = b"BZh";
+    // Sanity limit on the size of the decompressed data: 4GB
+    const MAX_DECOMPRESSED_SIZE: u64 = 4 * 1024 * 1024 * 1024;
+
+    // Decompresses a stream into decompressed_data, and returns the decoder so that any trailing data can be inspected
+    fn decompress<R: Read>(mut decoder: R, decompressed_data: &mut Vec<u8>) -> Option<R> {
+        decoder
+            .by_ref()
+            .take(MAX_DECOMPRESSED_SIZE)
+            .read_to_end(decompressed_data)
+            .ok()?;
+        Some(decoder)
+    }
+
+    let mut decompressed_data = Vec::new();
+
+    let trailing_data: &[u8] = if file_data.starts_with(GZIP_MAGIC) {
+        decompress(GzDecoder::new(file_data), &mut decompressed_data)?.into_inner()
+    } else if file_data.starts_with(XZ_MAGIC) {
+        decompress(XzDecoder::new(file_data), &mut decompressed_data)?.into_inner()
+    } else if file_data.starts_with(BZIP2_MAGIC) {
+        decompress(BzDecoder::new(file_data), &mut decompressed_data)?.into_inner()
+    } else {
+        return None;
+    };
+
+    if decompressed_data.len() as u64 >= MAX_DECOMPRESSED_SIZE {
+        debug!("Decompressed input data exceeds the maximum size of {MAX_DECOMPRESSED_SIZE} bytes");
+        return None;
+    }
+
+    if trailing_data.iter().any(|b| *b != 0) {
+        debug!(
+            "Input data contains {} bytes of trailing data after the compressed stream",
+            trailing_data.len()
+        );
+        return None;
+    }
+
+    debug!(
+        "Decompressed {} bytes of input data to {} bytes",
+        file_data.len(),
+        decompressed_data.len()
+    );
+
+    Some(decompressed_data)
+}
+
+/// Guesses the MIME type of data from its contents, using the [infer](https://crates.io/crates/infer) crate's
+/// magic database; returns None if the type is not known.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::common::guess_file_type;
+///
+/// assert_eq!(guess_file_type(b"\x89PNG\r\n\x1A\n\x00\x00\x00\x0DIHDR").as_deref(), Some("image/png"));
+/// assert_eq!(guess_file_type(b"\x00\x01\x02\x03"), None);
+/// ```
+pub fn guess_file_type(data: &[u8]) -> Option<String> {
+    infer::get(data).map(|file_type| file_type.mime_type().to_string())
+}
+
 /// Calculates the CRC32 checksum of the given data.
 ///
 /// ## Notes
@@ -93,6 +178,34 @@ pub fn crc32(data: &[u8]) -> u32 {
     crc32_v2::crc32(0, data)
 }
 
+/// Calculates the CRC-16/ARC checksum of the given data, as used by LHA archives.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::common::crc16;
+///
+/// assert_eq!(crc16(b"123456789"), 0xBB3D);
+/// ```
+pub fn crc16(data: &[u8]) -> u16 {
+    // Reversed polynomial 0x8005
+    const POLYNOMIAL: u16 = 0xA001;
+
+    let mut crc: u16 = 0;
+
+    for byte in data {
+        crc ^= *byte as u16;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                0 => crc >> 1,
+                _ => (crc >> 1) ^ POLYNOMIAL,
+            };
+        }
+    }
+
+    crc
+}
+
 /// Converts an epoch time to a formatted time string.
 ///
 /// ## Example
@@ -186,6 +299,33 @@ pub fn is_printable_ascii(b: u8) -> bool {
     (ASCII_MIN..=ASCII_MAX).contains(&b)
 }
 
+/// Formats data as a single line of space-separated hex bytes, followed by their ASCII representation, in the
+/// style of `hexdump -C`; non-printable bytes are displayed as `.`.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::common::hex_preview;
+///
+/// assert_eq!(hex_preview(b"\x1F\x8BAB\x00"), "1f 8b 41 42 00  |..AB.|");
+/// ```
+pub fn hex_preview(data: &[u8]) -> String {
+    let hex_bytes: Vec<String> = data.iter().map(|b| format!("{b:02x}")).collect();
+    let ascii: String = data
+        .iter()
+        .map(|b| {
+            // Unlike is_printable_ascii, exclude control characters so the preview stays on one line
+            if (0x20..=0x7E).contains(b) {
+                *b as char
+            } else {
+                '.'
+            }
+        })
+        .collect();
+
+    format!("{}  |{}|", hex_bytes.join(" "), ascii)
+}
+
 /// Validates data offsets to prevent out-of-bounds access and infinite loops while parsing file formats.
 ///
 /// ## Notes
@@ -226,3 +366,25 @@ pub fn is_offset_safe(
 
     true
 }
+
+/// Formats a 16 byte GUID as a string; the first three GUID fields are stored little endian, as in EFI/UEFI structures.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::common::guid_to_string;
+///
+/// let guid = b"\x28\x73\x2A\xC1\x1F\xF8\xD2\x11\xBA\x4B\x00\xA0\xC9\x3E\xC9\x3B";
+///
+/// assert_eq!(guid_to_string(guid), "C12A7328-F81F-11D2-BA4B-00A0C93EC93B");
+/// ```
+pub fn guid_to_string(guid: &[u8]) -> String {
+    format!(
+        "{:08X}-{:04X}-{:04X}-{}-{}",
+        u32::from_le_bytes(guid[0..4].try_into().unwrap()),
+        u16::from_le_bytes(guid[4..6].try_into().unwrap()),
+        u16::from_le_bytes(guid[6..8].try_into().unwrap()),
+        hex::encode_upper(&guid[8..10]),
+        hex::encode_upper(&guid[10..16]),
+    )
+}
@@ -1,4 +1,4 @@
-use crate::binwalk::AnalysisResults;
+use crate::binwalk::{AnalysisResults, PlannedSignature};
 use crate::extractors;
 use crate::signatures;
 use colored::ColoredString;
@@ -143,11 +143,32 @@ fn print_signature(signature: &signatures::common::SignatureResult) {
     } else {
         println!("{}", display_string.red());
     }
+
+    if let Some(preview) = &signature.preview {
+        // Not line wrapped, as that would collapse whitespace in the ASCII portion of the preview
+        println!("{}{}", " ".repeat(COLUMN1_WIDTH + COLUMN2_WIDTH), preview);
+    }
 }
 
 fn print_signatures(signatures: &Vec<signatures::common::SignatureResult>) {
     for signature in signatures {
-        print_signature(signature);
+        // Nested signatures are prefixed with one '>' per level of nesting
+        let mut depth: usize = 0;
+        let mut parent_id = signature.parent.as_ref();
+
+        while let Some(parent) = parent_id.and_then(|id| signatures.iter().find(|s| s.id == *id)) {
+            depth += 1;
+            parent_id = parent.parent.as_ref();
+        }
+
+        if depth > 0 {
+            let mut nested_signature = signature.clone();
+            nested_signature.description =
+                format!("{} {}", ">".repeat(depth), signature.description);
+            print_signature(&nested_signature);
+        } else {
+            print_signature(signature);
+        }
     }
 }
 
@@ -167,13 +188,27 @@ fn print_extraction(
             .yellow();
         }
         Some(extraction_result) => {
-            if extraction_result.success {
+            if extraction_result.skipped {
+                extraction_message = format!(
+                    "[#] Extraction of {} data at offset {:#X} skipped",
+                    signature.name, signature.offset
+                )
+                .bold()
+                .yellow();
+            } else if extraction_result.success {
                 extraction_message = format!(
                     "[+] Extraction of {} data at offset {:#X} completed successfully",
                     signature.name, signature.offset
                 )
                 .bold()
                 .green();
+            } else if let Some(error) = &extraction_result.error {
+                extraction_message = format!(
+                    "[-] Extraction of {} data at offset {:#X} failed: {}",
+                    signature.name, signature.offset, error
+                )
+                .bold()
+                .red();
             } else {
                 extraction_message = format!(
                     "[-] Extraction of {} data at offset {:#X} failed!",
@@ -199,11 +234,11 @@ fn print_extractions(
         let mut extraction_result: Option<&extractors::common::ExtractionResult> = None;
 
         // Only print extraction results if an extraction was attempted or explicitly declined
-        if signature.extraction_declined {
-            printable_extraction = true
-        } else if extraction_results.contains_key(&signature.id) {
+        if extraction_results.contains_key(&signature.id) {
             printable_extraction = true;
             extraction_result = Some(&extraction_results[&signature.id]);
+        } else if signature.extraction_declined {
+            printable_extraction = true
         }
 
         if printable_extraction {
@@ -226,6 +261,13 @@ pub fn print_analysis_results(quiet: bool, extraction_attempted: bool, results:
     print_header(&results.file_path);
     print_signatures(&results.file_map);
 
+    if let Some(file_type) = &results.file_type {
+        println!(
+            "{}",
+            format!("No signatures found, file type: {file_type}").yellow()
+        );
+    }
+
     // If extraction was attempted, print extraction results
     if extraction_attempted {
         print_extractions(&results.file_map, &results.extractions);
@@ -235,6 +277,87 @@ pub fn print_analysis_results(quiet: bool, extraction_attempted: bool, results:
     print_footer();
 }
 
+/// Prints the extractions planned for a file as a tree, with nested signatures indented beneath their parents
+pub fn print_extraction_plan(
+    quiet: bool,
+    file_path: &str,
+    recursive: bool,
+    plan: &[PlannedSignature],
+) {
+    const INDENT: &str = "    ";
+
+    if quiet {
+        return;
+    }
+
+    println!();
+    println!(
+        "{}",
+        center_text(&format!("Extraction plan: {file_path}"))
+            .bold()
+            .magenta()
+    );
+    print_delimiter();
+
+    for planned_signature in plan {
+        let signature = &planned_signature.signature;
+        let indent = INDENT.repeat(planned_signature.depth);
+        let summary = format!(
+            "{} data at offset {:#X}, {} bytes",
+            signature.name, signature.offset, signature.size
+        );
+
+        let Some(extraction) = &planned_signature.extraction else {
+            let skip_reason = planned_signature.skip_reason.as_deref().unwrap_or_default();
+            println!(
+                "{indent}{}",
+                format!("[#] {summary}: not extracted, {skip_reason}").yellow()
+            );
+            continue;
+        };
+
+        if extraction.dry_run_success == Some(false) {
+            println!("{indent}{}", format!("[-] {summary}").bold().red());
+        } else {
+            println!("{indent}{}", format!("[+] {summary}").bold().green());
+        }
+
+        let dry_run = match (extraction.dry_run_success, extraction.size) {
+            (Some(false), _) => " (dry run failed)".to_string(),
+            (Some(true), Some(size)) => format!(" (dry run consumed {size} bytes)"),
+            _ => "".to_string(),
+        };
+
+        println!(
+            "{indent}{INDENT}extractor: {}{dry_run}",
+            extraction.extractor
+        );
+
+        let recursion = if recursive && planned_signature.recurse {
+            " (scanned recursively)"
+        } else {
+            ""
+        };
+
+        println!(
+            "{indent}{INDENT}output:    {}{recursion}",
+            extraction.output_path
+        );
+    }
+
+    let extraction_count = plan
+        .iter()
+        .filter(|planned_signature| planned_signature.extraction.is_some())
+        .count();
+
+    print_delimiter();
+    println!(
+        "{extraction_count} of {} signatures would be extracted; nothing was written to disk",
+        plan.len()
+    );
+    println!();
+}
+
 // Used by print_signature_list
 #[derive(Debug, Default, Clone)]
 struct SignatureInfo {
@@ -342,6 +465,104 @@ pub fn print_signature_list(quiet: bool, signatures: &Vec<signatures::common::Si
     println!("Extractable signatures: {extractor_count}");
 }
 
+pub fn print_missing_dependencies(
+    quiet: bool,
+    missing_dependencies: &[extractors::common::MissingDependency],
+) {
+    if quiet {
+        return;
+    }
+
+    print_delimiter();
+    print_column_headers("Missing Extraction Utility", "Required By Signatures", "");
+    print_delimiter();
+
+    for dependency in missing_dependencies {
+        let display_line = format!(
+            "{}{}",
+            pad_to_length(&dependency.command, COLUMN1_WIDTH),
+            dependency.signatures.join(", ")
+        );
+
+        println!("{}", display_line.red());
+    }
+
+    print_delimiter();
+    println!();
+
+    if missing_dependencies.is_empty() {
+        println!("{}", "All external extraction utilities were found".green());
+    } else {
+        println!(
+            "Missing extraction utilities: {}",
+            missing_dependencies.len()
+        );
+    }
+}
+
+pub fn print_signature_statistics(
+    quiet: bool,
+    statistics: &extractors::common::ExtractionStatistics,
+) {
+    if quiet || statistics.signatures.is_empty() {
+        return;
+    }
+
+    print_delimiter();
+    print_column_headers("Signature", "Matches / Extracted", "Output Size");
+    print_delimiter();
+
+    // Signatures with the most matches are listed first
+    for signature_statistics in statistics.by_matches() {
+        let display_line = format!(
+            "{}{}{} bytes",
+            pad_to_length(&signature_statistics.signature, COLUMN1_WIDTH),
+            pad_to_length(
+                &format!(
+                    "{} / {}",
+                    signature_statistics.matches, signature_statistics.extracted
+                ),
+                COLUMN2_WIDTH
+            ),
+            signature_statistics.output_size
+        );
+
+        println!("{display_line}");
+    }
+
+    print_delimiter();
+    println!();
+}
+
+pub fn print_extraction_statistics(
+    quiet: bool,
+    statistics: &extractors::common::ExtractionStatistics,
+) {
+    if quiet || statistics.extractors.is_empty() {
+        return;
+    }
+
+    print_delimiter();
+    print_column_headers("Extractor", "Extractions", "Run Time / Throughput");
+    print_delimiter();
+
+    // Extractors that took the longest are listed first
+    for extractor_statistics in statistics.by_duration() {
+        let display_line = format!(
+            "{}{}{:.1} ms / {:.2} MB/s",
+            pad_to_length(&extractor_statistics.extractor, COLUMN1_WIDTH),
+            pad_to_length(&extractor_statistics.extractions.to_string(), COLUMN2_WIDTH),
+            extractor_statistics.duration_ms,
+            extractor_statistics.throughput_mbps
+        );
+
+        println!("{display_line}");
+    }
+
+    print_delimiter();
+    println!();
+}
+
 pub fn print_stats(
     quiet: bool,
     run_time: time::Instant,
@@ -1,17 +1,75 @@
-use crate::common::read_input;
+use crate::common::{is_printable_ascii, read_input};
 use entropy::shannon_entropy;
+use image::ImageEncoder;
 use plotly::layout::{Axis, Layout};
-use plotly::{ImageFormat, Plot, Scatter};
+use plotly::{Plot, Scatter};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone)]
 pub struct EntropyError;
 
+/// Width and height, in pixels, of rendered entropy graphs
+const GRAPH_WIDTH: u32 = 2048;
+const GRAPH_HEIGHT: u32 = 1024;
+
+/// Blocks with an entropy below this threshold are not classified
+const HIGH_ENTROPY_THRESHOLD: f64 = 7.0;
+
+/// Blocks smaller than this are too small for the statistical tests to be meaningful (expected count of 5 per byte value)
+const MIN_CLASSIFICATION_SIZE: usize = 256 * 5;
+
+/// Data smaller than this is processed on a single thread, as the overhead of distributing the blocks between threads
+/// outweighs any gains
+const PARALLEL_MIN_SIZE: usize = 4 * 1024 * 1024;
+
+/// Number of standard deviations from the expected value at which a statistical test is considered failed
+const TEST_THRESHOLD: f64 = 3.0;
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct BlockEntropy {
     pub end: usize,
     pub start: usize,
     pub entropy: f32,
+    /// Likely content of the block, only populated for high entropy blocks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classification: Option<EntropyClassification>,
+}
+
+/// Likely type of data in a high entropy block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntropyClass {
+    /// Byte frequencies or bit balance show the structured artifacts typical of compressed data
+    Compressed,
+    /// Statistically indistinguishable from random data, and embedded in lower entropy (structured) data
+    Encrypted,
+    /// Statistically indistinguishable from random data, and not surrounded by any lower entropy data
+    Random,
+    /// The block is too small to test, or the test results are inconclusive
+    Unknown,
+}
+
+/// Classification of a high entropy block, see `classify_entropy`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntropyClassification {
+    pub class: EntropyClass,
+    /// Confidence in the classification, from 0.0 to 1.0
+    pub confidence: f64,
+    /// Chi-square test statistic of the block's byte frequencies, expressed as standard deviations from the expected value
+    pub chi_square: f64,
+    /// Monobit (bit balance) test statistic, expressed as standard deviations from the expected value
+    pub monobit: f64,
+}
+
+/// Image formats supported by `render_graph` and `render_graph_to_buffer`
+#[allow(dead_code)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    #[default]
+    Png,
+    Svg,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -21,48 +79,466 @@ pub struct FileEntropy {
 }
 
 /// Splits the supplied data up into blocks and calculates the entropy of each block.
-fn blocks(data: &[u8]) -> Vec<BlockEntropy> {
+/// If no block size is specified, the data is split into 2048 blocks.
+fn blocks(
+    data: &[u8],
+    block_size: Option<usize>,
+    stride: Option<usize>,
+) -> Result<Vec<BlockEntropy>, EntropyError> {
     const BLOCK_COUNT: usize = 2048;
 
-    let mut offset: usize = 0;
-
-    let block_size = if data.len() < BLOCK_COUNT {
+    let block_size = block_size.unwrap_or(if data.len() < BLOCK_COUNT {
         data.len()
     } else {
         data.len() / BLOCK_COUNT
-    };
+    });
+
+    classify_entropy(data, block_size, stride)
+}
+
+/// Splits the supplied data up into `block_size` blocks, calculates the entropy of each block, and classifies each
+/// high entropy block as likely compressed, encrypted or random data.
+///
+/// Blocks start every `stride` bytes; a stride smaller than the block size produces overlapping blocks, for finer
+/// resolution. If no stride is specified, blocks do not overlap. The last block may be shorter than `block_size`.
+/// Returns an error if the block size is zero or larger than the data, or if the stride is zero.
+///
+/// Inputs of 4MB or more are split across rayon's global thread pool; blocks are still returned in order.
+///
+/// High entropy alone can not distinguish compressed data from encrypted data. Each high entropy block is additionally
+/// subjected to a chi-square test of its byte frequencies and a monobit test of its bit balance: compressed data shows
+/// structured byte frequency or bit balance artifacts, while encrypted data is flatter. Encrypted and random data are
+/// statistically indistinguishable; flat blocks are reported as `Encrypted` if the data also contains lower entropy
+/// (structured) blocks, as is typical of encrypted payloads inside a firmware image, and as `Random` otherwise.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::entropy::{classify_entropy, EntropyClass};
+///
+/// // Pseudo-random data generated with xorshift
+/// let mut state: u64 = 0x2545F4914F6CDD1D;
+/// let random_data: Vec<u8> = (0..32768)
+///     .map(|_| {
+///         state ^= state << 13;
+///         state ^= state >> 7;
+///         state ^= state << 17;
+///         (state >> 32) as u8
+///     })
+///     .collect();
+///
+/// let blocks = classify_entropy(&random_data, 8192, None).unwrap();
+/// assert!(blocks.iter().all(|block| block.classification.as_ref().unwrap().class == EntropyClass::Random));
+///
+/// // The same data following a block of zeros looks like an encrypted payload
+/// let firmware_data: Vec<u8> = [vec![0; 8192], random_data].concat();
+///
+/// let blocks = classify_entropy(&firmware_data, 8192, None).unwrap();
+/// assert!(blocks[0].classification.is_none());
+/// assert_eq!(blocks[1].classification.as_ref().unwrap().class, EntropyClass::Encrypted);
+///
+/// // Overlapping blocks
+/// let blocks = classify_entropy(&firmware_data, 8192, Some(4096)).unwrap();
+/// assert_eq!(blocks.len(), 9);
+///
+/// // Invalid block sizes
+/// assert!(classify_entropy(&firmware_data, 0, None).is_err());
+/// assert!(classify_entropy(&firmware_data, firmware_data.len() + 1, None).is_err());
+/// ```
+pub fn classify_entropy(
+    data: &[u8],
+    block_size: usize,
+    stride: Option<usize>,
+) -> Result<Vec<BlockEntropy>, EntropyError> {
+    let stride = stride.unwrap_or(block_size);
+
+    if block_size == 0 || block_size > data.len() || stride == 0 {
+        return Err(EntropyError);
+    }
 
-    let mut chunker = data.chunks(block_size);
-    let mut entropy_blocks: Vec<BlockEntropy> = vec![];
+    let mut block_ranges: Vec<(usize, usize)> = vec![];
+    let mut start: usize = 0;
 
     loop {
-        match chunker.next() {
-            None => break,
-            Some(block_data) => {
-                let mut block = BlockEntropy {
-                    ..Default::default()
-                };
-
-                block.start = offset;
-                block.entropy = shannon_entropy(block_data);
-                block.end = block.start + block_data.len();
-
-                offset = block.end;
-                entropy_blocks.push(block);
+        let end = std::cmp::min(start + block_size, data.len());
+        block_ranges.push((start, end));
+
+        start += stride;
+
+        if end == data.len() || start >= data.len() {
+            break;
+        }
+    }
+
+    let block_entropy = |(start, end): (usize, usize)| -> BlockEntropy {
+        let entropy = shannon_entropy(&data[start..end]);
+
+        let classification = if entropy as f64 >= HIGH_ENTROPY_THRESHOLD {
+            Some(classify_block(&data[start..end]))
+        } else {
+            None
+        };
+
+        BlockEntropy {
+            start,
+            end,
+            entropy,
+            classification,
+        }
+    };
+
+    // The entropy of each block is independent of the others, so large inputs are split across threads
+    let mut entropy_blocks: Vec<BlockEntropy> = if data.len() < PARALLEL_MIN_SIZE {
+        block_ranges.into_iter().map(block_entropy).collect()
+    } else {
+        block_ranges.into_par_iter().map(block_entropy).collect()
+    };
+
+    // Flat data inside otherwise structured data is most likely an encrypted payload
+    let structured = entropy_blocks
+        .iter()
+        .any(|block| block.classification.is_none());
+
+    for classification in entropy_blocks
+        .iter_mut()
+        .filter_map(|block| block.classification.as_mut())
+    {
+        if structured && classification.class == EntropyClass::Random {
+            classification.class = EntropyClass::Encrypted;
+        }
+    }
+
+    Ok(entropy_blocks)
+}
+
+/// Classifies a high entropy block of data based on chi-square and monobit tests
+fn classify_block(block_data: &[u8]) -> EntropyClassification {
+    let mut classification = EntropyClassification {
+        class: EntropyClass::Unknown,
+        confidence: 0.0,
+        chi_square: chi_square_deviation(block_data),
+        monobit: monobit_deviation(block_data),
+    };
+
+    if block_data.len() < MIN_CLASSIFICATION_SIZE {
+        return classification;
+    }
+
+    // Byte frequencies that are more uniform than random data would be are not the result of compression or encryption
+    if classification.chi_square < -TEST_THRESHOLD {
+        return classification;
+    }
+
+    let deviation = classification.chi_square.max(classification.monobit);
+
+    if deviation > TEST_THRESHOLD {
+        // Byte frequencies or bit balance are too uneven to be random; the further from random, the more confident we are
+        classification.class = EntropyClass::Compressed;
+        classification.confidence =
+            0.5 + (((deviation - TEST_THRESHOLD) / TEST_THRESHOLD).min(1.0) / 2.0);
+    } else {
+        // Both tests are consistent with random data; the closer to the expected values, the more confident we are
+        classification.class = EntropyClass::Random;
+        classification.confidence = 1.0 - (deviation.abs() / TEST_THRESHOLD / 2.0);
+    }
+
+    classification
+}
+
+/// Performs a chi-square test of the byte frequencies in the given data against a uniform distribution.
+/// Returns the number of standard deviations the test statistic is from its expected value (255 degrees of freedom).
+fn chi_square_deviation(data: &[u8]) -> f64 {
+    const DEGREES_OF_FREEDOM: f64 = 255.0;
+
+    let counts = byte_histogram(data);
+
+    let expected = data.len() as f64 / 256.0;
+    let chi_square: f64 = counts
+        .iter()
+        .map(|count| (*count as f64 - expected).powi(2) / expected)
+        .sum();
+
+    (chi_square - DEGREES_OF_FREEDOM) / (2.0 * DEGREES_OF_FREEDOM).sqrt()
+}
+
+/// Performs a monobit test on the given data.
+/// Returns the number of standard deviations the number of set bits is from the expected value (half of all bits).
+fn monobit_deviation(data: &[u8]) -> f64 {
+    let total_bits = (data.len() * 8) as f64;
+    let set_bits: u32 = data.iter().map(|byte| byte.count_ones()).sum();
+
+    (2.0 * set_bits as f64 - total_bits).abs() / total_bits.sqrt()
+}
+
+/// Counts the number of occurrences of each byte value in the data.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::entropy::byte_histogram;
+///
+/// let histogram = byte_histogram(b"hello");
+///
+/// assert_eq!(histogram[b'l' as usize], 2);
+/// assert_eq!(histogram[b'h' as usize], 1);
+/// assert_eq!(histogram.iter().sum::<u64>(), 5);
+/// ```
+pub fn byte_histogram(data: &[u8]) -> [u64; 256] {
+    let mut histogram = [0u64; 256];
+
+    for byte in data {
+        histogram[*byte as usize] += 1;
+    }
+
+    histogram
+}
+
+/// Calculates the Shannon entropy of each `block_size` block of the given data.
+///
+/// Entropy values range from 0.0 to 8.0 bits per byte; the last block may be shorter than `block_size`.
+/// Returns an empty list if `block_size` is 0. As with `classify_entropy`, inputs of 4MB or more are split across
+/// rayon's global thread pool.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::entropy::block_entropy;
+///
+/// let my_data: Vec<u8> = [vec![0; 256], (0..=255).collect()].concat();
+///
+/// let entropy = block_entropy(&my_data, 256);
+///
+/// assert_eq!(entropy, vec![0.0, 8.0]);
+/// ```
+#[allow(dead_code)]
+pub fn block_entropy(data: &[u8], block_size: usize) -> Vec<f64> {
+    if block_size == 0 {
+        return vec![];
+    }
+
+    let entropy = |block: &[u8]| shannon_entropy(block) as f64;
+
+    if data.len() < PARALLEL_MIN_SIZE {
+        data.chunks(block_size).map(entropy).collect()
+    } else {
+        data.par_chunks(block_size).map(entropy).collect()
+    }
+}
+
+/// Calculates the Shannon entropy of a `window_size` window of data at every offset in the given data.
+///
+/// The returned list contains one entry per window, the first of which starts at offset 0 and the last of which ends
+/// at the end of the data. Returns an empty list if `window_size` is 0 or larger than the data. As with
+/// `classify_entropy`, inputs of 4MB or more are split across rayon's global thread pool.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::entropy::rolling_entropy;
+///
+/// let my_data: &[u8] = b"AAAABBBB";
+///
+/// let entropy = rolling_entropy(my_data, 4);
+///
+/// assert_eq!(entropy.len(), 5);
+/// assert_eq!(entropy[0], 0.0);
+/// assert_eq!(entropy[2], 1.0);
+/// assert_eq!(entropy[4], 0.0);
+/// ```
+#[allow(dead_code)]
+pub fn rolling_entropy(data: &[u8], window_size: usize) -> Vec<f64> {
+    if window_size == 0 {
+        return vec![];
+    }
+
+    let entropy = |window: &[u8]| shannon_entropy(window) as f64;
+
+    if data.len() < PARALLEL_MIN_SIZE {
+        data.windows(window_size).map(entropy).collect()
+    } else {
+        data.par_windows(window_size).map(entropy).collect()
+    }
+}
+
+/// Byte frequency statistics, see `byte_frequency`
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct ByteFrequency {
+    /// Number of occurrences of each byte value
+    pub histogram: [u64; 256],
+    /// Up to the `BYTE_FREQUENCY_RANK_COUNT` most common byte values and their counts, most common first
+    pub most_common: Vec<(u8, u64)>,
+    /// Up to the `BYTE_FREQUENCY_RANK_COUNT` least common byte values that occur in the data, least common first
+    pub least_common: Vec<(u8, u64)>,
+    /// Number of distinct byte values in the data
+    pub distinct: usize,
+    /// Ratio of printable ASCII bytes (including tabs and line breaks) to all bytes, from 0.0 to 1.0
+    pub printable_ratio: f64,
+    /// True if the data is (almost) entirely printable, and is likely text rather than binary data
+    pub text_like: bool,
+}
+
+/// Number of byte values reported in `ByteFrequency.most_common` and `ByteFrequency.least_common`
+#[allow(dead_code)]
+pub const BYTE_FREQUENCY_RANK_COUNT: usize = 5;
+
+/// Data with at least this ratio of printable bytes is considered text
+#[allow(dead_code)]
+const TEXT_PRINTABLE_RATIO: f64 = 0.95;
+
+/// Calculates byte frequency statistics for the data, which help to identify the type of data beyond its entropy,
+/// e.g. text or base64 encoded data (printable, with few distinct byte values) versus binary data.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::entropy::byte_frequency;
+///
+/// let frequency = byte_frequency(b"aaaabbbc\x00");
+///
+/// assert_eq!(frequency.most_common[0], (b'a', 4));
+/// assert_eq!(frequency.least_common[0], (0, 1));
+/// assert_eq!(frequency.distinct, 4);
+/// assert!(!frequency.text_like);
+///
+/// assert!(byte_frequency(b"Hello, world!\n").text_like);
+/// ```
+#[allow(dead_code)]
+pub fn byte_frequency(data: &[u8]) -> ByteFrequency {
+    let histogram = byte_histogram(data);
+
+    // Byte values that occur in the data, most common first; ties are ordered by byte value
+    let mut ranked: Vec<(u8, u64)> = histogram
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .map(|(byte, count)| (byte as u8, *count))
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    let mut least_common = ranked.clone();
+    least_common.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+    least_common.truncate(BYTE_FREQUENCY_RANK_COUNT);
+
+    let printable: u64 = histogram
+        .iter()
+        .enumerate()
+        .filter(|(byte, _)| is_printable_ascii(*byte as u8) || *byte as u8 == b'\t')
+        .map(|(_, count)| count)
+        .sum();
+
+    let printable_ratio = if data.is_empty() {
+        0.0
+    } else {
+        printable as f64 / data.len() as f64
+    };
+
+    ByteFrequency {
+        histogram,
+        most_common: ranked
+            .iter()
+            .take(BYTE_FREQUENCY_RANK_COUNT)
+            .cloned()
+            .collect(),
+        least_common,
+        distinct: ranked.len(),
+        printable_ratio,
+        text_like: printable_ratio >= TEXT_PRINTABLE_RATIO,
+    }
+}
+
+/// Type of data in a region, see `classify_regions`
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RegionClass {
+    /// A single repeated byte value, e.g. erased flash or zero padding
+    Padding,
+    /// Low entropy, printable data
+    Text,
+    /// Low entropy, non-printable data, such as executable code or data structures
+    Binary,
+    /// High entropy data, see `EntropyClass`
+    Compressed,
+    Encrypted,
+    Random,
+    /// High entropy data that could not be classified further
+    Unknown,
+}
+
+/// A contiguous region of data of the same type, see `classify_regions`
+#[allow(dead_code)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Region {
+    pub start: usize,
+    pub end: usize,
+    pub class: RegionClass,
+}
+
+/// Splits the data into regions of the same type, based on the entropy (see `classify_entropy`) and the byte
+/// frequencies (see `byte_frequency`) of each `block_size` block. Adjacent blocks of the same type are merged into a
+/// single region. Returns an error if the block size is zero or larger than the data.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::entropy::{RegionClass, classify_regions};
+///
+/// let text = "The quick brown fox jumps over the lazy dog.\n".repeat(100);
+/// let data: Vec<u8> = [vec![0xFF; 8192], text.into_bytes()].concat();
+///
+/// let regions = classify_regions(&data, 1024).unwrap();
+///
+/// assert_eq!(regions.len(), 2);
+/// assert_eq!(regions[0].class, RegionClass::Padding);
+/// assert_eq!(regions[0].end, 8192);
+/// assert_eq!(regions[1].class, RegionClass::Text);
+/// assert_eq!(regions[1].end, data.len());
+/// ```
+#[allow(dead_code)]
+pub fn classify_regions(data: &[u8], block_size: usize) -> Result<Vec<Region>, EntropyError> {
+    let mut regions: Vec<Region> = vec![];
+
+    for block in classify_entropy(data, block_size, None)? {
+        let class = match &block.classification {
+            Some(classification) => match classification.class {
+                EntropyClass::Compressed => RegionClass::Compressed,
+                EntropyClass::Encrypted => RegionClass::Encrypted,
+                EntropyClass::Random => RegionClass::Random,
+                EntropyClass::Unknown => RegionClass::Unknown,
+            },
+            None => {
+                let frequency = byte_frequency(&data[block.start..block.end]);
+
+                if frequency.distinct == 1 {
+                    RegionClass::Padding
+                } else if frequency.text_like {
+                    RegionClass::Text
+                } else {
+                    RegionClass::Binary
+                }
             }
+        };
+
+        match regions.last_mut() {
+            Some(region) if region.class == class => region.end = block.end,
+            _ => regions.push(Region {
+                start: block.start,
+                end: block.end,
+                class,
+            }),
         }
     }
 
-    entropy_blocks
+    Ok(regions)
 }
 
 pub fn plot(
     file_path: impl Into<String>,
     stdin: bool,
     out_file: Option<String>,
+    block_size: Option<usize>,
+    stride: Option<usize>,
 ) -> Result<FileEntropy, EntropyError> {
-    let mut x: Vec<usize> = Vec::new();
-    let mut y: Vec<f32> = Vec::new();
     let target_file: String = file_path.into();
     let mut file_entropy = FileEntropy {
         file: target_file.clone(),
@@ -72,36 +548,164 @@ pub fn plot(
     // Read in the target file data
     if let Ok(file_data) = read_input(&target_file, stdin) {
         // Calculate the entropy of each file block
-        file_entropy.blocks = blocks(&file_data);
+        file_entropy.blocks = blocks(&file_data, block_size, stride)?;
 
-        for block in &file_entropy.blocks {
-            x.push(block.start);
-            x.push(block.end);
-            y.push(block.entropy);
-            y.push(block.entropy);
+        match out_file {
+            None => interactive_graph(&file_entropy.blocks).show(),
+            Some(out_file_name) => render_graph(&file_entropy.blocks, out_file_name)?,
         }
 
-        let mut plot = Plot::new();
-        let trace = Scatter::new(x, y);
-        let layout = Layout::new()
-            .title("Entropy Graph")
-            .x_axis(Axis::new().title("File Offset"))
-            .y_axis(Axis::new().title("Entropy").range(vec![0, 8]));
+        return Ok(file_entropy);
+    }
 
-        plot.add_trace(trace);
-        plot.set_layout(layout);
+    Err(EntropyError)
+}
 
-        match out_file {
-            None => plot.show(),
-            Some(out_file_name) => {
-                // TODO: Switch to plotly_static, which is the recommended way to do this
-                #[allow(deprecated)]
-                plot.write_image(&out_file_name, ImageFormat::PNG, 2048, 1024, 1.0);
-            }
+/// Builds an interactive Plotly entropy graph of the provided entropy blocks
+fn interactive_graph(blocks: &[BlockEntropy]) -> Plot {
+    let mut x: Vec<usize> = Vec::new();
+    let mut y: Vec<f32> = Vec::new();
+
+    for block in blocks {
+        x.push(block.start);
+        x.push(block.end);
+        y.push(block.entropy);
+        y.push(block.entropy);
+    }
+
+    let mut plot = Plot::new();
+    let trace = Scatter::new(x, y);
+    let layout = Layout::new()
+        .title("Entropy Graph")
+        .x_axis(Axis::new().title("File Offset"))
+        .y_axis(Axis::new().title("Entropy").range(vec![0, 8]));
+
+    plot.add_trace(trace);
+    plot.set_layout(layout);
+
+    plot
+}
+
+/// Renders an entropy graph of the provided entropy blocks to the specified image file.
+/// High entropy regions are shaded. The image format is SVG if the file name has a `.svg` extension, else PNG.
+pub fn render_graph(
+    blocks: &[BlockEntropy],
+    out_file: impl AsRef<std::path::Path>,
+) -> Result<(), EntropyError> {
+    let out_file = out_file.as_ref();
+    let is_svg = out_file
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("svg"));
+
+    if is_svg {
+        draw_graph(
+            SVGBackend::new(out_file, (GRAPH_WIDTH, GRAPH_HEIGHT)).into_drawing_area(),
+            blocks,
+        )
+    } else {
+        draw_graph(
+            BitMapBackend::new(out_file, (GRAPH_WIDTH, GRAPH_HEIGHT)).into_drawing_area(),
+            blocks,
+        )
+    }
+}
+
+/// Renders an entropy graph of the provided entropy blocks in the specified image format, and returns the image data.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::entropy::{GraphFormat, classify_entropy, render_graph_to_buffer};
+///
+/// let file_data: Vec<u8> = (0..=255).cycle().take(4096).collect();
+/// let blocks = classify_entropy(&file_data, 1024, None).unwrap();
+///
+/// let png = render_graph_to_buffer(&blocks, GraphFormat::Png).unwrap();
+/// assert!(png.starts_with(b"\x89PNG"));
+///
+/// let svg = render_graph_to_buffer(&blocks, GraphFormat::Svg).unwrap();
+/// assert!(svg.starts_with(b"<svg"));
+/// ```
+#[allow(dead_code)]
+pub fn render_graph_to_buffer(
+    blocks: &[BlockEntropy],
+    format: GraphFormat,
+) -> Result<Vec<u8>, EntropyError> {
+    match format {
+        GraphFormat::Svg => {
+            let mut svg = String::new();
+            draw_graph(
+                SVGBackend::with_string(&mut svg, (GRAPH_WIDTH, GRAPH_HEIGHT)).into_drawing_area(),
+                blocks,
+            )?;
+            Ok(svg.into_bytes())
         }
+        GraphFormat::Png => {
+            let mut pixels = vec![0; (GRAPH_WIDTH * GRAPH_HEIGHT * 3) as usize];
+            draw_graph(
+                BitMapBackend::with_buffer(&mut pixels, (GRAPH_WIDTH, GRAPH_HEIGHT))
+                    .into_drawing_area(),
+                blocks,
+            )?;
 
-        return Ok(file_entropy);
+            let mut png: Vec<u8> = vec![];
+            image::codecs::png::PngEncoder::new(&mut png)
+                .write_image(&pixels, GRAPH_WIDTH, GRAPH_HEIGHT, image::ColorType::Rgb8)
+                .map_err(|_| EntropyError)?;
+            Ok(png)
+        }
     }
+}
 
-    Err(EntropyError)
+/// Draws an entropy graph onto the provided drawing area
+fn draw_graph<DB: DrawingBackend>(
+    root: DrawingArea<DB, Shift>,
+    blocks: &[BlockEntropy],
+) -> Result<(), EntropyError> {
+    let end_offset = blocks.iter().map(|block| block.end).max().unwrap_or(0);
+
+    root.fill(&WHITE).map_err(|_| EntropyError)?;
+
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Entropy Graph", ("sans-serif", 40))
+        .margin(20)
+        .x_label_area_size(60)
+        .y_label_area_size(60)
+        .build_cartesian_2d(0..end_offset.max(1), 0f32..8f32)
+        .map_err(|_| EntropyError)?;
+
+    chart
+        .configure_mesh()
+        .x_desc("File Offset")
+        .y_desc("Entropy")
+        .label_style(("sans-serif", 20))
+        .x_label_formatter(&|offset| format!("{offset:#X}"))
+        .draw()
+        .map_err(|_| EntropyError)?;
+
+    // Shade high entropy regions
+    chart
+        .draw_series(
+            blocks
+                .iter()
+                .filter(|block| block.entropy as f64 >= HIGH_ENTROPY_THRESHOLD)
+                .map(|block| {
+                    Rectangle::new(
+                        [(block.start, 0.0), (block.end, 8.0)],
+                        RED.mix(0.2).filled(),
+                    )
+                }),
+        )
+        .map_err(|_| EntropyError)?;
+
+    chart
+        .draw_series(LineSeries::new(
+            blocks
+                .iter()
+                .flat_map(|block| [(block.start, block.entropy), (block.end, block.entropy)]),
+            &BLUE,
+        ))
+        .map_err(|_| EntropyError)?;
+
+    root.present().map_err(|_| EntropyError)
 }
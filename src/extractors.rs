@@ -73,10 +73,13 @@
 //! matches without performing an actual extraction.
 //!
 //! Internal extractors must return an `extractors::common::ExtractionResult` struct.
+//! Extractors that emit several logically distinct outputs (e.g. a kernel and a root file system) may describe each of them
+//! with `ExtractionResult::add_component`; each component is then hashed, and analyzed during recursion, independently.
 //!
 //! Internal extractors should use the `extractors::common::Chroot` API to write files to disk.
 //! The methods defined in the `Chroot` struct allow the manipulation of files on disk while ensuring that any file paths
-//! accessed do not traverse outside the specified output directory.
+//! accessed do not traverse outside the specified output directory. Failed operations return a `ChrootError`, which describes the
+//! kind of error that occurred and the file path it occurred on.
 //!
 //! ### Example
 //!
@@ -125,7 +128,7 @@
 //!                         result.success = chroot.carve_file(foobar_header.original_file_name,
 //!                                                            foobar_data,
 //!                                                            foobar_header.header_size,
-//!                                                            foobar_header.data_size);
+//!                                                            foobar_header.data_size).is_ok();
 //!                     } else {
 //!                         // Nothing else to do, consider this a success
 //!                         result.success = true;
@@ -138,9 +141,34 @@
 //!     return result;
 //! }
 //! ```
+//!
+//! ## Registering Extractors At Run Time
+//!
+//! Library users can add or override the extractor for any signature, without modifying the extractors defined in
+//! magic.rs, by registering it with `Binwalk::register_extractor`:
+//!
+//! ```no_run
+//! use binwalk::Binwalk;
+//! use binwalk::extractors::common::{Extractor, ExtractorType, SOURCE_FILE_PLACEHOLDER};
+//!
+//! let binwalker = Binwalk::new();
+//!
+//! // Extract SquashFS images with a site-specific wrapper script
+//! binwalker.register_extractor(
+//!     "squashfs",
+//!     Extractor {
+//!         utility: ExtractorType::External("site-unsquashfs".to_string()),
+//!         extension: "sqsh".to_string(),
+//!         arguments: vec![SOURCE_FILE_PLACEHOLDER.to_string()],
+//!         ..Default::default()
+//!     },
+//! );
+//! ```
 
 pub mod androidsparse;
+pub mod ar;
 pub mod arcadyan;
+pub mod arj;
 pub mod autel;
 pub mod bmp;
 pub mod bzip2;
@@ -148,28 +176,43 @@ pub mod cab;
 pub mod common;
 pub mod csman;
 pub mod dahua_zip;
+pub mod der;
 pub mod dmg;
 pub mod dtb;
 pub mod dumpifs;
 pub mod dxbc;
+pub mod efigpt;
+pub mod elf;
 pub mod encfw;
+pub mod encoded;
+pub mod exfat;
+pub mod ext;
+pub mod fat;
 pub mod gif;
 pub mod gpg;
 pub mod gzip;
+pub mod ihex;
 pub mod inflate;
 pub mod iso9660;
 pub mod jboot;
 pub mod jffs2;
 pub mod jpeg;
+pub mod lha;
 pub mod linux;
 pub mod lz4;
 pub mod lzfse;
+pub mod lzh;
 pub mod lzma;
+pub mod lzo;
 pub mod lzop;
 pub mod matter_ota;
 pub mod mbr;
 pub mod mh01;
+pub mod minix;
+pub mod ntfs;
 pub mod pcap;
+pub mod pdf;
+pub mod pe;
 pub mod pem;
 pub mod png;
 pub mod rar;
@@ -180,6 +223,7 @@ pub mod squashfs;
 pub mod srec;
 pub mod svg;
 pub mod swapped;
+pub mod swf;
 pub mod tarball;
 pub mod trx;
 pub mod tsk;
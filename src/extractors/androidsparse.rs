@@ -107,7 +107,7 @@ fn extract_chunk(
 ) -> bool {
     if chunk_header.is_raw {
         // Raw chunks are just data chunks stored verbatim
-        if !chroot.append_to_file(outfile, chunk_data) {
+        if chroot.append_to_file(outfile, chunk_data).is_err() {
             return false;
         }
     } else if chunk_header.is_fill {
@@ -123,7 +123,7 @@ fn extract_chunk(
             }
 
             // Append fill block to file
-            if !chroot.append_to_file(outfile, &fill_block) {
+            if chroot.append_to_file(outfile, &fill_block).is_err() {
                 return false;
             }
         }
@@ -137,7 +137,7 @@ fn extract_chunk(
 
         // Write block_count NULL blocks to disk
         for _ in 0..chunk_header.block_count {
-            if !chroot.append_to_file(outfile, &null_block) {
+            if chroot.append_to_file(outfile, &null_block).is_err() {
                 return false;
             }
         }
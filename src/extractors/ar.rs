@@ -0,0 +1,164 @@
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::warn;
+use crate::structures::ar::{AR_MAGIC, AR_MEMBER_HEADER_SIZE, parse_ar_member_header};
+use std::path::Path;
+
+/// Defines the internal extractor function for extracting Unix ar archives, such as Debian packages and static libraries
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::ar::ar_extractor;
+///
+/// match ar_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn ar_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_ar_archive),
+        ..Default::default()
+    }
+}
+
+/// Extracts each member of an ar archive to the output directory
+pub fn extract_ar_archive(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    // GNU archives store long member names in this member, and reference them as "/<offset into this member>"
+    const GNU_NAME_TABLE: &str = "//";
+    // BSD archives store long member names at the start of the member data, and reference them as "#1/<name length>"
+    const BSD_NAME_PREFIX: &str = "#1/";
+    // Symbol index members generated by ranlib; these are not extracted
+    const SYMBOL_TABLES: &[&str] = &["/", "/SYM64/", "__.SYMDEF", "__.SYMDEF SORTED"];
+    // Executable permission bits
+    const EXECUTABLE_MODE: usize = 0o111;
+
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let chroot = Chroot::new(output_directory);
+
+    let mut member_count: usize = 0;
+    let mut gnu_name_table: &[u8] = &[];
+    let mut next_offset = offset + AR_MAGIC.len();
+
+    if file_data.get(offset..next_offset) != Some(AR_MAGIC) {
+        return result;
+    }
+
+    // Members are stored back-to-back until the end of the archive
+    while let Some(member_data) = file_data.get(next_offset..) {
+        let member_header = match parse_ar_member_header(member_data) {
+            Err(_) => break,
+            Ok(member_header) => member_header,
+        };
+
+        let data_start = next_offset + AR_MEMBER_HEADER_SIZE;
+        let data_end = data_start + member_header.data_size;
+
+        let mut member_file_data = match file_data.get(data_start..data_end) {
+            None => break,
+            Some(member_file_data) => member_file_data,
+        };
+
+        // The final member's padding byte may be missing
+        next_offset = data_end;
+        if file_data.len() > data_end && member_header.data_size % 2 != 0 {
+            next_offset += 1;
+        }
+
+        member_count += 1;
+
+        let member_name: String = if member_header.name == GNU_NAME_TABLE {
+            gnu_name_table = member_file_data;
+            continue;
+        } else if SYMBOL_TABLES.contains(&member_header.name.as_str()) {
+            continue;
+        } else if let Some(name_length) = member_header.name.strip_prefix(BSD_NAME_PREFIX) {
+            let name_length = match name_length.parse::<usize>() {
+                Err(_) => break,
+                Ok(name_length) => name_length,
+            };
+
+            match member_file_data.get(0..name_length) {
+                None => break,
+                Some(name_bytes) => {
+                    member_file_data = &member_file_data[name_length..];
+                    String::from_utf8_lossy(name_bytes)
+                        .trim_end_matches('\0')
+                        .to_string()
+                }
+            }
+        } else if let Some(name_offset) = member_header.name.strip_prefix('/') {
+            // GNU long names are terminated by "/\n" in the name table
+            match name_offset
+                .parse::<usize>()
+                .ok()
+                .and_then(|name_offset| gnu_name_table.get(name_offset..))
+            {
+                None => break,
+                Some(name_bytes) => {
+                    let name_end = name_bytes
+                        .iter()
+                        .position(|&b| b == b'\n')
+                        .unwrap_or(name_bytes.len());
+                    String::from_utf8_lossy(&name_bytes[..name_end])
+                        .trim_end_matches('/')
+                        .to_string()
+                }
+            }
+        } else {
+            // GNU short names are terminated by a '/'
+            member_header.name.trim_end_matches('/').to_string()
+        };
+
+        if output_directory.is_some() {
+            let file_path = unique_member_path(&chroot, &member_name, member_count);
+
+            if let Err(e) = chroot.create_file(&file_path, member_file_data) {
+                warn!("Failed to extract ar member {member_name}: {e}");
+                return result;
+            }
+
+            if member_header.mode & EXECUTABLE_MODE != 0 {
+                chroot.make_executable(&file_path).ok();
+            }
+        }
+    }
+
+    if member_count > 0 {
+        result.size = Some(next_offset - offset);
+        result.success = true;
+    }
+
+    result
+}
+
+/// Static libraries may contain several members with the same name; each subsequent duplicate is suffixed with its member number
+fn unique_member_path(chroot: &Chroot, member_name: &str, member_number: usize) -> String {
+    let member_name = match member_name.is_empty() {
+        true => format!("member_{member_number}"),
+        false => member_name.to_string(),
+    };
+
+    if Path::new(&chroot.chrooted_path(&member_name)).exists() {
+        return format!("{member_name}.{member_number}");
+    }
+
+    member_name
+}
@@ -0,0 +1,202 @@
+use crate::common::crc32;
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::extractors::lzh::{BitReader, LzhError, lzh_decompress};
+use crate::logging::{debug, warn};
+use crate::structures::arj::{
+    ARJ_FILE_TYPE_DIRECTORY, ARJ_FILE_TYPE_MAIN_HEADER, ARJ_FLAG_GARBLED, ARJBlockHeader,
+    parse_arj_block_header,
+};
+
+/// Defines the internal extractor function for extracting ARJ archives
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::arj::arj_extractor;
+///
+/// match arj_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn arj_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_arj_archive),
+        ..Default::default()
+    }
+}
+
+/// Extracts the members of an ARJ archive, starting at its main header.
+///
+/// Members are validated against their CRCs; corrupt, password protected, or unsupported members are skipped.
+/// The reported size is the size of the entire archive, up to and including the end of archive marker.
+/// If no output directory is specified, only the archive headers are validated.
+pub fn extract_arj_archive(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let arj_data = &file_data[offset..];
+
+    // The archive must start with the main header
+    match parse_arj_block_header(arj_data) {
+        Ok(main_header) if main_header.file_type == ARJ_FILE_TYPE_MAIN_HEADER => {
+            let chroot = Chroot::new(output_directory);
+            let mut member_count: usize = 0;
+            let mut header_offset = main_header.size;
+
+            while let Ok(header) = parse_arj_block_header(&arj_data[header_offset..]) {
+                header_offset += header.size;
+
+                if header.end_of_archive {
+                    debug!("Extracted {member_count} ARJ members");
+                    result.size = Some(header_offset);
+                    result.success = true;
+                    break;
+                }
+
+                let Some(member_data) =
+                    arj_data.get(header_offset..header_offset + header.compressed_size)
+                else {
+                    warn!("ARJ member {} is truncated", header.name);
+                    break;
+                };
+
+                header_offset += header.compressed_size;
+
+                if output_directory.is_some() && extract_arj_member(&chroot, &header, member_data) {
+                    member_count += 1;
+                }
+            }
+        }
+        _ => debug!("ARJ data does not start with a main header"),
+    }
+
+    result
+}
+
+/// Extracts a single ARJ member; returns true if the member was extracted
+fn extract_arj_member(chroot: &Chroot, header: &ARJBlockHeader, member_data: &[u8]) -> bool {
+    // File types for binary and text files
+    const BINARY_FILE: usize = 0;
+    const TEXT_FILE: usize = 1;
+
+    // ARJ archives created on DOS may use backslashes as path separators
+    let member_path = chroot.safe_path_join("/", header.name.replace('\\', "/"));
+
+    if header.file_type == ARJ_FILE_TYPE_DIRECTORY {
+        return chroot.create_directory(&member_path).is_ok();
+    }
+
+    if header.file_type != BINARY_FILE && header.file_type != TEXT_FILE {
+        debug!(
+            "Skipping ARJ member {} of type {}",
+            header.name, header.file_type
+        );
+        return false;
+    }
+
+    if header.flags & ARJ_FLAG_GARBLED != 0 {
+        warn!(
+            "Skipping ARJ member {}: member is password protected",
+            header.name
+        );
+        return false;
+    }
+
+    let decompressed_data = match header.compression_method {
+        0 => Ok(member_data.to_vec()),
+        // Methods 1-3 differ only in compression effort; all use the same distance codes as LHA's -lh7- method
+        1..=3 => lzh_decompress(member_data, 16, header.uncompressed_size),
+        4 => arj_method4_decompress(member_data, header.uncompressed_size),
+        method => {
+            warn!(
+                "Skipping ARJ member {}: unsupported compression method {method}",
+                header.name
+            );
+            return false;
+        }
+    };
+
+    match decompressed_data {
+        Err(_) => warn!("Skipping ARJ member {}: decompression failed", header.name),
+        Ok(data) if crc32(&data) as usize != header.data_crc => {
+            warn!("Skipping ARJ member {}: CRC mismatch", header.name)
+        }
+        Ok(data) => {
+            // Members in sub directories may not have a directory entry of their own
+            if let Some((parent_directory, _)) = member_path.rsplit_once('/')
+                && !parent_directory.is_empty()
+                && chroot.create_directory(parent_directory).is_err()
+            {
+                return false;
+            }
+
+            return chroot.create_file(&member_path, &data).is_ok();
+        }
+    }
+
+    false
+}
+
+/// Decompresses data compressed with ARJ's "fastest" method 4, a plain LZ77 scheme with variable length codes
+fn arj_method4_decompress(compressed_data: &[u8], output_size: usize) -> Result<Vec<u8>, LzhError> {
+    // Minimum match length
+    const THRESHOLD: usize = 3;
+
+    let mut reader = BitReader::new(compressed_data);
+    let mut output: Vec<u8> = Vec::with_capacity(output_size);
+
+    while output.len() < output_size {
+        // A length code of 0 is followed by a literal byte
+        let length_code = arj_method4_value(&mut reader, 0, 7)?;
+
+        if length_code == 0 {
+            output.push(reader.bits(8)? as u8);
+            continue;
+        }
+
+        let match_length = length_code - 1 + THRESHOLD;
+        let distance = arj_method4_value(&mut reader, 9, 13)?;
+
+        let source = output.len().checked_sub(distance + 1).ok_or(LzhError)?;
+
+        for i in source..source + match_length.min(output_size - output.len()) {
+            output.push(output[i]);
+        }
+    }
+
+    Ok(output)
+}
+
+/// Method 4 values are encoded as a unary bit width between `start_bits` and `stop_bits`, followed by that many
+/// bits; each additional bit of width offsets the value by the range covered by the previous width
+fn arj_method4_value(
+    reader: &mut BitReader,
+    start_bits: usize,
+    stop_bits: usize,
+) -> Result<usize, LzhError> {
+    let mut value_offset: usize = 0;
+    let mut width = start_bits;
+
+    while width < stop_bits && reader.bits(1)? == 1 {
+        value_offset += 1 << width;
+        width += 1;
+    }
+
+    Ok(value_offset + reader.bits(width)?)
+}
@@ -71,7 +71,10 @@ pub fn autel_deobfuscate(
                         // Write to file, if requested
                         if output_directory.is_some() {
                             let chroot = Chroot::new(output_directory);
-                            if !chroot.append_to_file(OUTPUT_FILE_NAME, &decoded_block) {
+                            if chroot
+                                .append_to_file(OUTPUT_FILE_NAME, &decoded_block)
+                                .is_err()
+                            {
                                 break;
                             }
                         }
@@ -61,8 +61,9 @@ pub fn extract_bmp_image(
 
                 if output_directory.is_some() {
                     let chroot = Chroot::new(output_directory);
-                    result.success =
-                        chroot.carve_file(OUTFILE_NAME, file_data, offset, bmp_file_header.size);
+                    result.success = chroot
+                        .carve_file(OUTFILE_NAME, file_data, offset, bmp_file_header.size)
+                        .is_ok();
                 }
             }
         }
@@ -93,7 +93,10 @@ pub fn bzip2_decompressor(
                     let n: usize = (decompressor.total_out() as usize) - bytes_written;
 
                     let chroot = Chroot::new(output_directory);
-                    if !chroot.append_to_file(OUTPUT_FILE_NAME, &decompressed_buffer[0..n]) {
+                    if chroot
+                        .append_to_file(OUTPUT_FILE_NAME, &decompressed_buffer[0..n])
+                        .is_err()
+                    {
                         // If writing data to file fails, break
                         break;
                     }
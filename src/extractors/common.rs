@@ -1,10 +1,17 @@
-use crate::signatures::common::SignatureResult;
-use log::{debug, error, info, warn};
+use crate::common::guess_file_type;
+use crate::logging::{self, debug, error, info, warn};
+use crate::signatures::common::{Signature, SignatureResult};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::fs;
-use std::io::Write;
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
 use std::path;
 use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time;
 use walkdir::WalkDir;
 
 #[cfg(windows)]
@@ -18,14 +25,136 @@ use std::os::unix::fs::PermissionsExt;
 /// This contstant in command line arguments will be replaced with the path to the input file
 pub const SOURCE_FILE_PLACEHOLDER: &str = "%e";
 
-/// Return value of InternalExtractor upon error
-#[derive(Debug, Clone)]
-pub struct ExtractionError;
+/// This contstant in command line arguments will be replaced with the path to the extraction output directory
+pub const OUTPUT_DIRECTORY_PLACEHOLDER: &str = "%o";
+
+/// This contstant in command line arguments will be replaced with the decimal offset of the signature in the input file
+pub const OFFSET_PLACEHOLDER: &str = "%O";
+
+/// Name of the extraction manifest file written to the root of each `<file>.extracted` directory, see `write_manifest`
+pub const MANIFEST_FILE_NAME: &str = ".binwalk_manifest.json";
+
+/// Maximum number of bytes of stdout/stderr output retained from an external extractor, see `ExtractionConfig.capture_output`
+const MAX_CAPTURED_OUTPUT_SIZE: usize = 64 * 1024;
+
+/// Delay before the first retry of a failed external extractor, see `Extractor.retries`
+const RETRY_BACKOFF: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Path separators honored when sanitizing paths, regardless of the host operating system.
+/// Extracted file paths may originate from any operating system, and e.g. a `..\` in a file path
+/// must not be allowed to bypass directory traversal checks on Windows hosts.
+const PATH_SEPARATORS: [char; 2] = ['/', '\\'];
+
+/// Describes why an extraction failed
+#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExtractionError {
+    /// The external extractor could not be started
+    Spawn,
+    /// The extractor did not complete in the allotted time
+    Timeout,
+    /// The external extractor exited with an unexpected exit code
+    NonZeroExit(i32),
+    /// A file system or other I/O operation failed
+    Io(String),
+    /// Input data or file paths failed sanity checks
+    Sanitization(String),
+    /// The signature's offset and size do not describe a valid, non-empty, range of the file data
+    InvalidCarveRange {
+        offset: usize,
+        size: usize,
+        available: usize,
+    },
+    /// The output file system is full or read-only; no further extractions are attempted, see `OutputFailure`
+    OutputUnavailable(String),
+    /// The extractor reported success, but its output directory contained nothing but empty files; describes what
+    /// was found in the output directory
+    NothingExtracted(String),
+    /// The extracted data failed the extractor's validator, see `Extractor.validator`; the output directory is removed
+    /// unless `ExtractionConfig.keep_failed` is set
+    ValidationFailed,
+}
+
+impl ExtractionError {
+    /// Returns true if the error may be transient, e.g. due to resource contention, such that retrying the extraction
+    /// may succeed. Unexpected exit codes are not transient; they typically mean that the data is not supported.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, ExtractionError::Spawn | ExtractionError::Io(_))
+    }
+}
+
+impl std::fmt::Display for ExtractionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExtractionError::Spawn => write!(f, "failed to spawn external extractor"),
+            ExtractionError::Timeout => write!(f, "extractor timed out"),
+            ExtractionError::NonZeroExit(code) => {
+                write!(f, "extractor exited with unexpected code {code}")
+            }
+            ExtractionError::Io(message) => write!(f, "I/O error: {message}"),
+            ExtractionError::OutputUnavailable(message) => {
+                write!(f, "output file system unavailable: {message}")
+            }
+            ExtractionError::Sanitization(message) => write!(f, "sanitization error: {message}"),
+            ExtractionError::NothingExtracted(outputs) => {
+                write!(f, "no data was extracted, found {outputs}")
+            }
+            ExtractionError::ValidationFailed => write!(f, "extracted data failed validation"),
+            ExtractionError::InvalidCarveRange {
+                offset,
+                size,
+                available,
+            } => write!(
+                f,
+                "invalid carve range: {size:#X} bytes at offset {offset:#X}, but only {available:#X} bytes of data are available"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExtractionError {}
+
+impl From<ChrootError> for ExtractionError {
+    fn from(e: ChrootError) -> ExtractionError {
+        if is_output_unavailable(e.kind) {
+            ExtractionError::OutputUnavailable(e.to_string())
+        } else {
+            ExtractionError::Io(e.to_string())
+        }
+    }
+}
+
+/// Returns true if the I/O error kind means that nothing more can be written to the output file system
+fn is_output_unavailable(kind: std::io::ErrorKind) -> bool {
+    matches!(
+        kind,
+        std::io::ErrorKind::StorageFull
+            | std::io::ErrorKind::ReadOnlyFilesystem
+            | std::io::ErrorKind::QuotaExceeded
+    )
+}
 
 /// Built-in internal extractors must provide a function conforming to this definition.
 /// Arguments: file_data, offset, output_directory.
 pub type InternalExtractor = fn(&[u8], usize, Option<&str>) -> ExtractionResult;
 
+/// Post-extraction validators must provide a function conforming to this definition.
+/// Arguments: output_directory. Returns false if the extracted data is invalid.
+pub type ExtractionValidator = fn(&str) -> bool;
+
+/// Trial decoders must provide a function conforming to this definition.
+/// Arguments: file_data, offset, trial_size. Returns true if the data at the offset decodes without error until
+/// either `trial_size` bytes have been decoded, or the end of the encoded data is reached.
+pub type TrialDecoder = fn(&[u8], usize, usize) -> bool;
+
+/// A list of extended attribute names and values
+pub type ExtendedAttributes = Vec<(String, Vec<u8>)>;
+
+/// Extended attribute readers must provide a function conforming to this definition.
+/// Arguments: the signature data. Returns the extended attributes of each file stored in the data,
+/// keyed by the file's path relative to the extraction output directory.
+pub type XattrReader = fn(&[u8]) -> Vec<(String, ExtendedAttributes)>;
+
 /// Enum to define either an Internal or External extractor type
 #[derive(Debug, Default, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub enum ExtractorType {
@@ -36,7 +165,8 @@ pub enum ExtractorType {
 }
 
 /// Describes extractors, both external and internal
-#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[allow(unpredictable_function_pointer_comparisons)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Extractor {
     /// External command or internal function to execute
     pub utility: ExtractorType,
@@ -48,1105 +178,4527 @@ pub struct Extractor {
     pub exit_codes: Vec<i32>,
     /// Set to true to disable recursion into this extractor's extracted files
     pub do_not_recurse: bool,
+    /// Set to true to pipe the signature data to the external command's stdin, rather than carving it to disk.
+    /// The SOURCE_FILE_PLACEHOLDER is not substituted in the command arguments when this is enabled.
+    pub use_stdin: bool,
+    /// Environment variables to set for the external command
+    pub env: Vec<(String, String)>,
+    /// If true (the default), the external command is run from inside the extraction output directory;
+    /// otherwise it is run from binwalk's current working directory
+    pub run_in_output_dir: bool,
+    /// Optional function to validate the extracted data after a successful extraction
+    pub validator: Option<ExtractionValidator>,
+    /// Glob patterns of extractor output files (e.g. log files) that are not considered extracted data; patterns are
+    /// matched against file paths relative to the output directory
+    pub ignored_outputs: Vec<String>,
+    /// Ordered list of alternative extractors to use if this extractor's external command is not installed
+    pub alternatives: Vec<Extractor>,
+    /// Optional function to read the extended attributes of the extracted files from the signature data;
+    /// the attributes are applied to the extracted files if `ExtractionConfig.preserve_xattrs` is enabled
+    pub xattr_reader: Option<XattrReader>,
+    /// Arguments that stop the external command from setting extended attributes itself; these are inserted before
+    /// the other arguments unless `ExtractionConfig.preserve_xattrs` is enabled
+    pub no_xattr_arguments: Vec<String>,
+    /// Number of times to re-run the external command if it fails to start, or is terminated abnormally; it is not
+    /// re-run if it exits with an unexpected exit code. Each retry waits twice as long as the previous one.
+    pub retries: u8,
+    /// Optional function to cheaply decode the start of the signature data, used to reject false positive magic
+    /// matches for signatures that set `Signature.trial_validation`
+    pub trial_decoder: Option<TrialDecoder>,
+    /// Maximum time the external command may run for before it is killed and the extraction fails with
+    /// `ExtractionError::Timeout`; if None, it may run indefinitely
+    pub timeout: Option<time::Duration>,
+    /// Name of a file in the output directory to write the external command's stdout to, for commands that write
+    /// their output to stdout (e.g. `lzma -dc`); the extraction fails if the command writes nothing to stdout
+    pub stdout_to_file: Option<String>,
+}
+
+impl Default for Extractor {
+    fn default() -> Self {
+        Extractor {
+            utility: ExtractorType::None,
+            extension: String::new(),
+            arguments: vec![],
+            exit_codes: vec![],
+            do_not_recurse: false,
+            use_stdin: false,
+            env: vec![],
+            run_in_output_dir: true,
+            validator: None,
+            ignored_outputs: vec![],
+            alternatives: vec![],
+            xattr_reader: None,
+            no_xattr_arguments: vec![],
+            retries: 0,
+            trial_decoder: None,
+            timeout: None,
+            stdout_to_file: None,
+        }
+    }
+}
+
+/// Serializable definition of an external extractor, as loaded from an extractor configuration file by
+/// `ExtractorRegistry::register_from_file`.
+///
+/// Only external extractors can be defined this way; internal extractors, and options that take a function (e.g.
+/// validators), can only be registered from code.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::extractors::common::{ExtractorDefinition, ExtractorType};
+///
+/// let definition: ExtractorDefinition = serde_json::from_str(
+///     r#"{"command": "site-unsquashfs", "extension": "sqsh", "arguments": ["-d", "squashfs-root", "%e"], "timeout": 60}"#,
+/// )
+/// .unwrap();
+///
+/// let extractor = definition.to_extractor();
+/// assert_eq!(extractor.utility, ExtractorType::External("site-unsquashfs".to_string()));
+/// assert_eq!(extractor.timeout, Some(std::time::Duration::from_secs(60)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExtractorDefinition {
+    /// External command to execute
+    pub command: String,
+    /// File extension expected by the external command
+    #[serde(default)]
+    pub extension: String,
+    /// Arguments to pass to the external command; may include SOURCE_FILE_PLACEHOLDER
+    #[serde(default)]
+    pub arguments: Vec<String>,
+    /// Exit codes, other than 0, that indicate success
+    #[serde(default)]
+    pub exit_codes: Vec<i32>,
+    /// Set to true to disable recursion into the extracted files
+    #[serde(default)]
+    pub do_not_recurse: bool,
+    /// Maximum number of seconds the external command may run for, if any
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    /// Name of the file to write the external command's stdout to, if any
+    #[serde(default)]
+    pub stdout_to_file: Option<String>,
+}
+
+impl ExtractorDefinition {
+    /// Returns the external extractor described by this definition
+    pub fn to_extractor(&self) -> Extractor {
+        Extractor {
+            utility: ExtractorType::External(self.command.clone()),
+            extension: self.extension.clone(),
+            arguments: self.arguments.clone(),
+            exit_codes: self.exit_codes.clone(),
+            do_not_recurse: self.do_not_recurse,
+            timeout: self.timeout.map(time::Duration::from_secs),
+            stdout_to_file: self.stdout_to_file.clone(),
+            ..Default::default()
+        }
+    }
 }
 
 /// Stores information about a completed extraction
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ExtractionResult {
-    /// Size of the data consumed during extraction, if known; should be populated by the constructor
+    /// Size of the data consumed during extraction, if known; should be populated by the constructor.
+    ///
+    /// This is the number of input bytes, starting at the signature offset, that make up the extracted data
+    /// (e.g. the entire compressed stream, including any headers and trailers), *not* the size of the decompressed
+    /// output. Signature parsers use this value from a dry run to report the signature size, which in turn
+    /// determines where scanning resumes.
     pub size: Option<usize>,
+    /// Total size of the regular files written to the output directory, excluding any of the extractor's ignored
+    /// outputs; automatically populated by extractors::common::execute. Compare with `size` to get the expansion ratio.
+    pub output_size: Option<u64>,
     /// Extractor success status; should be populated by the constructor
     pub success: bool,
     /// Extractor name, automatically populated by extractors::common::execute
     pub extractor: String,
     /// Set to true to disable recursion into this extractor's extracted files.
-    /// Automatically populated with the corresponding Extractor.do_not_recurse field by extractors::common::execute.
+    /// Automatically populated by extractors::common::execute from the corresponding Extractor.do_not_recurse field
+    /// and ExtractionConfig.no_recurse.
     pub do_not_recurse: bool,
     /// The output directory where the extractor dropped its files, automatically populated by extractors::common::execute
     pub output_directory: String,
+    /// Path to the carved extractor input file, if it was preserved after a failed extraction (see `ExtractionConfig.keep_failed`)
+    pub carved_file: Option<String>,
+    /// Hex encoded SHA-256 of the carved signature data, automatically populated by extractors::common::execute
+    pub carved_sha256: Option<String>,
+    /// Hex encoded digest of the carved signature data, computed with `carved_digest_algorithm`; automatically populated
+    /// by extractors::common::execute if `ExtractionConfig.digest_algorithm` is set
+    pub carved_digest: Option<String>,
+    /// The algorithm that `carved_digest` was computed with
+    pub carved_digest_algorithm: Option<DigestAlgorithm>,
+    /// If this extraction was deduplicated, the output directory of the identical extraction it links to
+    pub duplicate_of: Option<String>,
+    /// The tail end of a failed external extractor's stdout, if `ExtractionConfig.capture_output` was enabled
+    pub extractor_stdout: Option<String>,
+    /// The tail end of a failed external extractor's stderr, if `ExtractionConfig.capture_output` was enabled
+    pub extractor_stderr: Option<String>,
+    /// Logically distinct components emitted by the extractor (e.g. kernel, ramdisk), if it chooses to describe them.
+    /// When populated, only these components are analyzed during recursion, rather than all extracted files.
+    pub components: Vec<ExtractedComponent>,
+    /// The reason the extraction failed, if known
+    pub error: Option<ExtractionError>,
+    /// Set to true if extraction was not attempted, e.g. because the signature's extractors were excluded
+    /// by `ExtractionConfig.extractor_filter`
+    pub skipped: bool,
+    /// Wall-clock time spent running the extractor, including any retries, in milliseconds; automatically populated
+    /// by extractors::common::execute
+    pub duration_ms: Option<f64>,
+    /// How the carved file differs from the signature data, if it was aligned or padded by `carve` or `carve_gap`
+    /// (see `ExtractionConfig.carve_alignment` and `ExtractionConfig.carve_padding`); `size` is always the size of
+    /// the signature data itself
+    pub carve_adjustment: Option<CarveAdjustment>,
 }
 
-/// Stores information about external extractor processes. For internal use only.
-#[derive(Debug)]
-pub struct ProcInfo {
-    pub child: process::Child,
-    pub exit_codes: Vec<i32>,
-    pub carved_file: String,
+/// Describes how a carved file was adjusted to meet `ExtractionConfig.carve_alignment` and
+/// `ExtractionConfig.carve_padding`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CarveAdjustment {
+    /// Offset in the source file that the carved file starts at
+    pub offset: usize,
+    /// Number of bytes preceding the signature data that were carved, so that the carved file starts at an aligned
+    /// offset
+    pub leading_bytes: usize,
+    /// Number of zero bytes appended to the carved file, so that its size is a multiple of the padding size
+    pub padding_bytes: usize,
 }
 
-/// Provides chroot-like functionality for internal extractors
-#[derive(Debug, Default, Clone)]
-pub struct Chroot {
-    /// The chroot directory passed to Chroot::new
-    pub chroot_directory: String,
+/// Describes a single component emitted by an extractor, see `ExtractionResult.components`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtractedComponent {
+    /// Component name, e.g. "kernel"
+    pub name: String,
+    /// Path to the component file, relative to the extraction output directory.
+    /// Automatically converted to an absolute path by extractors::common::execute.
+    pub path: String,
+    /// Size of the component data
+    pub size: usize,
+    /// Hex encoded SHA-256 of the component file, automatically populated by extractors::common::execute
+    pub sha256: Option<String>,
+    /// Set to true to disable recursion into this component
+    pub do_not_recurse: bool,
 }
 
-impl Chroot {
-    /// Create a new chrooted instance. All file paths will be effectively chrooted in the specified directory path.
-    /// The chroot directory path will be created if it does not already exist.
-    ///
-    /// If no directory path is specified, the chroot directory will be `/`.
+impl ExtractionResult {
+    /// Records a component emitted by an extractor.
     ///
     /// ## Example
     ///
     /// ```
-    /// use binwalk::extractors::common::Chroot;
+    /// use binwalk::extractors::common::ExtractionResult;
     ///
-    /// let chroot_dir = std::path::Path::new(&std::env::temp_dir())
-    ///     .join("binwalk_unit_tests")
-    ///     .display()
-    ///     .to_string();
+    /// let mut result = ExtractionResult::default();
     ///
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// let chroot = Chroot::new(Some(&chroot_dir));
+    /// result.add_component("kernel", "kernel.bin", 1024);
+    /// result.add_component("ramdisk", "ramdisk.cpio", 2048);
     ///
-    /// assert_eq!(&chroot.chroot_directory, &chroot_dir);
-    /// assert_eq!(std::path::Path::new(&chroot_dir).exists(), true);
-    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// assert_eq!(result.components.len(), 2);
+    /// assert_eq!(result.components[1].name, "ramdisk");
+    /// assert_eq!(result.components[1].size, 2048);
     /// ```
-    pub fn new(chroot_directory: Option<&str>) -> Chroot {
-        let mut chroot_instance = Chroot {
+    pub fn add_component(&mut self, name: impl Into<String>, path: impl Into<String>, size: usize) {
+        self.components.push(ExtractedComponent {
+            name: name.into(),
+            path: path.into(),
+            size,
             ..Default::default()
-        };
+        });
+    }
 
-        match chroot_directory {
-            None => {
-                // Default path is '/'
-                chroot_instance.chroot_directory = path::MAIN_SEPARATOR.to_string();
-            }
-            Some(chroot_dir) => {
-                // Attempt to ensure that the specified path is absolute. If this fails, just use the path as given.
-                match path::absolute(chroot_dir) {
-                    Ok(pathbuf) => {
-                        chroot_instance.chroot_directory = pathbuf.display().to_string();
-                    }
-                    Err(_) => {
-                        chroot_instance.chroot_directory = chroot_dir.to_string();
-                    }
-                }
-            }
+    /// Returns the list of extracted files that should be analyzed when recursing into this extraction's results.
+    /// If no components were described by the extractor, all regular files in the output directory are returned.
+    pub fn recursion_targets(&self) -> Vec<String> {
+        if !self.success || self.do_not_recurse {
+            return vec![];
         }
 
-        // Create the chroot directory if it does not exist
-        if !path::Path::new(&chroot_instance.chroot_directory).exists() {
-            match fs::create_dir_all(&chroot_instance.chroot_directory) {
-                Ok(_) => {
-                    debug!(
-                        "Created new chroot directory {}",
-                        chroot_instance.chroot_directory
-                    );
-                }
-                Err(e) => {
-                    error!(
-                        "Failed to create chroot directory {}: {}",
-                        chroot_instance.chroot_directory, e
-                    );
-                }
-            }
+        if self.components.is_empty() {
+            return get_extracted_files(&self.output_directory);
         }
 
-        chroot_instance
+        self.components
+            .iter()
+            .filter(|component| !component.do_not_recurse)
+            .map(|component| component.path.clone())
+            .filter(|component_path| path::Path::new(component_path).is_file())
+            .collect()
     }
+}
 
-    /// Joins two paths, ensuring that the final path does not traverse outside of the chroot directory.
+/// Stores information about external extractor processes. For internal use only.
+#[derive(Debug)]
+pub struct ProcInfo {
+    pub child: process::Child,
+    pub exit_codes: Vec<i32>,
+    pub carved_file: Option<String>,
+    /// Directory created to hold the carved file, if it was not carved to the output directory; removed along with the
+    /// carved file
+    pub carve_directory: Option<String>,
+    pub stdout_reader: Option<thread::JoinHandle<Vec<u8>>>,
+    pub stderr_reader: Option<thread::JoinHandle<Vec<u8>>>,
+    /// The process is killed if it runs for longer than this
+    pub timeout: Option<time::Duration>,
+    /// Path of the file the process's stdout is written to, see `Extractor.stdout_to_file`
+    pub stdout_file: Option<String>,
+    /// Held until the process has been reaped, at which point the slot is released for another process
+    #[allow(dead_code)]
+    pub process_slot: ProcessSlot,
+}
+
+/// Aggregated run time and throughput of a single extractor, see `ExtractionStatistics`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtractorStatistics {
+    /// Extractor name, as reported in `ExtractionResult.extractor`
+    pub extractor: String,
+    /// Number of extractions performed by this extractor
+    pub extractions: usize,
+    /// Total wall-clock time spent running this extractor, in milliseconds
+    pub duration_ms: f64,
+    /// Total number of input bytes processed by this extractor
+    pub input_size: u64,
+    /// Total number of bytes written by this extractor
+    pub output_size: u64,
+    /// Input bytes processed per second, in megabytes
+    pub throughput_mbps: f64,
+}
+
+/// Aggregated match and extraction counts of a single signature, see `ExtractionStatistics`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SignatureStatistics {
+    /// Signature name, as reported in `SignatureResult.name`
+    pub signature: String,
+    /// Number of times this signature was found
+    pub matches: usize,
+    /// Number of successful extractions of this signature
+    pub extracted: usize,
+    /// Total number of bytes written by successful extractions of this signature
+    pub output_size: u64,
+}
+
+/// Per-extractor run time and throughput statistics, and per-signature match and extraction counts, collected from
+/// analysis and extraction results
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtractionStatistics {
+    /// Statistics for each extractor, keyed by extractor name
+    pub extractors: HashMap<String, ExtractorStatistics>,
+    /// Statistics for each signature, keyed by signature name
+    #[serde(default)]
+    pub signatures: HashMap<String, SignatureStatistics>,
+}
+
+impl ExtractionStatistics {
+    /// Adds an extraction result to the statistics. Results that were not timed, such as skipped or deduplicated
+    /// extractions, are ignored.
     ///
     /// ## Example
     ///
     /// ```
-    /// use binwalk::extractors::common::Chroot;
-    /// use std::path::MAIN_SEPARATOR;
-    ///
-    /// let chroot_dir = std::path::Path::new(&std::env::temp_dir())
-    ///     .join("binwalk_unit_tests")
-    ///     .display()
-    ///     .to_string();
-    ///
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// let chroot = Chroot::new(Some(&chroot_dir));
+    /// use binwalk::extractors::common::{ExtractionResult, ExtractionStatistics};
+    /// use binwalk::signatures::common::SignatureResult;
     ///
-    /// let dir_name = "etc";
-    /// let file_name = "passwd";
-    /// let abs_path = format!("{}{}{}{}", MAIN_SEPARATOR, dir_name, MAIN_SEPARATOR, file_name);
-    /// let abs_path_dir = format!("{}{}", MAIN_SEPARATOR, dir_name);
-    /// let rel_path_dir = format!("..{}..{}..{}{}", MAIN_SEPARATOR, MAIN_SEPARATOR, MAIN_SEPARATOR, dir_name);
-    /// let abs_path_file = format!("{}{}", MAIN_SEPARATOR, file_name);
-    /// let rel_path_file = format!("..{}..{}..{}{}", MAIN_SEPARATOR, MAIN_SEPARATOR, MAIN_SEPARATOR, file_name);
+    /// let mut statistics = ExtractionStatistics::default();
     ///
-    /// let path1 = chroot.safe_path_join(&abs_path_dir, file_name);
-    /// let expected_path1 = std::path::Path::new(&chroot_dir).join(dir_name).join(file_name);
+    /// let signature = SignatureResult {
+    ///     size: 2_000_000,
+    ///     ..Default::default()
+    /// };
     ///
-    /// let path2 = chroot.safe_path_join(&abs_path_dir, &rel_path_file);
-    /// let expected_path2 = std::path::Path::new(&chroot_dir).join(file_name);
+    /// let result = ExtractionResult {
+    ///     extractor: "gzip_built_in".to_string(),
+    ///     duration_ms: Some(500.0),
+    ///     ..Default::default()
+    /// };
     ///
-    /// let path3 = chroot.safe_path_join(&rel_path_dir, &abs_path_file);
-    /// let expected_path3 = std::path::Path::new(&chroot_dir).join(dir_name).join(file_name);
+    /// statistics.add(&signature, &result);
+    /// statistics.add(&signature, &result);
     ///
-    /// let path4 = chroot.safe_path_join(&chroot_dir, &abs_path);
-    /// let expected_path4 = std::path::Path::new(&chroot_dir).join(dir_name).join(file_name);
+    /// let gzip_statistics = &statistics.extractors["gzip_built_in"];
     ///
-    /// assert_eq!(path1, expected_path1.display().to_string());
-    /// assert_eq!(path2, expected_path2.display().to_string());
-    /// assert_eq!(path3, expected_path3.display().to_string());
-    /// assert_eq!(path4, expected_path4.display().to_string());
-    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// assert_eq!(gzip_statistics.extractions, 2);
+    /// assert_eq!(gzip_statistics.input_size, 4_000_000);
+    /// assert_eq!(gzip_statistics.throughput_mbps, 4.0);
     /// ```
-    pub fn safe_path_join(&self, path1: impl Into<String>, path2: impl Into<String>) -> String {
-        // Join and sanitize both paths; retain the leading '/' (if there is one)
-        let mut joined_path: String = self.sanitize_path(
-            &format!("{}{}{}", path1.into(), path::MAIN_SEPARATOR, path2.into()),
-            true,
-        );
+    pub fn add(&mut self, signature: &SignatureResult, result: &ExtractionResult) {
+        const MS_PER_SECOND: f64 = 1000.0;
+        const BYTES_PER_MB: f64 = 1_000_000.0;
 
-        // If the joined path does not start with the chroot directory,
-        // prepend the chroot directory to the final joined path.
-        // on Windows: If no chroot directory is specified, skip the operation
-        if cfg!(windows) && self.chroot_directory == path::MAIN_SEPARATOR.to_string() {
-            // do nothing and skip
-        } else if !joined_path.starts_with(&self.chroot_directory) {
-            joined_path = format!(
-                "{}{}{}",
-                self.chroot_directory,
-                path::MAIN_SEPARATOR,
-                joined_path
-            );
-        }
+        let Some(duration_ms) = result.duration_ms else {
+            return;
+        };
 
-        self.strip_double_slash(&joined_path)
-    }
+        let statistics = self
+            .extractors
+            .entry(result.extractor.clone())
+            .or_insert_with(|| ExtractorStatistics {
+                extractor: result.extractor.clone(),
+                ..Default::default()
+            });
 
-    /// Given a file path, returns a sanitized path that is chrooted inside the specified chroot directory.
-    ///
-    /// ## Example
-    ///
-    /// ```
-    /// use binwalk::extractors::common::Chroot;
-    ///
-    /// let chroot_dir = std::path::Path::new(&std::env::temp_dir())
-    ///     .join("binwalk_unit_tests")
-    ///     .display()
-    ///     .to_string();
-    ///
-    /// let file_name = "test.txt";
-    ///
-    /// let chroot = Chroot::new(Some(&chroot_dir));
-    /// let path = chroot.chrooted_path(file_name);
-    ///
-    /// assert_eq!(path, std::path::Path::new(&chroot_dir).join(file_name).display().to_string());
-    /// ```
-    pub fn chrooted_path(&self, file_path: impl Into<String>) -> String {
-        self.safe_path_join(file_path, "".to_string())
+        statistics.extractions += 1;
+        statistics.duration_ms += duration_ms;
+        // Internal extractors report the size of the data they consumed, external extractors are given the signature data
+        statistics.input_size += result.size.unwrap_or(signature.size) as u64;
+        statistics.output_size += result.output_size.unwrap_or(0);
+
+        if statistics.duration_ms > 0.0 {
+            statistics.throughput_mbps = (statistics.input_size as f64 / BYTES_PER_MB)
+                / (statistics.duration_ms / MS_PER_SECOND);
+        }
     }
 
-    /// Creates a regular file in the chrooted directory and writes the provided data to it.
+    /// Adds a signature match, and its extraction result, if it was extracted, to the per-signature statistics.
     ///
     /// ## Example
     ///
     /// ```
-    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_213_0() -> Result<(), Box<dyn std::error::Error>> {
-    /// use binwalk::extractors::common::Chroot;
+    /// use binwalk::extractors::common::{ExtractionResult, ExtractionStatistics};
+    /// use binwalk::signatures::common::SignatureResult;
     ///
-    /// let file_data: &[u8] = b"foobar";
+    /// let mut statistics = ExtractionStatistics::default();
     ///
-    /// let file_name = "created_file.txt";
+    /// let signature = SignatureResult {
+    ///     name: "gzip".to_string(),
+    ///     ..Default::default()
+    /// };
     ///
-    /// let chroot_dir = std::path::Path::new("tests")
-    ///     .join("binwalk_unit_tests")
-    ///     .display()
-    ///     .to_string();
+    /// let result = ExtractionResult {
+    ///     success: true,
+    ///     output_size: Some(1024),
+    ///     ..Default::default()
+    /// };
     ///
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// let chroot = Chroot::new(Some(&chroot_dir));
+    /// statistics.add_match(&signature, Some(&result));
+    /// statistics.add_match(&signature, None);
     ///
-    /// assert_eq!(chroot.create_file(file_name, file_data), true);
-    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, std::str::from_utf8(file_data)?);
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// # Ok(())
-    /// # } _doctest_main_src_extractors_common_rs_213_0(); }
+    /// let gzip_statistics = &statistics.signatures["gzip"];
+    ///
+    /// assert_eq!(gzip_statistics.matches, 2);
+    /// assert_eq!(gzip_statistics.extracted, 1);
+    /// assert_eq!(gzip_statistics.output_size, 1024);
     /// ```
-    pub fn create_file(&self, file_path: impl Into<String>, file_data: &[u8]) -> bool {
-        let safe_file_path: String = self.chrooted_path(file_path);
+    pub fn add_match(&mut self, signature: &SignatureResult, result: Option<&ExtractionResult>) {
+        let statistics = self
+            .signatures
+            .entry(signature.name.clone())
+            .or_insert_with(|| SignatureStatistics {
+                signature: signature.name.clone(),
+                ..Default::default()
+            });
 
-        if !path::Path::new(&safe_file_path).exists() {
-            match fs::write(safe_file_path.clone(), file_data) {
-                Ok(_) => {
-                    return true;
-                }
-                Err(e) => {
-                    error!("Failed to write data to {safe_file_path}: {e}");
-                }
-            }
-        } else {
-            error!("Failed to create file {safe_file_path}: path already exists");
+        statistics.matches += 1;
+
+        if let Some(result) = result
+            && result.success
+        {
+            statistics.extracted += 1;
+            statistics.output_size += result.output_size.unwrap_or(0);
         }
+    }
 
-        false
+    /// Returns the statistics for each signature, ordered by the number of matches, most first
+    pub fn by_matches(&self) -> Vec<&SignatureStatistics> {
+        let mut statistics: Vec<&SignatureStatistics> = self.signatures.values().collect();
+        statistics.sort_by(|a, b| {
+            b.matches
+                .cmp(&a.matches)
+                .then_with(|| a.signature.cmp(&b.signature))
+        });
+        statistics
     }
 
-    /// Carve data and write it to a new file.
+    /// Returns the statistics for each extractor, ordered by the total time spent running the extractor, longest first
+    pub fn by_duration(&self) -> Vec<&ExtractorStatistics> {
+        let mut statistics: Vec<&ExtractorStatistics> = self.extractors.values().collect();
+        statistics.sort_by(|a, b| b.duration_ms.total_cmp(&a.duration_ms));
+        statistics
+    }
+}
+
+/// Naming schemes for the per-signature output directories created inside of `<file>.extracted`
+#[allow(dead_code)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum OutputDirectoryNaming {
+    /// Hexadecimal signature offset, e.g. `<file>.extracted/1F00`
+    #[default]
+    Hex,
+    /// Zero-padded decimal signature offset, e.g. `<file>.extracted/0000007936`
+    Decimal,
+    /// Signature name and hexadecimal signature offset, e.g. `<file>.extracted/gzip_1F00`
+    NameAndHex,
+}
+
+/// How symlinks with absolute target paths are neutralized
+#[allow(dead_code)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AbsoluteSymlinkPolicy {
+    /// Absolute targets are treated as relative to the chroot directory, e.g. `/bin/busybox` links to
+    /// `<chroot directory>/bin/busybox`
+    #[default]
+    ChrootAbsolute,
+    /// Absolute targets are treated as relative to the directory containing the symlink, e.g. `/bin/busybox` links
+    /// to `<symlink directory>/bin/busybox`
+    RelativizeOnly,
+    /// Symlinks with absolute targets are replaced with a placeholder file containing the text
+    /// `symlink -> <original target path>`
+    RejectAbsolute,
+}
+
+/// Digest algorithms that carved signature data may be hashed with, see `ExtractionConfig.digest_algorithm`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestAlgorithm {
+    Md5,
+    Sha1,
+    #[default]
+    Sha256,
+    Blake3,
+}
+
+impl DigestAlgorithm {
+    /// Returns the hex encoded digest of the data.
     ///
     /// ## Example
     ///
     /// ```
-    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_255_0() -> Result<(), Box<dyn std::error::Error>> {
-    /// use binwalk::extractors::common::Chroot;
-    ///
-    /// const CARVE_SIZE: usize = 6;
+    /// use binwalk::extractors::common::DigestAlgorithm;
     ///
-    /// let data: &[u8] = b"foobarJUNK";
-    ///
-    /// let file_name = "carved_file.txt";
-    ///
-    /// let chroot_dir = std::path::Path::new("tests")
-    ///     .join("binwalk_unit_tests")
-    ///     .display()
-    ///     .to_string();
-    ///
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// let chroot = Chroot::new(Some(&chroot_dir));
-    ///
-    /// assert_eq!(chroot.carve_file(file_name, data, 0, CARVE_SIZE), true);
-    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, std::str::from_utf8(&data[0..CARVE_SIZE])?);
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// # Ok(())
-    /// } _doctest_main_src_extractors_common_rs_255_0(); }
+    /// assert_eq!(DigestAlgorithm::Md5.hex_digest(b"abc"), "900150983cd24fb0d6963f7d28e17f72");
+    /// assert_eq!(DigestAlgorithm::Sha1.hex_digest(b"abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
     /// ```
-    pub fn carve_file(
-        &self,
-        file_path: impl Into<String>,
-        data: &[u8],
-        start: usize,
-        size: usize,
-    ) -> bool {
-        let mut retval: bool = false;
-
-        if let Some(file_data) = data.get(start..start + size) {
-            retval = self.create_file(file_path, file_data);
-        } else {
-            error!(
-                "Failed to create file {}: data offset/size are invalid",
-                file_path.into()
-            );
+    pub fn hex_digest(&self, data: &[u8]) -> String {
+        match self {
+            DigestAlgorithm::Md5 => format!("{:x}", md5::compute(data)),
+            DigestAlgorithm::Sha1 => hex::encode(sha1::Sha1::digest(data)),
+            DigestAlgorithm::Sha256 => hex::encode(Sha256::digest(data)),
+            DigestAlgorithm::Blake3 => blake3::hash(data).to_hex().to_string(),
         }
-
-        retval
     }
+}
 
-    /// Creates a device file in the chroot directory.
-    ///
-    /// Note that this does *not* create a real device file, just a regular file containing the device file info.
-    fn create_device(
-        &self,
-        file_path: impl Into<String>,
-        device_type: &str,
-        major: usize,
-        minor: usize,
-    ) -> bool {
-        let device_file_contents: String = format!("{device_type} {major} {minor}");
-        self.create_file(file_path, &device_file_contents.clone().into_bytes())
-    }
+/// How signatures found within the data of another signature are reported and extracted, e.g. a gzip file inside of
+/// an uncompressed archive
+#[allow(dead_code)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NestedSignaturePolicy {
+    /// Nested signatures are discarded; only the outermost signatures are reported and extracted
+    #[default]
+    Discard,
+    /// Nested signatures are reported, with `SignatureResult.parent` set, but only the outermost signatures are extracted
+    Outermost,
+    /// Nested signatures are reported, and only the innermost signatures, which contain no other signatures, are extracted
+    Innermost,
+    /// All signatures are reported and extracted. Nested signatures are not recursed into if the signature containing
+    /// them was extracted successfully, as they are found again when recursing into its extracted files.
+    Both,
+}
 
-    /// Creates a character device file in the chroot directory.
-    ///
-    /// Note that this does *not* create a real character device, just a regular file containing the text `c <major> <minor>`.
+/// What to do if an extraction's output directory already exists, e.g. when binwalk is re-run on the same file
+#[allow(dead_code)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ExistingOutputPolicy {
+    /// The extraction fails
+    Fail,
+    /// The existing output directory is assumed to hold the results of a previous extraction, and is used as is;
+    /// the extractor is not run, and the extraction is reported as skipped
+    Skip,
+    /// The existing output directory is removed and the extraction performed again
+    #[default]
+    Overwrite,
+}
+
+/// Run-time options controlling how extractors::common::execute performs extractions
+#[derive(Debug, Default, Clone)]
+pub struct ExtractionConfig {
+    /// Naming scheme for extraction output directories
+    pub output_directory_naming: OutputDirectoryNaming,
+    /// If true, any extracted symlinks whose fully resolved target lies outside of the extraction
+    /// output directory are replaced with placeholder files (see `Chroot::verify_symlink_targets`)
+    pub verify_symlink_targets: bool,
+    /// How symlinks with absolute targets are neutralized (see `Chroot::absolute_symlinks`). Extractors that use the
+    /// `Chroot` API always chroot absolute targets; with any other policy, symlinks with absolute targets that were
+    /// created by external extractors are re-created according to the policy.
+    pub absolute_symlinks: AbsoluteSymlinkPolicy,
+    /// If not empty, extracted symlinks whose targets, relative to the output directory, lie outside of all of these
+    /// directories (e.g. `/bin`, `/lib`) are replaced with placeholder files (see `Chroot::symlink_allowlist`)
+    pub symlink_allowlist: Vec<String>,
+    /// If true, the output directory and carved input file of a failed extraction are preserved for debugging
+    /// instead of being deleted; their paths are recorded in the ExtractionResult
+    pub keep_failed: bool,
+    /// If true, data identical to previously extracted data is not extracted again; instead, the output directory
+    /// is created as a symlink to the previous extraction's output directory
+    pub dedupe: bool,
+    /// Successful extractions performed during this run, used when `dedupe` is enabled
+    pub extraction_cache: ExtractionCache,
+    /// If true, the stdout and stderr of external extractors are captured and logged at debug level;
+    /// the output of failed extractors is also recorded in the ExtractionResult
+    pub capture_output: bool,
+    /// If true, extractors are never run; instead, each signature's data is carved to
+    /// `<file_path>.extracted/<offset>.<signature name>` (see `carve`)
+    pub carve_only: bool,
+    /// Names of signatures whose extracted files should not be recursed into, in addition to those whose
+    /// extractors set `Extractor.do_not_recurse`
+    pub no_recurse: Vec<String>,
+    /// Extracted files of these types are not recursed into, see `ExtractionConfig::is_terminal_file`. Types are either
+    /// file extensions, e.g. `txt`, or MIME types as guessed by `common::guess_file_type`, e.g. `image/png` or `image/*`.
+    pub terminal_types: Vec<String>,
+    /// Restricts which extractors may be run
+    pub extractor_filter: ExtractorFilter,
+    /// If set, signatures are only extracted if this predicate allows it; e.g. to only extract data within some range
+    /// of offsets, or below some size. Signatures that are vetoed are still reported, and their extractions skipped.
+    pub extraction_predicate: Option<ExtractionPredicate>,
+    /// If true, all extracted files are rendered inert after extraction (see `Chroot::quarantine_files`): no file is
+    /// left executable, and symlinks, device files, FIFOs and sockets are replaced with placeholder files.
+    /// This is the recommended setting when extracting untrusted data, e.g. in CI pipelines.
+    pub quarantine: bool,
+    /// If true, an extraction manifest is written to the root of each `<file>.extracted` directory (see `write_manifest`)
+    pub write_manifest: bool,
+    /// If true, extractions that were successfully completed by a previous run, as recorded in the previous run's
+    /// extraction manifest, are not performed again (see `resume_extraction`). Should be used with `write_manifest`.
+    pub resume: bool,
+    /// Signatures with a confidence lower than this are still reported, but not extracted.
+    /// One of `CONFIDENCE_LOW` (the default, all signatures are extracted), `CONFIDENCE_MEDIUM`, or `CONFIDENCE_HIGH`.
+    pub min_confidence: u8,
+    /// Output directories created while extracting a file, used to give overlapping signatures distinct output
+    /// directories; reset by `Binwalk::extract` for each file
+    pub output_directories: OutputDirectoryClaims,
+    /// If true, extended attributes stored in the extracted data (e.g. `security.capability`, SELinux labels) are
+    /// applied to the extracted files, for extractors that define an `xattr_reader`. Since extended attributes can be
+    /// used to smuggle data onto the host, this is disabled by default; it is also ignored in `quarantine` mode.
+    pub preserve_xattrs: bool,
+    /// Limits the number of concurrently running external extractor processes; unlimited by default.
+    /// Clones share the same limit, so it may be shared between worker threads.
+    pub process_limiter: ProcessLimiter,
+    /// Extractors registered at run time, which are used instead of the signatures' compiled in extractors
+    pub extractor_registry: ExtractorRegistry,
+    /// Maps external extractor command names to the executables to run in their place, e.g. `unsquashfs` to
+    /// `/opt/tools/bin/unsquashfs`, so that extractors may be run without relying on `PATH`
+    pub command_paths: HashMap<String, String>,
+    /// Maps signature names to the file extensions to give the data carved for their external extractors, e.g. `gzip`
+    /// to `gz`, in place of the extractors' own `Extractor.extension`
+    pub carved_extensions: HashMap<String, String>,
+    /// Set once the output file system is found to be full or read-only, after which all extractions fail immediately.
+    /// Clones share the same state, so it may be shared between worker threads.
+    pub output_failure: OutputFailure,
+    /// If set, extraction output is written to `<output_base>/<file name>.extracted` rather than alongside the input
+    /// file, e.g. for input files on read-only file systems; output directories may not be created outside of it.
+    /// Files already inside of `output_base`, such as previously extracted files, are extracted alongside themselves.
+    pub output_base: Option<path::PathBuf>,
+    /// If set, regions of at least this many bytes that are not covered by any signature are reported as gaps
+    /// (see `Binwalk::find_gaps`), and carved to `<file_path>.extracted/gap_<start>_<end>.bin` when extracting
+    /// (see `carve_gap`)
+    pub gap_min_size: Option<usize>,
+    /// What to do if an extraction's output directory already exists; by default, it is overwritten
+    pub existing_output: ExistingOutputPolicy,
+    /// How signatures found within the data of other signatures are handled; by default, they are discarded
+    pub nested_signatures: NestedSignaturePolicy,
+    /// If set, the carved data of each extraction is also hashed with this algorithm, e.g. for compatibility with
+    /// databases keyed by MD5 or SHA-1 hashes; see `ExtractionResult.carved_digest`. SHA-256 hashes are always computed.
+    pub digest_algorithm: Option<DigestAlgorithm>,
+    /// If set, the files carved by `carve` and `carve_gap` start at the signature offset rounded down to a multiple of
+    /// this many bytes, e.g. for tools that expect sector aligned data; the input files of extractors are not affected
+    pub carve_alignment: Option<usize>,
+    /// If set, the files carved by `carve` and `carve_gap` are padded with zeros to a multiple of this many bytes
+    pub carve_padding: Option<usize>,
+    /// If set, the input files of external extractors are carved to a temporary directory inside of this directory,
+    /// rather than to the output directory, e.g. to keep carved files on fast scratch storage; extracted files are
+    /// still written to the output directory. Use `std::env::temp_dir()` to honor `TMPDIR`.
+    pub carve_directory: Option<path::PathBuf>,
+    /// If set, no new extractions are started once this time has passed, see `Binwalk::extract`; extractions already
+    /// in progress are allowed to complete, unless `kill_at_deadline` is set
+    pub run_deadline: Option<time::Instant>,
+    /// If true, external extractors still running at `run_deadline` are killed, and their extractions fail with
+    /// `ExtractionError::Timeout`
+    pub kill_at_deadline: bool,
+}
+
+impl ExtractionConfig {
+    /// Returns true if `run_deadline` is set and has passed.
     ///
     /// ## Example
     ///
     /// ```
-    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_312_0() -> Result<(), Box<dyn std::error::Error>> {
-    /// use binwalk::extractors::common::Chroot;
-    ///
-    /// let chroot_dir = std::path::Path::new("tests")
-    ///     .join("binwalk_unit_tests")
-    ///     .display()
-    ///     .to_string();
+    /// use binwalk::extractors::common::ExtractionConfig;
+    /// use std::time::{Duration, Instant};
     ///
-    /// let dev_major: usize = 1;
-    /// let dev_minor: usize = 2;
-    /// let file_name = "char_device";
+    /// let mut config = ExtractionConfig::default();
+    /// assert!(!config.deadline_passed());
     ///
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// let chroot = Chroot::new(Some(&chroot_dir));
+    /// config.run_deadline = Some(Instant::now() + Duration::from_secs(3600));
+    /// assert!(!config.deadline_passed());
     ///
-    /// assert_eq!(chroot.create_character_device(file_name, dev_major, dev_minor), true);
-    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, "c 1 2");
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// # Ok(())
-    /// # } _doctest_main_src_extractors_common_rs_312_0(); }
+    /// config.run_deadline = Some(Instant::now());
+    /// assert!(config.deadline_passed());
     /// ```
-    pub fn create_character_device(
-        &self,
-        file_path: impl Into<String>,
-        major: usize,
-        minor: usize,
-    ) -> bool {
-        self.create_device(file_path, "c", major, minor)
+    pub fn deadline_passed(&self) -> bool {
+        self.run_deadline
+            .is_some_and(|deadline| time::Instant::now() >= deadline)
     }
 
-    /// Creates a block device file in the chroot directory.
-    ///
-    /// Note that this does *not* create a real block device, just a regular file containing the text `b <major> <minor>`.
+    /// Returns true if the file's extension or MIME type is listed in `terminal_types`, in which case the file is
+    /// assumed to contain nothing more to extract, and need not be analyzed. Extensions are matched case-insensitively.
     ///
     /// ## Example
     ///
     /// ```
-    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_345_0() -> Result<(), Box<dyn std::error::Error>> {
-    /// use binwalk::extractors::common::Chroot;
+    /// use binwalk::extractors::common::ExtractionConfig;
     ///
-    /// let chroot_dir = std::path::Path::new("tests")
-    ///     .join("binwalk_unit_tests")
-    ///     .display()
-    ///     .to_string();
-    ///
-    /// let dev_major: usize = 1;
-    /// let dev_minor: usize = 2;
-    /// let file_name = "block_device";
+    /// let config = ExtractionConfig {
+    ///     terminal_types: vec!["txt".to_string(), "application/*".to_string()],
+    ///     ..Default::default()
+    /// };
     ///
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// let chroot = Chroot::new(Some(&chroot_dir));
-    ///
-    /// assert_eq!(chroot.create_block_device(file_name, dev_major, dev_minor), true);
-    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, "b 1 2");
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// # Ok(())
-    /// # } _doctest_main_src_extractors_common_rs_345_0(); }
+    /// assert!(config.is_terminal_file("extracted/notes.TXT"));
+    /// assert!(config.is_terminal_file("tests/inputs/gzip.bin"));
+    /// assert!(!config.is_terminal_file("tests/inputs/ntfs.bin"));
     /// ```
-    pub fn create_block_device(
-        &self,
-        file_path: impl Into<String>,
-        major: usize,
-        minor: usize,
-    ) -> bool {
-        self.create_device(file_path, "b", major, minor)
+    pub fn is_terminal_file(&self, file_path: &str) -> bool {
+        // Number of bytes read from the start of the file to guess its MIME type
+        const FILE_TYPE_READ_SIZE: u64 = 8192;
+
+        let extension = path::Path::new(file_path)
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase());
+
+        if let Some(extension) = &extension
+            && self
+                .terminal_types
+                .iter()
+                .any(|terminal_type| terminal_type.to_lowercase() == *extension)
+        {
+            return true;
+        }
+
+        // Only read the file if there are MIME types to match
+        if !self
+            .terminal_types
+            .iter()
+            .any(|terminal_type| terminal_type.contains('/'))
+        {
+            return false;
+        }
+
+        let mut file_data = Vec::new();
+        let file_type = match fs::File::open(file_path) {
+            Err(_) => None,
+            Ok(file) => match file.take(FILE_TYPE_READ_SIZE).read_to_end(&mut file_data) {
+                Err(_) => None,
+                Ok(_) => guess_file_type(&file_data),
+            },
+        };
+
+        let Some(file_type) = file_type else {
+            return false;
+        };
+
+        self.terminal_types
+            .iter()
+            .any(|terminal_type| match terminal_type.strip_suffix("/*") {
+                Some(media_type) => file_type
+                    .split_once('/')
+                    .is_some_and(|(file_media_type, _)| file_media_type == media_type),
+                None => *terminal_type == file_type,
+            })
     }
+}
 
-    /// Creates a fifo file in the chroot directory.
-    ///
-    /// Note that this does *not* create a real fifo, just a regular file containing the text `fifo`.
-    ///
-    /// ## Example
-    ///
-    /// ```
-    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_377_0() -> Result<(), Box<dyn std::error::Error>> {
-    /// use binwalk::extractors::common::Chroot;
-    ///
-    /// let chroot_dir = std::path::Path::new("tests")
-    ///     .join("binwalk_unit_tests")
-    ///     .display()
-    ///     .to_string();
-    ///
-    /// let file_name = "fifo_file";
-    ///
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// let chroot = Chroot::new(Some(&chroot_dir));
-    ///
-    /// assert_eq!(chroot.create_fifo(file_name), true);
-    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, "fifo");
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// # Ok(())
-    /// # } _doctest_main_src_extractors_common_rs_377_0(); }
-    /// ```
-    pub fn create_fifo(&self, file_path: impl Into<String>) -> bool {
-        self.create_file(file_path, b"fifo")
+/// Describes all extractions performed on a single file, see `write_manifest`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtractionManifest {
+    /// Path to the file that the data was extracted from
+    pub file: String,
+    /// One entry per extraction, ordered by offset
+    pub extractions: Vec<ManifestEntry>,
+}
+
+/// Describes a single extraction in an `ExtractionManifest`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Offset of the signature data in the file
+    pub offset: usize,
+    /// Size of the signature data
+    pub size: usize,
+    /// Name of the signature
+    pub signature: String,
+    /// Name of the extractor that was run
+    pub extractor: String,
+    /// True if the extraction succeeded
+    pub success: bool,
+    /// Extraction output directory, relative to the `<file>.extracted` directory
+    pub output_directory: String,
+    /// Hex encoded SHA-256 of the signature data
+    pub sha256: Option<String>,
+    /// True if the extracted files should not be recursed into
+    #[serde(default)]
+    pub do_not_recurse: bool,
+}
+
+/// A predicate consulted before extracting each signature; signatures for which it returns false are still reported,
+/// but not extracted. Clones share the same predicate, so it may be shared between worker threads.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::extractors::common::ExtractionPredicate;
+/// use binwalk::signatures::common::SignatureResult;
+///
+/// // Never extract anything over 500MB
+/// let predicate = ExtractionPredicate::new(|signature| signature.size <= 500 * 1024 * 1024);
+///
+/// let signature = SignatureResult {
+///     size: 1024 * 1024 * 1024,
+///     ..Default::default()
+/// };
+///
+/// assert!(!predicate.allows(&signature));
+/// assert!(predicate.allows(&SignatureResult::default()));
+/// ```
+#[derive(Clone)]
+pub struct ExtractionPredicate {
+    predicate: Arc<dyn Fn(&SignatureResult) -> bool + Send + Sync>,
+}
+
+impl ExtractionPredicate {
+    /// Creates a new extraction predicate
+    #[allow(dead_code)]
+    pub fn new(
+        predicate: impl Fn(&SignatureResult) -> bool + Send + Sync + 'static,
+    ) -> ExtractionPredicate {
+        ExtractionPredicate {
+            predicate: Arc::new(predicate),
+        }
     }
 
-    /// Creates a socket file in the chroot directory.
-    ///
-    /// Note that this does *not* create a real socket, just a regular file containing the text `socket`.
-    ///
-    /// ## Example
-    ///
-    /// ```
-    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_401_0() -> Result<(), Box<dyn std::error::Error>> {
-    /// use binwalk::extractors::common::Chroot;
-    ///
-    /// let chroot_dir = std::path::Path::new("tests")
-    ///     .join("binwalk_unit_tests")
-    ///     .display()
-    ///     .to_string();
-    ///
-    /// let file_name = "socket_file";
-    ///
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// let chroot = Chroot::new(Some(&chroot_dir));
-    ///
-    /// assert_eq!(chroot.create_socket(file_name), true);
-    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, "socket");
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// # Ok(())
-    /// # } _doctest_main_src_extractors_common_rs_401_0(); }
-    /// ```
-    pub fn create_socket(&self, file_path: impl Into<String>) -> bool {
-        self.create_file(file_path, b"socket")
+    /// Returns true if the signature may be extracted
+    pub fn allows(&self, signature: &SignatureResult) -> bool {
+        (self.predicate)(signature)
     }
+}
 
-    /// Append the provided data to the specified file in the chroot directory.
-    ///
-    /// If the specified file does not exist, it will be created.
+impl std::fmt::Debug for ExtractionPredicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExtractionPredicate")
+            .finish_non_exhaustive()
+    }
+}
+
+/// Restricts which extractors are eligible to run, by name. Extractors may be referred to either by the name of the
+/// signature they extract, or, for external extractors, by the name of the external command.
+///
+/// If `include` is not empty, only extractors matching one of its names are eligible.
+/// Extractors matching any name in `exclude` are never eligible; exclusions take precedence over inclusions.
+#[derive(Debug, Default, Clone)]
+pub struct ExtractorFilter {
+    /// Names of extractors that are eligible to run; if empty, all extractors are eligible
+    pub include: Vec<String>,
+    /// Names of extractors that are not eligible to run
+    pub exclude: Vec<String>,
+}
+
+impl ExtractorFilter {
+    /// Returns true if the extractor for the specified signature is eligible to run.
     ///
     /// ## Example
     ///
     /// ```
-    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_426_0() -> Result<(), Box<dyn std::error::Error>> {
-    /// use binwalk::extractors::common::Chroot;
-    ///
-    /// let chroot_dir = std::path::Path::new("tests")
-    ///     .join("binwalk_unit_tests")
-    ///     .display()
-    ///     .to_string();
+    /// use binwalk::extractors::common::{Extractor, ExtractorFilter, ExtractorType};
     ///
-    /// let file_data: &[u8] = b"foobar";
-    /// let file_name = "append.txt";
+    /// let sevenzip = Extractor {
+    ///     utility: ExtractorType::External("7zz".to_string()),
+    ///     ..Default::default()
+    /// };
     ///
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// let chroot = Chroot::new(Some(&chroot_dir));
+    /// let filter = ExtractorFilter {
+    ///     include: vec!["xz".to_string(), "zip".to_string()],
+    ///     exclude: vec!["7zz".to_string()],
+    /// };
     ///
-    /// assert_eq!(chroot.append_to_file(file_name, file_data), true);
-    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, std::str::from_utf8(file_data)?);
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// # Ok(())
-    /// # } _doctest_main_src_extractors_common_rs_426_0(); }
+    /// assert!(filter.allows("xz", &Extractor::default()));
+    /// assert!(!filter.allows("gzip", &Extractor::default()));
+    /// assert!(!filter.allows("zip", &sevenzip));
     /// ```
-    pub fn append_to_file(&self, file_path: impl Into<String>, data: &[u8]) -> bool {
-        let safe_file_path: String = self.chrooted_path(file_path);
+    pub fn allows(&self, signature_name: &str, extractor: &Extractor) -> bool {
+        let mut names: Vec<&str> = vec![signature_name];
 
-        if !self.is_symlink(&safe_file_path) {
-            match fs::OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(safe_file_path.clone())
-            {
-                Err(e) => {
-                    error!("Failed to open file '{safe_file_path}' for appending: {e}");
-                }
-                Ok(mut fp) => match fp.write(data) {
-                    Err(e) => {
-                        error!("Failed to append to file '{safe_file_path}': {e}");
-                    }
-                    Ok(_) => {
-                        return true;
-                    }
-                },
-            }
-        } else {
-            error!("Attempted to append data to a symlink: {safe_file_path}");
+        if let ExtractorType::External(command) = &extractor.utility {
+            names.push(command);
         }
 
-        false
+        let is_match = |filter_names: &[String]| {
+            names
+                .iter()
+                .any(|name| filter_names.iter().any(|f| f == name))
+        };
+
+        if is_match(&self.exclude) {
+            return false;
+        }
+
+        self.include.is_empty() || is_match(&self.include)
     }
+}
 
-    /// Creates a directory in the chroot directory.
-    ///
-    /// Equivalent to mkdir -p.
-    ///
-    /// ## Example
-    ///
-    /// ```
-    /// use binwalk::extractors::common::Chroot;
-    ///
-    /// let chroot_dir = std::path::Path::new("tests")
-    ///     .join("binwalk_unit_tests")
-    ///     .display()
-    ///     .to_string();
-    ///
-    /// let dir_name = "my_directory";
-    ///
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// let chroot = Chroot::new(Some(&chroot_dir));
-    ///
-    /// assert_eq!(chroot.create_directory(dir_name), true);
-    /// assert_eq!(std::path::Path::new(&chroot_dir).join(dir_name).exists(), true);
-    /// # std::fs::remove_dir_all(&chroot_dir);
-    /// ```
-    pub fn create_directory(&self, dir_path: impl Into<String>) -> bool {
-        let safe_dir_path: String = self.chrooted_path(dir_path);
+/// Run-scoped cache of successful extraction results, keyed by the SHA-256 of the carved data.
+/// Clones share the same underlying cache, so it may be shared between worker threads.
+#[derive(Debug, Default, Clone)]
+pub struct ExtractionCache {
+    results: Arc<Mutex<HashMap<String, ExtractionResult>>>,
+}
 
-        match fs::create_dir_all(safe_dir_path.clone()) {
-            Ok(_) => {
-                return true;
-            }
+impl ExtractionCache {
+    /// Returns the cached extraction result for the given SHA-256, if any
+    pub fn get(&self, sha256: &str) -> Option<ExtractionResult> {
+        match self.results.lock() {
             Err(e) => {
-                error!("Failed to create output directory {safe_dir_path}: {e}");
+                error!("Failed to lock extraction cache: {e}");
+                None
             }
+            Ok(results) => results.get(sha256).cloned(),
         }
+    }
 
-        false
+    /// Adds an extraction result to the cache; existing entries are never replaced
+    pub fn insert(&self, sha256: &str, result: &ExtractionResult) {
+        match self.results.lock() {
+            Err(e) => error!("Failed to lock extraction cache: {e}"),
+            Ok(mut results) => {
+                results
+                    .entry(sha256.to_string())
+                    .or_insert_with(|| result.clone());
+            }
+        }
     }
+}
 
-    /// Delete a directory in the chroot directory.
+/// Records that the output file system has become full or read-only, at which point there is no point in attempting
+/// any further extractions. Clones share the same state, so it may be shared between worker threads.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::extractors::common::{ExtractionError, OutputFailure};
+///
+/// let output_failure = OutputFailure::default();
+///
+/// // Other errors are not recorded
+/// output_failure.record(&ExtractionError::NonZeroExit(1));
+/// assert_eq!(output_failure.get(), None);
+///
+/// output_failure.record(&ExtractionError::OutputUnavailable("No space left on device".to_string()));
+/// assert!(matches!(output_failure.get(), Some(ExtractionError::OutputUnavailable(_))));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct OutputFailure {
+    error: Arc<Mutex<Option<ExtractionError>>>,
+}
+
+impl OutputFailure {
+    /// Returns the error that made the output file system unavailable, if any
+    pub fn get(&self) -> Option<ExtractionError> {
+        match self.error.lock() {
+            Err(e) => {
+                error!("Failed to lock output failure state: {e}");
+                None
+            }
+            Ok(error) => error.clone(),
+        }
+    }
+
+    /// Records the error if it means that the output file system is unavailable; only the first such error is logged
+    pub fn record(&self, extraction_error: &ExtractionError) {
+        if !matches!(extraction_error, ExtractionError::OutputUnavailable(_)) {
+            return;
+        }
+
+        match self.error.lock() {
+            Err(e) => error!("Failed to lock output failure state: {e}"),
+            Ok(mut error) => {
+                if error.is_none() {
+                    error!("Aborting extraction: {extraction_error}");
+                    *error = Some(extraction_error.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Extractors registered at run time, keyed by signature name. Registered extractors take precedence over the
+/// extractors compiled in to binwalk, allowing library users to add or override the extractor for any signature
+/// without modifying magic.rs. Clones share the same underlying registry, so it may be shared between worker threads.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::extractors::common::{
+///     Extractor, ExtractorRegistry, ExtractorType, SOURCE_FILE_PLACEHOLDER,
+/// };
+///
+/// let registry = ExtractorRegistry::default();
+///
+/// registry.register_extractor(
+///     "squashfs",
+///     Extractor {
+///         utility: ExtractorType::External("site-unsquashfs".to_string()),
+///         extension: "sqsh".to_string(),
+///         arguments: vec![SOURCE_FILE_PLACEHOLDER.to_string()],
+///         ..Default::default()
+///     },
+/// );
+///
+/// assert!(registry.get("squashfs").is_some());
+/// assert!(registry.get("gzip").is_none());
+///
+/// registry.unregister_extractor("squashfs");
+/// assert!(registry.get("squashfs").is_none());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ExtractorRegistry {
+    extractors: Arc<Mutex<HashMap<String, Extractor>>>,
+}
+
+impl ExtractorRegistry {
+    /// Registers an extractor for the named signature, replacing any extractor previously registered for it
+    pub fn register_extractor(&self, signature_name: &str, extractor: Extractor) {
+        match self.extractors.lock() {
+            Err(e) => error!("Failed to lock extractor registry: {e}"),
+            Ok(mut extractors) => {
+                extractors.insert(signature_name.to_string(), extractor);
+            }
+        }
+    }
+
+    /// Registers the external extractors defined in a JSON configuration file, replacing any extractors previously
+    /// registered for the same signatures. The file contains an object that maps signature names to
+    /// `ExtractorDefinition`s, e.g.:
     ///
-    /// Equivalent to rm -rf.
+    /// ```json
+    /// {
+    ///     "squashfs": {"command": "site-unsquashfs", "extension": "sqsh", "arguments": ["%e"], "timeout": 60}
+    /// }
+    /// ```
+    ///
+    /// Returns the number of extractors registered. Nothing is registered if the file cannot be read, is not valid,
+    /// or defines an extractor with an empty command.
+    pub fn register_from_file(
+        &self,
+        file_path: impl AsRef<path::Path>,
+    ) -> Result<usize, std::io::Error> {
+        let config_data = fs::read(file_path)?;
+
+        let definitions: HashMap<String, ExtractorDefinition> =
+            serde_json::from_slice(&config_data)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        if let Some((signature_name, _)) = definitions
+            .iter()
+            .find(|(_, definition)| definition.command.is_empty())
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("no command specified for the '{signature_name}' extractor"),
+            ));
+        }
+
+        for (signature_name, definition) in &definitions {
+            self.register_extractor(signature_name, definition.to_extractor());
+        }
+
+        Ok(definitions.len())
+    }
+
+    /// Removes the extractor registered for the named signature, if any; the compiled in extractor is used again
+    #[allow(dead_code)]
+    pub fn unregister_extractor(&self, signature_name: &str) {
+        match self.extractors.lock() {
+            Err(e) => error!("Failed to lock extractor registry: {e}"),
+            Ok(mut extractors) => {
+                extractors.remove(signature_name);
+            }
+        }
+    }
+
+    /// Returns the extractor registered for the named signature, if any
+    pub fn get(&self, signature_name: &str) -> Option<Extractor> {
+        match self.extractors.lock() {
+            Err(e) => {
+                error!("Failed to lock extractor registry: {e}");
+                None
+            }
+            Ok(extractors) => extractors.get(signature_name).cloned(),
+        }
+    }
+}
+
+/// Record of the output directories created while extracting a file, and the signature IDs they were created for.
+/// Clones share the same underlying record, so it may be shared between worker threads.
+#[derive(Debug, Default, Clone)]
+pub struct OutputDirectoryClaims {
+    claims: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl OutputDirectoryClaims {
+    /// Claims the output directory for the given signature ID. Returns true if the directory was not already claimed,
+    /// or was already claimed by the same signature (e.g. when extraction of a signature is retried).
+    pub fn claim(&self, output_directory: &str, signature_id: &str) -> bool {
+        match self.claims.lock() {
+            Err(e) => {
+                error!("Failed to lock output directory claims: {e}");
+                true
+            }
+            Ok(mut claims) => {
+                let claimant = claims
+                    .entry(output_directory.to_string())
+                    .or_insert_with(|| signature_id.to_string());
+                claimant == signature_id
+            }
+        }
+    }
+}
+
+/// Limits the number of concurrently running external extractor processes, so that running many extractors at once
+/// does not exhaust the process or file descriptor limits. Clones share the same limit.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::extractors::common::ProcessLimiter;
+///
+/// let limiter = ProcessLimiter::new(2);
+///
+/// let first_slot = limiter.acquire();
+/// let second_slot = limiter.acquire();
+/// assert_eq!(limiter.running(), 2);
+///
+/// // Slots are released when dropped; acquiring a third slot would block until then
+/// drop(first_slot);
+/// assert_eq!(limiter.running(), 1);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ProcessLimiter {
+    limit: usize,
+    running: Arc<(Mutex<usize>, Condvar)>,
+}
+
+impl ProcessLimiter {
+    /// Creates a limiter that allows at most `limit` concurrently running processes; a limit of 0 is unlimited
+    pub fn new(limit: usize) -> ProcessLimiter {
+        ProcessLimiter {
+            limit,
+            ..Default::default()
+        }
+    }
+
+    /// Returns the maximum number of concurrently running processes, or 0 if unlimited
+    #[allow(dead_code)]
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Returns the number of process slots currently in use
+    #[allow(dead_code)]
+    pub fn running(&self) -> usize {
+        let (running, _) = &*self.running;
+        match running.lock() {
+            Err(e) => *e.into_inner(),
+            Ok(running) => *running,
+        }
+    }
+
+    /// Blocks until a process slot is available, then returns it. The slot is released when it is dropped.
+    pub fn acquire(&self) -> ProcessSlot {
+        let (running, slot_released) = &*self.running;
+        let mut running = running.lock().unwrap_or_else(|e| e.into_inner());
+
+        if self.limit > 0 && *running >= self.limit {
+            debug!(
+                "All {} external extractor process slots are in use, waiting for one to be released",
+                self.limit
+            );
+        }
+
+        while self.limit > 0 && *running >= self.limit {
+            running = slot_released
+                .wait(running)
+                .unwrap_or_else(|e| e.into_inner());
+        }
+
+        *running += 1;
+
+        ProcessSlot {
+            limiter: self.clone(),
+        }
+    }
+}
+
+/// A process slot acquired from a `ProcessLimiter`; released when dropped
+#[derive(Debug)]
+pub struct ProcessSlot {
+    limiter: ProcessLimiter,
+}
+
+impl Drop for ProcessSlot {
+    fn drop(&mut self) {
+        let (running, slot_released) = &*self.limiter.running;
+        let mut running = running.lock().unwrap_or_else(|e| e.into_inner());
+        *running = running.saturating_sub(1);
+        slot_released.notify_one();
+    }
+}
+
+/// Describes a failed Chroot file system operation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChrootError {
+    /// The kind of error that occurred, e.g. `std::io::ErrorKind::AlreadyExists`
+    pub kind: std::io::ErrorKind,
+    /// The chrooted path that the failed operation was performed on
+    pub path: String,
+    /// A description of the error
+    pub message: String,
+}
+
+impl ChrootError {
+    fn new(kind: std::io::ErrorKind, path: &str, message: impl Into<String>) -> ChrootError {
+        ChrootError {
+            kind,
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+
+    fn from_io(e: &std::io::Error, path: &str) -> ChrootError {
+        ChrootError::new(e.kind(), path, e.to_string())
+    }
+}
+
+impl std::fmt::Display for ChrootError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for ChrootError {}
+
+impl From<ChrootError> for std::io::Error {
+    fn from(e: ChrootError) -> std::io::Error {
+        std::io::Error::new(e.kind, e.to_string())
+    }
+}
+
+/// Provides chroot-like functionality for internal extractors
+///
+/// Files written by external utilities do not go through the `Chroot` API, and so are not constrained by it; they can
+/// be sanitized after the fact with `neutralize_escaping_symlinks`, `enforce_symlink_allowlist`,
+/// `apply_absolute_symlink_policy` and `quarantine_files`.
+#[derive(Debug, Default, Clone)]
+pub struct Chroot {
+    /// The chroot directory passed to Chroot::new
+    pub chroot_directory: String,
+    /// If true, symlinks are only created if their fully resolved target path, following any other symlinks
+    /// along the way, remains inside the chroot directory. Symlinks that would escape the chroot directory
+    /// are replaced with a placeholder file containing the text `symlink -> <original target path>`.
+    pub verify_symlink_targets: bool,
+    /// If true, files are never made executable, and symlinks are never created; instead, symlinks are written as
+    /// placeholder files containing the text `symlink -> <original target path>` (see `Chroot::quarantine_files`).
+    pub quarantine: bool,
+    /// How `create_symlink` handles absolute symlink targets
+    pub absolute_symlinks: AbsoluteSymlinkPolicy,
+    /// If not empty, symlinks are only created if their target, relative to the chroot directory, lies within one of
+    /// these directories, e.g. `/bin` or `/lib`. Symlinks with any other target are replaced with a placeholder file
+    /// containing the text `symlink -> <original target path>`.
+    pub symlink_allowlist: Vec<String>,
+}
+
+impl Chroot {
+    /// Create a new chrooted instance. All file paths will be effectively chrooted in the specified directory path.
+    /// The chroot directory path will be created if it does not already exist.
+    ///
+    /// If no directory path is specified, the chroot directory will be `/`.
     ///
     /// ## Example
     ///
     /// ```
     /// use binwalk::extractors::common::Chroot;
     ///
-    /// let chroot_dir = std::path::Path::new("tests")
+    /// let chroot_dir = std::path::Path::new(&std::env::temp_dir())
     ///     .join("binwalk_unit_tests")
     ///     .display()
     ///     .to_string();
     ///
-    /// let dir_name = "my_directory";
-    ///
     /// # std::fs::remove_dir_all(&chroot_dir);
     /// let chroot = Chroot::new(Some(&chroot_dir));
     ///
-    /// assert_eq!(chroot.create_directory(dir_name), true);
-    /// assert_eq!(chroot.remove_directory(dir_name), true);
-    /// assert_eq!(chroot.remove_directory("i_dont_exist"), true);
+    /// assert_eq!(&chroot.chroot_directory, &chroot_dir);
+    /// assert_eq!(std::path::Path::new(&chroot_dir).exists(), true);
     /// # std::fs::remove_dir_all(&chroot_dir);
     /// ```
-    pub fn remove_directory(&self, dir_path: impl Into<String>) -> bool {
-        let safe_dir_path: String = self.chrooted_path(dir_path);
+    pub fn new(chroot_directory: Option<&str>) -> Chroot {
+        let mut chroot_instance = Chroot {
+            ..Default::default()
+        };
 
-        match fs::exists(safe_dir_path.clone()) {
-            Ok(dir_exists) => {
-                if !dir_exists {
-                    return true;
-                }
+        match chroot_directory {
+            None => {
+                // Default path is '/'
+                chroot_instance.chroot_directory = path::MAIN_SEPARATOR.to_string();
             }
-            Err(e) => {
-                error!("Failed to check if directory {safe_dir_path} exists: {e:?}");
-                return false;
+            Some(chroot_dir) => {
+                // Attempt to ensure that the specified path is absolute. If this fails, just use the path as given.
+                match path::absolute(chroot_dir) {
+                    Ok(pathbuf) => {
+                        chroot_instance.chroot_directory = pathbuf.display().to_string();
+                    }
+                    Err(_) => {
+                        chroot_instance.chroot_directory = chroot_dir.to_string();
+                    }
+                }
             }
         }
 
-        match fs::remove_dir_all(safe_dir_path.clone()) {
-            Ok(_) => return true,
-            Err(e) => error!("Failed to delete directory {safe_dir_path}: {e}"),
+        // Create the chroot directory if it does not exist
+        if !path::Path::new(&chroot_instance.chroot_directory).exists() {
+            match fs::create_dir_all(&chroot_instance.chroot_directory) {
+                Ok(_) => {
+                    debug!(
+                        "Created new chroot directory {}",
+                        chroot_instance.chroot_directory
+                    );
+                }
+                Err(e) => {
+                    error!(
+                        "Failed to create chroot directory {}: {}",
+                        chroot_instance.chroot_directory, e
+                    );
+                }
+            }
         }
 
-        false
+        chroot_instance
     }
 
-    /// Set executable permissions on an existing file in the chroot directory.
+    /// Joins two paths, ensuring that the final path does not traverse outside of the chroot directory.
     ///
     /// ## Example
     ///
     /// ```
     /// use binwalk::extractors::common::Chroot;
+    /// use std::path::MAIN_SEPARATOR;
     ///
-    /// let chroot_dir = std::path::Path::new("tests")
+    /// let chroot_dir = std::path::Path::new(&std::env::temp_dir())
     ///     .join("binwalk_unit_tests")
     ///     .display()
     ///     .to_string();
     ///
-    /// let file_name = "runme.exe";
-    ///
     /// # std::fs::remove_dir_all(&chroot_dir);
     /// let chroot = Chroot::new(Some(&chroot_dir));
-    /// chroot.create_file(file_name, b"AAAA");
     ///
-    /// assert_eq!(chroot.make_executable(file_name), true);
+    /// let dir_name = "etc";
+    /// let file_name = "passwd";
+    /// let abs_path = format!("{}{}{}{}", MAIN_SEPARATOR, dir_name, MAIN_SEPARATOR, file_name);
+    /// let abs_path_dir = format!("{}{}", MAIN_SEPARATOR, dir_name);
+    /// let rel_path_dir = format!("..{}..{}..{}{}", MAIN_SEPARATOR, MAIN_SEPARATOR, MAIN_SEPARATOR, dir_name);
+    /// let abs_path_file = format!("{}{}", MAIN_SEPARATOR, file_name);
+    /// let rel_path_file = format!("..{}..{}..{}{}", MAIN_SEPARATOR, MAIN_SEPARATOR, MAIN_SEPARATOR, file_name);
+    ///
+    /// let path1 = chroot.safe_path_join(&abs_path_dir, file_name);
+    /// let expected_path1 = std::path::Path::new(&chroot_dir).join(dir_name).join(file_name);
+    ///
+    /// let path2 = chroot.safe_path_join(&abs_path_dir, &rel_path_file);
+    /// let expected_path2 = std::path::Path::new(&chroot_dir).join(file_name);
+    ///
+    /// let path3 = chroot.safe_path_join(&rel_path_dir, &abs_path_file);
+    /// let expected_path3 = std::path::Path::new(&chroot_dir).join(dir_name).join(file_name);
+    ///
+    /// let path4 = chroot.safe_path_join(&chroot_dir, &abs_path);
+    /// let expected_path4 = std::path::Path::new(&chroot_dir).join(dir_name).join(file_name);
+    ///
+    /// assert_eq!(path1, expected_path1.display().to_string());
+    /// assert_eq!(path2, expected_path2.display().to_string());
+    /// assert_eq!(path3, expected_path3.display().to_string());
+    /// assert_eq!(path4, expected_path4.display().to_string());
     /// # std::fs::remove_dir_all(&chroot_dir);
     /// ```
-    #[allow(dead_code)]
-    pub fn make_executable(&self, file_path: impl Into<String>) -> bool {
-        // Make the file globally executable
-        const UNIX_EXEC_FLAG: u32 = 1;
-
-        let safe_file_path: String = self.chrooted_path(file_path);
-
-        match fs::metadata(safe_file_path.clone()) {
-            Err(e) => {
-                error!("Failed to get permissions for file {safe_file_path}: {e}");
-            }
-            Ok(_metadata) => {
-                #[cfg(unix)]
-                {
-                    let mut permissions = _metadata.permissions();
-                    let mode = permissions.mode() | UNIX_EXEC_FLAG;
-                    permissions.set_mode(mode);
+    pub fn safe_path_join(&self, path1: impl Into<String>, path2: impl Into<String>) -> String {
+        // Join and sanitize both paths; retain the leading '/' (if there is one)
+        let mut joined_path: String = self.sanitize_path(
+            &format!("{}{}{}", path1.into(), path::MAIN_SEPARATOR, path2.into()),
+            true,
+        );
 
-                    match fs::set_permissions(&safe_file_path, permissions) {
-                        Err(e) => {
-                            error!("Failed to set permissions for file {safe_file_path}: {e}");
-                        }
-                        Ok(_) => {
-                            return true;
-                        }
-                    }
-                }
-                #[cfg(windows)]
-                {
-                    return true;
-                }
-            }
+        // If the joined path does not start with the chroot directory,
+        // prepend the chroot directory to the final joined path.
+        // on Windows: If no chroot directory is specified, skip the operation
+        if cfg!(windows) && self.chroot_directory == path::MAIN_SEPARATOR.to_string() {
+            // do nothing and skip
+        } else if !joined_path.starts_with(&self.chroot_directory) {
+            joined_path = format!(
+                "{}{}{}",
+                self.chroot_directory,
+                path::MAIN_SEPARATOR,
+                joined_path
+            );
         }
 
-        false
+        self.strip_double_slash(&joined_path)
     }
 
-    /// Creates a symbolic link in the chroot directory, named `symlink_path`, which points to `target_path`.
-    ///
-    /// Note that both the symlink and target paths will be sanitized to stay in the chroot directory.
-    /// Both the target path will be converted into a path relative to the symlink file path.
+    /// Given a file path, returns a sanitized path that is chrooted inside the specified chroot directory.
     ///
     /// ## Example
     ///
     /// ```
-    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_571_0() -> Result<(), Box<dyn std::error::Error>> {
     /// use binwalk::extractors::common::Chroot;
     ///
-    /// let chroot_dir = std::path::Path::new("tests")
+    /// let chroot_dir = std::path::Path::new(&std::env::temp_dir())
     ///     .join("binwalk_unit_tests")
     ///     .display()
     ///     .to_string();
     ///
-    /// let symlink_name = "symlink";
-    /// let target_path = "target";
+    /// let file_name = "test.txt";
     ///
-    /// let expected_symlink_path = std::path::Path::new(&chroot_dir).join(symlink_name);
-    /// let expected_target_path = std::path::Path::new(&chroot_dir).join(target_path);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    /// let path = chroot.chrooted_path(file_name);
+    ///
+    /// assert_eq!(path, std::path::Path::new(&chroot_dir).join(file_name).display().to_string());
+    /// ```
+    pub fn chrooted_path(&self, file_path: impl Into<String>) -> String {
+        self.safe_path_join(file_path, "".to_string())
+    }
+
+    /// Creates a regular file in the chrooted directory and writes the provided data to it.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_213_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let file_data: &[u8] = b"foobar";
+    ///
+    /// let file_name = "created_file.txt";
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
     ///
     /// # std::fs::remove_dir_all(&chroot_dir);
     /// let chroot = Chroot::new(Some(&chroot_dir));
     ///
-    /// assert_eq!(chroot.create_symlink(symlink_name, target_path), true);
-    /// assert_eq!(std::fs::canonicalize(expected_symlink_path)?.to_str(), expected_target_path.to_str());
+    /// assert!(chroot.create_file(file_name, file_data).is_ok());
+    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, std::str::from_utf8(file_data)?);
     /// # std::fs::remove_dir_all(&chroot_dir);
     /// # Ok(())
-    /// # } _doctest_main_src_extractors_common_rs_571_0(); }
+    /// # } _doctest_main_src_extractors_common_rs_213_0(); }
     /// ```
-    pub fn create_symlink(
+    pub fn create_file(
         &self,
-        symlink_path: impl Into<String>,
-        target_path: impl Into<String>,
-    ) -> bool {
-        let target = target_path.into();
-        let symlink = symlink_path.into();
+        file_path: impl Into<String>,
+        file_data: &[u8],
+    ) -> Result<(), ChrootError> {
+        let safe_file_path: String = self.chrooted_path(file_path);
+
+        if path::Path::new(&safe_file_path).exists() {
+            error!("Failed to create file {safe_file_path}: path already exists");
+            return Err(ChrootError::new(
+                std::io::ErrorKind::AlreadyExists,
+                &safe_file_path,
+                "path already exists",
+            ));
+        }
+
+        match fs::write(safe_file_path.clone(), file_data) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                error!("Failed to write data to {safe_file_path}: {e}");
+                Err(ChrootError::from_io(&e, &safe_file_path))
+            }
+        }
+    }
+
+    /// Write data to a new sparse file; blocks of zero bytes are skipped rather than written, so that they do not
+    /// occupy any disk space on file systems that support sparse files. On other file systems, the zero bytes are
+    /// written normally. Either way, the logical size of the file is the length of the provided data.
+    ///
+    /// Useful for writing large disk images, which often contain long runs of zero bytes.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_sparse_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests_sparse")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let file_data: Vec<u8> = [vec![0; 1024 * 1024], b"AAAA".to_vec()].concat();
+    /// let file_name = "disk.img";
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    ///
+    /// assert!(chroot.create_sparse_file(file_name, &file_data).is_ok());
+    /// assert_eq!(std::fs::read(std::path::Path::new(&chroot_dir).join(file_name))?, file_data);
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// # } _doctest_main_src_extractors_common_rs_sparse_0(); }
+    /// ```
+    #[allow(dead_code)]
+    pub fn create_sparse_file(
+        &self,
+        file_path: impl Into<String>,
+        file_data: &[u8],
+    ) -> Result<(), ChrootError> {
+        // Zero runs are only skipped if they span an entire block; this is the block size of most file systems
+        const SPARSE_BLOCK_SIZE: usize = 4096;
+
+        let safe_file_path: String = self.chrooted_path(file_path);
+
+        let mut fp = match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&safe_file_path)
+        {
+            Err(e) => {
+                error!("Failed to create file {safe_file_path}: {e}");
+                return Err(ChrootError::from_io(&e, &safe_file_path));
+            }
+            Ok(fp) => fp,
+        };
+
+        for block in file_data.chunks(SPARSE_BLOCK_SIZE) {
+            // Seek past blocks of zeros, leaving a hole in the file; if seeking fails, just write the zeros
+            if block.iter().all(|byte| *byte == 0)
+                && fp.seek(SeekFrom::Current(block.len() as i64)).is_ok()
+            {
+                continue;
+            }
+
+            if let Err(e) = fp.write_all(block) {
+                error!("Failed to write data to {safe_file_path}: {e}");
+                return Err(ChrootError::from_io(&e, &safe_file_path));
+            }
+        }
+
+        // Seeking past the end of the file does not change the file size, so trailing holes must be allocated explicitly
+        if let Err(e) = fp.set_len(file_data.len() as u64) {
+            error!("Failed to set the size of {safe_file_path}: {e}");
+            return Err(ChrootError::from_io(&e, &safe_file_path));
+        }
+
+        Ok(())
+    }
+
+    /// Carve data and write it to a new file.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_255_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// const CARVE_SIZE: usize = 6;
+    ///
+    /// let data: &[u8] = b"foobarJUNK";
+    ///
+    /// let file_name = "carved_file.txt";
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    ///
+    /// assert!(chroot.carve_file(file_name, data, 0, CARVE_SIZE).is_ok());
+    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, std::str::from_utf8(&data[0..CARVE_SIZE])?);
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// } _doctest_main_src_extractors_common_rs_255_0(); }
+    /// ```
+    pub fn carve_file(
+        &self,
+        file_path: impl Into<String>,
+        data: &[u8],
+        start: usize,
+        size: usize,
+    ) -> Result<(), ChrootError> {
+        match data.get(start..start + size) {
+            Some(file_data) => self.create_file(file_path, file_data),
+            None => {
+                let safe_file_path: String = self.chrooted_path(file_path);
+                error!("Failed to create file {safe_file_path}: data offset/size are invalid");
+                Err(ChrootError::new(
+                    std::io::ErrorKind::InvalidInput,
+                    &safe_file_path,
+                    "data offset/size are invalid",
+                ))
+            }
+        }
+    }
+
+    /// Creates a device file in the chroot directory.
+    ///
+    /// Note that this does *not* create a real device file, just a regular file containing the device file info.
+    fn create_device(
+        &self,
+        file_path: impl Into<String>,
+        device_type: &str,
+        major: usize,
+        minor: usize,
+    ) -> Result<(), ChrootError> {
+        let device_file_contents: String = format!("{device_type} {major} {minor}");
+        self.create_file(file_path, &device_file_contents.clone().into_bytes())
+    }
+
+    /// Creates a character device file in the chroot directory.
+    ///
+    /// Note that this does *not* create a real character device, just a regular file containing the text `c <major> <minor>`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_312_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let dev_major: usize = 1;
+    /// let dev_minor: usize = 2;
+    /// let file_name = "char_device";
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    ///
+    /// assert!(chroot.create_character_device(file_name, dev_major, dev_minor).is_ok());
+    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, "c 1 2");
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// # } _doctest_main_src_extractors_common_rs_312_0(); }
+    /// ```
+    pub fn create_character_device(
+        &self,
+        file_path: impl Into<String>,
+        major: usize,
+        minor: usize,
+    ) -> Result<(), ChrootError> {
+        self.create_device(file_path, "c", major, minor)
+    }
+
+    /// Creates a block device file in the chroot directory.
+    ///
+    /// Note that this does *not* create a real block device, just a regular file containing the text `b <major> <minor>`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_345_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let dev_major: usize = 1;
+    /// let dev_minor: usize = 2;
+    /// let file_name = "block_device";
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    ///
+    /// assert!(chroot.create_block_device(file_name, dev_major, dev_minor).is_ok());
+    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, "b 1 2");
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// # } _doctest_main_src_extractors_common_rs_345_0(); }
+    /// ```
+    pub fn create_block_device(
+        &self,
+        file_path: impl Into<String>,
+        major: usize,
+        minor: usize,
+    ) -> Result<(), ChrootError> {
+        self.create_device(file_path, "b", major, minor)
+    }
+
+    /// Creates a fifo file in the chroot directory.
+    ///
+    /// Note that this does *not* create a real fifo, just a regular file containing the text `fifo`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_377_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let file_name = "fifo_file";
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    ///
+    /// assert!(chroot.create_fifo(file_name).is_ok());
+    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, "fifo");
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// # } _doctest_main_src_extractors_common_rs_377_0(); }
+    /// ```
+    pub fn create_fifo(&self, file_path: impl Into<String>) -> Result<(), ChrootError> {
+        self.create_file(file_path, b"fifo")
+    }
+
+    /// Creates a socket file in the chroot directory.
+    ///
+    /// Note that this does *not* create a real socket, just a regular file containing the text `socket`.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_401_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let file_name = "socket_file";
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    ///
+    /// assert!(chroot.create_socket(file_name).is_ok());
+    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, "socket");
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// # } _doctest_main_src_extractors_common_rs_401_0(); }
+    /// ```
+    pub fn create_socket(&self, file_path: impl Into<String>) -> Result<(), ChrootError> {
+        self.create_file(file_path, b"socket")
+    }
+
+    /// Append the provided data to the specified file in the chroot directory.
+    ///
+    /// If the specified file does not exist, it will be created.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_426_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let file_data: &[u8] = b"foobar";
+    /// let file_name = "append.txt";
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    ///
+    /// assert!(chroot.append_to_file(file_name, file_data).is_ok());
+    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join(file_name))?, std::str::from_utf8(file_data)?);
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// # } _doctest_main_src_extractors_common_rs_426_0(); }
+    /// ```
+    pub fn append_to_file(
+        &self,
+        file_path: impl Into<String>,
+        data: &[u8],
+    ) -> Result<(), ChrootError> {
+        let safe_file_path: String = self.chrooted_path(file_path);
+
+        if self.is_symlink(&safe_file_path) {
+            error!("Attempted to append data to a symlink: {safe_file_path}");
+            return Err(ChrootError::new(
+                std::io::ErrorKind::InvalidInput,
+                &safe_file_path,
+                "attempted to append data to a symlink",
+            ));
+        }
+
+        match fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(safe_file_path.clone())
+        {
+            Err(e) => {
+                error!("Failed to open file '{safe_file_path}' for appending: {e}");
+                Err(ChrootError::from_io(&e, &safe_file_path))
+            }
+            Ok(mut fp) => match fp.write(data) {
+                Err(e) => {
+                    error!("Failed to append to file '{safe_file_path}': {e}");
+                    Err(ChrootError::from_io(&e, &safe_file_path))
+                }
+                Ok(_) => Ok(()),
+            },
+        }
+    }
+
+    /// Creates a directory in the chroot directory.
+    ///
+    /// Equivalent to mkdir -p.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let dir_name = "my_directory";
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    ///
+    /// assert!(chroot.create_directory(dir_name).is_ok());
+    /// assert_eq!(std::path::Path::new(&chroot_dir).join(dir_name).exists(), true);
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// ```
+    pub fn create_directory(&self, dir_path: impl Into<String>) -> Result<(), ChrootError> {
+        let safe_dir_path: String = self.chrooted_path(dir_path);
+
+        match fs::create_dir_all(safe_dir_path.clone()) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                error!("Failed to create output directory {safe_dir_path}: {e}");
+                Err(ChrootError::from_io(&e, &safe_dir_path))
+            }
+        }
+    }
+
+    /// Delete a directory in the chroot directory.
+    ///
+    /// Equivalent to rm -rf.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let dir_name = "my_directory";
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    ///
+    /// assert!(chroot.create_directory(dir_name).is_ok());
+    /// assert!(chroot.remove_directory(dir_name).is_ok());
+    /// assert!(chroot.remove_directory("i_dont_exist").is_ok());
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// ```
+    pub fn remove_directory(&self, dir_path: impl Into<String>) -> Result<(), ChrootError> {
+        let safe_dir_path: String = self.chrooted_path(dir_path);
+
+        match fs::exists(safe_dir_path.clone()) {
+            Ok(dir_exists) => {
+                if !dir_exists {
+                    return Ok(());
+                }
+            }
+            Err(e) => {
+                error!("Failed to check if directory {safe_dir_path} exists: {e:?}");
+                return Err(ChrootError::from_io(&e, &safe_dir_path));
+            }
+        }
+
+        match fs::remove_dir_all(safe_dir_path.clone()) {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                error!("Failed to delete directory {safe_dir_path}: {e}");
+                Err(ChrootError::from_io(&e, &safe_dir_path))
+            }
+        }
+    }
+
+    /// Set executable permissions on an existing file in the chroot directory.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let file_name = "runme.exe";
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    /// chroot.create_file(file_name, b"AAAA").unwrap();
+    ///
+    /// assert!(chroot.make_executable(file_name).is_ok());
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// ```
+    #[allow(dead_code)]
+    pub fn make_executable(&self, file_path: impl Into<String>) -> Result<(), ChrootError> {
+        // Make the file globally executable
+        const UNIX_EXEC_FLAG: u32 = 1;
+
+        let safe_file_path: String = self.chrooted_path(file_path);
+
+        // Quarantined files are never made executable
+        if self.quarantine {
+            debug!("Not making quarantined file {safe_file_path} executable");
+            return Ok(());
+        }
+
+        match fs::metadata(safe_file_path.clone()) {
+            Err(e) => {
+                error!("Failed to get permissions for file {safe_file_path}: {e}");
+                Err(ChrootError::from_io(&e, &safe_file_path))
+            }
+            Ok(_metadata) => {
+                #[cfg(unix)]
+                {
+                    let mut permissions = _metadata.permissions();
+                    let mode = permissions.mode() | UNIX_EXEC_FLAG;
+                    permissions.set_mode(mode);
+
+                    match fs::set_permissions(&safe_file_path, permissions) {
+                        Err(e) => {
+                            error!("Failed to set permissions for file {safe_file_path}: {e}");
+                            Err(ChrootError::from_io(&e, &safe_file_path))
+                        }
+                        Ok(_) => Ok(()),
+                    }
+                }
+                #[cfg(not(unix))]
+                {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Sets the permissions of an existing file or directory in the chroot directory.
+    ///
+    /// Only the read, write and execute permission bits of `mode` are applied; setuid, setgid and sticky bits are
+    /// ignored. Symlinks are left as-is. In quarantine mode, execute permissions are never set.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let file_name = "private.txt";
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    /// chroot.create_file(file_name, b"AAAA").unwrap();
+    ///
+    /// assert!(chroot.set_permissions(file_name, 0o4600).is_ok());
+    ///
+    /// #[cfg(unix)]
+    /// {
+    ///     use std::os::unix::fs::PermissionsExt;
+    ///     let metadata = std::fs::metadata(chroot.chrooted_path(file_name)).unwrap();
+    ///     assert_eq!(metadata.permissions().mode() & 0o7777, 0o600);
+    /// }
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// ```
+    pub fn set_permissions(
+        &self,
+        file_path: impl Into<String>,
+        mode: u32,
+    ) -> Result<(), ChrootError> {
+        const PERMISSION_BITS: u32 = 0o777;
+        const EXECUTE_BITS: u32 = 0o111;
+
+        let safe_file_path: String = self.chrooted_path(file_path);
+
+        match fs::symlink_metadata(&safe_file_path) {
+            Err(e) => {
+                error!("Failed to get permissions for file {safe_file_path}: {e}");
+                Err(ChrootError::from_io(&e, &safe_file_path))
+            }
+            Ok(metadata) if metadata.is_symlink() => Ok(()),
+            Ok(_metadata) => {
+                #[cfg(unix)]
+                {
+                    let mut mode = mode & PERMISSION_BITS;
+
+                    // Quarantined files are never made executable
+                    if self.quarantine && !_metadata.is_dir() {
+                        mode &= !EXECUTE_BITS;
+                    }
+
+                    if let Err(e) =
+                        fs::set_permissions(&safe_file_path, fs::Permissions::from_mode(mode))
+                    {
+                        error!("Failed to set permissions for file {safe_file_path}: {e}");
+                        return Err(ChrootError::from_io(&e, &safe_file_path));
+                    }
+
+                    Ok(())
+                }
+                #[cfg(not(unix))]
+                {
+                    let _ = (mode, PERMISSION_BITS, EXECUTE_BITS);
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Sets the owner and group of an existing file, directory or symlink in the chroot directory.
+    ///
+    /// Changing ownership typically requires elevated privileges, so failures are only logged at debug level.
+    /// This has no effect on non-unix systems.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let file_name = "owned.txt";
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    /// chroot.create_file(file_name, b"AAAA").unwrap();
+    ///
+    /// // May fail if not running as root
+    /// let _ = chroot.set_owner(file_name, 1000, 1000);
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// ```
+    pub fn set_owner(
+        &self,
+        file_path: impl Into<String>,
+        uid: u32,
+        gid: u32,
+    ) -> Result<(), ChrootError> {
+        let safe_file_path: String = self.chrooted_path(file_path);
+
+        #[cfg(unix)]
+        {
+            if let Err(e) = unix::fs::lchown(&safe_file_path, Some(uid), Some(gid)) {
+                debug!("Failed to set owner of file {safe_file_path} to {uid}:{gid}: {e}");
+                return Err(ChrootError::from_io(&e, &safe_file_path));
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (uid, gid, safe_file_path);
+        }
+
+        Ok(())
+    }
+
+    /// Sets the modification time, in seconds since the Unix epoch, of an existing file or directory in the
+    /// chroot directory. Symlinks, device files, FIFOs and sockets are left as-is. This has no effect on non-unix systems.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let file_name = "old.txt";
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    /// chroot.create_file(file_name, b"AAAA").unwrap();
+    ///
+    /// assert!(chroot.set_modification_time(file_name, 1577934245).is_ok());
+    ///
+    /// #[cfg(unix)]
+    /// {
+    ///     let metadata = std::fs::metadata(chroot.chrooted_path(file_name)).unwrap();
+    ///     let mtime = metadata.modified().unwrap().duration_since(std::time::UNIX_EPOCH).unwrap();
+    ///     assert_eq!(mtime.as_secs(), 1577934245);
+    /// }
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// ```
+    pub fn set_modification_time(
+        &self,
+        file_path: impl Into<String>,
+        mtime: i64,
+    ) -> Result<(), ChrootError> {
+        let safe_file_path: String = self.chrooted_path(file_path);
+
+        #[cfg(unix)]
+        {
+            let modification_time = match u64::try_from(mtime) {
+                Ok(seconds) => std::time::UNIX_EPOCH + std::time::Duration::from_secs(seconds),
+                Err(_) => {
+                    std::time::UNIX_EPOCH - std::time::Duration::from_secs(mtime.unsigned_abs())
+                }
+            };
+
+            // Opening anything other than a regular file or directory may block, or have side effects
+            let set_result = fs::symlink_metadata(&safe_file_path).and_then(|metadata| {
+                if metadata.is_file() || metadata.is_dir() {
+                    fs::File::open(&safe_file_path)?.set_modified(modification_time)
+                } else {
+                    Ok(())
+                }
+            });
+
+            if let Err(e) = set_result {
+                error!("Failed to set modification time of file {safe_file_path}: {e}");
+                return Err(ChrootError::from_io(&e, &safe_file_path));
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = (mtime, safe_file_path);
+        }
+
+        Ok(())
+    }
+
+    /// Creates a symbolic link in the chroot directory, named `symlink_path`, which points to `target_path`.
+    ///
+    /// Note that both the symlink and target paths will be sanitized to stay in the chroot directory.
+    /// Both the target path will be converted into a path relative to the symlink file path.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_571_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// let symlink_name = "symlink";
+    /// let target_path = "target";
+    ///
+    /// let expected_symlink_path = std::path::Path::new(&chroot_dir).join(symlink_name);
+    /// let expected_target_path = std::path::Path::new(&chroot_dir).join(target_path);
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    ///
+    /// assert!(chroot.create_symlink(symlink_name, target_path).is_ok());
+    /// assert_eq!(std::fs::canonicalize(expected_symlink_path)?.to_str(), expected_target_path.to_str());
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// # } _doctest_main_src_extractors_common_rs_571_0(); }
+    /// ```
+    pub fn create_symlink(
+        &self,
+        symlink_path: impl Into<String>,
+        target_path: impl Into<String>,
+    ) -> Result<(), ChrootError> {
+        let target = target_path.into();
+        let symlink = symlink_path.into();
+
+        // Chroot the symlink file path and create a Path object
+        let safe_symlink = self.chrooted_path(&symlink);
+        let safe_symlink_path = path::Path::new(&safe_symlink);
+
+        // Quarantined symlinks are written as placeholder files
+        if self.quarantine {
+            return self.create_file(&symlink, format!("symlink -> {target}").as_bytes());
+        }
+
+        let target_is_absolute = target.starts_with(PATH_SEPARATORS);
+
+        if target_is_absolute && self.absolute_symlinks == AbsoluteSymlinkPolicy::RejectAbsolute {
+            warn!(
+                "Symlink {symlink} -> {target} has an absolute target, replacing with a placeholder file"
+            );
+            return self.create_file(&symlink, format!("symlink -> {target}").as_bytes());
+        }
+
+        // Normalize the symlink target path to a chrooted absolute path
+        let safe_target = if target_is_absolute
+            && self.absolute_symlinks == AbsoluteSymlinkPolicy::ChrootAbsolute
+        {
+            // If the target path is absolute, just chroot it inside the chroot directory
+            self.chrooted_path(&target)
+        } else {
+            // Get the symlink file's parent directory path
+            let relative_dir: String = match safe_symlink_path.parent() {
+                None => {
+                    // There is no parent, or parent is the root directory; assume the root directory
+                    path::MAIN_SEPARATOR.to_string()
+                }
+                Some(parent_dir) => {
+                    // Got the parent directory
+                    parent_dir.display().to_string()
+                }
+            };
+
+            // Join the target path with its relative directory, ensuring it does not traverse outside
+            // the specified chroot directory
+            self.safe_path_join(&relative_dir, &target)
+        };
+
+        // Remove the chroot directory from the target and symlink paths.
+        // This results in each being an absolute path that is relative to the chroot directory,
+        // e.g., '/my_chroot_dir/bin/busybox' -> '/bin/busybox'.
+        let mut safe_target_rel_path = self.chroot_relative_path(&safe_target);
+        let safe_symlink_rel_path = self.chroot_relative_path(&safe_symlink);
+
+        // Symlinks may be restricted to targets within the allowlisted directories
+        if !self.symlink_target_allowed(&safe_target_rel_path) {
+            warn!(
+                "Symlink {symlink} -> {target} points outside of the symlink allowlist, replacing with a placeholder file"
+            );
+            return self.create_file(&symlink, format!("symlink -> {target}").as_bytes());
+        }
+
+        // Count the number of path separators (minus the leading one) and an '../' to the target
+        // path for each; e.g., '/bin/busybox' -> '..//bin/busybox'.
+        for _i in 0..safe_symlink_rel_path.matches(path::MAIN_SEPARATOR).count() - 1 {
+            safe_target_rel_path = format!("..{}{}", path::MAIN_SEPARATOR, safe_target_rel_path);
+        }
+
+        // Add a '.' at the beginning of any paths that start with '/', e.g., '/tmp' -> './tmp'.
+        if safe_target_rel_path.starts_with(path::MAIN_SEPARATOR) {
+            safe_target_rel_path = format!(".{safe_target_rel_path}");
+        }
+
+        // Replace any instances of '//' with '/'
+        safe_target_rel_path = self.strip_double_slash(&safe_target_rel_path);
+
+        // The target path is now a safely chrooted path that is relative to the symlink file path.
+        // Ex:
+        //
+        //     Original symlink: "/my_chroot_dir/usr/sbin/ls" is a symlink to "/bin/busybox"
+        //     Safe relative symlink: "/my_chroot_dir/usr/sbin/ls" is a symlink to "./../../bin/busybox"
+        let safe_target_path = path::Path::new(&safe_target_rel_path);
+
+        #[cfg(unix)]
+        let symlink_result = unix::fs::symlink(safe_target_path, safe_symlink_path);
+
+        #[cfg(windows)]
+        let symlink_result = windows::fs::symlink_dir(safe_target_path, safe_symlink_path);
+
+        // No symlink support on other platforms; fall back to a placeholder file
+        #[cfg(not(any(unix, windows)))]
+        let symlink_result = fs::write(safe_symlink_path, format!("symlink -> {target}"));
+
+        match symlink_result {
+            Err(e) => {
+                error!("Failed to create symlink from {symlink} -> {target}: {e}");
+                Err(ChrootError::from_io(&e, &safe_symlink))
+            }
+            Ok(_) => {
+                // Previously created symlinks may cause this symlink to resolve outside of the chroot directory
+                if self.verify_symlink_targets
+                    && !self.symlink_resolves_inside_chroot(&safe_symlink)
+                {
+                    warn!(
+                        "Symlink {symlink} -> {target} resolves outside of the chroot directory, replacing with a placeholder file"
+                    );
+                    return self.replace_symlink_with_placeholder(&safe_symlink, &target);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Creates a hard link in the chroot directory, named `link_path`, which refers to the existing file `target_path`.
+    ///
+    /// Both paths are sanitized to stay in the chroot directory. If a hard link can not be created (for example, if the
+    /// file system does not support hard links), the contents of the target file are copied to the link path instead.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_hardlink_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests_hardlink")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    ///
+    /// chroot.create_file("target", b"AAAA")?;
+    ///
+    /// assert!(chroot.create_hardlink("link", "/target").is_ok());
+    /// assert_eq!(std::fs::read(std::path::Path::new(&chroot_dir).join("link"))?, b"AAAA");
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// # } _doctest_main_src_extractors_common_rs_hardlink_0(); }
+    /// ```
+    #[allow(dead_code)]
+    pub fn create_hardlink(
+        &self,
+        link_path: impl Into<String>,
+        target_path: impl Into<String>,
+    ) -> Result<(), ChrootError> {
+        let safe_link_path: String = self.chrooted_path(link_path);
+        let safe_target_path: String = self.chrooted_path(target_path);
+
+        if path::Path::new(&safe_link_path).exists() {
+            error!("Failed to create hard link {safe_link_path}: path already exists");
+            return Err(ChrootError::new(
+                std::io::ErrorKind::AlreadyExists,
+                &safe_link_path,
+                "path already exists",
+            ));
+        }
+
+        if let Err(e) = fs::hard_link(&safe_target_path, &safe_link_path) {
+            warn!(
+                "Failed to create hard link {safe_link_path} -> {safe_target_path} ({e}), copying the file instead"
+            );
+
+            if let Err(e) = fs::copy(&safe_target_path, &safe_link_path) {
+                error!("Failed to copy {safe_target_path} to {safe_link_path}: {e}");
+                return Err(ChrootError::from_io(&e, &safe_link_path));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the specified extended attributes on a file in the chroot directory. Each attribute set is logged.
+    ///
+    /// Symlinks are not followed; attributes are set on the symlink itself. Extended attributes are only supported
+    /// on Unix systems.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_xattrs_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests_xattrs")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    ///
+    /// chroot.create_file("file.txt", b"AAAA")?;
+    ///
+    /// // Not all file systems support extended attributes
+    /// if chroot.set_xattrs("file.txt", &[("user.binwalk".to_string(), b"test".to_vec())]).is_ok() {
+    ///     let file_path = std::path::Path::new(&chroot_dir).join("file.txt");
+    ///     assert_eq!(xattr::get(&file_path, "user.binwalk")?, Some(b"test".to_vec()));
+    /// }
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// # } _doctest_main_src_extractors_common_rs_xattrs_0(); }
+    /// # #[cfg(not(unix))]
+    /// # fn main() {}
+    /// ```
+    pub fn set_xattrs(
+        &self,
+        file_path: impl Into<String>,
+        attributes: &[(String, Vec<u8>)],
+    ) -> Result<(), ChrootError> {
+        let safe_file_path: String = self.chrooted_path(file_path);
+
+        if let Err(e) = fs::symlink_metadata(&safe_file_path) {
+            error!("Failed to set extended attributes on {safe_file_path}: {e}");
+            return Err(ChrootError::from_io(&e, &safe_file_path));
+        }
+
+        for (name, value) in attributes {
+            #[cfg(unix)]
+            {
+                if let Err(e) = xattr::set(&safe_file_path, name, value) {
+                    error!("Failed to set extended attribute {name} on {safe_file_path}: {e}");
+                    return Err(ChrootError::from_io(&e, &safe_file_path));
+                }
+
+                info!(
+                    "Set extended attribute {} ({} bytes) on {}",
+                    name,
+                    value.len(),
+                    safe_file_path
+                );
+            }
+
+            #[cfg(not(unix))]
+            {
+                error!(
+                    "Failed to set extended attribute {name} on {safe_file_path}: not supported"
+                );
+                return Err(ChrootError::new(
+                    std::io::ErrorKind::Unsupported,
+                    &safe_file_path,
+                    "extended attributes are not supported",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Walks the chroot directory and replaces any symlinks whose fully resolved target path lies outside of
+    /// the chroot directory with placeholder files. Returns the number of symlinks that were replaced.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_neutralize_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests_neutralize")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    ///
+    /// // Symlinks created outside of the Chroot API are not sanitized
+    /// std::os::unix::fs::symlink("../../../../../../../../etc/passwd", std::path::Path::new(&chroot_dir).join("passwd"))?;
+    ///
+    /// assert_eq!(chroot.neutralize_escaping_symlinks(), 1);
+    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join("passwd"))?, "symlink -> ../../../../../../../../etc/passwd");
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// # } _doctest_main_src_extractors_common_rs_neutralize_0(); }
+    /// # #[cfg(not(unix))]
+    /// # fn main() {}
+    /// ```
+    pub fn neutralize_escaping_symlinks(&self) -> usize {
+        let mut replaced_count: usize = 0;
+
+        // Replacing one symlink may change how others resolve, so each symlink is checked just before it is replaced
+        for (symlink, target) in self.symlinks() {
+            if !self.symlink_resolves_inside_chroot(&symlink) {
+                warn!(
+                    "Symlink {symlink} -> {target} resolves outside of {}, replacing with a placeholder file",
+                    self.chroot_directory
+                );
+
+                if self
+                    .replace_symlink_with_placeholder(&symlink, &target)
+                    .is_ok()
+                {
+                    replaced_count += 1;
+                }
+            }
+        }
+
+        replaced_count
+    }
+
+    /// Walks the chroot directory and replaces any symlinks whose target, relative to the chroot directory, lies outside
+    /// of `Chroot::symlink_allowlist` with placeholder files. Absolute targets are taken to be relative to the chroot
+    /// directory. Returns the number of symlinks that were replaced.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_allowlist_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests_allowlist")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let mut chroot = Chroot::new(Some(&chroot_dir));
+    /// chroot.symlink_allowlist = vec!["/bin".to_string()];
+    ///
+    /// std::os::unix::fs::symlink("bin/busybox", std::path::Path::new(&chroot_dir).join("sh"))?;
+    /// std::os::unix::fs::symlink("/etc/passwd", std::path::Path::new(&chroot_dir).join("passwd"))?;
+    ///
+    /// assert_eq!(chroot.enforce_symlink_allowlist(), 1);
+    /// assert!(std::fs::symlink_metadata(std::path::Path::new(&chroot_dir).join("sh"))?.is_symlink());
+    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join("passwd"))?, "symlink -> /etc/passwd");
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// # } _doctest_main_src_extractors_common_rs_allowlist_0(); }
+    /// # #[cfg(not(unix))]
+    /// # fn main() {}
+    /// ```
+    pub fn enforce_symlink_allowlist(&self) -> usize {
+        let mut replaced_count: usize = 0;
+
+        for (symlink, target) in self.symlinks() {
+            // Symlinks whose target can't be read can't be checked against the allowlist, and are only kept if there is
+            // no allowlist
+            let allowed = if target.is_empty() {
+                self.symlink_allowlist.is_empty()
+            } else {
+                let safe_target = if target.starts_with(PATH_SEPARATORS) {
+                    self.chrooted_path(&target)
+                } else {
+                    let symlink_dir = path::Path::new(&symlink)
+                        .parent()
+                        .map(|parent_dir| parent_dir.display().to_string())
+                        .unwrap_or(path::MAIN_SEPARATOR.to_string());
+                    self.safe_path_join(&symlink_dir, &target)
+                };
+
+                self.symlink_target_allowed(&self.chroot_relative_path(&safe_target))
+            };
+
+            if !allowed {
+                warn!(
+                    "Symlink {symlink} -> {target} points outside of the symlink allowlist, replacing with a placeholder file"
+                );
+
+                if self
+                    .replace_symlink_with_placeholder(&symlink, &target)
+                    .is_ok()
+                {
+                    replaced_count += 1;
+                }
+            }
+        }
+
+        replaced_count
+    }
+
+    /// Walks the chroot directory and re-creates all symlinks that have absolute targets according to
+    /// `Chroot::absolute_symlinks`. Returns the number of symlinks that were re-created.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_absolute_symlinks_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::{AbsoluteSymlinkPolicy, Chroot};
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests_absolute_symlinks")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let mut chroot = Chroot::new(Some(&chroot_dir));
+    /// chroot.absolute_symlinks = AbsoluteSymlinkPolicy::RejectAbsolute;
+    ///
+    /// std::os::unix::fs::symlink("/etc/passwd", std::path::Path::new(&chroot_dir).join("passwd"))?;
+    ///
+    /// assert_eq!(chroot.apply_absolute_symlink_policy(), 1);
+    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join("passwd"))?, "symlink -> /etc/passwd");
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// # } _doctest_main_src_extractors_common_rs_absolute_symlinks_0(); }
+    /// # #[cfg(not(unix))]
+    /// # fn main() {}
+    /// ```
+    pub fn apply_absolute_symlink_policy(&self) -> usize {
+        let mut recreated_count: usize = 0;
+
+        let absolute_symlinks = self
+            .symlinks()
+            .into_iter()
+            .filter(|(_, target)| target.starts_with(PATH_SEPARATORS));
+
+        for (symlink, target) in absolute_symlinks {
+            if let Err(e) = fs::remove_file(&symlink) {
+                error!("Failed to remove symlink {symlink}: {e}");
+                continue;
+            }
+
+            if self.create_symlink(&symlink, &target).is_ok() {
+                recreated_count += 1;
+            }
+        }
+
+        recreated_count
+    }
+
+    /// Returns the path and target path of every symlink in the chroot directory. The list is collected up front, so
+    /// that callers may safely modify the symlinks while iterating over it, and sorted by file name, so that the results
+    /// of doing so do not depend on directory order. Symlinks whose target can't be read are still returned, with an
+    /// empty target path, so that they can be sanitized along with the rest.
+    fn symlinks(&self) -> Vec<(String, String)> {
+        WalkDir::new(&self.chroot_directory)
+            .sort_by_file_name()
+            .into_iter()
+            .flatten()
+            .map(|entry| entry.path().display().to_string())
+            .filter(|entry_path| self.is_symlink(entry_path))
+            .map(|symlink| {
+                let target = fs::read_link(&symlink)
+                    .map(|target| target.display().to_string())
+                    .unwrap_or_default();
+                (symlink, target)
+            })
+            .collect()
+    }
+
+    /// Walks the chroot directory and renders all files inert: symlinks are replaced with placeholder files containing
+    /// the text `symlink -> <original target path>`, device files, FIFOs and sockets are replaced with the same
+    /// placeholder files created by `create_character_device`, `create_block_device`, `create_fifo` and
+    /// `create_socket`, and executable permissions are removed from all regular files.
+    /// Returns the number of files that were modified.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// # #[cfg(unix)]
+    /// # fn main() { #[allow(non_snake_case)] fn _doctest_main_src_extractors_common_rs_quarantine_0() -> Result<(), Box<dyn std::error::Error>> {
+    /// use binwalk::extractors::common::Chroot;
+    /// use std::os::unix::fs::PermissionsExt;
+    ///
+    /// let chroot_dir = std::path::Path::new("tests")
+    ///     .join("binwalk_unit_tests_quarantine")
+    ///     .display()
+    ///     .to_string();
+    ///
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// let chroot = Chroot::new(Some(&chroot_dir));
+    /// let script_path = std::path::Path::new(&chroot_dir).join("script.sh");
+    ///
+    /// chroot.create_file("script.sh", b"#!/bin/sh")?;
+    /// chroot.make_executable("script.sh")?;
+    /// chroot.create_symlink("link", "script.sh")?;
+    ///
+    /// assert_eq!(chroot.quarantine_files(), 2);
+    /// assert_eq!(std::fs::metadata(&script_path)?.permissions().mode() & 0o111, 0);
+    /// assert_eq!(std::fs::read_to_string(std::path::Path::new(&chroot_dir).join("link"))?, "symlink -> ./script.sh");
+    /// # std::fs::remove_dir_all(&chroot_dir);
+    /// # Ok(())
+    /// # } _doctest_main_src_extractors_common_rs_quarantine_0(); }
+    /// # #[cfg(not(unix))]
+    /// # fn main() {}
+    /// ```
+    pub fn quarantine_files(&self) -> usize {
+        let mut modified_count: usize = 0;
+
+        // Get a list of all files before modifying anything
+        let file_paths: Vec<String> = WalkDir::new(&self.chroot_directory)
+            .min_depth(1)
+            .into_iter()
+            .flatten()
+            .map(|entry| entry.path().display().to_string())
+            .collect();
+
+        for file_path in file_paths {
+            let Ok(metadata) = fs::symlink_metadata(&file_path) else {
+                continue;
+            };
+
+            let file_type = metadata.file_type();
+
+            let quarantined = if file_type.is_symlink() {
+                let target = match fs::read_link(&file_path) {
+                    Ok(target_path) => target_path.display().to_string(),
+                    Err(_) => "".to_string(),
+                };
+
+                self.replace_symlink_with_placeholder(&file_path, &target)
+                    .is_ok()
+            } else if file_type.is_file() {
+                self.remove_executable_permissions(&file_path, &metadata)
+            } else if file_type.is_dir() {
+                false
+            } else {
+                self.replace_special_file_with_placeholder(&file_path, &metadata)
+                    .is_ok()
+            };
+
+            if quarantined {
+                debug!("Quarantined {file_path}");
+                modified_count += 1;
+            }
+        }
+
+        modified_count
+    }
+
+    /// Removes all executable permissions from the specified file. Returns true if the permissions were modified.
+    fn remove_executable_permissions(&self, _file_path: &str, _metadata: &fs::Metadata) -> bool {
+        #[cfg(unix)]
+        {
+            let mut permissions = _metadata.permissions();
+            let mode = permissions.mode();
+
+            if mode & 0o111 != 0 {
+                permissions.set_mode(mode & !0o111);
+
+                match fs::set_permissions(_file_path, permissions) {
+                    Err(e) => error!("Failed to set permissions for file {_file_path}: {e}"),
+                    Ok(_) => return true,
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Replaces an existing device file, FIFO or socket with a placeholder file
+    fn replace_special_file_with_placeholder(
+        &self,
+        file_path: &str,
+        _metadata: &fs::Metadata,
+    ) -> Result<(), ChrootError> {
+        let mut placeholder = "special file".to_string();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+            let file_type = _metadata.file_type();
+            let device = _metadata.rdev();
+
+            // Linux device number encoding, see gnu_dev_major and gnu_dev_minor
+            let major = ((device >> 32) & 0xFFFFF000) | ((device >> 8) & 0xFFF);
+            let minor = ((device >> 12) & 0xFFFFFF00) | (device & 0xFF);
+
+            if file_type.is_char_device() {
+                placeholder = format!("c {major} {minor}");
+            } else if file_type.is_block_device() {
+                placeholder = format!("b {major} {minor}");
+            } else if file_type.is_fifo() {
+                placeholder = "fifo".to_string();
+            } else if file_type.is_socket() {
+                placeholder = "socket".to_string();
+            }
+        }
+
+        if let Err(e) = fs::remove_file(file_path) {
+            error!("Failed to remove special file {file_path}: {e}");
+            return Err(ChrootError::from_io(&e, file_path));
+        }
+
+        self.create_file(file_path, placeholder.as_bytes())
+    }
+
+    /// Returns true if the specified symlink, after following any and all symlinks in its target path, resolves to
+    /// a location inside the chroot directory. The target path itself need not exist.
+    fn symlink_resolves_inside_chroot(&self, symlink_path: &str) -> bool {
+        // Maximum number of symlinks to follow before giving up; same as the Linux kernel's limit
+        const MAX_SYMLINK_HOPS: usize = 40;
+
+        let symlink = path::Path::new(symlink_path);
+
+        // Both the chroot directory and the directory containing the symlink must exist
+        let (Ok(chroot_root), Some(Ok(mut resolved_path))) = (
+            fs::canonicalize(&self.chroot_directory),
+            symlink.parent().map(fs::canonicalize),
+        ) else {
+            return false;
+        };
+
+        let Ok(target) = fs::read_link(symlink) else {
+            return false;
+        };
+
+        let mut symlink_hops: usize = 0;
+        let mut pending_components: Vec<path::PathBuf> = target
+            .components()
+            .rev()
+            .map(|component| component.as_os_str().into())
+            .collect();
+
+        // Resolve the target path one component at a time, following symlinks as they are encountered
+        while let Some(component) = pending_components.pop() {
+            match component.components().next() {
+                None | Some(path::Component::CurDir) => (),
+                Some(path::Component::RootDir) | Some(path::Component::Prefix(_)) => {
+                    resolved_path = component.clone();
+                }
+                Some(path::Component::ParentDir) => {
+                    resolved_path.pop();
+                }
+                Some(path::Component::Normal(name)) => {
+                    let next_path = resolved_path.join(name);
+
+                    match fs::read_link(&next_path) {
+                        Err(_) => resolved_path = next_path,
+                        Ok(next_target) => {
+                            symlink_hops += 1;
+
+                            if symlink_hops > MAX_SYMLINK_HOPS {
+                                warn!("Too many levels of symlinks while resolving {symlink_path}");
+                                return false;
+                            }
+
+                            pending_components.extend(
+                                next_target
+                                    .components()
+                                    .rev()
+                                    .map(|component| component.as_os_str().into()),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        resolved_path.starts_with(chroot_root)
+    }
+
+    /// Replaces an existing symlink with a placeholder file containing the text `symlink -> <target>`.
+    fn replace_symlink_with_placeholder(
+        &self,
+        symlink_path: &str,
+        target: &str,
+    ) -> Result<(), ChrootError> {
+        if let Err(e) = fs::remove_file(symlink_path) {
+            error!("Failed to remove symlink {symlink_path}: {e}");
+            return Err(ChrootError::from_io(&e, symlink_path));
+        }
+
+        self.create_file(symlink_path, format!("symlink -> {target}").as_bytes())
+    }
+
+    /// Returns true if the file path is a symlink.
+    fn is_symlink(&self, file_path: &str) -> bool {
+        if let Ok(metadata) = fs::symlink_metadata(file_path) {
+            return metadata.file_type().is_symlink();
+        }
+
+        false
+    }
+
+    /// Removes the chroot directory from a chrooted path, resulting in an absolute path that is relative to the chroot
+    /// directory, e.g. `/my_chroot_dir/bin/busybox` -> `/bin/busybox`
+    fn chroot_relative_path(&self, chrooted_path: &str) -> String {
+        // Need at least one leading '/', so if the chroot directory is just '/', just use the path as-is
+        if self.chroot_directory == path::MAIN_SEPARATOR.to_string() {
+            chrooted_path.to_string()
+        } else {
+            chrooted_path.replacen(&self.chroot_directory, "", 1)
+        }
+    }
+
+    /// Returns true if the symlink target, relative to the chroot directory, is permitted by `Chroot::symlink_allowlist`
+    fn symlink_target_allowed(&self, relative_target: &str) -> bool {
+        self.symlink_allowlist.is_empty()
+            || self.symlink_allowlist.iter().any(|allowed_directory| {
+                path::Path::new(relative_target).starts_with(
+                    path::Path::new(path::MAIN_SEPARATOR_STR)
+                        .join(allowed_directory.trim_start_matches(PATH_SEPARATORS)),
+                )
+            })
+    }
+
+    /// Replace `//` with `/`. This is for asthetics only.
+    fn strip_double_slash(&self, path: &str) -> String {
+        let mut stripped_path = path.to_owned();
+        let single_slash = path::MAIN_SEPARATOR.to_string();
+        let double_slash = format!("{single_slash}{single_slash}");
+
+        while stripped_path.contains(&double_slash) {
+            stripped_path = stripped_path.replace(&double_slash, &single_slash);
+        }
+
+        stripped_path
+    }
+
+    /// Interprets a given path containing '..' directories, returning a normalized path that can not traverse
+    /// above its starting point.
+    ///
+    /// Both `/` and `\` are treated as path separators, regardless of the host operating system; the returned path
+    /// uses the host's native path separator.
+    ///
+    /// Each `..` removes the preceding path component (if any), and empty path components (e.g., from `//`
+    /// or a trailing path separator) are discarded. NULL bytes and ASCII control characters in path components
+    /// are replaced with underscores. Non-empty return values are always prefixed with a path separator.
+    ///
+    /// This is the same logic used internally by `Chroot::safe_path_join` and `Chroot::chrooted_path`, and may be
+    /// used to validate untrusted paths before writing files outside of the `Chroot` API.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use binwalk::extractors::common::Chroot;
+    /// use std::path::MAIN_SEPARATOR;
+    ///
+    /// let chroot = Chroot::default();
+    ///
+    /// let unsafe_path = format!("..{}..{}etc{}{}passwd", MAIN_SEPARATOR, MAIN_SEPARATOR, MAIN_SEPARATOR, MAIN_SEPARATOR);
+    /// let expected_path = format!("{}etc{}passwd", MAIN_SEPARATOR, MAIN_SEPARATOR);
+    ///
+    /// assert_eq!(chroot.sanitize_path(&unsafe_path, true), expected_path);
+    /// ```
+    pub fn sanitize_path(&self, file_path: &str, preserve_root_path_sep: bool) -> String {
+        const DIR_TRAVERSAL: &str = "..";
+
+        let mut exclude_indicies: Vec<usize> = vec![];
+        let mut sanitized_path: String = "".to_string();
+
+        if preserve_root_path_sep && file_path.starts_with(PATH_SEPARATORS) {
+            sanitized_path = path::MAIN_SEPARATOR.to_string();
+        }
+
+        // Split the file path on both '/' and '\'
+        let path_parts: Vec<&str> = file_path.split(PATH_SEPARATORS).collect();
+
+        // Loop through each part of the file path
+        for (i, path_part) in path_parts.iter().enumerate() {
+            // If this part of the path is '..', don't include it in the final sanitized path
+            if *path_part == DIR_TRAVERSAL {
+                exclude_indicies.push(i);
+                if i > 0 {
+                    // Walk backwards through the path parts until a non-excluded part is found, then mark that part for exclusion as well
+                    let mut j = i - 1;
+                    while j > 0 && exclude_indicies.contains(&j) {
+                        j -= 1;
+                    }
+                    exclude_indicies.push(j);
+                }
+            // If this part of the path is an empty string, don't include that either (happens if the original file path has '//' in it)
+            } else if path_part.is_empty() {
+                exclude_indicies.push(i);
+            }
+        }
+
+        // Concatenate each non-excluded part of the file path, with each part separated by '/'
+        for (i, raw_path_part) in path_parts.iter().enumerate() {
+            if !exclude_indicies.contains(&i) {
+                // Don't allow NULL bytes or control characters (newlines, escape sequences, etc) in file names
+                let path_part = self.sanitize_path_component(raw_path_part);
+
+                #[cfg(windows)]
+                {
+                    // on Windows: in the first loop run, we cannot really prepend a '\' to drive letters like 'C:'
+                    if sanitized_path.is_empty() {
+                        sanitized_path = path_part.to_string();
+                        continue;
+                    }
+                }
+                sanitized_path = format!("{}{}{}", sanitized_path, path::MAIN_SEPARATOR, path_part);
+            }
+        }
+
+        self.strip_double_slash(&sanitized_path)
+    }
+
+    /// Replaces any NULL bytes or ASCII control characters in a single path component with underscores.
+    fn sanitize_path_component(&self, path_component: &str) -> String {
+        const PLACEHOLDER_CHARACTER: char = '_';
+
+        if !path_component.chars().any(|c| c.is_ascii_control()) {
+            return path_component.to_string();
+        }
+
+        let sanitized_component: String = path_component
+            .chars()
+            .map(|c| {
+                if c.is_ascii_control() {
+                    PLACEHOLDER_CHARACTER
+                } else {
+                    c
+                }
+            })
+            .collect();
+
+        warn!(
+            "Path component contains control characters; renaming [{}] to '{}'",
+            hex::encode(path_component.as_bytes()),
+            sanitized_component
+        );
+
+        sanitized_component
+    }
+}
+
+/// A zero-copy `Read` adapter over the file data, starting at the given offset.
+///
+/// Allows internal extractors to hand the data to decompressors that expect a reader, without first copying it
+/// into a new `Vec`. The number of bytes consumed so far is tracked, so the size of the compressed data can be
+/// determined once the decompressor is done with it.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::extractors::common::SliceReader;
+/// use std::io::{Read, Seek, SeekFrom};
+///
+/// let file_data = b"headerABCDEF";
+/// let mut reader = SliceReader::new(file_data, 6);
+///
+/// let mut buffer = [0; 4];
+/// reader.read_exact(&mut buffer).unwrap();
+///
+/// assert_eq!(&buffer, b"ABCD");
+/// assert_eq!(reader.consumed(), 4);
+/// assert_eq!(reader.remaining(), b"EF");
+///
+/// // Seek positions are relative to the starting offset
+/// reader.seek(SeekFrom::Start(1)).unwrap();
+/// assert_eq!(reader.remaining(), b"BCDEF");
+///
+/// // Offsets beyond the end of the data produce an empty reader
+/// assert!(SliceReader::new(file_data, 100).remaining().is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SliceReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> SliceReader<'a> {
+    pub fn new(file_data: &'a [u8], offset: usize) -> SliceReader<'a> {
+        SliceReader {
+            data: file_data.get(offset..).unwrap_or_default(),
+            position: 0,
+        }
+    }
+
+    /// Returns the number of bytes consumed from the starting offset
+    #[allow(dead_code)]
+    pub fn consumed(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the data that has not yet been consumed
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.data[self.position..]
+    }
+}
+
+impl Read for SliceReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = std::cmp::min(buf.len(), self.data.len() - self.position);
+        buf[..n].copy_from_slice(&self.data[self.position..self.position + n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+impl BufRead for SliceReader<'_> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        Ok(self.remaining())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.position = std::cmp::min(self.position + amt, self.data.len());
+    }
+}
+
+impl Seek for SliceReader<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let new_position = match pos {
+            SeekFrom::Start(n) => Some(n as i128),
+            SeekFrom::Current(n) => Some(self.position as i128 + n as i128),
+            SeekFrom::End(n) => Some(self.data.len() as i128 + n as i128),
+        }
+        .filter(|position| *position >= 0 && *position <= self.data.len() as i128);
+
+        match new_position {
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "seek outside of the slice data",
+            )),
+            Some(position) => {
+                self.position = position as usize;
+                Ok(self.position as u64)
+            }
+        }
+    }
+}
+
+/// Describes an external extraction utility that could not be found, see `check_extractor_dependencies`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MissingDependency {
+    /// Name of the missing external command
+    pub command: String,
+    /// Names of the signatures whose extractors rely on the missing command, if known
+    pub signatures: Vec<String>,
+}
+
+/// Checks that the external commands used by the provided extractors are available in the `PATH`.
+/// Returns a list of missing commands; internal extractors are ignored.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::extractors::common::{check_extractor_dependencies, Extractor, ExtractorType};
+///
+/// let extractors = vec![
+///     Extractor {
+///         utility: ExtractorType::External("this-command-does-not-exist".to_string()),
+///         ..Default::default()
+///     },
+///     Extractor {
+///         utility: ExtractorType::External("this-command-does-not-exist".to_string()),
+///         ..Default::default()
+///     },
+/// ];
+///
+/// let missing = check_extractor_dependencies(&extractors);
+///
+/// assert_eq!(missing.len(), 1);
+/// assert_eq!(missing[0].command, "this-command-does-not-exist");
+/// ```
+pub fn check_extractor_dependencies(extractors: &[Extractor]) -> Vec<MissingDependency> {
+    let mut missing: Vec<MissingDependency> = vec![];
+
+    for extractor in extractors {
+        if let ExtractorType::External(command) = &extractor.utility {
+            if missing
+                .iter()
+                .any(|dependency| dependency.command == *command)
+            {
+                continue;
+            }
+
+            if !command_exists(command) {
+                debug!("External extractor {command} was not found");
+                missing.push(MissingDependency {
+                    command: command.clone(),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    missing
+}
+
+/// Checks that the external commands used by the provided signatures' extractors are available in the `PATH`.
+/// Returns a list of missing commands, sorted by command name, along with the names of the signatures that use them.
+pub fn check_signature_dependencies(signatures: &[Signature]) -> Vec<MissingDependency> {
+    let extractors: Vec<Extractor> = signatures
+        .iter()
+        .filter_map(|signature| signature.extractor.clone())
+        .collect();
+
+    let mut missing = check_extractor_dependencies(&extractors);
+
+    for dependency in missing.iter_mut() {
+        for signature in signatures {
+            let uses_command = signature.extractor.as_ref().is_some_and(|extractor| {
+                matches!(&extractor.utility, ExtractorType::External(command) if *command == dependency.command)
+            });
+
+            if uses_command {
+                dependency.signatures.push(signature.name.clone());
+            }
+        }
+    }
+
+    missing.sort_by(|a, b| a.command.cmp(&b.command));
+    missing
+}
+
+/// Selects the extractor to run for a signature result.
+///
+/// The preferred extractor is used if one was specified, otherwise the default extractor is used. If the selected
+/// extractor's external command is not installed, its declared alternatives are tried in order, followed by the default
+/// extractor and its alternatives. If none of these are available, the first one is returned.
+/// Extractors not allowed by the provided filter are never selected; returns None if no extractor is allowed.
+fn select_extractor(
+    signature: &SignatureResult,
+    preferred_extractor: Option<&Extractor>,
+    default_extractor: &Extractor,
+    filter: &ExtractorFilter,
+    command_paths: &HashMap<String, String>,
+) -> Option<Extractor> {
+    let mut candidates: Vec<&Extractor> = vec![];
+
+    for extractor in preferred_extractor.into_iter().chain([default_extractor]) {
+        candidates.push(extractor);
+        candidates.extend(extractor.alternatives.iter());
+    }
+
+    // Only extractors allowed by the user's extractor filter are eligible
+    candidates.retain(|extractor| filter.allows(&signature.name, extractor));
+
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let selected = candidates
+        .iter()
+        .position(|extractor| match &extractor.utility {
+            ExtractorType::External(command) => {
+                command_exists(&resolve_command(command, command_paths))
+            }
+            _ => true,
+        })
+        .unwrap_or(0);
+
+    if selected > 0 {
+        info!(
+            "Signature {}: {} is not installed, using {} instead",
+            signature.name,
+            extractor_name(candidates[0]),
+            extractor_name(candidates[selected])
+        );
+    }
+
+    Some(candidates[selected].clone())
+}
+
+/// Returns a human readable name for an extractor, used for logging
+fn extractor_name(extractor: &Extractor) -> String {
+    match &extractor.utility {
+        ExtractorType::External(command) => command.clone(),
+        ExtractorType::Internal(_) => "built-in extractor".to_string(),
+        ExtractorType::None => "none".to_string(),
+    }
+}
+
+/// Returns the executable to run for an external extractor command: the path configured for the command in
+/// `command_paths`, if any, otherwise the command itself. Relative paths are made absolute, as external extractors may
+/// be run from within their output directories.
+fn resolve_command(command: &str, command_paths: &HashMap<String, String>) -> String {
+    let command = match command_paths.get(command) {
+        None => command,
+        Some(command_path) => command_path,
+    };
+
+    // Commands that don't include a path are looked up in PATH
+    if !command.contains(PATH_SEPARATORS) {
+        return command.to_string();
+    }
+
+    match path::absolute(command) {
+        Err(e) => {
+            warn!("Failed to get absolute path for {command}: {e}");
+            command.to_string()
+        }
+        Ok(command_path) => command_path.display().to_string(),
+    }
+}
+
+/// Returns true if the specified command is an executable file, or is an executable file in one of the `PATH` directories
+fn command_exists(command: &str) -> bool {
+    // Commands that include a path are not looked up in PATH
+    if command.contains(PATH_SEPARATORS) {
+        return is_executable(path::Path::new(command));
+    }
+
+    let Some(search_path) = std::env::var_os("PATH") else {
+        return false;
+    };
+
+    // On Windows, commands may be specified without their file extension
+    let extensions: Vec<String> = if cfg!(windows) {
+        std::env::var("PATHEXT")
+            .unwrap_or(".EXE;.BAT;.CMD".to_string())
+            .split(';')
+            .map(|extension| extension.to_string())
+            .chain([String::new()])
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+
+    std::env::split_paths(&search_path).any(|directory| {
+        extensions
+            .iter()
+            .any(|extension| is_executable(&directory.join(format!("{command}{extension}"))))
+    })
+}
+
+/// Returns true if the specified path is an executable file
+fn is_executable(file_path: &path::Path) -> bool {
+    match fs::metadata(file_path) {
+        Err(_) => false,
+        Ok(metadata) => {
+            #[cfg(unix)]
+            {
+                metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+            }
+            #[cfg(not(unix))]
+            {
+                metadata.is_file()
+            }
+        }
+    }
+}
+
+/// Recursively walks a given directory and returns a list of regular non-zero size files in the given directory path.
+#[allow(dead_code)]
+pub fn get_extracted_files(directory: &str) -> Vec<String> {
+    let mut regular_files: Vec<String> = vec![];
+
+    for entry in WalkDir::new(directory).into_iter() {
+        match entry {
+            Err(_e) => continue,
+            Ok(entry) => {
+                let entry_path = entry.path();
+                // Query file metadata *without* following symlinks
+                match fs::symlink_metadata(entry_path) {
+                    Err(_e) => continue,
+                    Ok(md) => {
+                        // Only interested in non-empty, regular files
+                        if md.is_file() && md.len() > 0 {
+                            regular_files.push(entry_path.display().to_string());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    regular_files
+}
+
+/// Carves the data for the provided SignatureResult to `<file_path>.extracted/<offset>.<signature name>`, without running
+/// any extractor. The offset is formatted according to `config.output_directory_naming`.
+///
+/// The carved file is recorded as the `carved_file` and sole component of the returned ExtractionResult, which is never
+/// recursed into.
+pub fn carve(
+    file_data: &[u8],
+    file_path: &str,
+    signature: &SignatureResult,
+    config: &ExtractionConfig,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        extractor: "carve".to_string(),
+        do_not_recurse: true,
+        carved_sha256: carved_data_sha256(file_data, signature),
+        ..Default::default()
+    };
+
+    set_carved_digest(&mut result, file_data, signature, config);
+
+    if let Some(e) = config.output_failure.get() {
+        result.error = Some(e);
+        return result;
+    }
+
+    let carved_file = format!(
+        "{}.{}",
+        output_directory_path(file_path, signature, config),
+        signature.name
+    );
+
+    carve_to_file(file_data, signature, carved_file, result, config)
+}
+
+/// Carves the data for the provided gap, as returned by `Binwalk::find_gaps`, to
+/// `<file_path>.extracted/gap_<start>_<end>.bin`, where the start and end offsets are in hexadecimal.
+///
+/// As with `carve`, the carved file is recorded as the `carved_file` and sole component of the returned
+/// ExtractionResult, which is never recursed into.
+pub fn carve_gap(
+    file_data: &[u8],
+    file_path: &str,
+    gap: &SignatureResult,
+    config: &ExtractionConfig,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        extractor: "carve".to_string(),
+        do_not_recurse: true,
+        carved_sha256: carved_data_sha256(file_data, gap),
+        ..Default::default()
+    };
+
+    set_carved_digest(&mut result, file_data, gap, config);
+
+    if let Some(e) = config.output_failure.get() {
+        result.error = Some(e);
+        return result;
+    }
+
+    let carved_file = format!(
+        "{}{}gap_{:X}_{:X}.bin",
+        extraction_directory(file_path, config),
+        path::MAIN_SEPARATOR,
+        gap.offset,
+        gap.offset + gap.size
+    );
+
+    carve_to_file(file_data, gap, carved_file, result, config)
+}
+
+/// Returns the range of data to carve for the signature, if it must be adjusted to meet `ExtractionConfig.carve_alignment`
+/// and `ExtractionConfig.carve_padding`
+fn carve_adjustment(
+    signature: &SignatureResult,
+    config: &ExtractionConfig,
+) -> Option<CarveAdjustment> {
+    let alignment = config.carve_alignment.unwrap_or(1).max(1);
+    let padding = config.carve_padding.unwrap_or(1).max(1);
+
+    // If the adjusted size would overflow, the signature's data is carved as-is
+    let leading_bytes = signature.offset % alignment;
+    let carve_size = signature.size.checked_add(leading_bytes)?;
+    let padding_bytes = carve_size.checked_next_multiple_of(padding)? - carve_size;
+
+    if leading_bytes == 0 && padding_bytes == 0 {
+        return None;
+    }
+
+    Some(CarveAdjustment {
+        offset: signature.offset - leading_bytes,
+        leading_bytes,
+        padding_bytes,
+    })
+}
+
+/// Carves the signature's data to `carved_file`, creating its parent directory if necessary, and records the carved
+/// file in `result`
+fn carve_to_file(
+    file_data: &[u8],
+    signature: &SignatureResult,
+    carved_file: String,
+    mut result: ExtractionResult,
+    config: &ExtractionConfig,
+) -> ExtractionResult {
+    let output_directory = match path::Path::new(&carved_file).parent() {
+        Some(parent_directory) => parent_directory.display().to_string(),
+        None => {
+            result.error = Some(ExtractionError::Io(format!(
+                "{carved_file}: invalid carved file path"
+            )));
+            return result;
+        }
+    };
+
+    let chroot = output_chroot(config);
+
+    // Remove the carved file from any previous run
+    if path::Path::new(&carved_file).exists() {
+        let _ = fs::remove_file(&carved_file);
+    }
+
+    let adjustment = carve_adjustment(signature, config);
+    let carve_offset = adjustment.map_or(signature.offset, |adjustment| adjustment.offset);
+    let carve_size = signature.size + adjustment.map_or(0, |adjustment| adjustment.leading_bytes);
+    let padding = vec![0; adjustment.map_or(0, |adjustment| adjustment.padding_bytes)];
+
+    if let Err(e) = chroot
+        .create_directory(&output_directory)
+        .and_then(|_| chroot.carve_file(&carved_file, file_data, carve_offset, carve_size))
+        .and_then(|_| chroot.append_to_file(&carved_file, &padding))
+    {
+        let e = ExtractionError::from(e);
+        config.output_failure.record(&e);
+        result.error = Some(e);
+        return result;
+    }
+
+    debug!(
+        "Carved {} data at offset {:#X} to {}",
+        signature.name, signature.offset, carved_file
+    );
+
+    // Unless the carved data was adjusted, the carved file holds exactly the signature data
+    let carved_file_sha256 = match adjustment {
+        None => result.carved_sha256.clone(),
+        Some(_) => file_data
+            .get(carve_offset..carve_offset + carve_size)
+            .map(|carved_data| {
+                hex::encode(
+                    Sha256::new()
+                        .chain_update(carved_data)
+                        .chain_update(&padding)
+                        .finalize(),
+                )
+            }),
+    };
+
+    result.success = true;
+    result.size = Some(signature.size);
+    result.output_directory = output_directory;
+    result.carved_file = Some(carved_file.clone());
+    result.carve_adjustment = adjustment;
+    result.components.push(ExtractedComponent {
+        name: signature.name.clone(),
+        path: carved_file,
+        size: carve_size + padding.len(),
+        sha256: carved_file_sha256,
+        do_not_recurse: true,
+    });
+
+    result
+}
+
+/// Writes an extraction manifest, describing each of the provided extraction results, to
+/// `<file_path>.extracted/.binwalk_manifest.json` (see `extraction_directory`). Any existing manifest is replaced.
+///
+/// Returns the path to the manifest file.
+pub fn write_manifest(
+    file_path: &str,
+    signatures: &[SignatureResult],
+    extractions: &HashMap<String, ExtractionResult>,
+    config: &ExtractionConfig,
+) -> Result<String, ChrootError> {
+    let extraction_directory = extraction_directory(file_path, config);
+    let mut manifest = ExtractionManifest {
+        file: file_path.to_string(),
+        ..Default::default()
+    };
+
+    for signature in signatures {
+        if let Some(extraction) = extractions.get(&signature.id) {
+            let output_directory = path::Path::new(&extraction.output_directory);
+
+            manifest.extractions.push(ManifestEntry {
+                offset: signature.offset,
+                size: signature.size,
+                signature: signature.name.clone(),
+                extractor: extraction.extractor.clone(),
+                success: extraction.success,
+                output_directory: output_directory
+                    .strip_prefix(&extraction_directory)
+                    .unwrap_or(output_directory)
+                    .display()
+                    .to_string(),
+                sha256: extraction.carved_sha256.clone(),
+                do_not_recurse: extraction.do_not_recurse,
+            });
+        }
+    }
+
+    manifest.extractions.sort_by_key(|entry| entry.offset);
+
+    let manifest_json = match serde_json::to_string_pretty(&manifest) {
+        Ok(json) => json,
+        Err(e) => {
+            error!("Failed to serialize extraction manifest for {file_path}: {e}");
+            return Err(ChrootError::new(
+                std::io::ErrorKind::InvalidData,
+                &extraction_directory,
+                e.to_string(),
+            ));
+        }
+    };
+
+    let chroot = Chroot::new(Some(&extraction_directory));
+    let manifest_path = chroot.chrooted_path(MANIFEST_FILE_NAME);
+    let temp_manifest_name = format!("{MANIFEST_FILE_NAME}.tmp");
+    let temp_manifest_path = chroot.chrooted_path(&temp_manifest_name);
+
+    // Remove any partially written manifest left over from an interrupted run
+    match fs::remove_file(&temp_manifest_path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            error!("Failed to remove temporary extraction manifest {temp_manifest_path}: {e}");
+            return Err(ChrootError::from_io(&e, &temp_manifest_path));
+        }
+        _ => (),
+    }
+
+    chroot.create_file(&temp_manifest_name, manifest_json.as_bytes())?;
+
+    // The manifest is updated after every extraction; replace it atomically so that it is never left incomplete
+    if let Err(e) = fs::rename(&temp_manifest_path, &manifest_path) {
+        error!("Failed to replace extraction manifest {manifest_path}: {e}");
+        return Err(ChrootError::from_io(&e, &manifest_path));
+    }
+
+    Ok(manifest_path)
+}
+
+/// Reads the extraction manifest previously written by `write_manifest` for the specified file, if any.
+pub fn read_manifest(file_path: &str, config: &ExtractionConfig) -> Option<ExtractionManifest> {
+    let manifest_path =
+        path::Path::new(&extraction_directory(file_path, config)).join(MANIFEST_FILE_NAME);
+
+    let manifest_data = fs::read(&manifest_path).ok()?;
+
+    match serde_json::from_slice(&manifest_data) {
+        Ok(manifest) => Some(manifest),
+        Err(e) => {
+            warn!(
+                "Failed to parse extraction manifest {}: {}",
+                manifest_path.display(),
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Checks if the extraction of the provided signature was successfully completed by a previous run, as recorded in
+/// the previous run's extraction manifest. It is considered complete if the manifest records a successful extraction
+/// of the same signature at the same offset, the SHA-256 of the signature data has not changed, and the output
+/// directory still exists.
+///
+/// Returns the previous extraction's result, or None if the extraction needs to be performed (again).
+pub fn resume_extraction(
+    file_data: &[u8],
+    file_path: &str,
+    signature: &SignatureResult,
+    manifest: &ExtractionManifest,
+    config: &ExtractionConfig,
+) -> Option<ExtractionResult> {
+    let entry = manifest.extractions.iter().find(|entry| {
+        entry.success && entry.offset == signature.offset && entry.signature == signature.name
+    })?;
+
+    let carved_sha256 = carved_data_sha256(file_data, signature);
+
+    if entry.sha256.is_none() || entry.sha256 != carved_sha256 {
+        return None;
+    }
+
+    let output_directory = path::Path::new(&extraction_directory(file_path, config))
+        .join(&entry.output_directory)
+        .display()
+        .to_string();
+
+    if !path::Path::new(&output_directory).exists() {
+        return None;
+    }
+
+    info!(
+        "{} data at offset {:#X} was already extracted to {}, skipping",
+        signature.name, signature.offset, output_directory
+    );
+
+    let mut result = ExtractionResult {
+        success: true,
+        extractor: entry.extractor.clone(),
+        do_not_recurse: entry.do_not_recurse,
+        output_directory,
+        carved_sha256,
+        ..Default::default()
+    };
+
+    set_carved_digest(&mut result, file_data, signature, config);
+
+    Some(result)
+}
+
+/// Writes the output of all successful extractions of the specified file into a single tar archive,
+/// `<file_path>.tar`, then removes the `<file_path>.extracted` directory. If `ExtractionConfig.output_base` is set,
+/// the archive is written to the output base directory instead (see `extraction_directory`).
+///
+/// Paths inside the archive mirror the layout of the `<file_path>.extracted` directory, e.g. `<offset>/...`.
+/// Symlinks are archived as symlinks, not followed. The extraction manifest, if any, is included in the archive.
+///
+/// Returns the path to the archive file.
+pub fn archive_extractions(
+    file_path: &str,
+    extractions: &HashMap<String, ExtractionResult>,
+    config: &ExtractionConfig,
+) -> Result<String, ChrootError> {
+    let extraction_directory = extraction_directory(file_path, config);
+    let archive_path = format!("{}.tar", output_path(file_path, config));
+
+    // Archive entries, keyed by their path relative to the extraction directory
+    let mut entries: Vec<(path::PathBuf, path::PathBuf)> = vec![];
+
+    for extraction in extractions.values().filter(|extraction| extraction.success) {
+        // Carved files are written directly to the extraction directory; archive just the carved file
+        let extraction_path = match &extraction.carved_file {
+            Some(carved_file) => path::PathBuf::from(carved_file),
+            None => path::PathBuf::from(&extraction.output_directory),
+        };
+
+        match extraction_path.strip_prefix(&extraction_directory) {
+            Ok(relative_path)
+                if extraction_path.exists() && !relative_path.as_os_str().is_empty() =>
+            {
+                entries.push((relative_path.to_path_buf(), extraction_path.clone()));
+            }
+            _ => warn!(
+                "Not archiving {}: not in extraction directory {}",
+                extraction_path.display(),
+                extraction_directory
+            ),
+        }
+    }
+
+    let manifest_path = path::Path::new(&extraction_directory).join(MANIFEST_FILE_NAME);
+    if manifest_path.exists() {
+        entries.push((path::PathBuf::from(MANIFEST_FILE_NAME), manifest_path));
+    }
+
+    entries.sort();
+
+    let archive_file = match fs::File::create(&archive_path) {
+        Err(e) => {
+            error!("Failed to create extraction archive {archive_path}: {e}");
+            return Err(ChrootError::from_io(&e, &archive_path));
+        }
+        Ok(archive_file) => archive_file,
+    };
+
+    let mut archive = tar::Builder::new(archive_file);
+    archive.follow_symlinks(false);
+
+    for (archive_name, entry_path) in &entries {
+        let append_result = if entry_path.is_dir() {
+            archive.append_dir_all(archive_name, entry_path)
+        } else {
+            archive.append_path_with_name(entry_path, archive_name)
+        };
+
+        if let Err(e) = append_result {
+            error!(
+                "Failed to add {} to extraction archive {}: {}",
+                entry_path.display(),
+                archive_path,
+                e
+            );
+            return Err(ChrootError::from_io(&e, &archive_path));
+        }
+    }
+
+    if let Err(e) = archive.into_inner().and_then(|mut file| file.flush()) {
+        error!("Failed to write extraction archive {archive_path}: {e}");
+        return Err(ChrootError::from_io(&e, &archive_path));
+    }
+
+    // Everything of value is now in the archive; remove the loose extracted files
+    if let Err(e) = fs::remove_dir_all(&extraction_directory) {
+        error!("Failed to remove extraction directory {extraction_directory}: {e}");
+        return Err(ChrootError::from_io(&e, &extraction_directory));
+    }
+
+    info!("Archived {} extractions to {}", entries.len(), archive_path);
+
+    Ok(archive_path)
+}
+
+/// Describes the extraction that would be performed for a signature, as returned by `plan_extraction`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlannedExtraction {
+    /// Name of the extractor that would be run, as it would be reported in `ExtractionResult.extractor`
+    pub extractor: String,
+    /// The output directory the extractor would write its files to, or in carve only mode, the file that the data
+    /// would be carved to
+    pub output_path: String,
+    /// Success status of an internal extractor's dry run; None for external extractors, which are not run
+    pub dry_run_success: Option<bool>,
+    /// Size of the data consumed by an internal extractor's dry run, if known
+    pub size: Option<usize>,
+    /// Set to true if the extracted files would not be analyzed during recursive extraction
+    pub do_not_recurse: bool,
+}
+
+/// Plans the extraction of the provided SignatureResult as `execute`, or in carve only mode `carve`, would perform it,
+/// without running any external extractors or writing anything to disk.
+///
+/// Internal extractors are dry run (i.e., run without an output directory) to validate the data and determine its size.
+/// Returns None if no extractor would be run, e.g. because the signature's extractors are all excluded by
+/// `ExtractionConfig.extractor_filter`.
+pub fn plan_extraction(
+    file_data: &[u8],
+    file_path: &str,
+    signature: &SignatureResult,
+    extractor: &Option<Extractor>,
+    config: &ExtractionConfig,
+) -> Option<PlannedExtraction> {
+    if config.carve_only {
+        return Some(PlannedExtraction {
+            extractor: "carve".to_string(),
+            output_path: format!(
+                "{}.{}",
+                output_directory_path(file_path, signature, config),
+                signature.name
+            ),
+            do_not_recurse: true,
+            ..Default::default()
+        });
+    }
+
+    // Extractors are selected exactly as they are by execute
+    let registered_extractor = config.extractor_registry.get(&signature.name);
+
+    let (preferred_extractor, extractor) = match &registered_extractor {
+        None => (signature.preferred_extractor.as_ref(), extractor.as_ref()),
+        Some(registered_extractor) => (None, Some(registered_extractor)),
+    };
+
+    let extractor_definition = select_extractor(
+        signature,
+        preferred_extractor,
+        extractor?,
+        &config.extractor_filter,
+        &config.command_paths,
+    )?;
+
+    let mut planned_extraction = PlannedExtraction {
+        output_path: unique_output_directory_path(file_path, signature, config),
+        do_not_recurse: extractor_definition.do_not_recurse
+            || config.no_recurse.contains(&signature.name),
+        ..Default::default()
+    };
+
+    match &extractor_definition.utility {
+        ExtractorType::None => return None,
+        ExtractorType::Internal(func) => {
+            let dry_run = func(file_data, signature.offset, None);
+            planned_extraction.extractor = format!("{}_built_in", signature.name);
+            planned_extraction.dry_run_success = Some(dry_run.success);
+            planned_extraction.size = dry_run.size;
+        }
+        ExtractorType::External(cmd) => {
+            planned_extraction.extractor = cmd.to_string();
+        }
+    }
+
+    Some(planned_extraction)
+}
+
+/// Executes an extractor for the provided SignatureResult.
+pub fn execute(
+    file_data: &[u8],
+    file_path: &str,
+    signature: &SignatureResult,
+    extractor: &Option<Extractor>,
+    config: &ExtractionConfig,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    // If the output file system is full or read-only, every extraction would only fail again
+    if let Some(e) = config.output_failure.get() {
+        result.error = Some(e);
+        return result;
+    }
+
+    /*
+     * Extractors registered at run time take precedence over the compiled in extractor, and over any extractor
+     * preferred by the signature parser, as the preferred extractor is itself one of the compiled in extractors.
+     */
+    let registered_extractor = config.extractor_registry.get(&signature.name);
+
+    let (preferred_extractor, extractor) = match &registered_extractor {
+        None => (signature.preferred_extractor.as_ref(), extractor.as_ref()),
+        Some(registered_extractor) => {
+            debug!("Using the registered extractor for {}", signature.name);
+            (None, Some(registered_extractor))
+        }
+    };
+
+    // Select the extractor to run, honoring the user's extractor filter
+    let extractor_definition = match extractor {
+        None => None,
+        Some(default_extractor) => {
+            match select_extractor(
+                signature,
+                preferred_extractor,
+                default_extractor,
+                &config.extractor_filter,
+                &config.command_paths,
+            ) {
+                None => {
+                    info!(
+                        "Extraction of {} data at offset {:#X} skipped: extractor excluded",
+                        signature.name, signature.offset
+                    );
+                    result.skipped = true;
+                    return result;
+                }
+                Some(extractor_definition) => Some(extractor_definition),
+            }
+        }
+    };
+
+    let carved_sha256 = carved_data_sha256(file_data, signature);
+
+    // If this exact data has already been extracted, link to the previous results instead of extracting it again
+    let previous_extraction = match &carved_sha256 {
+        Some(sha256) if config.dedupe => config.extraction_cache.get(sha256),
+        _ => None,
+    };
+
+    if let Some(duplicate_result) = previous_extraction.and_then(|previous_result| {
+        link_duplicate_extraction(file_path, signature, config, previous_result)
+    }) {
+        return duplicate_result;
+    }
+
+    // Create an output directory for the extraction
+    let output_directory = match create_output_directory(file_path, signature, config) {
+        Err(e) => {
+            let e = ExtractionError::from(e);
+            config.output_failure.record(&e);
+            result.error = Some(e);
+            return result;
+        }
+        Ok(OutputDirectory::Created(output_directory)) => output_directory,
+        Ok(OutputDirectory::Existing(output_directory)) => {
+            info!(
+                "Extraction of {} data at offset {:#X} skipped: output directory {} already exists",
+                signature.name, signature.offset, output_directory
+            );
+            result.output_directory = output_directory;
+            result.success = true;
+            result.skipped = true;
+            return result;
+        }
+    };
+
+    // Make sure a defalut extractor was actually defined (this function should not be called if signature.extractor is None)
+    match &extractor_definition {
+        None => {
+            error!(
+                "Attempted to extract {} data, but no extractor is defined!",
+                signature.name
+            );
+        }
+
+        Some(extractor_definition) => {
+            let extraction_start = time::Instant::now();
+
+            // Decide how to execute the extractor depending on the extractor type
+            match &extractor_definition.utility {
+                ExtractorType::None => {
+                    error!(
+                        "Signature {}: an extractor of type None is invalid!",
+                        signature.name
+                    );
+                }
+
+                ExtractorType::Internal(func) => {
+                    debug!("Executing internal {} extractor", signature.name);
+                    // Run the internal extractor function
+                    result = func(file_data, signature.offset, Some(&output_directory));
+                    // Set the extractor name to "<signature name>_built_in"
+                    result.extractor = format!("{}_built_in", signature.name);
+                }
+
+                ExtractorType::External(cmd) => {
+                    let mut extractor = extractor_definition.clone();
+
+                    // Unless requested, don't let the external command set extended attributes
+                    if !config.preserve_xattrs || config.quarantine {
+                        extractor
+                            .arguments
+                            .splice(0..0, extractor_definition.no_xattr_arguments.clone());
+                    }
+
+                    let mut attempt: u8 = 0;
+
+                    loop {
+                        // If so configured, the extractor may not run past the run deadline
+                        if config.kill_at_deadline
+                            && let Some(deadline) = config.run_deadline
+                        {
+                            let remaining =
+                                deadline.saturating_duration_since(time::Instant::now());
+                            extractor.timeout = Some(
+                                extractor_definition
+                                    .timeout
+                                    .map_or(remaining, |timeout| timeout.min(remaining)),
+                            );
+                        }
+
+                        // Spawn the external extractor command
+                        let extraction = match spawn(
+                            file_data,
+                            file_path,
+                            &output_directory,
+                            signature,
+                            extractor.clone(),
+                            config,
+                        ) {
+                            Err(e) => {
+                                error!(
+                                    "Failed to spawn external extractor for '{}' signature: {}",
+                                    signature.name, e
+                                );
+                                Err(e)
+                            }
+
+                            // Wait for the external process to exit
+                            Ok(proc_info) => proc_wait(proc_info, config.keep_failed)
+                                .inspect_err(|_| warn!("External extractor failed!")),
+                        };
+
+                        let error = match &extraction {
+                            Err(e) => Some(e),
+                            Ok(ext_result) => ext_result.error.as_ref(),
+                        };
+
+                        // Transient failures, such as the process being killed, are retried with an exponential backoff
+                        if let Some(e) = error
+                            && e.is_transient()
+                            && attempt < extractor.retries
+                        {
+                            let backoff = RETRY_BACKOFF * 2_u32.pow(attempt.into());
+                            attempt += 1;
 
-        // Chroot the symlink file path and create a Path object
-        let safe_symlink = self.chrooted_path(&symlink);
-        let safe_symlink_path = path::Path::new(&safe_symlink);
+                            warn!(
+                                "External extractor {} for '{}' signature failed ({}), retrying in {:?} (attempt {} of {})",
+                                cmd, signature.name, e, backoff, attempt, extractor.retries
+                            );
 
-        // Normalize the symlink target path to a chrooted absolute path
-        let safe_target = if target.starts_with(path::MAIN_SEPARATOR) {
-            // If the target path is absolute, just chroot it inside the chroot directory
-            self.chrooted_path(&target)
-        } else {
-            // Get the symlink file's parent directory path
-            let relative_dir: String = match safe_symlink_path.parent() {
-                None => {
-                    // There is no parent, or parent is the root directory; assume the root directory
-                    path::MAIN_SEPARATOR.to_string()
-                }
-                Some(parent_dir) => {
-                    // Got the parent directory
-                    parent_dir.display().to_string()
+                            // Start each attempt with an empty output directory
+                            if let Err(e) = reset_output_directory(&output_directory) {
+                                result.error = Some(e.into());
+                                break;
+                            }
+
+                            thread::sleep(backoff);
+                            continue;
+                        }
+
+                        match extraction {
+                            Err(e) => result.error = Some(e),
+                            Ok(ext_result) => {
+                                result = ext_result;
+                                // Set the extractor name to the name of the extraction utility
+                                result.extractor = cmd.to_string();
+                            }
+                        }
+
+                        break;
+                    }
                 }
-            };
+            }
 
-            // Join the target path with its relative directory, ensuring it does not traverse outside
-            // the specified chroot directory
-            self.safe_path_join(&relative_dir, &target)
-        };
+            // Populate these ExtractionResult fields automatically for all extractors
+            result.duration_ms = Some(extraction_start.elapsed().as_secs_f64() * 1000.0);
+            result.output_directory = output_directory.clone();
+            result.do_not_recurse =
+                extractor_definition.do_not_recurse || config.no_recurse.contains(&signature.name);
+            result.carved_sha256 = carved_sha256.clone();
+            set_carved_digest(&mut result, file_data, signature, config);
+            resolve_components(&mut result);
 
-        // Remove the chroot directory from the target and symlink paths.
-        // This results in each being an absolute path that is relative to the chroot directory,
-        // e.g., '/my_chroot_dir/bin/busybox' -> '/bin/busybox'.
-        //
-        // Note: need at least one leading '/', so if the chroot directory is just '/', just use the string as-is.
-        let mut safe_target_rel_path = if self.chroot_directory == path::MAIN_SEPARATOR.to_string()
-        {
-            safe_target.clone()
-        } else {
-            safe_target.replacen(&self.chroot_directory, "", 1)
-        };
+            // External utilities do not use the Chroot API, and may create symlinks with absolute targets
+            if config.absolute_symlinks != AbsoluteSymlinkPolicy::ChrootAbsolute {
+                let mut chroot = Chroot::new(Some(&output_directory));
+                chroot.absolute_symlinks = config.absolute_symlinks;
+                chroot.apply_absolute_symlink_policy();
+            }
 
-        let safe_symlink_rel_path = if self.chroot_directory == path::MAIN_SEPARATOR.to_string() {
-            safe_symlink.clone()
-        } else {
-            safe_symlink.replacen(&self.chroot_directory, "", 1)
-        };
+            // Neither external utilities nor previously created symlinks are constrained by the Chroot API;
+            // make sure that no extracted symlinks resolve to a location outside of the output directory.
+            if config.verify_symlink_targets {
+                Chroot::new(Some(&output_directory)).neutralize_escaping_symlinks();
+            }
 
-        // Count the number of path separators (minus the leading one) and an '../' to the target
-        // path for each; e.g., '/bin/busybox' -> '..//bin/busybox'.
-        for _i in 0..safe_symlink_rel_path.matches(path::MAIN_SEPARATOR).count() - 1 {
-            safe_target_rel_path = format!("..{}{}", path::MAIN_SEPARATOR, safe_target_rel_path);
-        }
+            // Extracted symlinks may only point within the user's symlink allowlist, if any
+            if !config.symlink_allowlist.is_empty() {
+                let mut chroot = Chroot::new(Some(&output_directory));
+                chroot.symlink_allowlist = config.symlink_allowlist.clone();
+                chroot.enforce_symlink_allowlist();
+            }
 
-        // Add a '.' at the beginning of any paths that start with '/', e.g., '/tmp' -> './tmp'.
-        if safe_target_rel_path.starts_with(path::MAIN_SEPARATOR) {
-            safe_target_rel_path = format!(".{safe_target_rel_path}");
-        }
+            // In quarantine mode, no extracted file may be executable, nor be a symlink, device file, FIFO or socket
+            if config.quarantine {
+                Chroot::new(Some(&output_directory)).quarantine_files();
+            }
 
-        // Replace any instances of '//' with '/'
-        safe_target_rel_path = self.strip_double_slash(&safe_target_rel_path);
+            let outputs = extracted_outputs(
+                &result.output_directory,
+                &extractor_definition.ignored_outputs,
+            );
+            result.output_size = Some(outputs.size);
 
-        // The target path is now a safely chrooted path that is relative to the symlink file path.
-        // Ex:
-        //
-        //     Original symlink: "/my_chroot_dir/usr/sbin/ls" is a symlink to "/bin/busybox"
-        //     Safe relative symlink: "/my_chroot_dir/usr/sbin/ls" is a symlink to "./../../bin/busybox"
-        let safe_target_path = path::Path::new(&safe_target_rel_path);
+            // If the extractor reported success, make sure it extracted something other than just an empty file
+            if result.success && !outputs.something_extracted() {
+                result.success = false;
+                result.error = Some(ExtractionError::NothingExtracted(outputs.to_string()));
+                warn!("Extractor exited successfully, but no data was extracted: found {outputs}");
+            }
 
-        #[cfg(unix)]
-        {
-            match unix::fs::symlink(safe_target_path, safe_symlink_path) {
-                Ok(_) => true,
-                Err(e) => {
-                    error!("Failed to create symlink from {symlink} -> {target}: {e}");
-                    false
+            // If the extractor defines a validator, make sure the extracted data passes validation
+            match extractor_definition.validator {
+                Some(validator) if result.success && !validator(&result.output_directory) => {
+                    result.success = false;
+                    result.error = Some(ExtractionError::ValidationFailed);
+                    warn!("Extracted {} data failed validation", signature.name);
                 }
+                _ => (),
             }
-        }
-        #[cfg(windows)]
-        {
-            // let sym = match safe_target_path.is_dir() {
-            //     true => windows::fs::symlink_dir(safe_target_path, safe_symlink_path),
-            //     false => windows::fs::symlink_file(safe_target_path, safe_symlink_path),
-            // };
 
-            match windows::fs::symlink_dir(safe_target_path, safe_symlink_path) {
-                Ok(_) => {
-                    return true;
+            // Apply any extended attributes stored in the extracted data, if so configured
+            match extractor_definition.xattr_reader {
+                Some(xattr_reader)
+                    if result.success && config.preserve_xattrs && !config.quarantine =>
+                {
+                    restore_xattrs(file_data, signature, &output_directory, xattr_reader);
                 }
-                Err(e) => {
-                    error!(
-                        "Failed to create symlink from {} -> {}: {}",
-                        symlink, target, e
-                    );
-                    return false;
+                _ => (),
+            }
+
+            // Remember successful extractions so that identical data can be deduplicated
+            match &carved_sha256 {
+                Some(sha256) if config.dedupe && result.success => {
+                    config.extraction_cache.insert(sha256, &result);
                 }
+                _ => (),
             }
         }
     }
 
-    /// Returns true if the file path is a symlink.
-    fn is_symlink(&self, file_path: &str) -> bool {
-        if let Ok(metadata) = fs::symlink_metadata(file_path) {
-            return metadata.file_type().is_symlink();
-        }
+    // Carving the extractor's input file, or resetting its output directory between attempts, may have hit a full disk
+    if let Some(e) = &result.error {
+        config.output_failure.record(e);
+    }
 
-        false
+    // Clean up extractor's output directory if extraction failed, unless it was requested that it be kept
+    if !result.success {
+        if config.keep_failed {
+            info!("Keeping output directory {output_directory} of failed extraction");
+        } else if let Err(e) = fs::remove_dir_all(&output_directory) {
+            warn!(
+                "Failed to clean up extraction directory {output_directory} after extraction failure: {e}"
+            );
+        }
     }
 
-    /// Replace `//` with `/`. This is for asthetics only.
-    fn strip_double_slash(&self, path: &str) -> String {
-        let mut stripped_path = path.to_owned();
-        let single_slash = path::MAIN_SEPARATOR.to_string();
-        let double_slash = format!("{single_slash}{single_slash}");
+    result
+}
 
-        while stripped_path.contains(&double_slash) {
-            stripped_path = stripped_path.replace(&double_slash, &single_slash);
+/// Spawn an external extractor process.
+fn spawn(
+    file_data: &[u8],
+    file_path: &str,
+    output_directory: &str,
+    signature: &SignatureResult,
+    mut extractor: Extractor,
+    config: &ExtractionConfig,
+) -> Result<ProcInfo, ExtractionError> {
+    let chroot = Chroot::new(None);
+    let capture_output = config.capture_output;
+
+    // This function *only* handles execution of external extraction utilities; internal extractors must be invoked directly
+    let command = match &extractor.utility {
+        ExtractorType::External(cmd) => resolve_command(cmd, &config.command_paths),
+        ExtractorType::Internal(_ext) => {
+            error!("Tried to run an internal extractor as an external command!");
+            return Err(ExtractionError::Spawn);
+        }
+        ExtractorType::None => {
+            error!("An extractor command was defined, but is set to None!");
+            return Err(ExtractionError::Spawn);
         }
+    };
 
-        stripped_path
+    // A miscalculated signature size would otherwise result in an out of range slice, or an empty carved file
+    validate_carve_range(file_data, signature)?;
+
+    // Wait for a free process slot; it is held until the ProcInfo is dropped, after the process has exited
+    let process_slot = config.process_limiter.acquire();
+
+    // Data is streamed to the extractor's stdin, no need to carve it to disk
+    if extractor.use_stdin {
+        return spawn_with_stdin(
+            file_data,
+            output_directory,
+            signature,
+            &command,
+            extractor,
+            capture_output,
+            process_slot,
+        );
     }
 
-    /// Interprets a given path containing '..' directories.
-    fn sanitize_path(&self, file_path: &str, preserve_root_path_sep: bool) -> String {
-        const DIR_TRAVERSAL: &str = "..";
+    // The extractor's extension may be overridden per signature, e.g. for extraction utilities that are picky about
+    // the names of their input files
+    let extension = config
+        .carved_extensions
+        .get(&signature.name)
+        .map(|extension| extension.trim_start_matches('.'))
+        .unwrap_or(&extractor.extension);
 
-        let mut exclude_indicies: Vec<usize> = vec![];
-        let mut sanitized_path: String = "".to_string();
+    let carved_file_name = format!("{}_{:X}.{}", signature.name, signature.offset, extension);
 
-        if preserve_root_path_sep && file_path.starts_with(path::MAIN_SEPARATOR) {
-            sanitized_path = path::MAIN_SEPARATOR.to_string();
+    // Carved file path will be <output directory>/<signature.name>_<hex offset>.<extension>, or the same file name in a
+    // directory of its own inside of the carve directory, if one was configured
+    let carve_directory = create_carve_directory(config)?;
+    let carved_file = match &carve_directory {
+        None => format!(
+            "{output_directory}{}{carved_file_name}",
+            path::MAIN_SEPARATOR
+        ),
+        Some(carve_directory) => {
+            Chroot::new(Some(carve_directory)).chrooted_path(&carved_file_name)
         }
+    };
 
-        // Split the file path on '/'
-        let path_parts: Vec<&str> = file_path.split(path::MAIN_SEPARATOR).collect();
-
-        // Loop through each part of the file path
-        for (i, path_part) in path_parts.iter().enumerate() {
-            // If this part of the path is '..', don't include it in the final sanitized path
-            if *path_part == DIR_TRAVERSAL {
-                exclude_indicies.push(i);
-                if i > 0 {
-                    // Walk backwards through the path parts until a non-excluded part is found, then mark that part for exclusion as well
-                    let mut j = i - 1;
-                    while j > 0 && exclude_indicies.contains(&j) {
-                        j -= 1;
-                    }
-                    exclude_indicies.push(j);
-                }
-            // If this part of the path is an empty string, don't include that either (happens if the original file path has '//' in it)
-            } else if path_part.is_empty() {
-                exclude_indicies.push(i);
-            }
+    // Removes the carve directory if the extractor could not be run
+    let remove_carve_directory = |e| {
+        if let Some(carve_directory) = &carve_directory {
+            let _ = fs::remove_dir_all(carve_directory);
         }
+        e
+    };
 
-        // Concatenate each non-excluded part of the file path, with each part separated by '/'
-        for (i, path_part) in path_parts.iter().enumerate() {
-            if !exclude_indicies.contains(&i) {
-                #[cfg(windows)]
-                {
-                    // on Windows: in the first loop run, we cannot really prepend a '\' to drive letters like 'C:'
-                    if sanitized_path.is_empty() {
-                        sanitized_path = path_part.to_string();
-                        continue;
-                    }
-                }
-                sanitized_path = format!("{}{}{}", sanitized_path, path::MAIN_SEPARATOR, path_part);
-            }
+    info!(
+        "Carving data from {} {:#X}..{:#X} to {}",
+        file_path,
+        signature.offset,
+        signature.offset + signature.size,
+        carved_file
+    );
+
+    // If the entirety of the source file is this one file type, no need to carve a copy of it, just create a symlink
+    if signature.offset == 0 && signature.size == file_data.len() {
+        chroot
+            .create_symlink(&carved_file, file_path)
+            .map_err(|e| remove_carve_directory(e.into()))?;
+    } else {
+        // Copy file data to carved file path
+        chroot
+            .carve_file(&carved_file, file_data, signature.offset, signature.size)
+            .map_err(|e| remove_carve_directory(e.into()))?;
+    }
+
+    // Replace all placeholder command arguments, e.g. "%e" with the path to the carved file
+    substitute_placeholders(
+        &mut extractor.arguments,
+        Some(&carved_file),
+        output_directory,
+        signature,
+    );
+
+    let (stdout_file, mut proc_command) =
+        extractor_command(&command, &extractor, output_directory, capture_output)
+            .map_err(remove_carve_directory)?;
+
+    info!("Spawning process {} {:?}", command, extractor.arguments);
+    match proc_command.spawn() {
+        Err(e) => {
+            error!(
+                "Failed to execute command {}{:?}: {}",
+                command, extractor.arguments, e
+            );
+            Err(remove_carve_directory(ExtractionError::Spawn))
         }
 
-        self.strip_double_slash(&sanitized_path)
+        Ok(mut child) => {
+            // If the process was spawned successfully, return some information about the process
+            let proc_info = ProcInfo {
+                stdout_reader: capture_stdout(&mut child),
+                stderr_reader: capture_stderr(&mut child),
+                child,
+                carved_file: Some(carved_file.clone()),
+                carve_directory,
+                exit_codes: extractor.exit_codes,
+                timeout: extractor.timeout,
+                stdout_file,
+                process_slot,
+            };
+
+            Ok(proc_info)
+        }
     }
 }
 
-/// Recursively walks a given directory and returns a list of regular non-zero size files in the given directory path.
-#[allow(dead_code)]
-pub fn get_extracted_files(directory: &str) -> Vec<String> {
-    let mut regular_files: Vec<String> = vec![];
+/// Creates a uniquely named directory inside of `ExtractionConfig.carve_directory` to carve an extractor's input file
+/// to; returns None if no carve directory is configured, in which case input files are carved to the output directory.
+fn create_carve_directory(config: &ExtractionConfig) -> Result<Option<String>, ExtractionError> {
+    // Distinguishes the carve directories created by this process
+    static CARVE_DIRECTORY_COUNT: AtomicUsize = AtomicUsize::new(0);
 
-    for entry in WalkDir::new(directory).into_iter() {
-        match entry {
-            Err(_e) => continue,
-            Ok(entry) => {
-                let entry_path = entry.path();
-                // Query file metadata *without* following symlinks
-                match fs::symlink_metadata(entry_path) {
-                    Err(_e) => continue,
-                    Ok(md) => {
-                        // Only interested in non-empty, regular files
-                        if md.is_file() && md.len() > 0 {
-                            regular_files.push(entry_path.display().to_string());
-                        }
-                    }
-                }
-            }
-        }
+    let Some(base_directory) = &config.carve_directory else {
+        return Ok(None);
+    };
+
+    // The extractor is run from the output directory, so the carved file path must be absolute
+    let carve_directory = path::absolute(base_directory)
+        .map_err(|e| ExtractionError::Io(e.to_string()))?
+        .join(format!(
+            "binwalk-{}-{}",
+            process::id(),
+            CARVE_DIRECTORY_COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+
+    if let Err(e) = fs::create_dir_all(&carve_directory) {
+        error!(
+            "Failed to create carve directory {}: {}",
+            carve_directory.display(),
+            e
+        );
+        return Err(ExtractionError::Io(e.to_string()));
     }
 
-    regular_files
+    Ok(Some(carve_directory.display().to_string()))
 }
 
-/// Executes an extractor for the provided SignatureResult.
-pub fn execute(
+/// Spawn an external extractor process, writing the signature data to the process's stdin.
+fn spawn_with_stdin(
     file_data: &[u8],
-    file_path: &str,
+    output_directory: &str,
     signature: &SignatureResult,
-    extractor: &Option<Extractor>,
-) -> ExtractionResult {
-    let mut result = ExtractionResult {
-        ..Default::default()
-    };
+    command: &str,
+    mut extractor: Extractor,
+    capture_output: bool,
+    process_slot: ProcessSlot,
+) -> Result<ProcInfo, ExtractionError> {
+    let signature_data = signature
+        .offset
+        .checked_add(signature.size)
+        .and_then(|end_offset| file_data.get(signature.offset..end_offset))
+        .ok_or(ExtractionError::InvalidCarveRange {
+            offset: signature.offset,
+            size: signature.size,
+            available: file_data.len(),
+        })?
+        .to_vec();
 
-    // Create an output directory for the extraction
-    if let Ok(output_directory) = create_output_directory(file_path, signature.offset) {
-        // Make sure a defalut extractor was actually defined (this function should not be called if signature.extractor is None)
-        match &extractor {
-            None => {
-                error!(
-                    "Attempted to extract {} data, but no extractor is defined!",
-                    signature.name
-                );
-            }
+    // There is no carved file, so the "%e" placeholder is not replaced
+    substitute_placeholders(&mut extractor.arguments, None, output_directory, signature);
 
-            Some(default_extractor) => {
-                let extractor_definition: Extractor;
+    let (stdout_file, mut proc_command) =
+        extractor_command(command, &extractor, output_directory, capture_output)?;
 
-                // If the signature result specified a preferred extractor, use that instead of the default signature extractor
-                if let Some(preferred_extractor) = &signature.preferred_extractor {
-                    extractor_definition = preferred_extractor.clone();
-                } else {
-                    extractor_definition = default_extractor.clone();
-                }
+    info!(
+        "Spawning process {} {:?} with {:#X} bytes of data on stdin",
+        command,
+        extractor.arguments,
+        signature_data.len()
+    );
+    let mut child = match proc_command.stdin(process::Stdio::piped()).spawn() {
+        Err(e) => {
+            error!(
+                "Failed to execute command {}{:?}: {}",
+                command, extractor.arguments, e
+            );
+            return Err(ExtractionError::Spawn);
+        }
+        Ok(child) => child,
+    };
 
-                // Decide how to execute the extractor depending on the extractor type
-                match &extractor_definition.utility {
-                    ExtractorType::None => {
-                        error!(
-                            "Signature {}: an extractor of type None is invalid!",
-                            signature.name
-                        );
-                    }
+    // Write the data from a separate thread so that a process which does not consume all of its input can't block us;
+    // stdin is closed when the thread exits, signaling EOF to the child process.
+    if let Some(mut stdin) = child.stdin.take() {
+        logging::spawn(move || {
+            if let Err(e) = stdin.write_all(&signature_data) {
+                debug!("Failed to write all data to extractor's stdin: {e}");
+            }
+        });
+    }
 
-                    ExtractorType::Internal(func) => {
-                        debug!("Executing internal {} extractor", signature.name);
-                        // Run the internal extractor function
-                        result = func(file_data, signature.offset, Some(&output_directory));
-                        // Set the extractor name to "<signature name>_built_in"
-                        result.extractor = format!("{}_built_in", signature.name);
-                    }
+    Ok(ProcInfo {
+        stdout_reader: capture_stdout(&mut child),
+        stderr_reader: capture_stderr(&mut child),
+        child,
+        carved_file: None,
+        carve_directory: None,
+        exit_codes: extractor.exit_codes,
+        timeout: extractor.timeout,
+        stdout_file,
+        process_slot,
+    })
+}
 
-                    ExtractorType::External(cmd) => {
-                        // Spawn the external extractor command
-                        match spawn(
-                            file_data,
-                            file_path,
-                            &output_directory,
-                            signature,
-                            extractor_definition.clone(),
-                        ) {
-                            Err(e) => {
-                                error!(
-                                    "Failed to spawn external extractor for '{}' signature: {}",
-                                    signature.name, e
-                                );
-                            }
+/// Replaces the placeholder constants in external command arguments with their respective values. Placeholders are
+/// replaced wherever they appear in an argument (e.g. `--output=%o/data`), not only when they make up the whole argument.
+/// If carved_file is None, SOURCE_FILE_PLACEHOLDER is left as-is.
+fn substitute_placeholders(
+    arguments: &mut [String],
+    carved_file: Option<&str>,
+    output_directory: &str,
+    signature: &SignatureResult,
+) {
+    let offset = signature.offset.to_string();
 
-                            Ok(proc_info) => {
-                                // Wait for the external process to exit
-                                match proc_wait(proc_info) {
-                                    Err(_) => {
-                                        warn!("External extractor failed!");
-                                    }
-                                    Ok(ext_result) => {
-                                        result = ext_result;
-                                        // Set the extractor name to the name of the extraction utility
-                                        result.extractor = cmd.to_string();
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+    let placeholders = [
+        (SOURCE_FILE_PLACEHOLDER, carved_file),
+        (OUTPUT_DIRECTORY_PLACEHOLDER, Some(output_directory)),
+        (OFFSET_PLACEHOLDER, Some(offset.as_str())),
+    ];
 
-                // Populate these ExtractionResult fields automatically for all extractors
-                result.output_directory = output_directory.clone();
-                result.do_not_recurse = extractor_definition.do_not_recurse;
+    for argument in arguments.iter_mut() {
+        // Arguments are scanned once, so that placeholders appearing in the substituted values are not replaced
+        let mut substituted = String::with_capacity(argument.len());
+        let mut remaining = argument.as_str();
 
-                // If the extractor reported success, make sure it extracted something other than just an empty file
-                if result.success && !was_something_extracted(&result.output_directory) {
-                    result.success = false;
-                    warn!("Extractor exited successfully, but no data was extracted");
+        while !remaining.is_empty() {
+            let replacement = placeholders.iter().find_map(|(placeholder, value)| {
+                value
+                    .filter(|_| remaining.starts_with(placeholder))
+                    .map(|value| (placeholder.len(), value))
+            });
+
+            match replacement {
+                Some((placeholder_size, value)) => {
+                    substituted.push_str(value);
+                    remaining = &remaining[placeholder_size..];
+                }
+                None => {
+                    let mut chars = remaining.chars();
+                    if let Some(c) = chars.next() {
+                        substituted.push(c);
+                    }
+                    remaining = chars.as_str();
                 }
             }
         }
 
-        // Clean up extractor's output directory if extraction failed
-        if !result.success {
-            if let Err(e) = fs::remove_dir_all(&output_directory) {
-                warn!(
-                    "Failed to clean up extraction directory {output_directory} after extraction failure: {e}"
-                );
-            }
-        }
+        *argument = substituted;
     }
-
-    result
 }
 
-/// Spawn an external extractor process.
-fn spawn(
-    file_data: &[u8],
-    file_path: &str,
+/// Builds the process::Command used to run an external extractor. If the extractor writes its output to stdout, the
+/// file that stdout is redirected to is created, and its path is returned along with the command.
+fn extractor_command(
+    command: &str,
+    extractor: &Extractor,
     output_directory: &str,
-    signature: &SignatureResult,
-    mut extractor: Extractor,
-) -> Result<ProcInfo, std::io::Error> {
-    let chroot = Chroot::new(None);
+    capture_output: bool,
+) -> Result<(Option<String>, process::Command), ExtractionError> {
+    let mut proc_command = process::Command::new(command);
+    let mut stdout_file: Option<String> = None;
 
-    // This function *only* handles execution of external extraction utilities; internal extractors must be invoked directly
-    let command = match &extractor.utility {
-        ExtractorType::External(cmd) => cmd.clone(),
-        ExtractorType::Internal(_ext) => {
-            error!("Tried to run an internal extractor as an external command!");
-            return Err(std::io::Error::other(
-                "attempt to execute an internal extractor as an external command",
-            ));
-        }
-        ExtractorType::None => {
-            error!("An extractor command was defined, but is set to None!");
-            return Err(std::io::Error::other(
-                "invalid external command of type None",
-            ));
-        }
-    };
+    proc_command
+        .args(&extractor.arguments)
+        .envs(extractor.env.iter().map(|(key, value)| (key, value)))
+        .stdout(output_stdio(capture_output))
+        .stderr(output_stdio(capture_output));
 
-    // Carved file path will be <output directory>/<signature.name>_<hex offset>.<extractor.extension>
-    let carved_file = format!(
-        "{}{}{}_{:X}.{}",
-        output_directory,
-        path::MAIN_SEPARATOR,
-        signature.name,
-        signature.offset,
-        extractor.extension
-    );
-    info!(
-        "Carving data from {} {:#X}..{:#X} to {}",
-        file_path,
-        signature.offset,
-        signature.offset + signature.size,
-        carved_file
-    );
+    if let Some(file_name) = &extractor.stdout_to_file {
+        let file_path = Chroot::new(Some(output_directory)).chrooted_path(file_name);
 
-    // If the entirety of the source file is this one file type, no need to carve a copy of it, just create a symlink
-    if signature.offset == 0 && signature.size == file_data.len() {
-        if !chroot.create_symlink(&carved_file, file_path) {
-            return Err(std::io::Error::other(
-                "Failed to create carved file symlink",
-            ));
+        match fs::File::create(&file_path) {
+            Err(e) => {
+                error!("Failed to create extractor output file {file_path}: {e}");
+                return Err(ExtractionError::Io(e.to_string()));
+            }
+            Ok(file) => {
+                proc_command.stdout(file);
+                stdout_file = Some(file_path);
+            }
         }
+    }
+
+    if extractor.run_in_output_dir {
+        proc_command.current_dir(output_directory);
+    }
+
+    Ok((stdout_file, proc_command))
+}
+
+/// Returns the Stdio configuration for an external extractor's stdout and stderr
+fn output_stdio(capture_output: bool) -> process::Stdio {
+    if capture_output {
+        process::Stdio::piped()
     } else {
-        // Copy file data to carved file path
-        if !chroot.carve_file(&carved_file, file_data, signature.offset, signature.size) {
-            return Err(std::io::Error::other("Failed to carve data to disk"));
-        }
+        process::Stdio::null()
     }
+}
+
+/// Starts a thread to collect the child process's stdout, if it was piped
+fn capture_stdout(child: &mut process::Child) -> Option<thread::JoinHandle<Vec<u8>>> {
+    let stdout = child.stdout.take()?;
+    Some(logging::spawn(move || read_output_tail(stdout)))
+}
+
+/// Starts a thread to collect the child process's stderr, if it was piped
+fn capture_stderr(child: &mut process::Child) -> Option<thread::JoinHandle<Vec<u8>>> {
+    let stderr = child.stderr.take()?;
+    Some(logging::spawn(move || read_output_tail(stderr)))
+}
+
+/// Reads all data from the reader, returning at most the last MAX_CAPTURED_OUTPUT_SIZE bytes read.
+/// The reader is always read until EOF so that the child process never blocks writing to a full pipe.
+fn read_output_tail(mut reader: impl Read) -> Vec<u8> {
+    let mut output: Vec<u8> = vec![];
+    let mut buffer = [0; 4096];
 
-    // Replace all "%e" command arguments with the path to the carved file
-    for i in 0..extractor.arguments.len() {
-        if extractor.arguments[i] == SOURCE_FILE_PLACEHOLDER {
-            extractor.arguments[i] = carved_file.clone();
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => {
+                output.extend_from_slice(&buffer[..n]);
+
+                // Trim the output periodically, rather than on every read
+                if output.len() > MAX_CAPTURED_OUTPUT_SIZE * 2 {
+                    output.drain(..output.len() - MAX_CAPTURED_OUTPUT_SIZE);
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => {
+                debug!("Failed to read extractor output: {e}");
+                break;
+            }
         }
     }
 
-    info!("Spawning process {} {:?}", command, extractor.arguments);
-    match process::Command::new(&command)
-        .args(&extractor.arguments)
-        .stdout(process::Stdio::null())
-        .stderr(process::Stdio::null())
-        .current_dir(output_directory)
-        .spawn()
-    {
-        Err(e) => {
-            error!(
-                "Failed to execute command {}{:?}: {}",
-                command, extractor.arguments, e
-            );
-            Err(e)
-        }
+    if output.len() > MAX_CAPTURED_OUTPUT_SIZE {
+        output.drain(..output.len() - MAX_CAPTURED_OUTPUT_SIZE);
+    }
 
-        Ok(child) => {
-            // If the process was spawned successfully, return some information about the process
-            let proc_info = ProcInfo {
-                child,
-                carved_file: carved_file.clone(),
-                exit_codes: extractor.exit_codes,
-            };
+    output
+}
 
-            Ok(proc_info)
+/// Waits for an output capture thread to exit and logs the captured output.
+/// Returns the captured output, if any.
+fn collect_output(
+    reader: Option<thread::JoinHandle<Vec<u8>>>,
+    stream_name: &str,
+) -> Option<String> {
+    let output = match reader?.join() {
+        Err(_) => {
+            warn!("Failed to collect extractor {stream_name}");
+            return None;
         }
+        Ok(output) => String::from_utf8_lossy(&output).to_string(),
+    };
+
+    if output.is_empty() {
+        return None;
     }
+
+    debug!("Extractor {stream_name}:\n{output}");
+    Some(output)
 }
 
 /// Waits for an extraction process to complete.
 /// Returns ExtractionError if the extractor was prematurely terminated, else returns an ExtractionResult.
-fn proc_wait(mut worker_info: ProcInfo) -> Result<ExtractionResult, ExtractionError> {
+/// If keep_failed is true, the carved file is not deleted when the extractor fails.
+fn proc_wait(
+    mut worker_info: ProcInfo,
+    keep_failed: bool,
+) -> Result<ExtractionResult, ExtractionError> {
     // The standard exit success value is 0
     const EXIT_SUCCESS: i32 = 0;
 
-    // Block until child process has terminated
-    match worker_info.child.wait() {
+    // Block until child process has terminated, or has been killed for running too long
+    let (wait_status, timed_out) = wait_with_timeout(&mut worker_info.child, worker_info.timeout);
+
+    match wait_status {
         // Child was terminated from an external signal, status unknown, assume failure but do nothing else
         Err(e) => {
             error!("Failed to retreive child process status: {e}");
-            Err(ExtractionError)
+            Err(ExtractionError::Io(e.to_string()))
         }
 
         // Child terminated with an exit status
         Ok(status) => {
             // Assume failure until proven otherwise
             let mut extraction_success: bool = false;
-
-            // Clean up the carved file used as input to the extractor
-            debug!("Deleting carved file {}", worker_info.carved_file);
-            if let Err(e) = fs::remove_file(worker_info.carved_file.clone()) {
-                warn!(
-                    "Failed to remove carved file '{}': {}",
-                    worker_info.carved_file, e
-                );
-            };
+            let mut extraction_error: Option<ExtractionError> = None;
+            let mut carved_file: Option<String> = None;
 
             // Check the extractor's exit status
             match status.code() {
+                None if timed_out => {
+                    warn!("Child process timed out, and was killed");
+                    extraction_error = Some(ExtractionError::Timeout);
+                }
                 None => {
                     extraction_success = false;
+                    extraction_error = Some(ExtractionError::Io(format!(
+                        "extractor was terminated abnormally ({status})"
+                    )));
                 }
 
                 Some(code) => {
@@ -1155,55 +4707,503 @@ fn proc_wait(mut worker_info: ProcInfo) -> Result<ExtractionResult, ExtractionEr
                         extraction_success = true;
                     } else {
                         warn!("Child process exited with unexpected code: {code}");
+                        extraction_error = Some(ExtractionError::NonZeroExit(code));
+                    }
+                }
+            }
+
+            // Extractors that write to stdout must have written something for the extraction to have succeeded
+            if let Some(stdout_file) = &worker_info.stdout_file {
+                let stdout_size = fs::metadata(stdout_file).map_or(0, |metadata| metadata.len());
+
+                if extraction_success && stdout_size == 0 {
+                    warn!("Child process wrote no data to {stdout_file}");
+                    extraction_success = false;
+                    extraction_error = Some(ExtractionError::Io(
+                        "extractor wrote no data to stdout".to_string(),
+                    ));
+                }
+
+                // Partial output is removed along with the carved file, unless failed extractions are kept
+                if !extraction_success && !keep_failed {
+                    debug!("Deleting extractor output file {stdout_file}");
+                    if let Err(e) = fs::remove_file(stdout_file) {
+                        warn!("Failed to remove extractor output file '{stdout_file}': {e}");
+                    }
+                }
+            }
+
+            // Clean up the carved file used as input to the extractor, unless it should be kept for debugging;
+            // there is no carved file if the data was passed to the extractor via stdin.
+            if let Some(worker_carved_file) = worker_info.carved_file {
+                if !extraction_success && keep_failed {
+                    info!("Keeping carved file {worker_carved_file}");
+                    carved_file = Some(worker_carved_file);
+                } else {
+                    debug!("Deleting carved file {worker_carved_file}");
+                    if let Err(e) = fs::remove_file(&worker_carved_file) {
+                        warn!("Failed to remove carved file '{worker_carved_file}': {e}");
+                    };
+
+                    if let Some(carve_directory) = &worker_info.carve_directory
+                        && let Err(e) = fs::remove_dir(carve_directory)
+                    {
+                        warn!("Failed to remove carve directory '{carve_directory}': {e}");
                     }
                 }
             }
 
+            // The output capture threads exit once the child process has exited and closed its end of the pipes
+            let stdout = collect_output(worker_info.stdout_reader, "stdout");
+            let stderr = collect_output(worker_info.stderr_reader, "stderr");
+
             // Return an ExtractionResult with the appropriate success status
-            Ok(ExtractionResult {
+            let mut result = ExtractionResult {
                 success: extraction_success,
+                error: extraction_error,
+                carved_file,
                 ..Default::default()
-            })
+            };
+
+            // Captured output is only of interest for diagnosing failed extractions
+            if !extraction_success {
+                result.extractor_stdout = stdout;
+                result.extractor_stderr = stderr;
+            }
+
+            Ok(result)
         }
     }
 }
 
-// Create an output directory in which to place extraction results
-fn create_output_directory(file_path: &str, offset: usize) -> Result<String, std::io::Error> {
-    let chroot = Chroot::new(None);
+/// Waits for the child process to exit, killing it if it is still running after `timeout`.
+/// Returns the process's exit status, and true if the process was killed because it timed out.
+fn wait_with_timeout(
+    child: &mut process::Child,
+    timeout: Option<time::Duration>,
+) -> (std::io::Result<process::ExitStatus>, bool) {
+    // How often to check if the process has exited
+    const POLL_INTERVAL: time::Duration = time::Duration::from_millis(10);
 
-    // Output directory will be: <file_path.extracted/<hex offset>
-    let output_directory = format!(
-        "{}.extracted{}{:X}",
-        file_path,
+    let Some(timeout) = timeout else {
+        return (child.wait(), false);
+    };
+
+    let deadline = time::Instant::now() + timeout;
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return (Ok(status), false),
+            Err(e) => return (Err(e), false),
+            Ok(None) if time::Instant::now() >= deadline => {
+                if let Err(e) = child.kill() {
+                    warn!("Failed to kill timed out child process: {e}");
+                }
+                return (child.wait(), true);
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+        }
+    }
+}
+
+/// Converts the extraction result's component paths to absolute paths inside the output directory, and hashes each component.
+fn resolve_components(result: &mut ExtractionResult) {
+    let chroot = Chroot::new(Some(&result.output_directory));
+
+    for component in result.components.iter_mut() {
+        component.path = chroot.chrooted_path(&component.path);
+
+        match fs::File::open(&component.path) {
+            Err(e) => {
+                warn!(
+                    "Failed to open extracted {} component {}: {}",
+                    component.name, component.path, e
+                );
+            }
+            Ok(mut component_file) => {
+                let mut hasher = Sha256::new();
+
+                match std::io::copy(&mut component_file, &mut hasher) {
+                    Err(e) => warn!("Failed to read component {}: {}", component.path, e),
+                    Ok(_) => component.sha256 = Some(hex::encode(hasher.finalize())),
+                }
+            }
+        }
+    }
+}
+
+/// Returns the hex encoded SHA-256 of the signature's data, or None if the signature's data extends beyond the end of file_data.
+fn carved_data_sha256(file_data: &[u8], signature: &SignatureResult) -> Option<String> {
+    let end_offset = signature.offset.checked_add(signature.size)?;
+    let carved_data = file_data.get(signature.offset..end_offset)?;
+
+    Some(hex::encode(Sha256::digest(carved_data)))
+}
+
+/// Populates the result's `carved_digest` with the digest of the signature's data, if `ExtractionConfig.digest_algorithm`
+/// is set and the signature's data lies within file_data
+fn set_carved_digest(
+    result: &mut ExtractionResult,
+    file_data: &[u8],
+    signature: &SignatureResult,
+    config: &ExtractionConfig,
+) {
+    let Some(algorithm) = config.digest_algorithm else {
+        return;
+    };
+
+    if let Some(carved_data) = signature
+        .offset
+        .checked_add(signature.size)
+        .and_then(|end_offset| file_data.get(signature.offset..end_offset))
+    {
+        result.carved_digest = Some(algorithm.hex_digest(carved_data));
+        result.carved_digest_algorithm = Some(algorithm);
+    }
+}
+
+/// Applies the extended attributes read from the signature data by `xattr_reader` to the extracted files.
+/// Failure to set an attribute is logged, but does not fail the extraction.
+fn restore_xattrs(
+    file_data: &[u8],
+    signature: &SignatureResult,
+    output_directory: &str,
+    xattr_reader: XattrReader,
+) {
+    let Some(signature_data) = signature
+        .offset
+        .checked_add(signature.size)
+        .and_then(|end_offset| file_data.get(signature.offset..end_offset))
+    else {
+        return;
+    };
+
+    let chroot = Chroot::new(Some(output_directory));
+
+    for (file_path, attributes) in xattr_reader(signature_data) {
+        if chroot.set_xattrs(&file_path, &attributes).is_err() {
+            warn!("Failed to restore extended attributes of {file_path}");
+        }
+    }
+}
+
+/// Checks that the signature's offset and size describe a valid, non-empty, range of the file data
+fn validate_carve_range(
+    file_data: &[u8],
+    signature: &SignatureResult,
+) -> Result<(), ExtractionError> {
+    let in_range = signature
+        .offset
+        .checked_add(signature.size)
+        .is_some_and(|end_offset| end_offset <= file_data.len());
+
+    if signature.size == 0 || !in_range {
+        let error = ExtractionError::InvalidCarveRange {
+            offset: signature.offset,
+            size: signature.size,
+            available: file_data.len(),
+        };
+        error!("Not extracting {} data: {}", signature.name, error);
+        return Err(error);
+    }
+
+    Ok(())
+}
+
+/// Creates the output directory for a duplicate extraction as a symlink to the previous extraction's output directory.
+/// Returns None if the symlink could not be created.
+fn link_duplicate_extraction(
+    file_path: &str,
+    signature: &SignatureResult,
+    config: &ExtractionConfig,
+    previous_result: ExtractionResult,
+) -> Option<ExtractionResult> {
+    let chroot = output_chroot(config);
+    let output_directory = unique_output_directory_path(file_path, signature, config);
+
+    info!(
+        "{} data at offset {:#X} was already extracted to {}, linking {} to it",
+        signature.name, signature.offset, previous_result.output_directory, output_directory
+    );
+
+    // Make sure the parent <file_path>.extracted directory exists, and remove results from any previous run
+    let parent_directory = path::Path::new(&output_directory)
+        .parent()?
+        .display()
+        .to_string();
+
+    if chroot.create_directory(&parent_directory).is_err()
+        || chroot.remove_directory(&output_directory).is_err()
+    {
+        return None;
+    }
+
+    if chroot
+        .create_symlink(&output_directory, &previous_result.output_directory)
+        .is_err()
+    {
+        warn!("Failed to link duplicate extraction, extracting {output_directory} instead");
+        return None;
+    }
+
+    // The previous extraction's files have already been (or will be) analyzed, there is no need to recurse into them again
+    Some(ExtractionResult {
+        output_directory,
+        do_not_recurse: true,
+        carved_file: None,
+        duplicate_of: Some(previous_result.output_directory.clone()),
+        ..previous_result
+    })
+}
+
+/// Returns the output directory path for the given signature: <file_path>.extracted/<directory name>
+fn output_directory_path(
+    file_path: &str,
+    signature: &SignatureResult,
+    config: &ExtractionConfig,
+) -> String {
+    // Width of zero-padded decimal offsets; sorts correctly for offsets up to ~10GB
+    const DECIMAL_OFFSET_WIDTH: usize = 10;
+
+    let directory_name = match config.output_directory_naming {
+        OutputDirectoryNaming::Hex => format!("{:X}", signature.offset),
+        OutputDirectoryNaming::Decimal => {
+            format!("{:0width$}", signature.offset, width = DECIMAL_OFFSET_WIDTH)
+        }
+        OutputDirectoryNaming::NameAndHex => {
+            format!("{}_{:X}", signature.name, signature.offset)
+        }
+    };
+
+    format!(
+        "{}{}{}",
+        extraction_directory(file_path, config),
         path::MAIN_SEPARATOR,
-        offset
+        directory_name
+    )
+}
+
+/// Returns the path of the `<file>.extracted` directory that the specified file's extractions are written to.
+///
+/// This is `<file_path>.extracted`, unless `ExtractionConfig.output_base` is set and the file is not already inside
+/// of it, in which case it is `<output_base>/<file name>.extracted`.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::extractors::common::{ExtractionConfig, extraction_directory};
+/// use std::path::{Path, absolute};
+///
+/// let mut config = ExtractionConfig::default();
+/// assert_eq!(extraction_directory("/input/firmware.bin", &config), "/input/firmware.bin.extracted");
+///
+/// let output_base = absolute("scratch").unwrap();
+/// config.output_base = Some(output_base.clone());
+///
+/// assert_eq!(
+///     extraction_directory("/input/firmware.bin", &config),
+///     format!("{}.extracted", output_base.join("firmware.bin").display())
+/// );
+///
+/// // Files that were extracted to the output base are extracted alongside themselves
+/// let extracted_file = output_base.join("firmware.bin.extracted").join("0").join("rootfs.img");
+/// assert_eq!(
+///     extraction_directory(&extracted_file.display().to_string(), &config),
+///     format!("{}.extracted", extracted_file.display())
+/// );
+/// ```
+pub fn extraction_directory(file_path: &str, config: &ExtractionConfig) -> String {
+    format!("{}.extracted", output_path(file_path, config))
+}
+
+/// Returns the path that the specified file's extraction outputs are named after, see `extraction_directory`
+fn output_path(file_path: &str, config: &ExtractionConfig) -> String {
+    let Some(output_base) = absolute_output_base(config) else {
+        return file_path.to_string();
+    };
+
+    let input_path = path::Path::new(file_path);
+    let absolute_input_path = path::absolute(input_path).unwrap_or(input_path.to_path_buf());
+
+    if absolute_input_path.starts_with(&output_base) {
+        return file_path.to_string();
+    }
+
+    match input_path.file_name() {
+        Some(file_name) => output_base.join(file_name).display().to_string(),
+        None => file_path.to_string(),
+    }
+}
+
+/// Returns the absolute path of `ExtractionConfig.output_base`, if set
+fn absolute_output_base(config: &ExtractionConfig) -> Option<path::PathBuf> {
+    config
+        .output_base
+        .as_ref()
+        .map(|output_base| path::absolute(output_base).unwrap_or(output_base.clone()))
+}
+
+/// Returns a Chroot for creating output directories: jailed to `ExtractionConfig.output_base`, if set
+fn output_chroot(config: &ExtractionConfig) -> Chroot {
+    match absolute_output_base(config) {
+        None => Chroot::new(None),
+        Some(output_base) => Chroot::new(Some(&output_base.display().to_string())),
+    }
+}
+
+/// Returns an output directory path for the given signature that has not been used by any other signature during this run.
+/// Normally this is the path returned by `output_directory_path`, but if another signature was already extracted there
+/// (e.g. two signatures at the same offset), the signature name, and if need be a numeric suffix, is appended to it.
+fn unique_output_directory_path(
+    file_path: &str,
+    signature: &SignatureResult,
+    config: &ExtractionConfig,
+) -> String {
+    let output_directory = output_directory_path(file_path, signature, config);
+
+    if config
+        .output_directories
+        .claim(&output_directory, &signature.id)
+    {
+        return output_directory;
+    }
+
+    let named_output_directory = format!("{}_{}", output_directory, signature.name);
+    let mut unique_output_directory = named_output_directory.clone();
+    let mut suffix: usize = 1;
+
+    while !config
+        .output_directories
+        .claim(&unique_output_directory, &signature.id)
+    {
+        unique_output_directory = format!("{named_output_directory}_{suffix}");
+        suffix += 1;
+    }
+
+    warn!(
+        "Output directory {} is already in use by another signature, extracting {} data at offset {:#X} to {} instead",
+        output_directory, signature.name, signature.offset, unique_output_directory
     );
 
-    // First, remove the output directory if it exists from a previous run
-    if !chroot.remove_directory(&output_directory) {
-        return Err(std::io::Error::other("Directory deletion failed"));
+    unique_output_directory
+}
+
+/// An extraction output directory, see `create_output_directory`
+enum OutputDirectory {
+    /// The output directory was newly created
+    Created(String),
+    /// The output directory already existed, and should be used as is
+    Existing(String),
+}
+
+// Create an output directory in which to place extraction results; what happens if the output directory already exists
+// is determined by `ExtractionConfig.existing_output`
+fn create_output_directory(
+    file_path: &str,
+    signature: &SignatureResult,
+    config: &ExtractionConfig,
+) -> Result<OutputDirectory, ChrootError> {
+    let chroot = output_chroot(config);
+
+    // Output directory will be: <file_path>.extracted/<directory name>, unless it is already in use by another signature
+    let output_directory = unique_output_directory_path(file_path, signature, config);
+
+    if path::Path::new(&chroot.chrooted_path(&output_directory)).exists() {
+        match config.existing_output {
+            ExistingOutputPolicy::Fail => {
+                return Err(ChrootError::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    &output_directory,
+                    "output directory already exists",
+                ));
+            }
+            ExistingOutputPolicy::Skip => return Ok(OutputDirectory::Existing(output_directory)),
+            // Remove the output directory left by a previous run
+            ExistingOutputPolicy::Overwrite => chroot.remove_directory(&output_directory)?,
+        }
     }
 
     // Create the output directory, equivalent of mkdir -p
-    if !chroot.create_directory(&output_directory) {
-        return Err(std::io::Error::other("Directory creation failed"));
+    chroot.create_directory(&output_directory)?;
+
+    Ok(OutputDirectory::Created(output_directory))
+}
+
+/// Removes everything in the output directory left behind by a failed extraction attempt
+fn reset_output_directory(output_directory: &str) -> Result<(), ChrootError> {
+    let chroot = Chroot::new(None);
+    chroot.remove_directory(output_directory)?;
+    chroot.create_directory(output_directory)
+}
+
+/// Describes everything found in an extractor's output directory
+#[derive(Debug, Default, Clone)]
+struct ExtractedOutputs {
+    /// Regular files that contain data
+    files: usize,
+    empty_files: usize,
+    directories: usize,
+    symlinks: usize,
+    /// Symlinks whose target does not exist; these are also counted in `symlinks`
+    dangling_symlinks: usize,
+    /// Device files, FIFOs and sockets
+    special_files: usize,
+    /// Entries that could not be read, e.g. due to insufficient permissions
+    unreadable: usize,
+    /// Total size of the regular files
+    size: u64,
+}
+
+impl ExtractedOutputs {
+    /// Returns true if the extractor outputs contain something, anything, that isn't an empty file
+    fn something_extracted(&self) -> bool {
+        self.files > 0 || self.directories > 0 || self.symlinks > 0
     }
+}
 
-    Ok(output_directory)
+impl std::fmt::Display for ExtractedOutputs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} non-empty files, {} empty files, {} directories, {} symlinks ({} dangling), {} special files, {} unreadable entries",
+            self.files,
+            self.empty_files,
+            self.directories,
+            self.symlinks,
+            self.dangling_symlinks,
+            self.special_files,
+            self.unreadable
+        )
+    }
 }
 
-/// Returns true if the size of the provided extractor output directory is greater than zero.
+/// Summarizes everything in the provided extractor output directory, other than the output directory itself.
+/// Files matching any of the ignored_outputs glob patterns are not included.
 /// Note that any intermediate/carved files must be deleted *before* calling this function.
-fn was_something_extracted(output_directory: &str) -> bool {
+fn extracted_outputs(output_directory: &str, ignored_outputs: &[String]) -> ExtractedOutputs {
     let output_directory_path = path::Path::new(output_directory);
     debug!("Checking output directory {output_directory} for results");
 
-    // Walk the output directory looking for something, anything, that isn't an empty file
+    let ignored_patterns: Vec<glob::Pattern> = ignored_outputs
+        .iter()
+        .filter_map(|pattern| match glob::Pattern::new(pattern) {
+            Err(e) => {
+                warn!("Invalid ignored output pattern '{pattern}': {e}");
+                None
+            }
+            Ok(glob_pattern) => Some(glob_pattern),
+        })
+        .collect();
+
+    let mut outputs = ExtractedOutputs {
+        ..Default::default()
+    };
+
     for entry in WalkDir::new(output_directory).into_iter() {
         match entry {
             Err(e) => {
                 warn!("Failed to retrieve output directory entry: {e}");
+                outputs.unreadable += 1;
                 continue;
             }
             Ok(entry) => {
@@ -1214,17 +5214,40 @@ fn was_something_extracted(output_directory: &str) -> bool {
 
                 debug!("Found output file {}", entry.path().display());
 
+                // Skip any files that the extractor is known to create regardless of whether or not it extracted anything
+                let relative_path = entry
+                    .path()
+                    .strip_prefix(output_directory_path)
+                    .unwrap_or(entry.path());
+
+                if ignored_patterns
+                    .iter()
+                    .any(|pattern| pattern.matches_path(relative_path))
+                {
+                    debug!("Ignoring output file {}", entry.path().display());
+                    continue;
+                }
+
                 match fs::symlink_metadata(entry.path()) {
-                    Err(_e) => continue,
-                    Ok(md) => {
-                        if md.len() > 0 {
-                            return true;
+                    Err(_e) => outputs.unreadable += 1,
+                    Ok(md) if md.is_symlink() => {
+                        outputs.symlinks += 1;
+
+                        if !entry.path().exists() {
+                            outputs.dangling_symlinks += 1;
                         }
                     }
+                    Ok(md) if md.is_dir() => outputs.directories += 1,
+                    Ok(md) if md.is_file() && md.len() > 0 => {
+                        outputs.files += 1;
+                        outputs.size += md.len();
+                    }
+                    Ok(md) if md.is_file() => outputs.empty_files += 1,
+                    Ok(_) => outputs.special_files += 1,
                 }
             }
         }
     }
 
-    false
+    outputs
 }
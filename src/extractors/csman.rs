@@ -134,7 +134,7 @@ pub fn extract_csman_dat(
                             processed_entries.insert(entry.key, 1);
                         }
 
-                        if !chroot.create_file(&file_name, &entry.value) {
+                        if chroot.create_file(&file_name, &entry.value).is_err() {
                             result.success = false;
                             break;
                         }
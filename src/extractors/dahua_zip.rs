@@ -65,11 +65,11 @@ pub fn extract_dahua_zip(
                 }
                 Some(zip_data) => {
                     // First write the normal ZIP header magic bytes to disk
-                    if !chroot.create_file(OUTFILE_NAME, ZIP_HEADER) {
+                    if chroot.create_file(OUTFILE_NAME, ZIP_HEADER).is_err() {
                         result.success = false;
                     } else {
                         // Append the rest of the ZIP archive to disk
-                        result.success = chroot.append_to_file(OUTFILE_NAME, zip_data);
+                        result.success = chroot.append_to_file(OUTFILE_NAME, zip_data).is_ok();
                     }
                 }
             }
@@ -0,0 +1,135 @@
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::info;
+use crate::structures::der::{X509Certificate, parse_der_size, parse_x509_certificate};
+
+/// Defines the internal extractor function for carving out DER certificates
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::der::der_certificate_extractor;
+///
+/// match der_certificate_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn der_certificate_extractor() -> Extractor {
+    Extractor {
+        do_not_recurse: true,
+        utility: ExtractorType::Internal(der_certificate_carver),
+        ..Default::default()
+    }
+}
+
+/// Defines the internal extractor function for carving out DER keys
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::der::der_key_extractor;
+///
+/// match der_key_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn der_key_extractor() -> Extractor {
+    Extractor {
+        do_not_recurse: true,
+        utility: ExtractorType::Internal(der_key_carver),
+        ..Default::default()
+    }
+}
+
+/// Carves a DER certificate to `certificate.der`, and logs the certificate's subject, issuer and validity period
+pub fn der_certificate_carver(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    const CERTIFICATE_FILE_NAME: &str = "certificate.der";
+
+    let result = der_carver(
+        file_data,
+        offset,
+        output_directory,
+        Some(CERTIFICATE_FILE_NAME),
+    );
+
+    if result.success
+        && output_directory.is_some()
+        && let Some(der_size) = result.size
+        && let Ok(certificate) = parse_x509_certificate(&file_data[offset..offset + der_size])
+    {
+        log_certificate_info(&certificate);
+    }
+
+    result
+}
+
+/// Carves a DER key to `key.der`
+pub fn der_key_carver(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    const KEY_FILE_NAME: &str = "key.der";
+    der_carver(file_data, offset, output_directory, Some(KEY_FILE_NAME))
+}
+
+/// Validates the ASN.1 structure of DER data to determine its exact size, and optionally carves it to disk
+pub fn der_carver(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+    fname: Option<&str>,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    if let Ok(der_size) = parse_der_size(&file_data[offset..]) {
+        result.size = Some(der_size);
+        result.success = true;
+
+        if let Some(outfile) = fname
+            && output_directory.is_some()
+        {
+            let chroot = Chroot::new(output_directory);
+            result.success = chroot
+                .carve_file(outfile, file_data, offset, der_size)
+                .is_ok();
+        }
+    }
+
+    result
+}
+
+/// Logs the subject, issuer and validity period of an extracted certificate
+pub fn log_certificate_info(certificate: &X509Certificate) {
+    info!(
+        "Certificate subject: \"{}\", issuer: \"{}\", valid from {} to {}",
+        certificate.subject, certificate.issuer, certificate.not_before, certificate.not_after
+    );
+}
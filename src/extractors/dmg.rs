@@ -1,6 +1,19 @@
-use crate::extractors;
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::{debug, warn};
+use crate::structures::dmg::{
+    DMG_CHUNK_ADC, DMG_CHUNK_BZIP2, DMG_CHUNK_COMMENT, DMG_CHUNK_IGNORED, DMG_CHUNK_LZFSE,
+    DMG_CHUNK_LZMA, DMG_CHUNK_RAW, DMG_CHUNK_ZERO_FILL, DMG_CHUNK_ZLIB, DMG_SECTOR_SIZE, DMGChunk,
+    DMGFooter, parse_dmg_block_table, parse_dmg_footer, parse_dmg_resource_fork,
+    parse_dmg_xml_resources,
+};
+use aho_corasick::AhoCorasick;
+use bzip2::bufread::BzDecoder;
+use flate2::bufread::ZlibDecoder;
+use liblzma::bufread::XzDecoder;
+use liblzma::stream::Stream;
+use std::io::Read;
 
-/// Describes how to run the dmg2img utility to convert DMG images to MBR
+/// Defines the internal extractor function for converting DMG images to raw disk images
 ///
 /// ```
 /// use std::io::ErrorKind;
@@ -22,17 +35,340 @@ use crate::extractors;
 ///     }
 /// }
 /// ```
-pub fn dmg_extractor() -> extractors::common::Extractor {
-    extractors::common::Extractor {
-        utility: extractors::common::ExtractorType::External("dmg2img".to_string()),
-        extension: "dmg".to_string(),
-        arguments: vec![
-            "-i".to_string(), // Input file
-            extractors::common::SOURCE_FILE_PLACEHOLDER.to_string(),
-            "-o".to_string(), // Output file
-            "mbr.img".to_string(),
-        ],
-        exit_codes: vec![0, 1],
+pub fn dmg_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_dmg),
         ..Default::default()
     }
 }
+
+/// Maximum size of a single decompressed chunk; hdiutil writes chunks of at most 1MB
+const MAX_CHUNK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Maximum size of a reconstructed disk image, unless the DMG itself is larger
+const MAX_IMAGE_SIZE: usize = 1024 * 1024 * 1024;
+
+/// A block table chunk, along with its location in the input and output data
+struct ImageChunk {
+    chunk: DMGChunk,
+    /// Offset of the chunk's data in the disk image
+    image_offset: usize,
+    /// Offset of the chunk's compressed data, relative to the start of the DMG
+    data_offset: usize,
+}
+
+/// Reconstructs the raw disk image described by a DMG's block tables.
+///
+/// The block tables are read from the XML property list, or from the resource fork of older DMGs, that the
+/// trailing "koly" footer points to. The reported size is the size of the DMG, up to and including the footer.
+/// If no output directory is specified, the block tables are validated but no chunks are decompressed.
+///
+/// Raw, zero fill, ADC, zlib, bzip2 and LZMA chunks are supported; LZFSE compressed chunks are not, and cause the
+/// extraction to fail.
+pub fn extract_dmg(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    const OUTPUT_FILE_NAME: &str = "disk.img";
+
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let Some(dmg_data) = file_data.get(offset..) else {
+        return result;
+    };
+
+    let Some((footer_offset, dmg_footer)) = find_dmg_footer(dmg_data) else {
+        debug!("Failed to locate a valid DMG footer");
+        return result;
+    };
+
+    let Some(chunks) = dmg_image_chunks(&dmg_data[..footer_offset], &dmg_footer) else {
+        debug!("Failed to parse DMG block tables");
+        return result;
+    };
+
+    let chroot = Chroot::new(output_directory);
+    let max_image_size = dmg_data.len().max(MAX_IMAGE_SIZE);
+    let mut image_size: usize = 0;
+
+    for image_chunk in chunks {
+        let chunk = &image_chunk.chunk;
+
+        let Some(chunk_size) = chunk.sector_count.checked_mul(DMG_SECTOR_SIZE) else {
+            warn!("DMG chunk at sector {} is too large", chunk.sector_number);
+            return result;
+        };
+
+        let Some(chunk_end) = image_chunk
+            .image_offset
+            .checked_add(chunk_size)
+            .filter(|chunk_end| *chunk_end <= max_image_size)
+        else {
+            warn!(
+                "DMG chunk at sector {} extends past the maximum image size",
+                chunk.sector_number
+            );
+            return result;
+        };
+
+        // Chunks must not overlap
+        if image_chunk.image_offset < image_size {
+            warn!(
+                "DMG chunk at sector {} overlaps previous data",
+                chunk.sector_number
+            );
+            return result;
+        }
+
+        let Some(compressed_data) = image_chunk
+            .data_offset
+            .checked_add(chunk.compressed_length)
+            .and_then(|data_end| dmg_data.get(image_chunk.data_offset..data_end))
+        else {
+            warn!(
+                "DMG chunk data at offset {:#X} is truncated",
+                image_chunk.data_offset
+            );
+            return result;
+        };
+
+        let chunk_data = match chunk.chunk_type {
+            DMG_CHUNK_ZERO_FILL | DMG_CHUNK_IGNORED => None,
+            DMG_CHUNK_RAW | DMG_CHUNK_ADC | DMG_CHUNK_ZLIB | DMG_CHUNK_BZIP2 | DMG_CHUNK_LZMA => {
+                // Decompressed chunks are held in memory
+                if chunk_size > MAX_CHUNK_SIZE {
+                    warn!("DMG chunk at sector {} is too large", chunk.sector_number);
+                    return result;
+                }
+
+                Some(compressed_data)
+            }
+            DMG_CHUNK_LZFSE => {
+                warn!("LZFSE compressed DMG chunks are not supported");
+                return result;
+            }
+            chunk_type => {
+                warn!("Unsupported DMG chunk type {chunk_type:#X}");
+                return result;
+            }
+        };
+
+        if output_directory.is_some() {
+            // Gaps between chunks, as well as zero fill chunks, are filled with NULL bytes
+            if !write_zeros(
+                &chroot,
+                OUTPUT_FILE_NAME,
+                image_chunk.image_offset - image_size,
+            ) {
+                return result;
+            }
+
+            match chunk_data {
+                None => {
+                    if !write_zeros(&chroot, OUTPUT_FILE_NAME, chunk_size) {
+                        return result;
+                    }
+                }
+                Some(data) => {
+                    let Some(decompressed_data) =
+                        decompress_dmg_chunk(chunk.chunk_type, data, chunk_size)
+                    else {
+                        warn!(
+                            "Failed to decompress DMG chunk at offset {:#X}",
+                            image_chunk.data_offset
+                        );
+                        return result;
+                    };
+
+                    if chroot
+                        .append_to_file(OUTPUT_FILE_NAME, &decompressed_data)
+                        .is_err()
+                    {
+                        return result;
+                    }
+                }
+            }
+        }
+
+        image_size = chunk_end;
+    }
+
+    debug!("Reconstructed a {image_size} byte disk image from DMG");
+
+    result.size = Some(footer_offset + dmg_footer.footer_size);
+    result.success = true;
+    result
+}
+
+/// Locates the first valid DMG footer, returning its offset and the parsed footer
+fn find_dmg_footer(dmg_data: &[u8]) -> Option<(usize, DMGFooter)> {
+    const FOOTER_MAGIC: &[u8] = b"koly";
+
+    let grep = AhoCorasick::new(vec![FOOTER_MAGIC]).ok()?;
+
+    for footer_match in grep.find_overlapping_iter(dmg_data) {
+        let footer_offset = footer_match.start();
+
+        if let Ok(dmg_footer) = parse_dmg_footer(&dmg_data[footer_offset..]) {
+            // The image data and resources must precede the footer
+            let resources_end = match dmg_footer.xml_length {
+                0 => dmg_footer.rsrc_offset.checked_add(dmg_footer.rsrc_length),
+                _ => dmg_footer.xml_offset.checked_add(dmg_footer.xml_length),
+            };
+            let data_end = dmg_footer.data_offset.checked_add(dmg_footer.data_length);
+
+            if let (Some(resources_end), Some(data_end)) = (resources_end, data_end)
+                && data_end <= footer_offset
+                && resources_end <= footer_offset
+            {
+                return Some((footer_offset, dmg_footer));
+            }
+        }
+    }
+
+    None
+}
+
+/// Parses the DMG block tables, and returns all of their chunks, sorted by their offset in the disk image
+fn dmg_image_chunks(dmg_data: &[u8], dmg_footer: &DMGFooter) -> Option<Vec<ImageChunk>> {
+    let resources = if dmg_footer.xml_length > 0 {
+        let xml_end = dmg_footer.xml_offset.checked_add(dmg_footer.xml_length)?;
+        parse_dmg_xml_resources(dmg_data.get(dmg_footer.xml_offset..xml_end)?).ok()?
+    } else {
+        let rsrc_end = dmg_footer.rsrc_offset.checked_add(dmg_footer.rsrc_length)?;
+        parse_dmg_resource_fork(dmg_data.get(dmg_footer.rsrc_offset..rsrc_end)?).ok()?
+    };
+
+    let mut chunks: Vec<ImageChunk> = vec![];
+
+    for resource in resources {
+        let block_table = parse_dmg_block_table(&resource).ok()?;
+
+        for chunk in block_table.chunks {
+            if chunk.chunk_type == DMG_CHUNK_COMMENT {
+                continue;
+            }
+
+            // Sector numbers and offsets that overflow can only come from a corrupt block table
+            chunks.push(ImageChunk {
+                image_offset: block_table
+                    .sector_number
+                    .checked_add(chunk.sector_number)?
+                    .checked_mul(DMG_SECTOR_SIZE)?,
+                data_offset: dmg_footer
+                    .data_offset
+                    .checked_add(block_table.data_offset)?
+                    .checked_add(chunk.compressed_offset)?,
+                chunk,
+            });
+        }
+    }
+
+    chunks.sort_by_key(|image_chunk| image_chunk.image_offset);
+
+    Some(chunks)
+}
+
+/// Decompresses a DMG chunk, which must decompress to exactly `chunk_size` bytes
+fn decompress_dmg_chunk(
+    chunk_type: usize,
+    chunk_data: &[u8],
+    chunk_size: usize,
+) -> Option<Vec<u8>> {
+    let mut decompressed_data: Vec<u8> = vec![];
+
+    // Read at most one byte more than expected, to detect chunks that decompress to more than their reported size
+    let read_limit = chunk_size as u64 + 1;
+
+    let status = match chunk_type {
+        DMG_CHUNK_RAW => {
+            decompressed_data.extend_from_slice(chunk_data);
+            Ok(chunk_data.len())
+        }
+        DMG_CHUNK_ADC => {
+            decompressed_data = adc_decompress(chunk_data, chunk_size)?;
+            Ok(decompressed_data.len())
+        }
+        DMG_CHUNK_ZLIB => ZlibDecoder::new(chunk_data)
+            .take(read_limit)
+            .read_to_end(&mut decompressed_data),
+        DMG_CHUNK_BZIP2 => BzDecoder::new(chunk_data)
+            .take(read_limit)
+            .read_to_end(&mut decompressed_data),
+        DMG_CHUNK_LZMA => {
+            XzDecoder::new_stream(chunk_data, Stream::new_auto_decoder(u64::MAX, 0).ok()?)
+                .take(read_limit)
+                .read_to_end(&mut decompressed_data)
+        }
+        _ => return None,
+    };
+
+    if status.is_ok() && decompressed_data.len() == chunk_size {
+        return Some(decompressed_data);
+    }
+
+    None
+}
+
+/// Decompresses Apple Data Compression (ADC) data, a simple LZ77 variant
+fn adc_decompress(compressed_data: &[u8], output_size: usize) -> Option<Vec<u8>> {
+    let mut output: Vec<u8> = vec![];
+    let mut i: usize = 0;
+
+    while i < compressed_data.len() && output.len() < output_size {
+        let code = compressed_data[i];
+
+        // Literal runs have the high bit set; matches use either a two or three byte code
+        let (match_length, distance) = if code & 0x80 != 0 {
+            let run_length = (code & 0x7F) as usize + 1;
+            output.extend_from_slice(compressed_data.get(i + 1..i + 1 + run_length)?);
+            i += 1 + run_length;
+            continue;
+        } else if code & 0x40 != 0 {
+            let distance_bytes = compressed_data.get(i + 1..i + 3)?;
+            i += 3;
+            (
+                (code & 0x3F) as usize + 4,
+                u16::from_be_bytes([distance_bytes[0], distance_bytes[1]]) as usize,
+            )
+        } else {
+            let distance_low = *compressed_data.get(i + 1)? as usize;
+            i += 2;
+            (
+                ((code & 0x3C) >> 2) as usize + 3,
+                (((code & 0x03) as usize) << 8) | distance_low,
+            )
+        };
+
+        // Distances are stored minus one, and matches may overlap the data being written
+        let source = output.len().checked_sub(distance + 1)?;
+        for j in source..source + match_length {
+            output.push(output[j]);
+        }
+    }
+
+    Some(output)
+}
+
+/// Appends `count` NULL bytes to the specified file
+fn write_zeros(chroot: &Chroot, file_name: &str, count: usize) -> bool {
+    const BLOCK_SIZE: usize = 1024 * 1024;
+
+    let zeros = vec![0; count.min(BLOCK_SIZE)];
+    let mut remaining = count;
+
+    while remaining > 0 {
+        let size = remaining.min(BLOCK_SIZE);
+
+        if chroot.append_to_file(file_name, &zeros[..size]).is_err() {
+            return false;
+        }
+
+        remaining -= size;
+    }
+
+    true
+}
@@ -1,7 +1,7 @@
 use crate::common::is_offset_safe;
 use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::error;
 use crate::structures::dtb::{parse_dtb_header, parse_dtb_node};
-use log::error;
 
 /// Defines the internal extractor function for extracting Device Tree Blobs
 ///
@@ -80,11 +80,11 @@ pub fn extract_dtb(
                         let dir_path = heirerarchy.join(std::path::MAIN_SEPARATOR_STR);
                         let file_path = chroot.safe_path_join(&dir_path, &node.name);
 
-                        if !chroot.create_directory(dir_path) {
+                        if chroot.create_directory(dir_path).is_err() {
                             break;
                         }
 
-                        if !chroot.create_file(file_path, &node.data) {
+                        if chroot.create_file(file_path, &node.data).is_err() {
                             break;
                         }
                     }
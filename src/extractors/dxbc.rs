@@ -50,8 +50,9 @@ pub fn extract_dxbc_file(
         // Do extraction, if requested
         if output_directory.is_some() {
             let chroot = Chroot::new(output_directory);
-            result.success =
-                chroot.carve_file(OUTFILE_NAME, file_data, offset, result.size.unwrap());
+            result.success = chroot
+                .carve_file(OUTFILE_NAME, file_data, offset, result.size.unwrap())
+                .is_ok();
         }
     }
 
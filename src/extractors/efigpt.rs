@@ -0,0 +1,135 @@
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::{debug, warn};
+use crate::structures::efigpt::{GPTPartition, parse_efigpt_header};
+
+/// Defines the internal extractor function for EFI GPT partitions
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::efigpt::efigpt_extractor;
+///
+/// match efigpt_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn efigpt_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_efigpt_partitions),
+        ..Default::default()
+    }
+}
+
+/// Validate and extract partitions from an EFI GPT disk image, starting at its protective MBR.
+///
+/// Each partition is carved to `<partition type>_partition.<partition number>`, or to
+/// `<partition type>_<partition name>_partition.<partition number>` if the partition is named. Partition types are
+/// identified by name if they are well known, else by their type GUID.
+///
+/// If the primary GPT fails CRC validation the backup GPT is used instead; CRC mismatches are logged, but do not
+/// fail the extraction.
+pub fn extract_efigpt_partitions(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let Some(gpt_data) = file_data.get(offset..) else {
+        return result;
+    };
+
+    let Ok(gpt_header) = parse_efigpt_header(gpt_data) else {
+        return result;
+    };
+
+    if !gpt_header.primary_crc_valid {
+        if gpt_header.used_backup {
+            warn!("Primary GPT failed CRC validation, using the backup GPT instead");
+        } else {
+            warn!("GPT failed CRC validation");
+        }
+    } else if gpt_header.backup_crc_valid == Some(false) {
+        warn!("Backup GPT failed CRC validation");
+    }
+
+    // Some EFI images have been observed to define partitions that extend beyond EOF
+    result.size = Some(gpt_header.total_size.min(gpt_data.len()));
+    result.success = true;
+
+    if output_directory.is_some() {
+        let chroot = Chroot::new(output_directory);
+
+        for (partition_number, partition) in gpt_header.partitions.iter().enumerate() {
+            let available_size = gpt_data.len().saturating_sub(partition.start);
+
+            if available_size == 0 {
+                warn!("GPT partition {partition_number} starts beyond EOF, not extracting it");
+                continue;
+            }
+
+            if partition.size > available_size {
+                warn!(
+                    "GPT partition {partition_number} extends beyond EOF, carving a truncated partition"
+                );
+            }
+
+            debug!(
+                "Carving GPT partition {} at {:#X}, type GUID: {}",
+                partition_number, partition.start, partition.type_guid
+            );
+
+            result.success = chroot
+                .carve_file(
+                    partition_file_name(partition, partition_number),
+                    gpt_data,
+                    partition.start,
+                    partition.size.min(available_size),
+                )
+                .is_ok();
+
+            // If partition extraction failed, quit and report a failure
+            if !result.success {
+                break;
+            }
+        }
+    }
+
+    result
+}
+
+/// Returns the file name to carve a partition to; partition types and names are restricted to safe characters
+fn partition_file_name(partition: &GPTPartition, partition_number: usize) -> String {
+    let sanitize = |name: &str| -> String {
+        name.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    };
+
+    let mut label = sanitize(&partition.type_name);
+
+    if !partition.name.is_empty() {
+        label = format!("{}_{}", label, sanitize(&partition.name));
+    }
+
+    format!("{label}_partition.{partition_number}")
+}
@@ -0,0 +1,242 @@
+use crate::common::get_cstring;
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::warn;
+use crate::structures::elf::{
+    ELFHeader, ELFSectionHeader, SHF_COMPRESSED, SHT_NOBITS, parse_elf_compression_header,
+    parse_elf_header, parse_elf_program_header, parse_elf_section_header,
+};
+use flate2::read::ZlibDecoder;
+use std::io::Read;
+use std::path::Path;
+
+/// Defines the internal extractor function for carving the individual sections of ELF files.
+///
+/// ELF signatures are not extracted by default, their size is only used to avoid matching signatures inside the ELF;
+/// to carve ELF sections, register this extractor for the `elf` signature with `Binwalk::register_extractor`.
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::elf::elf_sections_extractor;
+///
+/// match elf_sections_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn elf_sections_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_elf_sections),
+        ..Default::default()
+    }
+}
+
+/// Validates an ELF file and determines its size; if an output directory is provided, each section that occupies
+/// space in the file is written to a file named after the section. Compressed debug sections are decompressed.
+pub fn extract_elf_sections(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let elf_data = &file_data[offset..];
+
+    if let Some((elf_header, sections, elf_size)) = parse_elf_image(elf_data) {
+        result.size = Some(elf_size);
+        result.success = true;
+
+        if output_directory.is_some() {
+            let chroot = Chroot::new(output_directory);
+
+            // Section names are stored in the section name string table
+            let section_names: &[u8] = match sections.get(elf_header.section_names_index) {
+                None => &[],
+                Some(string_table) => section_data(elf_data, string_table),
+            };
+
+            for (index, section) in sections.iter().enumerate() {
+                if section.section_type == SHT_NOBITS || section.size == 0 {
+                    continue;
+                }
+
+                let section_name = match section_names.get(section.name_offset..) {
+                    None => String::new(),
+                    Some(name_bytes) => get_cstring(name_bytes),
+                };
+
+                let (data, extension) =
+                    decompress_section(&elf_header, &section_name, section, elf_data);
+
+                // Section names usually start with a '.'; don't create hidden files
+                let mut file_name = match section_name.trim_start_matches('.') {
+                    "" => format!("section_{index}"),
+                    name => name.to_string(),
+                };
+                file_name.push_str(extension);
+
+                if Path::new(&chroot.chrooted_path(&file_name)).exists() {
+                    file_name = format!("{file_name}.{index}");
+                }
+
+                if chroot.create_file(&file_name, &data).is_err() {
+                    result.success = false;
+                    break;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Parses the ELF header, program header table and section header table, and returns the ELF header, the section
+/// headers, and the size of the ELF image: the furthest extent of the headers, segments and sections.
+fn parse_elf_image(elf_data: &[u8]) -> Option<(ELFHeader, Vec<ELFSectionHeader>, usize)> {
+    let elf_header = parse_elf_header(elf_data).ok()?;
+
+    let mut sections: Vec<ELFSectionHeader> = vec![];
+    let mut elf_size: usize = elf_header.header_size;
+
+    // Program header table and the segments it describes
+    if elf_header.program_header_count > 0 {
+        for i in 0..elf_header.program_header_count {
+            let entry_offset = elf_header.program_header_entry_size.checked_mul(i)?;
+            let header_offset = elf_header.program_header_offset.checked_add(entry_offset)?;
+            let program_header =
+                parse_elf_program_header(elf_data.get(header_offset..)?, &elf_header).ok()?;
+
+            elf_size = elf_size.max(
+                program_header
+                    .offset
+                    .checked_add(program_header.file_size)?,
+            );
+        }
+
+        let table_size = elf_header
+            .program_header_entry_size
+            .checked_mul(elf_header.program_header_count)?;
+        elf_size = elf_size.max(elf_header.program_header_offset.checked_add(table_size)?);
+    }
+
+    // Section header table and the sections it describes
+    if elf_header.section_header_count > 0 {
+        for i in 0..elf_header.section_header_count {
+            let entry_offset = elf_header.section_header_entry_size.checked_mul(i)?;
+            let header_offset = elf_header.section_header_offset.checked_add(entry_offset)?;
+            let section_header =
+                parse_elf_section_header(elf_data.get(header_offset..)?, &elf_header).ok()?;
+
+            if section_header.section_type != SHT_NOBITS {
+                elf_size = elf_size.max(section_header.offset.checked_add(section_header.size)?);
+            }
+
+            sections.push(section_header);
+        }
+
+        let table_size = elf_header
+            .section_header_entry_size
+            .checked_mul(elf_header.section_header_count)?;
+        elf_size = elf_size.max(elf_header.section_header_offset.checked_add(table_size)?);
+    }
+
+    // The ELF image must be fully contained in the available data
+    if elf_size == 0 || elf_size > elf_data.len() {
+        return None;
+    }
+
+    Some((elf_header, sections, elf_size))
+}
+
+/// Returns the raw data of a section; sections are validated to be in bounds by `parse_elf_image`
+fn section_data<'a>(elf_data: &'a [u8], section: &ELFSectionHeader) -> &'a [u8] {
+    if section.section_type == SHT_NOBITS {
+        return &[];
+    }
+
+    &elf_data[section.offset..section.offset + section.size]
+}
+
+/// Returns the section data, decompressed if it is a compressed debug section, along with a file extension to use.
+/// Compressed sections that can't be decompressed are returned as-is.
+fn decompress_section(
+    elf_header: &ELFHeader,
+    section_name: &str,
+    section: &ELFSectionHeader,
+    elf_data: &[u8],
+) -> (Vec<u8>, &'static str) {
+    // Compression types used in the compression header of SHF_COMPRESSED sections
+    const ELFCOMPRESS_ZLIB: usize = 1;
+    const ELFCOMPRESS_ZSTD: usize = 2;
+
+    // Legacy GNU compressed sections are named .zdebug_*, and have a magic string and big endian size header
+    const ZDEBUG_PREFIX: &str = ".zdebug";
+    const ZDEBUG_MAGIC: &[u8] = b"ZLIB";
+    const ZDEBUG_HEADER_SIZE: usize = 12;
+
+    let data = section_data(elf_data, section);
+
+    if section.flags & SHF_COMPRESSED != 0 {
+        match parse_elf_compression_header(data, elf_header) {
+            Err(_) => warn!("Failed to parse the ELF compression header of section {section_name}"),
+            Ok(compression_header) => {
+                let compressed_data = &data[compression_header.header_size..];
+
+                match compression_header.compression_type {
+                    ELFCOMPRESS_ZLIB => {
+                        if let Some(decompressed_data) =
+                            zlib_decompress(compressed_data, compression_header.uncompressed_size)
+                        {
+                            return (decompressed_data, "");
+                        }
+                    }
+                    // Leave zstd data compressed for the zstd extractor to handle during recursion
+                    ELFCOMPRESS_ZSTD => return (compressed_data.to_vec(), ".zst"),
+                    _ => (),
+                }
+
+                warn!("Failed to decompress ELF section {section_name}");
+            }
+        }
+    } else if section_name.starts_with(ZDEBUG_PREFIX) && data.starts_with(ZDEBUG_MAGIC) {
+        if let Some(size_bytes) = data.get(ZDEBUG_MAGIC.len()..ZDEBUG_HEADER_SIZE)
+            && let Some(decompressed_data) = zlib_decompress(
+                &data[ZDEBUG_HEADER_SIZE..],
+                u64::from_be_bytes(size_bytes.try_into().unwrap()) as usize,
+            )
+        {
+            return (decompressed_data, "");
+        }
+
+        warn!("Failed to decompress ELF section {section_name}");
+    }
+
+    (data.to_vec(), "")
+}
+
+/// Decompresses zlib data; the decompressed data must be exactly the expected size
+fn zlib_decompress(compressed_data: &[u8], uncompressed_size: usize) -> Option<Vec<u8>> {
+    let mut decompressed_data = vec![];
+
+    ZlibDecoder::new(compressed_data)
+        .take(uncompressed_size as u64)
+        .read_to_end(&mut decompressed_data)
+        .ok()?;
+
+    match decompressed_data.len() == uncompressed_size {
+        true => Some(decompressed_data),
+        false => None,
+    }
+}
@@ -47,7 +47,9 @@ pub fn encfw_decrypt(
         // Write to file, if requested
         if output_directory.is_some() {
             let chroot = Chroot::new(output_directory);
-            result.success = chroot.create_file(OUTPUT_FILE_NAME, &decrypted_data);
+            result.success = chroot
+                .create_file(OUTPUT_FILE_NAME, &decrypted_data)
+                .is_ok();
         }
     }
 
@@ -0,0 +1,195 @@
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+
+/// Decoded data is written to this file, for recursive analysis
+const DECODED_FILE_NAME: &str = "decoded.bin";
+
+/// Runs of encoded data that decode to fewer bytes than this are ignored, as they are likely to be ordinary text
+pub const MIN_DECODED_SIZE: usize = 64;
+
+/// Defines the internal extractor function for decoding base64 encoded data
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::encoded::base64_extractor;
+///
+/// match base64_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn base64_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_base64),
+        ..Default::default()
+    }
+}
+
+/// Defines the internal extractor function for decoding hex encoded data
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::encoded::hex_extractor;
+///
+/// match hex_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn hex_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_hex),
+        ..Default::default()
+    }
+}
+
+/// Decodes a run of base64 encoded data, which may be wrapped over multiple lines, to `decoded.bin`.
+///
+/// The run must not start in the middle of other base64 characters, must be correctly padded, and must decode to at
+/// least `MIN_DECODED_SIZE` bytes. The reported size is the size of the encoded data, excluding any trailing line break.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::extractors::encoded::extract_base64;
+///
+/// // 97 'A' characters
+/// let encoded = format!("{}QQ==", "QUFB".repeat(32));
+/// let data = format!("key = \"{encoded}\"\n");
+///
+/// let result = extract_base64(data.as_bytes(), 7, None);
+/// assert!(result.success);
+/// assert_eq!(result.size, Some(encoded.len()));
+///
+/// // Starts in the middle of a run of base64 characters
+/// assert!(!extract_base64(data.as_bytes(), 8, None).success);
+/// ```
+pub fn extract_base64(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    // Maximum number of padding characters
+    const MAX_PADDING: usize = 2;
+
+    let mut encoded_data: Vec<u8> = vec![];
+    let mut encoded_size: usize = 0;
+    let mut padding: usize = 0;
+
+    if offset > 0 && is_base64_char(file_data[offset - 1]) {
+        return ExtractionResult::default();
+    }
+
+    for (i, byte) in file_data[offset..].iter().enumerate() {
+        match byte {
+            // Padding may only appear at the end of the encoded data
+            b'=' if padding < MAX_PADDING => padding += 1,
+            _ if padding > 0 => break,
+            b'\r' | b'\n' => continue,
+            byte if is_base64_char(*byte) => (),
+            _ => break,
+        }
+
+        encoded_data.push(*byte);
+        encoded_size = i + 1;
+    }
+
+    if !encoded_data.len().is_multiple_of(4) {
+        return ExtractionResult::default();
+    }
+
+    match BASE64_STANDARD.decode(&encoded_data) {
+        Ok(decoded_data) => write_decoded_data(&decoded_data, encoded_size, output_directory),
+        Err(_) => ExtractionResult::default(),
+    }
+}
+
+/// Decodes a run of hex encoded data to `decoded.bin`.
+///
+/// The run must not start in the middle of other hex digits, must contain an even number of hex digits, and must
+/// decode to at least `MIN_DECODED_SIZE` bytes. The reported size is the size of the encoded data.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::extractors::encoded::extract_hex;
+///
+/// let data = format!("blob={};", "1f8b0800".repeat(32));
+///
+/// let result = extract_hex(data.as_bytes(), 5, None);
+/// assert!(result.success);
+/// assert_eq!(result.size, Some(256));
+///
+/// // Starts in the middle of a run of hex digits
+/// assert!(!extract_hex(data.as_bytes(), 6, None).success);
+/// ```
+pub fn extract_hex(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    if offset > 0 && file_data[offset - 1].is_ascii_hexdigit() {
+        return ExtractionResult::default();
+    }
+
+    let encoded_size = file_data[offset..]
+        .iter()
+        .take_while(|byte| byte.is_ascii_hexdigit())
+        .count();
+
+    match hex::decode(&file_data[offset..offset + encoded_size]) {
+        Ok(decoded_data) => write_decoded_data(&decoded_data, encoded_size, output_directory),
+        Err(_) => ExtractionResult::default(),
+    }
+}
+
+/// Returns true if the byte is a character from the standard base64 alphabet, excluding padding
+fn is_base64_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'+' || byte == b'/'
+}
+
+/// Writes the decoded data to disk, if it is large enough to be of interest
+fn write_decoded_data(
+    decoded_data: &[u8],
+    encoded_size: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    if decoded_data.len() >= MIN_DECODED_SIZE {
+        result.size = Some(encoded_size);
+        result.success = true;
+
+        if output_directory.is_some() {
+            let chroot = Chroot::new(output_directory);
+            result.success = chroot.create_file(DECODED_FILE_NAME, decoded_data).is_ok();
+        }
+    }
+
+    result
+}
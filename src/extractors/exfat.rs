@@ -0,0 +1,398 @@
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::{debug, warn};
+use crate::structures::exfat::{
+    ATTR_DIRECTORY, DIRECTORY_ENTRY_SIZE, ENTRY_TYPE_ALLOCATION_BITMAP,
+    ENTRY_TYPE_END_OF_DIRECTORY, ENTRY_TYPE_FILE, ENTRY_TYPE_UPCASE_TABLE, EXFATHeader,
+    EXFATStreamEntry, FILE_NAME_ENTRY_CHARACTERS, exfat_entry_set_checksum, exfat_name_hash,
+    exfat_upcase_table_checksum, parse_exfat_file_entry, parse_exfat_file_name_entry,
+    parse_exfat_header, parse_exfat_stream_entry, parse_exfat_system_entry,
+    parse_exfat_upcase_table,
+};
+use std::collections::HashSet;
+
+/// Defines the internal extractor function for extracting exFAT file systems
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::exfat::exfat_extractor;
+///
+/// match exfat_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn exfat_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_exfat_filesystem),
+        ..Default::default()
+    }
+}
+
+/// Extracts the files and directories from an exFAT file system.
+///
+/// The reported size is the size of the volume, as recorded in its boot sector.
+pub fn extract_exfat_filesystem(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let exfat_data = &file_data[offset..];
+
+    if let Ok(exfat_header) = parse_exfat_header(exfat_data)
+        && let Some(image_data) = exfat_data.get(..exfat_header.volume_size)
+    {
+        let mut file_system = EXFATFileSystem {
+            data: image_data,
+            header: exfat_header,
+            allocation_bitmap: None,
+            upcase_table: (0..0x80u8).map(|c| c.to_ascii_uppercase() as u16).collect(),
+        };
+
+        // The root directory must, at the very least, be readable
+        if let Some(root_directory_data) =
+            file_system.cluster_chain(file_system.header.root_cluster, None, false)
+        {
+            result.size = Some(image_data.len());
+            result.success = true;
+
+            if output_directory.is_some() {
+                let chroot = Chroot::new(output_directory);
+                file_system.load_system_files(&root_directory_data);
+                let root_directory = file_system.directory_entries(&root_directory_data);
+                let file_count = file_system.extract(&chroot, root_directory);
+                debug!("Extracted {file_count} exFAT files");
+            }
+        }
+    }
+
+    result
+}
+
+/// Describes a file or directory in an exFAT directory
+struct EXFATFile {
+    name: String,
+    attributes: usize,
+    stream: EXFATStreamEntry,
+}
+
+/// An exFAT file system image
+struct EXFATFileSystem<'a> {
+    data: &'a [u8],
+    header: EXFATHeader,
+    /// One bit per cluster, set if the cluster is allocated; None if the allocation bitmap could not be read
+    allocation_bitmap: Option<Vec<u8>>,
+    /// Up-cased value of each UTF-16 character, used to validate file name hashes; defaults to ASCII up-casing
+    upcase_table: Vec<u16>,
+}
+
+impl EXFATFileSystem<'_> {
+    /// Extracts all files in the file system, returning the number of files extracted
+    fn extract(&self, chroot: &Chroot, root_directory: Vec<EXFATFile>) -> usize {
+        let mut file_count: usize = 0;
+
+        // Directories waiting to be extracted, and the first clusters of all directories seen so far
+        let mut directories: Vec<(String, Vec<EXFATFile>)> =
+            vec![("/".to_string(), root_directory)];
+        let mut seen_directories: HashSet<usize> = HashSet::from([self.header.root_cluster]);
+
+        while let Some((directory_path, files)) = directories.pop() {
+            for file in files {
+                let file_path = chroot.safe_path_join(&directory_path, &file.name);
+
+                if file.attributes & ATTR_DIRECTORY != 0 {
+                    if !seen_directories.insert(file.stream.first_cluster) {
+                        warn!(
+                            "exFAT directory {file_path} was already extracted, not following directory loop"
+                        );
+                        continue;
+                    }
+
+                    match self.file_data(&file.stream) {
+                        None => warn!("Failed to read exFAT directory {file_path}"),
+                        Some(directory_data) => {
+                            if chroot.create_directory(&file_path).is_ok() {
+                                file_count += 1;
+                                directories
+                                    .push((file_path, self.directory_entries(&directory_data)));
+                            }
+                        }
+                    }
+                } else {
+                    match self.file_data(&file.stream) {
+                        None => warn!("Failed to read exFAT file {file_path}"),
+                        Some(data) => {
+                            if chroot.create_file(&file_path, &data).is_ok() {
+                                file_count += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        file_count
+    }
+
+    /// Reads the allocation bitmap and up-case table, which are described by entries in the root directory
+    fn load_system_files(&mut self, root_directory_data: &[u8]) {
+        for entry_data in root_directory_data.chunks_exact(DIRECTORY_ENTRY_SIZE) {
+            let entry_type = entry_data[0];
+
+            if entry_type == ENTRY_TYPE_END_OF_DIRECTORY {
+                break;
+            }
+
+            if entry_type != ENTRY_TYPE_ALLOCATION_BITMAP && entry_type != ENTRY_TYPE_UPCASE_TABLE {
+                continue;
+            }
+
+            let Ok(entry) = parse_exfat_system_entry(entry_data) else {
+                continue;
+            };
+
+            let Some(mut system_file_data) = self.cluster_chain(
+                entry.first_cluster,
+                Some(entry.data_length.div_ceil(self.header.cluster_size)),
+                false,
+            ) else {
+                warn!(
+                    "Failed to read exFAT system file at cluster {}",
+                    entry.first_cluster
+                );
+                continue;
+            };
+
+            system_file_data.truncate(entry.data_length);
+
+            // Volumes with two FATs have two allocation bitmaps; like the FAT itself, only the first is used
+            if entry_type == ENTRY_TYPE_ALLOCATION_BITMAP {
+                if self.allocation_bitmap.is_none() {
+                    self.allocation_bitmap = Some(system_file_data);
+                }
+            } else if exfat_upcase_table_checksum(&system_file_data) != entry.checksum {
+                warn!("exFAT up-case table checksum mismatch, file name hashes may not validate");
+            } else {
+                self.upcase_table = parse_exfat_upcase_table(&system_file_data);
+            }
+        }
+    }
+
+    /// Returns the files in a directory, read from the file, stream extension and file name entries of each directory
+    /// entry set. Deleted entry sets, and entry sets that fail checksum validation, are skipped.
+    fn directory_entries(&self, directory_data: &[u8]) -> Vec<EXFATFile> {
+        let mut files: Vec<EXFATFile> = vec![];
+        let mut entry_offset: usize = 0;
+
+        while let Some(entry_data) =
+            directory_data.get(entry_offset..entry_offset + DIRECTORY_ENTRY_SIZE)
+        {
+            match entry_data[0] {
+                ENTRY_TYPE_END_OF_DIRECTORY => break,
+                ENTRY_TYPE_FILE => match parse_exfat_file_entry(entry_data) {
+                    Err(_) => entry_offset += DIRECTORY_ENTRY_SIZE,
+                    Ok(file_entry) => {
+                        let entry_set_size =
+                            (file_entry.secondary_count + 1) * DIRECTORY_ENTRY_SIZE;
+
+                        match directory_data.get(entry_offset..entry_offset + entry_set_size) {
+                            None => warn!("exFAT directory entry set is truncated"),
+                            Some(entry_set_data) => {
+                                if let Some(file) = self.entry_set_file(
+                                    entry_set_data,
+                                    file_entry.set_checksum,
+                                    file_entry.attributes,
+                                ) {
+                                    files.push(file);
+                                }
+                            }
+                        }
+
+                        entry_offset += entry_set_size;
+                    }
+                },
+                // Volume labels, allocation bitmaps, up-case tables, deleted entries, etc.
+                _ => entry_offset += DIRECTORY_ENTRY_SIZE,
+            }
+        }
+
+        files
+    }
+
+    /// Validates a directory entry set and assembles its file name
+    fn entry_set_file(
+        &self,
+        entry_set_data: &[u8],
+        set_checksum: usize,
+        attributes: usize,
+    ) -> Option<EXFATFile> {
+        if exfat_entry_set_checksum(entry_set_data) != set_checksum {
+            warn!("exFAT directory entry set checksum mismatch");
+            return None;
+        }
+
+        let stream = parse_exfat_stream_entry(&entry_set_data[DIRECTORY_ENTRY_SIZE..]).ok()?;
+
+        // The stream extension entry is followed by as many file name entries as are needed to store the name
+        let name_entry_count = stream.name_length.div_ceil(FILE_NAME_ENTRY_CHARACTERS);
+        let mut name: Vec<u16> = vec![];
+
+        for name_entry_data in entry_set_data[DIRECTORY_ENTRY_SIZE * 2..]
+            .chunks_exact(DIRECTORY_ENTRY_SIZE)
+            .take(name_entry_count)
+        {
+            name.extend(parse_exfat_file_name_entry(name_entry_data).ok()?);
+        }
+
+        if name.len() < stream.name_length {
+            warn!("exFAT directory entry set has too few file name entries");
+            return None;
+        }
+
+        name.truncate(stream.name_length);
+
+        let upcased_name: Vec<u16> = name
+            .iter()
+            .map(|c| self.upcase_table.get(*c as usize).copied().unwrap_or(*c))
+            .collect();
+
+        let name = String::from_utf16_lossy(&name);
+
+        if exfat_name_hash(&upcased_name) != stream.name_hash {
+            warn!("exFAT file name hash mismatch for {name}");
+        }
+
+        Some(EXFATFile {
+            name,
+            attributes,
+            stream,
+        })
+    }
+
+    /// Returns the data of a file or directory; data beyond the valid data length reads as zeros
+    fn file_data(&self, stream: &EXFATStreamEntry) -> Option<Vec<u8>> {
+        if stream.data_length == 0 {
+            return Some(vec![]);
+        }
+
+        let cluster_count = stream.data_length.div_ceil(self.header.cluster_size);
+        let mut data = self.cluster_chain(
+            stream.first_cluster,
+            Some(cluster_count),
+            stream.no_fat_chain,
+        )?;
+
+        data.truncate(stream.data_length);
+        data[stream.valid_data_length..].fill(0);
+
+        Some(data)
+    }
+
+    /// Returns the data of the clusters in a cluster chain. Contiguous chains are not recorded in the FAT, so their
+    /// cluster count must be specified; otherwise, the FAT is followed until the end of the chain, or until the
+    /// specified number of clusters has been read.
+    fn cluster_chain(
+        &self,
+        first_cluster: usize,
+        cluster_count: Option<usize>,
+        contiguous: bool,
+    ) -> Option<Vec<u8>> {
+        const END_OF_CHAIN: usize = 0xFFFFFFFF;
+
+        let mut data: Vec<u8> = vec![];
+        let mut cluster = first_cluster;
+
+        // A chain can't be longer than the number of clusters, unless it loops
+        for _ in 0..self.header.cluster_count {
+            let cluster_offset = self.cluster_offset(cluster)?;
+
+            if !self.cluster_allocated(cluster) {
+                warn!("exFAT cluster {cluster} is in use, but is not marked as allocated");
+            }
+
+            data.extend_from_slice(
+                self.data
+                    .get(cluster_offset..cluster_offset + self.header.cluster_size)?,
+            );
+
+            if cluster_count.is_some_and(|count| data.len() >= count * self.header.cluster_size) {
+                return Some(data);
+            }
+
+            cluster = match contiguous {
+                true => cluster + 1,
+                false => self.fat_entry(cluster)?,
+            };
+
+            if cluster == END_OF_CHAIN {
+                return match cluster_count {
+                    None => Some(data),
+                    Some(_) => {
+                        warn!(
+                            "exFAT cluster chain starting at cluster {first_cluster} is too short"
+                        );
+                        None
+                    }
+                };
+            }
+        }
+
+        warn!("exFAT cluster chain starting at cluster {first_cluster} loops");
+        None
+    }
+
+    /// Returns the offset of a data cluster; data clusters are numbered starting at 2
+    fn cluster_offset(&self, cluster: usize) -> Option<usize> {
+        const FIRST_DATA_CLUSTER: usize = 2;
+
+        if cluster < FIRST_DATA_CLUSTER || cluster >= self.header.cluster_count + FIRST_DATA_CLUSTER
+        {
+            warn!("Invalid exFAT cluster number: {cluster}");
+            return None;
+        }
+
+        Some(
+            self.header.cluster_heap_offset
+                + ((cluster - FIRST_DATA_CLUSTER) * self.header.cluster_size),
+        )
+    }
+
+    /// Returns true if the allocation bitmap marks the specified data cluster as allocated, or if there is no bitmap
+    fn cluster_allocated(&self, cluster: usize) -> bool {
+        const FIRST_DATA_CLUSTER: usize = 2;
+
+        let Some(allocation_bitmap) = &self.allocation_bitmap else {
+            return true;
+        };
+
+        let index = cluster - FIRST_DATA_CLUSTER;
+
+        allocation_bitmap
+            .get(index / 8)
+            .is_some_and(|bits| bits & (1 << (index % 8)) != 0)
+    }
+
+    /// Returns the FAT entry for the specified cluster, which is the next cluster in the chain
+    fn fat_entry(&self, cluster: usize) -> Option<usize> {
+        let entry_offset = self
+            .header
+            .fat_offset
+            .checked_add(cluster.checked_mul(4)?)?;
+        let entry_bytes = self.data.get(entry_offset..entry_offset + 4)?;
+        Some(u32::from_le_bytes(entry_bytes.try_into().unwrap()) as usize)
+    }
+}
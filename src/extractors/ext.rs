@@ -0,0 +1,470 @@
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::{debug, warn};
+use crate::structures::ext::{
+    EXTENT_ENTRY_SIZE, EXTHeader, EXTInode, INODE_BLOCK_MAP_SIZE, INODE_FLAG_EXTENTS,
+    INODE_FLAG_INLINE_DATA, ROOT_INODE, parse_ext_directory_entry, parse_ext_extent,
+    parse_ext_extent_header, parse_ext_extent_index, parse_ext_group_descriptor, parse_ext_header,
+    parse_ext_inode,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Defines the internal extractor function for extracting EXT2/3/4 file systems
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::ext::ext_extractor;
+///
+/// match ext_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn ext_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_ext_filesystem),
+        ..Default::default()
+    }
+}
+
+/// Extracts the directories, regular files, symlinks, device files, FIFOs and sockets from an EXT2/3/4 file system.
+/// File data may be mapped by either block maps or extent trees. Permissions, ownership and modification times are
+/// restored where possible.
+///
+/// The reported size is the size of the file system, as recorded in its superblock.
+pub fn extract_ext_filesystem(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let ext_data = &file_data[offset..];
+
+    if let Ok(ext_header) = parse_ext_header(ext_data)
+        && let Some(image_data) = ext_data.get(..ext_header.image_size)
+    {
+        let file_system = EXTFileSystem {
+            data: image_data,
+            header: ext_header,
+        };
+
+        // The root directory must, at the very least, be valid
+        if let Some(root_inode) = file_system.inode(ROOT_INODE)
+            && root_inode.mode & S_IFMT == S_IFDIR
+        {
+            result.size = Some(image_data.len());
+            result.success = true;
+
+            if output_directory.is_some() {
+                let chroot = Chroot::new(output_directory);
+                let file_count = file_system.extract(&chroot, &root_inode);
+                debug!("Extracted {file_count} EXT files");
+            }
+        }
+    }
+
+    result
+}
+
+// File type bits of the inode mode
+const S_IFMT: usize = 0xF000;
+const S_IFIFO: usize = 0x1000;
+const S_IFCHR: usize = 0x2000;
+const S_IFDIR: usize = 0x4000;
+const S_IFBLK: usize = 0x6000;
+const S_IFREG: usize = 0x8000;
+const S_IFLNK: usize = 0xA000;
+const S_IFSOCK: usize = 0xC000;
+
+/// Permission bits of the inode mode
+const PERMISSION_BITS: usize = 0o7777;
+
+/// Describes a run of consecutive file blocks stored in consecutive file system blocks
+struct DataRun {
+    file_block: usize,
+    start_block: usize,
+    block_count: usize,
+}
+
+/// An EXT file system image
+struct EXTFileSystem<'a> {
+    data: &'a [u8],
+    header: EXTHeader,
+}
+
+impl EXTFileSystem<'_> {
+    /// Extracts all files in the file system, returning the number of files extracted
+    fn extract(&self, chroot: &Chroot, root_inode: &EXTInode) -> usize {
+        let mut file_count: usize = 0;
+
+        // Directories waiting to be extracted, and all directories seen so far, to detect directory loops
+        let mut directories: Vec<(EXTInode, String)> = vec![(root_inode.clone(), "/".to_string())];
+        let mut seen_directories: HashSet<usize> = HashSet::from([ROOT_INODE]);
+
+        // Paths of extracted files, keyed by inode number, for creating hard links
+        let mut extracted_files: HashMap<usize, String> = HashMap::new();
+
+        // Metadata is restored once everything has been extracted
+        let mut extracted_inodes: Vec<(String, EXTInode)> = vec![];
+
+        while let Some((directory_inode, directory_path)) = directories.pop() {
+            for (name, inode_number) in self.directory_entries(&directory_inode) {
+                let file_path = chroot.safe_path_join(&directory_path, &name);
+
+                let Some(inode) = self.inode(inode_number) else {
+                    warn!("Failed to read EXT inode {inode_number} for {file_path}");
+                    continue;
+                };
+
+                let file_type = inode.mode & S_IFMT;
+
+                // Directories can't be hard linked, anything else may have been extracted already
+                if file_type != S_IFDIR
+                    && let Some(link_target) = extracted_files.get(&inode_number)
+                {
+                    if chroot.create_hardlink(&file_path, link_target).is_ok() {
+                        file_count += 1;
+                    }
+                    continue;
+                }
+
+                let extracted = match file_type {
+                    S_IFDIR => {
+                        if !seen_directories.insert(inode_number) {
+                            warn!(
+                                "EXT directory {file_path} was already extracted, not following directory loop"
+                            );
+                            false
+                        } else if chroot.create_directory(&file_path).is_ok() {
+                            directories.push((inode.clone(), file_path.clone()));
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                    S_IFREG => match self.file_data(&inode) {
+                        None => false,
+                        Some(data) => chroot.create_sparse_file(&file_path, &data).is_ok(),
+                    },
+                    S_IFLNK => match self.symlink_target(&inode) {
+                        None => false,
+                        Some(target) => chroot.create_symlink(&file_path, target).is_ok(),
+                    },
+                    S_IFCHR | S_IFBLK => {
+                        let (major, minor) = device_number(&inode);
+
+                        match file_type {
+                            S_IFCHR => chroot.create_character_device(&file_path, major, minor),
+                            _ => chroot.create_block_device(&file_path, major, minor),
+                        }
+                        .is_ok()
+                    }
+                    S_IFIFO => chroot.create_fifo(&file_path).is_ok(),
+                    S_IFSOCK => chroot.create_socket(&file_path).is_ok(),
+                    _ => {
+                        warn!("Unknown EXT file type {file_type:#X} for {file_path}");
+                        false
+                    }
+                };
+
+                if extracted {
+                    file_count += 1;
+
+                    if file_type != S_IFDIR {
+                        extracted_files.insert(inode_number, file_path.clone());
+                    }
+
+                    extracted_inodes.push((file_path, inode));
+                } else {
+                    warn!("Failed to extract EXT file {file_path}");
+                }
+            }
+        }
+
+        // Files are restored before the directories that contain them, so that read-only directories are made read-only last
+        for (file_path, inode) in extracted_inodes.iter().rev() {
+            // Timestamps are signed 32 bit values
+            let _ = chroot.set_modification_time(file_path, inode.mtime as u32 as i32 as i64);
+            let _ = chroot.set_owner(file_path, inode.uid as u32, inode.gid as u32);
+            let _ = chroot.set_permissions(file_path, (inode.mode & PERMISSION_BITS) as u32);
+        }
+
+        file_count
+    }
+
+    /// Returns the data of the specified file system block
+    fn block(&self, block_number: usize) -> Option<&[u8]> {
+        let start = block_number.checked_mul(self.header.block_size)?;
+        self.data
+            .get(start..start.checked_add(self.header.block_size)?)
+    }
+
+    /// Reads and parses the specified inode
+    fn inode(&self, inode_number: usize) -> Option<EXTInode> {
+        if inode_number == 0
+            || inode_number > self.header.inodes_count
+            || self.header.inodes_per_group == 0
+        {
+            return None;
+        }
+
+        let index = inode_number - 1;
+        let group = index / self.header.inodes_per_group;
+
+        // The group descriptor table immediately follows the superblock
+        let descriptor_offset = (self.header.first_data_block + 1)
+            .checked_mul(self.header.block_size)?
+            .checked_add(group.checked_mul(self.header.descriptor_size)?)?;
+        let inode_table =
+            parse_ext_group_descriptor(self.data.get(descriptor_offset..)?, &self.header).ok()?;
+
+        let inode_offset = inode_table
+            .checked_mul(self.header.block_size)?
+            .checked_add((index % self.header.inodes_per_group) * self.header.inode_size)?;
+
+        parse_ext_inode(
+            self.data
+                .get(inode_offset..inode_offset.checked_add(self.header.inode_size)?)?,
+        )
+        .ok()
+    }
+
+    /// Returns the names and inode numbers of the entries in a directory, excluding the "." and ".." entries
+    fn directory_entries(&self, directory_inode: &EXTInode) -> Vec<(String, usize)> {
+        let mut entries = vec![];
+
+        let Some(directory_data) = self.file_data(directory_inode) else {
+            return entries;
+        };
+
+        // Directory entries never span blocks
+        for block in directory_data.chunks(self.header.block_size) {
+            let mut entry_offset: usize = 0;
+
+            while let Some(entry_data) = block.get(entry_offset..) {
+                let Ok(entry) = parse_ext_directory_entry(entry_data, &self.header) else {
+                    break;
+                };
+
+                entry_offset += entry.entry_size;
+
+                // Unused entries have an inode number of 0
+                if entry.inode != 0 && entry.name != b"." && entry.name != b".." {
+                    entries.push((
+                        String::from_utf8_lossy(&entry.name).to_string(),
+                        entry.inode,
+                    ));
+                }
+            }
+        }
+
+        entries
+    }
+
+    /// Returns the target of a symlink
+    fn symlink_target(&self, inode: &EXTInode) -> Option<String> {
+        // Blocks are counted in 512 byte sectors, and include any extended attribute block
+        const SECTOR_SIZE: usize = 512;
+
+        let mut data_sectors = inode.sectors;
+        if inode.file_acl != 0 {
+            data_sectors = data_sectors.saturating_sub(self.header.block_size / SECTOR_SIZE);
+        }
+
+        // Short symlink targets are stored in the inode's block map, if no data blocks were allocated
+        let target = if inode.flags & (INODE_FLAG_EXTENTS | INODE_FLAG_INLINE_DATA) == 0
+            && inode.size < INODE_BLOCK_MAP_SIZE
+            && data_sectors == 0
+        {
+            inode.block[..inode.size].to_vec()
+        } else {
+            self.file_data(inode)?
+        };
+
+        Some(String::from_utf8_lossy(&target).to_string())
+    }
+
+    /// Returns the data of a regular file, directory, or symlink; unmapped and uninitialized blocks read as zeros
+    fn file_data(&self, inode: &EXTInode) -> Option<Vec<u8>> {
+        // Sparse files may be larger than the file system itself, but not unreasonably so
+        const MAX_SPARSE_FILE_SIZE: usize = 1024 * 1024 * 1024;
+
+        if inode.flags & INODE_FLAG_INLINE_DATA != 0 {
+            warn!("EXT inline data is not supported");
+            return None;
+        }
+
+        if inode.size > self.data.len().max(MAX_SPARSE_FILE_SIZE) {
+            warn!("EXT file size {:#X} is unreasonably large", inode.size);
+            return None;
+        }
+
+        let block_size = self.header.block_size;
+        let file_block_count = inode.size.div_ceil(block_size);
+        let mut data_runs: Vec<DataRun> = vec![];
+
+        if inode.flags & INODE_FLAG_EXTENTS != 0 {
+            self.extent_runs(&inode.block, None, &mut data_runs)?;
+        } else {
+            let mut file_block: usize = 0;
+
+            // The first 12 block pointers are direct, followed by single, double and triple indirect block pointers
+            for (i, block_pointer) in inode.block.chunks_exact(4).enumerate() {
+                let depth = i.saturating_sub(11);
+                self.block_map_runs(
+                    block_pointer,
+                    depth,
+                    &mut file_block,
+                    file_block_count,
+                    &mut data_runs,
+                );
+            }
+        }
+
+        let mut data = vec![0; inode.size];
+
+        for data_run in data_runs {
+            for i in 0..data_run.block_count {
+                let file_offset = data_run
+                    .file_block
+                    .checked_add(i)?
+                    .checked_mul(block_size)?;
+
+                if file_offset >= inode.size {
+                    break;
+                }
+
+                let block_data = self.block(data_run.start_block.checked_add(i)?)?;
+                let copy_size = block_size.min(inode.size - file_offset);
+                data[file_offset..file_offset + copy_size]
+                    .copy_from_slice(&block_data[..copy_size]);
+            }
+        }
+
+        Some(data)
+    }
+
+    /// Walks an extent tree node, appending the data runs described by its leaf nodes
+    fn extent_runs(
+        &self,
+        node_data: &[u8],
+        expected_depth: Option<usize>,
+        data_runs: &mut Vec<DataRun>,
+    ) -> Option<()> {
+        // Maximum depth of an extent tree
+        const MAX_EXTENT_DEPTH: usize = 5;
+
+        let node_header = parse_ext_extent_header(node_data).ok()?;
+
+        // Each child node must be exactly one level lower than its parent, which also prevents loops
+        if node_header.depth > MAX_EXTENT_DEPTH
+            || expected_depth.is_some_and(|depth| depth != node_header.depth)
+        {
+            warn!("Invalid EXT extent tree depth: {}", node_header.depth);
+            return None;
+        }
+
+        for entry_data in node_data[EXTENT_ENTRY_SIZE..]
+            .chunks_exact(EXTENT_ENTRY_SIZE)
+            .take(node_header.entries)
+        {
+            if node_header.depth == 0 {
+                let extent = parse_ext_extent(entry_data).ok()?;
+
+                if !extent.uninitialized {
+                    data_runs.push(DataRun {
+                        file_block: extent.file_block,
+                        start_block: extent.start_block,
+                        block_count: extent.block_count,
+                    });
+                }
+            } else {
+                let child_block = parse_ext_extent_index(entry_data).ok()?;
+                self.extent_runs(
+                    self.block(child_block)?,
+                    Some(node_header.depth - 1),
+                    data_runs,
+                )?;
+            }
+        }
+
+        Some(())
+    }
+
+    /// Walks a block map pointer, appending a data run for each mapped block. Block pointers of 0 are holes.
+    fn block_map_runs(
+        &self,
+        block_pointer: &[u8],
+        depth: usize,
+        file_block: &mut usize,
+        file_block_count: usize,
+        data_runs: &mut Vec<DataRun>,
+    ) {
+        if *file_block >= file_block_count {
+            return;
+        }
+
+        let block_number = u32::from_le_bytes(block_pointer.try_into().unwrap()) as usize;
+
+        // Number of file blocks mapped by this pointer
+        let pointers_per_block = self.header.block_size / 4;
+        let mapped_blocks = pointers_per_block.saturating_pow(depth as u32);
+
+        if block_number == 0 {
+            *file_block = file_block.saturating_add(mapped_blocks);
+        } else if depth == 0 {
+            data_runs.push(DataRun {
+                file_block: *file_block,
+                start_block: block_number,
+                block_count: 1,
+            });
+            *file_block += 1;
+        } else {
+            match self.block(block_number) {
+                None => {
+                    warn!("EXT indirect block {block_number} is outside of the file system");
+                    *file_block = file_block.saturating_add(mapped_blocks);
+                }
+                Some(indirect_block) => {
+                    for child_pointer in indirect_block.chunks_exact(4) {
+                        self.block_map_runs(
+                            child_pointer,
+                            depth - 1,
+                            file_block,
+                            file_block_count,
+                            data_runs,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Returns the major and minor numbers of a device file, which may be stored in either the old or new encoding
+fn device_number(inode: &EXTInode) -> (usize, usize) {
+    let old_device = u32::from_le_bytes(inode.block[0..4].try_into().unwrap()) as usize;
+    let new_device = u32::from_le_bytes(inode.block[4..8].try_into().unwrap()) as usize;
+
+    if old_device != 0 {
+        ((old_device >> 8) & 0xFF, old_device & 0xFF)
+    } else {
+        (
+            (new_device >> 8) & 0xFFF,
+            (new_device & 0xFF) | ((new_device >> 12) & 0xFFF00),
+        )
+    }
+}
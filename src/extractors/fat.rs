@@ -0,0 +1,412 @@
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::{debug, warn};
+use crate::structures::fat::{
+    ATTR_DIRECTORY, ATTR_VOLUME_ID, DIRECTORY_ENTRY_SIZE, FATDirectoryEntry, FATHeader,
+    FATLongNameEntry, fat_short_name_checksum, parse_fat_directory_entry, parse_fat_header,
+    parse_fat_long_name_entry,
+};
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Defines the internal extractor function for extracting FAT12/16/32 file systems
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::fat::fat_extractor;
+///
+/// match fat_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn fat_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_fat_filesystem),
+        ..Default::default()
+    }
+}
+
+/// Defines the internal extractor function for extracting FAT12/16/32 file systems, including deleted files.
+///
+/// Deleted files are not recovered by default; to recover them, register this extractor for the `fat` signature with
+/// `Binwalk::register_extractor`.
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::fat::fat_deleted_files_extractor;
+///
+/// match fat_deleted_files_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn fat_deleted_files_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_fat_filesystem_with_deleted_files),
+        ..Default::default()
+    }
+}
+
+/// Extracts the files and directories from a FAT12/16/32 file system, using VFAT long file names where available.
+///
+/// The reported size is the size of the file system, as recorded in its boot sector.
+pub fn extract_fat_filesystem(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    extract_fat(file_data, offset, output_directory, false)
+}
+
+/// Identical to `extract_fat_filesystem`, but also recovers deleted files.
+///
+/// The clusters of deleted files have been released, so their data is assumed to be stored contiguously, and may
+/// have been overwritten since. Deleted directories are not recovered.
+pub fn extract_fat_filesystem_with_deleted_files(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    extract_fat(file_data, offset, output_directory, true)
+}
+
+fn extract_fat(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+    recover_deleted_files: bool,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let fat_data = &file_data[offset..];
+
+    if let Ok(fat_header) = parse_fat_header(fat_data)
+        && let Some(image_data) = fat_data.get(..fat_header.total_size)
+    {
+        let file_system = FATFileSystem {
+            data: image_data,
+            header: fat_header,
+            recover_deleted_files,
+        };
+
+        // The root directory must, at the very least, be readable
+        if let Some(root_directory) = file_system.root_directory() {
+            result.size = Some(image_data.len());
+            result.success = true;
+
+            if output_directory.is_some() {
+                let chroot = Chroot::new(output_directory);
+                let file_count = file_system.extract(&chroot, root_directory);
+                debug!("Extracted {file_count} FAT files");
+            }
+        }
+    }
+
+    result
+}
+
+/// Describes a file or directory in a FAT directory
+struct FATFile {
+    name: String,
+    entry: FATDirectoryEntry,
+}
+
+/// A FAT file system image
+struct FATFileSystem<'a> {
+    data: &'a [u8],
+    header: FATHeader,
+    recover_deleted_files: bool,
+}
+
+impl FATFileSystem<'_> {
+    /// Extracts all files in the file system, returning the number of files extracted
+    fn extract(&self, chroot: &Chroot, root_directory: Vec<FATFile>) -> usize {
+        let mut file_count: usize = 0;
+
+        // Directories waiting to be extracted, and the first clusters of all directories seen so far
+        let mut directories: Vec<(String, Vec<FATFile>)> = vec![("/".to_string(), root_directory)];
+        let mut seen_directories: HashSet<usize> = HashSet::new();
+
+        while let Some((directory_path, files)) = directories.pop() {
+            for file in files {
+                let file_path = chroot.safe_path_join(&directory_path, &file.name);
+
+                if file.entry.attributes & ATTR_DIRECTORY != 0 {
+                    if file.entry.deleted {
+                        debug!("Not recovering deleted FAT directory {file_path}");
+                        continue;
+                    }
+
+                    if !seen_directories.insert(file.entry.first_cluster) {
+                        warn!(
+                            "FAT directory {file_path} was already extracted, not following directory loop"
+                        );
+                        continue;
+                    }
+
+                    match self.cluster_chain_data(file.entry.first_cluster) {
+                        None => warn!("Failed to read FAT directory {file_path}"),
+                        Some(directory_data) => {
+                            if chroot.create_directory(&file_path).is_ok() {
+                                file_count += 1;
+                                directories
+                                    .push((file_path, self.directory_entries(&directory_data)));
+                            }
+                        }
+                    }
+                } else if file.entry.deleted && Path::new(&file_path).exists() {
+                    warn!("Not recovering deleted FAT file {file_path}: path already exists");
+                } else {
+                    match self.file_data(&file.entry) {
+                        None => warn!("Failed to read FAT file {file_path}"),
+                        Some(data) => {
+                            if chroot.create_file(&file_path, &data).is_ok() {
+                                file_count += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        file_count
+    }
+
+    /// Returns the entries of the root directory, which is a fixed size region for FAT12/16, and a cluster chain for FAT32
+    fn root_directory(&self) -> Option<Vec<FATFile>> {
+        let root_directory_data = match self.header.is_fat32 {
+            true => self.cluster_chain_data(self.header.root_cluster)?,
+            false => self
+                .data
+                .get(
+                    self.header.root_directory_offset
+                        ..self.header.root_directory_offset + self.header.root_directory_size,
+                )?
+                .to_vec(),
+        };
+
+        Some(self.directory_entries(&root_directory_data))
+    }
+
+    /// Returns the files in a directory, excluding the "." and ".." entries and the volume label
+    fn directory_entries(&self, directory_data: &[u8]) -> Vec<FATFile> {
+        // A first name byte of 0 marks the end of the directory
+        const END_OF_DIRECTORY: u8 = 0;
+
+        let mut files: Vec<FATFile> = vec![];
+
+        // Long file name entries immediately precede the short name entry they belong to
+        let mut long_name_entries: Vec<FATLongNameEntry> = vec![];
+
+        for entry_data in directory_data.chunks_exact(DIRECTORY_ENTRY_SIZE) {
+            if entry_data[0] == END_OF_DIRECTORY {
+                break;
+            }
+
+            if let Ok(long_name_entry) = parse_fat_long_name_entry(entry_data) {
+                long_name_entries.push(long_name_entry);
+                continue;
+            }
+
+            let Ok(entry) = parse_fat_directory_entry(entry_data) else {
+                break;
+            };
+
+            let long_name = long_file_name(&long_name_entries, &entry);
+            long_name_entries.clear();
+
+            if entry.attributes & ATTR_VOLUME_ID != 0
+                || (entry.deleted && !self.recover_deleted_files)
+                || (!entry.deleted && (entry.short_name == "." || entry.short_name == ".."))
+            {
+                continue;
+            }
+
+            files.push(FATFile {
+                name: long_name.unwrap_or_else(|| entry.short_name.clone()),
+                entry,
+            });
+        }
+
+        files
+    }
+
+    /// Returns the data of a file
+    fn file_data(&self, entry: &FATDirectoryEntry) -> Option<Vec<u8>> {
+        if entry.size == 0 {
+            return Some(vec![]);
+        }
+
+        // The cluster chains of deleted files have been released; assume that their clusters are contiguous
+        let mut data = match entry.deleted {
+            true => {
+                let cluster_count = entry.size.div_ceil(self.header.cluster_size);
+                let start = self.cluster_offset(entry.first_cluster)?;
+                let end = self
+                    .cluster_offset(entry.first_cluster.checked_add(cluster_count - 1)?)?
+                    + self.header.cluster_size;
+                self.data.get(start..end)?.to_vec()
+            }
+            false => self.cluster_chain_data(entry.first_cluster)?,
+        };
+
+        if data.len() < entry.size {
+            warn!(
+                "FAT cluster chain is shorter than the file size: {:#X} < {:#X}",
+                data.len(),
+                entry.size
+            );
+            return None;
+        }
+
+        data.truncate(entry.size);
+        Some(data)
+    }
+
+    /// Returns the data of all clusters in a cluster chain
+    fn cluster_chain_data(&self, first_cluster: usize) -> Option<Vec<u8>> {
+        let mut data: Vec<u8> = vec![];
+        let mut cluster = first_cluster;
+
+        // A chain can't be longer than the number of clusters, unless it loops
+        for _ in 0..self.header.cluster_count {
+            let cluster_offset = self.cluster_offset(cluster)?;
+            data.extend_from_slice(
+                self.data
+                    .get(cluster_offset..cluster_offset + self.header.cluster_size)?,
+            );
+
+            cluster = self.fat_entry(cluster)?;
+
+            if cluster >= self.end_of_chain() {
+                return Some(data);
+            }
+        }
+
+        warn!("FAT cluster chain starting at cluster {first_cluster} loops");
+        None
+    }
+
+    /// Returns the offset of a data cluster; data clusters are numbered starting at 2
+    fn cluster_offset(&self, cluster: usize) -> Option<usize> {
+        const FIRST_DATA_CLUSTER: usize = 2;
+
+        if cluster < FIRST_DATA_CLUSTER || cluster >= self.header.cluster_count + FIRST_DATA_CLUSTER
+        {
+            warn!("Invalid FAT cluster number: {cluster}");
+            return None;
+        }
+
+        Some(self.header.data_offset + ((cluster - FIRST_DATA_CLUSTER) * self.header.cluster_size))
+    }
+
+    /// Returns the FAT entry for the specified cluster, which is the next cluster in the chain
+    fn fat_entry(&self, cluster: usize) -> Option<usize> {
+        // The upper 4 bits of FAT32 entries are reserved
+        const FAT32_ENTRY_MASK: usize = 0x0FFFFFFF;
+
+        let fat = self.data.get(self.header.fat_offset..)?;
+
+        match self.header.fat_bits {
+            // FAT12 entries are packed, two entries to every three bytes
+            12 => {
+                let entry_offset = cluster + (cluster / 2);
+                let entry_bytes = fat.get(entry_offset..entry_offset + 2)?;
+                let entry = u16::from_le_bytes(entry_bytes.try_into().unwrap()) as usize;
+
+                match cluster % 2 {
+                    0 => Some(entry & 0xFFF),
+                    _ => Some(entry >> 4),
+                }
+            }
+            16 => {
+                let entry_bytes = fat.get(cluster * 2..(cluster * 2) + 2)?;
+                Some(u16::from_le_bytes(entry_bytes.try_into().unwrap()) as usize)
+            }
+            _ => {
+                let entry_bytes = fat.get(cluster * 4..(cluster * 4) + 4)?;
+                Some(
+                    u32::from_le_bytes(entry_bytes.try_into().unwrap()) as usize & FAT32_ENTRY_MASK,
+                )
+            }
+        }
+    }
+
+    /// Returns the smallest FAT entry value that marks the end of a cluster chain
+    fn end_of_chain(&self) -> usize {
+        match self.header.fat_bits {
+            12 => 0xFF8,
+            16 => 0xFFF8,
+            _ => 0x0FFFFFF8,
+        }
+    }
+}
+
+/// Assembles the long file name stored in the long name entries that precede a short name entry.
+/// Returns None if the long name entries don't belong to the short name entry.
+fn long_file_name(
+    long_name_entries: &[FATLongNameEntry],
+    short_entry: &FATDirectoryEntry,
+) -> Option<String> {
+    let last_part = long_name_entries.first()?;
+
+    if long_name_entries
+        .iter()
+        .any(|entry| entry.checksum != last_part.checksum || entry.deleted != short_entry.deleted)
+    {
+        return None;
+    }
+
+    // The sequence numbers and the first character of the short name are lost when an entry is deleted, so only
+    // the long name entries of existing files can be validated
+    if !short_entry.deleted {
+        let sequence_ok = long_name_entries
+            .iter()
+            .rev()
+            .enumerate()
+            .all(|(i, entry)| entry.sequence == i + 1);
+
+        if !last_part.is_last
+            || !sequence_ok
+            || last_part.checksum != fat_short_name_checksum(&short_entry.raw_name)
+        {
+            return None;
+        }
+    }
+
+    // Parts of the long name are stored in reverse order
+    let long_name: Vec<u16> = long_name_entries
+        .iter()
+        .rev()
+        .flat_map(|entry| entry.name.clone())
+        .collect();
+
+    match long_name.is_empty() {
+        true => None,
+        false => Some(String::from_utf16_lossy(&long_name)),
+    }
+}
@@ -58,8 +58,9 @@ pub fn extract_gif_image(
                 // Do extraction, if requested
                 if output_directory.is_some() {
                     let chroot = Chroot::new(output_directory);
-                    result.success =
-                        chroot.carve_file(OUTFILE_NAME, file_data, offset, result.size.unwrap());
+                    result.success = chroot
+                        .carve_file(OUTFILE_NAME, file_data, offset, result.size.unwrap())
+                        .is_ok();
                 }
             }
         }
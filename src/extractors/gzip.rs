@@ -27,6 +27,7 @@ use crate::structures::gzip::parse_gzip_header;
 pub fn gzip_extractor() -> Extractor {
     Extractor {
         utility: ExtractorType::Internal(gzip_decompress),
+        trial_decoder: Some(gzip_trial),
         ..Default::default()
     }
 }
@@ -37,6 +38,9 @@ pub fn gzip_decompress(
     offset: usize,
     output_directory: Option<&str>,
 ) -> ExtractionResult {
+    // Size of the CRC32 and ISIZE fields that follow the deflate data
+    const GZIP_TRAILER_SIZE: usize = 8;
+
     let mut exresult = ExtractionResult {
         ..Default::default()
     };
@@ -51,10 +55,23 @@ pub fn gzip_decompress(
                 inflate::inflate_decompressor(file_data, deflate_data_start, output_directory);
             if inflate_result.success {
                 exresult.success = true;
-                exresult.size = Some(inflate_result.size);
+                // Report the size of the entire gzip stream, not just the deflate data
+                exresult.size = Some(gzip_header.size + inflate_result.size + GZIP_TRAILER_SIZE);
             }
         }
     }
 
     exresult
 }
+
+/// Trial decoder for gzip compressed data; decompresses up to `trial_size` bytes of the deflate data that follows
+/// the gzip header
+pub fn gzip_trial(file_data: &[u8], offset: usize, trial_size: usize) -> bool {
+    match parse_gzip_header(&file_data[offset..]) {
+        Err(_) => false,
+        Ok(gzip_header) => file_data
+            .get(offset + gzip_header.size..)
+            .and_then(|deflate_data| inflate::inflate_trial(deflate_data, false, trial_size))
+            .is_some(),
+    }
+}
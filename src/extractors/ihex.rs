@@ -0,0 +1,84 @@
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::debug;
+use crate::structures::common::StructureError;
+use crate::structures::ihex::{MemoryImage, parse_ihex};
+
+/// Defines the internal extractor function for converting Intel HEX files to binary
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::ihex::ihex_extractor;
+///
+/// match ihex_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn ihex_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_ihex),
+        ..Default::default()
+    }
+}
+
+/// Converts Intel HEX records to a raw binary image, starting at the lowest record address
+pub fn extract_ihex(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    const OUTPUT_FILE_NAME: &str = "ihex.bin";
+
+    write_memory_image(
+        parse_ihex(&file_data[offset..]),
+        OUTPUT_FILE_NAME,
+        output_directory,
+    )
+}
+
+/// Writes a decoded Intel HEX or S-record image to `output_file_name`; the reported size is the size of the text records
+pub fn write_memory_image(
+    memory_image: Result<MemoryImage, StructureError>,
+    output_file_name: &str,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    if let Ok(image) = memory_image
+        && let Some((start_address, end_address)) = image.address_span()
+    {
+        debug!(
+            "Decoded {} records spanning addresses {start_address:#X}-{end_address:#X}",
+            image.records.len()
+        );
+
+        if output_directory.is_some() {
+            let chroot = Chroot::new(output_directory);
+
+            if chroot
+                .create_file(output_file_name, &image.to_binary())
+                .is_err()
+            {
+                return result;
+            }
+        }
+
+        result.size = Some(image.size);
+        result.success = true;
+    }
+
+    result
+}
@@ -1,6 +1,7 @@
-use crate::extractors::common::Chroot;
+use crate::extractors::common::{Chroot, SliceReader};
 use adler32::RollingAdler32;
 use flate2::bufread::DeflateDecoder;
+use flate2::{Decompress, FlushDecompress, Status};
 use std::io::Read;
 
 #[derive(Debug, Default, Clone)]
@@ -28,7 +29,7 @@ pub fn inflate_decompressor(
 
     let mut adler32_checksum = RollingAdler32::new();
     let mut decompressed_buffer = [0; BLOCK_SIZE];
-    let mut decompressor = DeflateDecoder::new(&file_data[offset..]);
+    let mut decompressor = DeflateDecoder::new(SliceReader::new(file_data, offset));
 
     /*
      * Loop through all compressed data and decompress it.
@@ -54,7 +55,10 @@ pub fn inflate_decompressor(
 
                     if output_directory.is_some() {
                         let chroot = Chroot::new(output_directory);
-                        if !chroot.append_to_file(OUTPUT_FILE_NAME, &decompressed_buffer[0..n]) {
+                        if chroot
+                            .append_to_file(OUTPUT_FILE_NAME, &decompressed_buffer[0..n])
+                            .is_err()
+                        {
                             // If writing data to file fails, break
                             break;
                         }
@@ -79,3 +83,42 @@ pub fn inflate_decompressor(
 
     result
 }
+
+/// Decompresses deflate data, with or without a ZLIB header, until at least `trial_size` bytes have been decompressed
+/// or the end of the deflate stream is reached, whichever comes first.
+/// For internal use, see `extractors::common::TrialDecoder`.
+///
+/// Returns the number of bytes decompressed, or None if the data is corrupt or truncated.
+pub fn inflate_trial(deflate_data: &[u8], zlib_header: bool, trial_size: usize) -> Option<usize> {
+    // Size of decompression buffer
+    const BLOCK_SIZE: usize = 8192;
+
+    let mut decompressed_buffer = [0; BLOCK_SIZE];
+    let mut decompressor = Decompress::new(zlib_header);
+
+    while (decompressor.total_out() as usize) < trial_size {
+        let stream_offset = decompressor.total_in() as usize;
+        let bytes_decompressed = decompressor.total_out();
+
+        let status = decompressor
+            .decompress(
+                &deflate_data[stream_offset..],
+                &mut decompressed_buffer,
+                FlushDecompress::None,
+            )
+            .ok()?;
+
+        if status == Status::StreamEnd {
+            break;
+        }
+
+        // No progress can be made; the stream is truncated
+        if decompressor.total_in() as usize == stream_offset
+            && decompressor.total_out() == bytes_decompressed
+        {
+            return None;
+        }
+    }
+
+    Some(decompressor.total_out() as usize)
+}
@@ -60,12 +60,14 @@ pub fn extract_jboot_sch2_kernel(
 
                     if output_directory.is_some() {
                         let chroot = Chroot::new(output_directory);
-                        result.success = chroot.carve_file(
-                            OUTFILE_NAME,
-                            file_data,
-                            kernel_start,
-                            sch2_header.kernel_size,
-                        );
+                        result.success = chroot
+                            .carve_file(
+                                OUTFILE_NAME,
+                                file_data,
+                                kernel_start,
+                                sch2_header.kernel_size,
+                            )
+                            .is_ok();
                     }
                 }
             }
@@ -49,8 +49,9 @@ pub fn extract_jpeg_image(
 
         if output_directory.is_some() {
             let chroot = Chroot::new(output_directory);
-            result.success =
-                chroot.carve_file(OUTFILE_NAME, file_data, offset, result.size.unwrap());
+            result.success = chroot
+                .carve_file(OUTFILE_NAME, file_data, offset, result.size.unwrap())
+                .is_ok();
         }
     }
 
@@ -90,6 +91,11 @@ fn get_jpeg_data_size(jpeg_data: &[u8]) -> Option<usize> {
                 // Include marker magic byte in side of the marker
                 next_marker_offset += 1;
 
+                // Markers may be preceded by any number of 0xFF fill bytes
+                while jpeg_data.get(next_marker_offset) == Some(&MARKER_MAGIC) {
+                    next_marker_offset += 1;
+                }
+
                 // Read the marker ID byte
                 match jpeg_data.get(next_marker_offset) {
                     None => {
@@ -0,0 +1,138 @@
+use crate::common::crc16;
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::extractors::lzh::{lzh_decompress, lzh_dynamic_decompress};
+use crate::logging::{debug, warn};
+use crate::structures::lha::{LHA_DIRECTORY_METHOD, LHAHeader, parse_lha_header};
+
+/// Defines the internal extractor function for extracting LHA/LZH archives
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::lha::lha_extractor;
+///
+/// match lha_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn lha_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_lha_archive),
+        ..Default::default()
+    }
+}
+
+/// Extracts the members of an LHA archive.
+///
+/// Stored (-lh0-, -lz4-) members, and members compressed with the -lh1- and -lh4- through -lh7- methods, are
+/// supported. The -lh2- and -lh3- methods were only ever experimental, and are not supported; such members are skipped
+/// with a warning, just like any other unknown method. Members are validated against their CRCs, and corrupt or
+/// unsupported members are skipped.
+///
+/// The reported size is the size of all consecutive members, plus the end of archive marker, if present.
+/// If no output directory is specified, only the member headers are validated.
+pub fn extract_lha_archive(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    // A header size of 0 marks the end of the archive
+    const END_OF_ARCHIVE: u8 = 0;
+
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let lha_data = &file_data[offset..];
+    let chroot = Chroot::new(output_directory);
+
+    let mut member_count: usize = 0;
+    let mut extracted_count: usize = 0;
+    let mut member_offset: usize = 0;
+
+    while let Some(header_byte) = lha_data.get(member_offset) {
+        if *header_byte == END_OF_ARCHIVE {
+            member_offset += 1;
+            break;
+        }
+
+        let Ok(header) = parse_lha_header(&lha_data[member_offset..]) else {
+            break;
+        };
+
+        let data_start = member_offset + header.header_size;
+        let Some(member_data) = lha_data.get(data_start..data_start + header.compressed_size)
+        else {
+            warn!("LHA member {} is truncated", header.name);
+            break;
+        };
+
+        member_offset = data_start + header.compressed_size;
+        member_count += 1;
+
+        if output_directory.is_some() && extract_lha_member(&chroot, &header, member_data) {
+            extracted_count += 1;
+        }
+    }
+
+    if member_count > 0 {
+        debug!("Extracted {extracted_count} of {member_count} LHA members");
+        result.size = Some(member_offset);
+        result.success = true;
+    }
+
+    result
+}
+
+/// Extracts a single LHA member; returns true if the member was extracted
+fn extract_lha_member(chroot: &Chroot, header: &LHAHeader, member_data: &[u8]) -> bool {
+    let member_path = chroot.safe_path_join("/", &header.name);
+
+    let decompressed_data = match header.method.as_str() {
+        LHA_DIRECTORY_METHOD => return chroot.create_directory(&member_path).is_ok(),
+        "-lh0-" | "-lz4-" => Ok(member_data.to_vec()),
+        "-lh1-" => lzh_dynamic_decompress(member_data, header.uncompressed_size),
+        "-lh4-" => lzh_decompress(member_data, 12, header.uncompressed_size),
+        "-lh5-" => lzh_decompress(member_data, 13, header.uncompressed_size),
+        "-lh6-" => lzh_decompress(member_data, 15, header.uncompressed_size),
+        "-lh7-" => lzh_decompress(member_data, 16, header.uncompressed_size),
+        method => {
+            warn!(
+                "Skipping LHA member {}: unsupported compression method {method}",
+                header.name
+            );
+            return false;
+        }
+    };
+
+    match decompressed_data {
+        Err(_) => warn!("Skipping LHA member {}: decompression failed", header.name),
+        Ok(data) if crc16(&data) != header.data_crc => {
+            warn!("Skipping LHA member {}: CRC mismatch", header.name)
+        }
+        Ok(data) => {
+            // Members in sub directories may not have a directory entry of their own
+            if let Some((parent_directory, _)) = member_path.rsplit_once('/')
+                && !parent_directory.is_empty()
+                && chroot.create_directory(parent_directory).is_err()
+            {
+                return false;
+            }
+
+            return chroot.create_file(&member_path, &data).is_ok();
+        }
+    }
+
+    false
+}
@@ -1,4 +1,14 @@
 use crate::extractors;
+use crate::extractors::bzip2::bzip2_decompressor;
+use crate::extractors::common::{
+    Chroot, ExtractionResult, Extractor, ExtractorType, InternalExtractor,
+};
+use crate::extractors::gzip::gzip_decompress;
+use crate::extractors::lzma::lzma_decompress;
+use crate::extractors::lzop::lzop_decompress;
+use crate::logging::debug;
+use crate::structures::linux::{parse_linux_arm_zimage_header, parse_linux_bzimage_header};
+use aho_corasick::AhoCorasick;
 
 /// Describes how to run the vmlinux-to-elf utility to convert raw kernel images to ELF files
 ///
@@ -34,5 +44,224 @@ pub fn linux_kernel_extractor() -> extractors::common::Extractor {
             "linux_kernel.elf".to_string(),
         ],
         exit_codes: vec![0],
+        ..Default::default()
     }
 }
+
+/// Defines the internal extractor for decompressing the kernel embedded in a compressed Linux kernel image
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::linux::vmlinuz_extractor;
+///
+/// match vmlinuz_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn vmlinuz_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_vmlinuz),
+        ..Default::default()
+    }
+}
+
+/// Describes a compression format used for compressed Linux kernels
+struct KernelCompression {
+    magic: &'static [u8],
+    /// Internal decompressor; if None, the compressed kernel is carved to `file_name` for recursive extraction
+    decompressor: Option<InternalExtractor>,
+    file_name: &'static str,
+}
+
+/// Compression formats supported by the kernel's self-decompressor
+const KERNEL_COMPRESSIONS: [KernelCompression; 7] = [
+    KernelCompression {
+        magic: b"\x1F\x8B\x08",
+        decompressor: Some(gzip_decompress),
+        file_name: "vmlinux",
+    },
+    KernelCompression {
+        magic: b"\xFD7zXZ\x00",
+        decompressor: Some(lzma_decompress),
+        file_name: "vmlinux",
+    },
+    KernelCompression {
+        magic: b"\x5D\x00\x00",
+        decompressor: Some(lzma_decompress),
+        file_name: "vmlinux",
+    },
+    KernelCompression {
+        magic: b"BZh",
+        decompressor: Some(bzip2_decompressor),
+        file_name: "vmlinux",
+    },
+    KernelCompression {
+        magic: b"\x89LZO\x00",
+        decompressor: Some(lzop_decompress),
+        file_name: "vmlinux",
+    },
+    KernelCompression {
+        magic: b"\x02\x21\x4C\x18",
+        decompressor: None,
+        file_name: "vmlinux.lz4",
+    },
+    KernelCompression {
+        magic: b"\x28\xB5\x2F\xFD",
+        decompressor: None,
+        file_name: "vmlinux.zst",
+    },
+];
+
+/// Decompresses the kernel embedded in an x86 bzImage or ARM zImage to a file named `vmlinux`.
+///
+/// The compressed kernel is located by scanning the kernel image for known compression magic bytes; the first
+/// candidate that successfully decompresses is used. The reported size is the size of the kernel image.
+/// Compression formats without an internal decompressor are only carved when a bzImage header gives their location.
+pub fn extract_vmlinuz(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    // Decompressors write their output to this file, which is renamed to the kernel file name
+    const DECOMPRESSED_FILE_NAME: &str = "decompressed.bin";
+
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let Some(kernel_data) = file_data.get(offset..) else {
+        return result;
+    };
+
+    let window = kernel_image_window(kernel_data);
+
+    let magics: Vec<&[u8]> = KERNEL_COMPRESSIONS
+        .iter()
+        .map(|compression| compression.magic)
+        .collect();
+    let Ok(grep) = AhoCorasick::new(magics) else {
+        return result;
+    };
+
+    let mut candidates: Vec<(usize, usize)> = grep
+        .find_overlapping_iter(&kernel_data[window.start..window.end])
+        .map(|magic_match| {
+            (
+                window.start + magic_match.start(),
+                magic_match.pattern().as_usize(),
+            )
+        })
+        .collect();
+    candidates.sort();
+
+    let chroot = Chroot::new(output_directory);
+
+    for (candidate_offset, compression_index) in candidates {
+        let compression = &KERNEL_COMPRESSIONS[compression_index];
+
+        let payload_size = match compression.decompressor {
+            Some(decompressor) => {
+                let dry_run = decompressor(file_data, offset + candidate_offset, None);
+                if !dry_run.success {
+                    continue;
+                }
+
+                if output_directory.is_some()
+                    && (!decompressor(file_data, offset + candidate_offset, output_directory)
+                        .success
+                        || std::fs::rename(
+                            chroot.chrooted_path(DECOMPRESSED_FILE_NAME),
+                            chroot.chrooted_path(compression.file_name),
+                        )
+                        .is_err())
+                {
+                    break;
+                }
+
+                dry_run.size.unwrap_or_default()
+            }
+            None => {
+                let Some((payload_offset, payload_size)) = window.payload else {
+                    continue;
+                };
+
+                if payload_offset != candidate_offset {
+                    continue;
+                }
+
+                if output_directory.is_some()
+                    && chroot
+                        .carve_file(
+                            compression.file_name,
+                            kernel_data,
+                            candidate_offset,
+                            payload_size,
+                        )
+                        .is_err()
+                {
+                    break;
+                }
+
+                payload_size
+            }
+        };
+
+        debug!(
+            "Found {} compressed kernel at offset {:#X}",
+            compression.file_name,
+            offset + candidate_offset
+        );
+
+        result.size = Some(window.end.max(candidate_offset + payload_size));
+        result.success = true;
+        break;
+    }
+
+    result
+}
+
+/// The region of a kernel image that contains the compressed kernel
+struct KernelImageWindow {
+    start: usize,
+    end: usize,
+    /// Offset and size of the compressed kernel, if specified by the kernel image header
+    payload: Option<(usize, usize)>,
+}
+
+/// Determines the region of the kernel image to search, bounded by the available data
+fn kernel_image_window(kernel_data: &[u8]) -> KernelImageWindow {
+    let mut window = KernelImageWindow {
+        start: 0,
+        end: kernel_data.len(),
+        payload: None,
+    };
+
+    if let Ok(bzimage_header) = parse_linux_bzimage_header(kernel_data) {
+        window.start = bzimage_header.setup_size.min(window.end);
+        window.end = bzimage_header.image_size.clamp(window.start, window.end);
+
+        if let Some(payload_offset) = bzimage_header.payload_offset
+            && payload_offset + bzimage_header.payload_size <= window.end
+        {
+            window.payload = Some((payload_offset, bzimage_header.payload_size));
+        }
+    } else if let Ok(zimage_header) = parse_linux_arm_zimage_header(kernel_data)
+        && zimage_header.image_size != 0
+    {
+        window.end = zimage_header.image_size.min(window.end);
+    }
+
+    window
+}
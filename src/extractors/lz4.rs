@@ -29,10 +29,11 @@ pub fn lz4_extractor() -> extractors::common::Extractor {
         arguments: vec![
             "-f".to_string(), // Force overwirte if, for some reason, the output file exists
             "-d".to_string(), // Perform a decompression
-            extractors::common::SOURCE_FILE_PLACEHOLDER.to_string(),
+            "-".to_string(),  // Read the compressed data from stdin
             "decompressed.bin".to_string(), // Output file
         ],
         exit_codes: vec![0],
+        use_stdin: true,
         ..Default::default()
     }
 }
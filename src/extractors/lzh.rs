@@ -0,0 +1,491 @@
+/// Error return value of the LZH decompressor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LzhError;
+
+/// Minimum match length; lengths are encoded relative to this
+const THRESHOLD: usize = 3;
+/// Number of literal/match length codes
+const NC: usize = 256 + 256 + 2 - THRESHOLD;
+/// Number of codes used to encode the literal/match length code lengths
+const NT: usize = 19;
+/// Number of bits used to store the number of NT code lengths
+const TBIT: usize = 5;
+/// Number of bits used to store the number of NC code lengths
+const CBIT: usize = 9;
+/// Maximum length of a Huffman code
+const MAX_CODE_LENGTH: usize = 16;
+
+/// Decompressor for LZH data with static Huffman blocks, as used by LHA's -lh5-, -lh6- and -lh7- methods, and by
+/// ARJ's compression methods 1-3. For internal use, does not conform to the standard extractor format.
+///
+/// `dictionary_bits` is the base 2 log of the dictionary size (13 for -lh5-, 15 for -lh6-, 16 for -lh7- and ARJ),
+/// and determines the number of distance codes. The data does not mark its own end, so exactly `output_size`
+/// bytes are decompressed; fails if the compressed data is malformed, or ends before then.
+///
+/// Matches that reach back before the start of the data resolve to spaces, which is what LHA's dictionary is
+/// initialized with.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::extractors::lzh::lzh_decompress;
+///
+/// // A single block containing the literals "abc", and a 6 byte match 3 bytes back
+/// let compressed_data = b"\x00\x04\x28\x05\x30\x41\x37\x91\x70\x08\x6c";
+///
+/// assert_eq!(lzh_decompress(compressed_data, 16, 9), Ok(b"abcabcabc".to_vec()));
+/// assert!(lzh_decompress(&compressed_data[..8], 16, 9).is_err());
+/// ```
+pub fn lzh_decompress(
+    compressed_data: &[u8],
+    dictionary_bits: usize,
+    output_size: usize,
+) -> Result<Vec<u8>, LzhError> {
+    // Distances are encoded as the number of significant bits, followed by the remaining bits of the distance
+    let np = dictionary_bits + 1;
+    let pbit = (usize::BITS - np.leading_zeros()) as usize;
+
+    let mut reader = BitReader::new(compressed_data);
+    let mut output: Vec<u8> = Vec::with_capacity(output_size);
+
+    let mut block_codes_remaining: usize = 0;
+    let mut c_table = HuffmanTable::default();
+    let mut p_table = HuffmanTable::default();
+
+    while output.len() < output_size {
+        // Each block starts with the number of codes in the block, followed by the Huffman tables for the block
+        if block_codes_remaining == 0 {
+            block_codes_remaining = reader.bits(16)?;
+
+            if block_codes_remaining == 0 {
+                return Err(LzhError);
+            }
+
+            let t_table = read_code_lengths(&mut reader, NT, TBIT, Some(3))?;
+            c_table = read_literal_code_lengths(&mut reader, &t_table)?;
+            p_table = read_code_lengths(&mut reader, np, pbit, None)?;
+        }
+
+        block_codes_remaining -= 1;
+
+        let code = c_table.decode(&mut reader)?;
+
+        if code < 256 {
+            output.push(code as u8);
+            continue;
+        }
+
+        let match_length = code - 256 + THRESHOLD;
+
+        let distance = match p_table.decode(&mut reader)? {
+            0 => 0,
+            bit_count => (1 << (bit_count - 1)) + reader.bits(bit_count - 1)?,
+        };
+
+        for _ in 0..match_length.min(output_size - output.len()) {
+            let byte = match output.len().checked_sub(distance + 1) {
+                Some(source) => output[source],
+                None => b' ',
+            };
+            output.push(byte);
+        }
+    }
+
+    Ok(output)
+}
+
+/// Decompressor for LHA's -lh1- method, which uses an adaptive Huffman code for literals and match lengths, and a
+/// 4KB dictionary. For internal use, does not conform to the standard extractor format.
+///
+/// As with `lzh_decompress`, exactly `output_size` bytes are decompressed, and matches that reach back before the
+/// start of the data resolve to spaces.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::extractors::lzh::lzh_dynamic_decompress;
+///
+/// // The literals "abc", and a 6 byte match 3 bytes back
+/// let compressed_data = b"\xf6\xfb\xbd\xf1\xe0\x20";
+///
+/// assert_eq!(lzh_dynamic_decompress(compressed_data, 9), Ok(b"abcabcabc".to_vec()));
+/// assert!(lzh_dynamic_decompress(&compressed_data[..4], 9).is_err());
+/// ```
+pub fn lzh_dynamic_decompress(
+    compressed_data: &[u8],
+    output_size: usize,
+) -> Result<Vec<u8>, LzhError> {
+    // The upper 6 bits of match distances are encoded with a fixed Huffman code; the lower 6 bits are stored as is
+    const DISTANCE_CODE_LENGTHS: [(usize, usize); 6] =
+        [(3, 1), (4, 3), (5, 8), (6, 12), (7, 24), (8, 16)];
+    const DISTANCE_LOW_BITS: usize = 6;
+
+    let distance_code_lengths: Vec<usize> = DISTANCE_CODE_LENGTHS
+        .iter()
+        .flat_map(|(length, count)| vec![*length; *count])
+        .collect();
+    let p_table = HuffmanTable::new(&distance_code_lengths)?;
+
+    let mut reader = BitReader::new(compressed_data);
+    let mut c_tree = AdaptiveHuffmanTree::new();
+    let mut output: Vec<u8> = Vec::with_capacity(output_size);
+
+    while output.len() < output_size {
+        let code = c_tree.decode(&mut reader)?;
+
+        if code < 256 {
+            output.push(code as u8);
+            continue;
+        }
+
+        let match_length = code - 256 + THRESHOLD;
+        let distance =
+            (p_table.decode(&mut reader)? << DISTANCE_LOW_BITS) + reader.bits(DISTANCE_LOW_BITS)?;
+
+        for _ in 0..match_length.min(output_size - output.len()) {
+            let byte = match output.len().checked_sub(distance + 1) {
+                Some(source) => output[source],
+                None => b' ',
+            };
+            output.push(byte);
+        }
+    }
+
+    Ok(output)
+}
+
+/// Reads the code lengths of the NT or distance Huffman tables.
+/// After `special_index` code lengths, a 2 bit count of zero length codes is inserted.
+fn read_code_lengths(
+    reader: &mut BitReader,
+    code_count: usize,
+    count_bits: usize,
+    special_index: Option<usize>,
+) -> Result<HuffmanTable, LzhError> {
+    let stored_count = reader.bits(count_bits)?;
+
+    // A count of zero means that there is only one code, and it has a length of zero
+    if stored_count == 0 {
+        return HuffmanTable::single(reader.bits(count_bits)?, code_count);
+    }
+
+    if stored_count > code_count {
+        return Err(LzhError);
+    }
+
+    let mut code_lengths: Vec<usize> = vec![0; code_count];
+    let mut i: usize = 0;
+
+    while i < stored_count {
+        // Lengths of 7 or more are stored as 7, followed by a unary encoded remainder
+        let mut code_length = reader.bits(3)?;
+        if code_length == 7 {
+            while reader.bits(1)? == 1 {
+                code_length += 1;
+                if code_length > MAX_CODE_LENGTH {
+                    return Err(LzhError);
+                }
+            }
+        }
+
+        code_lengths[i] = code_length;
+        i += 1;
+
+        if Some(i) == special_index {
+            i += reader.bits(2)?;
+        }
+    }
+
+    HuffmanTable::new(&code_lengths)
+}
+
+/// Reads the code lengths of the literal/match length Huffman table, which are themselves Huffman encoded
+fn read_literal_code_lengths(
+    reader: &mut BitReader,
+    t_table: &HuffmanTable,
+) -> Result<HuffmanTable, LzhError> {
+    let stored_count = reader.bits(CBIT)?;
+
+    if stored_count == 0 {
+        return HuffmanTable::single(reader.bits(CBIT)?, NC);
+    }
+
+    if stored_count > NC {
+        return Err(LzhError);
+    }
+
+    let mut code_lengths: Vec<usize> = vec![0; NC];
+    let mut i: usize = 0;
+
+    while i < stored_count {
+        // Codes 0-2 encode runs of zero length codes, the rest encode a code length plus 2
+        let zero_run = match t_table.decode(reader)? {
+            0 => 1,
+            1 => reader.bits(4)? + 3,
+            2 => reader.bits(CBIT)? + 20,
+            code => {
+                code_lengths[i] = code - 2;
+                i += 1;
+                continue;
+            }
+        };
+
+        i += zero_run;
+
+        if i > NC {
+            return Err(LzhError);
+        }
+    }
+
+    HuffmanTable::new(&code_lengths)
+}
+
+/// A canonical Huffman table; shorter codes precede longer codes, and codes of the same length are in symbol order
+#[derive(Debug, Default, Clone)]
+struct HuffmanTable {
+    /// Number of codes of each length
+    length_counts: [usize; MAX_CODE_LENGTH + 1],
+    /// Symbols, sorted by code
+    symbols: Vec<usize>,
+    /// The only symbol in the table, encoded with zero bits
+    single_symbol: Option<usize>,
+}
+
+impl HuffmanTable {
+    fn new(code_lengths: &[usize]) -> Result<HuffmanTable, LzhError> {
+        let mut table = HuffmanTable::default();
+
+        for length in 1..=MAX_CODE_LENGTH {
+            for (symbol, code_length) in code_lengths.iter().enumerate() {
+                if *code_length == length {
+                    table.length_counts[length] += 1;
+                    table.symbols.push(symbol);
+                }
+            }
+        }
+
+        // Reject tables with no codes, or with more codes of a given length than there are bit patterns left for
+        let mut available_codes: usize = 1;
+        for count in &table.length_counts[1..] {
+            available_codes = (available_codes * 2).checked_sub(*count).ok_or(LzhError)?;
+        }
+
+        match table.symbols.is_empty() {
+            true => Err(LzhError),
+            false => Ok(table),
+        }
+    }
+
+    fn single(symbol: usize, code_count: usize) -> Result<HuffmanTable, LzhError> {
+        match symbol < code_count {
+            false => Err(LzhError),
+            true => Ok(HuffmanTable {
+                single_symbol: Some(symbol),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Reads bits until they match a code, and returns that code's symbol
+    fn decode(&self, reader: &mut BitReader) -> Result<usize, LzhError> {
+        if let Some(symbol) = self.single_symbol {
+            return Ok(symbol);
+        }
+
+        // First code of the current length, and the index of its symbol
+        let mut code: usize = 0;
+        let mut first_code: usize = 0;
+        let mut index: usize = 0;
+
+        for count in &self.length_counts[1..] {
+            code |= reader.bits(1)?;
+
+            if code < first_code + count {
+                return Ok(self.symbols[index + code - first_code]);
+            }
+
+            index += count;
+            first_code = (first_code + count) << 1;
+            code <<= 1;
+        }
+
+        Err(LzhError)
+    }
+}
+
+/// The adaptive Huffman tree used by -lh1-; symbol frequencies are updated as each symbol is decoded, and the tree
+/// is re-arranged so that it remains a Huffman tree for the updated frequencies.
+///
+/// Nodes are ordered by increasing frequency, the root is the last node, and siblings are always adjacent.
+struct AdaptiveHuffmanTree {
+    frequencies: Vec<usize>,
+    /// Index of each node's parent; leaves are at index (node count + symbol)
+    parents: Vec<usize>,
+    /// Index of each node's first child, or (node count + symbol) for leaves
+    children: Vec<usize>,
+}
+
+impl AdaptiveHuffmanTree {
+    /// Number of literal/match length symbols; matches are at most 60 bytes long
+    const SYMBOL_COUNT: usize = 256 - THRESHOLD + 60 + 1;
+    const NODE_COUNT: usize = (Self::SYMBOL_COUNT * 2) - 1;
+    const ROOT: usize = Self::NODE_COUNT - 1;
+    /// Frequencies are halved when the root's frequency reaches this value
+    const MAX_FREQUENCY: usize = 0x8000;
+
+    fn new() -> AdaptiveHuffmanTree {
+        let mut tree = AdaptiveHuffmanTree {
+            frequencies: vec![0; Self::NODE_COUNT + 1],
+            parents: vec![0; Self::NODE_COUNT + Self::SYMBOL_COUNT],
+            children: vec![0; Self::NODE_COUNT],
+        };
+
+        // All symbols start out with a frequency of 1
+        for symbol in 0..Self::SYMBOL_COUNT {
+            tree.frequencies[symbol] = 1;
+            tree.children[symbol] = symbol + Self::NODE_COUNT;
+            tree.parents[symbol + Self::NODE_COUNT] = symbol;
+        }
+
+        let mut child: usize = 0;
+        for node in Self::SYMBOL_COUNT..Self::NODE_COUNT {
+            tree.frequencies[node] = tree.frequencies[child] + tree.frequencies[child + 1];
+            tree.children[node] = child;
+            tree.parents[child] = node;
+            tree.parents[child + 1] = node;
+            child += 2;
+        }
+
+        // Sentinel, stops the search for a node to swap with at the end of the list
+        tree.frequencies[Self::NODE_COUNT] = usize::MAX;
+        tree.parents[Self::ROOT] = 0;
+
+        tree
+    }
+
+    fn decode(&mut self, reader: &mut BitReader) -> Result<usize, LzhError> {
+        let mut node = self.children[Self::ROOT];
+
+        while node < Self::NODE_COUNT {
+            node = self.children[node + reader.bits(1)?];
+        }
+
+        let symbol = node - Self::NODE_COUNT;
+        self.update(symbol);
+        Ok(symbol)
+    }
+
+    /// Increments the frequency of a symbol and its ancestors, swapping nodes as necessary to keep them in order
+    fn update(&mut self, symbol: usize) {
+        if self.frequencies[Self::ROOT] == Self::MAX_FREQUENCY {
+            self.rebuild();
+        }
+
+        let mut node = self.parents[symbol + Self::NODE_COUNT];
+
+        loop {
+            self.frequencies[node] += 1;
+            let frequency = self.frequencies[node];
+
+            // Swap with the last node that now has a lower frequency than this one
+            let mut swap_node = node + 1;
+            if frequency > self.frequencies[swap_node] {
+                while frequency > self.frequencies[swap_node + 1] {
+                    swap_node += 1;
+                }
+
+                self.frequencies[node] = self.frequencies[swap_node];
+                self.frequencies[swap_node] = frequency;
+
+                let child = self.children[node];
+                self.set_parent(child, swap_node);
+
+                let swap_child = self.children[swap_node];
+                self.children[swap_node] = child;
+                self.set_parent(swap_child, node);
+                self.children[node] = swap_child;
+
+                node = swap_node;
+            }
+
+            node = self.parents[node];
+            if node == 0 {
+                break;
+            }
+        }
+    }
+
+    /// Sets the parent of a child node, and its sibling if it is not a leaf
+    fn set_parent(&mut self, child: usize, parent: usize) {
+        self.parents[child] = parent;
+        if child < Self::NODE_COUNT {
+            self.parents[child + 1] = parent;
+        }
+    }
+
+    /// Halves all symbol frequencies, and rebuilds the tree
+    fn rebuild(&mut self) {
+        // Collect the leaves at the start of the node list
+        let mut leaf_count: usize = 0;
+        for node in 0..Self::NODE_COUNT {
+            if self.children[node] >= Self::NODE_COUNT {
+                self.frequencies[leaf_count] = self.frequencies[node].div_ceil(2);
+                self.children[leaf_count] = self.children[node];
+                leaf_count += 1;
+            }
+        }
+
+        // Join pairs of nodes, inserting each new node so that the nodes remain in order of frequency
+        let mut child: usize = 0;
+        for node in Self::SYMBOL_COUNT..Self::NODE_COUNT {
+            let frequency = self.frequencies[child] + self.frequencies[child + 1];
+
+            let mut insert_at = node;
+            while frequency < self.frequencies[insert_at - 1] {
+                insert_at -= 1;
+            }
+
+            self.frequencies.copy_within(insert_at..node, insert_at + 1);
+            self.frequencies[insert_at] = frequency;
+            self.children.copy_within(insert_at..node, insert_at + 1);
+            self.children[insert_at] = child;
+
+            child += 2;
+        }
+
+        for node in 0..Self::NODE_COUNT {
+            let child = self.children[node];
+            self.set_parent(child, node);
+        }
+    }
+}
+
+/// Reads bits from a byte stream, most significant bit first, as is the convention for LZH compressed data
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    data: &'a [u8],
+    bit_offset: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(data: &'a [u8]) -> BitReader<'a> {
+        BitReader {
+            data,
+            bit_offset: 0,
+        }
+    }
+
+    /// Reads the specified number of bits; fails if the end of the data is reached
+    pub fn bits(&mut self, count: usize) -> Result<usize, LzhError> {
+        let mut value: usize = 0;
+
+        for _ in 0..count {
+            let byte = self.data.get(self.bit_offset / 8).ok_or(LzhError)?;
+            let bit = (byte >> (7 - (self.bit_offset % 8))) & 1;
+            value = (value << 1) | bit as usize;
+            self.bit_offset += 1;
+        }
+
+        Ok(value)
+    }
+}
@@ -107,7 +107,10 @@ pub fn lzma_decompress(
                         let n = (decompressor.total_out() as usize) - bytes_written;
 
                         let chroot = Chroot::new(output_directory);
-                        if !chroot.append_to_file(OUTPUT_FILE_NAME, &output_buf[0..n]) {
+                        if chroot
+                            .append_to_file(OUTPUT_FILE_NAME, &output_buf[0..n])
+                            .is_err()
+                        {
                             // If writing data to disk fails, report failure and break
                             result.success = false;
                             break;
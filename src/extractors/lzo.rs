@@ -0,0 +1,184 @@
+/// Error return value of the LZO decompressor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LzoError;
+
+/// Decompressor for raw LZO1X compressed data, e.g. LZOP data blocks, or LZO compressed SquashFS/JFFS2 blocks.
+/// For internal use, does not conform to the standard extractor format.
+///
+/// Decompresses `compressed_data` up to and including the LZO1X end of stream marker, and returns the decompressed
+/// data. Fails if the data is malformed, or if more than `max_output_size` bytes would be decompressed.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::extractors::lzo::lzo1x_decompress;
+///
+/// // A literal run of "abc", a 6 byte match 3 bytes back, and the end of stream marker
+/// let compressed_data = b"\x14abc\xa8\x00\x11\x00\x00";
+///
+/// assert_eq!(lzo1x_decompress(compressed_data, 1024), Ok(b"abcabcabc".to_vec()));
+/// assert!(lzo1x_decompress(compressed_data, 8).is_err());
+/// assert!(lzo1x_decompress(&compressed_data[..6], 1024).is_err());
+/// ```
+pub fn lzo1x_decompress(
+    compressed_data: &[u8],
+    max_output_size: usize,
+) -> Result<Vec<u8>, LzoError> {
+    // Offsets are relative to these bases for each of the match instruction types
+    const M1_OFFSET_BASE: usize = 0x801;
+    const M4_OFFSET_BASE: usize = 0x4000;
+
+    let mut decoder = LzoDecoder {
+        input: compressed_data,
+        input_offset: 0,
+        output: Vec::new(),
+        max_output_size,
+    };
+
+    /*
+     * The low two bits of each match instruction encode the number of literals (0-3) that follow the match.
+     * The previous state determines how the next 0000XXXX instruction is interpreted:
+     * 0 means it is a literal run, 1-3 a 2 byte match, and 4 (after a literal run of 4+ bytes) a 3 byte match.
+     */
+    let mut state: usize = 0;
+
+    // The first byte may encode a literal run longer than would normally be allowed
+    if decoder.peek()? > 17 {
+        let literal_count = (decoder.next_byte()? - 17) as usize;
+        decoder.copy_literals(literal_count)?;
+        state = literal_count.min(4);
+    }
+
+    loop {
+        let instruction = decoder.next_byte()? as usize;
+
+        let (match_length, match_offset, next_state) = if instruction >= 0x40 {
+            // M2: LLLDDDSS DDDDDDDD; short match, within 2KB
+            let match_length = (instruction >> 5) + 1;
+            let match_offset =
+                ((decoder.next_byte()? as usize) << 3) + ((instruction >> 2) & 0x7) + 1;
+            (match_length, match_offset, instruction & 0x3)
+        } else if instruction >= 0x20 {
+            // M3: 001LLLLL [length bytes] DDDDDDSS DDDDDDDD; match within 16KB
+            let match_length = match instruction & 0x1F {
+                0 => decoder.extended_length()? + 0x1F + 2,
+                length => length + 2,
+            };
+            let distance = decoder.next_u16_le()?;
+            (match_length, (distance >> 2) + 1, distance & 0x3)
+        } else if instruction >= 0x10 {
+            // M4: 0001HLLL [length bytes] DDDDDDSS DDDDDDDD; match within 48KB, or end of stream
+            let match_length = match instruction & 0x7 {
+                0 => decoder.extended_length()? + 0x7 + 2,
+                length => length + 2,
+            };
+            let distance = decoder.next_u16_le()?;
+            let match_offset = ((instruction & 0x8) << 11) + (distance >> 2);
+
+            // A zero offset marks the end of the compressed data
+            if match_offset == 0 {
+                return Ok(decoder.output);
+            }
+
+            (match_length, match_offset + M4_OFFSET_BASE, distance & 0x3)
+        } else if state == 0 {
+            // 0000LLLL [length bytes]; a run of literal bytes
+            let literal_count = match instruction {
+                0 => decoder.extended_length()? + 0xF + 3,
+                length => length + 3,
+            };
+            decoder.copy_literals(literal_count)?;
+            state = 4;
+            continue;
+        } else if state < 4 {
+            // M1: 0000DDSS DDDDDDDD; 2 byte match within 1KB, immediately following a short literal run
+            let match_offset = (instruction >> 2) + ((decoder.next_byte()? as usize) << 2) + 1;
+            (2, match_offset, instruction & 0x3)
+        } else {
+            // M1: 0000DDSS DDDDDDDD; 3 byte match between 2KB and 3KB, immediately following a long literal run
+            let match_offset =
+                (instruction >> 2) + ((decoder.next_byte()? as usize) << 2) + M1_OFFSET_BASE;
+            (3, match_offset, instruction & 0x3)
+        };
+
+        decoder.copy_match(match_offset, match_length)?;
+        decoder.copy_literals(next_state)?;
+        state = next_state;
+    }
+}
+
+/// Tracks the LZO decompressor's input and output
+struct LzoDecoder<'a> {
+    input: &'a [u8],
+    input_offset: usize,
+    output: Vec<u8>,
+    max_output_size: usize,
+}
+
+impl LzoDecoder<'_> {
+    fn peek(&self) -> Result<u8, LzoError> {
+        self.input.get(self.input_offset).copied().ok_or(LzoError)
+    }
+
+    fn next_byte(&mut self) -> Result<u8, LzoError> {
+        let byte = self.peek()?;
+        self.input_offset += 1;
+        Ok(byte)
+    }
+
+    fn next_u16_le(&mut self) -> Result<usize, LzoError> {
+        let low = self.next_byte()? as usize;
+        let high = self.next_byte()? as usize;
+        Ok((high << 8) | low)
+    }
+
+    /// Long lengths are encoded as a run of zero bytes, each worth 255, followed by a non-zero byte
+    fn extended_length(&mut self) -> Result<usize, LzoError> {
+        let mut length: usize = 0;
+
+        loop {
+            match self.next_byte()? {
+                0 => length += 255,
+                byte => return Ok(length + byte as usize),
+            }
+        }
+    }
+
+    fn reserve_output(&self, size: usize) -> Result<(), LzoError> {
+        match self.output.len().checked_add(size) {
+            Some(output_size) if output_size <= self.max_output_size => Ok(()),
+            _ => Err(LzoError),
+        }
+    }
+
+    fn copy_literals(&mut self, count: usize) -> Result<(), LzoError> {
+        self.reserve_output(count)?;
+
+        let literals = self
+            .input_offset
+            .checked_add(count)
+            .and_then(|end_offset| self.input.get(self.input_offset..end_offset))
+            .ok_or(LzoError)?;
+
+        self.output.extend_from_slice(literals);
+        self.input_offset += count;
+        Ok(())
+    }
+
+    fn copy_match(&mut self, offset: usize, length: usize) -> Result<(), LzoError> {
+        self.reserve_output(length)?;
+
+        if offset > self.output.len() {
+            return Err(LzoError);
+        }
+
+        // Matches may overlap the data being written, so copy one byte at a time
+        let match_start = self.output.len() - offset;
+        for i in 0..length {
+            let byte = self.output[match_start + i];
+            self.output.push(byte);
+        }
+
+        Ok(())
+    }
+}
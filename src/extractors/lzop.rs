@@ -1,6 +1,12 @@
-use crate::extractors;
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::extractors::lzo::lzo1x_decompress;
+use crate::logging::warn;
+use crate::structures::lzop::{
+    LZOPChecksumType, parse_lzop_block_header, parse_lzop_eof_marker, parse_lzop_file_header,
+};
+use adler32::RollingAdler32;
 
-/// Describes how to run the lzop utility to extract LZO compressed files
+/// Defines the internal extractor function for decompressing LZOP files
 ///
 /// ```
 /// use std::io::ErrorKind;
@@ -22,17 +28,108 @@ use crate::extractors;
 ///     }
 /// }
 /// ```
-pub fn lzop_extractor() -> extractors::common::Extractor {
-    extractors::common::Extractor {
-        utility: extractors::common::ExtractorType::External("lzop".to_string()),
-        extension: "lzo".to_string(),
-        arguments: vec![
-            "-p".to_string(), // Output to the current directory
-            "-N".to_string(), // Restore original file name
-            "-d".to_string(), // Perform a decompression
-            extractors::common::SOURCE_FILE_PLACEHOLDER.to_string(),
-        ],
-        exit_codes: vec![0],
+pub fn lzop_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(lzop_decompress),
         ..Default::default()
     }
 }
+
+/// Internal extractor for LZOP compressed files; each block's checksums are validated as it is decompressed
+pub fn lzop_decompress(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    // Output file for decompressed data
+    const OUTPUT_FILE_NAME: &str = "decompressed.bin";
+
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let lzop_header = match parse_lzop_file_header(&file_data[offset..]) {
+        Err(_) => return result,
+        Ok(lzop_header) => lzop_header,
+    };
+
+    let chroot = Chroot::new(output_directory);
+    let mut block_offset: usize = offset + lzop_header.header_size;
+
+    // Process each block until the EOF marker is reached, or an invalid block is encountered
+    while let Some(block_data) = file_data.get(block_offset..) {
+        if let Ok(eof_marker_size) = parse_lzop_eof_marker(block_data) {
+            result.success = true;
+            result.size = Some(block_offset + eof_marker_size - offset);
+            break;
+        }
+
+        let block_header =
+            match parse_lzop_block_header(block_data, lzop_header.block_checksum_present) {
+                Err(_) => break,
+                Ok(block_header) => block_header,
+            };
+
+        let compressed_data_start =
+            block_offset + block_header.header_size + block_header.checksum_size;
+        let compressed_data_end = compressed_data_start + block_header.compressed_size;
+
+        let compressed_data = match file_data.get(compressed_data_start..compressed_data_end) {
+            None => break,
+            Some(compressed_data) => compressed_data,
+        };
+
+        if let Some(compressed_checksum) = block_header.compressed_checksum
+            && !checksum_matches(
+                lzop_header.compressed_checksum,
+                compressed_data,
+                compressed_checksum,
+            )
+        {
+            warn!("LZOP compressed block checksum mismatch at offset {block_offset:#X}");
+            break;
+        }
+
+        // Blocks that do not compress well are stored uncompressed
+        let block = if block_header.compressed_size == block_header.uncompressed_size {
+            compressed_data.to_vec()
+        } else {
+            match lzo1x_decompress(compressed_data, block_header.uncompressed_size) {
+                Err(_) => break,
+                Ok(block) => block,
+            }
+        };
+
+        if block.len() != block_header.uncompressed_size
+            || !checksum_matches(
+                lzop_header.uncompressed_checksum,
+                &block,
+                block_header.uncompressed_checksum,
+            )
+        {
+            warn!("LZOP block checksum mismatch at offset {block_offset:#X}");
+            break;
+        }
+
+        if output_directory.is_some() && chroot.append_to_file(OUTPUT_FILE_NAME, &block).is_err() {
+            break;
+        }
+
+        block_offset = compressed_data_end;
+    }
+
+    result
+}
+
+/// Returns true if the checksum of the data matches the expected checksum
+fn checksum_matches(checksum_type: LZOPChecksumType, data: &[u8], expected_checksum: u32) -> bool {
+    match checksum_type {
+        LZOPChecksumType::None => true,
+        LZOPChecksumType::Adler32 => RollingAdler32::from_buffer(data).hash() == expected_checksum,
+        LZOPChecksumType::Crc32 => {
+            let mut crc = flate2::Crc::new();
+            crc.update(data);
+            crc.sum() == expected_checksum
+        }
+    }
+}
@@ -60,8 +60,9 @@ pub fn extract_matter_ota(
         if let Some(payload_data) = file_data.get(payload_start..payload_end) {
             if output_directory.is_some() {
                 let chroot = Chroot::new(output_directory);
-                result.success =
-                    chroot.carve_file(OUTFILE_NAME, payload_data, 0, payload_data.len());
+                result.success = chroot
+                    .carve_file(OUTFILE_NAME, payload_data, 0, payload_data.len())
+                    .is_ok();
             }
         }
     }
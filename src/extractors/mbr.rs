@@ -1,5 +1,6 @@
 use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
-use crate::structures::mbr::parse_mbr_image;
+use crate::extractors::efigpt::extract_efigpt_partitions;
+use crate::structures::mbr::{MBR_GPT_PROTECTIVE_TYPE, parse_mbr_image};
 
 /// Defines the internal extractor function for MBR partitions
 ///
@@ -30,7 +31,8 @@ pub fn mbr_extractor() -> Extractor {
     }
 }
 
-/// Validate and extract partitions from an MBR
+/// Validate and extract partitions from an MBR.
+/// If the MBR is the protective MBR of a GPT disk image, the GPT partitions are extracted instead.
 pub fn extract_mbr_partitions(
     file_data: &[u8],
     offset: usize,
@@ -45,6 +47,16 @@ pub fn extract_mbr_partitions(
 
     // Parse the MBR header
     if let Ok(mbr_header) = parse_mbr_image(&file_data[offset..]) {
+        // A protective partition means that the real partition table is the GPT that follows the MBR
+        if mbr_header
+            .partitions
+            .iter()
+            .any(|partition| partition.os_type == MBR_GPT_PROTECTIVE_TYPE)
+            && extract_efigpt_partitions(file_data, offset, None).success
+        {
+            return extract_efigpt_partitions(file_data, offset, output_directory);
+        }
+
         // Make sure there is at least one valid partition
         if !mbr_header.partitions.is_empty() {
             // Make sure the reported size of the MBR does not extend beyond EOF
@@ -65,12 +77,9 @@ pub fn extract_mbr_partitions(
                             format!("{}_partition.{}", partition.name, partition_count);
 
                         // Carve out the partition
-                        result.success = chroot.carve_file(
-                            partition_name,
-                            file_data,
-                            partition.start,
-                            partition.size,
-                        );
+                        result.success = chroot
+                            .carve_file(partition_name, file_data, partition.start, partition.size)
+                            .is_ok();
 
                         // If partition extraction failed, quit and report a failure
                         if !result.success {
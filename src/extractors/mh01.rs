@@ -60,27 +60,36 @@ pub fn extract_mh01_image(
                 match delink::mh01::decrypt(mh01_data) {
                     Ok(decrypted_data) => {
                         // Write decrypted data to disk
-                        result.success =
-                            chroot.create_file(DECRYPTED_DATA_FILE_NAME, &decrypted_data);
+                        result.success = chroot
+                            .create_file(DECRYPTED_DATA_FILE_NAME, &decrypted_data)
+                            .is_ok();
                     }
                     Err(_) => {
                         // Decryption failture; extract each part of the firmware image, ensuring that each one extracts without error
-                        result.success = chroot.carve_file(
-                            IV_FILE_NAME,
-                            mh01_data,
-                            mh01_header.iv_offset,
-                            mh01_header.iv_size,
-                        ) && chroot.carve_file(
-                            SIGNATURE_FILE_NAME,
-                            mh01_data,
-                            mh01_header.signature_offset,
-                            mh01_header.signature_size,
-                        ) && chroot.carve_file(
-                            ENCRYPTED_DATA_FILE_NAME,
-                            mh01_data,
-                            mh01_header.encrypted_data_offset,
-                            mh01_header.encrypted_data_size,
-                        );
+                        result.success = chroot
+                            .carve_file(
+                                IV_FILE_NAME,
+                                mh01_data,
+                                mh01_header.iv_offset,
+                                mh01_header.iv_size,
+                            )
+                            .is_ok()
+                            && chroot
+                                .carve_file(
+                                    SIGNATURE_FILE_NAME,
+                                    mh01_data,
+                                    mh01_header.signature_offset,
+                                    mh01_header.signature_size,
+                                )
+                                .is_ok()
+                            && chroot
+                                .carve_file(
+                                    ENCRYPTED_DATA_FILE_NAME,
+                                    mh01_data,
+                                    mh01_header.encrypted_data_offset,
+                                    mh01_header.encrypted_data_size,
+                                )
+                                .is_ok();
                     }
                 }
             // No extraction requested, just return success
@@ -0,0 +1,336 @@
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::{debug, warn};
+use crate::structures::minix::{
+    DIRECT_ZONES, MinixHeader, MinixInode, ROOT_INODE, parse_minix_directory_entry,
+    parse_minix_header, parse_minix_inode, parse_minix_zone_pointers,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Defines the internal extractor function for extracting Minix file systems
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::minix::minix_extractor;
+///
+/// match minix_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn minix_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_minix_filesystem),
+        ..Default::default()
+    }
+}
+
+/// Extracts the directories, regular files, symlinks, device files, FIFOs and sockets from a Minix v1, v2 or v3
+/// file system. Permissions, ownership and modification times are restored where possible.
+///
+/// The reported size is the size of the file system, as recorded in its superblock, so that any trailing data is
+/// analyzed separately.
+pub fn extract_minix_filesystem(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let minix_data = &file_data[offset..];
+
+    if let Ok(minix_header) = parse_minix_header(minix_data)
+        && let Some(image_data) = minix_data.get(..minix_header.image_size)
+    {
+        let file_system = MinixFileSystem {
+            data: image_data,
+            header: minix_header,
+        };
+
+        // The root directory must, at the very least, be valid
+        if let Some(root_inode) = file_system.inode(ROOT_INODE)
+            && root_inode.mode & S_IFMT == S_IFDIR
+        {
+            result.size = Some(image_data.len());
+            result.success = true;
+
+            if output_directory.is_some() {
+                let chroot = Chroot::new(output_directory);
+                let file_count = file_system.extract(&chroot, &root_inode);
+                debug!("Extracted {file_count} Minix files");
+            }
+        }
+    }
+
+    result
+}
+
+// File type bits of the inode mode
+const S_IFMT: usize = 0xF000;
+const S_IFIFO: usize = 0x1000;
+const S_IFCHR: usize = 0x2000;
+const S_IFDIR: usize = 0x4000;
+const S_IFBLK: usize = 0x6000;
+const S_IFREG: usize = 0x8000;
+const S_IFLNK: usize = 0xA000;
+const S_IFSOCK: usize = 0xC000;
+
+/// Permission bits of the inode mode
+const PERMISSION_BITS: usize = 0o7777;
+
+/// A Minix file system image
+struct MinixFileSystem<'a> {
+    data: &'a [u8],
+    header: MinixHeader,
+}
+
+impl MinixFileSystem<'_> {
+    /// Extracts all files in the file system, returning the number of files extracted
+    fn extract(&self, chroot: &Chroot, root_inode: &MinixInode) -> usize {
+        let mut file_count: usize = 0;
+
+        // Directories waiting to be extracted, and all directories seen so far, to detect directory loops
+        let mut directories: Vec<(MinixInode, String)> =
+            vec![(root_inode.clone(), "/".to_string())];
+        let mut seen_directories: HashSet<usize> = HashSet::from([ROOT_INODE]);
+
+        // Paths of extracted files, keyed by inode number, for creating hard links
+        let mut extracted_files: HashMap<usize, String> = HashMap::new();
+
+        // Metadata is restored once everything has been extracted
+        let mut extracted_inodes: Vec<(String, MinixInode)> = vec![];
+
+        while let Some((directory_inode, directory_path)) = directories.pop() {
+            for (name, inode_number) in self.directory_entries(&directory_inode) {
+                let file_path = chroot.safe_path_join(&directory_path, &name);
+
+                let Some(inode) = self.inode(inode_number) else {
+                    warn!("Failed to read Minix inode {inode_number} for {file_path}");
+                    continue;
+                };
+
+                let file_type = inode.mode & S_IFMT;
+
+                // Directories can't be hard linked, anything else may have been extracted already
+                if file_type != S_IFDIR
+                    && let Some(link_target) = extracted_files.get(&inode_number)
+                {
+                    if chroot.create_hardlink(&file_path, link_target).is_ok() {
+                        file_count += 1;
+                    }
+                    continue;
+                }
+
+                let extracted = match file_type {
+                    S_IFDIR => {
+                        if !seen_directories.insert(inode_number) {
+                            warn!(
+                                "Minix directory {file_path} was already extracted, not following directory loop"
+                            );
+                            false
+                        } else if chroot.create_directory(&file_path).is_ok() {
+                            directories.push((inode.clone(), file_path.clone()));
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                    S_IFREG => match self.file_data(&inode) {
+                        None => false,
+                        Some(data) => chroot.create_sparse_file(&file_path, &data).is_ok(),
+                    },
+                    // Symlink targets are always stored in data zones
+                    S_IFLNK => match self.file_data(&inode) {
+                        None => false,
+                        Some(target) => chroot
+                            .create_symlink(&file_path, String::from_utf8_lossy(&target))
+                            .is_ok(),
+                    },
+                    S_IFCHR | S_IFBLK => {
+                        // The device number is stored in the first zone pointer
+                        let device = inode.zones[0];
+                        let (major, minor) = ((device >> 8) & 0xFF, device & 0xFF);
+
+                        match file_type {
+                            S_IFCHR => chroot.create_character_device(&file_path, major, minor),
+                            _ => chroot.create_block_device(&file_path, major, minor),
+                        }
+                        .is_ok()
+                    }
+                    S_IFIFO => chroot.create_fifo(&file_path).is_ok(),
+                    S_IFSOCK => chroot.create_socket(&file_path).is_ok(),
+                    _ => {
+                        warn!("Unknown Minix file type {file_type:#X} for {file_path}");
+                        false
+                    }
+                };
+
+                if extracted {
+                    file_count += 1;
+
+                    if file_type != S_IFDIR {
+                        extracted_files.insert(inode_number, file_path.clone());
+                    }
+
+                    extracted_inodes.push((file_path, inode));
+                } else {
+                    warn!("Failed to extract Minix file {file_path}");
+                }
+            }
+        }
+
+        // Files are restored before the directories that contain them, so that read-only directories are made read-only last
+        for (file_path, inode) in extracted_inodes.iter().rev() {
+            // Timestamps are signed 32 bit values
+            let _ = chroot.set_modification_time(file_path, inode.mtime as u32 as i32 as i64);
+            let _ = chroot.set_owner(file_path, inode.uid as u32, inode.gid as u32);
+            let _ = chroot.set_permissions(file_path, (inode.mode & PERMISSION_BITS) as u32);
+        }
+
+        file_count
+    }
+
+    /// Returns the data of the specified zone; zones are always the same size as blocks
+    fn zone(&self, zone_number: usize) -> Option<&[u8]> {
+        let start = zone_number.checked_mul(self.header.block_size)?;
+        self.data
+            .get(start..start.checked_add(self.header.block_size)?)
+    }
+
+    /// Reads and parses the specified inode
+    fn inode(&self, inode_number: usize) -> Option<MinixInode> {
+        if inode_number == 0 || inode_number > self.header.inodes_count {
+            return None;
+        }
+
+        let inode_offset = self
+            .header
+            .inode_table
+            .checked_mul(self.header.block_size)?
+            .checked_add((inode_number - 1) * self.header.inode_size)?;
+
+        parse_minix_inode(self.data.get(inode_offset..)?, &self.header).ok()
+    }
+
+    /// Returns the names and inode numbers of the entries in a directory, excluding the "." and ".." entries
+    fn directory_entries(&self, directory_inode: &MinixInode) -> Vec<(String, usize)> {
+        let mut entries = vec![];
+
+        let Some(directory_data) = self.file_data(directory_inode) else {
+            return entries;
+        };
+
+        for entry_data in directory_data.chunks_exact(self.header.directory_entry_size) {
+            let Ok(entry) = parse_minix_directory_entry(entry_data, &self.header) else {
+                break;
+            };
+
+            // Unused entries have an inode number of 0
+            if entry.inode != 0 && entry.name != b"." && entry.name != b".." {
+                entries.push((
+                    String::from_utf8_lossy(&entry.name).to_string(),
+                    entry.inode,
+                ));
+            }
+        }
+
+        entries
+    }
+
+    /// Returns the data of a regular file, directory, or symlink; unmapped zones read as zeros
+    fn file_data(&self, inode: &MinixInode) -> Option<Vec<u8>> {
+        // Sparse files may be larger than the file system itself, but not unreasonably so
+        const MAX_SPARSE_FILE_SIZE: usize = 1024 * 1024 * 1024;
+
+        if inode.size > self.data.len().max(MAX_SPARSE_FILE_SIZE) {
+            warn!("Minix file size {:#X} is unreasonably large", inode.size);
+            return None;
+        }
+
+        let block_size = self.header.block_size;
+        let file_block_count = inode.size.div_ceil(block_size);
+        let mut file_zones: Vec<(usize, usize)> = vec![];
+        let mut file_block: usize = 0;
+
+        // The direct zone pointers are followed by single, double and triple indirect zone pointers
+        for (i, zone_number) in inode.zones.iter().enumerate() {
+            let depth = (i + 1).saturating_sub(DIRECT_ZONES);
+            self.zone_map(
+                *zone_number,
+                depth,
+                &mut file_block,
+                file_block_count,
+                &mut file_zones,
+            );
+        }
+
+        let mut data = vec![0; inode.size];
+
+        for (file_block, zone_number) in file_zones {
+            let file_offset = file_block.checked_mul(block_size)?;
+            let zone_data = self.zone(zone_number)?;
+            let copy_size = block_size.min(inode.size - file_offset);
+            data[file_offset..file_offset + copy_size].copy_from_slice(&zone_data[..copy_size]);
+        }
+
+        Some(data)
+    }
+
+    /// Walks a zone pointer, appending the file block and zone number of each mapped zone. Zone pointers of 0 are holes.
+    fn zone_map(
+        &self,
+        zone_number: usize,
+        depth: usize,
+        file_block: &mut usize,
+        file_block_count: usize,
+        file_zones: &mut Vec<(usize, usize)>,
+    ) {
+        if *file_block >= file_block_count {
+            return;
+        }
+
+        // Number of file blocks mapped by this pointer
+        let pointers_per_zone = self.header.block_size / self.header.pointer_size;
+        let mapped_blocks = pointers_per_zone.saturating_pow(depth as u32);
+
+        if zone_number == 0 {
+            *file_block = file_block.saturating_add(mapped_blocks);
+        } else if depth == 0 {
+            file_zones.push((*file_block, zone_number));
+            *file_block += 1;
+        } else {
+            match self
+                .zone(zone_number)
+                .and_then(|zone_data| parse_minix_zone_pointers(zone_data, &self.header).ok())
+            {
+                None => {
+                    warn!("Minix indirect zone {zone_number} is outside of the file system");
+                    *file_block = file_block.saturating_add(mapped_blocks);
+                }
+                Some(child_zones) => {
+                    for child_zone in child_zones {
+                        self.zone_map(
+                            child_zone,
+                            depth - 1,
+                            file_block,
+                            file_block_count,
+                            file_zones,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,561 @@
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::{debug, warn};
+use crate::structures::ntfs::{
+    NTFS_ATTR_DATA, NTFS_ATTR_FILE_NAME, NTFS_ATTR_FLAG_COMPRESSED, NTFS_ATTR_FLAG_ENCRYPTED,
+    NTFS_ATTR_STANDARD_INFORMATION, NTFS_FIRST_USER_RECORD, NTFS_NAMESPACE_DOS, NTFS_ROOT_RECORD,
+    NTFSAttribute, NTFSAttributeContent, NTFSDataRun, NTFSFileName, NTFSNonResidentAttribute,
+    NTFSPartition, parse_ntfs_file_name, parse_ntfs_file_record, parse_ntfs_header,
+    parse_ntfs_modification_time,
+};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Defines the internal extractor function for extracting NTFS partitions
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::ntfs::ntfs_extractor;
+///
+/// match ntfs_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn ntfs_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_ntfs_partition),
+        ..Default::default()
+    }
+}
+
+/// Extracts the directories and files from an NTFS partition, without modifying it.
+///
+/// Files are located by walking the MFT records, rather than the directory indexes. Both resident and non-resident
+/// data is extracted, including sparse and LZNT1 compressed data; named alternate data streams are extracted to
+/// `<file name>:<stream name>`. File system metadata files (e.g. `$MFT`), deleted files and encrypted data streams are
+/// not extracted. File modification times are restored where possible.
+///
+/// The reported size is the size of the partition, as recorded in its boot sector.
+pub fn extract_ntfs_partition(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let Some(ntfs_data) = file_data.get(offset..) else {
+        return result;
+    };
+
+    if let Ok(ntfs_header) = parse_ntfs_header(ntfs_data)
+        && ntfs_header.cluster_size > 0
+        && ntfs_header.mft_record_size > 0
+        && let Some(image_size) = ntfs_header
+            .sector_count
+            .checked_add(1)
+            .and_then(|sector_count| sector_count.checked_mul(ntfs_header.sector_size))
+        && let Some(image_data) = ntfs_data.get(..image_size)
+    {
+        let file_system = NTFSFileSystem {
+            data: image_data,
+            header: ntfs_header,
+        };
+
+        // The MFT must be readable, and contain the root directory
+        if let Some(files) = file_system.files()
+            && files
+                .get(&NTFS_ROOT_RECORD)
+                .is_some_and(|root| root.is_directory)
+        {
+            result.size = Some(image_data.len());
+            result.success = true;
+
+            if output_directory.is_some() {
+                let chroot = Chroot::new(output_directory);
+                let file_count = file_system.extract(&chroot, &files);
+                debug!("Extracted {file_count} NTFS files");
+            }
+        }
+    }
+
+    result
+}
+
+/// Maximum size of an extracted data stream, unless the partition is larger; sparse and compressed streams may be
+/// larger than the partition that contains them
+const MAX_STREAM_SIZE: usize = 1024 * 1024 * 1024;
+
+/// Size of an uncompressed LZNT1 chunk
+const LZNT1_CHUNK_SIZE: usize = 4096;
+
+/// A file or directory, assembled from its base MFT record and any extension records
+#[derive(Debug, Default, Clone)]
+struct NTFSFile {
+    is_directory: bool,
+    /// The file's long name, if it has one, else its DOS name
+    file_name: Option<NTFSFileName>,
+    modification_time: Option<i64>,
+    attributes: Vec<NTFSAttribute>,
+}
+
+/// An NTFS partition image
+struct NTFSFileSystem<'a> {
+    data: &'a [u8],
+    header: NTFSPartition,
+}
+
+impl NTFSFileSystem<'_> {
+    /// Extracts all user files in the file system, returning the number of files and directories extracted
+    fn extract(&self, chroot: &Chroot, files: &BTreeMap<usize, NTFSFile>) -> usize {
+        let mut file_count: usize = 0;
+
+        // Paths of directories, keyed by MFT record number; None for directories that are not extracted
+        let mut directory_paths: HashMap<usize, Option<String>> =
+            HashMap::from([(NTFS_ROOT_RECORD, Some("/".to_string()))]);
+
+        // Modification times are restored once everything has been extracted
+        let mut modification_times: Vec<(String, i64)> = vec![];
+
+        for (record_number, file) in files.range(NTFS_FIRST_USER_RECORD..) {
+            let Some(file_path) = resolve_path(*record_number, files, &mut directory_paths, chroot)
+            else {
+                debug!(
+                    "Not extracting NTFS file record {record_number}, it is not in the directory tree"
+                );
+                continue;
+            };
+
+            if file.is_directory {
+                if chroot.create_directory(&file_path).is_ok() {
+                    file_count += 1;
+                }
+            } else if let Some(parent_directory) = Path::new(&file_path).parent() {
+                // Files may be extracted before the directory that contains them
+                let _ = chroot.create_directory(parent_directory.display().to_string());
+            }
+
+            for attribute in &file.attributes {
+                if attribute.attribute_type != NTFS_ATTR_DATA
+                    || (file.is_directory && attribute.name.is_empty())
+                {
+                    continue;
+                }
+
+                let stream_path = match attribute.name.is_empty() {
+                    true => file_path.clone(),
+                    false => format!("{}:{}", file_path, attribute.name),
+                };
+
+                if attribute.flags & NTFS_ATTR_FLAG_ENCRYPTED != 0 {
+                    warn!("Not extracting encrypted NTFS data stream {stream_path}");
+                    continue;
+                }
+
+                match self.stream_data(attribute) {
+                    None => warn!("Failed to read NTFS data stream {stream_path}"),
+                    Some(data) => {
+                        if chroot.create_sparse_file(&stream_path, &data).is_ok() {
+                            file_count += 1;
+                        }
+                    }
+                }
+            }
+
+            if let Some(modification_time) = file.modification_time {
+                modification_times.push((file_path, modification_time));
+            }
+        }
+
+        // Files must be restored before the directories that contain them, as extracting files updates the mtime of
+        // their directory; paths inside of a directory are always longer than the directory's path
+        modification_times.sort_by_key(|(file_path, _)| Reverse(file_path.len()));
+
+        for (file_path, modification_time) in &modification_times {
+            let _ = chroot.set_modification_time(file_path, *modification_time);
+        }
+
+        file_count
+    }
+
+    /// Reads all MFT records, merging extension records into their base records. Returns None if the MFT can't be read.
+    fn files(&self) -> Option<BTreeMap<usize, NTFSFile>> {
+        let record_size = self.header.mft_record_size;
+
+        // The first MFT record describes the MFT itself, which may be fragmented
+        let mft_record = self
+            .data
+            .get(self.header.mft_offset..self.header.mft_offset.checked_add(record_size)?)?;
+        let mft_data = parse_ntfs_file_record(mft_record)
+            .ok()?
+            .attributes
+            .into_iter()
+            .find(|attribute| {
+                attribute.attribute_type == NTFS_ATTR_DATA && attribute.name.is_empty()
+            })?;
+        let mft = self.stream_data(&mft_data)?;
+
+        let mut files: BTreeMap<usize, NTFSFile> = BTreeMap::new();
+        let mut extension_attributes: Vec<(usize, Vec<NTFSAttribute>)> = vec![];
+
+        for (record_number, record_data) in mft.chunks_exact(record_size).enumerate() {
+            let Ok(record) = parse_ntfs_file_record(record_data) else {
+                continue;
+            };
+
+            if !record.in_use {
+                continue;
+            }
+
+            if let Some(base_record) = record.base_record {
+                extension_attributes.push((base_record, record.attributes));
+                continue;
+            }
+
+            let mut file = NTFSFile {
+                is_directory: record.is_directory,
+                ..Default::default()
+            };
+
+            for attribute in record.attributes {
+                if let NTFSAttributeContent::Resident(value) = &attribute.content {
+                    match attribute.attribute_type {
+                        NTFS_ATTR_STANDARD_INFORMATION => {
+                            file.modification_time = parse_ntfs_modification_time(value).ok();
+                        }
+                        NTFS_ATTR_FILE_NAME => {
+                            if let Ok(file_name) = parse_ntfs_file_name(value)
+                                && (file.file_name.is_none()
+                                    || file_name.namespace != NTFS_NAMESPACE_DOS)
+                            {
+                                file.file_name = Some(file_name);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+
+                file.attributes.push(attribute);
+            }
+
+            files.insert(record_number, file);
+        }
+
+        for (base_record, attributes) in extension_attributes {
+            match files.get_mut(&base_record) {
+                None => warn!("NTFS extension record refers to missing base record {base_record}"),
+                Some(file) => file.attributes.extend(attributes),
+            }
+        }
+
+        for file in files.values_mut() {
+            file.attributes = merge_attribute_extents(std::mem::take(&mut file.attributes));
+        }
+
+        Some(files)
+    }
+
+    /// Returns the data of a resident or non-resident attribute
+    fn stream_data(&self, attribute: &NTFSAttribute) -> Option<Vec<u8>> {
+        let non_resident = match &attribute.content {
+            NTFSAttributeContent::Resident(data) => return Some(data.clone()),
+            NTFSAttributeContent::NonResident(non_resident) => non_resident,
+        };
+
+        if non_resident.data_size > self.data.len().max(MAX_STREAM_SIZE) {
+            warn!(
+                "NTFS data stream size {:#X} is unreasonably large",
+                non_resident.data_size
+            );
+            return None;
+        }
+
+        let mut data = match attribute.flags & NTFS_ATTR_FLAG_COMPRESSED != 0
+            && non_resident.compression_unit != 0
+        {
+            true => self.compressed_data(non_resident)?,
+            false => self.run_data(&non_resident.runs, non_resident.data_size)?,
+        };
+
+        data.resize(non_resident.data_size, 0);
+
+        // Data beyond the initialized size has never been written, and reads as zeros
+        if non_resident.initialized_size < data.len() {
+            data[non_resident.initialized_size..].fill(0);
+        }
+
+        Some(data)
+    }
+
+    /// Reads up to `size` bytes of data from a list of runs; sparse runs read as zeros
+    fn run_data(&self, runs: &[NTFSDataRun], size: usize) -> Option<Vec<u8>> {
+        let mut data: Vec<u8> = Vec::with_capacity(size);
+
+        for run in runs {
+            if data.len() >= size {
+                break;
+            }
+
+            let run_size = run
+                .cluster_count
+                .checked_mul(self.header.cluster_size)?
+                .min(size - data.len());
+
+            match run.lcn {
+                None => data.resize(data.len() + run_size, 0),
+                Some(lcn) => {
+                    let start = lcn.checked_mul(self.header.cluster_size)?;
+                    data.extend_from_slice(self.data.get(start..start.checked_add(run_size)?)?);
+                }
+            }
+        }
+
+        Some(data)
+    }
+
+    /// Reads and decompresses LZNT1 compressed data. Compressed data is stored in compression units: units with no
+    /// clusters allocated are all zeros, fully allocated units are stored uncompressed, and all others are compressed.
+    fn compressed_data(&self, attribute: &NTFSNonResidentAttribute) -> Option<Vec<u8>> {
+        // Compression units are normally 16 clusters
+        const MAX_COMPRESSION_UNIT: usize = 8;
+
+        if attribute.compression_unit > MAX_COMPRESSION_UNIT {
+            warn!(
+                "Unsupported NTFS compression unit size: {}",
+                attribute.compression_unit
+            );
+            return None;
+        }
+
+        let unit_clusters: usize = 1 << attribute.compression_unit;
+        let unit_size = unit_clusters * self.header.cluster_size;
+        let mut data: Vec<u8> = Vec::with_capacity(attribute.data_size);
+
+        // Split the runs into compression units
+        let mut unit_runs: Vec<NTFSDataRun> = vec![];
+        let mut unit_cluster_count: usize = 0;
+
+        for run in &attribute.runs {
+            let mut run = run.clone();
+
+            while run.cluster_count > 0 && data.len() < attribute.data_size {
+                let cluster_count = run.cluster_count.min(unit_clusters - unit_cluster_count);
+
+                unit_runs.push(NTFSDataRun {
+                    cluster_count,
+                    lcn: run.lcn,
+                });
+
+                unit_cluster_count += cluster_count;
+                run.cluster_count -= cluster_count;
+                run.lcn = run.lcn.map(|lcn| lcn.saturating_add(cluster_count));
+
+                if unit_cluster_count == unit_clusters {
+                    data.extend(self.compression_unit_data(&unit_runs, unit_size)?);
+                    unit_runs.clear();
+                    unit_cluster_count = 0;
+                }
+            }
+        }
+
+        // The runs may end part way through the last compression unit
+        if !unit_runs.is_empty() {
+            data.extend(self.compression_unit_data(&unit_runs, unit_size)?);
+        }
+
+        Some(data)
+    }
+
+    /// Returns the decompressed data of a single compression unit
+    fn compression_unit_data(
+        &self,
+        unit_runs: &[NTFSDataRun],
+        unit_size: usize,
+    ) -> Option<Vec<u8>> {
+        let allocated_runs: Vec<NTFSDataRun> = unit_runs
+            .iter()
+            .filter(|run| run.lcn.is_some())
+            .cloned()
+            .collect();
+        let allocated_size = allocated_runs
+            .iter()
+            .map(|run| run.cluster_count * self.header.cluster_size)
+            .sum();
+
+        if allocated_size == 0 {
+            return Some(vec![0; unit_size]);
+        }
+
+        let unit_data = self.run_data(&allocated_runs, allocated_size)?;
+
+        if allocated_size == unit_size {
+            return Some(unit_data);
+        }
+
+        let mut decompressed = lznt1_decompress(&unit_data, unit_size)?;
+        decompressed.resize(unit_size, 0);
+        Some(decompressed)
+    }
+}
+
+/// Merges the extents of non-resident attributes that are split across multiple MFT records into a single attribute
+fn merge_attribute_extents(attributes: Vec<NTFSAttribute>) -> Vec<NTFSAttribute> {
+    let mut merged: Vec<NTFSAttribute> = vec![];
+
+    // Extents are merged in VCN order; the first extent holds the attribute's sizes
+    let mut sorted = attributes;
+    sorted.sort_by_key(|attribute| match &attribute.content {
+        NTFSAttributeContent::Resident(_) => 0,
+        NTFSAttributeContent::NonResident(non_resident) => non_resident.start_vcn,
+    });
+
+    for attribute in sorted {
+        let existing = merged.iter_mut().find(|existing| {
+            existing.attribute_type == attribute.attribute_type && existing.name == attribute.name
+        });
+
+        match (existing, &attribute.content) {
+            (Some(existing), NTFSAttributeContent::NonResident(extent)) if extent.start_vcn > 0 => {
+                if let NTFSAttributeContent::NonResident(first_extent) = &mut existing.content {
+                    first_extent.runs.extend(extent.runs.clone());
+                }
+            }
+            _ => merged.push(attribute),
+        }
+    }
+
+    merged
+}
+
+/// Returns the chrooted path of a file, or None if the file is not in the directory tree below the root directory
+fn resolve_path(
+    record_number: usize,
+    files: &BTreeMap<usize, NTFSFile>,
+    directory_paths: &mut HashMap<usize, Option<String>>,
+    chroot: &Chroot,
+) -> Option<String> {
+    let file_name = files.get(&record_number)?.file_name.as_ref()?;
+    let parent_record = file_name.parent_record;
+
+    if let Some(parent_path) = directory_paths.get(&parent_record) {
+        return Some(chroot.safe_path_join(parent_path.as_ref()?, &file_name.name));
+    }
+
+    // Resolve the parent directory's path first; it is marked as unresolvable while doing so, to break directory loops
+    directory_paths.insert(parent_record, None);
+
+    let parent_path = match files.get(&parent_record) {
+        Some(parent) if parent.is_directory && parent_record >= NTFS_FIRST_USER_RECORD => {
+            resolve_path(parent_record, files, directory_paths, chroot)
+        }
+        _ => None,
+    };
+
+    directory_paths.insert(parent_record, parent_path.clone());
+
+    Some(chroot.safe_path_join(parent_path?, &file_name.name))
+}
+
+/// Decompresses LZNT1 compressed data, returning at most `max_size` bytes.
+///
+/// The data is a series of chunks, each of which decompresses to at most 4KB. Each chunk starts with a 16-bit header
+/// specifying the chunk size and whether or not it is compressed. Compressed chunks are a series of flag bytes, each
+/// followed by 8 tokens that are either literal bytes, or 16-bit back references whose split between offset and length
+/// bits depends on the current position in the chunk.
+fn lznt1_decompress(compressed_data: &[u8], max_size: usize) -> Option<Vec<u8>> {
+    const CHUNK_COMPRESSED: usize = 0x8000;
+    const CHUNK_SIZE_MASK: usize = 0x0FFF;
+
+    let mut data: Vec<u8> = vec![];
+    let mut offset: usize = 0;
+
+    while data.len() < max_size {
+        // The compressed data ends with a zero header, or at the end of the compression unit
+        let Some(header_bytes) = compressed_data.get(offset..offset + 2) else {
+            break;
+        };
+
+        let header = u16::from_le_bytes(header_bytes.try_into().unwrap()) as usize;
+
+        if header == 0 {
+            break;
+        }
+
+        let chunk_start = offset + 2;
+        let chunk_end = chunk_start + (header & CHUNK_SIZE_MASK) + 1;
+        let chunk = compressed_data.get(chunk_start..chunk_end)?;
+        offset = chunk_end;
+
+        // Chunks that decompress to less than 4KB are padded with zeros
+        let chunk_data_start = data.len().next_multiple_of(LZNT1_CHUNK_SIZE);
+        data.resize(chunk_data_start, 0);
+
+        if header & CHUNK_COMPRESSED == 0 {
+            data.extend_from_slice(chunk);
+            continue;
+        }
+
+        let mut chunk_offset: usize = 0;
+
+        while let Some(flags) = chunk.get(chunk_offset) {
+            chunk_offset += 1;
+
+            for bit in 0..8 {
+                if chunk_offset >= chunk.len() {
+                    break;
+                }
+
+                if (flags >> bit) & 1 == 0 {
+                    data.push(chunk[chunk_offset]);
+                    chunk_offset += 1;
+                    continue;
+                }
+
+                let token_bytes = chunk.get(chunk_offset..chunk_offset + 2)?;
+                let token = u16::from_le_bytes(token_bytes.try_into().unwrap()) as usize;
+                chunk_offset += 2;
+
+                // The more data has been decompressed in this chunk, the more bits are used for the offset
+                let position = data.len() - chunk_data_start;
+                let mut length_mask: usize = 0x0FFF;
+                let mut offset_shift: usize = 12;
+                let mut i = position.saturating_sub(1);
+
+                while i >= 0x10 {
+                    length_mask >>= 1;
+                    offset_shift -= 1;
+                    i >>= 1;
+                }
+
+                let back_offset = (token >> offset_shift) + 1;
+                let length = (token & length_mask) + 3;
+
+                // Back references may not reach outside of the current chunk, and chunks are at most 4KB
+                if back_offset > position || position + length > LZNT1_CHUNK_SIZE {
+                    warn!("Invalid LZNT1 back reference");
+                    return None;
+                }
+
+                for _ in 0..length {
+                    data.push(data[data.len() - back_offset]);
+                }
+            }
+        }
+    }
+
+    data.truncate(max_size);
+    Some(data)
+}
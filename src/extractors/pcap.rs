@@ -88,8 +88,9 @@ pub fn pcapng_carver(
             // Do extraction if requested
             if output_directory.is_some() {
                 let chroot = Chroot::new(output_directory);
-                result.success =
-                    chroot.carve_file(OUTPUT_FILE_NAME, file_data, offset, result.size.unwrap());
+                result.success = chroot
+                    .carve_file(OUTPUT_FILE_NAME, file_data, offset, result.size.unwrap())
+                    .is_ok();
             }
         }
     }
@@ -0,0 +1,319 @@
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::warn;
+use crate::structures::pdf::{
+    PDFObject, parse_pdf_object_stream, parse_pdf_objects, parse_pdf_size, pdf_dictionary_integer,
+    pdf_dictionary_names, pdf_dictionary_value, pdf_reference, pdf_string, pdf_sub_dictionary,
+};
+use flate2::read::ZlibDecoder;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+
+/// Defines the internal extractor function for extracting streams and embedded files from PDF documents
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::pdf::pdf_extractor;
+///
+/// match pdf_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn pdf_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_pdf_streams),
+        ..Default::default()
+    }
+}
+
+/// Decodes each stream object in a PDF document and writes it to the output directory.
+/// Embedded files are written using their original file names; all other streams are named after their object numbers.
+pub fn extract_pdf_streams(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    // The end of the PDF is determined from its trailer(s)
+    if let Ok(pdf_size) = parse_pdf_size(&file_data[offset..]) {
+        result.size = Some(pdf_size);
+        result.success = true;
+
+        if output_directory.is_some() {
+            let chroot = Chroot::new(output_directory);
+            let mut objects = parse_pdf_objects(&file_data[offset..offset + pdf_size]);
+            let mut compressed_objects: Vec<PDFObject> = vec![];
+            let mut decoded_streams: Vec<(usize, usize, Vec<u8>, &str)> = vec![];
+
+            for object in objects.iter() {
+                if let Some(stream) = &object.stream {
+                    let (stream_data, extension) = decode_pdf_stream(&object.value, stream);
+
+                    // Objects, such as embedded file specifications, may themselves be stored inside object streams
+                    if pdf_dictionary_names(&object.value, "Type").contains(&"ObjStm".to_string()) {
+                        compressed_objects
+                            .extend(parse_pdf_object_stream(&object.value, &stream_data));
+                    }
+
+                    decoded_streams.push((
+                        object.number,
+                        object.generation,
+                        stream_data,
+                        extension,
+                    ));
+                }
+            }
+
+            objects.extend(compressed_objects);
+
+            let embedded_file_names = embedded_file_names(&objects);
+
+            for (number, generation, stream_data, extension) in decoded_streams {
+                let file_name = match embedded_file_names.get(&number) {
+                    None => format!("object_{number}_{generation}.{extension}"),
+                    Some(embedded_file_name) => embedded_file_name.to_string(),
+                };
+
+                // Incremental updates may re-define existing objects, and embedded file names need not be unique
+                let file_name = unique_file_name(&chroot, &file_name);
+
+                if chroot.create_file(&file_name, &stream_data).is_err() {
+                    result.success = false;
+                    break;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Returns the file name, suffixed with a number if a file of that name already exists
+fn unique_file_name(chroot: &Chroot, file_name: &str) -> String {
+    let mut unique_name = file_name.to_string();
+    let mut suffix: usize = 1;
+
+    while Path::new(&chroot.chrooted_path(&unique_name)).exists() {
+        unique_name = format!("{file_name}.{suffix}");
+        suffix += 1;
+    }
+
+    unique_name
+}
+
+/// Maps the object numbers of embedded file streams to their original file names, as recorded in file specification dictionaries
+fn embedded_file_names(objects: &[PDFObject]) -> HashMap<usize, String> {
+    let mut file_names = HashMap::new();
+
+    for object in objects {
+        // File specifications reference the embedded file stream via their /EF dictionary
+        let Some(ef_value) = pdf_dictionary_value(&object.value, "EF") else {
+            continue;
+        };
+        let Some(ef_dictionary) = pdf_sub_dictionary(ef_value) else {
+            continue;
+        };
+        let Some((stream_number, _)) = ["UF", "F"]
+            .iter()
+            .find_map(|key| pdf_dictionary_value(ef_dictionary, key).and_then(pdf_reference))
+        else {
+            continue;
+        };
+
+        // The /EF dictionary also uses the /F key, so exclude it when looking up the file name
+        let ef_start = object.value.len() - ef_value.len();
+        let ef_end = ef_start + ef_dictionary.len();
+        let file_spec = [&object.value[..ef_start], &object.value[ef_end..]].concat();
+
+        // Prefer the unicode file name, if available
+        if let Some(file_name) = ["UF", "F"]
+            .iter()
+            .find_map(|key| pdf_dictionary_value(&file_spec, key).and_then(pdf_string))
+            .and_then(|file_name| {
+                Path::new(&file_name)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+            })
+        {
+            file_names.insert(stream_number, file_name);
+        }
+    }
+
+    file_names
+}
+
+/// Applies the stream's filters to the stream data, and returns the decoded data along with a suitable file extension.
+/// If a filter is not supported, or fails, the data is returned as decoded up to that point.
+fn decode_pdf_stream(stream_dictionary: &[u8], stream_data: &[u8]) -> (Vec<u8>, &'static str) {
+    let mut data = stream_data.to_vec();
+
+    for filter in pdf_dictionary_names(stream_dictionary, "Filter") {
+        let decoded_data = match filter.as_str() {
+            "FlateDecode" | "Fl" => flate_decode(&data),
+            "ASCIIHexDecode" | "AHx" => ascii_hex_decode(&data),
+            "LZWDecode" | "LZW" => lzw_decode(
+                &data,
+                pdf_dictionary_integer(stream_dictionary, "EarlyChange") != Some(0),
+            ),
+            // Image data is left encoded
+            "DCTDecode" | "DCT" => return (data, "jpg"),
+            "JPXDecode" => return (data, "jp2"),
+            _ => {
+                warn!("Unsupported PDF stream filter: {filter}");
+                return (data, "bin");
+            }
+        };
+
+        match decoded_data {
+            None => {
+                warn!("Failed to decode PDF stream with the {filter} filter");
+                return (data, "bin");
+            }
+            Some(decoded_data) => {
+                data = decoded_data;
+
+                // Flate and LZW data may additionally be run through a PNG predictor
+                if matches!(filter.as_str(), "FlateDecode" | "Fl" | "LZWDecode" | "LZW") {
+                    data = png_unpredict(stream_dictionary, data);
+                }
+            }
+        }
+    }
+
+    (data, "bin")
+}
+
+/// Decodes zlib compressed data
+fn flate_decode(data: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded_data = vec![];
+    ZlibDecoder::new(data)
+        .read_to_end(&mut decoded_data)
+        .ok()
+        .map(|_| decoded_data)
+}
+
+/// Decodes hex encoded data, which is terminated by a '>' character
+fn ascii_hex_decode(data: &[u8]) -> Option<Vec<u8>> {
+    let mut hex_digits: Vec<u8> = data
+        .iter()
+        .copied()
+        .take_while(|b| *b != b'>')
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+
+    // A missing final digit is assumed to be zero
+    if !hex_digits.len().is_multiple_of(2) {
+        hex_digits.push(b'0');
+    }
+
+    hex::decode(hex_digits).ok()
+}
+
+/// Decodes LZW compressed data; by default, PDF LZW data increases the code width one code early, as TIFF does
+fn lzw_decode(data: &[u8], early_change: bool) -> Option<Vec<u8>> {
+    const LZW_MIN_CODE_SIZE: u8 = 8;
+
+    let mut decoder = match early_change {
+        true => {
+            weezl::decode::Decoder::with_tiff_size_switch(weezl::BitOrder::Msb, LZW_MIN_CODE_SIZE)
+        }
+        false => weezl::decode::Decoder::new(weezl::BitOrder::Msb, LZW_MIN_CODE_SIZE),
+    };
+
+    decoder.decode(data).ok()
+}
+
+/// Reverses the PNG predictor, if one was applied to the stream data, as specified by the stream's /DecodeParms
+fn png_unpredict(stream_dictionary: &[u8], data: Vec<u8>) -> Vec<u8> {
+    // PNG predictors have values of 10 and above; TIFF predictors are not supported
+    const PNG_PREDICTOR_MIN: usize = 10;
+
+    let predictor = pdf_dictionary_integer(stream_dictionary, "Predictor").unwrap_or(1);
+    if predictor < PNG_PREDICTOR_MIN {
+        return data;
+    }
+
+    let columns = pdf_dictionary_integer(stream_dictionary, "Columns").unwrap_or(1);
+    let colors = pdf_dictionary_integer(stream_dictionary, "Colors").unwrap_or(1);
+    let bits_per_component =
+        pdf_dictionary_integer(stream_dictionary, "BitsPerComponent").unwrap_or(8);
+
+    let bytes_per_pixel = (colors * bits_per_component).div_ceil(8).max(1);
+    let row_size = (columns * colors * bits_per_component).div_ceil(8);
+
+    if row_size >= data.len() {
+        return data;
+    }
+
+    let mut decoded_data: Vec<u8> = vec![];
+    let mut previous_row: Vec<u8> = vec![0; row_size];
+
+    // Each row is prefixed with a byte specifying the PNG filter type used for that row
+    for row in data.chunks_exact(row_size + 1) {
+        let mut current_row = row[1..].to_vec();
+
+        for i in 0..row_size {
+            let left = if i >= bytes_per_pixel {
+                current_row[i - bytes_per_pixel]
+            } else {
+                0
+            };
+            let up = previous_row[i];
+            let up_left = if i >= bytes_per_pixel {
+                previous_row[i - bytes_per_pixel]
+            } else {
+                0
+            };
+
+            let prediction = match row[0] {
+                0 => 0,
+                1 => left,
+                2 => up,
+                3 => ((left as u16 + up as u16) / 2) as u8,
+                4 => paeth(left, up, up_left),
+                _ => {
+                    warn!("Invalid PNG predictor row filter type: {}", row[0]);
+                    return data;
+                }
+            };
+
+            current_row[i] = current_row[i].wrapping_add(prediction);
+        }
+
+        decoded_data.extend_from_slice(&current_row);
+        previous_row = current_row;
+    }
+
+    decoded_data
+}
+
+/// The PNG Paeth predictor function
+fn paeth(left: u8, up: u8, up_left: u8) -> u8 {
+    let estimate = left as i16 + up as i16 - up_left as i16;
+    let left_distance = (estimate - left as i16).abs();
+    let up_distance = (estimate - up as i16).abs();
+    let up_left_distance = (estimate - up_left as i16).abs();
+
+    if left_distance <= up_distance && left_distance <= up_left_distance {
+        left
+    } else if up_distance <= up_left_distance {
+        up
+    } else {
+        up_left
+    }
+}
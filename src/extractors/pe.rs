@@ -0,0 +1,217 @@
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::warn;
+use crate::structures::pe::{
+    PE_RESOURCE_DIRECTORY, PEHeader, parse_pe_header, parse_pe_resource_data_entry,
+    parse_pe_resource_directory,
+};
+
+/// Defines the internal extractor function for extracting resources and overlay data from Windows PE files
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::pe::pe_extractor;
+///
+/// match pe_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn pe_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_pe_resources),
+        ..Default::default()
+    }
+}
+
+/// Writes each resource in the PE file's resource directory to `resources/<type>/<name>_<language>`, and any overlay
+/// data appended after the PE image to `overlay.bin`.
+///
+/// The reported size includes the overlay data, which extends to the end of the available data.
+pub fn extract_pe_resources(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    const RESOURCES_DIRECTORY: &str = "resources";
+    const OVERLAY_FILE_NAME: &str = "overlay.bin";
+
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let pe_data = &file_data[offset..];
+
+    if let Ok(pe_header) = parse_pe_header(pe_data)
+        && let Some(image_size) = pe_header.image_size()
+        && image_size <= pe_data.len()
+    {
+        result.size = Some(pe_data.len());
+        result.success = true;
+
+        if output_directory.is_some() {
+            let chroot = Chroot::new(output_directory);
+
+            for resource in pe_resources(&pe_header, pe_data) {
+                let resource_directory =
+                    chroot.safe_path_join(RESOURCES_DIRECTORY, &resource.type_name);
+                let resource_path =
+                    chroot.safe_path_join(&resource_directory, resource.file_name());
+
+                if chroot.create_directory(&resource_directory).is_err()
+                    || chroot.create_file(&resource_path, resource.data).is_err()
+                {
+                    result.success = false;
+                    return result;
+                }
+            }
+
+            // Overlay data is anything appended after the end of the PE image
+            if image_size < pe_data.len()
+                && chroot
+                    .create_file(OVERLAY_FILE_NAME, &pe_data[image_size..])
+                    .is_err()
+            {
+                result.success = false;
+            }
+        }
+    }
+
+    result
+}
+
+/// Describes a resource stored in a PE file
+struct PEResource<'a> {
+    type_name: String,
+    name: String,
+    language: usize,
+    data: &'a [u8],
+}
+
+impl PEResource<'_> {
+    fn file_name(&self) -> String {
+        // Resource types with a well known file format
+        let extension = match self.type_name.as_str() {
+            "RT_MANIFEST" => "xml",
+            "RT_HTML" => "html",
+            _ => "bin",
+        };
+
+        format!("{}_{}.{}", self.name, self.language, extension)
+    }
+}
+
+/// Walks the PE file's resource directory, and returns each resource that it describes
+fn pe_resources<'a>(pe_header: &PEHeader, pe_data: &'a [u8]) -> Vec<PEResource<'a>> {
+    // Malformed resource directories may reference the same sub-directories over and over
+    const MAX_RESOURCES: usize = 0x10000;
+
+    let mut resources = vec![];
+
+    let resource_table = match pe_header.data_directories.get(PE_RESOURCE_DIRECTORY) {
+        Some(resource_table) if resource_table.size > 0 => resource_table,
+        _ => return resources,
+    };
+
+    // Offsets within the resource directory are relative to the start of the resource directory
+    let resource_data = match pe_header
+        .rva_to_offset(resource_table.address)
+        .and_then(|resource_offset| pe_data.get(resource_offset..))
+    {
+        None => {
+            warn!("PE resource directory lies outside of the file data");
+            return resources;
+        }
+        Some(resource_data) => resource_data,
+    };
+
+    // Resource directories are made up of three levels: resource type, resource name, and language
+    let type_entries = parse_pe_resource_directory(resource_data, 0).unwrap_or_default();
+
+    for type_entry in type_entries.iter().filter(|entry| entry.is_directory) {
+        let type_name = match &type_entry.name {
+            Some(name) => name.clone(),
+            None => resource_type_name(type_entry.id),
+        };
+
+        let name_entries =
+            parse_pe_resource_directory(resource_data, type_entry.offset).unwrap_or_default();
+
+        for name_entry in name_entries.iter().filter(|entry| entry.is_directory) {
+            let name = match &name_entry.name {
+                Some(name) => name.clone(),
+                None => name_entry.id.to_string(),
+            };
+
+            let language_entries =
+                parse_pe_resource_directory(resource_data, name_entry.offset).unwrap_or_default();
+
+            for language_entry in language_entries.iter().filter(|entry| !entry.is_directory) {
+                if resources.len() >= MAX_RESOURCES {
+                    warn!("Too many PE resources, ignoring the rest");
+                    return resources;
+                }
+
+                // Resource data is located by its RVA
+                if let Ok(data_entry) =
+                    parse_pe_resource_data_entry(resource_data, language_entry.offset)
+                    && let Some(data) =
+                        pe_header
+                            .rva_to_offset(data_entry.address)
+                            .and_then(|data_offset| {
+                                pe_data.get(data_offset..data_offset.checked_add(data_entry.size)?)
+                            })
+                {
+                    resources.push(PEResource {
+                        type_name: type_name.clone(),
+                        name: name.clone(),
+                        language: language_entry.id,
+                        data,
+                    });
+                }
+            }
+        }
+    }
+
+    resources
+}
+
+/// Returns the name of a predefined resource type
+fn resource_type_name(type_id: usize) -> String {
+    let name = match type_id {
+        1 => "RT_CURSOR",
+        2 => "RT_BITMAP",
+        3 => "RT_ICON",
+        4 => "RT_MENU",
+        5 => "RT_DIALOG",
+        6 => "RT_STRING",
+        7 => "RT_FONTDIR",
+        8 => "RT_FONT",
+        9 => "RT_ACCELERATOR",
+        10 => "RT_RCDATA",
+        11 => "RT_MESSAGETABLE",
+        12 => "RT_GROUP_CURSOR",
+        14 => "RT_GROUP_ICON",
+        16 => "RT_VERSION",
+        17 => "RT_DLGINCLUDE",
+        19 => "RT_PLUGPLAY",
+        20 => "RT_VXD",
+        21 => "RT_ANICURSOR",
+        22 => "RT_ANIICON",
+        23 => "RT_HTML",
+        24 => "RT_MANIFEST",
+        _ => return type_id.to_string(),
+    };
+
+    name.to_string()
+}
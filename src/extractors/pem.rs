@@ -1,5 +1,9 @@
 use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::extractors::der::log_certificate_info;
+use crate::structures::der::parse_x509_certificate;
 use aho_corasick::AhoCorasick;
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
 
 /// Defines the internal extractor function for carving out PEM keys
 ///
@@ -61,26 +65,40 @@ pub fn pem_certificate_extractor() -> Extractor {
     }
 }
 
+/// Carves a PEM certificate to `certificate.pem`, and logs the certificate's subject, issuer and validity period
 pub fn pem_certificate_carver(
     file_data: &[u8],
     offset: usize,
     output_directory: Option<&str>,
 ) -> ExtractionResult {
-    const CERTIFICATE_FILE_NAME: &str = "pem.crt";
-    pem_carver(
+    const CERTIFICATE_FILE_NAME: &str = "certificate.pem";
+
+    let result = pem_carver(
         file_data,
         offset,
         output_directory,
         Some(CERTIFICATE_FILE_NAME),
-    )
+    );
+
+    if result.success
+        && output_directory.is_some()
+        && let Some(pem_size) = result.size
+        && let Some(der_data) = pem_to_der(&file_data[offset..offset + pem_size])
+        && let Ok(certificate) = parse_x509_certificate(&der_data)
+    {
+        log_certificate_info(&certificate);
+    }
+
+    result
 }
 
+/// Carves a PEM key to `key.pem`
 pub fn pem_key_carver(
     file_data: &[u8],
     offset: usize,
     output_directory: Option<&str>,
 ) -> ExtractionResult {
-    const KEY_FILE_NAME: &str = "pem.key";
+    const KEY_FILE_NAME: &str = "key.pem";
     pem_carver(file_data, offset, output_directory, Some(KEY_FILE_NAME))
 }
 
@@ -101,8 +119,9 @@ pub fn pem_carver(
         if let Some(outfile) = fname {
             if output_directory.is_some() {
                 let chroot = Chroot::new(output_directory);
-                result.success =
-                    chroot.carve_file(outfile, file_data, offset, result.size.unwrap());
+                result.success = chroot
+                    .carve_file(outfile, file_data, offset, result.size.unwrap())
+                    .is_ok();
             }
         }
     }
@@ -147,3 +166,16 @@ fn get_pem_size(file_data: &[u8], start_of_pem_offset: usize) -> Option<usize> {
 
     None
 }
+
+/// Base64 decodes the contents of a PEM file, ignoring the BEGIN/END delimiter lines and any header lines
+fn pem_to_der(pem_data: &[u8]) -> Option<Vec<u8>> {
+    let pem_string = String::from_utf8_lossy(pem_data);
+
+    let base64_string: String = pem_string
+        .lines()
+        .filter(|line| !line.starts_with("-----") && !line.contains(':'))
+        .map(|line| line.trim())
+        .collect();
+
+    BASE64_STANDARD.decode(base64_string).ok()
+}
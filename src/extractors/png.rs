@@ -54,8 +54,9 @@ pub fn extract_png_image(
             // If extraction was requested, extract the PNG
             if output_directory.is_some() {
                 let chroot = Chroot::new(output_directory);
-                result.success =
-                    chroot.carve_file(OUTFILE_NAME, file_data, offset, result.size.unwrap());
+                result.success = chroot
+                    .carve_file(OUTFILE_NAME, file_data, offset, result.size.unwrap())
+                    .is_ok();
             }
         }
     }
@@ -58,7 +58,9 @@ pub fn extract_riff_image(
                 OUTFILE_NAME.to_string()
             };
 
-            result.success = chroot.carve_file(file_path, file_data, offset, result.size.unwrap());
+            result.success = chroot
+                .carve_file(file_path, file_data, offset, result.size.unwrap())
+                .is_ok();
         }
     }
 
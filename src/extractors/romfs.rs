@@ -2,8 +2,8 @@ use crate::common::is_offset_safe;
 use crate::extractors::common::{
     Chroot, ExtractionError, ExtractionResult, Extractor, ExtractorType,
 };
+use crate::logging::warn;
 use crate::structures::romfs::{parse_romfs_file_entry, parse_romfs_header};
-use log::warn;
 
 #[derive(Default, Debug, Clone)]
 struct RomFSEntry {
@@ -92,7 +92,7 @@ pub fn extract_romfs(
                         let romfs_chroot_dir = chroot.chrooted_path(&romfs_header.volume_name);
 
                         // Create the romfs output directory, ensuring that it is contained inside the specified extraction directory
-                        if chroot.create_directory(&romfs_chroot_dir) {
+                        if chroot.create_directory(&romfs_chroot_dir).is_ok() {
                             // Extract RomFS contents
                             file_count = extract_romfs_entries(
                                 romfs_data,
@@ -170,7 +170,10 @@ fn process_romfs_entries(
             // Sanity check the file data offset and size fields
             if (file_entry.offset + file_entry.size) > romfs_data.len() {
                 warn!("Invalid offset/size specified for file {}", file_entry.name);
-                return Err(ExtractionError);
+                return Err(ExtractionError::Sanitization(format!(
+                    "invalid offset/size specified for file {}",
+                    file_entry.name
+                )));
             }
 
             // Don't do anything special for '.' or '..' directory entries
@@ -183,7 +186,9 @@ fn process_romfs_entries(
                         match String::from_utf8(symlink_bytes.to_vec()) {
                             Err(e) => {
                                 warn!("Failed to convert symlink target path to string: {e}");
-                                return Err(ExtractionError);
+                                return Err(ExtractionError::Sanitization(format!(
+                                    "invalid symlink target path: {e}"
+                                )));
                             }
                             Ok(path) => {
                                 file_entry.symlink_target = path.clone();
@@ -240,28 +245,31 @@ fn extract_romfs_entries(
         let file_path = chroot.safe_path_join(parent_directory, &file_entry.name);
 
         if file_entry.directory {
-            extraction_success = chroot.create_directory(&file_path);
+            extraction_success = chroot.create_directory(&file_path).is_ok();
         } else if file_entry.regular {
-            extraction_success =
-                chroot.carve_file(&file_path, romfs_data, file_entry.offset, file_entry.size);
+            extraction_success = chroot
+                .carve_file(&file_path, romfs_data, file_entry.offset, file_entry.size)
+                .is_ok();
         } else if file_entry.symlink {
-            extraction_success = chroot.create_symlink(&file_path, &file_entry.symlink_target);
+            extraction_success = chroot
+                .create_symlink(&file_path, &file_entry.symlink_target)
+                .is_ok();
         } else if file_entry.fifo {
-            extraction_success = chroot.create_fifo(&file_path);
+            extraction_success = chroot.create_fifo(&file_path).is_ok();
         } else if file_entry.socket {
-            extraction_success = chroot.create_socket(&file_path);
+            extraction_success = chroot.create_socket(&file_path).is_ok();
         } else if file_entry.block_device {
-            extraction_success = chroot.create_block_device(
-                &file_path,
-                file_entry.device_major,
-                file_entry.device_minor,
-            );
+            extraction_success = chroot
+                .create_block_device(&file_path, file_entry.device_major, file_entry.device_minor)
+                .is_ok();
         } else if file_entry.character_device {
-            extraction_success = chroot.create_character_device(
-                &file_path,
-                file_entry.device_major,
-                file_entry.device_minor,
-            );
+            extraction_success = chroot
+                .create_character_device(
+                    &file_path,
+                    file_entry.device_major,
+                    file_entry.device_minor,
+                )
+                .is_ok();
         } else {
             continue;
         }
@@ -281,7 +289,7 @@ fn extract_romfs_entries(
 
             // Make executable files executable
             if file_entry.regular && file_entry.executable {
-                chroot.make_executable(&file_path);
+                let _ = chroot.make_executable(&file_path);
             }
         } else {
             warn!("Failed to extract RomFS file {file_path}");
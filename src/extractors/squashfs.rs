@@ -1,4 +1,22 @@
 use crate::extractors;
+use crate::extractors::common::{Chroot, ExtractionResult};
+use crate::logging::{debug, info, warn};
+use crate::structures::squashfs::{
+    SQUASHFS_BLOCK_DEVICE, SQUASHFS_CHARACTER_DEVICE, SQUASHFS_DIRECTORY, SQUASHFS_FIFO,
+    SQUASHFS_METADATA_SIZE, SQUASHFS_NO_FRAGMENT, SQUASHFS_REGULAR_FILE, SQUASHFS_SOCKET,
+    SQUASHFS_SYMLINK, SquashFSFragment, SquashFSHeader, SquashFSInode, parse_squashfs_directory,
+    parse_squashfs_fragment, parse_squashfs_header, parse_squashfs_id, parse_squashfs_inode,
+    parse_squashfs_metadata_header, parse_squashfs_table_pointer,
+};
+use flate2::bufread::ZlibDecoder;
+use liblzma::bufread::XzDecoder;
+use liblzma::stream::Stream;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+
+/// Name of the directory that sasquatch extracts file system contents to
+const SQUASHFS_ROOT_DIRECTORY: &str = "squashfs-root";
 
 /// Describes how to run the sasquatch utility to extract SquashFS images
 ///
@@ -29,6 +47,8 @@ pub fn squashfs_extractor() -> extractors::common::Extractor {
         arguments: vec![extractors::common::SOURCE_FILE_PLACEHOLDER.to_string()],
         // Exit code may be 0 or 2; 2 indicates running as not root, but otherwise extraction is ok
         exit_codes: vec![0, 2],
+        validator: Some(validate_squashfs_extraction),
+        no_xattr_arguments: vec!["-no-xattrs".to_string()],
         ..Default::default()
     }
 }
@@ -65,6 +85,8 @@ pub fn squashfs_le_extractor() -> extractors::common::Extractor {
         ],
         // Exit code may be 0 or 2; 2 indicates running as not root, but otherwise extraction is ok
         exit_codes: vec![0, 2],
+        validator: Some(validate_squashfs_extraction),
+        no_xattr_arguments: vec!["-no-xattrs".to_string()],
         ..Default::default()
     }
 }
@@ -101,6 +123,8 @@ pub fn squashfs_be_extractor() -> extractors::common::Extractor {
         ],
         // Exit code may be 0 or 2; 2 indicates running as not root, but otherwise extraction is ok
         exit_codes: vec![0, 2],
+        validator: Some(validate_squashfs_extraction),
+        no_xattr_arguments: vec!["-no-xattrs".to_string()],
         ..Default::default()
     }
 }
@@ -134,6 +158,557 @@ pub fn squashfs_v4_be_extractor() -> extractors::common::Extractor {
         arguments: vec![extractors::common::SOURCE_FILE_PLACEHOLDER.to_string()],
         // Exit code may be 0 or 2; 2 indicates running as not root, but otherwise extraction is ok
         exit_codes: vec![0, 2],
+        validator: Some(validate_squashfs_extraction),
+        no_xattr_arguments: vec!["-no-xattrs".to_string()],
+        ..Default::default()
+    }
+}
+
+/// Validates SquashFS extractor output; both sasquatch and `extract_squashfs_v4` always extract the SquashFS image
+/// contents into a `squashfs-root` directory
+pub fn validate_squashfs_extraction(output_directory: &str) -> bool {
+    std::path::Path::new(output_directory)
+        .join(SQUASHFS_ROOT_DIRECTORY)
+        .is_dir()
+}
+
+/// Defines the internal extractor function for extracting SquashFSv4 images
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::squashfs::squashfs_v4_extractor;
+///
+/// match squashfs_v4_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn squashfs_v4_extractor() -> extractors::common::Extractor {
+    extractors::common::Extractor {
+        utility: extractors::common::ExtractorType::Internal(extract_squashfs_v4),
+        validator: Some(validate_squashfs_extraction),
+        ..Default::default()
+    }
+}
+
+// SquashFSv4 compression IDs
+const SQUASHFS_GZIP: usize = 1;
+const SQUASHFS_LZMA: usize = 2;
+const SQUASHFS_XZ: usize = 4;
+
+/// Returns true if the internal SquashFSv4 extractor supports the specified compression ID
+pub fn squashfs_v4_compression_supported(compression: usize) -> bool {
+    [SQUASHFS_GZIP, SQUASHFS_LZMA, SQUASHFS_XZ].contains(&compression)
+}
+
+/// Extracts the directories, regular files, symlinks, device files, FIFOs and sockets from a gzip, LZMA or XZ
+/// compressed SquashFSv4 image, of either byte order, into a `squashfs-root` directory. Permissions, ownership and
+/// modification times are restored where possible.
+///
+/// Like sasquatch, the header values that vendor-modified SquashFS tools are known to change are tolerated: the magic
+/// bytes, the flags, and block sizes that are not a power of two, are out of spec, or disagree with the block log.
+/// Each of these is logged. Extended attributes are not extracted.
+pub fn extract_squashfs_v4(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
         ..Default::default()
+    };
+
+    let Some(squashfs_data) = file_data.get(offset..) else {
+        return result;
+    };
+
+    let Ok(squashfs_header) = parse_squashfs_header(squashfs_data) else {
+        return result;
+    };
+
+    if squashfs_header.major_version != 4
+        || !squashfs_v4_compression_supported(squashfs_header.compression)
+    {
+        return result;
+    }
+
+    let Some(image_data) = squashfs_data.get(..squashfs_header.image_size) else {
+        return result;
+    };
+
+    let Some(file_system) = SquashFSFileSystem::new(image_data, squashfs_header) else {
+        debug!("Failed to read SquashFS metadata tables");
+        return result;
+    };
+
+    // The root directory must, at the very least, be valid
+    if let Some(root_inode) = file_system.inode(file_system.header.root_inode)
+        && root_inode.file_type == SQUASHFS_DIRECTORY
+    {
+        result.size = Some(image_data.len());
+        result.success = true;
+
+        for quirk in &file_system.header.quirks {
+            info!("Extracting nonstandard SquashFS image with {quirk}");
+        }
+
+        if output_directory.is_some() {
+            let chroot = Chroot::new(output_directory);
+            let file_count = file_system.extract(&chroot, root_inode);
+            debug!("Extracted {file_count} SquashFS files");
+        }
+    }
+
+    result
+}
+
+/// Permission bits of the inode permissions
+const PERMISSION_BITS: usize = 0o7777;
+
+/// Data block size words with this bit set are stored uncompressed
+const UNCOMPRESSED_BLOCK: usize = 1 << 24;
+
+/// Mask of the size bits of a data block size word
+const BLOCK_SIZE_MASK: usize = UNCOMPRESSED_BLOCK - 1;
+
+/// A metadata table, decompressed in its entirety
+struct MetadataTable {
+    data: Vec<u8>,
+    /// Offsets of each metadata block in the uncompressed data, keyed by their offset from the start of the table
+    blocks: HashMap<usize, usize>,
+}
+
+impl MetadataTable {
+    /// Returns the uncompressed data starting at the specified metadata block and offset
+    fn get(&self, block: usize, offset: usize) -> Option<&[u8]> {
+        self.data
+            .get(self.blocks.get(&block)?.checked_add(offset)?..)
+    }
+}
+
+/// A SquashFSv4 image
+struct SquashFSFileSystem<'a> {
+    data: &'a [u8],
+    header: SquashFSHeader,
+    inode_table: MetadataTable,
+    directory_table: MetadataTable,
+    fragments: Vec<SquashFSFragment>,
+    /// UIDs and GIDs, indexed by the inodes
+    ids: Vec<usize>,
+}
+
+impl<'a> SquashFSFileSystem<'a> {
+    /// Reads the inode, directory, fragment and ID tables
+    fn new(data: &'a [u8], header: SquashFSHeader) -> Option<Self> {
+        const FRAGMENT_ENTRY_SIZE: usize = 16;
+        const ID_ENTRY_SIZE: usize = 4;
+
+        let mut file_system = SquashFSFileSystem {
+            data,
+            header,
+            inode_table: MetadataTable {
+                data: vec![],
+                blocks: HashMap::new(),
+            },
+            directory_table: MetadataTable {
+                data: vec![],
+                blocks: HashMap::new(),
+            },
+            fragments: vec![],
+            ids: vec![],
+        };
+
+        // The directory table is followed by the fragment table, if any, and always by the ID table
+        let directory_table_end = [
+            file_system.header.fragment_table_start,
+            file_system.header.uid_table_start,
+        ]
+        .into_iter()
+        .filter(|table_start| *table_start > file_system.header.directory_table_start)
+        .min()?;
+
+        file_system.inode_table = file_system.metadata_table(
+            file_system.header.inode_table_start,
+            file_system.header.directory_table_start,
+        )?;
+        file_system.directory_table = file_system.metadata_table(
+            file_system.header.directory_table_start,
+            directory_table_end,
+        )?;
+
+        if file_system.header.fragment_count > 0 {
+            let fragment_table = file_system.lookup_table(
+                file_system.header.fragment_table_start,
+                file_system
+                    .header
+                    .fragment_count
+                    .checked_mul(FRAGMENT_ENTRY_SIZE)?,
+            )?;
+
+            for fragment_data in fragment_table.chunks_exact(FRAGMENT_ENTRY_SIZE) {
+                file_system.fragments.push(
+                    parse_squashfs_fragment(fragment_data, &file_system.header.endianness).ok()?,
+                );
+            }
+        }
+
+        let id_table = file_system.lookup_table(
+            file_system.header.uid_table_start,
+            file_system.header.id_count * ID_ENTRY_SIZE,
+        )?;
+
+        for id_data in id_table.chunks_exact(ID_ENTRY_SIZE) {
+            file_system
+                .ids
+                .push(parse_squashfs_id(id_data, &file_system.header.endianness).ok()?);
+        }
+
+        Some(file_system)
+    }
+
+    /// Extracts all files in the file system, returning the number of files extracted
+    fn extract(&self, chroot: &Chroot, root_inode: SquashFSInode) -> usize {
+        let mut file_count: usize = 0;
+
+        if chroot.create_directory(SQUASHFS_ROOT_DIRECTORY).is_err() {
+            return file_count;
+        }
+
+        // Directories waiting to be extracted, and all directories seen so far, to detect directory loops
+        let mut seen_directories: HashSet<usize> = HashSet::from([root_inode.inode_number]);
+        let mut directories: Vec<(SquashFSInode, String)> =
+            vec![(root_inode.clone(), SQUASHFS_ROOT_DIRECTORY.to_string())];
+
+        // Paths of extracted files, keyed by inode number, for creating hard links
+        let mut extracted_files: HashMap<usize, String> = HashMap::new();
+
+        // Metadata is restored once everything has been extracted
+        let mut extracted_inodes: Vec<(String, SquashFSInode)> =
+            vec![(SQUASHFS_ROOT_DIRECTORY.to_string(), root_inode)];
+
+        // Fragment blocks are shared by many files, so they are only decompressed once
+        let mut fragment_blocks: HashMap<usize, Vec<u8>> = HashMap::new();
+
+        while let Some((directory_inode, directory_path)) = directories.pop() {
+            for (name, inode_reference) in self.directory_entries(&directory_inode) {
+                let file_path = chroot.safe_path_join(&directory_path, &name);
+
+                let Some(inode) = self.inode(inode_reference) else {
+                    warn!("Failed to read SquashFS inode for {file_path}");
+                    continue;
+                };
+
+                // Directories can't be hard linked, anything else may have been extracted already
+                if inode.file_type != SQUASHFS_DIRECTORY
+                    && let Some(link_target) = extracted_files.get(&inode.inode_number)
+                {
+                    if chroot.create_hardlink(&file_path, link_target).is_ok() {
+                        file_count += 1;
+                    }
+                    continue;
+                }
+
+                let extracted = match inode.file_type {
+                    SQUASHFS_DIRECTORY => {
+                        if !seen_directories.insert(inode.inode_number) {
+                            warn!(
+                                "SquashFS directory {file_path} was already extracted, not following directory loop"
+                            );
+                            false
+                        } else if chroot.create_directory(&file_path).is_ok() {
+                            directories.push((inode.clone(), file_path.clone()));
+                            true
+                        } else {
+                            false
+                        }
+                    }
+                    SQUASHFS_REGULAR_FILE => match self.file_data(&inode, &mut fragment_blocks) {
+                        None => false,
+                        Some(data) => chroot.create_sparse_file(&file_path, &data).is_ok(),
+                    },
+                    SQUASHFS_SYMLINK => chroot
+                        .create_symlink(&file_path, String::from_utf8_lossy(&inode.symlink_target))
+                        .is_ok(),
+                    SQUASHFS_BLOCK_DEVICE | SQUASHFS_CHARACTER_DEVICE => {
+                        // Minor numbers are split around the major number
+                        let major = (inode.device >> 8) & 0xFFF;
+                        let minor = (inode.device & 0xFF) | ((inode.device >> 12) & 0xFFF00);
+
+                        match inode.file_type {
+                            SQUASHFS_CHARACTER_DEVICE => {
+                                chroot.create_character_device(&file_path, major, minor)
+                            }
+                            _ => chroot.create_block_device(&file_path, major, minor),
+                        }
+                        .is_ok()
+                    }
+                    SQUASHFS_FIFO => chroot.create_fifo(&file_path).is_ok(),
+                    SQUASHFS_SOCKET => chroot.create_socket(&file_path).is_ok(),
+                    _ => false,
+                };
+
+                if extracted {
+                    file_count += 1;
+
+                    if inode.file_type != SQUASHFS_DIRECTORY {
+                        extracted_files.insert(inode.inode_number, file_path.clone());
+                    }
+
+                    extracted_inodes.push((file_path, inode));
+                } else {
+                    warn!("Failed to extract SquashFS file {file_path}");
+                }
+            }
+        }
+
+        // Files are restored before the directories that contain them, so that read-only directories are made read-only last
+        for (file_path, inode) in extracted_inodes.iter().rev() {
+            let _ = chroot.set_modification_time(file_path, inode.mtime as i64);
+
+            if let (Some(uid), Some(gid)) =
+                (self.ids.get(inode.uid_index), self.ids.get(inode.gid_index))
+            {
+                let _ = chroot.set_owner(file_path, *uid as u32, *gid as u32);
+            }
+
+            let _ = chroot.set_permissions(file_path, (inode.permissions & PERMISSION_BITS) as u32);
+        }
+
+        file_count
+    }
+
+    /// Decompresses a metadata or data block, which must not decompress to more than `max_size` bytes
+    fn decompress(&self, compressed_data: &[u8], max_size: usize) -> Option<Vec<u8>> {
+        let mut decompressed_data: Vec<u8> = vec![];
+
+        // Read at most one byte more than allowed, to detect blocks that decompress to more than the maximum size
+        let read_limit = max_size as u64 + 1;
+
+        let status = match self.header.compression {
+            SQUASHFS_GZIP => ZlibDecoder::new(compressed_data)
+                .take(read_limit)
+                .read_to_end(&mut decompressed_data),
+            SQUASHFS_LZMA => {
+                XzDecoder::new_stream(compressed_data, Stream::new_lzma_decoder(u64::MAX).ok()?)
+                    .take(read_limit)
+                    .read_to_end(&mut decompressed_data)
+            }
+            SQUASHFS_XZ => XzDecoder::new_stream(
+                compressed_data,
+                Stream::new_stream_decoder(u64::MAX, 0).ok()?,
+            )
+            .take(read_limit)
+            .read_to_end(&mut decompressed_data),
+            _ => return None,
+        };
+
+        if status.is_ok() && decompressed_data.len() <= max_size {
+            return Some(decompressed_data);
+        }
+
+        None
+    }
+
+    /// Reads the metadata block at the specified image offset, returning its uncompressed data and the offset of the
+    /// next metadata block
+    fn metadata_block(&self, block_start: usize) -> Option<(Vec<u8>, usize)> {
+        let metadata_header =
+            parse_squashfs_metadata_header(self.data.get(block_start..)?, &self.header.endianness)
+                .ok()?;
+
+        let data_start = block_start + metadata_header.header_size;
+        let data_end = data_start + metadata_header.data_size;
+        let block_data = self.data.get(data_start..data_end)?;
+
+        let uncompressed_data = match metadata_header.compressed {
+            false => block_data.to_vec(),
+            true => self.decompress(block_data, SQUASHFS_METADATA_SIZE)?,
+        };
+
+        Some((uncompressed_data, data_end))
+    }
+
+    /// Reads and decompresses all metadata blocks between the specified image offsets
+    fn metadata_table(&self, table_start: usize, table_end: usize) -> Option<MetadataTable> {
+        // Metadata compresses well, but not unreasonably so
+        const MAX_TABLE_SIZE: usize = 64 * 1024 * 1024;
+
+        let max_table_size = self.data.len().max(MAX_TABLE_SIZE);
+        let mut table = MetadataTable {
+            data: vec![],
+            blocks: HashMap::new(),
+        };
+
+        let mut block_start = table_start;
+
+        while block_start < table_end {
+            let (block_data, next_block) = self.metadata_block(block_start)?;
+            table
+                .blocks
+                .insert(block_start - table_start, table.data.len());
+            table.data.extend_from_slice(&block_data);
+            block_start = next_block;
+
+            if table.data.len() > max_table_size {
+                warn!("SquashFS metadata table at offset {table_start:#X} is unreasonably large");
+                return None;
+            }
+        }
+
+        Some(table)
+    }
+
+    /// Reads a fragment or ID table, which is an array of pointers to the metadata blocks containing the table entries
+    fn lookup_table(&self, table_start: usize, table_size: usize) -> Option<Vec<u8>> {
+        const POINTER_SIZE: usize = 8;
+
+        let pointer_count = table_size.div_ceil(SQUASHFS_METADATA_SIZE);
+        let pointers_end = table_start.checked_add(pointer_count * POINTER_SIZE)?;
+        let mut table_data: Vec<u8> = vec![];
+
+        for pointer_data in self
+            .data
+            .get(table_start..pointers_end)?
+            .chunks_exact(POINTER_SIZE)
+        {
+            let block_start =
+                parse_squashfs_table_pointer(pointer_data, &self.header.endianness).ok()?;
+            table_data.extend_from_slice(&self.metadata_block(block_start)?.0);
+        }
+
+        table_data
+            .get(..table_size)
+            .map(|table_data| table_data.to_vec())
+    }
+
+    /// Reads and parses the inode at the specified inode reference
+    fn inode(&self, inode_reference: usize) -> Option<SquashFSInode> {
+        let inode_data = self
+            .inode_table
+            .get(inode_reference >> 16, inode_reference & 0xFFFF)?;
+        parse_squashfs_inode(inode_data, self.header.block_size, &self.header.endianness).ok()
+    }
+
+    /// Returns the names and inode references of the entries in a directory
+    fn directory_entries(&self, directory_inode: &SquashFSInode) -> Vec<(String, usize)> {
+        // Directory sizes include the "." and ".." entries, which are not stored
+        const IMPLICIT_ENTRIES_SIZE: usize = 3;
+
+        let listing_size = directory_inode
+            .directory_size
+            .saturating_sub(IMPLICIT_ENTRIES_SIZE);
+
+        let Some(listing_data) = self
+            .directory_table
+            .get(
+                directory_inode.directory_block,
+                directory_inode.directory_offset,
+            )
+            .and_then(|directory_data| directory_data.get(..listing_size))
+        else {
+            warn!("SquashFS directory listing is outside of the directory table");
+            return vec![];
+        };
+
+        match parse_squashfs_directory(listing_data, &self.header.endianness) {
+            Err(_) => {
+                warn!("Failed to parse SquashFS directory listing");
+                vec![]
+            }
+            Ok(entries) => entries
+                .into_iter()
+                .map(|entry| {
+                    (
+                        String::from_utf8_lossy(&entry.name).to_string(),
+                        (entry.inode_block << 16) | entry.inode_offset,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Reads a data or fragment block, given its image offset and size word
+    fn data_block(&self, block_start: usize, block_size_word: usize) -> Option<Vec<u8>> {
+        let block_data = self
+            .data
+            .get(block_start..block_start.checked_add(block_size_word & BLOCK_SIZE_MASK)?)?;
+
+        match block_size_word & UNCOMPRESSED_BLOCK {
+            0 => self.decompress(block_data, self.header.block_size),
+            _ => Some(block_data.to_vec()),
+        }
+    }
+
+    /// Returns the data of a regular file; sparse blocks read as zeros
+    fn file_data(
+        &self,
+        inode: &SquashFSInode,
+        fragment_blocks: &mut HashMap<usize, Vec<u8>>,
+    ) -> Option<Vec<u8>> {
+        // Sparse files may be larger than the file system itself, but not unreasonably so
+        const MAX_SPARSE_FILE_SIZE: usize = 1024 * 1024 * 1024;
+
+        if inode.file_size > self.data.len().max(MAX_SPARSE_FILE_SIZE) {
+            warn!(
+                "SquashFS file size {:#X} is unreasonably large",
+                inode.file_size
+            );
+            return None;
+        }
+
+        let block_size = self.header.block_size;
+        let mut data: Vec<u8> = vec![];
+        let mut block_start = inode.blocks_start;
+
+        for block_size_word in &inode.block_sizes {
+            let compressed_size = block_size_word & BLOCK_SIZE_MASK;
+
+            // Sparse blocks are not stored in the image
+            if compressed_size == 0 {
+                let sparse_size = block_size.min(inode.file_size - data.len());
+                data.resize(data.len() + sparse_size, 0);
+                continue;
+            }
+
+            data.extend_from_slice(&self.data_block(block_start, *block_size_word)?);
+            block_start += compressed_size;
+
+            if data.len() > inode.file_size {
+                return None;
+            }
+        }
+
+        if inode.fragment_index != SQUASHFS_NO_FRAGMENT {
+            let fragment = self.fragments.get(inode.fragment_index)?;
+
+            let fragment_block = match fragment_blocks.entry(fragment.start) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(self.data_block(fragment.start, fragment.size)?)
+                }
+            };
+
+            let tail_size = inode.file_size - data.len();
+            let tail_start = inode.fragment_offset;
+            data.extend_from_slice(
+                fragment_block.get(tail_start..tail_start.checked_add(tail_size)?)?,
+            );
+        }
+
+        if data.len() != inode.file_size {
+            return None;
+        }
+
+        Some(data)
     }
 }
@@ -1,6 +1,8 @@
-use crate::extractors;
+use crate::extractors::common::{ExtractionResult, Extractor, ExtractorType};
+use crate::extractors::ihex::write_memory_image;
+use crate::structures::srec::parse_srec;
 
-/// Describes how to run the srec_cat utility to convert Motorola S-records to binary
+/// Defines the internal extractor function for converting Motorola S-records to binary
 ///
 /// ```
 /// use std::io::ErrorKind;
@@ -22,17 +24,24 @@ use crate::extractors;
 ///     }
 /// }
 /// ```
-pub fn srec_extractor() -> extractors::common::Extractor {
-    extractors::common::Extractor {
-        utility: extractors::common::ExtractorType::External("srec_cat".to_string()),
-        extension: "hex".to_string(),
-        arguments: vec![
-            "-output".to_string(),
-            "s-record.bin".to_string(),
-            "-binary".to_string(),
-            extractors::common::SOURCE_FILE_PLACEHOLDER.to_string(),
-        ],
-        exit_codes: vec![0],
+pub fn srec_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_srec),
         ..Default::default()
     }
 }
+
+/// Converts Motorola S-records to a raw binary image, starting at the lowest record address
+pub fn extract_srec(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    const OUTPUT_FILE_NAME: &str = "s-record.bin";
+
+    write_memory_image(
+        parse_srec(&file_data[offset..]),
+        OUTPUT_FILE_NAME,
+        output_directory,
+    )
+}
@@ -63,7 +63,7 @@ fn extract_swapped(
             // Write to file, if requested
             if output_directory.is_some() {
                 let chroot = Chroot::new(output_directory);
-                result.success = chroot.create_file(OUTPUT_FILE_NAME, &swapped_data);
+                result.success = chroot.create_file(OUTPUT_FILE_NAME, &swapped_data).is_ok();
             }
         }
     }
@@ -0,0 +1,169 @@
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::debug;
+use crate::structures::swf::{SWF_HEADER_SIZE, SWFCompression, parse_swf_header};
+use flate2::bufread::ZlibDecoder;
+use liblzma::bufread::XzDecoder;
+use liblzma::stream::Stream;
+use std::io::Read;
+
+/// Defines the internal extractor function for decompressing compressed SWF files
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::swf::swf_extractor;
+///
+/// match swf_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn swf_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(swf_decompress),
+        ..Default::default()
+    }
+}
+
+/// Internal extractor for zlib (CWS) and LZMA (ZWS) compressed SWF files.
+///
+/// The SWF body is decompressed and written, along with an uncompressed (FWS) SWF header, to `decompressed.swf`.
+/// The body must decompress to exactly the file length specified in the SWF header.
+pub fn swf_decompress(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    // Output file for the uncompressed SWF file
+    const OUTPUT_FILE_NAME: &str = "decompressed.swf";
+    // Memory limit for the LZMA decoder: 1GB
+    const MEM_LIMIT: u64 = 1024 * 1024 * 1024;
+
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let swf_data = file_data.get(offset..).unwrap_or_default();
+
+    let Ok(swf_header) = parse_swf_header(swf_data) else {
+        return result;
+    };
+
+    let Some(compressed_body) = swf_data.get(swf_header.body_offset..) else {
+        return result;
+    };
+
+    let body_size = swf_header.file_length - SWF_HEADER_SIZE;
+    let chroot = Chroot::new(output_directory);
+
+    // The uncompressed SWF header differs from the compressed SWF header only in its magic bytes
+    if output_directory.is_some() {
+        let mut uncompressed_header = b"FWS".to_vec();
+        uncompressed_header.extend_from_slice(&swf_data[3..SWF_HEADER_SIZE]);
+
+        if chroot
+            .create_file(OUTPUT_FILE_NAME, &uncompressed_header)
+            .is_err()
+        {
+            return result;
+        }
+    }
+
+    let output_file = output_directory.map(|_| (&chroot, OUTPUT_FILE_NAME));
+
+    let compressed_size = match swf_header.compression {
+        SWFCompression::Zlib => {
+            let mut decompressor = ZlibDecoder::new(compressed_body);
+
+            decompress_body(&mut decompressor, body_size, output_file)
+                .then(|| decompressor.total_in() as usize)
+        }
+        SWFCompression::Lzma => {
+            // The LZMA body has no header of its own; decode it as .lzma data with the SWF's LZMA properties and size
+            let mut lzma_header = swf_header.lzma_properties.clone();
+            lzma_header.extend_from_slice(&(body_size as u64).to_le_bytes());
+
+            match Stream::new_lzma_decoder(MEM_LIMIT) {
+                Err(_) => None,
+                Ok(stream) => {
+                    let mut decompressor = XzDecoder::new_stream(
+                        lzma_header.as_slice().chain(compressed_body),
+                        stream,
+                    );
+
+                    decompress_body(&mut decompressor, body_size, output_file)
+                        .then_some(swf_header.compressed_size)
+                        .flatten()
+                }
+            }
+        }
+        SWFCompression::None => None,
+    };
+
+    if let Some(compressed_size) = compressed_size
+        && let Some(swf_size) = swf_header.body_offset.checked_add(compressed_size)
+        && swf_size <= swf_data.len()
+    {
+        result.size = Some(swf_size);
+        result.success = true;
+    }
+
+    result
+}
+
+/// Decompresses the SWF body, optionally appending it to the output file, and returns true if it decompressed to
+/// exactly `body_size` bytes
+fn decompress_body(
+    decompressor: &mut impl Read,
+    body_size: usize,
+    output_file: Option<(&Chroot, &str)>,
+) -> bool {
+    // Size of decompression buffer
+    const BLOCK_SIZE: usize = 8192;
+
+    let mut decompressed_buffer = [0; BLOCK_SIZE];
+    let mut bytes_decompressed: usize = 0;
+
+    loop {
+        match decompressor.read(&mut decompressed_buffer) {
+            Err(e) => {
+                debug!("SWF decompression failed after {bytes_decompressed} bytes: {e}");
+                return false;
+            }
+            Ok(0) => break,
+            Ok(n) => {
+                bytes_decompressed += n;
+
+                if bytes_decompressed > body_size {
+                    debug!("SWF body decompresses to more than {body_size} bytes");
+                    return false;
+                }
+
+                if let Some((chroot, file_name)) = output_file
+                    && chroot
+                        .append_to_file(file_name, &decompressed_buffer[..n])
+                        .is_err()
+                {
+                    return false;
+                }
+            }
+        }
+    }
+
+    if bytes_decompressed != body_size {
+        debug!("SWF body decompressed to {bytes_decompressed} bytes, expected {body_size} bytes");
+        return false;
+    }
+
+    true
+}
@@ -1,4 +1,6 @@
 use crate::extractors;
+use crate::extractors::common::ExtendedAttributes;
+use crate::logging::warn;
 
 /// Describes how to run the tar utility to extract tarball archives
 ///
@@ -33,6 +35,56 @@ pub fn tarball_extractor() -> extractors::common::Extractor {
         ],
         // Exit code may be 2 if attempting to create special device files fails
         exit_codes: vec![0, 2],
+        xattr_reader: Some(tarball_xattrs),
         ..Default::default()
     }
 }
+
+/// Reads the extended attributes of each file in a tarball from its PAX headers
+pub fn tarball_xattrs(tarball_data: &[u8]) -> Vec<(String, ExtendedAttributes)> {
+    // PAX header keys that store extended attributes are prefixed with this string
+    const XATTR_KEY_PREFIX: &str = "SCHILY.xattr.";
+
+    let mut file_xattrs = vec![];
+    let mut tarball = tar::Archive::new(tarball_data);
+
+    let entries = match tarball.entries() {
+        Err(e) => {
+            warn!("Failed to read tarball entries: {e}");
+            return file_xattrs;
+        }
+        Ok(entries) => entries,
+    };
+
+    for entry in entries {
+        let mut entry = match entry {
+            Err(e) => {
+                warn!("Failed to read tarball entry: {e}");
+                break;
+            }
+            Ok(entry) => entry,
+        };
+
+        let file_path = match entry.path() {
+            Err(_) => continue,
+            Ok(file_path) => file_path.display().to_string(),
+        };
+
+        let attributes: ExtendedAttributes = match entry.pax_extensions() {
+            Ok(Some(pax_extensions)) => pax_extensions
+                .filter_map(|extension| extension.ok())
+                .filter_map(|extension| {
+                    let name = extension.key().ok()?.strip_prefix(XATTR_KEY_PREFIX)?;
+                    Some((name.to_string(), extension.value_bytes().to_vec()))
+                })
+                .collect(),
+            _ => continue,
+        };
+
+        if !attributes.is_empty() {
+            file_xattrs.push((file_path, attributes));
+        }
+    }
+
+    file_xattrs
+}
@@ -72,16 +72,24 @@ pub fn extract_trx_partitions(
                             }
 
                             let this_partition_file_name = format!("partition_{i}.bin");
-                            result.success = chroot.carve_file(
-                                &this_partition_file_name,
-                                file_data,
-                                this_partition_absolute_offset,
-                                this_partition_size,
-                            );
+                            result.success = chroot
+                                .carve_file(
+                                    &this_partition_file_name,
+                                    file_data,
+                                    this_partition_absolute_offset,
+                                    this_partition_size,
+                                )
+                                .is_ok();
 
                             if !result.success {
                                 break;
                             }
+
+                            result.add_component(
+                                format!("partition_{i}"),
+                                this_partition_file_name,
+                                this_partition_size,
+                            );
                         }
                     }
                 }
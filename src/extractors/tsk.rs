@@ -1,6 +1,10 @@
 use crate::extractors;
 
-/// Describes how to run the tsk_recover utility to extract various file systems
+/// Describes how to run the tsk_recover utility to extract various file systems.
+///
+/// NTFS partitions are extracted by the internal `extractors::ntfs` extractor; register this extractor for the `ntfs`
+/// signature with `Binwalk::register_extractor` to use tsk_recover instead.
+#[allow(dead_code)]
 pub fn tsk_extractor() -> extractors::common::Extractor {
     extractors::common::Extractor {
         utility: extractors::common::ExtractorType::External("tsk_recover".to_string()),
@@ -1,4 +1,18 @@
 use crate::extractors;
+use crate::extractors::common::{Chroot, ChrootError, ExtractionResult, Extractor, ExtractorType};
+use crate::extractors::lzh::lzh_decompress;
+use crate::logging::{debug, warn};
+use crate::structures::uefi::{
+    UEFI_FILE_TYPE_PAD, UEFI_FILE_TYPE_RAW, UEFI_SECTION_COMPRESSION,
+    UEFI_SECTION_FIRMWARE_VOLUME_IMAGE, UEFI_SECTION_GUID_DEFINED, UEFI_SECTION_USER_INTERFACE,
+    UEFI_VOLUME_ERASE_POLARITY, UEFISectionHeader, UEFIVolumeHeader,
+    parse_uefi_compression_section, parse_uefi_file_header, parse_uefi_guid_defined_section,
+    parse_uefi_section_header, parse_uefi_volume_header,
+};
+use liblzma::bufread::XzDecoder;
+use liblzma::stream::Stream;
+use std::io::Read;
+use std::path::Path;
 
 /// Describes how to run the uefi-firmware-parser utility to extract UEFI images
 ///
@@ -38,5 +52,454 @@ pub fn uefi_extractor() -> extractors::common::Extractor {
          * Recursing into this data would result in double extractions for no good reason.
          */
         do_not_recurse: true,
+        ..Default::default()
     }
 }
+
+/// Defines the internal extractor function for UEFI PI firmware volumes
+///
+/// ```
+/// use std::io::ErrorKind;
+/// use std::process::Command;
+/// use binwalk::extractors::common::ExtractorType;
+/// use binwalk::extractors::uefi::uefi_volume_extractor;
+///
+/// match uefi_volume_extractor().utility {
+///     ExtractorType::None => panic!("Invalid extractor type of None"),
+///     ExtractorType::Internal(func) => println!("Internal extractor OK: {:?}", func),
+///     ExtractorType::External(cmd) => {
+///         if let Err(e) = Command::new(&cmd).output() {
+///             if e.kind() == ErrorKind::NotFound {
+///                 panic!("External extractor '{}' not found", cmd);
+///             } else {
+///                 panic!("Failed to execute external extractor '{}': {}", cmd, e);
+///             }
+///         }
+///     }
+/// }
+/// ```
+pub fn uefi_volume_extractor() -> Extractor {
+    Extractor {
+        utility: ExtractorType::Internal(extract_uefi_volume),
+        ..Default::default()
+    }
+}
+
+/// Extracts the files from a UEFI PI firmware volume, starting at the beginning of the volume header.
+///
+/// Each FFS file is extracted to a directory named after its GUID. The leaf sections of a file are written to that
+/// directory as `section_<n>.<type>`, e.g. `section_0.pe32` or `section_1.ui`; compressed (EFI, Tiano and LZMA) and
+/// other encapsulation sections are decoded, and their contents extracted in their place. Files in nested firmware
+/// volumes are extracted to directories inside the directory of the file that contains them. Raw files are written
+/// to `<GUID>/raw.bin`.
+///
+/// The reported size is the volume size from the volume header, which separates volumes that are concatenated
+/// together, as they usually are in flash dumps. If no output directory is specified, only the header is validated.
+pub fn extract_uefi_volume(
+    file_data: &[u8],
+    offset: usize,
+    output_directory: Option<&str>,
+) -> ExtractionResult {
+    let mut result = ExtractionResult {
+        ..Default::default()
+    };
+
+    let Some(volume_data) = file_data.get(offset..) else {
+        return result;
+    };
+
+    let Ok(volume_header) = parse_uefi_volume_header(volume_data) else {
+        return result;
+    };
+
+    if volume_header.volume_size > volume_data.len() {
+        debug!("UEFI volume is truncated");
+        return result;
+    }
+
+    if !volume_header.checksum_valid {
+        warn!("UEFI volume header checksum mismatch");
+    }
+
+    result.size = Some(volume_header.volume_size);
+    result.success = true;
+
+    if output_directory.is_some() {
+        let chroot = Chroot::new(output_directory);
+
+        result.success = extract_volume_files(
+            &chroot,
+            &volume_data[..volume_header.volume_size],
+            &volume_header,
+            "/",
+            0,
+        )
+        .is_ok();
+    }
+
+    result
+}
+
+/// Maximum nesting depth of encapsulation sections and nested volumes
+const MAX_NESTING_DEPTH: usize = 16;
+/// Maximum size of a decompressed section
+const MAX_DECOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
+/// File system GUIDs of FFSv1, FFSv2 and FFSv3 volumes
+const FFS_GUIDS: [&str; 3] = [
+    "7A9354D9-0468-444A-81CE-0BF617D890DF",
+    "8C8CE578-8A3D-4F1C-9935-896185C32DD3",
+    "5473C07A-3DCB-4DCA-BD6F-1E9689E7349A",
+];
+
+/// GUIDs of GUID defined sections that can be decoded
+const LZMA_SECTION_GUID: &str = "EE4E5898-3914-4259-9D6E-DC7BD79403CF";
+const TIANO_SECTION_GUID: &str = "A31280AD-481E-41B6-95E8-127F4C984779";
+const CRC32_SECTION_GUID: &str = "FC1BCDB0-7D31-49AA-936A-A4600D9DD083";
+
+/// Extracts each FFS file in a volume to a directory named after the file's GUID, inside `directory`
+fn extract_volume_files(
+    chroot: &Chroot,
+    volume_data: &[u8],
+    volume_header: &UEFIVolumeHeader,
+    directory: &str,
+    depth: usize,
+) -> Result<(), ChrootError> {
+    // Files are 8-byte aligned, relative to the start of the volume
+    const FILE_ALIGNMENT: usize = 8;
+    // State bit set on files that have been deleted
+    const EFI_FILE_DELETED: usize = 0x10;
+
+    // Only volumes with firmware file systems contain FFS files; others, such as NVRAM volumes, are extracted as is
+    if !FFS_GUIDS.contains(&volume_header.file_system.as_str()) {
+        debug!(
+            "UEFI volume has unknown file system {}, extracting it as is",
+            volume_header.file_system
+        );
+        return chroot.create_file(
+            unique_path(chroot, &chroot.safe_path_join(directory, "volume.bin")),
+            volume_data,
+        );
+    }
+
+    let erased_byte: u8 = if volume_header.attributes & UEFI_VOLUME_ERASE_POLARITY != 0 {
+        0xFF
+    } else {
+        0
+    };
+
+    let mut file_offset = volume_header.files_offset;
+    let mut file_count: usize = 0;
+
+    while let Some(file_data) = volume_data.get(file_offset..) {
+        // Free space at the end of the volume is erased
+        if file_data
+            .iter()
+            .take(FILE_ALIGNMENT * 3)
+            .all(|b| *b == erased_byte)
+        {
+            break;
+        }
+
+        let Ok(file_header) = parse_uefi_file_header(file_data) else {
+            warn!("Invalid UEFI file header at volume offset {file_offset:#X}");
+            break;
+        };
+
+        let Some(file_body) = file_data.get(file_header.header_size..file_header.size) else {
+            warn!("UEFI file {} is truncated", file_header.name);
+            break;
+        };
+
+        file_offset = (file_offset + file_header.size).next_multiple_of(FILE_ALIGNMENT);
+
+        // State bits are inverted if the erase polarity is set
+        let state = if erased_byte == 0xFF {
+            !file_header.state & 0xFF
+        } else {
+            file_header.state
+        };
+
+        if state & EFI_FILE_DELETED != 0 || file_header.file_type == UEFI_FILE_TYPE_PAD {
+            continue;
+        }
+
+        let file_directory =
+            unique_path(chroot, &chroot.safe_path_join(directory, &file_header.name));
+        chroot.create_directory(&file_directory)?;
+
+        debug!(
+            "Extracting UEFI file {} of type {:#X}",
+            file_header.name, file_header.file_type
+        );
+
+        if file_header.file_type == UEFI_FILE_TYPE_RAW {
+            chroot.create_file(chroot.safe_path_join(&file_directory, "raw.bin"), file_body)?;
+        } else {
+            let mut section_count: usize = 0;
+            extract_sections(
+                chroot,
+                file_body,
+                &file_directory,
+                &mut section_count,
+                depth,
+            )?;
+        }
+
+        file_count += 1;
+    }
+
+    debug!("Extracted {file_count} UEFI files");
+
+    Ok(())
+}
+
+/// Extracts a stream of sections to `directory`; encapsulated sections are extracted in place of the encapsulation
+/// section, and numbered consecutively using `section_count`
+fn extract_sections(
+    chroot: &Chroot,
+    sections_data: &[u8],
+    directory: &str,
+    section_count: &mut usize,
+    depth: usize,
+) -> Result<(), ChrootError> {
+    if depth > MAX_NESTING_DEPTH {
+        warn!("UEFI sections are nested too deeply, not extracting them");
+        return Ok(());
+    }
+
+    for section in walk_sections(sections_data) {
+        let Some((header, section_data)) = section else {
+            warn!("Invalid UEFI section in {directory}");
+            break;
+        };
+
+        let section_body = &section_data[header.header_size..];
+
+        match header.section_type {
+            UEFI_SECTION_COMPRESSION | UEFI_SECTION_GUID_DEFINED => {
+                match decode_encapsulation_section(&header, section_data) {
+                    Some(decoded_data) => extract_sections(
+                        chroot,
+                        &decoded_data,
+                        directory,
+                        section_count,
+                        depth + 1,
+                    )?,
+                    None => {
+                        warn!("Failed to decode UEFI encapsulation section, extracting it as is");
+                        write_section(
+                            chroot,
+                            directory,
+                            section_count,
+                            "encapsulated",
+                            section_body,
+                        )?;
+                    }
+                }
+            }
+            UEFI_SECTION_FIRMWARE_VOLUME_IMAGE => match parse_uefi_volume_header(section_body) {
+                Ok(volume_header) if volume_header.volume_size <= section_body.len() => {
+                    extract_volume_files(
+                        chroot,
+                        &section_body[..volume_header.volume_size],
+                        &volume_header,
+                        directory,
+                        depth + 1,
+                    )?;
+                }
+                _ => {
+                    warn!("Invalid nested UEFI volume, extracting it as is");
+                    write_section(chroot, directory, section_count, "fv", section_body)?;
+                }
+            },
+            // User interface sections contain the UTF-16 name of the file
+            UEFI_SECTION_USER_INTERFACE => {
+                let name_utf16: Vec<u16> = section_body
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .take_while(|c| *c != 0)
+                    .collect();
+                let name = String::from_utf16_lossy(&name_utf16);
+                write_section(chroot, directory, section_count, "ui", name.as_bytes())?;
+            }
+            section_type => {
+                let extension = match section_type {
+                    0x10 => "pe32",
+                    0x11 => "pic",
+                    0x12 => "te",
+                    0x13 | 0x1B | 0x1C => "depex",
+                    0x14 => "version",
+                    0x16 => "compat16",
+                    0x18 => "freeform",
+                    0x19 => "raw",
+                    _ => "bin",
+                };
+                write_section(chroot, directory, section_count, extension, section_body)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a leaf section to `<directory>/section_<section_count>.<extension>`
+fn write_section(
+    chroot: &Chroot,
+    directory: &str,
+    section_count: &mut usize,
+    extension: &str,
+    data: &[u8],
+) -> Result<(), ChrootError> {
+    let section_path =
+        chroot.safe_path_join(directory, format!("section_{section_count}.{extension}"));
+    *section_count += 1;
+
+    chroot.create_file(section_path, data)
+}
+
+/// Iterates over a stream of sections, yielding each section's header and data (including the header).
+/// Yields None, and stops, if an invalid section is encountered.
+fn walk_sections(sections_data: &[u8]) -> impl Iterator<Item = Option<(UEFISectionHeader, &[u8])>> {
+    // Sections are 4-byte aligned, relative to the start of the section stream
+    const SECTION_ALIGNMENT: usize = 4;
+
+    let mut section_offset: usize = 0;
+    let mut done = false;
+
+    std::iter::from_fn(move || {
+        if done || section_offset >= sections_data.len() {
+            return None;
+        }
+
+        let section = sections_data.get(section_offset..).and_then(|data| {
+            let header = parse_uefi_section_header(data).ok()?;
+            Some((header.clone(), data.get(..header.size)?))
+        });
+
+        match &section {
+            None => done = true,
+            Some((header, _)) => {
+                section_offset = (section_offset + header.size).next_multiple_of(SECTION_ALIGNMENT)
+            }
+        }
+
+        Some(section)
+    })
+}
+
+/// Returns true if the data is a valid stream of sections
+fn is_section_stream(sections_data: &[u8]) -> bool {
+    !sections_data.is_empty() && walk_sections(sections_data).all(|section| section.is_some())
+}
+
+/// Decodes the contents of a compression or GUID defined section, returning the encapsulated section stream
+fn decode_encapsulation_section(
+    header: &UEFISectionHeader,
+    section_data: &[u8],
+) -> Option<Vec<u8>> {
+    // Compression types
+    const NOT_COMPRESSED: usize = 0;
+    const STANDARD_COMPRESSION: usize = 1;
+    const CUSTOMIZED_COMPRESSION: usize = 2;
+
+    let section_body = &section_data[header.header_size..];
+
+    if header.section_type == UEFI_SECTION_COMPRESSION {
+        let compression_header = parse_uefi_compression_section(section_body).ok()?;
+        let compressed_data = section_body.get(compression_header.data_offset..)?;
+
+        if compression_header.uncompressed_size > MAX_DECOMPRESSED_SIZE {
+            return None;
+        }
+
+        let decoded_data = match compression_header.compression_type {
+            NOT_COMPRESSED => Some(compressed_data.to_vec()),
+            STANDARD_COMPRESSION => tiano_decompress(compressed_data),
+            CUSTOMIZED_COMPRESSION => lzma_decompress(compressed_data),
+            compression_type => {
+                warn!("Unsupported UEFI compression type {compression_type}");
+                None
+            }
+        }?;
+
+        return Some(decoded_data);
+    }
+
+    let guid_header = parse_uefi_guid_defined_section(section_body).ok()?;
+    let guid_data = section_data.get(guid_header.data_offset..)?;
+
+    debug!("Decoding UEFI GUID defined section {}", guid_header.guid);
+
+    match guid_header.guid.as_str() {
+        LZMA_SECTION_GUID => lzma_decompress(guid_data),
+        TIANO_SECTION_GUID => tiano_decompress(guid_data),
+        CRC32_SECTION_GUID => Some(guid_data.to_vec()),
+        _ => {
+            // Unless processing is required, the section data is a plain section stream
+            const EFI_GUIDED_SECTION_PROCESSING_REQUIRED: usize = 0x01;
+
+            if guid_header.attributes & EFI_GUIDED_SECTION_PROCESSING_REQUIRED == 0 {
+                Some(guid_data.to_vec())
+            } else {
+                warn!("Unsupported UEFI GUID defined section {}", guid_header.guid);
+                None
+            }
+        }
+    }
+}
+
+/// Decompresses EFI or Tiano compressed data. Both use the same LZH format and are identified by the same
+/// compression type; they differ only in their dictionary size, so each is tried in turn, and the first that
+/// decompresses to a valid stream of sections is used.
+fn tiano_decompress(compressed_data: &[u8]) -> Option<Vec<u8>> {
+    // Dictionary sizes used by EFI and Tiano compression, respectively
+    const EFI_DICTIONARY_BITS: usize = 13;
+    const TIANO_DICTIONARY_BITS: usize = 19;
+
+    let (compressed_size, uncompressed_size) = match compressed_data {
+        [c0, c1, c2, c3, u0, u1, u2, u3, ..] => (
+            u32::from_le_bytes([*c0, *c1, *c2, *c3]) as usize,
+            u32::from_le_bytes([*u0, *u1, *u2, *u3]) as usize,
+        ),
+        _ => return None,
+    };
+
+    if uncompressed_size > MAX_DECOMPRESSED_SIZE {
+        return None;
+    }
+
+    let bit_stream = compressed_data.get(8..8_usize.checked_add(compressed_size)?)?;
+
+    [EFI_DICTIONARY_BITS, TIANO_DICTIONARY_BITS]
+        .into_iter()
+        .filter_map(|dictionary_bits| {
+            lzh_decompress(bit_stream, dictionary_bits, uncompressed_size).ok()
+        })
+        .find(|decompressed_data| is_section_stream(decompressed_data))
+}
+
+/// Decompresses LZMA compressed data
+fn lzma_decompress(compressed_data: &[u8]) -> Option<Vec<u8>> {
+    let mut decompressed_data: Vec<u8> = vec![];
+
+    XzDecoder::new_stream(compressed_data, Stream::new_auto_decoder(u64::MAX, 0).ok()?)
+        .take(MAX_DECOMPRESSED_SIZE as u64)
+        .read_to_end(&mut decompressed_data)
+        .ok()?;
+
+    Some(decompressed_data)
+}
+
+/// Appends a numeric suffix to the path if it already exists, e.g. if a volume contains the same file GUID twice
+fn unique_path(chroot: &Chroot, path: &str) -> String {
+    let mut unique_path = path.to_string();
+    let mut suffix: usize = 1;
+
+    while Path::new(&chroot.chrooted_path(&unique_path)).exists() {
+        unique_path = format!("{path}_{suffix}");
+        suffix += 1;
+    }
+
+    unique_path
+}
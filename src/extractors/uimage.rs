@@ -76,7 +76,7 @@ pub fn extract_uimage(
 
                     let output_file = format!("{file_base_name}.{OUTPUT_FILE_EXT}");
 
-                    result.success = chroot.create_file(&output_file, image_data);
+                    result.success = chroot.create_file(&output_file, image_data).is_ok();
                 }
             }
         }
@@ -1,9 +1,9 @@
 use crate::common::is_offset_safe;
 use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
+use crate::logging::error;
 use crate::structures::vxworks::{
     VxWorksSymbolTableEntry, get_symtab_endianness, parse_symtab_entry,
 };
-use log::error;
 use serde_json;
 
 /// Describes the VxWorks symbol table extractor
@@ -93,8 +93,9 @@ pub fn extract_symbol_table(
 
                 // Write JSON to file
                 Ok(symtab_json) => {
-                    result.success =
-                        chroot.create_file(OUTFILE_NAME, &symtab_json.clone().into_bytes());
+                    result.success = chroot
+                        .create_file(OUTFILE_NAME, &symtab_json.clone().into_bytes())
+                        .is_ok();
                 }
             }
         }
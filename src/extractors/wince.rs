@@ -62,12 +62,15 @@ pub fn wince_dump(
                                 let block_file_name = format!("{:X}.bin", block.address);
 
                                 // If file carving fails, report a failure to extract
-                                if !chroot.carve_file(
-                                    block_file_name,
-                                    wince_block_data,
-                                    block.offset,
-                                    block.size,
-                                ) {
+                                if chroot
+                                    .carve_file(
+                                        block_file_name,
+                                        wince_block_data,
+                                        block.offset,
+                                        block.size,
+                                    )
+                                    .is_err()
+                                {
                                     result.success = false;
                                     break;
                                 }
@@ -1,8 +1,10 @@
-use crate::extractors::common::{ExtractionResult, Extractor, ExtractorType};
+use crate::extractors::common::{Chroot, ExtractionResult, Extractor, ExtractorType};
 use crate::extractors::inflate;
+use crate::logging::debug;
+use flate2::{Decompress, FlushDecompress, Status};
 
-/// Size of the checksum that follows the ZLIB deflate data stream
-pub const CHECKSUM_SIZE: usize = 4;
+/// ZLIB streams that decompress to less than this many bytes are assumed to be false positive matches
+pub const MIN_DECOMPRESSED_SIZE: usize = 16;
 
 /// Defines the internal extractor function for decompressing zlib data
 ///
@@ -29,44 +31,93 @@ pub const CHECKSUM_SIZE: usize = 4;
 pub fn zlib_extractor() -> Extractor {
     Extractor {
         utility: ExtractorType::Internal(zlib_decompress),
+        trial_decoder: Some(zlib_trial),
         ..Default::default()
     }
 }
 
-/// Internal extractor for decompressing ZLIB data
+/// Internal extractor for decompressing bare ZLIB streams, i.e. deflate data with a ZLIB header and Adler-32 trailer,
+/// but no gzip container.
+///
+/// The ZLIB magic bytes are only two bytes long, so the stream must decompress to at least `MIN_DECOMPRESSED_SIZE`
+/// bytes, with a valid checksum, for the extraction to be successful. The reported size is the exact number of
+/// compressed bytes consumed by the decompressor, including the header and checksum.
 pub fn zlib_decompress(
     file_data: &[u8],
     offset: usize,
     output_directory: Option<&str>,
 ) -> ExtractionResult {
-    // Size of the zlib header
-    const HEADER_SIZE: usize = 2;
+    // Size of decompression buffer
+    const BLOCK_SIZE: usize = 8192;
+    // Output file for decompressed data
+    const OUTPUT_FILE_NAME: &str = "decompressed.bin";
 
     let mut exresult = ExtractionResult {
         ..Default::default()
     };
 
-    // Do the decompression, ignoring the ZLIB header
-    let inflate_result =
-        inflate::inflate_decompressor(file_data, offset + HEADER_SIZE, output_directory);
+    let chroot = Chroot::new(output_directory);
+    let zlib_data = file_data.get(offset..).unwrap_or_default();
+    let mut decompressed_buffer = [0; BLOCK_SIZE];
+    let mut decompressor = Decompress::new(true);
+
+    loop {
+        let stream_offset = decompressor.total_in() as usize;
+        let bytes_written = decompressor.total_out() as usize;
+
+        // The decompressor verifies the header and checksum, and fails on corrupt data
+        let status = match decompressor.decompress(
+            &zlib_data[stream_offset..],
+            &mut decompressed_buffer,
+            FlushDecompress::None,
+        ) {
+            Err(e) => {
+                debug!("ZLIB decompression failed after {bytes_written} bytes: {e}");
+                break;
+            }
+            Ok(status) => status,
+        };
 
-    // Check that the data decompressed OK
-    if inflate_result.success {
-        // Calculate the ZLIB checksum offsets
-        let checksum_start = offset + HEADER_SIZE + inflate_result.size;
-        let checksum_end = checksum_start + CHECKSUM_SIZE;
+        let n = decompressor.total_out() as usize - bytes_written;
 
-        // Get the ZLIB checksum
-        if let Some(adler32_checksum_bytes) = file_data.get(checksum_start..checksum_end) {
-            let reported_checksum = u32::from_be_bytes(adler32_checksum_bytes.try_into().unwrap());
+        if output_directory.is_some()
+            && chroot
+                .append_to_file(OUTPUT_FILE_NAME, &decompressed_buffer[0..n])
+                .is_err()
+        {
+            break;
+        }
 
-            // Make sure the checksum matches
-            if reported_checksum == inflate_result.adler32 {
-                exresult.success = true;
-                exresult.size = Some(HEADER_SIZE + inflate_result.size + CHECKSUM_SIZE);
+        match status {
+            Status::StreamEnd => {
+                if decompressor.total_out() as usize >= MIN_DECOMPRESSED_SIZE {
+                    exresult.success = true;
+                    exresult.size = Some(decompressor.total_in() as usize);
+                } else {
+                    debug!(
+                        "ZLIB stream only decompressed to {} bytes, assuming a false positive",
+                        decompressor.total_out()
+                    );
+                }
+                break;
+            }
+            // No progress can be made; the stream is truncated
+            _ if n == 0 && decompressor.total_in() as usize == stream_offset => {
+                debug!("ZLIB stream is truncated");
+                break;
             }
+            _ => (),
         }
     }
 
     exresult
 }
+
+/// Trial decoder for ZLIB streams; decompresses up to `trial_size` bytes of data. Streams that end after
+/// decompressing fewer than `MIN_DECOMPRESSED_SIZE` bytes fail the trial.
+pub fn zlib_trial(file_data: &[u8], offset: usize, trial_size: usize) -> bool {
+    file_data
+        .get(offset..)
+        .and_then(|zlib_data| inflate::inflate_trial(zlib_data, true, trial_size))
+        .is_some_and(|decompressed_size| decompressed_size >= MIN_DECOMPRESSED_SIZE.min(trial_size))
+}
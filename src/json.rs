@@ -8,6 +8,7 @@ use std::io::Write;
 use crate::binwalk::AnalysisResults;
 use crate::display;
 use crate::entropy::FileEntropy;
+use crate::extractors::common::ExtractionStatistics;
 
 const STDOUT: &str = "-";
 const JSON_LIST_START: &str = "[\n";
@@ -18,6 +19,7 @@ const JSON_LIST_SEP: &str = ",\n";
 pub enum JSONType {
     Entropy(FileEntropy),
     Analysis(AnalysisResults),
+    ExtractionStatistics(ExtractionStatistics),
 }
 
 #[derive(Debug, Default, Clone)]
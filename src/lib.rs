@@ -18,8 +18,10 @@
 //! ```
 mod binwalk;
 pub mod common;
+pub mod entropy;
 pub mod extractors;
+pub mod logging;
 mod magic;
 pub mod signatures;
 pub mod structures;
-pub use binwalk::{AnalysisResults, Binwalk, BinwalkError};
+pub use binwalk::{AnalysisResults, Binwalk, BinwalkError, DEFAULT_TRIAL_SIZE, MagicMatch};
@@ -0,0 +1,224 @@
+//! Routes binwalk's log messages to a per-run sink.
+//!
+//! By default, messages are passed to the global logger via the `log` crate. If a `LogSink` is installed, messages
+//! logged on the current thread, and on any threads that binwalk spawns from it (see `spawn`), are delivered to the
+//! sink instead, as structured `LogEvent`s, without requiring a global logger to be set up.
+
+use log::{Level, LevelFilter};
+use serde::Serialize;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::sync::Arc;
+use std::thread;
+
+thread_local! {
+    /// The sink installed for the current thread, if any
+    static SINK: RefCell<Option<LogSink>> = const { RefCell::new(None) };
+    /// Offset of the signature currently being processed on this thread, if any
+    static OFFSET: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// A single log message, as delivered to a `LogSink`
+#[derive(Debug, Clone, Serialize)]
+pub struct LogEvent {
+    /// Severity of the message
+    #[serde(serialize_with = "serialize_level")]
+    pub level: Level,
+    /// Module that logged the message, e.g. `binwalk::extractors::gzip`
+    pub target: String,
+    /// Offset of the signature that was being scanned or extracted when the message was logged, if any
+    pub offset: Option<usize>,
+    pub message: String,
+}
+
+fn serialize_level<S: serde::Serializer>(level: &Level, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(level.as_str())
+}
+
+/// Receives binwalk's log messages in place of the global logger.
+/// Clones share the same callback, so a sink may be shared between worker threads.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::Binwalk;
+/// use binwalk::logging::{LogEvent, LogSink};
+/// use log::LevelFilter;
+/// use std::sync::{Arc, Mutex};
+///
+/// let events: Arc<Mutex<Vec<LogEvent>>> = Arc::new(Mutex::new(vec![]));
+/// let sink_events = events.clone();
+///
+/// let mut binwalker = Binwalk::new();
+/// binwalker.log_sink = Some(LogSink::new(LevelFilter::Debug, move |event: &LogEvent| {
+///     sink_events.lock().unwrap().push(event.clone());
+/// }));
+///
+/// let file_data = std::fs::read("tests/inputs/gzip.bin").expect("Unable to read file");
+/// binwalker.scan(&file_data);
+///
+/// let events = events.lock().unwrap();
+/// assert!(events.iter().any(|event| event.offset == Some(0) && event.message.contains("gzip")));
+/// ```
+#[derive(Clone)]
+pub struct LogSink {
+    callback: Arc<dyn Fn(&LogEvent) + Send + Sync>,
+    level: LevelFilter,
+}
+
+impl LogSink {
+    /// Creates a sink that passes messages at, or more severe than, `level` to `callback`.
+    /// Less severe messages are discarded without being formatted.
+    #[allow(dead_code)]
+    pub fn new(
+        level: LevelFilter,
+        callback: impl Fn(&LogEvent) + Send + Sync + 'static,
+    ) -> LogSink {
+        LogSink {
+            callback: Arc::new(callback),
+            level,
+        }
+    }
+}
+
+impl fmt::Debug for LogSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogSink")
+            .field("level", &self.level)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Restores the previously installed sink when dropped, see `install_sink`
+pub struct LogSinkGuard {
+    previous: Option<Option<LogSink>>,
+}
+
+impl Drop for LogSinkGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            SINK.with(|sink| *sink.borrow_mut() = previous);
+        }
+    }
+}
+
+/// Installs `sink` for the current thread until the returned guard is dropped.
+/// If `sink` is None, any sink that is already installed remains in place.
+pub fn install_sink(sink: Option<&LogSink>) -> LogSinkGuard {
+    LogSinkGuard {
+        previous: sink.map(|sink| SINK.with(|installed| installed.replace(Some(sink.clone())))),
+    }
+}
+
+/// Restores the previous offset context when dropped, see `offset_context`
+pub struct OffsetGuard {
+    previous: Option<usize>,
+}
+
+impl Drop for OffsetGuard {
+    fn drop(&mut self) {
+        OFFSET.with(|offset| offset.set(self.previous));
+    }
+}
+
+/// Attributes messages logged on the current thread to the signature at `offset`, until the returned guard is dropped
+pub fn offset_context(offset: usize) -> OffsetGuard {
+    OffsetGuard {
+        previous: OFFSET.with(|current| current.replace(Some(offset))),
+    }
+}
+
+/// Spawns a thread that inherits the current thread's sink and offset context, so that messages it logs are delivered
+/// to the same sink, and attributed to the same signature, as messages logged by the spawning thread.
+///
+/// ## Example
+///
+/// ```
+/// use binwalk::logging::{self, LogEvent, LogSink};
+/// use log::{Level, LevelFilter};
+/// use std::sync::{Arc, Mutex};
+///
+/// let events: Arc<Mutex<Vec<LogEvent>>> = Arc::new(Mutex::new(vec![]));
+/// let sink_events = events.clone();
+/// let sink = LogSink::new(LevelFilter::Debug, move |event: &LogEvent| {
+///     sink_events.lock().unwrap().push(event.clone());
+/// });
+///
+/// let _sink = logging::install_sink(Some(&sink));
+/// let _offset = logging::offset_context(0x100);
+///
+/// logging::spawn(|| logging::log_event(Level::Info, "test", format_args!("from another thread")))
+///     .join()
+///     .unwrap();
+///
+/// let events = events.lock().unwrap();
+/// assert_eq!(events.len(), 1);
+/// assert_eq!(events[0].offset, Some(0x100));
+/// assert_eq!(events[0].message, "from another thread");
+/// ```
+pub fn spawn<F, T>(f: F) -> thread::JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let sink = SINK.with(|sink| sink.borrow().clone());
+    let offset = OFFSET.with(|offset| offset.get());
+
+    thread::spawn(move || {
+        let _sink = install_sink(sink.as_ref());
+        let _offset = offset.map(offset_context);
+        f()
+    })
+}
+
+/// Delivers a message to the current thread's sink, or to the global logger if no sink is installed.
+/// Use the `debug!`, `info!`, `warn!` and `error!` macros in this module rather than calling this directly.
+pub fn log_event(level: Level, target: &str, args: fmt::Arguments) {
+    let delivered = SINK.with(|sink| match &*sink.borrow() {
+        None => false,
+        Some(sink) => {
+            if level <= sink.level {
+                (sink.callback)(&LogEvent {
+                    level,
+                    target: target.to_string(),
+                    offset: OFFSET.with(|offset| offset.get()),
+                    message: args.to_string(),
+                });
+            }
+            true
+        }
+    });
+
+    if !delivered {
+        log::log!(target: target, level, "{args}");
+    }
+}
+
+/// Drop-in replacements for the `log` crate's macros, which honor the current thread's `LogSink`
+macro_rules! log_debug {
+    ($($arg:tt)+) => {
+        $crate::logging::log_event(log::Level::Debug, module_path!(), format_args!($($arg)+))
+    };
+}
+
+macro_rules! log_info {
+    ($($arg:tt)+) => {
+        $crate::logging::log_event(log::Level::Info, module_path!(), format_args!($($arg)+))
+    };
+}
+
+macro_rules! log_warn {
+    ($($arg:tt)+) => {
+        $crate::logging::log_event(log::Level::Warn, module_path!(), format_args!($($arg)+))
+    };
+}
+
+macro_rules! log_error {
+    ($($arg:tt)+) => {
+        $crate::logging::log_event(log::Level::Error, module_path!(), format_args!($($arg)+))
+    };
+}
+
+// The macros are defined under other names, since `warn` would otherwise be ambiguous with the built-in attribute
+#[allow(unused_imports)]
+pub(crate) use {log_debug as debug, log_error as error, log_info as info, log_warn as warn};
@@ -14,6 +14,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::gzip::gzip_parser,
             description: signatures::gzip::DESCRIPTION.to_string(),
             extractor: Some(extractors::gzip::gzip_extractor()),
+            trial_validation: true,
         },
         // .deb
         signatures::common::Signature {
@@ -24,7 +25,20 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             magic: signatures::deb::deb_magic(),
             parser: signatures::deb::deb_parser,
             description: signatures::deb::DESCRIPTION.to_string(),
-            extractor: None,
+            extractor: Some(extractors::ar::ar_extractor()),
+            trial_validation: false,
+        },
+        // ar archive
+        signatures::common::Signature {
+            name: "ar".to_string(),
+            short: false,
+            magic_offset: 0,
+            always_display: false,
+            magic: signatures::ar::ar_magic(),
+            parser: signatures::ar::ar_parser,
+            description: signatures::ar::DESCRIPTION.to_string(),
+            extractor: Some(extractors::ar::ar_extractor()),
+            trial_validation: false,
         },
         // 7-zip
         signatures::common::Signature {
@@ -36,6 +50,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::sevenzip::sevenzip_parser,
             description: signatures::sevenzip::DESCRIPTION.to_string(),
             extractor: Some(extractors::sevenzip::sevenzip_extractor()),
+            trial_validation: false,
         },
         // xz
         signatures::common::Signature {
@@ -47,6 +62,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::xz::xz_parser,
             description: signatures::xz::DESCRIPTION.to_string(),
             extractor: Some(extractors::lzma::lzma_extractor()),
+            trial_validation: false,
         },
         // tarball
         signatures::common::Signature {
@@ -58,6 +74,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::tarball::tarball_parser,
             description: signatures::tarball::DESCRIPTION.to_string(),
             extractor: Some(extractors::tarball::tarball_extractor()),
+            trial_validation: false,
         },
         // squashfs
         signatures::common::Signature {
@@ -69,6 +86,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::squashfs::squashfs_parser,
             description: signatures::squashfs::DESCRIPTION.to_string(),
             extractor: Some(extractors::squashfs::squashfs_extractor()),
+            trial_validation: false,
         },
         // dlob
         signatures::common::Signature {
@@ -80,6 +98,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::dlob::dlob_parser,
             description: signatures::dlob::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // lzma
         signatures::common::Signature {
@@ -92,6 +111,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             description: signatures::lzma::DESCRIPTION.to_string(),
             //extractor: Some(extractors::sevenzip::sevenzip_extractor()),
             extractor: Some(extractors::lzma::lzma_extractor()),
+            trial_validation: false,
         },
         // bmp
         signatures::common::Signature {
@@ -103,6 +123,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::bmp::bmp_parser,
             description: signatures::bmp::DESCRIPTION.to_string(),
             extractor: Some(extractors::bmp::bmp_extractor()),
+            trial_validation: false,
         },
         // bzip2
         signatures::common::Signature {
@@ -114,6 +135,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::bzip2::bzip2_parser,
             description: signatures::bzip2::DESCRIPTION.to_string(),
             extractor: Some(extractors::bzip2::bzip2_extractor()),
+            trial_validation: false,
         },
         // uimage
         signatures::common::Signature {
@@ -125,6 +147,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::uimage::uimage_parser,
             description: signatures::uimage::DESCRIPTION.to_string(),
             extractor: Some(extractors::uimage::uimage_extractor()),
+            trial_validation: false,
         },
         // packimg header
         signatures::common::Signature {
@@ -136,6 +159,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::packimg::packimg_parser,
             description: signatures::packimg::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // crc32 constants
         signatures::common::Signature {
@@ -147,6 +171,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::hashes::crc32_parser,
             description: signatures::hashes::CRC32_DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // sha256 constants
         signatures::common::Signature {
@@ -158,6 +183,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::hashes::sha256_parser,
             description: signatures::hashes::SHA256_DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // cpio
         signatures::common::Signature {
@@ -169,6 +195,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::cpio::cpio_parser,
             description: signatures::cpio::DESCRIPTION.to_string(),
             extractor: Some(extractors::sevenzip::sevenzip_extractor()),
+            trial_validation: false,
         },
         // iso9660 primary volume
         signatures::common::Signature {
@@ -180,6 +207,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::iso9660::iso_parser,
             description: signatures::iso9660::DESCRIPTION.to_string(),
             extractor: Some(extractors::iso9660::iso9660_extractor()),
+            trial_validation: false,
         },
         // linux kernel
         signatures::common::Signature {
@@ -191,6 +219,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::linux::linux_kernel_version_parser,
             description: signatures::linux::LINUX_KERNEL_VERSION_DESCRIPTION.to_string(),
             extractor: Some(extractors::linux::linux_kernel_extractor()),
+            trial_validation: false,
         },
         // linux boot image
         signatures::common::Signature {
@@ -201,7 +230,8 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             magic: signatures::linux::linux_boot_image_magic(),
             parser: signatures::linux::linux_boot_image_parser,
             description: signatures::linux::LINUX_BOOT_IMAGE_DESCRIPTION.to_string(),
-            extractor: None,
+            extractor: Some(extractors::linux::vmlinuz_extractor()),
+            trial_validation: false,
         },
         // linux arm zimage
         signatures::common::Signature {
@@ -212,7 +242,8 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             magic: signatures::linux::linux_arm_zimage_magic(),
             parser: signatures::linux::linux_arm_zimage_parser,
             description: signatures::linux::LINUX_ARM_ZIMAGE_DESCRIPTION.to_string(),
-            extractor: None,
+            extractor: Some(extractors::linux::vmlinuz_extractor()),
+            trial_validation: false,
         },
         // zstd
         signatures::common::Signature {
@@ -224,6 +255,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::zstd::zstd_parser,
             description: signatures::zstd::DESCRIPTION.to_string(),
             extractor: Some(extractors::zstd::zstd_extractor()),
+            trial_validation: false,
         },
         // zip
         signatures::common::Signature {
@@ -235,6 +267,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::zip::zip_parser,
             description: signatures::zip::DESCRIPTION.to_string(),
             extractor: Some(extractors::sevenzip::sevenzip_extractor()),
+            trial_validation: false,
         },
         // Intel PCH ROM
         signatures::common::Signature {
@@ -246,6 +279,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::pchrom::pch_rom_parser,
             description: signatures::pchrom::DESCRIPTION.to_string(),
             extractor: Some(extractors::uefi::uefi_extractor()),
+            trial_validation: false,
         },
         // UEFI PI volume
         signatures::common::Signature {
@@ -256,7 +290,8 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             magic: signatures::uefi::uefi_volume_magic(),
             parser: signatures::uefi::uefi_volume_parser,
             description: signatures::uefi::VOLUME_DESCRIPTION.to_string(),
-            extractor: Some(extractors::uefi::uefi_extractor()),
+            extractor: Some(extractors::uefi::uefi_volume_extractor()),
+            trial_validation: false,
         },
         // UEFI capsule image
         signatures::common::Signature {
@@ -268,6 +303,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::uefi::uefi_capsule_parser,
             description: signatures::uefi::CAPSULE_DESCRIPTION.to_string(),
             extractor: Some(extractors::uefi::uefi_extractor()),
+            trial_validation: false,
         },
         // PDF document
         signatures::common::Signature {
@@ -278,7 +314,8 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             magic: signatures::pdf::pdf_magic(),
             parser: signatures::pdf::pdf_parser,
             description: signatures::pdf::DESCRIPTION.to_string(),
-            extractor: None,
+            extractor: Some(extractors::pdf::pdf_extractor()),
+            trial_validation: false,
         },
         // ELF
         signatures::common::Signature {
@@ -290,6 +327,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::elf::elf_parser,
             description: signatures::elf::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // CramFS
         signatures::common::Signature {
@@ -301,6 +339,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::cramfs::cramfs_parser,
             description: signatures::cramfs::DESCRIPTION.to_string(),
             extractor: Some(extractors::sevenzip::sevenzip_extractor()),
+            trial_validation: false,
         },
         // QNX IFS
         // TODO: The signature and extractor are untested. Need a sample IFS image.
@@ -313,6 +352,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::qnx::qnx_ifs_parser,
             description: signatures::qnx::IFS_DESCRIPTION.to_string(),
             extractor: Some(extractors::dumpifs::dumpifs_extractor()),
+            trial_validation: false,
         },
         // RomFS
         signatures::common::Signature {
@@ -324,6 +364,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::romfs::romfs_parser,
             description: signatures::romfs::DESCRIPTION.to_string(),
             extractor: Some(extractors::romfs::romfs_extractor()),
+            trial_validation: false,
         },
         // EXT
         signatures::common::Signature {
@@ -334,7 +375,8 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             magic: signatures::ext::ext_magic(),
             parser: signatures::ext::ext_parser,
             description: signatures::ext::DESCRIPTION.to_string(),
-            extractor: Some(extractors::tsk::tsk_extractor()),
+            extractor: Some(extractors::ext::ext_extractor()),
+            trial_validation: false,
         },
         // CAB archive
         signatures::common::Signature {
@@ -346,6 +388,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::cab::cab_parser,
             description: signatures::cab::DESCRIPTION.to_string(),
             extractor: Some(extractors::cab::cab_extractor()),
+            trial_validation: false,
         },
         // JFFS2
         signatures::common::Signature {
@@ -357,6 +400,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::jffs2::jffs2_parser,
             description: signatures::jffs2::DESCRIPTION.to_string(),
             extractor: Some(extractors::jffs2::jffs2_extractor()),
+            trial_validation: false,
         },
         // YAFFS
         signatures::common::Signature {
@@ -368,6 +412,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::yaffs::yaffs_parser,
             description: signatures::yaffs::DESCRIPTION.to_string(),
             extractor: Some(extractors::yaffs2::yaffs2_extractor()),
+            trial_validation: false,
         },
         // lz4
         signatures::common::Signature {
@@ -379,6 +424,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::lz4::lz4_parser,
             description: signatures::lz4::DESCRIPTION.to_string(),
             extractor: Some(extractors::lz4::lz4_extractor()),
+            trial_validation: false,
         },
         // lzop
         signatures::common::Signature {
@@ -390,6 +436,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::lzop::lzop_parser,
             description: signatures::lzop::DESCRIPTION.to_string(),
             extractor: Some(extractors::lzop::lzop_extractor()),
+            trial_validation: false,
         },
         // lzop
         signatures::common::Signature {
@@ -400,19 +447,21 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             magic: signatures::pe::pe_magic(),
             parser: signatures::pe::pe_parser,
             description: signatures::pe::DESCRIPTION.to_string(),
-            extractor: None,
+            extractor: Some(extractors::pe::pe_extractor()),
+            trial_validation: false,
         },
         // zlib
         signatures::common::Signature {
             name: "zlib".to_string(),
-            // The magic bytes for this signature are only 2 bytes, only match on the beginning of a file
-            short: true,
+            // The magic bytes for this signature are only 2 bytes, but the parser requires a successful decompression
+            short: false,
             magic_offset: 0,
             always_display: false,
             magic: signatures::zlib::zlib_magic(),
             parser: signatures::zlib::zlib_parser,
             description: signatures::zlib::DESCRIPTION.to_string(),
             extractor: Some(extractors::zlib::zlib_extractor()),
+            trial_validation: true,
         },
         // gpg signed data
         signatures::common::Signature {
@@ -425,6 +474,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::gpg::gpg_signed_parser,
             description: signatures::gpg::GPG_SIGNED_DESCRIPTION.to_string(),
             extractor: Some(extractors::gpg::gpg_extractor()),
+            trial_validation: false,
         },
         // pem certificates
         signatures::common::Signature {
@@ -436,6 +486,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::pem::pem_parser,
             description: signatures::pem::PEM_CERTIFICATE_DESCRIPTION.to_string(),
             extractor: Some(extractors::pem::pem_certificate_extractor()),
+            trial_validation: false,
         },
         // pem public keys
         signatures::common::Signature {
@@ -447,6 +498,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::pem::pem_parser,
             description: signatures::pem::PEM_PUBLIC_KEY_DESCRIPTION.to_string(),
             extractor: Some(extractors::pem::pem_key_extractor()),
+            trial_validation: false,
         },
         // pem private keys
         signatures::common::Signature {
@@ -458,6 +510,55 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::pem::pem_parser,
             description: signatures::pem::PEM_PRIVATE_KEY_DESCRIPTION.to_string(),
             extractor: Some(extractors::pem::pem_key_extractor()),
+            trial_validation: false,
+        },
+        // base64 encoded data
+        signatures::common::Signature {
+            name: "base64".to_string(),
+            short: false,
+            magic_offset: 0,
+            always_display: false,
+            magic: signatures::encoded::base64_magic(),
+            parser: signatures::encoded::base64_parser,
+            description: signatures::encoded::BASE64_DESCRIPTION.to_string(),
+            extractor: Some(extractors::encoded::base64_extractor()),
+            trial_validation: false,
+        },
+        // hex encoded data
+        signatures::common::Signature {
+            name: "hex".to_string(),
+            short: false,
+            magic_offset: 0,
+            always_display: false,
+            magic: signatures::encoded::hex_magic(),
+            parser: signatures::encoded::hex_parser,
+            description: signatures::encoded::HEX_DESCRIPTION.to_string(),
+            extractor: Some(extractors::encoded::hex_extractor()),
+            trial_validation: false,
+        },
+        // der certificates
+        signatures::common::Signature {
+            name: "der_certificate".to_string(),
+            short: false,
+            magic_offset: 0,
+            always_display: false,
+            magic: signatures::der::der_certificate_magic(),
+            parser: signatures::der::der_certificate_parser,
+            description: signatures::der::DER_CERTIFICATE_DESCRIPTION.to_string(),
+            extractor: Some(extractors::der::der_certificate_extractor()),
+            trial_validation: false,
+        },
+        // der keys
+        signatures::common::Signature {
+            name: "der_key".to_string(),
+            short: false,
+            magic_offset: 0,
+            always_display: true,
+            magic: signatures::der::der_key_magic(),
+            parser: signatures::der::der_key_parser,
+            description: signatures::der::DER_KEY_DESCRIPTION.to_string(),
+            extractor: Some(extractors::der::der_key_extractor()),
+            trial_validation: false,
         },
         // netgear chk
         signatures::common::Signature {
@@ -469,6 +570,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::chk::chk_parser,
             description: signatures::chk::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // trx
         signatures::common::Signature {
@@ -480,6 +582,31 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::trx::trx_parser,
             description: signatures::trx::DESCRIPTION.to_string(),
             extractor: Some(extractors::trx::trx_extractor()),
+            trial_validation: false,
+        },
+        // Intel HEX
+        signatures::common::Signature {
+            name: "ihex".to_string(),
+            short: false,
+            magic_offset: 0,
+            always_display: false,
+            magic: signatures::ihex::ihex_magic(),
+            parser: signatures::ihex::ihex_parser,
+            description: signatures::ihex::IHEX_DESCRIPTION.to_string(),
+            extractor: Some(extractors::ihex::ihex_extractor()),
+            trial_validation: false,
+        },
+        // Intel HEX (generic)
+        signatures::common::Signature {
+            name: "ihex_generic".to_string(),
+            short: true,
+            magic_offset: 0,
+            always_display: false,
+            magic: signatures::ihex::ihex_short_magic(),
+            parser: signatures::ihex::ihex_parser,
+            description: signatures::ihex::IHEX_SHORT_DESCRIPTION.to_string(),
+            extractor: Some(extractors::ihex::ihex_extractor()),
+            trial_validation: false,
         },
         // Motorola S-record
         signatures::common::Signature {
@@ -491,6 +618,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::srec::srec_parser,
             description: signatures::srec::SREC_DESCRIPTION.to_string(),
             extractor: Some(extractors::srec::srec_extractor()),
+            trial_validation: false,
         },
         // Motorola S-record (generic)
         signatures::common::Signature {
@@ -502,6 +630,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::srec::srec_parser,
             description: signatures::srec::SREC_SHORT_DESCRIPTION.to_string(),
             extractor: Some(extractors::srec::srec_extractor()),
+            trial_validation: false,
         },
         // Android sparse
         signatures::common::Signature {
@@ -513,6 +642,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::androidsparse::android_sparse_parser,
             description: signatures::androidsparse::DESCRIPTION.to_string(),
             extractor: Some(extractors::androidsparse::android_sparse_extractor()),
+            trial_validation: false,
         },
         // device tree blob
         signatures::common::Signature {
@@ -524,6 +654,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::dtb::dtb_parser,
             description: signatures::dtb::DESCRIPTION.to_string(),
             extractor: Some(extractors::dtb::dtb_extractor()),
+            trial_validation: false,
         },
         // ubi
         signatures::common::Signature {
@@ -535,6 +666,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::ubi::ubi_parser,
             description: signatures::ubi::UBI_IMAGE_DESCRIPTION.to_string(),
             extractor: Some(extractors::ubi::ubi_extractor()),
+            trial_validation: false,
         },
         // ubifs
         signatures::common::Signature {
@@ -546,6 +678,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::ubi::ubifs_parser,
             description: signatures::ubi::UBI_FS_DESCRIPTION.to_string(),
             extractor: Some(extractors::ubi::ubifs_extractor()),
+            trial_validation: false,
         },
         // cfe bootloader
         signatures::common::Signature {
@@ -557,6 +690,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::cfe::cfe_parser,
             description: signatures::cfe::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // SEAMA firmware header
         signatures::common::Signature {
@@ -568,6 +702,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::seama::seama_parser,
             description: signatures::seama::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // compress'd
         signatures::common::Signature {
@@ -579,6 +714,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::compressd::compressd_parser,
             description: signatures::compressd::DESCRIPTION.to_string(),
             extractor: Some(extractors::sevenzip::sevenzip_extractor()),
+            trial_validation: false,
         },
         // rar archive
         signatures::common::Signature {
@@ -590,6 +726,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::rar::rar_parser,
             description: signatures::rar::DESCRIPTION.to_string(),
             extractor: Some(extractors::rar::rar_extractor()),
+            trial_validation: false,
         },
         // PNG image
         signatures::common::Signature {
@@ -601,6 +738,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::png::png_parser,
             description: signatures::png::DESCRIPTION.to_string(),
             extractor: Some(extractors::png::png_extractor()),
+            trial_validation: false,
         },
         // JPEG image
         signatures::common::Signature {
@@ -612,6 +750,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::jpeg::jpeg_parser,
             description: signatures::jpeg::DESCRIPTION.to_string(),
             extractor: Some(extractors::jpeg::jpeg_extractor()),
+            trial_validation: false,
         },
         // arcadyan obfuscated lzma
         signatures::common::Signature {
@@ -623,6 +762,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::arcadyan::obfuscated_lzma_parser,
             description: signatures::arcadyan::DESCRIPTION.to_string(),
             extractor: Some(extractors::arcadyan::obfuscated_lzma_extractor()),
+            trial_validation: false,
         },
         // copyright text
         signatures::common::Signature {
@@ -634,6 +774,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::copyright::copyright_parser,
             description: signatures::copyright::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // WIND kernel version
         signatures::common::Signature {
@@ -645,6 +786,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::vxworks::wind_kernel_parser,
             description: signatures::vxworks::WIND_KERNEL_DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // vxworks symbol table
         signatures::common::Signature {
@@ -656,6 +798,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::vxworks::symbol_table_parser,
             description: signatures::vxworks::SYMTAB_DESCRIPTION.to_string(),
             extractor: Some(extractors::vxworks::vxworks_symtab_extractor()),
+            trial_validation: false,
         },
         // ecos mips exception handler
         signatures::common::Signature {
@@ -667,6 +810,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::ecos::exception_handler_parser,
             description: signatures::ecos::EXCEPTION_HANDLER_DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // dmg
         signatures::common::Signature {
@@ -678,6 +822,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::dmg::dmg_parser,
             description: signatures::dmg::DESCRIPTION.to_string(),
             extractor: Some(extractors::dmg::dmg_extractor()),
+            trial_validation: false,
         },
         // riff
         signatures::common::Signature {
@@ -689,6 +834,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::riff::riff_parser,
             description: signatures::riff::DESCRIPTION.to_string(),
             extractor: Some(extractors::riff::riff_extractor()),
+            trial_validation: false,
         },
         // openssl
         signatures::common::Signature {
@@ -700,6 +846,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::openssl::openssl_crypt_parser,
             description: signatures::openssl::DESCRIPTION.to_string(),
             extractor: Some(extractors::encfw::encfw_extractor()),
+            trial_validation: false,
         },
         // lzfse
         signatures::common::Signature {
@@ -711,6 +858,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::lzfse::lzfse_parser,
             description: signatures::lzfse::DESCRIPTION.to_string(),
             extractor: Some(extractors::lzfse::lzfse_extractor()),
+            trial_validation: false,
         },
         // MBR
         signatures::common::Signature {
@@ -722,6 +870,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::mbr::mbr_parser,
             description: signatures::mbr::DESCRIPTION.to_string(),
             extractor: Some(extractors::mbr::mbr_extractor()),
+            trial_validation: false,
         },
         // tp-link
         signatures::common::Signature {
@@ -733,6 +882,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::tplink::tplink_parser,
             description: signatures::tplink::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // HP PJL
         signatures::common::Signature {
@@ -744,6 +894,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::pjl::pjl_parser,
             description: signatures::pjl::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // JBOOT ARM firmware image
         signatures::common::Signature {
@@ -755,6 +906,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::jboot::jboot_arm_parser,
             description: signatures::jboot::JBOOT_ARM_DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // JBOOT STAG header
         signatures::common::Signature {
@@ -766,6 +918,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::jboot::jboot_stag_parser,
             description: signatures::jboot::JBOOT_STAG_DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // JBOOT SCH2 header
         signatures::common::Signature {
@@ -777,6 +930,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::jboot::jboot_sch2_parser,
             description: signatures::jboot::JBOOT_SCH2_DESCRIPTION.to_string(),
             extractor: Some(extractors::jboot::sch2_extractor()),
+            trial_validation: false,
         },
         // pcap-ng
         signatures::common::Signature {
@@ -788,6 +942,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::pcap::pcapng_parser,
             description: signatures::pcap::PCAPNG_DESCRIPTION.to_string(),
             extractor: Some(extractors::pcap::pcapng_extractor()),
+            trial_validation: false,
         },
         // RSA encrypted data
         signatures::common::Signature {
@@ -799,6 +954,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::rsa::rsa_parser,
             description: signatures::rsa::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // GIF image
         signatures::common::Signature {
@@ -810,6 +966,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::gif::gif_parser,
             description: signatures::gif::DESCRIPTION.to_string(),
             extractor: Some(extractors::gif::gif_extractor()),
+            trial_validation: false,
         },
         // SVG image
         signatures::common::Signature {
@@ -821,6 +978,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::svg::svg_parser,
             description: signatures::svg::DESCRIPTION.to_string(),
             extractor: Some(extractors::svg::svg_extractor()),
+            trial_validation: false,
         },
         // Linux ARM64 boot image
         signatures::common::Signature {
@@ -832,6 +990,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::linux::linux_arm64_boot_image_parser,
             description: signatures::linux::LINUX_ARM64_BOOT_IMAGE_DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // FAT
         signatures::common::Signature {
@@ -842,7 +1001,8 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             magic: signatures::fat::fat_magic(),
             parser: signatures::fat::fat_parser,
             description: signatures::fat::DESCRIPTION.to_string(),
-            extractor: Some(extractors::tsk::tsk_extractor()),
+            extractor: Some(extractors::fat::fat_extractor()),
+            trial_validation: false,
         },
         // EFI GPT
         signatures::common::Signature {
@@ -853,7 +1013,8 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             magic: signatures::efigpt::efigpt_magic(),
             parser: signatures::efigpt::efigpt_parser,
             description: signatures::efigpt::DESCRIPTION.to_string(),
-            extractor: Some(extractors::sevenzip::sevenzip_extractor()),
+            extractor: Some(extractors::efigpt::efigpt_extractor()),
+            trial_validation: false,
         },
         // RTK firmware header
         signatures::common::Signature {
@@ -865,6 +1026,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::rtk::rtk_parser,
             description: signatures::rtk::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // AES S-Box
         signatures::common::Signature {
@@ -876,6 +1038,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::aes::aes_sbox_parser,
             description: signatures::aes::DESCRIPTION_AES_SBOX.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // AES Forward table
         signatures::common::Signature {
@@ -887,6 +1050,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::aes::aes_forward_table_parser,
             description: signatures::aes::DESCRIPTION_AES_FT.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // AES Reverse table
         signatures::common::Signature {
@@ -898,6 +1062,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::aes::aes_reverse_table_parser,
             description: signatures::aes::DESCRIPTION_AES_RT.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // AES RCON
         signatures::common::Signature {
@@ -909,6 +1074,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::aes::aes_rcon_parser,
             description: signatures::aes::DESCRIPTION_AES_RCON.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // Accelerated AES
         signatures::common::Signature {
@@ -920,6 +1086,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::aes::aes_acceleration_table_parser,
             description: signatures::aes::DESCRIPTION_AES_ACC.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // LUKS
         signatures::common::Signature {
@@ -931,6 +1098,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::luks::luks_parser,
             description: signatures::luks::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // TP-Link RTOS
         signatures::common::Signature {
@@ -942,6 +1110,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::tplink::tplink_rtos_parser,
             description: signatures::tplink::RTOS_DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // BIN firmware header
         signatures::common::Signature {
@@ -953,6 +1122,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::binhdr::bin_hdr_parser,
             description: signatures::binhdr::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // Autel obfuscated firmware
         signatures::common::Signature {
@@ -964,6 +1134,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::autel::autel_parser,
             description: signatures::autel::DESCRIPTION.to_string(),
             extractor: Some(extractors::autel::autel_extractor()),
+            trial_validation: false,
         },
         // NTFS
         signatures::common::Signature {
@@ -974,7 +1145,8 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             magic: signatures::ntfs::ntfs_magic(),
             parser: signatures::ntfs::ntfs_parser,
             description: signatures::ntfs::DESCRIPTION.to_string(),
-            extractor: Some(extractors::tsk::tsk_extractor()),
+            extractor: Some(extractors::ntfs::ntfs_extractor()),
+            trial_validation: false,
         },
         // APFS
         signatures::common::Signature {
@@ -986,6 +1158,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::apfs::apfs_parser,
             description: signatures::apfs::DESCRIPTION.to_string(),
             extractor: Some(extractors::sevenzip::sevenzip_extractor()),
+            trial_validation: false,
         },
         // BTRFS
         signatures::common::Signature {
@@ -997,6 +1170,19 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::btrfs::btrfs_parser,
             description: signatures::btrfs::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
+        },
+        // F2FS
+        signatures::common::Signature {
+            name: "f2fs".to_string(),
+            short: false,
+            magic_offset: 0,
+            always_display: true,
+            magic: signatures::f2fs::f2fs_magic(),
+            parser: signatures::f2fs::f2fs_parser,
+            description: signatures::f2fs::DESCRIPTION.to_string(),
+            extractor: None,
+            trial_validation: false,
         },
         // WinCE
         signatures::common::Signature {
@@ -1008,6 +1194,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::wince::wince_parser,
             description: signatures::wince::DESCRIPTION.to_string(),
             extractor: Some(extractors::wince::wince_extractor()),
+            trial_validation: false,
         },
         // Dahua ZIP
         signatures::common::Signature {
@@ -1019,6 +1206,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::dahua_zip::dahua_zip_parser,
             description: signatures::dahua_zip::DESCRIPTION.to_string(),
             extractor: Some(extractors::dahua_zip::dahua_zip_extractor()),
+            trial_validation: false,
         },
         // DLink MH01
         signatures::common::Signature {
@@ -1030,6 +1218,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::mh01::mh01_parser,
             description: signatures::mh01::DESCRIPTION.to_string(),
             extractor: Some(extractors::mh01::mh01_extractor()),
+            trial_validation: false,
         },
         // CSman DAT
         signatures::common::Signature {
@@ -1041,6 +1230,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::csman::csman_parser,
             description: signatures::csman::DESCRIPTION.to_string(),
             extractor: Some(extractors::csman::csman_extractor()),
+            trial_validation: false,
         },
         // DirectX ByteCode
         signatures::common::Signature {
@@ -1052,6 +1242,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::dxbc::dxbc_parser,
             description: signatures::dxbc::DESCRIPTION.to_string(),
             extractor: Some(extractors::dxbc::dxbc_extractor()),
+            trial_validation: false,
         },
         // D-Link TLV firmware
         signatures::common::Signature {
@@ -1063,6 +1254,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::dlink_tlv::dlink_tlv_parser,
             description: signatures::dlink_tlv::DESCRIPTION.to_string(),
             extractor: Some(extractors::encfw::encfw_extractor()),
+            trial_validation: false,
         },
         // DLKE encrypted firmware
         signatures::common::Signature {
@@ -1074,6 +1266,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::dlke::dlke_parser,
             description: signatures::dlke::DESCRIPTION.to_string(),
             extractor: Some(extractors::encfw::encfw_extractor()),
+            trial_validation: false,
         },
         // SHRS encrypted firmware
         signatures::common::Signature {
@@ -1085,6 +1278,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::shrs::shrs_parser,
             description: signatures::shrs::DESCRIPTION.to_string(),
             extractor: Some(extractors::encfw::encfw_extractor()),
+            trial_validation: false,
         },
         // PKCS DER hashes
         signatures::common::Signature {
@@ -1096,6 +1290,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::pkcs_der::der_hash_parser,
             description: signatures::pkcs_der::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // LogFS
         signatures::common::Signature {
@@ -1107,6 +1302,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::logfs::logfs_parser,
             description: signatures::logfs::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // encrpted_img
         signatures::common::Signature {
@@ -1118,6 +1314,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::encrpted_img::encrpted_img_parser,
             description: signatures::encrpted_img::DESCRIPTION.to_string(),
             extractor: Some(extractors::encfw::encfw_extractor()),
+            trial_validation: false,
         },
         // Android boot image
         signatures::common::Signature {
@@ -1129,6 +1326,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::android_bootimg::android_bootimg_parser,
             description: signatures::android_bootimg::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // uboot
         signatures::common::Signature {
@@ -1140,6 +1338,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::uboot::uboot_parser,
             description: signatures::uboot::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // dms firmware
         signatures::common::Signature {
@@ -1151,6 +1350,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::dms::dms_parser,
             description: signatures::dms::DESCRIPTION.to_string(),
             extractor: Some(extractors::swapped::swapped_extractor_u16()),
+            trial_validation: false,
         },
         // dkbs firmware
         signatures::common::Signature {
@@ -1162,6 +1362,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::dkbs::dkbs_parser,
             description: signatures::dkbs::DESCRIPTION.to_string(),
             extractor: Some(extractors::encfw::encfw_extractor()),
+            trial_validation: false,
         },
         // known encrypted firmware
         signatures::common::Signature {
@@ -1173,6 +1374,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::encfw::encfw_parser,
             description: signatures::encfw::DESCRIPTION.to_string(),
             extractor: Some(extractors::encfw::encfw_extractor()),
+            trial_validation: false,
         },
         // matter ota firmware
         signatures::common::Signature {
@@ -1184,6 +1386,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::matter_ota::matter_ota_parser,
             description: signatures::matter_ota::DESCRIPTION.to_string(),
             extractor: Some(extractors::matter_ota::matter_ota_extractor()),
+            trial_validation: false,
         },
         // DPAPI blob data
         signatures::common::Signature {
@@ -1195,6 +1398,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::dpapi::dpapi_parser,
             description: signatures::dpapi::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // QEMU QCOW image
         signatures::common::Signature {
@@ -1206,6 +1410,7 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::qcow::qcow_parser,
             description: signatures::qcow::DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
         },
         // ARJ archive
         signatures::common::Signature {
@@ -1216,7 +1421,20 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             magic: signatures::arj::arj_magic(),
             parser: signatures::arj::arj_parser,
             description: signatures::arj::DESCRIPTION.to_string(),
-            extractor: Some(extractors::sevenzip::sevenzip_extractor()),
+            extractor: Some(extractors::arj::arj_extractor()),
+            trial_validation: false,
+        },
+        // LHA archive
+        signatures::common::Signature {
+            name: "lha".to_string(),
+            short: false,
+            magic_offset: 0,
+            always_display: false,
+            magic: signatures::lha::lha_magic(),
+            parser: signatures::lha::lha_parser,
+            description: signatures::lha::DESCRIPTION.to_string(),
+            extractor: Some(extractors::lha::lha_extractor()),
+            trial_validation: false,
         },
         // MD5 hashes
         signatures::common::Signature {
@@ -1228,6 +1446,43 @@ pub fn patterns() -> Vec<signatures::common::Signature> {
             parser: signatures::hashes::md5_parser,
             description: signatures::hashes::MD5_DESCRIPTION.to_string(),
             extractor: None,
+            trial_validation: false,
+        },
+        // Compressed SWF
+        signatures::common::Signature {
+            name: "swf".to_string(),
+            short: false,
+            magic_offset: 0,
+            always_display: false,
+            magic: signatures::swf::swf_magic(),
+            parser: signatures::swf::swf_parser,
+            description: signatures::swf::DESCRIPTION.to_string(),
+            extractor: Some(extractors::swf::swf_extractor()),
+            trial_validation: false,
+        },
+        // Minix file system
+        signatures::common::Signature {
+            name: "minix".to_string(),
+            short: false,
+            magic_offset: 0,
+            always_display: false,
+            magic: signatures::minix::minix_magic(),
+            parser: signatures::minix::minix_parser,
+            description: signatures::minix::DESCRIPTION.to_string(),
+            extractor: Some(extractors::minix::minix_extractor()),
+            trial_validation: false,
+        },
+        // exFAT file system
+        signatures::common::Signature {
+            name: "exfat".to_string(),
+            short: false,
+            magic_offset: 0,
+            always_display: false,
+            magic: signatures::exfat::exfat_magic(),
+            parser: signatures::exfat::exfat_parser,
+            description: signatures::exfat::DESCRIPTION.to_string(),
+            extractor: Some(extractors::exfat::exfat_extractor()),
+            trial_validation: false,
         },
     ];
 
@@ -1,6 +1,7 @@
 use binwalk::AnalysisResults;
-use log::{debug, error, info};
-use std::collections::VecDeque;
+use log::{debug, error, info, warn};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
 use std::panic;
 use std::process;
 use std::process::ExitCode;
@@ -16,6 +17,7 @@ mod display;
 mod entropy;
 mod extractors;
 mod json;
+mod logging;
 mod magic;
 mod signatures;
 mod structures;
@@ -45,11 +47,17 @@ fn main() -> ExitCode {
      */
     let mut target_files = VecDeque::new();
 
+    // Extraction results for the initial target file, used if the extracted files are to be archived
+    let mut base_extractions = HashMap::new();
+
     // Statistics variables; keeps track of analyzed file count and total analysis run time
     let mut file_count: usize = 0;
     let run_time = time::Instant::now();
     let mut last_progress_interval = time::Instant::now();
 
+    // Run time and throughput of each extractor
+    let mut extraction_statistics = extractors::common::ExtractionStatistics::default();
+
     // Initialize logging
     env_logger::init();
 
@@ -62,6 +70,18 @@ fn main() -> ExitCode {
         return ExitCode::SUCCESS;
     }
 
+    // If --dependencies was specified, just display a list of missing extraction utilities and return
+    if cliargs.dependencies {
+        let missing_dependencies =
+            extractors::common::check_signature_dependencies(&magic::patterns());
+        display::print_missing_dependencies(cliargs.quiet, &missing_dependencies);
+
+        if missing_dependencies.is_empty() {
+            return ExitCode::SUCCESS;
+        }
+        return ExitCode::FAILURE;
+    }
+
     // Set a dummy file name when reading from stdin
     if cliargs.stdin {
         cliargs.file_name = Some(STDIN.to_string());
@@ -73,28 +93,37 @@ fn main() -> ExitCode {
     if cliargs.entropy {
         display::print_plain(cliargs.quiet, "Calculating file entropy...");
 
-        if let Ok(entropy_results) =
-            entropy::plot(cliargs.file_name.unwrap(), cliargs.stdin, cliargs.png)
-        {
+        if let Ok(entropy_results) = entropy::plot(
+            cliargs.file_name.unwrap(),
+            cliargs.stdin,
+            cliargs.png,
+            cliargs.block_size,
+            cliargs.stride,
+        ) {
             // Log entropy results to JSON file, if requested
             json_logger.log(json::JSONType::Entropy(entropy_results.clone()));
             json_logger.close();
 
             display::println_plain(cliargs.quiet, "done.");
         } else {
-            panic!("Entropy analysis failed!");
+            display::println_plain(cliargs.quiet, "failed.");
+            error!(
+                "Entropy analysis failed! Check that the input file is readable, and the block size and stride are valid"
+            );
+            return ExitCode::FAILURE;
         }
 
         return ExitCode::SUCCESS;
     }
 
-    // If extraction or data carving was requested, we need to initialize the output directory
-    if cliargs.extract || cliargs.carve {
-        output_directory = Some(cliargs.directory);
+    // If extraction or data carving was requested, we need to initialize the output directory; when only planning the
+    // extraction, nothing is written to it
+    if (cliargs.extract || cliargs.carve || cliargs.carve_only) && !cliargs.plan {
+        output_directory = Some(cliargs.directory.clone());
     }
 
     // Initialize binwalk
-    let binwalker = match binwalk::Binwalk::configure(
+    let mut binwalker = match binwalk::Binwalk::configure(
         cliargs.file_name,
         output_directory,
         cliargs.include,
@@ -109,6 +138,127 @@ fn main() -> ExitCode {
         Ok(bw) => bw,
     };
 
+    binwalker.trial_size = cliargs.trial_size;
+    binwalker.preview_size = cliargs.preview.unwrap_or(0);
+    binwalker.file_type_lookup = cliargs.file_type;
+    binwalker.extraction_config.keep_failed = cliargs.keep_failed;
+    binwalker.extraction_config.carve_directory = cliargs
+        .carve_dir
+        .map(|carve_dir| carve_dir.map_or_else(std::env::temp_dir, std::path::PathBuf::from));
+    binwalker.extraction_config.run_deadline = cliargs
+        .deadline
+        .map(|deadline| run_time + time::Duration::from_secs(deadline));
+    binwalker.extraction_config.kill_at_deadline = cliargs.kill_at_deadline;
+    binwalker.extraction_config.dedupe = cliargs.dedupe;
+    binwalker.extraction_config.carve_only = cliargs.carve_only;
+    binwalker.extraction_config.carve_alignment = cliargs.carve_align;
+    binwalker.extraction_config.carve_padding = cliargs.carve_pad;
+    binwalker.extraction_config.quarantine = cliargs.quarantine;
+    binwalker.extraction_config.preserve_xattrs = cliargs.xattrs;
+    binwalker.extraction_config.symlink_allowlist = cliargs.symlink_allowlist.unwrap_or_default();
+    binwalker.extraction_config.resume = cliargs.resume;
+    binwalker.extraction_config.min_confidence = cliargs.min_confidence;
+    binwalker.extraction_config.gap_min_size = cliargs.gaps;
+    binwalker.extraction_config.nested_signatures = match cliargs.nested.as_str() {
+        "outermost" => extractors::common::NestedSignaturePolicy::Outermost,
+        "innermost" => extractors::common::NestedSignaturePolicy::Innermost,
+        "both" => extractors::common::NestedSignaturePolicy::Both,
+        _ => extractors::common::NestedSignaturePolicy::Discard,
+    };
+    binwalker.extraction_config.digest_algorithm =
+        cliargs.digest.map(|digest| match digest.as_str() {
+            "md5" => extractors::common::DigestAlgorithm::Md5,
+            "sha1" => extractors::common::DigestAlgorithm::Sha1,
+            "blake3" => extractors::common::DigestAlgorithm::Blake3,
+            _ => extractors::common::DigestAlgorithm::Sha256,
+        });
+    binwalker.extraction_config.existing_output = match cliargs.existing.as_str() {
+        "fail" => extractors::common::ExistingOutputPolicy::Fail,
+        "skip" => extractors::common::ExistingOutputPolicy::Skip,
+        _ => extractors::common::ExistingOutputPolicy::Overwrite,
+    };
+    // Resumed runs must keep the manifest up to date, in case they too are interrupted
+    binwalker.extraction_config.write_manifest = cliargs.manifest || cliargs.resume;
+    binwalker.extraction_config.no_recurse = cliargs.no_recurse.unwrap_or_default();
+    binwalker.extraction_config.terminal_types = cliargs.terminal.unwrap_or_default();
+    binwalker.extraction_config.extractor_filter = extractors::common::ExtractorFilter {
+        include: cliargs.include_extractors.unwrap_or_default(),
+        exclude: cliargs.exclude_extractors.unwrap_or_default(),
+    };
+
+    // Each extractor path is specified as <command name>=<path>
+    for extractor_path in cliargs.extractor_paths.unwrap_or_default() {
+        match extractor_path.split_once('=') {
+            Some((command, command_path)) if !command.is_empty() && !command_path.is_empty() => {
+                binwalker
+                    .extraction_config
+                    .command_paths
+                    .insert(command.to_string(), command_path.to_string());
+            }
+            _ => {
+                error!("Invalid extractor path '{extractor_path}', expected <name>=<path>");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    // Each carved file extension is specified as <signature name>=<extension>
+    for carved_extension in cliargs.carved_extensions.unwrap_or_default() {
+        match carved_extension.split_once('=') {
+            Some((signature, extension)) if !signature.is_empty() && !extension.is_empty() => {
+                binwalker
+                    .extraction_config
+                    .carved_extensions
+                    .insert(signature.to_string(), extension.to_string());
+            }
+            _ => {
+                error!(
+                    "Invalid carved file extension '{carved_extension}', expected <name>=<extension>"
+                );
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    if let Some(extractor_config) = cliargs.extractor_config {
+        match binwalker
+            .extraction_config
+            .extractor_registry
+            .register_from_file(&extractor_config)
+        {
+            Ok(count) => debug!("Loaded {count} extractor definitions from {extractor_config}"),
+            Err(e) => {
+                error!("Failed to load extractor configuration '{extractor_config}': {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    // ELF files are only sized, not extracted, unless section carving was requested
+    if cliargs.elf_sections {
+        binwalker.register_extractor("elf", extractors::elf::elf_sections_extractor());
+    }
+
+    // Deleted FAT files are only recovered if explicitly requested
+    if cliargs.fat_deleted {
+        binwalker.register_extractor("fat", extractors::fat::fat_deleted_files_extractor());
+    }
+
+    // If --plan was specified, just display the extractions that would be performed and return
+    if cliargs.plan {
+        return print_plan(
+            &binwalker,
+            &cliargs.directory,
+            cliargs.stdin,
+            cliargs.decompress_input,
+            cliargs.quiet,
+            cliargs.matryoshka,
+        );
+    }
+
+    // External extractor output is only of use when debugging
+    binwalker.extraction_config.capture_output = log::log_enabled!(log::Level::Debug);
+
     // If the user specified --threads, honor that request; else, auto-detect available parallelism
     let available_workers = cliargs.threads.unwrap_or_else(|| {
         // Get CPU core info
@@ -127,6 +277,17 @@ fn main() -> ExitCode {
         panic!("No available worker threads!");
     }
 
+    // Limit the number of concurrently running external extractors
+    let max_processes = cliargs.max_processes.unwrap_or(available_workers);
+    binwalker.extraction_config.process_limiter =
+        extractors::common::ProcessLimiter::new(max_processes);
+
+    if max_processes == 0 {
+        info!("Not limiting the number of concurrent external extractor processes");
+    } else {
+        info!("Running at most {max_processes} external extractor processes at a time");
+    }
+
     // Initialize thread pool
     debug!("Initializing thread pool with {available_workers} workers");
     let workers = ThreadPool::new(available_workers);
@@ -149,7 +310,14 @@ fn main() -> ExitCode {
     );
 
     // Queue the initial file path
-    target_files.insert(target_files.len(), binwalker.base_target_file.clone());
+    target_files.insert(
+        target_files.len(),
+        TargetFile {
+            path: binwalker.base_target_file.clone(),
+            ancestors: vec![],
+            decompress: cliargs.decompress_input,
+        },
+    );
 
     /*
      * Main loop.
@@ -169,7 +337,7 @@ fn main() -> ExitCode {
                 binwalker.clone(),
                 target_file,
                 cliargs.stdin && file_count == 0,
-                cliargs.extract,
+                cliargs.extract || cliargs.carve_only,
                 cliargs.carve,
                 worker_tx.clone(),
             );
@@ -193,44 +361,116 @@ fn main() -> ExitCode {
         }
 
         // Get response from a worker thread, if any
-        if let Ok(results) = worker_rx.try_recv() {
+        if let Ok((results, lineage)) = worker_rx.try_recv() {
             // Keep a tally of how many files have been analyzed
             file_count += 1;
 
             // Log analysis results to JSON file
             json_logger.log(json::JSONType::Analysis(results.clone()));
 
-            // Nothing found? Nothing else to do for this file.
-            if results.file_map.is_empty() {
+            // Keep a tally of how long each extractor took, and of what each signature matched and produced
+            for signature in &results.file_map {
+                let extraction_result = results.extractions.get(&signature.id);
+
+                if let Some(extraction_result) = extraction_result {
+                    extraction_statistics.add(signature, extraction_result);
+                }
+
+                extraction_statistics.add_match(signature, extraction_result);
+            }
+
+            if cliargs.archive && results.file_path == binwalker.base_target_file {
+                base_extractions = results.extractions.clone();
+            }
+
+            // Nothing found? Nothing else to do for this file, other than reporting its file type, if known.
+            if results.file_map.is_empty() && results.file_type.is_none() {
                 debug!("Found no results for file {}", results.file_path);
                 continue;
             }
 
             // Print analysis results to screen
             if should_display(&results, file_count, cliargs.verbose) {
-                display::print_analysis_results(cliargs.quiet, cliargs.extract, &results);
+                display::print_analysis_results(
+                    cliargs.quiet,
+                    cliargs.extract || cliargs.carve_only,
+                    &results,
+                );
+            }
+
+            // Once the output file system is full or read-only, there is no point in analyzing any more files
+            if binwalker.extraction_config.output_failure.get().is_some() {
+                target_files.clear();
+                continue;
+            }
+
+            // Once the run deadline has passed, no more files are analyzed
+            if binwalker.extraction_config.deadline_passed() {
+                if !target_files.is_empty() {
+                    warn!(
+                        "Run deadline reached, not analyzing {} queued file(s)",
+                        target_files.len()
+                    );
+                    target_files.clear();
+                }
+                continue;
             }
 
             // If running recursively, add extraction results to list of files to analyze
             if cliargs.matryoshka {
                 for (_signature_id, extraction_result) in results.extractions.into_iter() {
-                    if !extraction_result.do_not_recurse {
-                        for file_path in extractors::common::get_extracted_files(
-                            &extraction_result.output_directory,
-                        ) {
-                            debug!("Queuing {file_path} for analysis");
-                            target_files.insert(target_files.len(), file_path.clone());
+                    for file_path in extraction_result.recursion_targets() {
+                        if binwalker.extraction_config.is_terminal_file(&file_path) {
+                            debug!("Not queuing {file_path} for analysis: terminal file type");
+                            continue;
                         }
+
+                        debug!("Queuing {file_path} for analysis");
+                        target_files.insert(
+                            target_files.len(),
+                            TargetFile {
+                                path: file_path.clone(),
+                                ancestors: lineage.clone(),
+                                decompress: false,
+                            },
+                        );
                     }
                 }
             }
         }
     }
 
+    if !extraction_statistics.extractors.is_empty() || !extraction_statistics.signatures.is_empty()
+    {
+        json_logger.log(json::JSONType::ExtractionStatistics(
+            extraction_statistics.clone(),
+        ));
+    }
+
     json_logger.close();
 
+    // The reason for aborting the extraction has already been logged
+    if binwalker.extraction_config.output_failure.get().is_some() {
+        return ExitCode::FAILURE;
+    }
+
+    // Archiving happens once all files, including recursively extracted files, have been processed
+    if cliargs.archive
+        && (cliargs.extract || cliargs.carve_only)
+        && extractors::common::archive_extractions(
+            &binwalker.base_target_file,
+            &base_extractions,
+            &binwalker.extraction_config,
+        )
+        .is_err()
+    {
+        return ExitCode::FAILURE;
+    }
+
     // If BINWALK_RM_SYMLINK env var was set, delete the base_target_file symlink
-    if (cliargs.carve || cliargs.extract) && std::env::var(BINWALK_RM_SYMLINK).is_ok() {
+    if (cliargs.carve || cliargs.extract || cliargs.carve_only)
+        && std::env::var(BINWALK_RM_SYMLINK).is_ok()
+    {
         if let Err(e) = std::fs::remove_file(&binwalker.base_target_file) {
             error!(
                 "Request to remove extraction symlink file {} failed: {}",
@@ -240,6 +480,11 @@ fn main() -> ExitCode {
     }
 
     // All done, show some basic statistics
+    display::print_signature_statistics(
+        cliargs.quiet || (file_count <= 1 && extraction_statistics.extractors.is_empty()),
+        &extraction_statistics,
+    );
+    display::print_extraction_statistics(cliargs.quiet, &extraction_statistics);
     display::print_stats(
         cliargs.quiet,
         run_time,
@@ -251,16 +496,58 @@ fn main() -> ExitCode {
     ExitCode::SUCCESS
 }
 
+/// Displays the extractions that would be performed on the target file, without extracting anything
+fn print_plan(
+    binwalker: &binwalk::Binwalk,
+    output_directory: &str,
+    stdin: bool,
+    decompress: bool,
+    quiet: bool,
+    recursive: bool,
+) -> ExitCode {
+    let file_data = match common::read_input(&binwalker.base_target_file, stdin) {
+        Err(_) => {
+            error!("Failed to read {} data", binwalker.base_target_file);
+            return ExitCode::FAILURE;
+        }
+        Ok(data) => data,
+    };
+
+    // As when extracting, fall back to the raw file data if it can't be decompressed
+    let file_data = if decompress {
+        common::decompress_input(&file_data).unwrap_or(file_data)
+    } else {
+        file_data
+    };
+
+    // Output directories are planned as they would be created when extracting: inside the extraction directory, next to
+    // the symlink to the target file
+    let file_name = std::path::Path::new(&binwalker.base_target_file)
+        .file_name()
+        .unwrap_or_default();
+    let planned_path = std::path::Path::new(output_directory).join(file_name);
+    let planned_path = std::path::absolute(&planned_path).unwrap_or(planned_path);
+
+    let file_map = binwalker.scan(&file_data);
+    let plan = binwalker.plan(&file_data, planned_path.display().to_string(), &file_map);
+
+    display::print_extraction_plan(quiet, &binwalker.base_target_file, recursive, &plan);
+
+    ExitCode::SUCCESS
+}
+
 /// Returns true if the specified results should be displayed to screen
 fn should_display(results: &AnalysisResults, file_count: usize, verbose: bool) -> bool {
     let mut display_results: bool = false;
 
     /*
      * For brevity, when analyzing more than one file only display subsequent files whose results
-     * contain signatures that we always want displayed, or which contain extractable signatures.
+     * contain signatures that we always want displayed, which contain extractable signatures, or whose file
+     * type is known.
      * This can be overridden with the --verbose command line flag.
      */
-    if file_count == 1 || verbose || !results.extractions.is_empty() {
+    if file_count == 1 || verbose || !results.extractions.is_empty() || results.file_type.is_some()
+    {
         display_results = true;
     } else {
         for signature in &results.file_map {
@@ -274,17 +561,28 @@ fn should_display(results: &AnalysisResults, file_count: usize, verbose: bool) -
     display_results
 }
 
+/// A file queued for analysis
+struct TargetFile {
+    path: String,
+    /// SHA-256 hashes of the files that this file was recursively extracted from, starting with the initial target file
+    ancestors: Vec<String>,
+    /// If true, and the file is a single compressed stream, the decompressed data is analyzed instead
+    decompress: bool,
+}
+
 /// Spawn a worker thread to analyze a file
 fn spawn_worker(
     pool: &ThreadPool,
     bw: binwalk::Binwalk,
-    target_file: String,
+    target: TargetFile,
     stdin: bool,
     do_extraction: bool,
     do_carve: bool,
-    worker_tx: mpsc::Sender<AnalysisResults>,
+    worker_tx: mpsc::Sender<(AnalysisResults, Vec<String>)>,
 ) {
     pool.execute(move || {
+        let target_file = target.path;
+
         // Read in file data
         let file_data = match common::read_input(&target_file, stdin) {
             Err(_) => {
@@ -294,17 +592,64 @@ fn spawn_worker(
             Ok(data) => data,
         };
 
-        // Analyze target file, with extraction, if specified
-        let results = bw.analyze_buf(&file_data, &target_file, do_extraction);
+        // Analyze the decompressed data if requested, falling back to the raw file data if it can't be decompressed
+        let file_data = if target.decompress {
+            match common::decompress_input(&file_data) {
+                Some(decompressed_data) => {
+                    info!(
+                        "Analyzing {} bytes of data decompressed from {target_file}",
+                        decompressed_data.len()
+                    );
+                    decompressed_data
+                }
+                None => {
+                    warn!("{target_file} is not a single compressed stream, analyzing it as-is");
+                    file_data
+                }
+            }
+        } else {
+            file_data
+        };
+
+        /*
+         * Archives can be crafted to contain a copy of themselves, which is not necessarily caught by limiting the
+         * recursion depth if each level differs slightly. If this file is identical to any file that it was extracted
+         * from, analyzing it would only repeat the same extraction, so break the cycle here.
+         */
+        let sha256 = hex::encode(Sha256::digest(&file_data));
+        let mut lineage = target.ancestors;
+
+        let results = match lineage.iter().position(|ancestor| *ancestor == sha256) {
+            Some(ancestor_index) => {
+                warn!(
+                    "Not analyzing {}: it is identical to the file it was extracted from {} level(s) up, extraction cycle detected",
+                    target_file,
+                    lineage.len() - ancestor_index
+                );
+                AnalysisResults {
+                    file_path: target_file.clone(),
+                    ..Default::default()
+                }
+            }
+            None => {
+                // Files extracted from this file inherit its lineage
+                lineage.push(sha256);
+
+                // Analyze target file, with extraction, if specified
+                let results = bw.analyze_buf(&file_data, &target_file, do_extraction);
 
-        // If data carving was requested as part of extraction, carve analysis results to disk
-        if do_carve {
-            let carve_count = carve_file_map(&file_data, &results);
-            info!("Carved {carve_count} data blocks to disk from {target_file}");
-        }
+                // If data carving was requested as part of extraction, carve analysis results to disk
+                if do_carve {
+                    let carve_count = carve_file_map(&file_data, &results);
+                    info!("Carved {carve_count} data blocks to disk from {target_file}");
+                }
+
+                results
+            }
+        };
 
         // Report file results back to main thread
-        if let Err(e) = worker_tx.send(results) {
+        if let Err(e) = worker_tx.send((results, lineage)) {
             panic!(
                 "Worker thread for {target_file} failed to send results back to main thread: {e}"
             );
@@ -383,7 +728,10 @@ fn carve_file_data_to_disk(
     debug!("Carving {carved_file_path}");
 
     // Carve the data to disk
-    if !chroot.carve_file(&carved_file_path, file_data, offset, size) {
+    if chroot
+        .carve_file(&carved_file_path, file_data, offset, size)
+        .is_err()
+    {
         error!(
             "Failed to carve {} [{:#X}..{:#X}] to disk",
             carved_file_path,
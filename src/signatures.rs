@@ -36,6 +36,8 @@
 //!     description: "FooBar file".to_string(),
 //!     // The extractor to use to extract this file/data type
 //!     extractor: Some(foobar_extractor()),
+//!     // Set to true for signatures with very short magic bytes, if the extractor provides a trial decoder
+//!     trial_validation: false,
 //! };
 //! ```
 //!
@@ -109,6 +111,7 @@ pub mod aes;
 pub mod android_bootimg;
 pub mod androidsparse;
 pub mod apfs;
+pub mod ar;
 pub mod arcadyan;
 pub mod arj;
 pub mod autel;
@@ -127,6 +130,7 @@ pub mod cramfs;
 pub mod csman;
 pub mod dahua_zip;
 pub mod deb;
+pub mod der;
 pub mod dkbs;
 pub mod dlink_tlv;
 pub mod dlke;
@@ -140,17 +144,22 @@ pub mod ecos;
 pub mod efigpt;
 pub mod elf;
 pub mod encfw;
+pub mod encoded;
 pub mod encrpted_img;
+pub mod exfat;
 pub mod ext;
+pub mod f2fs;
 pub mod fat;
 pub mod gif;
 pub mod gpg;
 pub mod gzip;
 pub mod hashes;
+pub mod ihex;
 pub mod iso9660;
 pub mod jboot;
 pub mod jffs2;
 pub mod jpeg;
+pub mod lha;
 pub mod linux;
 pub mod logfs;
 pub mod luks;
@@ -161,6 +170,7 @@ pub mod lzop;
 pub mod matter_ota;
 pub mod mbr;
 pub mod mh01;
+pub mod minix;
 pub mod ntfs;
 pub mod openssl;
 pub mod packimg;
@@ -185,6 +195,7 @@ pub mod shrs;
 pub mod squashfs;
 pub mod srec;
 pub mod svg;
+pub mod swf;
 pub mod tarball;
 pub mod tplink;
 pub mod trx;
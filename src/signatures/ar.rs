@@ -0,0 +1,44 @@
+use crate::extractors::ar::extract_ar_archive;
+use crate::signatures::common::{CONFIDENCE_HIGH, SignatureError, SignatureResult};
+use crate::signatures::deb::deb_magic;
+use crate::structures::ar::AR_MAGIC;
+
+/// Human readable description
+pub const DESCRIPTION: &str = "Unix ar archive";
+
+/// ar archives always start with these bytes
+pub fn ar_magic() -> Vec<Vec<u8>> {
+    vec![AR_MAGIC.to_vec()]
+}
+
+/// Validates ar archive signatures
+pub fn ar_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, SignatureError> {
+    // Successful return value
+    let mut result = SignatureResult {
+        offset,
+        description: DESCRIPTION.to_string(),
+        confidence: CONFIDENCE_HIGH,
+        ..Default::default()
+    };
+
+    // Debian packages are ar archives too, but are reported by the more specific deb signature
+    if deb_magic()
+        .iter()
+        .any(|magic| file_data[offset..].starts_with(magic))
+    {
+        return Err(SignatureError);
+    }
+
+    // Do an extraction dry-run to validate the archive members and determine the archive size
+    let dry_run = extract_ar_archive(file_data, offset, None);
+
+    if dry_run.success
+        && let Some(ar_size) = dry_run.size
+    {
+        result.size = ar_size;
+        result.description = format!("{}, total size: {} bytes", result.description, result.size);
+        return Ok(result);
+    }
+
+    Err(SignatureError)
+}
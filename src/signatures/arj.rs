@@ -1,4 +1,7 @@
-use crate::signatures::common::{CONFIDENCE_MEDIUM, SignatureError, SignatureResult};
+use crate::extractors::arj::extract_arj_archive;
+use crate::signatures::common::{
+    CONFIDENCE_HIGH, CONFIDENCE_MEDIUM, SignatureError, SignatureResult,
+};
 use crate::structures::arj::parse_arj_header;
 
 pub const DESCRIPTION: &str = "ARJ archive data";
@@ -11,6 +14,18 @@ pub fn arj_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, Si
         let available_data = file_data.len() - offset;
         // Sanity check the reported ARJ header size
         if arj_header.header_size <= available_data {
+            let mut size = arj_header.header_size;
+            let mut confidence = CONFIDENCE_MEDIUM;
+
+            // If this is the archive's main header, walk the archive headers to determine the size of the archive
+            let dry_run = extract_arj_archive(file_data, offset, None);
+            if dry_run.success
+                && let Some(archive_size) = dry_run.size
+            {
+                size = archive_size;
+                confidence = CONFIDENCE_HIGH;
+            }
+
             // Return success
             return Ok(SignatureResult {
                 description: format!(
@@ -29,8 +44,8 @@ pub fn arj_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, Si
                     arj_header.host_os,
                 ),
                 offset,
-                size: arj_header.header_size,
-                confidence: CONFIDENCE_MEDIUM,
+                size,
+                confidence,
                 extraction_declined: arj_header.file_type != *"comment header",
                 ..Default::default()
             });
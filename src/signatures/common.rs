@@ -58,6 +58,11 @@ pub struct SignatureResult {
     pub always_display: bool,
     /// Set to true to disable extraction for this particular signature result (default: false)
     pub extraction_declined: bool,
+    /// Hex and ASCII preview of the first bytes of the signature data, see `Binwalk.preview_size`; None if disabled
+    pub preview: Option<String>,
+    /// ID of the signature whose data contains this signature, if nested signatures are reported; see
+    /// `ExtractionConfig.nested_signatures`
+    pub parent: Option<String>,
     /// Signatures may specify a preferred extractor, which overrides the default extractor specified in the Signature.extractor definition
     #[serde(skip_deserializing, skip_serializing)]
     pub preferred_extractor: Option<extractors::common::Extractor>,
@@ -82,4 +87,8 @@ pub struct Signature {
     pub parser: SignatureParser,
     /// Specifies the extractor to use when extracting this file type
     pub extractor: Option<extractors::common::Extractor>,
+    /// If true, magic matches must first pass a trial decode by the extractor's `trial_decoder` (see
+    /// `Binwalk.trial_size`) before being validated by the signature parser. Intended for signatures with short
+    /// magic bytes that are prone to false positives.
+    pub trial_validation: bool,
 }
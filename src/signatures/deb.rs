@@ -1,3 +1,4 @@
+use crate::extractors::ar::extract_ar_archive;
 use crate::signatures::common::{CONFIDENCE_HIGH, SignatureError, SignatureResult};
 use crate::structures::deb::parse_deb_header;
 
@@ -23,6 +24,11 @@ pub fn deb_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, Si
     if let Ok(deb_header) = parse_deb_header(&file_data[offset..]) {
         result.size = deb_header.file_size;
 
+        // The header does not account for member padding; if the archive members can be walked, trust that size instead
+        if let Some(ar_size) = extract_ar_archive(file_data, offset, None).size {
+            result.size = ar_size;
+        }
+
         // Make sure the reported size of the DEB file is sane
         if result.size <= (file_data.len() - offset) {
             result.description =
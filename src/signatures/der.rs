@@ -0,0 +1,112 @@
+use crate::extractors::der::der_carver;
+use crate::signatures::common::{CONFIDENCE_HIGH, SignatureError, SignatureResult};
+use crate::structures::der::{
+    DER_SEQUENCE, parse_der_element, parse_der_key, parse_x509_certificate,
+};
+
+/// Human readable descriptions
+pub const DER_CERTIFICATE_DESCRIPTION: &str = "DER certificate";
+pub const DER_KEY_DESCRIPTION: &str = "DER key";
+
+/// X.509 v3 certificates start their TBSCertificate with an explicitly tagged version field, followed by the serial number
+pub fn der_certificate_magic() -> Vec<Vec<u8>> {
+    vec![b"\xA0\x03\x02\x01\x02\x02".to_vec()]
+}
+
+/// Key magics match the first fields inside the key's outer SEQUENCE
+pub fn der_key_magic() -> Vec<Vec<u8>> {
+    vec![
+        // PKCS#1 RSA private key: version 0, followed by the modulus
+        b"\x02\x01\x00\x02\x81".to_vec(),
+        b"\x02\x01\x00\x02\x82".to_vec(),
+        // PKCS#8 RSA private key
+        b"\x02\x01\x00\x30\x0D\x06\x09\x2A\x86\x48\x86\xF7\x0D\x01\x01\x01".to_vec(),
+        // PKCS#8 EC private key
+        b"\x02\x01\x00\x30\x13\x06\x07\x2A\x86\x48\xCE\x3D\x02\x01".to_vec(),
+        // RSA SubjectPublicKeyInfo
+        b"\x30\x0D\x06\x09\x2A\x86\x48\x86\xF7\x0D\x01\x01\x01\x05\x00\x03".to_vec(),
+        // EC SubjectPublicKeyInfo
+        b"\x30\x13\x06\x07\x2A\x86\x48\xCE\x3D\x02\x01\x06".to_vec(),
+    ]
+}
+
+/// Validates DER certificate signatures
+pub fn der_certificate_parser(
+    file_data: &[u8],
+    offset: usize,
+) -> Result<SignatureResult, SignatureError> {
+    // The magic bytes are inside the TBSCertificate SEQUENCE, which is itself inside the certificate SEQUENCE
+    if let Some(tbs_start) = sequence_start(file_data, offset)
+        && let Some(certificate_start) = sequence_start(file_data, tbs_start)
+        && let Some(certificate_size) = der_dry_run(file_data, certificate_start)
+        && let Ok(certificate) = parse_x509_certificate(
+            &file_data[certificate_start..certificate_start + certificate_size],
+        )
+    {
+        return Ok(SignatureResult {
+            offset: certificate_start,
+            size: certificate_size,
+            confidence: CONFIDENCE_HIGH,
+            // If the file starts and ends with this certificate, no sense in carving it out to another file on disk
+            extraction_declined: certificate_start == 0 && certificate_size == file_data.len(),
+            description: format!(
+                "{}, subject: \"{}\", total size: {} bytes",
+                DER_CERTIFICATE_DESCRIPTION, certificate.subject, certificate_size
+            ),
+            ..Default::default()
+        });
+    }
+
+    Err(SignatureError)
+}
+
+/// Validates DER key signatures
+pub fn der_key_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, SignatureError> {
+    if let Some(key_start) = sequence_start(file_data, offset)
+        && let Some(key_size) = der_dry_run(file_data, key_start)
+        && let Ok(key) = parse_der_key(&file_data[key_start..key_start + key_size])
+    {
+        let key_type = match key.private {
+            true => "private",
+            false => "public",
+        };
+
+        return Ok(SignatureResult {
+            offset: key_start,
+            size: key_size,
+            confidence: CONFIDENCE_HIGH,
+            extraction_declined: key_start == 0 && key_size == file_data.len(),
+            description: format!(
+                "{}, {} {}, total size: {} bytes",
+                DER_KEY_DESCRIPTION, key.algorithm, key_type, key_size
+            ),
+            ..Default::default()
+        });
+    }
+
+    Err(SignatureError)
+}
+
+/// Do an extraction dry-run to validate the DER structure and determine its size
+fn der_dry_run(file_data: &[u8], offset: usize) -> Option<usize> {
+    let dry_run = der_carver(file_data, offset, None, None);
+
+    match dry_run.success {
+        true => dry_run.size,
+        false => None,
+    }
+}
+
+/// Given the offset of a DER element, locates the header of the SEQUENCE that immediately encloses it
+fn sequence_start(file_data: &[u8], element_offset: usize) -> Option<usize> {
+    // A SEQUENCE header is a tag byte followed by a short form length byte, or by up to 4 long form length bytes
+    (2..=6).find_map(|header_size| {
+        let sequence_start = element_offset.checked_sub(header_size)?;
+        let sequence = parse_der_element(&file_data[sequence_start..]).ok()?;
+
+        match sequence.tag == DER_SEQUENCE && sequence.header_size == header_size {
+            true => Some(sequence_start),
+            false => None,
+        }
+    })
+}
@@ -1,5 +1,5 @@
 use crate::signatures::common::{CONFIDENCE_HIGH, SignatureError, SignatureResult};
-use crate::structures::dmg::parse_dmg_footer;
+use crate::structures::dmg::{parse_dmg_footer, parse_dmg_resource_fork};
 use aho_corasick::AhoCorasick;
 
 /// Human readable description
@@ -39,15 +39,33 @@ pub fn dmg_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, Si
          * Instead, we have to search the file data for the XML property, then the correct offset can be calculated.
          */
 
+        // Older DMGs store their block tables in a resource fork, which immediately precedes the footer
+        if dmg_footer.xml_length == 0 && dmg_footer.rsrc_length > 0 {
+            if let Some(dmg_start) =
+                offset.checked_sub(dmg_footer.rsrc_offset + dmg_footer.rsrc_length)
+                && dmg_footer.data_offset + dmg_footer.data_length <= dmg_footer.rsrc_offset
+                && parse_dmg_resource_fork(&file_data[dmg_start + dmg_footer.rsrc_offset..offset])
+                    .is_ok_and(|resources| !resources.is_empty())
+            {
+                result.offset = dmg_start;
+                result.size = offset + dmg_footer.footer_size - dmg_start;
+                result.description =
+                    format!("{}, total size: {} bytes", result.description, result.size);
+                return Ok(result);
+            }
+
+            return Err(SignatureError);
+        }
+
         // Make sure the length of image data and length of XML data are sane
         if (dmg_footer.data_length + dmg_footer.xml_length) <= offset {
             // Locate the XML data
             if let Some(xml_offset) = find_xml_property_list(file_data) {
                 // Make sure the XML data comes after the image data
                 if xml_offset >= dmg_footer.data_length {
-                    // Report the result
-                    result.size = offset + dmg_footer.footer_size;
+                    // Report the result; the DMG ends with the footer
                     result.offset = xml_offset - dmg_footer.data_length;
+                    result.size = offset + dmg_footer.footer_size - result.offset;
                     result.description =
                         format!("{}, total size: {} bytes", result.description, result.size);
                     return Ok(result);
@@ -1,4 +1,6 @@
-use crate::signatures::common::{CONFIDENCE_HIGH, SignatureError, SignatureResult};
+use crate::signatures::common::{
+    CONFIDENCE_HIGH, CONFIDENCE_MEDIUM, SignatureError, SignatureResult,
+};
 use crate::structures::efigpt::parse_efigpt_header;
 
 /// Human readable description
@@ -21,23 +23,31 @@ pub fn efigpt_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult,
         ..Default::default()
     };
 
-    let available_data = file_data.len() - offset;
-
     if offset >= MAGIC_OFFSET {
         // MBR actually starts this may bytes before the magic bytes
         result.offset = offset - MAGIC_OFFSET;
 
         // Get the EFI data, including the MBR block
         if let Some(efi_data) = file_data.get(result.offset..) {
-            // Parse the EFI data; this also validates CRC so if this succeeds, confidence is high
+            // Parse the EFI data; this also validates the GPT CRCs
             if let Ok(efi_header) = parse_efigpt_header(efi_data) {
+                // If neither the primary nor the backup GPT passed CRC validation, be less confident in this signature
+                if !efi_header.primary_crc_valid && efi_header.backup_crc_valid != Some(true) {
+                    result.confidence = CONFIDENCE_MEDIUM;
+                    result.description = format!("{}, CRC mismatch", result.description);
+                } else if efi_header.used_backup {
+                    result.description = format!("{}, primary GPT is corrupt", result.description);
+                }
+
                 // Some EFI images have been observed to define partitions that extend beyond EOF.
                 // If that is the case, assume the EFI image extends to EOF.
-                if efi_header.total_size > available_data {
-                    result.size = available_data;
-                } else {
-                    result.size = efi_header.total_size;
+                result.size = efi_header.total_size.min(efi_data.len());
+
+                for partition in &efi_header.partitions {
+                    result.description =
+                        format!("{}, partition: {}", result.description, partition.type_name);
                 }
+
                 result.description = format!("{}, total size: {}", result.description, result.size);
                 return Ok(result);
             }
@@ -1,3 +1,4 @@
+use crate::extractors::elf::extract_elf_sections;
 use crate::signatures::common::{CONFIDENCE_MEDIUM, SignatureError, SignatureResult};
 use crate::structures::elf::parse_elf_header;
 
@@ -31,6 +32,18 @@ pub fn elf_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, Si
             elf_header.osabi,
             elf_header.endianness
         );
+
+        // Do an extraction dry-run to determine the size of the ELF image from its program and section headers
+        let dry_run = extract_elf_sections(file_data, offset, None);
+
+        if dry_run.success
+            && let Some(elf_size) = dry_run.size
+        {
+            result.size = elf_size;
+            result.description =
+                format!("{}, total size: {} bytes", result.description, result.size);
+        }
+
         return Ok(result);
     }
 
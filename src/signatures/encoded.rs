@@ -0,0 +1,99 @@
+use crate::extractors::encoded::{extract_base64, extract_hex};
+use crate::signatures::common::{CONFIDENCE_MEDIUM, SignatureError, SignatureResult};
+
+/// Human readable descriptions
+pub const BASE64_DESCRIPTION: &str = "Base64 encoded data";
+pub const HEX_DESCRIPTION: &str = "Hex encoded data";
+
+/// File types that are commonly found encoded in config files and web pages: their names, their headers, and the base64
+/// encodings of their headers, up to the last character that is fully determined by the header
+const ENCODED_FILE_HEADERS: [(&str, &[u8], &[u8]); 10] = [
+    ("gzip", b"\x1F\x8B\x08", b"H4sI"),
+    ("ELF", b"\x7FELF", b"f0VMR"),
+    ("zip", b"PK\x03\x04", b"UEsDB"),
+    ("xz", b"\xFD7zXZ\x00", b"/Td6WFoA"),
+    ("bzip2", b"BZh", b"Qlpo"),
+    ("7-zip", b"7z\xBC\xAF\x27\x1C", b"N3q8rycc"),
+    ("squashfs", b"hsqs", b"aHNxc"),
+    ("PNG", b"\x89PNG\r\n", b"iVBORw0K"),
+    ("JPEG", b"\xFF\xD8\xFF", b"/9j/"),
+    ("uImage", b"\x27\x05\x19\x56", b"JwUZV"),
+];
+
+/// Encoded data has no magic bytes of its own; instead, look for the encoded headers of interesting file types
+pub fn base64_magic() -> Vec<Vec<u8>> {
+    ENCODED_FILE_HEADERS
+        .iter()
+        .map(|(_, _, base64_header)| base64_header.to_vec())
+        .collect()
+}
+
+/// Lower and upper case hex encodings of the file headers
+pub fn hex_magic() -> Vec<Vec<u8>> {
+    ENCODED_FILE_HEADERS
+        .iter()
+        .flat_map(|(_, header, _)| {
+            let hex_header = hex::encode(header);
+            [
+                hex_header.to_uppercase().into_bytes(),
+                hex_header.into_bytes(),
+            ]
+        })
+        .collect()
+}
+
+/// Validates base64 encoded data
+pub fn base64_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, SignatureError> {
+    let file_type = ENCODED_FILE_HEADERS
+        .iter()
+        .find(|(_, _, base64_header)| file_data[offset..].starts_with(base64_header))
+        .map(|(file_type, _, _)| *file_type);
+
+    encoded_parser(
+        extract_base64(file_data, offset, None).size,
+        offset,
+        BASE64_DESCRIPTION,
+        file_type,
+    )
+}
+
+/// Validates hex encoded data
+pub fn hex_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, SignatureError> {
+    let file_type = ENCODED_FILE_HEADERS
+        .iter()
+        .find(|(_, header, _)| {
+            let hex_header = hex::encode(header);
+            file_data[offset..]
+                .get(..hex_header.len())
+                .is_some_and(|data| data.eq_ignore_ascii_case(hex_header.as_bytes()))
+        })
+        .map(|(file_type, _, _)| *file_type);
+
+    encoded_parser(
+        extract_hex(file_data, offset, None).size,
+        offset,
+        HEX_DESCRIPTION,
+        file_type,
+    )
+}
+
+/// Builds the signature result for encoded data of the given size, as reported by a dry run of its extractor
+fn encoded_parser(
+    encoded_size: Option<usize>,
+    offset: usize,
+    description: &str,
+    file_type: Option<&str>,
+) -> Result<SignatureResult, SignatureError> {
+    match (encoded_size, file_type) {
+        (Some(size), Some(file_type)) => Ok(SignatureResult {
+            offset,
+            size,
+            confidence: CONFIDENCE_MEDIUM,
+            description: format!(
+                "{description}, likely {file_type} data, encoded size: {size} bytes"
+            ),
+            ..Default::default()
+        }),
+        _ => Err(SignatureError),
+    }
+}
@@ -0,0 +1,37 @@
+use crate::signatures::common::{CONFIDENCE_HIGH, SignatureError, SignatureResult};
+use crate::structures::exfat::parse_exfat_header;
+
+/// Human readable description
+pub const DESCRIPTION: &str = "exFAT file system";
+
+/// exFAT boot sectors start with a jump instruction, followed by the file system name
+pub fn exfat_magic() -> Vec<Vec<u8>> {
+    vec![b"\xEB\x76\x90EXFAT   ".to_vec()]
+}
+
+/// Validates the exFAT boot sector
+pub fn exfat_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, SignatureError> {
+    let mut result = SignatureResult {
+        description: DESCRIPTION.to_string(),
+        offset,
+        confidence: CONFIDENCE_HIGH,
+        ..Default::default()
+    };
+
+    if let Ok(exfat_header) = parse_exfat_header(&file_data[offset..]) {
+        // Report the volume length from the boot sector
+        result.size = exfat_header.volume_size;
+        result.description = format!(
+            "{}, revision: {}, serial number: {:#010X}, cluster size: {}, cluster count: {}, total size: {} bytes",
+            result.description,
+            exfat_header.revision,
+            exfat_header.serial_number,
+            exfat_header.cluster_size,
+            exfat_header.cluster_count,
+            result.size
+        );
+        return Ok(result);
+    }
+
+    Err(SignatureError)
+}
@@ -0,0 +1,48 @@
+use crate::signatures::common::{CONFIDENCE_HIGH, SignatureError, SignatureResult};
+use crate::structures::f2fs::{SUPERBLOCK_OFFSET, parse_f2fs_superblock};
+
+/// Human readable description
+pub const DESCRIPTION: &str = "F2FS filesystem";
+
+/// F2FS magic bytes, followed by the major version number
+pub fn f2fs_magic() -> Vec<Vec<u8>> {
+    vec![b"\x10\x20\xF5\xF2\x01\x00".to_vec()]
+}
+
+/// Validates the F2FS superblock and reports the size of the file system
+pub fn f2fs_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, SignatureError> {
+    let mut result = SignatureResult {
+        description: DESCRIPTION.to_string(),
+        confidence: CONFIDENCE_HIGH,
+        ..Default::default()
+    };
+
+    // The magic bytes are at the start of the superblock
+    if offset >= SUPERBLOCK_OFFSET {
+        result.offset = offset - SUPERBLOCK_OFFSET;
+
+        if let Ok(superblock) = parse_f2fs_superblock(&file_data[result.offset..]) {
+            result.size = superblock.image_size;
+
+            let volume_name = match superblock.volume_name.is_empty() {
+                true => "".to_string(),
+                false => format!(", volume name: \"{}\"", superblock.volume_name),
+            };
+
+            result.description = format!(
+                "{}, version {}.{}{}, block size: {}, block count: {}, total size: {} bytes",
+                result.description,
+                superblock.major_version,
+                superblock.minor_version,
+                volume_name,
+                superblock.block_size,
+                superblock.block_count,
+                result.size
+            );
+
+            return Ok(result);
+        }
+    }
+
+    Err(SignatureError)
+}
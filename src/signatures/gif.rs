@@ -28,12 +28,13 @@ pub fn gif_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, Si
         if let Some(total_size) = dry_run.size {
             // Everything looks ok, parse the GIF header to report some info to the user
             if let Ok(gif_header) = parse_gif_header(&file_data[offset..]) {
-                // No sense in extracting a GIF from a file if the GIF data starts at offset 0
-                if offset == 0 {
+                result.size = total_size;
+
+                // No sense in extracting a GIF from a file if the entire file is a GIF
+                if offset == 0 && result.size == file_data.len() {
                     result.extraction_declined = true;
                 }
 
-                result.size = total_size;
                 result.description = format!(
                     "{}, {}x{} pixels, total size: {} bytes",
                     result.description,
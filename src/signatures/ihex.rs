@@ -0,0 +1,50 @@
+use crate::signatures::common::{
+    CONFIDENCE_HIGH, CONFIDENCE_MEDIUM, SignatureError, SignatureResult,
+};
+use crate::structures::ihex::parse_ihex;
+
+/// Human readable descriptions
+pub const IHEX_DESCRIPTION: &str = "Intel HEX";
+pub const IHEX_SHORT_DESCRIPTION: &str = "Intel HEX (generic)";
+
+/// Generic, short signature for Intel HEX files that start with a 16-byte data record, should only be matched at the
+/// beginning of a file
+pub fn ihex_short_magic() -> Vec<Vec<u8>> {
+    vec![b":10".to_vec()]
+}
+
+/// Extended segment and extended linear address records, which typically start an Intel HEX file
+pub fn ihex_magic() -> Vec<Vec<u8>> {
+    vec![b":02000002".to_vec(), b":02000004".to_vec()]
+}
+
+/// Validates an Intel HEX signature
+pub fn ihex_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, SignatureError> {
+    // Images with only a handful of records are more likely to be coincidental
+    const MIN_CONFIDENT_RECORD_COUNT: usize = 2;
+
+    let mut result = SignatureResult {
+        offset,
+        description: IHEX_DESCRIPTION.to_string(),
+        confidence: CONFIDENCE_MEDIUM,
+        ..Default::default()
+    };
+
+    // Every record's checksum is validated, and the records must end with an end of file record
+    if let Ok(ihex_image) = parse_ihex(&file_data[offset..])
+        && let Some((start_address, end_address)) = ihex_image.address_span()
+    {
+        if ihex_image.records.len() >= MIN_CONFIDENT_RECORD_COUNT {
+            result.confidence = CONFIDENCE_HIGH;
+        }
+
+        result.size = ihex_image.size;
+        result.description = format!(
+            "{}, address span: {:#X}-{:#X}, total size: {} bytes",
+            result.description, start_address, end_address, result.size
+        );
+        return Ok(result);
+    }
+
+    Err(SignatureError)
+}
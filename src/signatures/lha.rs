@@ -0,0 +1,61 @@
+use crate::extractors::lha::extract_lha_archive;
+use crate::signatures::common::{CONFIDENCE_MEDIUM, SignatureError, SignatureResult};
+use crate::structures::lha::parse_lha_header;
+
+/// Human readable description
+pub const DESCRIPTION: &str = "LHA archive data";
+
+/// LHA member headers contain the compression method ID, two bytes from the start of the header
+pub fn lha_magic() -> Vec<Vec<u8>> {
+    vec![
+        b"-lh0-".to_vec(),
+        b"-lh1-".to_vec(),
+        b"-lh2-".to_vec(),
+        b"-lh3-".to_vec(),
+        b"-lh4-".to_vec(),
+        b"-lh5-".to_vec(),
+        b"-lh6-".to_vec(),
+        b"-lh7-".to_vec(),
+        b"-lhd-".to_vec(),
+        b"-lz4-".to_vec(),
+        b"-lz5-".to_vec(),
+        b"-lzs-".to_vec(),
+    ]
+}
+
+/// Validates the LHA member headers
+pub fn lha_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, SignatureError> {
+    // Offset of the method ID in the member header
+    const MAGIC_OFFSET: usize = 2;
+
+    let mut result = SignatureResult {
+        description: DESCRIPTION.to_string(),
+        confidence: CONFIDENCE_MEDIUM,
+        ..Default::default()
+    };
+
+    if offset >= MAGIC_OFFSET {
+        result.offset = offset - MAGIC_OFFSET;
+
+        // Walk the member headers to determine the size of the archive
+        let dry_run = extract_lha_archive(file_data, result.offset, None);
+
+        if dry_run.success
+            && let Some(archive_size) = dry_run.size
+            && let Ok(lha_header) = parse_lha_header(&file_data[result.offset..])
+        {
+            result.size = archive_size;
+            result.description = format!(
+                "{}, header level {}, first member: {}, compression method: {}, total size: {} bytes",
+                result.description,
+                lha_header.level,
+                lha_header.name,
+                lha_header.method,
+                result.size
+            );
+            return Ok(result);
+        }
+    }
+
+    Err(SignatureError)
+}
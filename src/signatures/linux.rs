@@ -3,7 +3,7 @@ use crate::signatures::common::{
     CONFIDENCE_LOW, CONFIDENCE_MEDIUM, SignatureError, SignatureResult,
 };
 use crate::structures::linux::{
-    parse_linux_arm_zimage_header, parse_linux_arm64_boot_image_header,
+    parse_linux_arm_zimage_header, parse_linux_arm64_boot_image_header, parse_linux_bzimage_header,
 };
 use aho_corasick::AhoCorasick;
 
@@ -55,6 +55,12 @@ pub fn linux_arm_zimage_parser(
                     "{}, {} endian",
                     result.description, zimage_header.endianness
                 );
+
+                // The image size is only reported if the image is not truncated
+                if zimage_header.image_size <= zimage_data.len() {
+                    result.size = zimage_header.image_size;
+                }
+
                 return Ok(result);
             }
         }
@@ -100,29 +106,21 @@ pub fn linux_boot_image_parser(
     file_data: &[u8],
     offset: usize,
 ) -> Result<SignatureResult, SignatureError> {
-    // There should be the string "!HdrS" 514 bytes from the start of the magic signature
-    const HDRS_OFFSET: usize = 514;
-    const HDRS_EXPECTED_VALUE: &str = "!HdrS";
-
-    let result = SignatureResult {
+    let mut result = SignatureResult {
         description: LINUX_BOOT_IMAGE_DESCRIPTION.to_string(),
         offset,
         size: 0,
         ..Default::default()
     };
 
-    // Calculate start and end offset of the expected !HdrS string
-    let hdrs_start: usize = offset + HDRS_OFFSET;
-    let hdrs_end: usize = hdrs_start + HDRS_EXPECTED_VALUE.len();
-
-    if let Some(hdrs_bytes) = file_data.get(hdrs_start..hdrs_end) {
-        // Get the string that should equal HDRS_EXPECTED_VALUE
-        if let Ok(actual_hdrs_value) = String::from_utf8(hdrs_bytes.to_vec()) {
-            // Validate that the hdrs string matches
-            if actual_hdrs_value == HDRS_EXPECTED_VALUE {
-                return Ok(result);
-            }
+    // The boot sector is followed by the setup header, which starts with a jump instruction and the "HdrS" magic
+    if let Ok(bzimage_header) = parse_linux_bzimage_header(&file_data[offset..]) {
+        // The image size is only reported if the image is not truncated
+        if offset + bzimage_header.image_size <= file_data.len() {
+            result.size = bzimage_header.image_size;
         }
+
+        return Ok(result);
     }
 
     Err(SignatureError)
@@ -0,0 +1,89 @@
+use crate::signatures::common::{CONFIDENCE_MEDIUM, SignatureError, SignatureResult};
+use crate::structures::minix::{
+    SUPERBLOCK_OFFSET, V2_MAGIC_OFFSET, V3_MAGIC_OFFSET, parse_minix_header,
+};
+
+/// Human readable description
+pub const DESCRIPTION: &str = "Minix filesystem";
+
+/// Minix magic bytes
+pub fn minix_magic() -> Vec<Vec<u8>> {
+    /*
+     * The Minix magic bytes are only a u16, resulting in many false positives.
+     * For v1 and v2 file systems, these magic signatures include the valid and error bits of the state field
+     * which follows the magic bytes; for v3 file systems, they include the padding field which follows the magic bytes.
+     * Both little and big endian file systems are supported.
+     */
+    vec![
+        // v1, 14 character names
+        b"\x7F\x13\x01\x00".to_vec(),
+        b"\x7F\x13\x02\x00".to_vec(),
+        b"\x7F\x13\x03\x00".to_vec(),
+        b"\x13\x7F\x00\x01".to_vec(),
+        b"\x13\x7F\x00\x02".to_vec(),
+        b"\x13\x7F\x00\x03".to_vec(),
+        // v1, 30 character names
+        b"\x8F\x13\x01\x00".to_vec(),
+        b"\x8F\x13\x02\x00".to_vec(),
+        b"\x8F\x13\x03\x00".to_vec(),
+        b"\x13\x8F\x00\x01".to_vec(),
+        b"\x13\x8F\x00\x02".to_vec(),
+        b"\x13\x8F\x00\x03".to_vec(),
+        // v2, 14 character names
+        b"\x68\x24\x01\x00".to_vec(),
+        b"\x68\x24\x02\x00".to_vec(),
+        b"\x68\x24\x03\x00".to_vec(),
+        b"\x24\x68\x00\x01".to_vec(),
+        b"\x24\x68\x00\x02".to_vec(),
+        b"\x24\x68\x00\x03".to_vec(),
+        // v2, 30 character names
+        b"\x78\x24\x01\x00".to_vec(),
+        b"\x78\x24\x02\x00".to_vec(),
+        b"\x78\x24\x03\x00".to_vec(),
+        b"\x24\x78\x00\x01".to_vec(),
+        b"\x24\x78\x00\x02".to_vec(),
+        b"\x24\x78\x00\x03".to_vec(),
+        // v3
+        b"\x5A\x4D\x00\x00".to_vec(),
+        b"\x4D\x5A\x00\x00".to_vec(),
+    ]
+}
+
+/// Parse the Minix signature
+pub fn minix_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, SignatureError> {
+    // Magic bytes of v3 file systems, in either byte order
+    const V3_MAGICS: [&[u8]; 2] = [b"\x5A\x4D", b"\x4D\x5A"];
+
+    let mut result = SignatureResult {
+        description: DESCRIPTION.to_string(),
+        confidence: CONFIDENCE_MEDIUM,
+        ..Default::default()
+    };
+
+    // The magic bytes are at a different offset in v3 superblocks
+    let magic_offset = match file_data.get(offset..offset + 2) {
+        Some(magic) if V3_MAGICS.contains(&magic) => V3_MAGIC_OFFSET,
+        _ => V2_MAGIC_OFFSET,
+    };
+
+    // Magic bytes too close to the start of the file can't be a valid Minix file system
+    if let Some(start) = offset.checked_sub(SUPERBLOCK_OFFSET + magic_offset)
+        && let Ok(minix_header) = parse_minix_header(&file_data[start..])
+    {
+        result.offset = start;
+        result.size = minix_header.image_size;
+        result.description = format!(
+            "{}, version {}, {} endian, inodes: {}, block size: {}, zone count: {}, total size: {} bytes",
+            result.description,
+            minix_header.version,
+            minix_header.endianness,
+            minix_header.inodes_count,
+            minix_header.block_size,
+            minix_header.zones_count,
+            result.size
+        );
+        return Ok(result);
+    }
+
+    Err(SignatureError)
+}
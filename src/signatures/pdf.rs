@@ -1,3 +1,4 @@
+use crate::extractors::pdf::extract_pdf_streams;
 use crate::signatures::common::{SignatureError, SignatureResult};
 
 /// Human readable description
@@ -56,6 +57,21 @@ pub fn pdf_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, Si
                     continue;
                 // There should be a percent character
                 } else if byte == ASCII_PERCENT {
+                    // Do an extraction dry-run to find the end of the PDF
+                    let dry_run = extract_pdf_streams(file_data, offset, None);
+
+                    match dry_run.size {
+                        Some(pdf_size) if dry_run.success => {
+                            result.size = pdf_size;
+                            result.description = format!(
+                                "{}, total size: {} bytes",
+                                result.description, result.size
+                            );
+                        }
+                        // Without a trailer, the PDF is probably truncated; there is nothing to extract
+                        _ => result.extraction_declined = true,
+                    }
+
                     return Ok(result);
                 // Anything else is invalid
                 } else {
@@ -1,5 +1,6 @@
+use crate::extractors::pe::extract_pe_resources;
 use crate::signatures::common::{CONFIDENCE_MEDIUM, SignatureError, SignatureResult};
-use crate::structures::pe::parse_pe_header;
+use crate::structures::pe::{PE_RESOURCE_DIRECTORY, parse_pe_header};
 
 /// Human readable description
 pub const DESCRIPTION: &str = "Windows PE binary";
@@ -32,6 +33,31 @@ pub fn pe_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, Sig
             "{}, machine type: {}",
             result.description, pe_header.machine
         );
+
+        // Do an extraction dry-run to validate the PE image; the reported size includes any overlay data
+        let dry_run = extract_pe_resources(file_data, offset, None);
+
+        match (dry_run.size, pe_header.image_size()) {
+            (Some(pe_size), Some(image_size)) if dry_run.success => {
+                result.size = pe_size;
+                result.description = format!(
+                    "{}, image size: {} bytes, overlay size: {} bytes",
+                    result.description,
+                    image_size,
+                    pe_size - image_size
+                );
+
+                // Nothing to extract if there are no resources, and no overlay data
+                let has_resources = pe_header
+                    .data_directories
+                    .get(PE_RESOURCE_DIRECTORY)
+                    .is_some_and(|resource_table| resource_table.size > 0);
+
+                result.extraction_declined = !has_resources && pe_size == image_size;
+            }
+            _ => result.extraction_declined = true,
+        }
+
         return Ok(result);
     }
 
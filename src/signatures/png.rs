@@ -30,13 +30,14 @@ pub fn png_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, Si
     if dry_run.success {
         // Get the total size of the PNG
         if let Some(png_size) = dry_run.size {
-            // If the start of a file PNG, there's no need to extract it
-            if offset == 0 {
+            // Report signature result
+            result.size = png_size;
+
+            // If this entire file is a PNG, no need to extract it
+            if offset == 0 && result.size == file_data.len() {
                 result.extraction_declined = true;
             }
 
-            // Report signature result
-            result.size = png_size;
             result.description =
                 format!("{}, total size: {} bytes", result.description, result.size);
             return Ok(result);
@@ -1,7 +1,9 @@
 use crate::common::epoch_to_string;
 use crate::extractors::squashfs::{
     squashfs_be_extractor, squashfs_le_extractor, squashfs_v4_be_extractor,
+    squashfs_v4_compression_supported, squashfs_v4_extractor,
 };
+use crate::logging::debug;
 use crate::signatures::common::{CONFIDENCE_HIGH, SignatureError, SignatureResult};
 use crate::structures::squashfs::{parse_squashfs_header, parse_squashfs_uid_entry};
 use std::collections::HashMap;
@@ -92,8 +94,14 @@ pub fn squashfs_parser(file_data: &[u8], offset: usize) -> Result<SignatureResul
                                     [&squashfs_header.compression]
                                     .to_string();
 
-                                // Select the appropriate extractor to use
-                                if squashfs_header.endianness == "little" {
+                                // Select the appropriate extractor to use; SquashFSv4 images are extracted internally where possible
+                                if squashfs_header.major_version == SQUASHFSV4
+                                    && squashfs_v4_compression_supported(
+                                        squashfs_header.compression,
+                                    )
+                                {
+                                    result.preferred_extractor = Some(squashfs_v4_extractor());
+                                } else if squashfs_header.endianness == "little" {
                                     result.preferred_extractor = Some(squashfs_le_extractor());
                                 } else if squashfs_header.major_version == SQUASHFSV4 {
                                     result.preferred_extractor = Some(squashfs_v4_be_extractor());
@@ -115,6 +123,21 @@ pub fn squashfs_parser(file_data: &[u8], offset: usize) -> Result<SignatureResul
                                     create_date
                                 );
 
+                                // Vendor quirks are tolerated, but logged and reported
+                                for quirk in &squashfs_header.quirks {
+                                    debug!(
+                                        "Tolerating nonstandard SquashFS image at offset {offset:#X}: {quirk}"
+                                    );
+                                }
+
+                                if !squashfs_header.quirks.is_empty() {
+                                    result.description = format!(
+                                        "{}, vendor quirks: {}",
+                                        result.description,
+                                        squashfs_header.quirks.join(", ")
+                                    );
+                                }
+
                                 return Ok(result);
                             }
                         }
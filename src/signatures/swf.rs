@@ -0,0 +1,49 @@
+use crate::extractors::swf::swf_decompress;
+use crate::signatures::common::{CONFIDENCE_HIGH, SignatureError, SignatureResult};
+use crate::structures::swf::{SWFCompression, parse_swf_header};
+
+/// Human readable description
+pub const DESCRIPTION: &str = "Compressed Adobe Flash (SWF) file";
+
+/// Zlib and LZMA compressed SWF files start with these magic bytes
+pub fn swf_magic() -> Vec<Vec<u8>> {
+    vec![b"CWS".to_vec(), b"ZWS".to_vec()]
+}
+
+/// Validates compressed SWF files by decompressing them
+pub fn swf_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, SignatureError> {
+    let mut result = SignatureResult {
+        offset,
+        description: DESCRIPTION.to_string(),
+        confidence: CONFIDENCE_HIGH,
+        ..Default::default()
+    };
+
+    if let Ok(swf_header) = parse_swf_header(&file_data[offset..]) {
+        // Decompress the SWF body to validate it and determine the compressed size
+        let dry_run = swf_decompress(file_data, offset, None);
+
+        if dry_run.success
+            && let Some(swf_size) = dry_run.size
+        {
+            let compression = match swf_header.compression {
+                SWFCompression::Zlib => "zlib",
+                SWFCompression::Lzma => "LZMA",
+                SWFCompression::None => "none",
+            };
+
+            result.size = swf_size;
+            result.description = format!(
+                "{}, version {}, compression: {}, uncompressed size: {} bytes, total size: {} bytes",
+                result.description,
+                swf_header.version,
+                compression,
+                swf_header.file_length,
+                result.size
+            );
+            return Ok(result);
+        }
+    }
+
+    Err(SignatureError)
+}
@@ -1,4 +1,6 @@
-use crate::signatures::common::{CONFIDENCE_MEDIUM, SignatureError, SignatureResult};
+use crate::signatures::common::{
+    CONFIDENCE_HIGH, CONFIDENCE_MEDIUM, SignatureError, SignatureResult,
+};
 use crate::structures::uefi::{parse_uefi_capsule_header, parse_uefi_volume_header};
 
 /// Human readable descriptions
@@ -44,6 +46,7 @@ pub fn uefi_volume_parser(
         if let Ok(uefi_volume_header) = parse_uefi_volume_header(&file_data[result.offset..]) {
             // Make sure the volume size is sane
             if file_data.len() >= (result.offset + uefi_volume_header.volume_size) {
+                // Multiple volumes are commonly concatenated together in flash dumps; the volume size separates them
                 result.size = uefi_volume_header.volume_size;
                 result.description = format!(
                     "{}, header CRC: {:#X}, header size: {} bytes, total size: {} bytes",
@@ -52,6 +55,13 @@ pub fn uefi_volume_parser(
                     uefi_volume_header.header_size,
                     uefi_volume_header.volume_size
                 );
+
+                if uefi_volume_header.checksum_valid {
+                    result.confidence = CONFIDENCE_HIGH;
+                } else {
+                    result.description = format!("{}, checksum mismatch", result.description);
+                }
+
                 return Ok(result);
             }
         }
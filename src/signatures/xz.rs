@@ -25,7 +25,7 @@ pub fn xz_parser(file_data: &[u8], offset: usize) -> Result<SignatureResult, Sig
     let mut next_offset = offset;
     let mut previous_offset = None;
     let mut stream_header_count = 0;
-    let available_data = file_data.len() - offset;
+    let available_data = file_data.len();
 
     // XZ streams can be concatenated together, need to process them all to determine the size of an XZ file
     while is_offset_safe(available_data, next_offset, previous_offset) {
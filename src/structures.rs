@@ -99,6 +99,7 @@
 pub mod android_bootimg;
 pub mod androidsparse;
 pub mod apfs;
+pub mod ar;
 pub mod arj;
 pub mod autel;
 pub mod binhdr;
@@ -111,6 +112,7 @@ pub mod cpio;
 pub mod cramfs;
 pub mod csman;
 pub mod deb;
+pub mod der;
 pub mod dkbs;
 pub mod dlink_tlv;
 pub mod dlob;
@@ -121,13 +123,17 @@ pub mod dtb;
 pub mod dxbc;
 pub mod efigpt;
 pub mod elf;
+pub mod exfat;
 pub mod ext;
+pub mod f2fs;
 pub mod fat;
 pub mod gif;
 pub mod gzip;
+pub mod ihex;
 pub mod iso9660;
 pub mod jboot;
 pub mod jffs2;
+pub mod lha;
 pub mod linux;
 pub mod logfs;
 pub mod luks;
@@ -138,11 +144,13 @@ pub mod lzop;
 pub mod matter_ota;
 pub mod mbr;
 pub mod mh01;
+pub mod minix;
 pub mod ntfs;
 pub mod openssl;
 pub mod packimg;
 pub mod pcap;
 pub mod pchrom;
+pub mod pdf;
 pub mod pe;
 pub mod png;
 pub mod qcow;
@@ -155,7 +163,9 @@ pub mod seama;
 pub mod sevenzip;
 pub mod shrs;
 pub mod squashfs;
+pub mod srec;
 pub mod svg;
+pub mod swf;
 pub mod tplink;
 pub mod trx;
 pub mod ubi;
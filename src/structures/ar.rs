@@ -0,0 +1,71 @@
+use crate::structures::common::StructureError;
+
+/// All ar archives start with this global header
+pub const AR_MAGIC: &[u8] = b"!<arch>\n";
+
+/// Size of each ar member header
+pub const AR_MEMBER_HEADER_SIZE: usize = 60;
+
+/// Storage struct for ar member header info
+#[derive(Debug, Clone, Default)]
+pub struct ArMemberHeader {
+    /// The raw name field, with trailing padding removed; may be a GNU (`/N`) or BSD (`#1/len`) long name reference
+    pub name: String,
+    pub mode: usize,
+    /// Size of the member data; member data is padded to a 2-byte boundary, the padding is not included in this size
+    pub data_size: usize,
+}
+
+/// Parses an ar member header
+pub fn parse_ar_member_header(ar_data: &[u8]) -> Result<ArMemberHeader, StructureError> {
+    // Header field offsets; all fields are space-padded ASCII strings
+    const NAME_START: usize = 0;
+    const NAME_END: usize = 16;
+    const MODE_START: usize = 40;
+    const MODE_END: usize = 48;
+    const SIZE_START: usize = 48;
+    const SIZE_END: usize = 58;
+    const END_MARKER_START: usize = 58;
+    const END_MARKER: &[u8] = b"`\n";
+
+    if let Some(header_data) = ar_data.get(0..AR_MEMBER_HEADER_SIZE) {
+        // Every member header ends with a fixed end marker
+        if &header_data[END_MARKER_START..] == END_MARKER {
+            let name = ar_string(&header_data[NAME_START..NAME_END])?;
+            let data_size = ar_decimal(&header_data[SIZE_START..SIZE_END])?;
+
+            // The mode is octal, and is left empty for the GNU symbol and extended name tables
+            let mode_str = ar_string(&header_data[MODE_START..MODE_END])?;
+            let mode = if mode_str.is_empty() {
+                0
+            } else {
+                usize::from_str_radix(&mode_str, 8).map_err(|_| StructureError)?
+            };
+
+            if !name.is_empty() {
+                return Ok(ArMemberHeader {
+                    name,
+                    mode,
+                    data_size,
+                });
+            }
+        }
+    }
+
+    Err(StructureError)
+}
+
+/// Converts a space-padded ASCII field to a string
+fn ar_string(field: &[u8]) -> Result<String, StructureError> {
+    match String::from_utf8(field.to_vec()) {
+        Ok(field_str) => Ok(field_str.trim_end_matches(' ').to_string()),
+        Err(_) => Err(StructureError),
+    }
+}
+
+/// Converts a space-padded ASCII decimal field to an integer
+fn ar_decimal(field: &[u8]) -> Result<usize, StructureError> {
+    ar_string(field)?
+        .parse::<usize>()
+        .map_err(|_| StructureError)
+}
@@ -1,4 +1,4 @@
-use crate::common::{epoch_to_string, get_cstring};
+use crate::common::{crc32, epoch_to_string, get_cstring};
 use crate::structures::common;
 use crate::structures::common::StructureError;
 
@@ -120,3 +120,148 @@ pub fn parse_arj_header(arj_data: &[u8]) -> Result<ARJHeader, StructureError> {
 
     Err(StructureError)
 }
+
+/// ARJ file type of the archive's main header
+pub const ARJ_FILE_TYPE_MAIN_HEADER: usize = 2;
+/// ARJ file type of directories
+pub const ARJ_FILE_TYPE_DIRECTORY: usize = 3;
+/// ARJ flag set for password protected (garbled) members
+pub const ARJ_FLAG_GARBLED: usize = 0x01;
+
+/// Describes an ARJ header block: the main archive header, a member's local file header, or the end of archive marker
+#[derive(Debug, Default, Clone)]
+pub struct ARJBlockHeader {
+    /// Total size of the header block, including any extended headers; a member's data immediately follows
+    pub size: usize,
+    /// True if this is the end of archive marker
+    pub end_of_archive: bool,
+    pub flags: usize,
+    pub compression_method: usize,
+    pub file_type: usize,
+    pub compressed_size: usize,
+    pub uncompressed_size: usize,
+    pub data_crc: usize,
+    pub name: String,
+}
+
+/// Parses an ARJ header block, validating the basic header and extended header CRCs
+pub fn parse_arj_block_header(arj_data: &[u8]) -> Result<ARJBlockHeader, StructureError> {
+    const ARJ_MAGIC: usize = 0xEA60;
+    const MAX_BASIC_HEADER_SIZE: usize = 2600;
+    // Size of the magic bytes and the basic header size field
+    const PREFIX_SIZE: usize = 4;
+    // Size of the header CRC that follows the basic header and each extended header
+    const CRC_SIZE: usize = 4;
+    // Size of the extended header size fields
+    const EXTENDED_HEADER_SIZE_SIZE: usize = 2;
+
+    let prefix_structure = vec![("magic", "u16"), ("basic_header_size", "u16")];
+
+    // ARJ basic header structure (https://www.fileformat.info/format/arj/corion.htm)
+    let basic_header_structure = vec![
+        ("first_header_size", "u8"),
+        ("archiver_version", "u8"),
+        ("min_version", "u8"),
+        ("host_os", "u8"),
+        ("flags", "u8"),
+        ("compression_method", "u8"),
+        ("file_type", "u8"),
+        ("reserved", "u8"),
+        ("datetime", "u32"),
+        ("compressed_size", "u32"),
+        ("original_size", "u32"),
+        ("original_crc", "u32"),
+        ("filespec_position", "u16"),
+        ("file_mode", "u16"),
+        ("host_data", "u16"),
+    ];
+
+    let prefix = common::parse(arj_data, &prefix_structure, "little")?;
+
+    if prefix["magic"] != ARJ_MAGIC || prefix["basic_header_size"] > MAX_BASIC_HEADER_SIZE {
+        return Err(StructureError);
+    }
+
+    // A basic header size of zero marks the end of the archive
+    if prefix["basic_header_size"] == 0 {
+        return Ok(ARJBlockHeader {
+            size: PREFIX_SIZE,
+            end_of_archive: true,
+            ..Default::default()
+        });
+    }
+
+    let basic_header_end = PREFIX_SIZE + prefix["basic_header_size"];
+    let basic_header_data = arj_data
+        .get(PREFIX_SIZE..basic_header_end)
+        .ok_or(StructureError)?;
+    let basic_header_crc = arj_data
+        .get(basic_header_end..basic_header_end + CRC_SIZE)
+        .ok_or(StructureError)?;
+
+    if crc32(basic_header_data) != u32::from_le_bytes(basic_header_crc.try_into().unwrap()) {
+        return Err(StructureError);
+    }
+
+    let basic_header = common::parse(basic_header_data, &basic_header_structure, "little")?;
+
+    if basic_header["first_header_size"] < common::size(&basic_header_structure)
+        || basic_header["first_header_size"] > basic_header_data.len()
+    {
+        return Err(StructureError);
+    }
+
+    // The file name follows the first header
+    let name_data = &basic_header_data[basic_header["first_header_size"]..];
+    let name = String::from_utf8_lossy(
+        name_data
+            .split(|byte| *byte == 0)
+            .next()
+            .unwrap_or_default(),
+    )
+    .to_string();
+
+    // Extended headers follow the basic header CRC; each has a size and CRC, and a size of zero marks the end
+    let mut header_size = basic_header_end + CRC_SIZE;
+
+    loop {
+        let extended_header_size = arj_data
+            .get(header_size..header_size + EXTENDED_HEADER_SIZE_SIZE)
+            .ok_or(StructureError)?;
+        let extended_header_size =
+            u16::from_le_bytes(extended_header_size.try_into().unwrap()) as usize;
+
+        header_size += EXTENDED_HEADER_SIZE_SIZE;
+
+        if extended_header_size == 0 {
+            break;
+        }
+
+        let extended_header_data = arj_data
+            .get(header_size..header_size + extended_header_size)
+            .ok_or(StructureError)?;
+        let extended_header_crc = arj_data
+            .get(header_size + extended_header_size..header_size + extended_header_size + CRC_SIZE)
+            .ok_or(StructureError)?;
+
+        if crc32(extended_header_data)
+            != u32::from_le_bytes(extended_header_crc.try_into().unwrap())
+        {
+            return Err(StructureError);
+        }
+
+        header_size += extended_header_size + CRC_SIZE;
+    }
+
+    Ok(ARJBlockHeader {
+        size: header_size,
+        end_of_archive: false,
+        flags: basic_header["flags"],
+        compression_method: basic_header["compression_method"],
+        file_type: basic_header["file_type"],
+        compressed_size: basic_header["compressed_size"],
+        uncompressed_size: basic_header["original_size"],
+        data_crc: basic_header["original_crc"],
+        name,
+    })
+}
@@ -1,4 +1,4 @@
-use log::error;
+use crate::logging::error;
 use std::collections::HashMap;
 
 /*
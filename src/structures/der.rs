@@ -0,0 +1,374 @@
+use crate::structures::common::StructureError;
+
+/// ASN.1 universal tags used by certificates and keys
+pub const DER_INTEGER: u8 = 0x02;
+pub const DER_BIT_STRING: u8 = 0x03;
+pub const DER_OCTET_STRING: u8 = 0x04;
+pub const DER_OBJECT_IDENTIFIER: u8 = 0x06;
+pub const DER_SEQUENCE: u8 = 0x30;
+pub const DER_SET: u8 = 0x31;
+const DER_UTC_TIME: u8 = 0x17;
+const DER_GENERALIZED_TIME: u8 = 0x18;
+
+/// Stores info about a DER encoded element
+#[derive(Debug, Clone, Default)]
+pub struct DERElement {
+    pub tag: u8,
+    /// Size of the tag and length fields
+    pub header_size: usize,
+    /// Size of the element's contents
+    pub data_size: usize,
+}
+
+impl DERElement {
+    /// Total size of the element, including the tag and length fields
+    pub fn size(&self) -> usize {
+        self.header_size + self.data_size
+    }
+
+    /// Returns true if the element contains other DER elements
+    pub fn is_constructed(&self) -> bool {
+        (self.tag & 0x20) != 0
+    }
+}
+
+/// Stores info about an X.509 certificate
+#[derive(Debug, Clone, Default)]
+pub struct X509Certificate {
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: String,
+    pub not_after: String,
+}
+
+/// Stores info about a DER encoded key
+#[derive(Debug, Clone, Default)]
+pub struct DERKey {
+    /// Key algorithm name, e.g. "RSA"
+    pub algorithm: String,
+    pub private: bool,
+}
+
+/// Parses the tag and length of a DER element; the element's contents must be present in `der_data`.
+/// Only single-byte tags and definite lengths are valid in DER.
+pub fn parse_der_element(der_data: &[u8]) -> Result<DERElement, StructureError> {
+    // Long form lengths of more than 4 bytes are not reasonable for certificates and keys
+    const MAX_LENGTH_BYTES: usize = 4;
+    const HIGH_TAG_NUMBER: u8 = 0x1F;
+    const LONG_FORM_LENGTH: u8 = 0x80;
+
+    let tag = *der_data.first().ok_or(StructureError)?;
+    let length_byte = *der_data.get(1).ok_or(StructureError)?;
+
+    if (tag & HIGH_TAG_NUMBER) == HIGH_TAG_NUMBER {
+        return Err(StructureError);
+    }
+
+    let mut element = DERElement {
+        tag,
+        header_size: 2,
+        data_size: length_byte as usize,
+    };
+
+    if (length_byte & LONG_FORM_LENGTH) != 0 {
+        let length_size = (length_byte & !LONG_FORM_LENGTH) as usize;
+
+        // A length size of 0 is the BER indefinite length form
+        if length_size == 0 || length_size > MAX_LENGTH_BYTES {
+            return Err(StructureError);
+        }
+
+        let length_bytes = der_data.get(2..2 + length_size).ok_or(StructureError)?;
+
+        // DER requires lengths to be encoded in the minimum number of bytes
+        if length_bytes[0] == 0 {
+            return Err(StructureError);
+        }
+
+        element.header_size += length_size;
+        element.data_size = length_bytes
+            .iter()
+            .fold(0, |size, byte| (size << 8) | (*byte as usize));
+
+        if element.data_size < (LONG_FORM_LENGTH as usize) {
+            return Err(StructureError);
+        }
+    }
+
+    if element.size() > der_data.len() {
+        return Err(StructureError);
+    }
+
+    Ok(element)
+}
+
+/// Returns the total size of the DER element at the start of `der_data`, after validating that every constructed
+/// element within it is made up of well-formed child elements that exactly fill it.
+pub fn parse_der_size(der_data: &[u8]) -> Result<usize, StructureError> {
+    // Certificates and keys are not nested anywhere near this deep
+    const MAX_DEPTH: usize = 32;
+
+    fn validate(der_data: &[u8], depth: usize) -> Result<usize, StructureError> {
+        let element = parse_der_element(der_data)?;
+
+        if element.is_constructed() {
+            if depth >= MAX_DEPTH {
+                return Err(StructureError);
+            }
+
+            let mut child_offset = element.header_size;
+
+            while child_offset < element.size() {
+                child_offset += validate(&der_data[child_offset..element.size()], depth + 1)?;
+            }
+        }
+
+        Ok(element.size())
+    }
+
+    validate(der_data, 0)
+}
+
+/// Returns the child elements of the constructed DER element at the start of `der_data`, each including its header
+pub fn der_children(der_data: &[u8]) -> Result<Vec<&[u8]>, StructureError> {
+    let mut children = vec![];
+
+    let element = parse_der_element(der_data)?;
+
+    if !element.is_constructed() {
+        return Err(StructureError);
+    }
+
+    let contents = &der_data[element.header_size..element.size()];
+    let mut child_offset: usize = 0;
+
+    while child_offset < contents.len() {
+        let child = parse_der_element(&contents[child_offset..])?;
+        children.push(&contents[child_offset..child_offset + child.size()]);
+        child_offset += child.size();
+    }
+
+    Ok(children)
+}
+
+/// Parses the subject, issuer and validity period of a DER encoded X.509 certificate
+pub fn parse_x509_certificate(der_data: &[u8]) -> Result<X509Certificate, StructureError> {
+    // Optional explicitly tagged version field at the start of the TBSCertificate
+    const VERSION_TAG: u8 = 0xA0;
+
+    // Certificate ::= SEQUENCE { tbsCertificate, signatureAlgorithm, signatureValue }
+    let certificate = der_children(der_data)?;
+
+    if certificate.len() != 3
+        || certificate[0][0] != DER_SEQUENCE
+        || certificate[1][0] != DER_SEQUENCE
+        || certificate[2][0] != DER_BIT_STRING
+    {
+        return Err(StructureError);
+    }
+
+    let mut tbs_certificate = der_children(certificate[0])?;
+
+    if tbs_certificate.first().map(|field| field[0]) == Some(VERSION_TAG) {
+        tbs_certificate.remove(0);
+    }
+
+    // serialNumber, signature, issuer, validity, subject, ...
+    if tbs_certificate.len() < 5
+        || tbs_certificate[0][0] != DER_INTEGER
+        || tbs_certificate[1..5]
+            .iter()
+            .any(|field| field[0] != DER_SEQUENCE)
+    {
+        return Err(StructureError);
+    }
+
+    let validity = der_children(tbs_certificate[3])?;
+
+    if validity.len() != 2
+        || validity
+            .iter()
+            .any(|time| time[0] != DER_UTC_TIME && time[0] != DER_GENERALIZED_TIME)
+    {
+        return Err(StructureError);
+    }
+
+    Ok(X509Certificate {
+        issuer: der_name(tbs_certificate[2])?,
+        subject: der_name(tbs_certificate[4])?,
+        not_before: der_time(validity[0])?,
+        not_after: der_time(validity[1])?,
+    })
+}
+
+/// Identifies a DER encoded RSA (PKCS#1) private key, PKCS#8 private key, or SubjectPublicKeyInfo public key
+pub fn parse_der_key(der_data: &[u8]) -> Result<DERKey, StructureError> {
+    // RSAPrivateKey ::= SEQUENCE { version, modulus, publicExponent, privateExponent, prime1, prime2, exponent1,
+    // exponent2, coefficient }
+    const RSA_PRIVATE_KEY_FIELDS: usize = 9;
+
+    let key = der_children(der_data)?;
+    let tags: Vec<u8> = key.iter().map(|field| field[0]).collect();
+
+    if tags.len() == RSA_PRIVATE_KEY_FIELDS && tags.iter().all(|tag| *tag == DER_INTEGER) {
+        return Ok(DERKey {
+            algorithm: "RSA".to_string(),
+            private: true,
+        });
+    }
+
+    // PrivateKeyInfo ::= SEQUENCE { version, privateKeyAlgorithm, privateKey, ... }
+    if tags.len() >= 3 && tags[..3] == [DER_INTEGER, DER_SEQUENCE, DER_OCTET_STRING] {
+        return Ok(DERKey {
+            algorithm: der_algorithm_name(key[1])?,
+            private: true,
+        });
+    }
+
+    // SubjectPublicKeyInfo ::= SEQUENCE { algorithm, subjectPublicKey }
+    if tags == [DER_SEQUENCE, DER_BIT_STRING] {
+        return Ok(DERKey {
+            algorithm: der_algorithm_name(key[0])?,
+            private: false,
+        });
+    }
+
+    Err(StructureError)
+}
+
+/// Returns the contents of a primitive DER element
+fn der_contents(der_data: &[u8]) -> Result<&[u8], StructureError> {
+    let element = parse_der_element(der_data)?;
+    Ok(&der_data[element.header_size..element.size()])
+}
+
+/// Converts a DER encoded object identifier to its dotted string representation
+fn der_oid(der_data: &[u8]) -> Result<String, StructureError> {
+    if der_data.first() != Some(&DER_OBJECT_IDENTIFIER) {
+        return Err(StructureError);
+    }
+
+    let oid_bytes = der_contents(der_data)?;
+    let first_byte = *oid_bytes.first().ok_or(StructureError)? as usize;
+
+    // The first two components are encoded in the first byte; the first component is at most 2
+    let first_component = (first_byte / 40).min(2);
+    let mut components: Vec<usize> = vec![first_component, first_byte - (first_component * 40)];
+    let mut component: usize = 0;
+
+    // Remaining components are base-128 encoded, with the high bit set on all but the last byte
+    for byte in &oid_bytes[1..] {
+        component = component.checked_mul(128).ok_or(StructureError)? + (*byte & 0x7F) as usize;
+
+        if (*byte & 0x80) == 0 {
+            components.push(component);
+            component = 0;
+        }
+    }
+
+    Ok(components
+        .iter()
+        .map(|component| component.to_string())
+        .collect::<Vec<String>>()
+        .join("."))
+}
+
+/// Returns a name for the algorithm identified in an AlgorithmIdentifier sequence
+fn der_algorithm_name(der_data: &[u8]) -> Result<String, StructureError> {
+    let algorithm_identifier = der_children(der_data)?;
+    let oid = der_oid(algorithm_identifier.first().ok_or(StructureError)?)?;
+
+    let name = match oid.as_str() {
+        "1.2.840.113549.1.1.1" => "RSA",
+        "1.2.840.10040.4.1" => "DSA",
+        "1.2.840.10045.2.1" => "EC",
+        "1.3.101.112" => "Ed25519",
+        "1.3.101.113" => "Ed448",
+        "1.3.101.110" => "X25519",
+        "1.3.101.111" => "X448",
+        _ => return Ok(oid),
+    };
+
+    Ok(name.to_string())
+}
+
+/// Converts a DER encoded X.509 name to a string, e.g. "CN=example.com, O=Example"
+fn der_name(der_data: &[u8]) -> Result<String, StructureError> {
+    let mut attributes: Vec<String> = vec![];
+
+    // Name ::= SEQUENCE OF SET OF SEQUENCE { type, value }
+    for relative_name in der_children(der_data)? {
+        if relative_name[0] != DER_SET {
+            return Err(StructureError);
+        }
+
+        for attribute in der_children(relative_name)? {
+            let attribute_fields = der_children(attribute)?;
+
+            if attribute_fields.len() != 2 {
+                return Err(StructureError);
+            }
+
+            let oid = der_oid(attribute_fields[0])?;
+            let attribute_type = match oid.as_str() {
+                "2.5.4.3" => "CN",
+                "2.5.4.6" => "C",
+                "2.5.4.7" => "L",
+                "2.5.4.8" => "ST",
+                "2.5.4.10" => "O",
+                "2.5.4.11" => "OU",
+                "1.2.840.113549.1.9.1" => "emailAddress",
+                _ => &oid,
+            };
+
+            let value = String::from_utf8_lossy(der_contents(attribute_fields[1])?);
+            attributes.push(format!("{attribute_type}={value}"));
+        }
+    }
+
+    Ok(attributes.join(", "))
+}
+
+/// Converts a DER encoded UTCTime or GeneralizedTime to a string, e.g. "2024-01-31 23:59:59 UTC"
+fn der_time(der_data: &[u8]) -> Result<String, StructureError> {
+    // UTCTime two-digit years below this are in the 21st century
+    const UTC_TIME_CENTURY_CUTOFF: usize = 50;
+
+    let time = String::from_utf8(der_contents(der_data)?.to_vec()).map_err(|_| StructureError)?;
+
+    let (year, rest) = if der_data[0] == DER_UTC_TIME {
+        let year: usize = time
+            .get(0..2)
+            .ok_or(StructureError)?
+            .parse()
+            .map_err(|_| StructureError)?;
+        match year < UTC_TIME_CENTURY_CUTOFF {
+            true => (2000 + year, &time[2..]),
+            false => (1900 + year, &time[2..]),
+        }
+    } else {
+        let year: usize = time
+            .get(0..4)
+            .ok_or(StructureError)?
+            .parse()
+            .map_err(|_| StructureError)?;
+        (year, time.get(4..).ok_or(StructureError)?)
+    };
+
+    // MMDDHHMMSS, followed by a time zone
+    let fields: Vec<&str> = (0..5)
+        .map(|i| rest.get(i * 2..i * 2 + 2).ok_or(StructureError))
+        .collect::<Result<Vec<&str>, StructureError>>()?;
+
+    if fields
+        .iter()
+        .any(|field| !field.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return Err(StructureError);
+    }
+
+    Ok(format!(
+        "{}-{}-{} {}:{}:{} UTC",
+        year, fields[0], fields[1], fields[2], fields[3], fields[4]
+    ))
+}
@@ -1,11 +1,54 @@
 use crate::structures::common::{self, StructureError};
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
 
-/// Struct to store DMG footer info
+/// Chunk types used in DMG block tables
+pub const DMG_CHUNK_ZERO_FILL: usize = 0x00000000;
+pub const DMG_CHUNK_RAW: usize = 0x00000001;
+pub const DMG_CHUNK_IGNORED: usize = 0x00000002;
+pub const DMG_CHUNK_ADC: usize = 0x80000004;
+pub const DMG_CHUNK_ZLIB: usize = 0x80000005;
+pub const DMG_CHUNK_BZIP2: usize = 0x80000006;
+pub const DMG_CHUNK_LZFSE: usize = 0x80000007;
+pub const DMG_CHUNK_LZMA: usize = 0x80000008;
+pub const DMG_CHUNK_COMMENT: usize = 0x7FFFFFFE;
+pub const DMG_CHUNK_TERMINATOR: usize = 0xFFFFFFFF;
+
+/// DMG images are divided into 512-byte sectors
+pub const DMG_SECTOR_SIZE: usize = 512;
+
+/// Struct to store DMG footer info; all offsets are relative to the start of the DMG
 #[derive(Debug, Default, Clone)]
 pub struct DMGFooter {
     pub footer_size: usize,
+    pub data_offset: usize,
     pub data_length: usize,
+    pub xml_offset: usize,
     pub xml_length: usize,
+    pub rsrc_offset: usize,
+    pub rsrc_length: usize,
+}
+
+/// Struct to store a DMG block table ("mish" block), which describes how to reconstruct one partition of the disk image
+#[derive(Debug, Default, Clone)]
+pub struct DMGBlockTable {
+    /// First sector of the partition in the disk image
+    pub sector_number: usize,
+    /// Offset of the partition's chunk data, relative to the start of the data fork
+    pub data_offset: usize,
+    pub chunks: Vec<DMGChunk>,
+}
+
+/// Struct to store a DMG block table chunk entry
+#[derive(Debug, Default, Clone)]
+pub struct DMGChunk {
+    pub chunk_type: usize,
+    /// First sector of the chunk, relative to the start of the partition
+    pub sector_number: usize,
+    pub sector_count: usize,
+    /// Offset of the chunk's compressed data, relative to the block table's data offset
+    pub compressed_offset: usize,
+    pub compressed_length: usize,
 }
 
 /// Parses a DMG footer structure
@@ -124,8 +167,12 @@ pub fn parse_dmg_footer(dmg_data: &[u8]) -> Result<DMGFooter, StructureError> {
         // Sanity check, make sure the reported header size is the size of this structure
         if dmg_footer["header_size"] == structure_size {
             return Ok(DMGFooter {
+                data_offset: dmg_footer["data_fork_offset"],
                 data_length: dmg_footer["data_fork_length"],
+                xml_offset: dmg_footer["xml_offset"],
                 xml_length: dmg_footer["xml_length"],
+                rsrc_offset: dmg_footer["rsrc_fork_offset"],
+                rsrc_length: dmg_footer["rsrc_fork_length"],
                 footer_size: structure_size,
             });
         }
@@ -133,3 +180,223 @@ pub fn parse_dmg_footer(dmg_data: &[u8]) -> Result<DMGFooter, StructureError> {
 
     Err(StructureError)
 }
+
+/// Parses a DMG block table ("mish" block)
+pub fn parse_dmg_block_table(mish_data: &[u8]) -> Result<DMGBlockTable, StructureError> {
+    const MISH_MAGIC: usize = 0x6D697368;
+    // Size of the checksum type, checksum size, and 32 checksum words
+    const CHECKSUM_SIZE: usize = 136;
+
+    let mish_structure = vec![
+        ("magic", "u32"),
+        ("version", "u32"),
+        ("sector_number", "u64"),
+        ("sector_count", "u64"),
+        ("data_offset", "u64"),
+        ("buffers_needed", "u32"),
+        ("block_descriptors", "u32"),
+        ("reserved_1", "u32"),
+        ("reserved_2", "u32"),
+        ("reserved_3", "u32"),
+        ("reserved_4", "u32"),
+        ("reserved_5", "u32"),
+        ("reserved_6", "u32"),
+    ];
+
+    let chunk_count_structure = vec![("chunk_count", "u32")];
+
+    let chunk_structure = vec![
+        ("chunk_type", "u32"),
+        ("comment", "u32"),
+        ("sector_number", "u64"),
+        ("sector_count", "u64"),
+        ("compressed_offset", "u64"),
+        ("compressed_length", "u64"),
+    ];
+
+    let mish_header = common::parse(mish_data, &mish_structure, "big")?;
+
+    if mish_header["magic"] != MISH_MAGIC {
+        return Err(StructureError);
+    }
+
+    let chunk_count_offset = common::size(&mish_structure) + CHECKSUM_SIZE;
+    let chunk_count = common::parse(
+        mish_data.get(chunk_count_offset..).ok_or(StructureError)?,
+        &chunk_count_structure,
+        "big",
+    )?["chunk_count"];
+
+    let mut block_table = DMGBlockTable {
+        sector_number: mish_header["sector_number"],
+        data_offset: mish_header["data_offset"],
+        chunks: vec![],
+    };
+
+    let chunk_size = common::size(&chunk_structure);
+    let chunks_start = chunk_count_offset + common::size(&chunk_count_structure);
+
+    for i in 0..chunk_count {
+        let chunk_data = mish_data
+            .get(chunks_start + (i * chunk_size)..)
+            .ok_or(StructureError)?;
+        let chunk = common::parse(chunk_data, &chunk_structure, "big")?;
+
+        if chunk["chunk_type"] == DMG_CHUNK_TERMINATOR {
+            break;
+        }
+
+        block_table.chunks.push(DMGChunk {
+            chunk_type: chunk["chunk_type"],
+            sector_number: chunk["sector_number"],
+            sector_count: chunk["sector_count"],
+            compressed_offset: chunk["compressed_offset"],
+            compressed_length: chunk["compressed_length"],
+        });
+    }
+
+    Ok(block_table)
+}
+
+/// Parses a classic Mac OS resource fork, as used by older DMG images, and returns the data of each "blkx" resource
+pub fn parse_dmg_resource_fork(rsrc_data: &[u8]) -> Result<Vec<Vec<u8>>, StructureError> {
+    const BLKX_TYPE: usize = 0x626C6B78;
+    // Resource data offsets are 24 bits
+    const DATA_OFFSET_MASK: usize = 0x00FFFFFF;
+
+    let header_structure = vec![
+        ("data_offset", "u32"),
+        ("map_offset", "u32"),
+        ("data_length", "u32"),
+        ("map_length", "u32"),
+    ];
+
+    let map_structure = vec![
+        ("header_copy_1", "u64"),
+        ("header_copy_2", "u64"),
+        ("next_map", "u32"),
+        ("file_reference", "u16"),
+        ("attributes", "u16"),
+        ("type_list_offset", "u16"),
+        ("name_list_offset", "u16"),
+    ];
+
+    let type_count_structure = vec![("type_count", "u16")];
+
+    let type_structure = vec![
+        ("resource_type", "u32"),
+        ("resource_count", "u16"),
+        ("reference_list_offset", "u16"),
+    ];
+
+    let reference_structure = vec![
+        ("id", "u16"),
+        ("name_offset", "u16"),
+        ("data_offset", "u32"),
+        ("handle", "u32"),
+    ];
+
+    let data_length_structure = vec![("length", "u32")];
+
+    let header = common::parse(rsrc_data, &header_structure, "big")?;
+    let map_data = rsrc_data
+        .get(header["map_offset"]..)
+        .ok_or(StructureError)?;
+    let resource_map = common::parse(map_data, &map_structure, "big")?;
+
+    // The type list starts with the type count; type and reference counts are stored as the count minus one
+    let type_list = map_data
+        .get(resource_map["type_list_offset"]..)
+        .ok_or(StructureError)?;
+    let type_count = common::parse(type_list, &type_count_structure, "big")?["type_count"];
+    let type_size = common::size(&type_structure);
+
+    let mut resources: Vec<Vec<u8>> = vec![];
+
+    for i in 0..=type_count {
+        let type_entry = common::parse(
+            type_list
+                .get(common::size(&type_count_structure) + (i * type_size)..)
+                .ok_or(StructureError)?,
+            &type_structure,
+            "big",
+        )?;
+
+        if type_entry["resource_type"] != BLKX_TYPE {
+            continue;
+        }
+
+        let reference_size = common::size(&reference_structure);
+
+        for j in 0..=type_entry["resource_count"] {
+            let reference = common::parse(
+                type_list
+                    .get(type_entry["reference_list_offset"] + (j * reference_size)..)
+                    .ok_or(StructureError)?,
+                &reference_structure,
+                "big",
+            )?;
+
+            // Each resource's data is prefixed with its length
+            let resource_offset =
+                header["data_offset"] + (reference["data_offset"] & DATA_OFFSET_MASK);
+            let resource_length = common::parse(
+                rsrc_data.get(resource_offset..).ok_or(StructureError)?,
+                &data_length_structure,
+                "big",
+            )?["length"];
+
+            let resource_start = resource_offset + common::size(&data_length_structure);
+            let resource_data = rsrc_data
+                .get(resource_start..resource_start + resource_length)
+                .ok_or(StructureError)?;
+
+            resources.push(resource_data.to_vec());
+        }
+    }
+
+    Ok(resources)
+}
+
+/// Parses a DMG XML property list and returns the decoded data of each "blkx" resource
+pub fn parse_dmg_xml_resources(xml_data: &[u8]) -> Result<Vec<Vec<u8>>, StructureError> {
+    const BLKX_KEY: &str = "<key>blkx</key>";
+    const ARRAY_END: &str = "</array>";
+    const DATA_KEY: &str = "<key>Data</key>";
+    const DATA_START: &str = "<data>";
+    const DATA_END: &str = "</data>";
+
+    let xml = String::from_utf8_lossy(xml_data);
+
+    // The blkx resources are dictionaries in the array that follows the blkx key
+    let blkx_start = xml.find(BLKX_KEY).ok_or(StructureError)? + BLKX_KEY.len();
+    let blkx_end = blkx_start + xml[blkx_start..].find(ARRAY_END).ok_or(StructureError)?;
+    let mut blkx_xml = &xml[blkx_start..blkx_end];
+
+    let mut resources: Vec<Vec<u8>> = vec![];
+
+    while let Some(data_key_start) = blkx_xml.find(DATA_KEY) {
+        let after_key = &blkx_xml[data_key_start + DATA_KEY.len()..];
+        let data_start = after_key.find(DATA_START).ok_or(StructureError)? + DATA_START.len();
+        let data_end = data_start
+            + after_key[data_start..]
+                .find(DATA_END)
+                .ok_or(StructureError)?;
+
+        // Base64 data is wrapped across multiple lines
+        let base64_data: String = after_key[data_start..data_end]
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace())
+            .collect();
+
+        resources.push(
+            BASE64_STANDARD
+                .decode(base64_data)
+                .map_err(|_| StructureError)?,
+        );
+
+        blkx_xml = &after_key[data_end + DATA_END.len()..];
+    }
+
+    Ok(resources)
+}
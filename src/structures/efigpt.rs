@@ -1,17 +1,113 @@
-use crate::common::{crc32, is_offset_safe};
+use crate::common::{crc32, guid_to_string};
 use crate::structures::common::{self, StructureError};
 
-const BLOCK_SIZE: usize = 512;
+/// Logical block size; the primary GPT header is always located in the second block of the disk image
+pub const GPT_BLOCK_SIZE: usize = 512;
+
+/// Describes a single GPT partition
+#[derive(Debug, Default, Clone)]
+pub struct GPTPartition {
+    /// Offset of the partition, relative to the start of the disk image
+    pub start: usize,
+    pub size: usize,
+    /// Partition type GUID, e.g. "C12A7328-F81F-11D2-BA4B-00A0C93EC93B"
+    pub type_guid: String,
+    /// Human readable name of the partition type, or the partition type GUID if the type is not known
+    pub type_name: String,
+    /// Partition name, may be empty
+    pub name: String,
+}
 
 /// Struct to store EFI GPT header info
 #[derive(Debug, Default, Clone)]
 pub struct EFIGPTHeader {
+    /// Size of the disk image: the end of the farthest away partition, or of the backup GPT header, if it is present
     pub total_size: usize,
+    pub disk_guid: String,
+    pub partitions: Vec<GPTPartition>,
+    /// True if the primary GPT header and its partition entries passed CRC validation
+    pub primary_crc_valid: bool,
+    /// True if the backup GPT header and its partition entries passed CRC validation; None if there is no backup
+    /// GPT header within the available data
+    pub backup_crc_valid: Option<bool>,
+    /// True if the partitions were read from the backup GPT, because the primary GPT failed CRC validation
+    pub used_backup: bool,
 }
 
-/// Parses an EFI GPT header
+/// Parses an EFI GPT, starting with the protective MBR, and validates the CRCs of both the primary and backup GPTs.
+///
+/// If the primary GPT fails CRC validation, the backup GPT is used instead, provided that it is valid. If neither is
+/// valid, the primary GPT is used regardless; callers should check `primary_crc_valid` and `backup_crc_valid`.
 pub fn parse_efigpt_header(efi_data: &[u8]) -> Result<EFIGPTHeader, StructureError> {
-    const EXPTECTED_REVISION: usize = 0x00010000;
+    // The primary GPT header is located in LBA 1; this also serves to locate the backup GPT header
+    let primary = parse_gpt_table(efi_data, 1)?;
+
+    let backup = match primary.alternate_lba {
+        0 | 1 => None,
+        alternate_lba => parse_gpt_table(efi_data, alternate_lba).ok(),
+    };
+
+    let mut result = EFIGPTHeader {
+        primary_crc_valid: primary.crc_valid,
+        backup_crc_valid: backup.as_ref().map(|backup| backup.crc_valid),
+        ..Default::default()
+    };
+
+    let gpt_table = match backup {
+        Some(backup) if backup.crc_valid && !primary.crc_valid => {
+            result.used_backup = true;
+            backup
+        }
+        _ => primary,
+    };
+
+    result.disk_guid = gpt_table.disk_guid;
+    result.partitions = gpt_table.partitions;
+
+    // The backup GPT header, if present, is in the last block of the disk image
+    if result.backup_crc_valid.is_some() {
+        result.total_size = lba_to_offset(gpt_table.last_lba) + GPT_BLOCK_SIZE;
+    }
+
+    for partition in &result.partitions {
+        result.total_size = result
+            .total_size
+            .max(partition.start.saturating_add(partition.size));
+    }
+
+    if result.partitions.is_empty() {
+        return Err(StructureError);
+    }
+
+    Ok(result)
+}
+
+/// A GPT header and its partition entries
+#[derive(Debug, Default, Clone)]
+struct GPTTable {
+    disk_guid: String,
+    /// LBA of the other (primary or backup) GPT header
+    alternate_lba: usize,
+    /// LBA of the backup GPT header, which is the last block of the disk
+    last_lba: usize,
+    partitions: Vec<GPTPartition>,
+    /// True if both the header and the partition entries CRCs are valid
+    crc_valid: bool,
+}
+
+/// Parses the GPT header located at the specified LBA, and its partition entries
+fn parse_gpt_table(efi_data: &[u8], lba: usize) -> Result<GPTTable, StructureError> {
+    const EXPECTED_REVISION: usize = 0x00010000;
+    const GPT_MAGIC: usize = 0x5452415020494645;
+    // Offset and size of the header CRC field, which is zeroed when calculating the header CRC
+    const HEADER_CRC_OFFSET: usize = 16;
+    const HEADER_CRC_SIZE: usize = 4;
+    // Minimum size of a partition entry
+    const MIN_ENTRY_SIZE: usize = 128;
+    // Sanity limit on the number of partition entries
+    const MAX_ENTRY_COUNT: usize = 4096;
+    // The protective MBR and primary GPT header occupy the first two blocks, which may not be used by partitions
+    const MIN_FIRST_USABLE_LBA: usize = 2;
 
     // https://uefi.org/sites/default/files/resources/UEFI_Spec_2_10_Aug29.pdf, p.116
     let efi_gpt_structure = vec![
@@ -32,79 +128,68 @@ pub fn parse_efigpt_header(efi_data: &[u8]) -> Result<EFIGPTHeader, StructureErr
         ("partition_entries_crc", "u32"),
     ];
 
-    let mut result = EFIGPTHeader {
+    let header_offset = lba_to_offset(lba);
+    let gpt_data = efi_data.get(header_offset..).ok_or(StructureError)?;
+    let gpt_header = common::parse(gpt_data, &efi_gpt_structure, "little")?;
+
+    let header_size = gpt_header["header_size"];
+    let entry_size = gpt_header["partition_entry_size"];
+    let entry_count = gpt_header["partition_entry_count"];
+
+    if gpt_header["magic"] != GPT_MAGIC
+        || gpt_header["revision"] != EXPECTED_REVISION
+        || gpt_header["reserved"] != 0
+        || gpt_header["my_lba"] != lba
+        || header_size < common::size(&efi_gpt_structure)
+        || header_size > GPT_BLOCK_SIZE
+        || entry_size < MIN_ENTRY_SIZE
+        || entry_count > MAX_ENTRY_COUNT
+        || gpt_header["first_usable_lba"] < MIN_FIRST_USABLE_LBA
+    {
+        return Err(StructureError);
+    }
+
+    // The header CRC is calculated with the header CRC field set to zero
+    let mut header_data = gpt_data[..header_size].to_vec();
+    header_data[HEADER_CRC_OFFSET..HEADER_CRC_OFFSET + HEADER_CRC_SIZE].fill(0);
+    let header_crc_valid = crc32(&header_data) as usize == gpt_header["header_crc"];
+
+    let partition_entries_start = lba_to_offset(gpt_header["partition_entry_lba"]);
+    let partition_entries_end = partition_entries_start.saturating_add(entry_count * entry_size);
+    let partition_entries_data = efi_data
+        .get(partition_entries_start..partition_entries_end)
+        .ok_or(StructureError)?;
+    let entries_crc_valid =
+        crc32(partition_entries_data) as usize == gpt_header["partition_entries_crc"];
+
+    let first_usable_offset = lba_to_offset(gpt_header["first_usable_lba"]);
+
+    let mut gpt_table = GPTTable {
+        disk_guid: guid_to_string(&gpt_data[56..72]),
+        alternate_lba: gpt_header["alternate_lba"],
+        last_lba: lba.max(gpt_header["alternate_lba"]),
+        crc_valid: header_crc_valid && entries_crc_valid,
         ..Default::default()
     };
 
-    // EFI GPT structure starts at the second block (first block is MBR)
-    if let Some(gpt_data) = efi_data.get(BLOCK_SIZE..) {
-        // Parse the EFI GPT structure
-        if let Ok(gpt_header) = common::parse(gpt_data, &efi_gpt_structure, "little") {
-            // Make sure the reserved field is NULL
-            if gpt_header["reserved"] == 0 {
-                // Make sure the revision field is the expected valid
-                if gpt_header["revision"] == EXPTECTED_REVISION {
-                    // Calculate the start and end offsets of the partition entries
-                    let partition_entries_start: usize =
-                        lba_to_offset(gpt_header["partition_entry_lba"]);
-                    let partition_entries_end: usize = partition_entries_start
-                        + (gpt_header["partition_entry_count"]
-                            * gpt_header["partition_entry_size"]);
-
-                    // Get the partition entires
-                    if let Some(partition_entries_data) =
-                        efi_data.get(partition_entries_start..partition_entries_end)
-                    {
-                        // Validate the partition entries' CRC
-                        if crc32(partition_entries_data)
-                            == (gpt_header["partition_entries_crc"] as u32)
-                        {
-                            let mut next_partition_offset = 0;
-                            let mut previous_partition_offset = None;
-                            let available_data = partition_entries_data.len();
-
-                            // Loop through all partition entries
-                            while is_offset_safe(
-                                available_data,
-                                next_partition_offset,
-                                previous_partition_offset,
-                            ) {
-                                if let Some(partition) = parse_gpt_partition_entry(
-                                    &partition_entries_data[next_partition_offset..],
-                                ) {
-                                    // EOF is the end of the farthest away partition
-                                    if partition.start_offset < partition.end_offset
-                                        && partition.end_offset > result.total_size
-                                    {
-                                        result.total_size = partition.end_offset;
-                                    }
-                                }
-
-                                previous_partition_offset = Some(next_partition_offset);
-                                next_partition_offset += gpt_header["partition_entry_size"];
-                            }
-
-                            if result.total_size > 0 {
-                                return Ok(result);
-                            }
-                        }
-                    }
-                }
+    for entry_data in partition_entries_data.chunks_exact(entry_size) {
+        if let Some(partition) = parse_gpt_partition_entry(entry_data) {
+            // Partitions may not overlap the GPT itself; among other things, this prevents infinite recursion
+            if partition.start >= first_usable_offset && partition.size > 0 {
+                gpt_table.partitions.push(partition);
             }
         }
     }
 
-    Err(StructureError)
+    Ok(gpt_table)
 }
 
-#[derive(Debug, Default, Clone)]
-struct GPTPartitionEntry {
-    pub end_offset: usize,
-    pub start_offset: usize,
-}
+/// Parse a GPT partition entry; returns None for unused entries
+fn parse_gpt_partition_entry(entry_data: &[u8]) -> Option<GPTPartition> {
+    // Offset and size of the UTF-16LE partition name
+    const NAME_OFFSET: usize = 56;
+    const NAME_SIZE: usize = 72;
 
-/// Parse a GPT partition entry
-fn parse_gpt_partition_entry(entry_data: &[u8]) -> Option<GPTPartitionEntry> {
     let entry_structure = vec![
         ("type_guid_p1", "u64"),
         ("type_guid_p2", "u64"),
@@ -115,23 +200,68 @@ fn parse_gpt_partition_entry(entry_data: &[u8]) -> Option<GPTPartitionEntry> {
         ("attributes", "u64"),
     ];
 
-    let mut result = GPTPartitionEntry {
-        ..Default::default()
-    };
+    let entry_header = common::parse(entry_data, &entry_structure, "little").ok()?;
 
-    if let Ok(entry_header) = common::parse(entry_data, &entry_structure, "little") {
-        // GUID types of NULL can be ignored
-        if entry_header["type_guid_p1"] != 0 && entry_header["type_guid_p2"] != 0 {
-            result.start_offset = lba_to_offset(entry_header["starting_lba"]);
-            result.end_offset = lba_to_offset(entry_header["ending_lba"]);
-            return Some(result);
-        }
+    // GUID types of NULL can be ignored
+    if entry_header["type_guid_p1"] == 0 && entry_header["type_guid_p2"] == 0 {
+        return None;
     }
 
-    None
+    // The ending LBA is inclusive
+    if entry_header["ending_lba"] < entry_header["starting_lba"] {
+        return None;
+    }
+
+    let name_utf16: Vec<u16> = entry_data
+        .get(NAME_OFFSET..NAME_OFFSET + NAME_SIZE)?
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|c| *c != 0)
+        .collect();
+
+    let type_guid = guid_to_string(&entry_data[0..16]);
+
+    Some(GPTPartition {
+        start: lba_to_offset(entry_header["starting_lba"]),
+        size: lba_to_offset(entry_header["ending_lba"] - entry_header["starting_lba"])
+            .saturating_add(GPT_BLOCK_SIZE),
+        type_name: partition_type_name(&type_guid)
+            .unwrap_or(&type_guid)
+            .to_string(),
+        type_guid,
+        name: String::from_utf16_lossy(&name_utf16),
+    })
+}
+
+/// Returns a human readable name for well known partition type GUIDs
+fn partition_type_name(type_guid: &str) -> Option<&'static str> {
+    let name = match type_guid {
+        "C12A7328-F81F-11D2-BA4B-00A0C93EC93B" => "EFI System",
+        "21686148-6449-6E6F-744E-656564454649" => "BIOS boot",
+        "024DEE41-33E7-11D3-9D69-0008C781F39F" => "MBR partition scheme",
+        "E3C9E316-0B5C-4DB8-817D-F92DF00215AE" => "Microsoft reserved",
+        "EBD0A0A2-B9E5-4433-87C0-68B6B72699C7" => "Microsoft basic data",
+        "DE94BBA4-06D1-4D40-A16A-BFD50179D6AC" => "Windows recovery environment",
+        "0FC63DAF-8483-4772-8E79-3D69D8477DE4" => "Linux filesystem",
+        "0657FD6D-A4AB-43C4-84E5-0933C84B4F4F" => "Linux swap",
+        "E6D6D379-F507-44C2-A23C-238F2A3DF928" => "Linux LVM",
+        "A19D880F-05FC-4D3B-A006-743F0F84911E" => "Linux RAID",
+        "4F68BCE3-E8CD-4DB1-96E7-FBCAF984B709" => "Linux root (x86-64)",
+        "BC13C2FF-59E6-4262-A352-B275FD6F7172" => "Linux extended boot",
+        "933AC7E1-2EB4-4F13-B844-0E14E2AEF915" => "Linux home",
+        "48465300-0000-11AA-AA11-00306543ECAC" => "Apple HFS+",
+        "7C3457EF-0000-11AA-AA11-00306543ECAC" => "Apple APFS",
+        "FE3A2A5D-4F32-41A7-B725-ACCC3285A309" => "ChromeOS kernel",
+        "3CB8E202-3B7E-47DD-8A3C-7FF2A13CFCEC" => "ChromeOS root filesystem",
+        "516E7CB4-6ECF-11D6-8FF8-00022D09712B" => "FreeBSD data",
+        "83BD6B9D-7F41-11DC-BE0B-001560B84F0F" => "FreeBSD boot",
+        _ => return None,
+    };
+
+    Some(name)
 }
 
 // Convert LBA to offset
 fn lba_to_offset(lba: usize) -> usize {
-    lba * BLOCK_SIZE
+    lba.saturating_mul(GPT_BLOCK_SIZE)
 }
@@ -9,8 +9,47 @@ pub struct ELFHeader {
     pub machine: String,
     pub exe_type: String,
     pub endianness: String,
+    pub header_size: usize,
+    pub program_header_offset: usize,
+    pub program_header_entry_size: usize,
+    pub program_header_count: usize,
+    pub section_header_offset: usize,
+    pub section_header_entry_size: usize,
+    pub section_header_count: usize,
+    pub section_names_index: usize,
 }
 
+/// Struct to store ELF program header (segment) info
+#[derive(Debug, Default, Clone)]
+pub struct ELFProgramHeader {
+    pub offset: usize,
+    pub file_size: usize,
+}
+
+/// Struct to store ELF section header info
+#[derive(Debug, Default, Clone)]
+pub struct ELFSectionHeader {
+    pub name_offset: usize,
+    pub section_type: usize,
+    pub flags: usize,
+    pub offset: usize,
+    pub size: usize,
+}
+
+/// Struct to store the compression header of compressed ELF sections
+#[derive(Debug, Default, Clone)]
+pub struct ELFCompressionHeader {
+    pub compression_type: usize,
+    pub uncompressed_size: usize,
+    pub header_size: usize,
+}
+
+/// Section type of sections that occupy no space in the file, e.g. .bss
+pub const SHT_NOBITS: usize = 8;
+
+/// Section flag indicating that the section data is compressed, and starts with a compression header
+pub const SHF_COMPRESSED: usize = 0x800;
+
 /// Partially parses an ELF header
 pub fn parse_elf_header(elf_data: &[u8]) -> Result<ELFHeader, StructureError> {
     const ELF_INFO_STRUCT_SIZE: usize = 8;
@@ -32,6 +71,33 @@ pub fn parse_elf_header(elf_data: &[u8]) -> Result<ELFHeader, StructureError> {
     // Just enough of the ELF structure to grab some useful info
     let elf_info_structure = vec![("type", "u16"), ("machine", "u16"), ("version", "u32")];
 
+    // Locations of the program and section header tables; field sizes differ between 32 and 64 bit ELFs
+    let elf_tables_structure_32 = vec![
+        ("entry", "u32"),
+        ("phoff", "u32"),
+        ("shoff", "u32"),
+        ("flags", "u32"),
+        ("ehsize", "u16"),
+        ("phentsize", "u16"),
+        ("phnum", "u16"),
+        ("shentsize", "u16"),
+        ("shnum", "u16"),
+        ("shstrndx", "u16"),
+    ];
+
+    let elf_tables_structure_64 = vec![
+        ("entry", "u64"),
+        ("phoff", "u64"),
+        ("shoff", "u64"),
+        ("flags", "u32"),
+        ("ehsize", "u16"),
+        ("phentsize", "u16"),
+        ("phnum", "u16"),
+        ("shentsize", "u16"),
+        ("shnum", "u16"),
+        ("shstrndx", "u16"),
+    ];
+
     let elf_classes = HashMap::from([(1, 32), (2, 64)]);
 
     let elf_endianness = HashMap::from([(1, "little"), (2, "big")]);
@@ -308,7 +374,30 @@ pub fn parse_elf_header(elf_data: &[u8]) -> Result<ELFHeader, StructureError> {
                             .unwrap_or(&"Unknown")
                             .to_string();
 
-                        return Ok(elf_hdr_info);
+                        // The program and section header table info follows the version field
+                        let elf_tables_structure = match elf_hdr_info.class.as_str() {
+                            "32" => &elf_tables_structure_32,
+                            _ => &elf_tables_structure_64,
+                        };
+
+                        if let Some(elf_tables_raw) = elf_data.get(elf_info_end..)
+                            && let Ok(elf_tables) = common::parse(
+                                elf_tables_raw,
+                                elf_tables_structure,
+                                &elf_hdr_info.endianness,
+                            )
+                        {
+                            elf_hdr_info.header_size = elf_tables["ehsize"];
+                            elf_hdr_info.program_header_offset = elf_tables["phoff"];
+                            elf_hdr_info.program_header_entry_size = elf_tables["phentsize"];
+                            elf_hdr_info.program_header_count = elf_tables["phnum"];
+                            elf_hdr_info.section_header_offset = elf_tables["shoff"];
+                            elf_hdr_info.section_header_entry_size = elf_tables["shentsize"];
+                            elf_hdr_info.section_header_count = elf_tables["shnum"];
+                            elf_hdr_info.section_names_index = elf_tables["shstrndx"];
+
+                            return Ok(elf_hdr_info);
+                        }
                     }
                 }
             }
@@ -317,3 +406,141 @@ pub fn parse_elf_header(elf_data: &[u8]) -> Result<ELFHeader, StructureError> {
 
     Err(StructureError)
 }
+
+/// Parses an ELF program header table entry
+pub fn parse_elf_program_header(
+    header_data: &[u8],
+    elf_header: &ELFHeader,
+) -> Result<ELFProgramHeader, StructureError> {
+    let program_header_structure_32 = vec![
+        ("type", "u32"),
+        ("offset", "u32"),
+        ("vaddr", "u32"),
+        ("paddr", "u32"),
+        ("filesz", "u32"),
+        ("memsz", "u32"),
+        ("flags", "u32"),
+        ("align", "u32"),
+    ];
+
+    let program_header_structure_64 = vec![
+        ("type", "u32"),
+        ("flags", "u32"),
+        ("offset", "u64"),
+        ("vaddr", "u64"),
+        ("paddr", "u64"),
+        ("filesz", "u64"),
+        ("memsz", "u64"),
+        ("align", "u64"),
+    ];
+
+    let program_header_structure = match elf_header.class.as_str() {
+        "32" => &program_header_structure_32,
+        _ => &program_header_structure_64,
+    };
+
+    // Sanity check the reported entry size
+    if elf_header.program_header_entry_size == common::size(program_header_structure) {
+        let program_header = common::parse(
+            header_data,
+            program_header_structure,
+            &elf_header.endianness,
+        )?;
+
+        return Ok(ELFProgramHeader {
+            offset: program_header["offset"],
+            file_size: program_header["filesz"],
+        });
+    }
+
+    Err(StructureError)
+}
+
+/// Parses an ELF section header table entry
+pub fn parse_elf_section_header(
+    header_data: &[u8],
+    elf_header: &ELFHeader,
+) -> Result<ELFSectionHeader, StructureError> {
+    let section_header_structure_32 = vec![
+        ("name", "u32"),
+        ("type", "u32"),
+        ("flags", "u32"),
+        ("addr", "u32"),
+        ("offset", "u32"),
+        ("size", "u32"),
+        ("link", "u32"),
+        ("info", "u32"),
+        ("addralign", "u32"),
+        ("entsize", "u32"),
+    ];
+
+    let section_header_structure_64 = vec![
+        ("name", "u32"),
+        ("type", "u32"),
+        ("flags", "u64"),
+        ("addr", "u64"),
+        ("offset", "u64"),
+        ("size", "u64"),
+        ("link", "u32"),
+        ("info", "u32"),
+        ("addralign", "u64"),
+        ("entsize", "u64"),
+    ];
+
+    let section_header_structure = match elf_header.class.as_str() {
+        "32" => &section_header_structure_32,
+        _ => &section_header_structure_64,
+    };
+
+    // Sanity check the reported entry size
+    if elf_header.section_header_entry_size == common::size(section_header_structure) {
+        let section_header = common::parse(
+            header_data,
+            section_header_structure,
+            &elf_header.endianness,
+        )?;
+
+        return Ok(ELFSectionHeader {
+            name_offset: section_header["name"],
+            section_type: section_header["type"],
+            flags: section_header["flags"],
+            offset: section_header["offset"],
+            size: section_header["size"],
+        });
+    }
+
+    Err(StructureError)
+}
+
+/// Parses the compression header at the start of an ELF section with the SHF_COMPRESSED flag set
+pub fn parse_elf_compression_header(
+    section_data: &[u8],
+    elf_header: &ELFHeader,
+) -> Result<ELFCompressionHeader, StructureError> {
+    let compression_header_structure_32 =
+        vec![("type", "u32"), ("size", "u32"), ("addralign", "u32")];
+
+    let compression_header_structure_64 = vec![
+        ("type", "u32"),
+        ("reserved", "u32"),
+        ("size", "u64"),
+        ("addralign", "u64"),
+    ];
+
+    let compression_header_structure = match elf_header.class.as_str() {
+        "32" => &compression_header_structure_32,
+        _ => &compression_header_structure_64,
+    };
+
+    let compression_header = common::parse(
+        section_data,
+        compression_header_structure,
+        &elf_header.endianness,
+    )?;
+
+    Ok(ELFCompressionHeader {
+        compression_type: compression_header["type"],
+        uncompressed_size: compression_header["size"],
+        header_size: common::size(compression_header_structure),
+    })
+}
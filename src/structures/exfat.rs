@@ -0,0 +1,359 @@
+use crate::structures::common::{self, StructureError};
+
+/// Size of an exFAT directory entry
+pub const DIRECTORY_ENTRY_SIZE: usize = 32;
+
+/// Directory entry type: marks the end of the directory
+pub const ENTRY_TYPE_END_OF_DIRECTORY: u8 = 0x00;
+
+/// Directory entry type: allocation bitmap
+pub const ENTRY_TYPE_ALLOCATION_BITMAP: u8 = 0x81;
+
+/// Directory entry type: up-case table
+pub const ENTRY_TYPE_UPCASE_TABLE: u8 = 0x82;
+
+/// Directory entry type: file, the primary entry of a file's directory entry set
+pub const ENTRY_TYPE_FILE: u8 = 0x85;
+
+/// Directory entry type: stream extension, the first secondary entry of a file's directory entry set
+pub const ENTRY_TYPE_STREAM_EXTENSION: u8 = 0xC0;
+
+/// Directory entry type: file name, which follows the stream extension entry
+pub const ENTRY_TYPE_FILE_NAME: u8 = 0xC1;
+
+/// File attribute: the entry is a directory
+pub const ATTR_DIRECTORY: usize = 0x10;
+
+/// Number of UTF-16 name characters stored in each file name entry
+pub const FILE_NAME_ENTRY_CHARACTERS: usize = 15;
+
+/// Struct to store exFAT boot sector info
+#[derive(Debug, Default, Clone)]
+pub struct EXFATHeader {
+    /// Size of the volume, as recorded in the boot sector
+    pub volume_size: usize,
+    pub cluster_size: usize,
+    pub cluster_count: usize,
+    /// Offset of the first FAT, from the start of the volume
+    pub fat_offset: usize,
+    /// Offset of cluster 2, the first data cluster, from the start of the volume
+    pub cluster_heap_offset: usize,
+    pub root_cluster: usize,
+    pub serial_number: usize,
+    /// File system revision, as a "major.minor" string
+    pub revision: String,
+}
+
+/// Struct to store exFAT file directory entry info
+#[derive(Debug, Default, Clone)]
+pub struct EXFATFileEntry {
+    /// Number of secondary entries which follow the file entry
+    pub secondary_count: usize,
+    pub set_checksum: usize,
+    pub attributes: usize,
+}
+
+/// Struct to store exFAT stream extension directory entry info
+#[derive(Debug, Default, Clone)]
+pub struct EXFATStreamEntry {
+    /// True if the file's clusters are contiguous, and the FAT does not describe them
+    pub no_fat_chain: bool,
+    /// Length of the file name, in UTF-16 characters
+    pub name_length: usize,
+    pub name_hash: usize,
+    /// Data beyond the valid data length reads as zeros
+    pub valid_data_length: usize,
+    pub first_cluster: usize,
+    pub data_length: usize,
+}
+
+/// Struct to store exFAT allocation bitmap and up-case table directory entry info
+#[derive(Debug, Default, Clone)]
+pub struct EXFATSystemEntry {
+    /// Up-case table checksum; unused by allocation bitmap entries
+    pub checksum: usize,
+    pub first_cluster: usize,
+    pub data_length: usize,
+}
+
+/// Parses an exFAT boot sector
+pub fn parse_exfat_header(exfat_data: &[u8]) -> Result<EXFATHeader, StructureError> {
+    // Offset and size of the region of the boot sector which must be zero, where a FAT boot sector has its BIOS parameter block
+    const MUST_BE_ZERO_OFFSET: usize = 11;
+    const MUST_BE_ZERO_SIZE: usize = 53;
+    // The FAT can't overlap the main and backup boot regions
+    const MIN_FAT_OFFSET: usize = 24;
+    // Sector size limits, as a power of two
+    const MIN_SECTOR_SHIFT: usize = 9;
+    const MAX_SECTOR_SHIFT: usize = 12;
+    // Cluster size limit, as a power of two
+    const MAX_CLUSTER_SHIFT: usize = 25;
+    // The first data cluster is cluster 2
+    const FIRST_DATA_CLUSTER: usize = 2;
+    // Jump instruction (EB 76 90) and file system name ("EXFAT   "), as little endian integers
+    const JUMP_BOOT: usize = 0x9076EB;
+    const FILE_SYSTEM_NAME: usize = 0x2020205441465845;
+
+    // https://learn.microsoft.com/en-us/windows/win32/fileio/exfat-specification
+    let exfat_boot_sector_structure = vec![("jump_boot", "u24"), ("file_system_name", "u64")];
+
+    let exfat_parameters_structure = vec![
+        ("partition_offset", "u64"),
+        ("volume_length", "u64"),
+        ("fat_offset", "u32"),
+        ("fat_length", "u32"),
+        ("cluster_heap_offset", "u32"),
+        ("cluster_count", "u32"),
+        ("root_cluster", "u32"),
+        ("serial_number", "u32"),
+        ("revision_minor", "u8"),
+        ("revision_major", "u8"),
+        ("volume_flags", "u16"),
+        ("bytes_per_sector_shift", "u8"),
+        ("sectors_per_cluster_shift", "u8"),
+        ("fat_count", "u8"),
+        ("drive_select", "u8"),
+        ("percent_in_use", "u8"),
+    ];
+
+    let parameters_offset = MUST_BE_ZERO_OFFSET + MUST_BE_ZERO_SIZE;
+
+    let boot_sector = common::parse(exfat_data, &exfat_boot_sector_structure, "little")?;
+    let parameters = common::parse(
+        exfat_data.get(parameters_offset..).ok_or(StructureError)?,
+        &exfat_parameters_structure,
+        "little",
+    )?;
+
+    let must_be_zero = &exfat_data[MUST_BE_ZERO_OFFSET..parameters_offset];
+
+    let sector_shift = parameters["bytes_per_sector_shift"];
+    let cluster_shift = sector_shift + parameters["sectors_per_cluster_shift"];
+
+    if boot_sector["jump_boot"] != JUMP_BOOT
+        || boot_sector["file_system_name"] != FILE_SYSTEM_NAME
+        || must_be_zero.iter().any(|byte| *byte != 0)
+        || !(MIN_SECTOR_SHIFT..=MAX_SECTOR_SHIFT).contains(&sector_shift)
+        || cluster_shift > MAX_CLUSTER_SHIFT
+        || ![1, 2].contains(&parameters["fat_count"])
+        || parameters["fat_offset"] < MIN_FAT_OFFSET
+        || parameters["cluster_heap_offset"]
+            < parameters["fat_offset"] + (parameters["fat_length"] * parameters["fat_count"])
+        || parameters["cluster_count"] == 0
+        || parameters["root_cluster"] < FIRST_DATA_CLUSTER
+        || parameters["root_cluster"] >= parameters["cluster_count"] + FIRST_DATA_CLUSTER
+    {
+        return Err(StructureError);
+    }
+
+    let exfat_header = EXFATHeader {
+        volume_size: parameters["volume_length"]
+            .checked_mul(1 << sector_shift)
+            .ok_or(StructureError)?,
+        cluster_size: 1 << cluster_shift,
+        cluster_count: parameters["cluster_count"],
+        fat_offset: parameters["fat_offset"] << sector_shift,
+        cluster_heap_offset: parameters["cluster_heap_offset"] << sector_shift,
+        root_cluster: parameters["root_cluster"],
+        serial_number: parameters["serial_number"],
+        revision: format!(
+            "{}.{}",
+            parameters["revision_major"], parameters["revision_minor"]
+        ),
+    };
+
+    // The cluster heap must fit in the volume
+    let cluster_heap_size = exfat_header
+        .cluster_count
+        .checked_mul(exfat_header.cluster_size)
+        .ok_or(StructureError)?;
+
+    if exfat_header.cluster_heap_offset + cluster_heap_size > exfat_header.volume_size {
+        return Err(StructureError);
+    }
+
+    Ok(exfat_header)
+}
+
+/// Parses an exFAT file directory entry
+pub fn parse_exfat_file_entry(entry_data: &[u8]) -> Result<EXFATFileEntry, StructureError> {
+    // A file entry is followed by a stream extension entry and 1 to 17 file name entries
+    const MIN_SECONDARY_COUNT: usize = 2;
+    const MAX_SECONDARY_COUNT: usize = 18;
+
+    let file_entry_structure = vec![
+        ("entry_type", "u8"),
+        ("secondary_count", "u8"),
+        ("set_checksum", "u16"),
+        ("file_attributes", "u16"),
+    ];
+
+    let entry = common::parse(entry_data, &file_entry_structure, "little")?;
+
+    if entry["entry_type"] != ENTRY_TYPE_FILE as usize
+        || entry["secondary_count"] < MIN_SECONDARY_COUNT
+        || entry["secondary_count"] > MAX_SECONDARY_COUNT
+    {
+        return Err(StructureError);
+    }
+
+    Ok(EXFATFileEntry {
+        secondary_count: entry["secondary_count"],
+        set_checksum: entry["set_checksum"],
+        attributes: entry["file_attributes"],
+    })
+}
+
+/// Parses an exFAT stream extension directory entry
+pub fn parse_exfat_stream_entry(entry_data: &[u8]) -> Result<EXFATStreamEntry, StructureError> {
+    const NO_FAT_CHAIN_FLAG: usize = 0x02;
+
+    let stream_entry_structure = vec![
+        ("entry_type", "u8"),
+        ("flags", "u8"),
+        ("reserved1", "u8"),
+        ("name_length", "u8"),
+        ("name_hash", "u16"),
+        ("reserved2", "u16"),
+        ("valid_data_length", "u64"),
+        ("reserved3", "u32"),
+        ("first_cluster", "u32"),
+        ("data_length", "u64"),
+    ];
+
+    let entry = common::parse(entry_data, &stream_entry_structure, "little")?;
+
+    if entry["entry_type"] != ENTRY_TYPE_STREAM_EXTENSION as usize
+        || entry["name_length"] == 0
+        || entry["valid_data_length"] > entry["data_length"]
+    {
+        return Err(StructureError);
+    }
+
+    Ok(EXFATStreamEntry {
+        no_fat_chain: entry["flags"] & NO_FAT_CHAIN_FLAG != 0,
+        name_length: entry["name_length"],
+        name_hash: entry["name_hash"],
+        valid_data_length: entry["valid_data_length"],
+        first_cluster: entry["first_cluster"],
+        data_length: entry["data_length"],
+    })
+}
+
+/// Parses an exFAT file name directory entry, returning its UTF-16 name characters
+pub fn parse_exfat_file_name_entry(entry_data: &[u8]) -> Result<Vec<u16>, StructureError> {
+    const NAME_OFFSET: usize = 2;
+
+    let entry_data = entry_data
+        .get(..DIRECTORY_ENTRY_SIZE)
+        .ok_or(StructureError)?;
+
+    if entry_data[0] != ENTRY_TYPE_FILE_NAME {
+        return Err(StructureError);
+    }
+
+    Ok(entry_data[NAME_OFFSET..]
+        .chunks_exact(2)
+        .map(|character| u16::from_le_bytes(character.try_into().unwrap()))
+        .collect())
+}
+
+/// Parses an exFAT allocation bitmap or up-case table directory entry
+pub fn parse_exfat_system_entry(entry_data: &[u8]) -> Result<EXFATSystemEntry, StructureError> {
+    let system_entry_structure = vec![
+        ("entry_type", "u8"),
+        ("flags", "u8"),
+        ("reserved1", "u16"),
+        ("checksum", "u32"),
+        ("reserved2", "u64"),
+        ("reserved3", "u32"),
+        ("first_cluster", "u32"),
+        ("data_length", "u64"),
+    ];
+
+    let entry = common::parse(entry_data, &system_entry_structure, "little")?;
+
+    if ![ENTRY_TYPE_ALLOCATION_BITMAP, ENTRY_TYPE_UPCASE_TABLE]
+        .contains(&(entry["entry_type"] as u8))
+    {
+        return Err(StructureError);
+    }
+
+    Ok(EXFATSystemEntry {
+        checksum: entry["checksum"],
+        first_cluster: entry["first_cluster"],
+        data_length: entry["data_length"],
+    })
+}
+
+/// Calculates the checksum of a directory entry set, excluding the checksum field of the file entry
+pub fn exfat_entry_set_checksum(entry_set_data: &[u8]) -> usize {
+    const SET_CHECKSUM_OFFSETS: [usize; 2] = [2, 3];
+
+    let mut checksum: u16 = 0;
+
+    for (i, byte) in entry_set_data.iter().enumerate() {
+        if !SET_CHECKSUM_OFFSETS.contains(&i) {
+            checksum = checksum.rotate_right(1).wrapping_add(*byte as u16);
+        }
+    }
+
+    checksum as usize
+}
+
+/// Calculates the hash of an up-cased file name, as stored in the stream extension entry
+pub fn exfat_name_hash(upcased_name: &[u16]) -> usize {
+    let mut hash: u16 = 0;
+
+    for character in upcased_name {
+        for byte in character.to_le_bytes() {
+            hash = hash.rotate_right(1).wrapping_add(byte as u16);
+        }
+    }
+
+    hash as usize
+}
+
+/// Calculates the checksum of the raw, possibly compressed, up-case table
+pub fn exfat_upcase_table_checksum(table_data: &[u8]) -> usize {
+    let mut checksum: u32 = 0;
+
+    for byte in table_data {
+        checksum = checksum.rotate_right(1).wrapping_add(*byte as u32);
+    }
+
+    checksum as usize
+}
+
+/// Parses an up-case table, returning the up-cased value of each UTF-16 character; characters beyond the end of the
+/// table are their own up-cased value.
+///
+/// Up-case tables are usually compressed: a value of 0xFFFF is followed by the number of characters that are their
+/// own up-cased value.
+pub fn parse_exfat_upcase_table(table_data: &[u8]) -> Vec<u16> {
+    const IDENTITY_RUN_MARKER: u16 = 0xFFFF;
+
+    let mut table: Vec<u16> = vec![];
+    let mut values = table_data
+        .chunks_exact(2)
+        .map(|value| u16::from_le_bytes(value.try_into().unwrap()));
+
+    while let Some(value) = values.next() {
+        if value == IDENTITY_RUN_MARKER
+            && let Some(run_length) = values.next()
+        {
+            let start = table.len();
+            table.extend((start..start + run_length as usize).map(|character| character as u16));
+        } else {
+            table.push(value);
+        }
+
+        // The table maps UTF-16 characters, so can have no more than 0x10000 entries
+        if table.len() > u16::MAX as usize {
+            table.truncate(u16::MAX as usize + 1);
+            break;
+        }
+    }
+
+    table
+}
@@ -7,6 +7,27 @@ pub const SUPERBLOCK_SIZE: usize = 1024;
 /// Expected file offset of an EXT superblock
 pub const SUPERBLOCK_OFFSET: usize = 1024;
 
+/// Inode number of the root directory
+pub const ROOT_INODE: usize = 2;
+
+/// Incompatible feature flag: directory entries record the file type
+pub const FEATURE_INCOMPAT_FILETYPE: usize = 0x2;
+
+/// Incompatible feature flag: block numbers are 64 bits, and group descriptors may be larger than 32 bytes
+pub const FEATURE_INCOMPAT_64BIT: usize = 0x80;
+
+/// Inode flag: the inode's data is mapped by an extent tree, rather than a block map
+pub const INODE_FLAG_EXTENTS: usize = 0x80000;
+
+/// Inode flag: the inode's data is stored inside the inode itself
+pub const INODE_FLAG_INLINE_DATA: usize = 0x10000000;
+
+/// Size of the block map / extent tree root stored in each inode
+pub const INODE_BLOCK_MAP_SIZE: usize = 60;
+
+/// Size of an extent tree node header, and of each extent tree node entry
+pub const EXTENT_ENTRY_SIZE: usize = 12;
+
 /// Struct to store some useful EXT info
 #[derive(Debug, Default, Clone)]
 pub struct EXTHeader {
@@ -17,6 +38,52 @@ pub struct EXTHeader {
     pub inodes_count: usize,
     pub free_blocks_count: usize,
     pub reserved_blocks_count: usize,
+    pub first_data_block: usize,
+    pub blocks_per_group: usize,
+    pub inodes_per_group: usize,
+    pub inode_size: usize,
+    pub descriptor_size: usize,
+    pub incompat_features: usize,
+}
+
+/// Struct to store EXT inode info
+#[derive(Debug, Default, Clone)]
+pub struct EXTInode {
+    pub mode: usize,
+    pub uid: usize,
+    pub gid: usize,
+    pub size: usize,
+    pub mtime: usize,
+    pub sectors: usize,
+    pub flags: usize,
+    pub file_acl: usize,
+    /// The raw block map, extent tree root, fast symlink target, or device number
+    pub block: Vec<u8>,
+}
+
+/// Struct to store EXT extent tree node header info
+#[derive(Debug, Default, Clone)]
+pub struct EXTExtentHeader {
+    pub entries: usize,
+    pub depth: usize,
+}
+
+/// Struct to store a leaf node of an EXT extent tree, which maps a run of file blocks to file system blocks
+#[derive(Debug, Default, Clone)]
+pub struct EXTExtent {
+    pub file_block: usize,
+    pub block_count: usize,
+    pub start_block: usize,
+    /// Uninitialized extents are allocated, but read back as zeros
+    pub uninitialized: bool,
+}
+
+/// Struct to store EXT directory entry info
+#[derive(Debug, Default, Clone)]
+pub struct EXTDirectoryEntry {
+    pub inode: usize,
+    pub entry_size: usize,
+    pub name: Vec<u8>,
 }
 
 /// Partially parses an EXT superblock structure
@@ -92,9 +159,23 @@ pub fn parse_ext_header(ext_data: &[u8]) -> Result<EXTHeader, StructureError> {
                             ext_header.os = supported_os[&ext_superblock["creator_os"]].to_string();
                             ext_header.reserved_blocks_count =
                                 ext_superblock["reserved_blocks_count"];
+                            ext_header.first_data_block = ext_superblock["first_data_block"];
+                            ext_header.blocks_per_group = ext_superblock["blocks_per_group"];
+                            ext_header.inodes_per_group = ext_superblock["inodes_per_group"];
                             ext_header.image_size =
                                 ext_header.block_size * ext_superblock["blocks_count"];
 
+                            // Revision 0 file systems have fixed size inodes and group descriptors, and no features
+                            ext_header.inode_size = 128;
+                            ext_header.descriptor_size = 32;
+
+                            if ext_superblock["s_rev_level"] > 0 {
+                                parse_ext_dynamic_header(
+                                    &ext_data[SUPERBLOCK_OFFSET..],
+                                    &mut ext_header,
+                                )?;
+                            }
+
                             return Ok(ext_header);
                         }
                     }
@@ -105,3 +186,265 @@ pub fn parse_ext_header(ext_data: &[u8]) -> Result<EXTHeader, StructureError> {
 
     Err(StructureError)
 }
+
+/// Parses the dynamic revision fields of an EXT superblock, which describe inode size and file system features
+fn parse_ext_dynamic_header(
+    superblock_data: &[u8],
+    ext_header: &mut EXTHeader,
+) -> Result<(), StructureError> {
+    const DYNAMIC_FIELDS_OFFSET: usize = 0x54;
+    const DESCRIPTOR_SIZE_OFFSET: usize = 0xFE;
+    const BLOCKS_COUNT_HI_OFFSET: usize = 0x150;
+    const MIN_INODE_SIZE: usize = 128;
+    const MIN_64BIT_DESCRIPTOR_SIZE: usize = 64;
+
+    let dynamic_structure = vec![
+        ("first_inode", "u32"),
+        ("inode_size", "u16"),
+        ("block_group_number", "u16"),
+        ("compat_features", "u32"),
+        ("incompat_features", "u32"),
+        ("ro_compat_features", "u32"),
+    ];
+
+    let dynamic_fields = common::parse(
+        &superblock_data[DYNAMIC_FIELDS_OFFSET..],
+        &dynamic_structure,
+        "little",
+    )?;
+
+    // Inode sizes are a power of two, no smaller than the revision 0 inode size, and no larger than a block
+    let inode_size = dynamic_fields["inode_size"];
+    if inode_size < MIN_INODE_SIZE
+        || !inode_size.is_power_of_two()
+        || inode_size > ext_header.block_size
+    {
+        return Err(StructureError);
+    }
+
+    ext_header.inode_size = inode_size;
+    ext_header.incompat_features = dynamic_fields["incompat_features"];
+
+    if ext_header.incompat_features & FEATURE_INCOMPAT_64BIT != 0 {
+        let descriptor_size = common::parse(
+            &superblock_data[DESCRIPTOR_SIZE_OFFSET..],
+            &vec![("descriptor_size", "u16")],
+            "little",
+        )?["descriptor_size"];
+        let blocks_count_hi = common::parse(
+            &superblock_data[BLOCKS_COUNT_HI_OFFSET..],
+            &vec![("blocks_count_hi", "u32")],
+            "little",
+        )?["blocks_count_hi"];
+
+        if descriptor_size < MIN_64BIT_DESCRIPTOR_SIZE || !descriptor_size.is_power_of_two() {
+            return Err(StructureError);
+        }
+
+        ext_header.descriptor_size = descriptor_size;
+        ext_header.blocks_count |= blocks_count_hi << 32;
+        ext_header.image_size = ext_header
+            .blocks_count
+            .checked_mul(ext_header.block_size)
+            .ok_or(StructureError)?;
+    }
+
+    Ok(())
+}
+
+/// Parses an EXT block group descriptor, returning the block number of the group's inode table
+pub fn parse_ext_group_descriptor(
+    descriptor_data: &[u8],
+    ext_header: &EXTHeader,
+) -> Result<usize, StructureError> {
+    const INODE_TABLE_LO_OFFSET: usize = 0x08;
+    const INODE_TABLE_HI_OFFSET: usize = 0x28;
+
+    let descriptor_data = descriptor_data
+        .get(..ext_header.descriptor_size)
+        .ok_or(StructureError)?;
+
+    let inode_table_structure = vec![("inode_table", "u32")];
+
+    let mut inode_table = common::parse(
+        &descriptor_data[INODE_TABLE_LO_OFFSET..],
+        &inode_table_structure,
+        "little",
+    )?["inode_table"];
+
+    if ext_header.incompat_features & FEATURE_INCOMPAT_64BIT != 0 {
+        inode_table |= common::parse(
+            &descriptor_data[INODE_TABLE_HI_OFFSET..],
+            &inode_table_structure,
+            "little",
+        )?["inode_table"]
+            << 32;
+    }
+
+    Ok(inode_table)
+}
+
+/// Parses an EXT inode
+pub fn parse_ext_inode(inode_data: &[u8]) -> Result<EXTInode, StructureError> {
+    const BLOCK_MAP_OFFSET: usize = 0x28;
+    const EXTENDED_FIELDS_OFFSET: usize = 0x68;
+    const OWNER_HI_OFFSET: usize = 0x78;
+
+    let inode_structure = vec![
+        ("mode", "u16"),
+        ("uid_lo", "u16"),
+        ("size_lo", "u32"),
+        ("atime", "u32"),
+        ("ctime", "u32"),
+        ("mtime", "u32"),
+        ("dtime", "u32"),
+        ("gid_lo", "u16"),
+        ("links_count", "u16"),
+        ("sectors_lo", "u32"),
+        ("flags", "u32"),
+        ("osd1", "u32"),
+    ];
+
+    let extended_structure = vec![
+        ("file_acl_lo", "u32"),
+        ("size_hi", "u32"),
+        ("obsolete_fragment_address", "u32"),
+        ("sectors_hi", "u16"),
+        ("file_acl_hi", "u16"),
+    ];
+
+    let owner_hi_structure = vec![("uid_hi", "u16"), ("gid_hi", "u16")];
+
+    let inode = common::parse(inode_data, &inode_structure, "little")?;
+    let extended = common::parse(
+        inode_data
+            .get(EXTENDED_FIELDS_OFFSET..)
+            .ok_or(StructureError)?,
+        &extended_structure,
+        "little",
+    )?;
+    let owner_hi = common::parse(
+        inode_data.get(OWNER_HI_OFFSET..).ok_or(StructureError)?,
+        &owner_hi_structure,
+        "little",
+    )?;
+
+    Ok(EXTInode {
+        mode: inode["mode"],
+        uid: inode["uid_lo"] | (owner_hi["uid_hi"] << 16),
+        gid: inode["gid_lo"] | (owner_hi["gid_hi"] << 16),
+        size: inode["size_lo"] | (extended["size_hi"] << 32),
+        mtime: inode["mtime"],
+        sectors: inode["sectors_lo"] | (extended["sectors_hi"] << 32),
+        flags: inode["flags"],
+        file_acl: extended["file_acl_lo"] | (extended["file_acl_hi"] << 32),
+        block: inode_data[BLOCK_MAP_OFFSET..BLOCK_MAP_OFFSET + INODE_BLOCK_MAP_SIZE].to_vec(),
+    })
+}
+
+/// Parses an EXT extent tree node header
+pub fn parse_ext_extent_header(node_data: &[u8]) -> Result<EXTExtentHeader, StructureError> {
+    const EXTENT_MAGIC: usize = 0xF30A;
+
+    let extent_header_structure = vec![
+        ("magic", "u16"),
+        ("entries", "u16"),
+        ("max_entries", "u16"),
+        ("depth", "u16"),
+        ("generation", "u32"),
+    ];
+
+    let extent_header = common::parse(node_data, &extent_header_structure, "little")?;
+
+    // All entries must fit in the node
+    let entries_size = (extent_header["entries"] + 1) * EXTENT_ENTRY_SIZE;
+
+    if extent_header["magic"] != EXTENT_MAGIC
+        || extent_header["entries"] > extent_header["max_entries"]
+        || entries_size > node_data.len()
+    {
+        return Err(StructureError);
+    }
+
+    Ok(EXTExtentHeader {
+        entries: extent_header["entries"],
+        depth: extent_header["depth"],
+    })
+}
+
+/// Parses an EXT extent tree leaf node entry
+pub fn parse_ext_extent(entry_data: &[u8]) -> Result<EXTExtent, StructureError> {
+    // Extents longer than this are uninitialized; their length is offset by this value
+    const MAX_INITIALIZED_LENGTH: usize = 0x8000;
+
+    let extent_structure = vec![
+        ("file_block", "u32"),
+        ("length", "u16"),
+        ("start_hi", "u16"),
+        ("start_lo", "u32"),
+    ];
+
+    let extent = common::parse(entry_data, &extent_structure, "little")?;
+    let uninitialized = extent["length"] > MAX_INITIALIZED_LENGTH;
+
+    Ok(EXTExtent {
+        file_block: extent["file_block"],
+        block_count: match uninitialized {
+            true => extent["length"] - MAX_INITIALIZED_LENGTH,
+            false => extent["length"],
+        },
+        start_block: extent["start_lo"] | (extent["start_hi"] << 32),
+        uninitialized,
+    })
+}
+
+/// Parses an EXT extent tree index node entry, returning the block number of the child node
+pub fn parse_ext_extent_index(entry_data: &[u8]) -> Result<usize, StructureError> {
+    let index_structure = vec![
+        ("file_block", "u32"),
+        ("leaf_lo", "u32"),
+        ("leaf_hi", "u16"),
+        ("unused", "u16"),
+    ];
+
+    let index = common::parse(entry_data, &index_structure, "little")?;
+
+    Ok(index["leaf_lo"] | (index["leaf_hi"] << 32))
+}
+
+/// Parses an EXT directory entry
+pub fn parse_ext_directory_entry(
+    entry_data: &[u8],
+    ext_header: &EXTHeader,
+) -> Result<EXTDirectoryEntry, StructureError> {
+    // File types are recorded in the upper byte of the name length, if the file type feature is enabled
+    const NAME_LENGTH_MASK: usize = 0xFF;
+
+    let directory_entry_structure = vec![
+        ("inode", "u32"),
+        ("entry_size", "u16"),
+        ("name_length", "u16"),
+    ];
+
+    let entry = common::parse(entry_data, &directory_entry_structure, "little")?;
+    let header_size = common::size(&directory_entry_structure);
+
+    let mut name_length = entry["name_length"];
+    if ext_header.incompat_features & FEATURE_INCOMPAT_FILETYPE != 0 {
+        name_length &= NAME_LENGTH_MASK;
+    }
+
+    // Entries are 4-byte aligned, and must be large enough to hold the entry name
+    if entry["entry_size"] < header_size + name_length
+        || !entry["entry_size"].is_multiple_of(4)
+        || entry["entry_size"] > entry_data.len()
+    {
+        return Err(StructureError);
+    }
+
+    Ok(EXTDirectoryEntry {
+        inode: entry["inode"],
+        entry_size: entry["entry_size"],
+        name: entry_data[header_size..header_size + name_length].to_vec(),
+    })
+}
@@ -0,0 +1,126 @@
+use crate::structures::common::{self, StructureError};
+
+/// Offset of the F2FS superblock from the start of the file system; a backup copy follows in the next block
+pub const SUPERBLOCK_OFFSET: usize = 0x400;
+
+/// Struct to store useful F2FS superblock info
+#[derive(Debug, Default, Clone)]
+pub struct F2FSSuperblock {
+    pub major_version: usize,
+    pub minor_version: usize,
+    pub block_size: usize,
+    pub block_count: usize,
+    pub image_size: usize,
+    pub volume_name: String,
+}
+
+/// Parses and validates an F2FS superblock; the provided data must start at the beginning of the F2FS file system
+pub fn parse_f2fs_superblock(f2fs_data: &[u8]) -> Result<F2FSSuperblock, StructureError> {
+    // F2FS only supports 4KB blocks, and 512 blocks per segment
+    const LOG_BLOCK_SIZE: usize = 12;
+    const LOG_BLOCKS_PER_SEGMENT: usize = 9;
+    const BLOCK_SIZE: usize = 1 << LOG_BLOCK_SIZE;
+    const BLOCKS_PER_SEGMENT: usize = 1 << LOG_BLOCKS_PER_SEGMENT;
+    const MIN_LOG_SECTOR_SIZE: usize = 9;
+
+    // The volume name is stored as 512 UTF-16 code units, immediately after the superblock structure
+    const VOLUME_NAME_SIZE: usize = 1024;
+
+    // Partial superblock structure, enough to validate the file system layout and calculate the image size
+    let f2fs_structure = vec![
+        ("magic", "u32"),
+        ("major_version", "u16"),
+        ("minor_version", "u16"),
+        ("log_sector_size", "u32"),
+        ("log_sectors_per_block", "u32"),
+        ("log_block_size", "u32"),
+        ("log_blocks_per_segment", "u32"),
+        ("segments_per_section", "u32"),
+        ("sections_per_zone", "u32"),
+        ("checksum_offset", "u32"),
+        ("block_count", "u64"),
+        ("section_count", "u32"),
+        ("segment_count", "u32"),
+        ("segment_count_checkpoint", "u32"),
+        ("segment_count_sit", "u32"),
+        ("segment_count_nat", "u32"),
+        ("segment_count_ssa", "u32"),
+        ("segment_count_main", "u32"),
+        ("segment0_address", "u32"),
+        ("checkpoint_address", "u32"),
+        ("sit_address", "u32"),
+        ("nat_address", "u32"),
+        ("ssa_address", "u32"),
+        ("main_address", "u32"),
+        ("root_inode", "u32"),
+        ("node_inode", "u32"),
+        ("meta_inode", "u32"),
+        ("uuid_p1", "u64"),
+        ("uuid_p2", "u64"),
+    ];
+
+    let superblock_data = f2fs_data.get(SUPERBLOCK_OFFSET..).ok_or(StructureError)?;
+    let superblock = common::parse(superblock_data, &f2fs_structure, "little")?;
+
+    // Sector size must be between 512 bytes and the block size
+    if superblock["log_block_size"] != LOG_BLOCK_SIZE
+        || superblock["log_blocks_per_segment"] != LOG_BLOCKS_PER_SEGMENT
+        || superblock["log_sector_size"] < MIN_LOG_SECTOR_SIZE
+        || superblock["log_sector_size"] + superblock["log_sectors_per_block"] != LOG_BLOCK_SIZE
+        || superblock["segments_per_section"] == 0
+        || superblock["sections_per_zone"] == 0
+        || superblock["segment_count_main"] == 0
+    {
+        return Err(StructureError);
+    }
+
+    // The checkpoint, SIT, NAT, SSA and main areas are laid out back to back, starting at segment 0
+    let mut area_address = superblock["segment0_address"];
+
+    for (address_field, segment_count_field) in [
+        ("checkpoint_address", "segment_count_checkpoint"),
+        ("sit_address", "segment_count_sit"),
+        ("nat_address", "segment_count_nat"),
+        ("ssa_address", "segment_count_ssa"),
+        ("main_address", "segment_count_main"),
+    ] {
+        if superblock[address_field] != area_address {
+            return Err(StructureError);
+        }
+
+        area_address = superblock[segment_count_field]
+            .checked_mul(BLOCKS_PER_SEGMENT)
+            .and_then(|area_size| area_address.checked_add(area_size))
+            .ok_or(StructureError)?;
+    }
+
+    // All segments must fit within the file system
+    let segments_end = superblock["segment_count"]
+        .checked_mul(BLOCKS_PER_SEGMENT)
+        .and_then(|segments_size| superblock["segment0_address"].checked_add(segments_size))
+        .ok_or(StructureError)?;
+
+    if area_address > segments_end || segments_end > superblock["block_count"] {
+        return Err(StructureError);
+    }
+
+    let volume_name_start = common::size(&f2fs_structure);
+    let volume_name: Vec<u16> = superblock_data
+        .get(volume_name_start..volume_name_start + VOLUME_NAME_SIZE)
+        .ok_or(StructureError)?
+        .chunks_exact(2)
+        .map(|code_unit| u16::from_le_bytes([code_unit[0], code_unit[1]]))
+        .take_while(|code_unit| *code_unit != 0)
+        .collect();
+
+    Ok(F2FSSuperblock {
+        major_version: superblock["major_version"],
+        minor_version: superblock["minor_version"],
+        block_size: BLOCK_SIZE,
+        block_count: superblock["block_count"],
+        image_size: superblock["block_count"]
+            .checked_mul(BLOCK_SIZE)
+            .ok_or(StructureError)?,
+        volume_name: String::from_utf16_lossy(&volume_name),
+    })
+}
@@ -1,10 +1,63 @@
 use crate::structures::common::{self, StructureError};
+use std::collections::HashMap;
+
+/// Size of a FAT directory entry
+pub const DIRECTORY_ENTRY_SIZE: usize = 32;
+
+/// Directory entry attribute: the entry is the volume label
+pub const ATTR_VOLUME_ID: usize = 0x08;
+
+/// Directory entry attribute: the entry is a directory
+pub const ATTR_DIRECTORY: usize = 0x10;
+
+/// Directory entry attributes that identify a VFAT long file name entry
+pub const ATTR_LONG_NAME: usize = 0x0F;
 
 /// Struct to store FAT header info
 #[derive(Debug, Default, Clone)]
 pub struct FATHeader {
     pub is_fat32: bool,
     pub total_size: usize,
+    /// Size of each FAT entry, in bits: 12, 16 or 32
+    pub fat_bits: usize,
+    pub bytes_per_sector: usize,
+    pub cluster_size: usize,
+    pub cluster_count: usize,
+    /// Offset of the first FAT, from the start of the file system
+    pub fat_offset: usize,
+    /// Offset of the fixed size FAT12/16 root directory region, from the start of the file system
+    pub root_directory_offset: usize,
+    pub root_directory_size: usize,
+    /// First cluster of the FAT32 root directory
+    pub root_cluster: usize,
+    /// Offset of cluster 2, the first data cluster, from the start of the file system
+    pub data_offset: usize,
+}
+
+/// Struct to store FAT directory entry info
+#[derive(Debug, Default, Clone)]
+pub struct FATDirectoryEntry {
+    /// The 8.3 short name, including any lowercase flags
+    pub short_name: String,
+    /// The raw 11 byte short name, used to validate the long name checksum
+    pub raw_name: Vec<u8>,
+    pub attributes: usize,
+    pub first_cluster: usize,
+    pub size: usize,
+    pub deleted: bool,
+}
+
+/// Struct to store VFAT long file name entry info
+#[derive(Debug, Default, Clone)]
+pub struct FATLongNameEntry {
+    /// 1-based index of this part of the long name
+    pub sequence: usize,
+    /// True if this is the last part of the long name, which is stored first
+    pub is_last: bool,
+    pub checksum: usize,
+    pub deleted: bool,
+    /// UTF-16 characters of this part of the long name, excluding any terminator and padding
+    pub name: Vec<u16>,
 }
 
 /// Parses a FAT header
@@ -78,7 +131,7 @@ pub fn parse_fat_header(fat_data: &[u8]) -> Result<FATHeader, StructureError> {
 
                                 // If both total_sectors_32 and total_sectors_16 is 0, this is not a valid FAT
                                 if result.total_size > 0 {
-                                    return Ok(result);
+                                    return parse_fat_layout(fat_data, &bs_header, result);
                                 }
                             }
                         }
@@ -90,3 +143,196 @@ pub fn parse_fat_header(fat_data: &[u8]) -> Result<FATHeader, StructureError> {
 
     Err(StructureError)
 }
+
+/// Determines the location of the FATs, root directory and data clusters, and the FAT type
+fn parse_fat_layout(
+    fat_data: &[u8],
+    bs_header: &HashMap<String, usize>,
+    mut fat_header: FATHeader,
+) -> Result<FATHeader, StructureError> {
+    // Offset of the FAT32 extended boot sector fields
+    const FAT32_FIELDS_OFFSET: usize = 0x24;
+
+    // FAT12 and FAT16 are distinguished by the number of clusters
+    const MAX_FAT12_CLUSTERS: usize = 4084;
+
+    let fat32_structure = vec![
+        ("fat_size_32", "u32"),
+        ("ext_flags", "u16"),
+        ("fs_version", "u16"),
+        ("root_cluster", "u32"),
+    ];
+
+    let bytes_per_sector = bs_header["bytes_per_sector"];
+    let mut fat_size = bs_header["fat_size_16"];
+
+    if fat_header.is_fat32 {
+        let fat32_fields = common::parse(
+            fat_data.get(FAT32_FIELDS_OFFSET..).ok_or(StructureError)?,
+            &fat32_structure,
+            "little",
+        )?;
+        fat_size = fat32_fields["fat_size_32"];
+        fat_header.root_cluster = fat32_fields["root_cluster"];
+    }
+
+    fat_header.bytes_per_sector = bytes_per_sector;
+    fat_header.cluster_size = bs_header["sectors_per_cluster"] * bytes_per_sector;
+    fat_header.fat_offset = bs_header["reserved_sectors"] * bytes_per_sector;
+    fat_header.root_directory_offset =
+        fat_header.fat_offset + (fat_size * bs_header["fat_count"] * bytes_per_sector);
+    fat_header.root_directory_size = (bs_header["root_entries_count_16"] * DIRECTORY_ENTRY_SIZE)
+        .next_multiple_of(bytes_per_sector);
+    fat_header.data_offset = fat_header.root_directory_offset + fat_header.root_directory_size;
+
+    // The FATs and root directory must fit in the file system
+    if fat_size == 0 || fat_header.data_offset > fat_header.total_size {
+        return Err(StructureError);
+    }
+
+    fat_header.cluster_count =
+        (fat_header.total_size - fat_header.data_offset) / fat_header.cluster_size;
+
+    fat_header.fat_bits = if fat_header.is_fat32 {
+        32
+    } else if fat_header.cluster_count <= MAX_FAT12_CLUSTERS {
+        12
+    } else {
+        16
+    };
+
+    Ok(fat_header)
+}
+
+/// Parses a FAT directory entry
+pub fn parse_fat_directory_entry(entry_data: &[u8]) -> Result<FATDirectoryEntry, StructureError> {
+    // Marks a deleted entry; a first name byte of 0x05 represents an actual 0xE5 character
+    const DELETED_MARKER: u8 = 0xE5;
+    const ESCAPED_DELETED_MARKER: u8 = 0x05;
+
+    // Flags indicating that the base name and extension should be displayed in lowercase
+    const LOWERCASE_BASE: usize = 0x08;
+    const LOWERCASE_EXTENSION: usize = 0x10;
+
+    const NAME_SIZE: usize = 11;
+    const BASE_NAME_SIZE: usize = 8;
+
+    let directory_entry_structure = vec![
+        ("name", "u64"),
+        ("extension", "u24"),
+        ("attributes", "u8"),
+        ("nt_reserved", "u8"),
+        ("create_time_tenth", "u8"),
+        ("create_time", "u16"),
+        ("create_date", "u16"),
+        ("access_date", "u16"),
+        ("first_cluster_hi", "u16"),
+        ("write_time", "u16"),
+        ("write_date", "u16"),
+        ("first_cluster_lo", "u16"),
+        ("size", "u32"),
+    ];
+
+    let entry = common::parse(entry_data, &directory_entry_structure, "little")?;
+
+    let mut raw_name = entry_data[..NAME_SIZE].to_vec();
+    let deleted = raw_name[0] == DELETED_MARKER;
+
+    // The first character of a deleted entry is lost
+    let mut display_name = raw_name.clone();
+    if deleted {
+        display_name[0] = b'_';
+    } else if raw_name[0] == ESCAPED_DELETED_MARKER {
+        display_name[0] = DELETED_MARKER;
+        raw_name[0] = DELETED_MARKER;
+    }
+
+    let mut base_name = String::from_utf8_lossy(&display_name[..BASE_NAME_SIZE])
+        .trim_end()
+        .to_string();
+    let mut extension = String::from_utf8_lossy(&display_name[BASE_NAME_SIZE..])
+        .trim_end()
+        .to_string();
+
+    if entry["nt_reserved"] & LOWERCASE_BASE != 0 {
+        base_name = base_name.to_lowercase();
+    }
+
+    if entry["nt_reserved"] & LOWERCASE_EXTENSION != 0 {
+        extension = extension.to_lowercase();
+    }
+
+    let short_name = match extension.is_empty() {
+        true => base_name,
+        false => format!("{base_name}.{extension}"),
+    };
+
+    Ok(FATDirectoryEntry {
+        short_name,
+        raw_name,
+        attributes: entry["attributes"],
+        first_cluster: entry["first_cluster_lo"] | (entry["first_cluster_hi"] << 16),
+        size: entry["size"],
+        deleted,
+    })
+}
+
+/// Parses a VFAT long file name directory entry
+pub fn parse_fat_long_name_entry(entry_data: &[u8]) -> Result<FATLongNameEntry, StructureError> {
+    const DELETED_MARKER: usize = 0xE5;
+    const LAST_ENTRY_FLAG: usize = 0x40;
+    const SEQUENCE_MASK: usize = 0x1F;
+
+    // Offsets and sizes of the three parts of the name stored in each entry
+    const NAME_PARTS: [(usize, usize); 3] = [(1, 10), (14, 12), (28, 4)];
+
+    let long_name_structure = vec![
+        ("order", "u8"),
+        ("name1", "u64"),
+        ("name1_end", "u16"),
+        ("attributes", "u8"),
+        ("type", "u8"),
+        ("checksum", "u8"),
+        ("name2", "u64"),
+        ("name2_end", "u32"),
+        ("first_cluster", "u16"),
+        ("name3", "u32"),
+    ];
+
+    let entry = common::parse(entry_data, &long_name_structure, "little")?;
+
+    if entry["attributes"] != ATTR_LONG_NAME || entry["first_cluster"] != 0 {
+        return Err(StructureError);
+    }
+
+    // Names are terminated by a NULL character, and padded with 0xFFFF
+    let name: Vec<u16> = NAME_PARTS
+        .iter()
+        .flat_map(|(part_offset, part_size)| {
+            entry_data[*part_offset..part_offset + part_size].chunks_exact(2)
+        })
+        .map(|character| u16::from_le_bytes(character.try_into().unwrap()))
+        .take_while(|character| *character != 0 && *character != 0xFFFF)
+        .collect();
+
+    let deleted = entry["order"] == DELETED_MARKER;
+
+    Ok(FATLongNameEntry {
+        sequence: entry["order"] & SEQUENCE_MASK,
+        is_last: !deleted && entry["order"] & LAST_ENTRY_FLAG != 0,
+        checksum: entry["checksum"],
+        deleted,
+        name,
+    })
+}
+
+/// Calculates the checksum of a raw 11 byte short name, as stored in the associated long file name entries
+pub fn fat_short_name_checksum(raw_name: &[u8]) -> usize {
+    let mut checksum: u8 = 0;
+
+    for byte in raw_name {
+        checksum = checksum.rotate_right(1).wrapping_add(*byte);
+    }
+
+    checksum as usize
+}
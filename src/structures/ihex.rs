@@ -0,0 +1,139 @@
+use crate::structures::common::StructureError;
+
+/// Maximum address span of a decoded image; gaps between records are filled, so sparse images could otherwise be huge
+const MAX_IMAGE_SPAN: usize = 256 * 1024 * 1024;
+
+/// Maximum length of a single text record line, including the line terminator
+const MAX_LINE_LENGTH: usize = 1024;
+
+/// A binary image decoded from the data records of an Intel HEX or Motorola S-record file
+#[derive(Debug, Default, Clone)]
+pub struct MemoryImage {
+    /// Size of the text records, up to and including the end of file record's line terminator
+    pub size: usize,
+    /// Load address and data of each data record, in file order
+    pub records: Vec<(usize, Vec<u8>)>,
+}
+
+impl MemoryImage {
+    /// Returns the lowest and highest (exclusive) addresses covered by the image's data records
+    pub fn address_span(&self) -> Option<(usize, usize)> {
+        let start = self.records.iter().map(|(address, _)| *address).min()?;
+        let end = self
+            .records
+            .iter()
+            .map(|(address, data)| address + data.len())
+            .max()?;
+        Some((start, end))
+    }
+
+    /// Returns the raw binary image, starting at the lowest record address; gaps between records are filled with 0xFF
+    pub fn to_binary(&self) -> Vec<u8> {
+        const FILL_BYTE: u8 = 0xFF;
+
+        let Some((start, end)) = self.address_span() else {
+            return vec![];
+        };
+
+        let mut binary = vec![FILL_BYTE; end - start];
+
+        for (address, data) in &self.records {
+            binary[address - start..address - start + data.len()].copy_from_slice(data);
+        }
+
+        binary
+    }
+
+    /// Validates the decoded records once the end of file record has been reached at `size`
+    pub fn finish(mut self, size: usize) -> Result<MemoryImage, StructureError> {
+        match self.address_span() {
+            Some((start, end)) if end - start <= MAX_IMAGE_SPAN => {
+                self.size = size;
+                Ok(self)
+            }
+            _ => Err(StructureError),
+        }
+    }
+}
+
+/// Returns the text record line that starts at `offset`, without its line terminator, and the offset of the next line
+pub fn record_line(text_data: &[u8], offset: usize) -> Option<(&[u8], usize)> {
+    const LINE_FEED: u8 = b'\n';
+    const CARRIAGE_RETURN: u8 = b'\r';
+
+    let remaining_data = text_data.get(offset..)?;
+    let search_data = &remaining_data[..remaining_data.len().min(MAX_LINE_LENGTH)];
+
+    // The last line of the file need not be terminated
+    let (line, next_offset) = match search_data.iter().position(|b| *b == LINE_FEED) {
+        Some(line_end) => (&search_data[..line_end], offset + line_end + 1),
+        None if remaining_data.len() <= MAX_LINE_LENGTH => {
+            (remaining_data, offset + remaining_data.len())
+        }
+        None => return None,
+    };
+
+    Some((
+        line.strip_suffix(&[CARRIAGE_RETURN]).unwrap_or(line),
+        next_offset,
+    ))
+}
+
+/// Parses Intel HEX records up to, and including, the end of file record; every record's checksum is validated
+pub fn parse_ihex(ihex_data: &[u8]) -> Result<MemoryImage, StructureError> {
+    // Record types
+    const DATA: u8 = 0x00;
+    const END_OF_FILE: u8 = 0x01;
+    const EXTENDED_SEGMENT_ADDRESS: u8 = 0x02;
+    const START_SEGMENT_ADDRESS: u8 = 0x03;
+    const EXTENDED_LINEAR_ADDRESS: u8 = 0x04;
+    const START_LINEAR_ADDRESS: u8 = 0x05;
+    // Size of the byte count, address, and record type fields that precede the record data
+    const RECORD_HEADER_SIZE: usize = 4;
+    const CHECKSUM_SIZE: usize = 1;
+
+    let mut image = MemoryImage::default();
+    let mut base_address: usize = 0;
+    let mut offset: usize = 0;
+
+    while let Some((line, next_offset)) = record_line(ihex_data, offset) {
+        let record_hex = line.strip_prefix(b":").ok_or(StructureError)?;
+        let record = hex::decode(record_hex).map_err(|_| StructureError)?;
+
+        if record.len() < RECORD_HEADER_SIZE + CHECKSUM_SIZE
+            || record.len() != RECORD_HEADER_SIZE + record[0] as usize + CHECKSUM_SIZE
+        {
+            return Err(StructureError);
+        }
+
+        // The two's complement checksum makes the sum of all record bytes zero
+        if record
+            .iter()
+            .fold(0_u8, |sum, byte| sum.wrapping_add(*byte))
+            != 0
+        {
+            return Err(StructureError);
+        }
+
+        let address = u16::from_be_bytes([record[1], record[2]]) as usize;
+        let data = &record[RECORD_HEADER_SIZE..record.len() - CHECKSUM_SIZE];
+
+        match (record[3], data.len()) {
+            (DATA, _) => image.records.push((base_address + address, data.to_vec())),
+            (END_OF_FILE, 0) => return image.finish(next_offset),
+            (EXTENDED_SEGMENT_ADDRESS, 2) => {
+                base_address = (u16::from_be_bytes([data[0], data[1]]) as usize) << 4
+            }
+            (EXTENDED_LINEAR_ADDRESS, 2) => {
+                base_address = (u16::from_be_bytes([data[0], data[1]]) as usize) << 16
+            }
+            // Start addresses do not affect the image contents
+            (START_SEGMENT_ADDRESS, 4) | (START_LINEAR_ADDRESS, 4) => (),
+            _ => return Err(StructureError),
+        }
+
+        offset = next_offset;
+    }
+
+    Err(StructureError)
+}
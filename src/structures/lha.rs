@@ -0,0 +1,269 @@
+use crate::common::crc16;
+use crate::structures::common::{self, StructureError};
+
+/// Method ID of LHA directory entries
+pub const LHA_DIRECTORY_METHOD: &str = "-lhd-";
+
+/// Describes an LHA member header
+#[derive(Debug, Default, Clone)]
+pub struct LHAHeader {
+    /// Total size of the header, including any extended headers; the member's data immediately follows
+    pub header_size: usize,
+    pub level: usize,
+    /// Compression method ID, e.g. "-lh5-"
+    pub method: String,
+    pub compressed_size: usize,
+    pub uncompressed_size: usize,
+    /// CRC-16 of the uncompressed data
+    pub data_crc: u16,
+    /// Member path, with '/' path separators
+    pub name: String,
+}
+
+/// Parses an LHA member header; level 0, 1 and 2 headers are supported
+pub fn parse_lha_header(lha_data: &[u8]) -> Result<LHAHeader, StructureError> {
+    // Offset of the header level, which is common to all header levels
+    const LEVEL_OFFSET: usize = 20;
+
+    match lha_data.get(LEVEL_OFFSET) {
+        Some(0) | Some(1) => parse_lha_level01_header(lha_data),
+        Some(2) => parse_lha_level2_header(lha_data),
+        _ => Err(StructureError),
+    }
+}
+
+/// Parses level 0 and level 1 headers, which are validated by an 8-bit checksum
+fn parse_lha_level01_header(lha_data: &[u8]) -> Result<LHAHeader, StructureError> {
+    // Size of the header size and checksum fields, which are not included in the header size
+    const PREFIX_SIZE: usize = 2;
+    // Size of the data CRC field that follows the file name
+    const CRC_SIZE: usize = 2;
+    // Size of the OS ID and first extended header size fields that follow the data CRC in level 1 headers
+    const LEVEL1_TRAILER_SIZE: usize = 3;
+
+    let header_structure = vec![
+        ("header_size", "u8"),
+        ("header_checksum", "u8"),
+        ("method_p1", "u32"),
+        ("method_p2", "u8"),
+        ("compressed_size", "u32"),
+        ("uncompressed_size", "u32"),
+        ("timestamp", "u32"),
+        ("attributes", "u8"),
+        ("level", "u8"),
+        ("name_length", "u8"),
+    ];
+
+    let header = common::parse(lha_data, &header_structure, "little")?;
+
+    let header_end = PREFIX_SIZE + header["header_size"];
+    let header_data = lha_data
+        .get(PREFIX_SIZE..header_end)
+        .ok_or(StructureError)?;
+
+    let checksum = header_data
+        .iter()
+        .fold(0_u8, |sum, byte| sum.wrapping_add(*byte));
+    if checksum as usize != header["header_checksum"] {
+        return Err(StructureError);
+    }
+
+    let name_start = common::size(&header_structure);
+    let name_end = name_start + header["name_length"];
+    let crc_end = name_end + CRC_SIZE;
+
+    let mut minimum_header_end = crc_end;
+    if header["level"] == 1 {
+        minimum_header_end += LEVEL1_TRAILER_SIZE;
+    }
+
+    if minimum_header_end > header_end {
+        return Err(StructureError);
+    }
+
+    let mut lha_header = LHAHeader {
+        header_size: header_end,
+        level: header["level"],
+        method: method_id(lha_data)?,
+        compressed_size: header["compressed_size"],
+        uncompressed_size: header["uncompressed_size"],
+        data_crc: u16::from_le_bytes(lha_data[name_end..crc_end].try_into().unwrap()),
+        name: member_name(&lha_data[name_start..name_end]),
+    };
+
+    // Level 1 headers are followed by extended headers, which are included in the compressed size
+    if lha_header.level == 1 {
+        let first_extended_header_size =
+            u16::from_le_bytes(lha_data[header_end - 2..header_end].try_into().unwrap()) as usize;
+
+        let extended_headers_size = parse_extended_headers(
+            lha_data,
+            header_end,
+            first_extended_header_size,
+            &mut lha_header,
+        )?;
+
+        lha_header.header_size += extended_headers_size;
+        lha_header.compressed_size = lha_header
+            .compressed_size
+            .checked_sub(extended_headers_size)
+            .ok_or(StructureError)?;
+    }
+
+    Ok(lha_header)
+}
+
+/// Parses level 2 headers, which are optionally validated by a CRC-16 in the common extended header
+fn parse_lha_level2_header(lha_data: &[u8]) -> Result<LHAHeader, StructureError> {
+    let header_structure = vec![
+        ("header_size", "u16"),
+        ("method_p1", "u32"),
+        ("method_p2", "u8"),
+        ("compressed_size", "u32"),
+        ("uncompressed_size", "u32"),
+        ("timestamp", "u32"),
+        ("reserved", "u8"),
+        ("level", "u8"),
+        ("data_crc", "u16"),
+        ("os_id", "u8"),
+        ("first_extended_header_size", "u16"),
+    ];
+
+    let header = common::parse(lha_data, &header_structure, "little")?;
+
+    let mut lha_header = LHAHeader {
+        header_size: header["header_size"],
+        level: header["level"],
+        method: method_id(lha_data)?,
+        compressed_size: header["compressed_size"],
+        uncompressed_size: header["uncompressed_size"],
+        data_crc: header["data_crc"] as u16,
+        ..Default::default()
+    };
+
+    let extended_headers_start = common::size(&header_structure);
+    let extended_headers_size = parse_extended_headers(
+        lha_data,
+        extended_headers_start,
+        header["first_extended_header_size"],
+        &mut lha_header,
+    )?;
+
+    // The header may be padded with a single byte
+    let header_size = extended_headers_start + extended_headers_size;
+    if header_size != lha_header.header_size && header_size + 1 != lha_header.header_size {
+        return Err(StructureError);
+    }
+
+    Ok(lha_header)
+}
+
+/// Parses the chain of extended headers that starts at `offset`; the name and directory extended headers are used
+/// to update the member name. Returns the total size of the extended headers.
+fn parse_extended_headers(
+    lha_data: &[u8],
+    offset: usize,
+    first_header_size: usize,
+    lha_header: &mut LHAHeader,
+) -> Result<usize, StructureError> {
+    // Extended header types
+    const COMMON_HEADER: u8 = 0x00;
+    const FILE_NAME_HEADER: u8 = 0x01;
+    const DIRECTORY_NAME_HEADER: u8 = 0x02;
+    // Size of the type field at the start, and the next header size field at the end, of each extended header
+    const TYPE_SIZE: usize = 1;
+    const NEXT_SIZE_SIZE: usize = 2;
+
+    let mut file_name: Option<String> = None;
+    let mut directory_name: Option<String> = None;
+
+    let mut header_offset = offset;
+    let mut header_size = first_header_size;
+
+    while header_size != 0 {
+        if header_size < TYPE_SIZE + NEXT_SIZE_SIZE {
+            return Err(StructureError);
+        }
+
+        let header_data = lha_data
+            .get(header_offset..header_offset + header_size)
+            .ok_or(StructureError)?;
+        let header_payload = &header_data[TYPE_SIZE..header_size - NEXT_SIZE_SIZE];
+
+        match header_data[0] {
+            // The common header stores the CRC of the entire header, calculated with the CRC field itself zeroed
+            COMMON_HEADER if lha_header.level == 2 && header_payload.len() >= 2 => {
+                let crc_offset = header_offset + TYPE_SIZE;
+                let expected_crc = u16::from_le_bytes(header_payload[0..2].try_into().unwrap());
+
+                let mut crc_data = lha_data
+                    .get(..lha_header.header_size)
+                    .ok_or(StructureError)?
+                    .to_vec();
+                crc_data
+                    .get_mut(crc_offset..crc_offset + 2)
+                    .ok_or(StructureError)?
+                    .fill(0);
+
+                if crc16(&crc_data) != expected_crc {
+                    return Err(StructureError);
+                }
+            }
+            FILE_NAME_HEADER => file_name = Some(member_name(header_payload)),
+            // Directory names are terminated, and their components separated, by 0xFF bytes
+            DIRECTORY_NAME_HEADER => {
+                let directory: Vec<u8> = header_payload
+                    .iter()
+                    .map(|byte| if *byte == 0xFF { b'/' } else { *byte })
+                    .collect();
+                directory_name = Some(member_name(&directory));
+            }
+            _ => (),
+        }
+
+        header_offset += header_size;
+        header_size = u16::from_le_bytes(
+            header_data[header_data.len() - NEXT_SIZE_SIZE..]
+                .try_into()
+                .unwrap(),
+        ) as usize;
+    }
+
+    if let Some(file_name) = file_name {
+        lha_header.name = file_name;
+    }
+
+    if let Some(directory_name) = directory_name {
+        lha_header.name = format!(
+            "{}/{}",
+            directory_name.trim_end_matches('/'),
+            lha_header.name
+        );
+    }
+
+    Ok(header_offset - offset)
+}
+
+/// Returns the compression method ID, which must be of the form "-xxx-"
+fn method_id(lha_data: &[u8]) -> Result<String, StructureError> {
+    const METHOD_START: usize = 2;
+    const METHOD_END: usize = 7;
+
+    let method = lha_data
+        .get(METHOD_START..METHOD_END)
+        .ok_or(StructureError)?;
+
+    if method[0] != b'-'
+        || method[4] != b'-'
+        || !method[1..4].iter().all(|byte| byte.is_ascii_alphanumeric())
+    {
+        return Err(StructureError);
+    }
+
+    Ok(String::from_utf8_lossy(method).to_string())
+}
+
+/// Converts a member name to a string; names created on MS-DOS use '\' as a path separator
+fn member_name(name: &[u8]) -> String {
+    String::from_utf8_lossy(name).replace('\\', "/")
+}
@@ -12,6 +12,20 @@ pub struct LinuxARM64BootHeader {
 #[derive(Debug, Default, Clone)]
 pub struct LinuxARMzImageHeader {
     pub endianness: String,
+    /// Size of the zImage, or 0 if unknown
+    pub image_size: usize,
+}
+
+/// Struct to store Linux x86 bzImage setup header info
+#[derive(Debug, Default, Clone)]
+pub struct LinuxBzImageHeader {
+    /// Size of the real-mode setup code; the protected-mode kernel immediately follows it
+    pub setup_size: usize,
+    /// Total size of the bzImage
+    pub image_size: usize,
+    /// Offset of the compressed kernel, relative to the start of the bzImage; only available since boot protocol 2.08
+    pub payload_offset: Option<usize>,
+    pub payload_size: usize,
 }
 
 /// Parses a Linux ARM zImage header
@@ -20,6 +34,10 @@ pub fn parse_linux_arm_zimage_header(
 ) -> Result<LinuxARMzImageHeader, StructureError> {
     const NOP_LE: usize = 0xE1A00000;
     const NOP_BE: usize = 0x0000A0E1;
+    // Offset of the zImage magic bytes, followed by the start and end addresses
+    const ADDRESSES_OFFSET: usize = 0x24;
+
+    let address_structure = vec![("magic", "u32"), ("start", "u32"), ("end", "u32")];
 
     let zimage_structure = vec![
         ("nop1", "u32"),
@@ -41,17 +59,27 @@ pub fn parse_linux_arm_zimage_header(
             && zimage_nops["nop1"] == zimage_nops["nop7"]
             && zimage_nops["nop1"] == zimage_nops["nop8"]
         {
-            if zimage_nops["nop1"] == NOP_LE {
-                return Ok(LinuxARMzImageHeader {
-                    endianness: "little".to_string(),
-                });
-            }
+            let endianness = match zimage_nops["nop1"] {
+                NOP_LE => "little",
+                NOP_BE => "big",
+                _ => return Err(StructureError),
+            };
 
-            if zimage_nops["nop1"] == NOP_BE {
-                return Ok(LinuxARMzImageHeader {
-                    endianness: "big".to_string(),
-                });
-            }
+            // The zImage start and end addresses follow the magic bytes; if they are sane, they give the image size
+            let image_size = match zimage_data.get(ADDRESSES_OFFSET..) {
+                None => 0,
+                Some(address_data) => {
+                    match common::parse(address_data, &address_structure, endianness) {
+                        Err(_) => 0,
+                        Ok(addresses) => addresses["end"].saturating_sub(addresses["start"]),
+                    }
+                }
+            };
+
+            return Ok(LinuxARMzImageHeader {
+                endianness: endianness.to_string(),
+                image_size,
+            });
         }
     }
 
@@ -123,3 +151,70 @@ pub fn parse_linux_arm64_boot_image_header(
 
     Err(StructureError)
 }
+
+/// Parses a Linux x86 bzImage setup header
+pub fn parse_linux_bzimage_header(
+    bzimage_data: &[u8],
+) -> Result<LinuxBzImageHeader, StructureError> {
+    // Offset of the setup header in the boot sector
+    const SETUP_HEADER_OFFSET: usize = 0x1F1;
+    // Offset of the payload location fields, relative to the setup header
+    const PAYLOAD_FIELDS_OFFSET: usize = 0x248 - SETUP_HEADER_OFFSET;
+    const BOOT_FLAG: usize = 0xAA55;
+    const HDRS_MAGIC: usize = 0x53726448;
+    const SECTOR_SIZE: usize = 512;
+    // A setup_sects value of 0 means 4 setup sectors
+    const DEFAULT_SETUP_SECTORS: usize = 4;
+    // The size of the protected-mode kernel is stored in 16 byte units
+    const SYSSIZE_UNIT: usize = 16;
+    const PAYLOAD_FIELDS_MIN_VERSION: usize = 0x208;
+
+    // https://www.kernel.org/doc/html/latest/arch/x86/boot.html
+    let setup_header_structure = vec![
+        ("setup_sects", "u8"),
+        ("root_flags", "u16"),
+        ("syssize", "u32"),
+        ("ram_size", "u16"),
+        ("vid_mode", "u16"),
+        ("root_dev", "u16"),
+        ("boot_flag", "u16"),
+        ("jump", "u16"),
+        ("header", "u32"),
+        ("version", "u16"),
+    ];
+
+    let payload_structure = vec![("payload_offset", "u32"), ("payload_length", "u32")];
+
+    let setup_header_data = bzimage_data
+        .get(SETUP_HEADER_OFFSET..)
+        .ok_or(StructureError)?;
+    let setup_header = common::parse(setup_header_data, &setup_header_structure, "little")?;
+
+    if setup_header["boot_flag"] != BOOT_FLAG || setup_header["header"] != HDRS_MAGIC {
+        return Err(StructureError);
+    }
+
+    let setup_sectors = match setup_header["setup_sects"] {
+        0 => DEFAULT_SETUP_SECTORS,
+        sectors => sectors,
+    };
+
+    let mut header = LinuxBzImageHeader {
+        // The boot sector precedes the setup sectors
+        setup_size: (setup_sectors + 1) * SECTOR_SIZE,
+        ..Default::default()
+    };
+
+    header.image_size = header.setup_size + (setup_header["syssize"] * SYSSIZE_UNIT);
+
+    // Payload offsets are relative to the start of the protected-mode kernel
+    if setup_header["version"] >= PAYLOAD_FIELDS_MIN_VERSION
+        && let Some(payload_data) = setup_header_data.get(PAYLOAD_FIELDS_OFFSET..)
+    {
+        let payload = common::parse(payload_data, &payload_structure, "little")?;
+        header.payload_offset = Some(header.setup_size + payload["payload_offset"]);
+        header.payload_size = payload["payload_length"];
+    }
+
+    Ok(header)
+}
@@ -3,11 +3,22 @@ use crate::structures::common::{self, StructureError};
 /// LZO checksums are 4-bytes long
 const LZO_CHECKSUM_SIZE: usize = 4;
 
+/// Checksum algorithms used by LZOP data blocks
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LZOPChecksumType {
+    #[default]
+    None,
+    Adler32,
+    Crc32,
+}
+
 /// Struct to store LZOP file header info
 #[derive(Debug, Default, Clone)]
 pub struct LZOPFileHeader {
     pub header_size: usize,
     pub block_checksum_present: bool,
+    pub uncompressed_checksum: LZOPChecksumType,
+    pub compressed_checksum: LZOPChecksumType,
 }
 
 /// Parse an LZOP file header
@@ -21,9 +32,9 @@ pub fn parse_lzop_file_header(lzop_data: &[u8]) -> Result<LZOPFileHeader, Struct
     const FILTER_SIZE: usize = 4;
 
     const FLAG_FILTER: usize = 0x000_00800;
-    //const FLAG_CRC32_D: usize = 0x0000_0100;
+    const FLAG_CRC32_D: usize = 0x0000_0100;
     const FLAG_CRC32_C: usize = 0x0000_0200;
-    //const FLAG_ADLER32_D: usize = 0x0000_0001;
+    const FLAG_ADLER32_D: usize = 0x0000_0001;
     const FLAG_ADLER32_C: usize = 0x0000_0002;
 
     let lzo_structure_p1 = vec![
@@ -80,7 +91,13 @@ pub fn parse_lzop_file_header(lzop_data: &[u8]) -> Result<LZOPFileHeader, Struct
 
                         // Check if block headers include an optional compressed data checksum field
                         lzop_info.block_checksum_present =
-                            (lzo_header_p1["flags"] & FLAG_ADLER32_C & FLAG_CRC32_C) != 0;
+                            (lzo_header_p1["flags"] & (FLAG_ADLER32_C | FLAG_CRC32_C)) != 0;
+
+                        // Determine which checksums are used for the uncompressed and compressed block data
+                        lzop_info.uncompressed_checksum =
+                            checksum_type(lzo_header_p1["flags"], FLAG_ADLER32_D, FLAG_CRC32_D);
+                        lzop_info.compressed_checksum =
+                            checksum_type(lzo_header_p1["flags"], FLAG_ADLER32_C, FLAG_CRC32_C);
 
                         // Sanity check on the calculated header size
                         if lzop_info.header_size <= lzop_data.len() {
@@ -95,12 +112,25 @@ pub fn parse_lzop_file_header(lzop_data: &[u8]) -> Result<LZOPFileHeader, Struct
     Err(StructureError)
 }
 
+/// Returns the checksum type indicated by the LZOP header flags; Adler32 takes precedence, as it does in lzop
+fn checksum_type(flags: usize, adler32_flag: usize, crc32_flag: usize) -> LZOPChecksumType {
+    if (flags & adler32_flag) != 0 {
+        LZOPChecksumType::Adler32
+    } else if (flags & crc32_flag) != 0 {
+        LZOPChecksumType::Crc32
+    } else {
+        LZOPChecksumType::None
+    }
+}
+
 /// Struct to store info on LZOP block headers
 #[derive(Debug, Default, Clone)]
 pub struct LZOPBlockHeader {
     pub header_size: usize,
     pub compressed_size: usize,
     pub uncompressed_size: usize,
+    pub uncompressed_checksum: u32,
+    pub compressed_checksum: Option<u32>,
     pub checksum_size: usize,
 }
 
@@ -134,10 +164,20 @@ pub fn parse_lzop_block_header(
             block_hdr_info.header_size = BLOCK_HEADER_SIZE;
             block_hdr_info.compressed_size = block_header["compressed_size"];
             block_hdr_info.uncompressed_size = block_header["uncompressed_size"];
+            block_hdr_info.uncompressed_checksum = block_header["uncompressed_checksum"] as u32;
+
+            // Checksum field is optional, and omitted for blocks that are stored uncompressed
+            if compressed_checksum_present
+                && block_hdr_info.compressed_size < block_hdr_info.uncompressed_size
+            {
+                let checksum_end = BLOCK_HEADER_SIZE + LZO_CHECKSUM_SIZE;
+                let checksum_bytes = lzo_data
+                    .get(BLOCK_HEADER_SIZE..checksum_end)
+                    .ok_or(StructureError)?;
 
-            // Checksum field is optional
-            if compressed_checksum_present {
                 block_hdr_info.checksum_size = LZO_CHECKSUM_SIZE;
+                block_hdr_info.compressed_checksum =
+                    Some(u32::from_be_bytes(checksum_bytes.try_into().unwrap()));
             }
 
             return Ok(block_hdr_info);
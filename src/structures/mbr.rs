@@ -1,12 +1,16 @@
 use crate::structures::common::{self, StructureError};
 use std::collections::HashMap;
 
+/// Partition type of the protective partition that covers the entire disk in GPT disk images
+pub const MBR_GPT_PROTECTIVE_TYPE: usize = 0xEE;
+
 /// Struct to store MBR partition info
 #[derive(Debug, Default, Clone)]
 pub struct MBRPartition {
     pub start: usize,
     pub size: usize,
     pub name: String,
+    pub os_type: usize,
 }
 
 /// Struct to store MBR info
@@ -47,7 +51,7 @@ pub fn parse_mbr_image(mbr_data: &[u8]) -> Result<MBRHeader, StructureError> {
         (0xB1, "QNXv6 File System"),
         (0xB2, "QNXv6 File System"),
         (0xB3, "QNXv6 File System"),
-        (0xEE, "EFI GPT Protective"),
+        (MBR_GPT_PROTECTIVE_TYPE, "EFI GPT Protective"),
         (0xEF, "EFI System Partition"),
     ]);
 
@@ -96,6 +100,7 @@ pub fn parse_mbr_image(mbr_data: &[u8]) -> Result<MBRHeader, StructureError> {
                                 start: partition_entry["lba_start"] * BLOCK_SIZE,
                                 size: partition_entry["lba_size"] * BLOCK_SIZE,
                                 name: this_partition_name.to_string(),
+                                os_type: partition_entry["os_type"],
                             };
 
                             // Calculate where this partition ends
@@ -0,0 +1,310 @@
+use crate::structures::common::{self, StructureError};
+
+/// Expected file offset of a Minix superblock
+pub const SUPERBLOCK_OFFSET: usize = 1024;
+
+/// Size of the Minix superblock; it occupies the second 1KB block of the file system
+pub const SUPERBLOCK_SIZE: usize = 1024;
+
+/// Offset of the magic bytes in a version 1 or 2 superblock
+pub const V2_MAGIC_OFFSET: usize = 16;
+
+/// Offset of the magic bytes in a version 3 superblock
+pub const V3_MAGIC_OFFSET: usize = 24;
+
+/// Inode number of the root directory
+pub const ROOT_INODE: usize = 1;
+
+/// Number of direct zone pointers in an inode; they are followed by single, double and (v2/v3 only) triple indirect pointers
+pub const DIRECT_ZONES: usize = 7;
+
+/// Struct to store some useful Minix info
+#[derive(Debug, Default, Clone)]
+pub struct MinixHeader {
+    /// File system version: 1, 2 or 3
+    pub version: usize,
+    pub endianness: String,
+    pub block_size: usize,
+    pub image_size: usize,
+    pub inodes_count: usize,
+    pub zones_count: usize,
+    /// Block number of the first inode
+    pub inode_table: usize,
+    pub inode_size: usize,
+    /// Size of the zone pointers in inodes and indirect zones
+    pub pointer_size: usize,
+    /// Maximum length of a directory entry name
+    pub name_length: usize,
+    pub directory_entry_size: usize,
+}
+
+/// Struct to store Minix inode info
+#[derive(Debug, Default, Clone)]
+pub struct MinixInode {
+    pub mode: usize,
+    pub uid: usize,
+    pub gid: usize,
+    pub size: usize,
+    pub mtime: usize,
+    /// Direct zone pointers, followed by the indirect zone pointers
+    pub zones: Vec<usize>,
+}
+
+/// Struct to store Minix directory entry info
+#[derive(Debug, Default, Clone)]
+pub struct MinixDirectoryEntry {
+    pub inode: usize,
+    pub name: Vec<u8>,
+}
+
+/// Parses a Minix v1, v2 or v3 superblock, in either byte order
+pub fn parse_minix_header(minix_data: &[u8]) -> Result<MinixHeader, StructureError> {
+    let superblock_data = minix_data
+        .get(SUPERBLOCK_OFFSET..SUPERBLOCK_OFFSET + SUPERBLOCK_SIZE)
+        .ok_or(StructureError)?;
+
+    for endianness in ["little", "big"] {
+        if let Ok(minix_header) = parse_minix_superblock(superblock_data, endianness) {
+            return Ok(minix_header);
+        }
+    }
+
+    Err(StructureError)
+}
+
+/// Parses a Minix superblock with the specified byte order
+fn parse_minix_superblock(
+    superblock_data: &[u8],
+    endianness: &str,
+) -> Result<MinixHeader, StructureError> {
+    // Magic bytes identify the version and the maximum directory entry name length
+    const MINIX_V1_MAGIC: usize = 0x137F;
+    const MINIX_V1_30_MAGIC: usize = 0x138F;
+    const MINIX_V2_MAGIC: usize = 0x2468;
+    const MINIX_V2_30_MAGIC: usize = 0x2478;
+    const MINIX_V3_MAGIC: usize = 0x4D5A;
+    // Block size of v1 and v2 file systems
+    const V2_BLOCK_SIZE: usize = 1024;
+    // Block size limits of v3 file systems
+    const MIN_V3_BLOCK_SIZE: usize = 1024;
+    const MAX_V3_BLOCK_SIZE: usize = 32768;
+    // Valid bits of the v1 / v2 state field
+    const STATE_MASK: usize = 0x3;
+
+    let v2_superblock_structure = vec![
+        ("inodes_count", "u16"),
+        ("v1_zones_count", "u16"),
+        ("imap_blocks", "u16"),
+        ("zmap_blocks", "u16"),
+        ("first_data_zone", "u16"),
+        ("log_zone_size", "u16"),
+        ("max_size", "u32"),
+        ("magic", "u16"),
+        ("state", "u16"),
+        ("zones_count", "u32"),
+    ];
+
+    let v3_superblock_structure = vec![
+        ("inodes_count", "u32"),
+        ("padding1", "u16"),
+        ("imap_blocks", "u16"),
+        ("zmap_blocks", "u16"),
+        ("first_data_zone", "u16"),
+        ("log_zone_size", "u16"),
+        ("padding2", "u16"),
+        ("max_size", "u32"),
+        ("zones_count", "u32"),
+        ("magic", "u16"),
+        ("padding3", "u16"),
+        ("block_size", "u16"),
+        ("disk_version", "u8"),
+    ];
+
+    let mut minix_header = MinixHeader {
+        endianness: endianness.to_string(),
+        ..Default::default()
+    };
+
+    let mut superblock = common::parse(superblock_data, &v2_superblock_structure, endianness)?;
+
+    // Version 1 file systems record the zone count in a 16 bit field
+    let mut zones_count_field = "zones_count";
+
+    match superblock["magic"] {
+        MINIX_V1_MAGIC | MINIX_V1_30_MAGIC => {
+            minix_header.version = 1;
+            zones_count_field = "v1_zones_count";
+        }
+        MINIX_V2_MAGIC | MINIX_V2_30_MAGIC => minix_header.version = 2,
+        _ => {
+            superblock = common::parse(superblock_data, &v3_superblock_structure, endianness)?;
+
+            if superblock["magic"] != MINIX_V3_MAGIC {
+                return Err(StructureError);
+            }
+
+            minix_header.version = 3;
+        }
+    }
+
+    if minix_header.version == 3 {
+        minix_header.block_size = superblock["block_size"];
+        minix_header.inode_size = 64;
+        minix_header.pointer_size = 4;
+        minix_header.name_length = 60;
+        minix_header.directory_entry_size = 64;
+
+        if !minix_header.block_size.is_power_of_two()
+            || minix_header.block_size < MIN_V3_BLOCK_SIZE
+            || minix_header.block_size > MAX_V3_BLOCK_SIZE
+        {
+            return Err(StructureError);
+        }
+    } else {
+        minix_header.block_size = V2_BLOCK_SIZE;
+        minix_header.name_length = match superblock["magic"] {
+            MINIX_V1_MAGIC | MINIX_V2_MAGIC => 14,
+            _ => 30,
+        };
+        minix_header.directory_entry_size = minix_header.name_length + 2;
+
+        (minix_header.inode_size, minix_header.pointer_size) = match minix_header.version {
+            1 => (32, 2),
+            _ => (64, 4),
+        };
+
+        if superblock["state"] & !STATE_MASK != 0 {
+            return Err(StructureError);
+        }
+    }
+
+    minix_header.inodes_count = superblock["inodes_count"];
+    minix_header.zones_count = superblock[zones_count_field];
+    minix_header.inode_table = 2 + superblock["imap_blocks"] + superblock["zmap_blocks"];
+
+    // The inode and zone bitmaps must be large enough to describe every inode and data zone; bit 0 of each is reserved
+    let bits_per_block = minix_header.block_size * 8;
+    let inode_table_blocks =
+        (minix_header.inodes_count * minix_header.inode_size).div_ceil(minix_header.block_size);
+
+    // Zones larger than a block are not used in practice, and are not supported
+    if superblock["log_zone_size"] != 0
+        || minix_header.inodes_count == 0
+        || superblock["imap_blocks"] * bits_per_block <= minix_header.inodes_count
+        || superblock["first_data_zone"] < minix_header.inode_table + inode_table_blocks
+        || superblock["first_data_zone"] >= minix_header.zones_count
+        || superblock["zmap_blocks"] * bits_per_block
+            <= minix_header.zones_count - superblock["first_data_zone"]
+    {
+        return Err(StructureError);
+    }
+
+    minix_header.image_size = minix_header
+        .zones_count
+        .checked_mul(minix_header.block_size)
+        .ok_or(StructureError)?;
+
+    Ok(minix_header)
+}
+
+/// Parses a Minix inode
+pub fn parse_minix_inode(
+    inode_data: &[u8],
+    minix_header: &MinixHeader,
+) -> Result<MinixInode, StructureError> {
+    // Number of zone pointers in v1 inodes, which have no triple indirect pointer, and in v2 / v3 inodes
+    const V1_ZONE_COUNT: usize = 9;
+    const V2_ZONE_COUNT: usize = 10;
+
+    let v1_inode_structure = vec![
+        ("mode", "u16"),
+        ("uid", "u16"),
+        ("size", "u32"),
+        ("mtime", "u32"),
+        ("gid", "u8"),
+        ("links_count", "u8"),
+    ];
+
+    let v2_inode_structure = vec![
+        ("mode", "u16"),
+        ("links_count", "u16"),
+        ("uid", "u16"),
+        ("gid", "u16"),
+        ("size", "u32"),
+        ("atime", "u32"),
+        ("mtime", "u32"),
+        ("ctime", "u32"),
+    ];
+
+    let (inode_structure, zone_count) = match minix_header.version {
+        1 => (v1_inode_structure, V1_ZONE_COUNT),
+        _ => (v2_inode_structure, V2_ZONE_COUNT),
+    };
+
+    let inode = common::parse(inode_data, &inode_structure, &minix_header.endianness)?;
+
+    let zones_offset = common::size(&inode_structure);
+    let zones_data = inode_data
+        .get(zones_offset..zones_offset + (zone_count * minix_header.pointer_size))
+        .ok_or(StructureError)?;
+
+    Ok(MinixInode {
+        mode: inode["mode"],
+        uid: inode["uid"],
+        gid: inode["gid"],
+        size: inode["size"],
+        mtime: inode["mtime"],
+        zones: parse_minix_zone_pointers(zones_data, minix_header)?,
+    })
+}
+
+/// Parses an array of Minix zone pointers, as found in inodes and indirect zones
+pub fn parse_minix_zone_pointers(
+    pointer_data: &[u8],
+    minix_header: &MinixHeader,
+) -> Result<Vec<usize>, StructureError> {
+    let pointer_structure = match minix_header.pointer_size {
+        2 => vec![("zone", "u16")],
+        _ => vec![("zone", "u32")],
+    };
+
+    pointer_data
+        .chunks_exact(minix_header.pointer_size)
+        .map(|pointer| {
+            common::parse(pointer, &pointer_structure, &minix_header.endianness)
+                .map(|pointer| pointer["zone"])
+        })
+        .collect()
+}
+
+/// Parses a Minix directory entry
+pub fn parse_minix_directory_entry(
+    entry_data: &[u8],
+    minix_header: &MinixHeader,
+) -> Result<MinixDirectoryEntry, StructureError> {
+    let directory_entry_structure = match minix_header.version {
+        3 => vec![("inode", "u32")],
+        _ => vec![("inode", "u16")],
+    };
+
+    let entry = common::parse(
+        entry_data,
+        &directory_entry_structure,
+        &minix_header.endianness,
+    )?;
+
+    let name_offset = common::size(&directory_entry_structure);
+    let name_data = entry_data
+        .get(name_offset..name_offset + minix_header.name_length)
+        .ok_or(StructureError)?;
+
+    // Names are NULL padded, but names of the maximum length are not NULL terminated
+    Ok(MinixDirectoryEntry {
+        inode: entry["inode"],
+        name: name_data
+            .iter()
+            .take_while(|byte| **byte != 0)
+            .copied()
+            .collect(),
+    })
+}
@@ -1,10 +1,34 @@
 use crate::structures::common::{self, StructureError};
 
+/// MFT record number of the root directory
+pub const NTFS_ROOT_RECORD: usize = 5;
+
+/// MFT record numbers below this are reserved for file system metadata files, other than the root directory
+pub const NTFS_FIRST_USER_RECORD: usize = 16;
+
+/// Attribute types
+pub const NTFS_ATTR_STANDARD_INFORMATION: usize = 0x10;
+pub const NTFS_ATTR_FILE_NAME: usize = 0x30;
+pub const NTFS_ATTR_DATA: usize = 0x80;
+
+/// Attribute flags
+pub const NTFS_ATTR_FLAG_COMPRESSED: usize = 0x0001;
+pub const NTFS_ATTR_FLAG_ENCRYPTED: usize = 0x4000;
+
+/// $FILE_NAME namespace of MS-DOS 8.3 names, which are stored alongside a file's long name
+pub const NTFS_NAMESPACE_DOS: usize = 2;
+
 /// Struct to store NTFS info
 #[derive(Debug, Default, Clone)]
 pub struct NTFSPartition {
     pub sector_size: usize,
     pub sector_count: usize,
+    /// Size of a cluster in bytes; 0 if the boot sector specifies an invalid cluster size
+    pub cluster_size: usize,
+    /// Offset of the $MFT, relative to the start of the partition
+    pub mft_offset: usize,
+    /// Size of each MFT record in bytes; 0 if the boot sector specifies an invalid record size
+    pub mft_record_size: usize,
 }
 
 /// Parses an NTFS partition header
@@ -26,6 +50,13 @@ pub fn parse_ntfs_header(ntfs_data: &[u8]) -> Result<NTFSPartition, StructureErr
         ("unused5", "u32"),
         ("unknown", "u32"),
         ("sector_count", "u64"),
+        ("mft_cluster", "u64"),
+        ("mft_mirror_cluster", "u64"),
+        ("clusters_per_mft_record", "u8"),
+        ("unused6", "u24"),
+        ("clusters_per_index_record", "u8"),
+        ("unused7", "u24"),
+        ("serial_number", "u64"),
     ];
 
     // Parse the NTFS partition header
@@ -37,12 +68,377 @@ pub fn parse_ntfs_header(ntfs_data: &[u8]) -> Result<NTFSPartition, StructureErr
             && ntfs_header["unused4"] == 0
             && ntfs_header["unused5"] == 0
         {
+            let sector_size = ntfs_header["bytes_per_sector"];
+            let cluster_size =
+                cluster_count_to_size(ntfs_header["sectors_per_cluster"], sector_size);
+
             return Ok(NTFSPartition {
                 sector_count: ntfs_header["sector_count"],
-                sector_size: ntfs_header["bytes_per_sector"],
+                sector_size,
+                cluster_size,
+                mft_offset: ntfs_header["mft_cluster"].saturating_mul(cluster_size),
+                mft_record_size: cluster_count_to_size(
+                    ntfs_header["clusters_per_mft_record"],
+                    cluster_size,
+                ),
             });
         }
     }
 
     Err(StructureError)
 }
+
+/// Sizes in the boot sector are a count of units, or if the value is negative, the size is 2^-value bytes.
+/// Returns 0 for invalid sizes.
+fn cluster_count_to_size(value: usize, unit_size: usize) -> usize {
+    // Largest supported size is 2MB
+    const MAX_SIZE_SHIFT: u32 = 21;
+
+    match (value as u8 as i8).checked_neg() {
+        Some(shift) if shift > 0 && (shift as u32) <= MAX_SIZE_SHIFT => 1 << shift,
+        _ if value < 0x80 => value * unit_size,
+        _ => 0,
+    }
+}
+
+/// A run of consecutive clusters in a non-resident attribute
+#[derive(Debug, Default, Clone)]
+pub struct NTFSDataRun {
+    pub cluster_count: usize,
+    /// First logical cluster number of the run; None for sparse runs, which read as zeros
+    pub lcn: Option<usize>,
+}
+
+/// Describes the data of a non-resident attribute, which is stored in clusters outside of the MFT
+#[derive(Debug, Default, Clone)]
+pub struct NTFSNonResidentAttribute {
+    /// First virtual cluster number described by this attribute's runs; large attributes may be split across records
+    pub start_vcn: usize,
+    pub runs: Vec<NTFSDataRun>,
+    /// Compression units are 2^compression_unit clusters; 0 if the attribute is not compressed
+    pub compression_unit: usize,
+    /// These sizes are only valid in the attribute that starts at VCN 0
+    pub data_size: usize,
+    /// Data beyond the initialized size reads as zeros
+    pub initialized_size: usize,
+}
+
+/// Content of an NTFS attribute
+#[derive(Debug, Clone)]
+pub enum NTFSAttributeContent {
+    Resident(Vec<u8>),
+    NonResident(NTFSNonResidentAttribute),
+}
+
+/// Struct to store NTFS attribute info
+#[derive(Debug, Clone)]
+pub struct NTFSAttribute {
+    pub attribute_type: usize,
+    /// Attribute name; named $DATA attributes are alternate data streams
+    pub name: String,
+    pub flags: usize,
+    pub content: NTFSAttributeContent,
+}
+
+/// Struct to store NTFS MFT file record info
+#[derive(Debug, Clone)]
+pub struct NTFSFileRecord {
+    pub in_use: bool,
+    pub is_directory: bool,
+    /// MFT record number of the base file record, if this is an extension record that holds some of its attributes
+    pub base_record: Option<usize>,
+    pub attributes: Vec<NTFSAttribute>,
+}
+
+/// Parses an MFT file record, applying its update sequence fixups
+pub fn parse_ntfs_file_record(record_data: &[u8]) -> Result<NTFSFileRecord, StructureError> {
+    // Fixups are applied to the last two bytes of every 512 byte stride, regardless of the sector size
+    const FIXUP_STRIDE: usize = 512;
+    const FILE_MAGIC: usize = 0x454C4946;
+    const FLAG_IN_USE: usize = 0x01;
+    const FLAG_DIRECTORY: usize = 0x02;
+    // Only the low 48 bits of a file reference are the record number; the upper 16 bits are a sequence number
+    const RECORD_NUMBER_MASK: usize = 0xFFFF_FFFF_FFFF;
+    const END_OF_ATTRIBUTES: usize = 0xFFFFFFFF;
+
+    let record_structure = vec![
+        ("magic", "u32"),
+        ("update_sequence_offset", "u16"),
+        ("update_sequence_count", "u16"),
+        ("log_sequence_number", "u64"),
+        ("sequence_number", "u16"),
+        ("link_count", "u16"),
+        ("first_attribute_offset", "u16"),
+        ("flags", "u16"),
+        ("used_size", "u32"),
+        ("allocated_size", "u32"),
+        ("base_record", "u64"),
+    ];
+
+    let record_header = common::parse(record_data, &record_structure, "little")?;
+
+    let usa_offset = record_header["update_sequence_offset"];
+    let usa_count = record_header["update_sequence_count"];
+    let used_size = record_header["used_size"];
+
+    if record_header["magic"] != FILE_MAGIC
+        || usa_count == 0
+        || (usa_count - 1) * FIXUP_STRIDE > record_data.len()
+        || used_size > record_data.len()
+    {
+        return Err(StructureError);
+    }
+
+    // The last two bytes of each stride must match the update sequence number, and are replaced with the original bytes
+    let mut record = record_data.to_vec();
+    let update_sequence = record_data
+        .get(usa_offset..usa_offset + (usa_count * 2))
+        .ok_or(StructureError)?;
+
+    for (i, original) in update_sequence[2..].chunks_exact(2).enumerate() {
+        let fixup_offset = ((i + 1) * FIXUP_STRIDE) - 2;
+
+        if record[fixup_offset..fixup_offset + 2] != update_sequence[0..2] {
+            return Err(StructureError);
+        }
+
+        record[fixup_offset..fixup_offset + 2].copy_from_slice(original);
+    }
+
+    let mut file_record = NTFSFileRecord {
+        in_use: record_header["flags"] & FLAG_IN_USE != 0,
+        is_directory: record_header["flags"] & FLAG_DIRECTORY != 0,
+        base_record: match record_header["base_record"] & RECORD_NUMBER_MASK {
+            0 => None,
+            base_record => Some(base_record),
+        },
+        attributes: vec![],
+    };
+
+    let mut attribute_offset = record_header["first_attribute_offset"];
+
+    while let Some(attribute_data) = record.get(attribute_offset..used_size) {
+        let Some(type_bytes) = attribute_data.get(0..4) else {
+            break;
+        };
+
+        if u32::from_le_bytes(type_bytes.try_into().unwrap()) as usize == END_OF_ATTRIBUTES {
+            break;
+        }
+
+        let (attribute, attribute_size) = parse_ntfs_attribute(attribute_data)?;
+        file_record.attributes.push(attribute);
+        attribute_offset += attribute_size;
+    }
+
+    Ok(file_record)
+}
+
+/// Parses an attribute, returning the attribute and its size in the MFT record
+fn parse_ntfs_attribute(attribute_data: &[u8]) -> Result<(NTFSAttribute, usize), StructureError> {
+    let attribute_structure = vec![
+        ("type", "u32"),
+        ("size", "u32"),
+        ("non_resident", "u8"),
+        ("name_length", "u8"),
+        ("name_offset", "u16"),
+        ("flags", "u16"),
+        ("id", "u16"),
+    ];
+
+    let resident_structure = vec![("value_size", "u32"), ("value_offset", "u16")];
+
+    let non_resident_structure = vec![
+        ("start_vcn", "u64"),
+        ("last_vcn", "u64"),
+        ("runs_offset", "u16"),
+        ("compression_unit", "u16"),
+        ("padding", "u32"),
+        ("allocated_size", "u64"),
+        ("data_size", "u64"),
+        ("initialized_size", "u64"),
+    ];
+
+    let attribute_header = common::parse(attribute_data, &attribute_structure, "little")?;
+    let attribute_size = attribute_header["size"];
+    let header_size = common::size(&attribute_structure);
+
+    // Attributes are 8 byte aligned, and must at least contain their header
+    if attribute_size <= header_size || !attribute_size.is_multiple_of(8) {
+        return Err(StructureError);
+    }
+
+    let attribute_data = attribute_data.get(..attribute_size).ok_or(StructureError)?;
+
+    let name_offset = attribute_header["name_offset"];
+    let name_utf16: Vec<u16> = attribute_data
+        .get(name_offset..name_offset + (attribute_header["name_length"] * 2))
+        .ok_or(StructureError)?
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+
+    let fields_data = &attribute_data[header_size..];
+
+    let content = if attribute_header["non_resident"] == 0 {
+        let resident_header = common::parse(fields_data, &resident_structure, "little")?;
+        let value_offset = resident_header["value_offset"];
+
+        NTFSAttributeContent::Resident(
+            attribute_data
+                .get(value_offset..value_offset + resident_header["value_size"])
+                .ok_or(StructureError)?
+                .to_vec(),
+        )
+    } else {
+        let non_resident_header = common::parse(fields_data, &non_resident_structure, "little")?;
+
+        NTFSAttributeContent::NonResident(NTFSNonResidentAttribute {
+            start_vcn: non_resident_header["start_vcn"],
+            runs: parse_ntfs_data_runs(
+                attribute_data
+                    .get(non_resident_header["runs_offset"]..)
+                    .ok_or(StructureError)?,
+            )?,
+            compression_unit: non_resident_header["compression_unit"],
+            data_size: non_resident_header["data_size"],
+            initialized_size: non_resident_header["initialized_size"],
+        })
+    };
+
+    Ok((
+        NTFSAttribute {
+            attribute_type: attribute_header["type"],
+            name: String::from_utf16_lossy(&name_utf16),
+            flags: attribute_header["flags"],
+            content,
+        },
+        attribute_size,
+    ))
+}
+
+/// Parses a run list. Each run starts with a byte specifying the sizes of its length and offset fields; offsets are
+/// signed, and relative to the previous run's LCN. Runs without an offset field are sparse.
+fn parse_ntfs_data_runs(runs_data: &[u8]) -> Result<Vec<NTFSDataRun>, StructureError> {
+    let mut runs: Vec<NTFSDataRun> = vec![];
+    let mut lcn: i64 = 0;
+    let mut offset: usize = 0;
+
+    loop {
+        let header = *runs_data.get(offset).ok_or(StructureError)? as usize;
+
+        // A zero header byte terminates the run list
+        if header == 0 {
+            break;
+        }
+
+        let length_size = header & 0x0F;
+        let lcn_size = header >> 4;
+
+        if length_size == 0 || length_size > 8 || lcn_size > 8 {
+            return Err(StructureError);
+        }
+
+        let length_data = runs_data
+            .get(offset + 1..offset + 1 + length_size)
+            .ok_or(StructureError)?;
+        let lcn_data = runs_data
+            .get(offset + 1 + length_size..offset + 1 + length_size + lcn_size)
+            .ok_or(StructureError)?;
+
+        let mut length_bytes = [0; 8];
+        length_bytes[..length_size].copy_from_slice(length_data);
+
+        let run_lcn = match lcn_data.last() {
+            None => None,
+            Some(last_byte) => {
+                // Sign extend the LCN delta
+                let mut delta_bytes = [if *last_byte & 0x80 != 0 { 0xFF } else { 0 }; 8];
+                delta_bytes[..lcn_size].copy_from_slice(lcn_data);
+                lcn = lcn.wrapping_add(i64::from_le_bytes(delta_bytes));
+                Some(usize::try_from(lcn).map_err(|_| StructureError)?)
+            }
+        };
+
+        runs.push(NTFSDataRun {
+            cluster_count: u64::from_le_bytes(length_bytes) as usize,
+            lcn: run_lcn,
+        });
+
+        offset += 1 + length_size + lcn_size;
+    }
+
+    Ok(runs)
+}
+
+/// Struct to store the contents of a $FILE_NAME attribute
+#[derive(Debug, Default, Clone)]
+pub struct NTFSFileName {
+    /// MFT record number of the directory containing the file
+    pub parent_record: usize,
+    pub name: String,
+    /// One of POSIX (0), Win32 (1), DOS (2), or Win32 & DOS (3)
+    pub namespace: usize,
+}
+
+/// Parses the contents of a $FILE_NAME attribute
+pub fn parse_ntfs_file_name(file_name_data: &[u8]) -> Result<NTFSFileName, StructureError> {
+    const NAME_OFFSET: usize = 66;
+    const RECORD_NUMBER_MASK: usize = 0xFFFF_FFFF_FFFF;
+
+    let file_name_structure = vec![
+        ("parent_reference", "u64"),
+        ("creation_time", "u64"),
+        ("modification_time", "u64"),
+        ("mft_modification_time", "u64"),
+        ("access_time", "u64"),
+        ("allocated_size", "u64"),
+        ("data_size", "u64"),
+        ("flags", "u32"),
+        ("reparse_value", "u32"),
+        ("name_length", "u8"),
+        ("namespace", "u8"),
+    ];
+
+    let file_name = common::parse(file_name_data, &file_name_structure, "little")?;
+
+    let name_utf16: Vec<u16> = file_name_data
+        .get(NAME_OFFSET..NAME_OFFSET + (file_name["name_length"] * 2))
+        .ok_or(StructureError)?
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+
+    if name_utf16.is_empty() {
+        return Err(StructureError);
+    }
+
+    Ok(NTFSFileName {
+        parent_record: file_name["parent_reference"] & RECORD_NUMBER_MASK,
+        name: String::from_utf16_lossy(&name_utf16),
+        namespace: file_name["namespace"],
+    })
+}
+
+/// Parses the contents of a $STANDARD_INFORMATION attribute, returning the file's modification time as a UNIX timestamp
+pub fn parse_ntfs_modification_time(
+    standard_information_data: &[u8],
+) -> Result<i64, StructureError> {
+    // Number of 100ns intervals between the Windows epoch (1601-01-01) and the UNIX epoch
+    const UNIX_EPOCH_FILETIME: i64 = 116444736000000000;
+    const FILETIME_TICKS_PER_SECOND: i64 = 10000000;
+
+    let standard_information_structure =
+        vec![("creation_time", "u64"), ("modification_time", "u64")];
+
+    let standard_information = common::parse(
+        standard_information_data,
+        &standard_information_structure,
+        "little",
+    )?;
+
+    Ok(
+        (standard_information["modification_time"] as i64 - UNIX_EPOCH_FILETIME)
+            / FILETIME_TICKS_PER_SECOND,
+    )
+}
@@ -0,0 +1,523 @@
+use crate::structures::common::StructureError;
+
+/// Storage struct for a PDF indirect object
+#[derive(Debug, Clone, Default)]
+pub struct PDFObject {
+    pub number: usize,
+    pub generation: usize,
+    /// The object's value, typically a dictionary; for stream objects, this is the stream dictionary
+    pub value: Vec<u8>,
+    /// The raw, still encoded, stream data of stream objects
+    pub stream: Option<Vec<u8>>,
+}
+
+/// Returns the size of a PDF document, by following its trailers from the `%PDF-` header to the final `%%EOF` marker.
+/// Incrementally updated documents have several trailers; updates are only included if they immediately follow the
+/// previous `%%EOF` marker.
+pub fn parse_pdf_size(pdf_data: &[u8]) -> Result<usize, StructureError> {
+    const PDF_MAGIC: &[u8] = b"%PDF-";
+    const STARTXREF: &[u8] = b"startxref";
+    const EOF_MARKER: &[u8] = b"%%EOF";
+
+    let mut pdf_size: Option<usize> = None;
+
+    // Incremental updates must not search past the start of the next PDF document
+    let search_end = find(pdf_data, PDF_MAGIC.len(), PDF_MAGIC).unwrap_or(pdf_data.len());
+
+    loop {
+        let search_start = pdf_size.unwrap_or(0);
+
+        // Data appended after a %%EOF marker is only an incremental update if it starts with an object or xref table
+        if let Some(previous_end) = pdf_size {
+            let update_start = skip_whitespace(pdf_data, previous_end);
+            if !pdf_data[update_start..].starts_with(b"xref")
+                && parse_object_header(pdf_data, update_start).is_none()
+            {
+                break;
+            }
+        }
+
+        let startxref_offset = match find(&pdf_data[..search_end], search_start, STARTXREF) {
+            None => break,
+            Some(startxref_offset) => startxref_offset,
+        };
+
+        // startxref is followed by the offset of the cross-reference table, and then the %%EOF marker
+        let xref_offset_start = skip_whitespace(pdf_data, startxref_offset + STARTXREF.len());
+        let xref_offset_end = skip_digits(pdf_data, xref_offset_start);
+        let eof_start = skip_whitespace(pdf_data, xref_offset_end);
+
+        if xref_offset_end == xref_offset_start || !pdf_data[eof_start..].starts_with(EOF_MARKER) {
+            break;
+        }
+
+        // Include the end of line that terminates the %%EOF marker
+        let mut eof_end = eof_start + EOF_MARKER.len();
+        for eol in [b'\r', b'\n'] {
+            if pdf_data.get(eof_end) == Some(&eol) {
+                eof_end += 1;
+            }
+        }
+
+        pdf_size = Some(eof_end);
+    }
+
+    pdf_size.ok_or(StructureError)
+}
+
+/// Parses all indirect objects in the PDF document
+pub fn parse_pdf_objects(pdf_data: &[u8]) -> Vec<PDFObject> {
+    const OBJ: &[u8] = b"obj";
+    const ENDOBJ: &[u8] = b"endobj";
+    const STREAM: &[u8] = b"stream";
+    const ENDSTREAM: &[u8] = b"endstream";
+
+    let mut objects = vec![];
+    let mut next_offset: usize = 0;
+
+    while let Some(obj_offset) = find(pdf_data, next_offset, OBJ) {
+        next_offset = obj_offset + OBJ.len();
+
+        // The obj keyword must be preceeded by the object and generation numbers
+        let (number, generation) = match parse_object_header_before(pdf_data, obj_offset) {
+            None => continue,
+            Some(header) => header,
+        };
+
+        // Objects that are not properly terminated are ignored
+        let endobj_offset = match find(pdf_data, next_offset, ENDOBJ) {
+            None => break,
+            Some(endobj_offset) => endobj_offset,
+        };
+
+        let mut object = PDFObject {
+            number,
+            generation,
+            ..Default::default()
+        };
+
+        match find_keyword(&pdf_data[..endobj_offset], next_offset, STREAM) {
+            None => {
+                object.value = trim(&pdf_data[next_offset..endobj_offset]).to_vec();
+                next_offset = endobj_offset + ENDOBJ.len();
+            }
+            Some(stream_offset) => {
+                object.value = trim(&pdf_data[next_offset..stream_offset]).to_vec();
+
+                // The stream keyword is followed by a CRLF or LF end of line
+                let mut data_start = stream_offset + STREAM.len();
+                if pdf_data.get(data_start) == Some(&b'\r') {
+                    data_start += 1;
+                }
+                if pdf_data.get(data_start) == Some(&b'\n') {
+                    data_start += 1;
+                }
+
+                // Prefer the length reported in the stream dictionary; fall back to searching for the endstream keyword
+                let data_end = match pdf_stream_length(pdf_data, data_start, &object.value) {
+                    Some(length)
+                        if pdf_data
+                            .get(skip_whitespace(pdf_data, data_start.saturating_add(length))..)
+                            .is_some_and(|data| data.starts_with(ENDSTREAM)) =>
+                    {
+                        data_start + length
+                    }
+                    _ => match find(pdf_data, data_start, ENDSTREAM) {
+                        None => break,
+                        Some(endstream_offset) => trim_eol(pdf_data, data_start, endstream_offset),
+                    },
+                };
+
+                object.stream = Some(pdf_data[data_start..data_end].to_vec());
+
+                // Stream data may contain anything, including the endobj keyword; resume the search after the stream
+                next_offset = match find(pdf_data, data_end, ENDOBJ) {
+                    None => pdf_data.len(),
+                    Some(endobj_offset) => endobj_offset + ENDOBJ.len(),
+                };
+            }
+        }
+
+        objects.push(object);
+    }
+
+    objects
+}
+
+/// Parses the objects stored inside a decoded object stream
+pub fn parse_pdf_object_stream(stream_dictionary: &[u8], stream_data: &[u8]) -> Vec<PDFObject> {
+    let mut objects = vec![];
+
+    let object_count = pdf_dictionary_integer(stream_dictionary, "N");
+    let first_offset = pdf_dictionary_integer(stream_dictionary, "First");
+
+    if let (Some(object_count), Some(first_offset)) = (object_count, first_offset) {
+        // The stream starts with pairs of object numbers and offsets, relative to the first object
+        let mut offsets = vec![];
+        let mut next_offset: usize = 0;
+
+        for _ in 0..object_count {
+            match (
+                parse_integer(stream_data, &mut next_offset),
+                parse_integer(stream_data, &mut next_offset),
+            ) {
+                (Some(number), Some(offset)) => {
+                    offsets.push((number, first_offset.saturating_add(offset)))
+                }
+                _ => break,
+            }
+        }
+
+        for (i, (number, object_start)) in offsets.iter().enumerate() {
+            let object_end = match offsets.get(i + 1) {
+                None => stream_data.len(),
+                Some((_, next_object_start)) => *next_object_start,
+            };
+
+            if let Some(object_value) = stream_data.get(*object_start..object_end) {
+                objects.push(PDFObject {
+                    number: *number,
+                    value: trim(object_value).to_vec(),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    objects
+}
+
+/// Returns the raw value of the specified key in a PDF dictionary, e.g. `pdf_dictionary_value(b"<< /Length 5 >>", "Length")`
+/// returns `b"5 >>"`. Keys in nested dictionaries are also searched.
+pub fn pdf_dictionary_value<'a>(dictionary: &'a [u8], key: &str) -> Option<&'a [u8]> {
+    let name = format!("/{key}");
+    let mut next_offset: usize = 0;
+
+    while let Some(key_offset) = find(dictionary, next_offset, name.as_bytes()) {
+        next_offset = key_offset + name.len();
+
+        // Make sure this isn't just the start of a longer name, e.g. /Length1
+        if dictionary
+            .get(next_offset)
+            .is_none_or(|b| is_whitespace(*b) || is_delimiter(*b))
+        {
+            return Some(&dictionary[skip_whitespace(dictionary, next_offset)..]);
+        }
+    }
+
+    None
+}
+
+/// Returns the integer value of the specified key in a PDF dictionary
+pub fn pdf_dictionary_integer(dictionary: &[u8], key: &str) -> Option<usize> {
+    pdf_dictionary_value(dictionary, key).and_then(|value| parse_integer(value, &mut 0))
+}
+
+/// Returns the names of the specified key in a PDF dictionary; the value may be a single name, or an array of names
+pub fn pdf_dictionary_names(dictionary: &[u8], key: &str) -> Vec<String> {
+    let mut names = vec![];
+
+    if let Some(value) = pdf_dictionary_value(dictionary, key) {
+        let value_end = match value.first() {
+            Some(b'[') => value.iter().position(|b| *b == b']').unwrap_or(value.len()),
+            _ => value
+                .iter()
+                .skip(1)
+                .position(|b| is_whitespace(*b) || is_delimiter(*b))
+                .map_or(value.len(), |end| end + 1),
+        };
+
+        for name in value[..value_end].split(|b| *b == b'/').skip(1) {
+            let name_end = name
+                .iter()
+                .position(|b| is_whitespace(*b) || is_delimiter(*b))
+                .unwrap_or(name.len());
+            names.push(String::from_utf8_lossy(&name[..name_end]).to_string());
+        }
+    }
+
+    names
+}
+
+/// Returns the object and generation numbers of an indirect reference value, e.g. `12 0 R`
+pub fn pdf_reference(value: &[u8]) -> Option<(usize, usize)> {
+    let mut next_offset: usize = 0;
+
+    let number = parse_integer(value, &mut next_offset)?;
+    let generation = parse_integer(value, &mut next_offset)?;
+
+    next_offset = skip_whitespace(value, next_offset);
+    match value.get(next_offset) {
+        Some(b'R') => Some((number, generation)),
+        _ => None,
+    }
+}
+
+/// Returns the sub-dictionary that starts at the beginning of the provided value
+pub fn pdf_sub_dictionary(value: &[u8]) -> Option<&[u8]> {
+    if !value.starts_with(b"<<") {
+        return None;
+    }
+
+    let mut depth: usize = 0;
+    let mut i: usize = 0;
+
+    while i + 1 < value.len() {
+        match &value[i..i + 2] {
+            b"<<" => {
+                depth += 1;
+                i += 2;
+            }
+            b">>" => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return Some(&value[..i]);
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    None
+}
+
+/// Decodes a PDF literal `(...)` or hexadecimal `<...>` string value; UTF-16 strings are converted to UTF-8
+pub fn pdf_string(value: &[u8]) -> Option<String> {
+    let mut string_bytes: Vec<u8> = vec![];
+
+    match value.first() {
+        Some(b'(') => {
+            let mut depth: usize = 0;
+            let mut bytes = value.iter().skip(1).peekable();
+
+            while let Some(b) = bytes.next() {
+                match *b {
+                    b'(' => {
+                        depth += 1;
+                        string_bytes.push(*b);
+                    }
+                    b')' if depth == 0 => break,
+                    b')' => {
+                        depth -= 1;
+                        string_bytes.push(*b);
+                    }
+                    b'\\' => match bytes.next()? {
+                        b'n' => string_bytes.push(b'\n'),
+                        b'r' => string_bytes.push(b'\r'),
+                        b't' => string_bytes.push(b'\t'),
+                        b'b' => string_bytes.push(0x08),
+                        b'f' => string_bytes.push(0x0C),
+                        b'\r' | b'\n' => (),
+                        escaped @ b'0'..=b'7' => {
+                            // Octal escapes are one to three digits long
+                            let mut octal = (escaped - b'0') as u32;
+                            for _ in 0..2 {
+                                match bytes.next_if(|b| (b'0'..=b'7').contains(*b)) {
+                                    None => break,
+                                    Some(digit) => octal = (octal << 3) | (digit - b'0') as u32,
+                                }
+                            }
+                            string_bytes.push(octal as u8);
+                        }
+                        escaped => string_bytes.push(*escaped),
+                    },
+                    _ => string_bytes.push(*b),
+                }
+            }
+        }
+        Some(b'<') => {
+            let hex_end = value.iter().position(|b| *b == b'>')?;
+            let mut hex_digits: Vec<u8> = value[1..hex_end]
+                .iter()
+                .copied()
+                .filter(|b| !is_whitespace(*b))
+                .collect();
+
+            // A missing final digit is assumed to be zero
+            if !hex_digits.len().is_multiple_of(2) {
+                hex_digits.push(b'0');
+            }
+
+            string_bytes = hex::decode(hex_digits).ok()?;
+        }
+        _ => return None,
+    }
+
+    // Strings starting with a UTF-16BE byte order mark are unicode
+    if let Some(utf16_bytes) = string_bytes.strip_prefix(b"\xFE\xFF") {
+        let utf16: Vec<u16> = utf16_bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_be_bytes([c[0], c[1]]))
+            .collect();
+        return String::from_utf16(&utf16).ok();
+    }
+
+    Some(String::from_utf8_lossy(&string_bytes).to_string())
+}
+
+/// Returns the stream length from a stream dictionary; the length may be an indirect reference to another object
+fn pdf_stream_length(
+    pdf_data: &[u8],
+    stream_offset: usize,
+    stream_dictionary: &[u8],
+) -> Option<usize> {
+    let length_value = pdf_dictionary_value(stream_dictionary, "Length")?;
+
+    match pdf_reference(length_value) {
+        None => parse_integer(length_value, &mut 0),
+        Some((number, generation)) => {
+            let object_header = format!("{number} {generation} obj");
+
+            // Length objects are usually written immediately after the stream, so look there first
+            for search_start in [stream_offset, 0] {
+                let mut next_offset = search_start;
+
+                while let Some(header_offset) =
+                    find(pdf_data, next_offset, object_header.as_bytes())
+                {
+                    next_offset = header_offset + object_header.len();
+
+                    if header_offset == 0 || is_whitespace(pdf_data[header_offset - 1]) {
+                        return parse_integer(pdf_data, &mut next_offset);
+                    }
+                }
+            }
+
+            None
+        }
+    }
+}
+
+/// Parses an object header, e.g. `12 0 obj`, at the specified offset
+fn parse_object_header(data: &[u8], offset: usize) -> Option<(usize, usize)> {
+    let mut next_offset = offset;
+
+    let number = parse_integer(data, &mut next_offset)?;
+    let generation = parse_integer(data, &mut next_offset)?;
+
+    next_offset = skip_whitespace(data, next_offset);
+    match data.get(next_offset..)?.starts_with(b"obj") {
+        true => Some((number, generation)),
+        false => None,
+    }
+}
+
+/// Parses the object header that ends with the obj keyword at the specified offset
+fn parse_object_header_before(data: &[u8], obj_offset: usize) -> Option<(usize, usize)> {
+    let mut header_start = obj_offset;
+
+    // Walk back over: whitespace, the generation number, whitespace, and the object number
+    for digits in [false, true, false, true] {
+        let field_end = header_start;
+        while header_start > 0
+            && match digits {
+                true => data[header_start - 1].is_ascii_digit(),
+                false => is_whitespace(data[header_start - 1]),
+            }
+        {
+            header_start -= 1;
+        }
+
+        if header_start == field_end {
+            return None;
+        }
+    }
+
+    parse_object_header(data, header_start)
+}
+
+/// Parses an unsigned integer, preceeded by optional whitespace, at the specified offset and advances the offset past it
+fn parse_integer(data: &[u8], offset: &mut usize) -> Option<usize> {
+    let digits_start = skip_whitespace(data, *offset);
+    let digits_end = skip_digits(data, digits_start);
+
+    let integer = std::str::from_utf8(&data[digits_start..digits_end])
+        .ok()?
+        .parse::<usize>()
+        .ok()?;
+
+    *offset = digits_end;
+    Some(integer)
+}
+
+/// Finds a keyword that is not part of a longer token, e.g. `stream` but not `endstream`
+fn find_keyword(data: &[u8], start: usize, keyword: &[u8]) -> Option<usize> {
+    let mut next_offset = start;
+
+    while let Some(keyword_offset) = find(data, next_offset, keyword) {
+        next_offset = keyword_offset + keyword.len();
+
+        let before_ok = keyword_offset == 0
+            || is_whitespace(data[keyword_offset - 1])
+            || is_delimiter(data[keyword_offset - 1]);
+        let after_ok = data
+            .get(next_offset)
+            .is_none_or(|b| is_whitespace(*b) || is_delimiter(*b));
+
+        if before_ok && after_ok {
+            return Some(keyword_offset);
+        }
+    }
+
+    None
+}
+
+/// Returns the end of the data, excluding any end of line immediately preceeding the end offset
+fn trim_eol(data: &[u8], start: usize, end: usize) -> usize {
+    let mut trimmed_end = end;
+
+    if trimmed_end > start && data[trimmed_end - 1] == b'\n' {
+        trimmed_end -= 1;
+    }
+    if trimmed_end > start && data[trimmed_end - 1] == b'\r' {
+        trimmed_end -= 1;
+    }
+
+    trimmed_end
+}
+
+/// Finds the first occurrence of needle in haystack, starting at the specified offset
+fn find(haystack: &[u8], start: usize, needle: &[u8]) -> Option<usize> {
+    haystack
+        .get(start..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|position| position + start)
+}
+
+fn skip_whitespace(data: &[u8], offset: usize) -> usize {
+    let mut next_offset = offset;
+    while data.get(next_offset).is_some_and(|b| is_whitespace(*b)) {
+        next_offset += 1;
+    }
+    next_offset.min(data.len())
+}
+
+fn skip_digits(data: &[u8], offset: usize) -> usize {
+    let mut next_offset = offset;
+    while data.get(next_offset).is_some_and(|b| b.is_ascii_digit()) {
+        next_offset += 1;
+    }
+    next_offset.min(data.len())
+}
+
+fn trim(data: &[u8]) -> &[u8] {
+    let start = skip_whitespace(data, 0);
+    let end = data
+        .iter()
+        .rposition(|b| !is_whitespace(*b))
+        .map_or(start, |end| end + 1);
+    &data[start..end.max(start)]
+}
+
+fn is_whitespace(b: u8) -> bool {
+    matches!(b, b'\0' | b'\t' | b'\n' | b'\x0C' | b'\r' | b' ')
+}
+
+fn is_delimiter(b: u8) -> bool {
+    matches!(
+        b,
+        b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+    )
+}
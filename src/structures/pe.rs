@@ -1,9 +1,95 @@
 use crate::structures::common::{self, StructureError};
 use std::collections::HashMap;
 
+/// Index of the resource table in the optional header's data directories
+pub const PE_RESOURCE_DIRECTORY: usize = 2;
+
+/// Index of the certificate table in the optional header's data directories; unlike other data directories,
+/// its address is a file offset rather than an RVA
+pub const PE_CERTIFICATE_DIRECTORY: usize = 4;
+
 /// Stores info about the PE file
+#[derive(Debug, Clone, Default)]
 pub struct PEHeader {
     pub machine: String,
+    /// Combined size of the DOS header, PE headers and section table, as reported by the optional header
+    pub header_size: usize,
+    pub sections: Vec<PESection>,
+    pub data_directories: Vec<PEDataDirectory>,
+}
+
+/// Stores info about a PE section
+#[derive(Debug, Clone, Default)]
+pub struct PESection {
+    pub virtual_address: usize,
+    pub virtual_size: usize,
+    pub raw_data_offset: usize,
+    pub raw_data_size: usize,
+}
+
+/// Stores the address and size of an optional header data directory
+#[derive(Debug, Clone, Default)]
+pub struct PEDataDirectory {
+    pub address: usize,
+    pub size: usize,
+}
+
+impl PEHeader {
+    /// Returns the size of the PE image in the file: the furthest extent of the headers, section data, and certificate
+    /// table. Any data beyond this is overlay data.
+    pub fn image_size(&self) -> Option<usize> {
+        let mut image_size = self.header_size;
+
+        for section in self.sections.iter() {
+            image_size =
+                image_size.max(section.raw_data_offset.checked_add(section.raw_data_size)?);
+        }
+
+        // The certificate table is not mapped into memory; its address is a file offset
+        if let Some(certificate_table) = self.data_directories.get(PE_CERTIFICATE_DIRECTORY)
+            && certificate_table.size > 0
+        {
+            image_size = image_size.max(
+                certificate_table
+                    .address
+                    .checked_add(certificate_table.size)?,
+            );
+        }
+
+        Some(image_size)
+    }
+
+    /// Converts a relative virtual address to a file offset, relative to the start of the PE file
+    pub fn rva_to_offset(&self, rva: usize) -> Option<usize> {
+        self.sections.iter().find_map(|section| {
+            let section_size = section.virtual_size.max(section.raw_data_size);
+            match rva >= section.virtual_address && rva - section.virtual_address < section_size {
+                true => Some(rva - section.virtual_address + section.raw_data_offset),
+                false => None,
+            }
+        })
+    }
+}
+
+/// Stores info about a resource directory entry
+#[derive(Debug, Clone, Default)]
+pub struct PEResourceDirectoryEntry {
+    /// Resource name, for named entries
+    pub name: Option<String>,
+    /// Resource ID, for entries that are identified by ID rather than name
+    pub id: usize,
+    /// True if this entry points to another resource directory, false if it points to a resource data entry
+    pub is_directory: bool,
+    /// Offset of the sub-directory or data entry, relative to the start of the resource directory
+    pub offset: usize,
+}
+
+/// Stores info about a resource data entry
+#[derive(Debug, Clone, Default)]
+pub struct PEResourceDataEntry {
+    /// RVA of the resource data
+    pub address: usize,
+    pub size: usize,
 }
 
 /// Partially parse a PE header
@@ -86,6 +172,85 @@ pub fn parse_pe_header(pe_data: &[u8]) -> Result<PEHeader, StructureError> {
         (0x169, "MIPS WCEv2"),
     ]);
 
+    // The optional header fields needed to locate the data directories; PE32+ headers have no base_of_data field,
+    // and some fields are 64 bits wide
+    let optional_header_structure_32 = vec![
+        ("magic", "u16"),
+        ("linker_version", "u16"),
+        ("size_of_code", "u32"),
+        ("size_of_initialized_data", "u32"),
+        ("size_of_uninitialized_data", "u32"),
+        ("entry_point", "u32"),
+        ("base_of_code", "u32"),
+        ("base_of_data", "u32"),
+        ("image_base", "u32"),
+        ("section_alignment", "u32"),
+        ("file_alignment", "u32"),
+        ("os_version", "u32"),
+        ("image_version", "u32"),
+        ("subsystem_version", "u32"),
+        ("win32_version", "u32"),
+        ("size_of_image", "u32"),
+        ("size_of_headers", "u32"),
+        ("checksum", "u32"),
+        ("subsystem", "u16"),
+        ("dll_characteristics", "u16"),
+        ("size_of_stack_reserve", "u32"),
+        ("size_of_stack_commit", "u32"),
+        ("size_of_heap_reserve", "u32"),
+        ("size_of_heap_commit", "u32"),
+        ("loader_flags", "u32"),
+        ("number_of_rva_and_sizes", "u32"),
+    ];
+
+    let optional_header_structure_64 = vec![
+        ("magic", "u16"),
+        ("linker_version", "u16"),
+        ("size_of_code", "u32"),
+        ("size_of_initialized_data", "u32"),
+        ("size_of_uninitialized_data", "u32"),
+        ("entry_point", "u32"),
+        ("base_of_code", "u32"),
+        ("image_base", "u64"),
+        ("section_alignment", "u32"),
+        ("file_alignment", "u32"),
+        ("os_version", "u32"),
+        ("image_version", "u32"),
+        ("subsystem_version", "u32"),
+        ("win32_version", "u32"),
+        ("size_of_image", "u32"),
+        ("size_of_headers", "u32"),
+        ("checksum", "u32"),
+        ("subsystem", "u16"),
+        ("dll_characteristics", "u16"),
+        ("size_of_stack_reserve", "u64"),
+        ("size_of_stack_commit", "u64"),
+        ("size_of_heap_reserve", "u64"),
+        ("size_of_heap_commit", "u64"),
+        ("loader_flags", "u32"),
+        ("number_of_rva_and_sizes", "u32"),
+    ];
+
+    let data_directory_structure = vec![("address", "u32"), ("size", "u32")];
+
+    let section_structure = vec![
+        ("name", "u64"),
+        ("virtual_size", "u32"),
+        ("virtual_address", "u32"),
+        ("raw_data_size", "u32"),
+        ("raw_data_offset", "u32"),
+        ("relocations_offset", "u32"),
+        ("line_numbers_offset", "u32"),
+        ("relocations_count", "u16"),
+        ("line_numbers_count", "u16"),
+        ("characteristics", "u32"),
+    ];
+
+    const PE32_MAGIC: usize = 0x10B;
+    const PE32_PLUS_MAGIC: usize = 0x20B;
+    // The loader ignores any data directories beyond the first 16
+    const MAX_DATA_DIRECTORIES: usize = 16;
+
     // Size of PE header structure
     let pe_header_size = common::size(&pe_structure);
 
@@ -119,9 +284,78 @@ pub fn parse_pe_header(pe_data: &[u8]) -> Result<PEHeader, StructureError> {
                     if pe_header["magic"] == PE_MAGIC {
                         // Check the reported machine type
                         if known_machine_types.contains_key(&pe_header["machine"]) {
-                            return Ok(PEHeader {
+                            let mut pe_info = PEHeader {
                                 machine: known_machine_types[&pe_header["machine"]].to_string(),
-                            });
+                                ..Default::default()
+                            };
+
+                            // The optional header immediately follows the PE header
+                            let optional_header_data =
+                                pe_data.get(pe_header_end..).ok_or(StructureError)?;
+
+                            let optional_header_structure = match common::parse(
+                                optional_header_data,
+                                &vec![("magic", "u16")],
+                                "little",
+                            )?["magic"]
+                            {
+                                PE32_MAGIC => &optional_header_structure_32,
+                                PE32_PLUS_MAGIC => &optional_header_structure_64,
+                                _ => return Err(StructureError),
+                            };
+
+                            let optional_header = common::parse(
+                                optional_header_data,
+                                optional_header_structure,
+                                "little",
+                            )?;
+
+                            pe_info.header_size = optional_header["size_of_headers"];
+
+                            // The data directories follow the fixed size fields of the optional header
+                            let mut data_directory_offset = common::size(optional_header_structure);
+
+                            let data_directory_count = optional_header["number_of_rva_and_sizes"]
+                                .min(MAX_DATA_DIRECTORIES);
+
+                            for _ in 0..data_directory_count {
+                                let data_directory = common::parse(
+                                    optional_header_data
+                                        .get(data_directory_offset..)
+                                        .ok_or(StructureError)?,
+                                    &data_directory_structure,
+                                    "little",
+                                )?;
+
+                                pe_info.data_directories.push(PEDataDirectory {
+                                    address: data_directory["address"],
+                                    size: data_directory["size"],
+                                });
+
+                                data_directory_offset += common::size(&data_directory_structure);
+                            }
+
+                            // The section table follows the optional header
+                            let mut section_offset =
+                                pe_header_end + pe_header["optional_header_size"];
+
+                            for _ in 0..pe_header["number_of_sections"] {
+                                let section_data =
+                                    pe_data.get(section_offset..).ok_or(StructureError)?;
+                                let section =
+                                    common::parse(section_data, &section_structure, "little")?;
+
+                                pe_info.sections.push(PESection {
+                                    virtual_address: section["virtual_address"],
+                                    virtual_size: section["virtual_size"],
+                                    raw_data_offset: section["raw_data_offset"],
+                                    raw_data_size: section["raw_data_size"],
+                                });
+
+                                section_offset += common::size(&section_structure);
+                            }
+
+                            return Ok(pe_info);
                         }
                     }
                 }
@@ -131,3 +365,97 @@ pub fn parse_pe_header(pe_data: &[u8]) -> Result<PEHeader, StructureError> {
 
     Err(StructureError)
 }
+
+/// Parses a resource directory, located at `directory_offset` in the resource data, and returns its entries
+pub fn parse_pe_resource_directory(
+    resource_data: &[u8],
+    directory_offset: usize,
+) -> Result<Vec<PEResourceDirectoryEntry>, StructureError> {
+    // High bit of the entry fields; indicates that the entry has a name, or points to a sub-directory
+    const HIGH_BIT: usize = 0x80000000;
+
+    let directory_structure = vec![
+        ("characteristics", "u32"),
+        ("timestamp", "u32"),
+        ("major_version", "u16"),
+        ("minor_version", "u16"),
+        ("named_entries", "u16"),
+        ("id_entries", "u16"),
+    ];
+
+    let entry_structure = vec![("name", "u32"), ("offset", "u32")];
+
+    let mut entries = vec![];
+
+    let directory_data = resource_data
+        .get(directory_offset..)
+        .ok_or(StructureError)?;
+    let directory = common::parse(directory_data, &directory_structure, "little")?;
+
+    let mut entry_offset = common::size(&directory_structure);
+
+    for _ in 0..(directory["named_entries"] + directory["id_entries"]) {
+        let entry = common::parse(
+            directory_data.get(entry_offset..).ok_or(StructureError)?,
+            &entry_structure,
+            "little",
+        )?;
+
+        let mut directory_entry = PEResourceDirectoryEntry {
+            is_directory: (entry["offset"] & HIGH_BIT) != 0,
+            offset: entry["offset"] & !HIGH_BIT,
+            ..Default::default()
+        };
+
+        // Names are length-prefixed UTF-16 strings
+        if (entry["name"] & HIGH_BIT) != 0 {
+            let name_offset = entry["name"] & !HIGH_BIT;
+            let name_length = common::parse(
+                resource_data.get(name_offset..).ok_or(StructureError)?,
+                &vec![("length", "u16")],
+                "little",
+            )?["length"];
+
+            let name_data = resource_data
+                .get(name_offset + 2..name_offset + 2 + (name_length * 2))
+                .ok_or(StructureError)?;
+            let name_utf16: Vec<u16> = name_data
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+
+            directory_entry.name = Some(String::from_utf16_lossy(&name_utf16));
+        } else {
+            directory_entry.id = entry["name"];
+        }
+
+        entries.push(directory_entry);
+        entry_offset += common::size(&entry_structure);
+    }
+
+    Ok(entries)
+}
+
+/// Parses a resource data entry, located at `entry_offset` in the resource data
+pub fn parse_pe_resource_data_entry(
+    resource_data: &[u8],
+    entry_offset: usize,
+) -> Result<PEResourceDataEntry, StructureError> {
+    let data_entry_structure = vec![
+        ("address", "u32"),
+        ("size", "u32"),
+        ("codepage", "u32"),
+        ("reserved", "u32"),
+    ];
+
+    let data_entry = common::parse(
+        resource_data.get(entry_offset..).ok_or(StructureError)?,
+        &data_entry_structure,
+        "little",
+    )?;
+
+    Ok(PEResourceDataEntry {
+        address: data_entry["address"],
+        size: data_entry["size"],
+    })
+}
@@ -14,6 +14,18 @@ pub struct SquashFSHeader {
     pub major_version: usize,
     pub minor_version: usize,
     pub uid_table_start: usize,
+    /// Location of the root directory inode: the offset of its metadata block from the start of the inode table,
+    /// shifted left 16 bits, plus its offset inside of the uncompressed metadata block
+    pub root_inode: usize,
+    pub inode_table_start: usize,
+    pub directory_table_start: usize,
+    pub fragment_table_start: usize,
+    /// SquashFSv4 only
+    pub fragment_count: usize,
+    /// SquashFSv4 only
+    pub id_count: usize,
+    /// Out-of-spec header values that were tolerated, as produced by vendor-modified SquashFS tools
+    pub quirks: Vec<String>,
 }
 
 /// Parse a SquashFS superblock header
@@ -23,6 +35,15 @@ pub fn parse_squashfs_header(sqsh_data: &[u8]) -> Result<SquashFSHeader, Structu
     const SQUASHFS_VERSION_END: usize = 30;
     const SQUASHFS_VERSION_START: usize = 28;
     const MIN_SQUASHFS_HEADER_SIZE: usize = 120;
+    // Block sizes permitted by the SquashFS specification
+    const MIN_SPEC_BLOCK_SIZE: usize = 4096;
+    const MAX_SPEC_BLOCK_SIZE: usize = 1024 * 1024;
+    // Sanity limit on vendor block sizes
+    const MAX_BLOCK_SIZE: usize = 16 * 1024 * 1024;
+    // Flags defined by SquashFSv4
+    const SQUASHFS_V4_FLAGS_MASK: usize = 0x0FFF;
+    // Magic bytes used by standard SquashFS tools, for little and big endian images respectively
+    const STANDARD_MAGICS: [&[u8]; 2] = [b"hsqs", b"sqsh"];
 
     let squashfs_v4_structure = vec![
         ("magic", "u32"),
@@ -39,6 +60,11 @@ pub fn parse_squashfs_header(sqsh_data: &[u8]) -> Result<SquashFSHeader, Structu
         ("root_inode_ref", "u64"),
         ("image_size", "u64"),
         ("uid_start", "u64"),
+        ("xattr_table_start", "u64"),
+        ("inode_table_start", "u64"),
+        ("directory_table_start", "u64"),
+        ("fragment_table_start", "u64"),
+        ("export_table_start", "u64"),
     ];
 
     let squashfs_v3_structure = vec![
@@ -150,14 +176,66 @@ pub fn parse_squashfs_header(sqsh_data: &[u8]) -> Result<SquashFSHeader, Structu
 
             // Make sure the reported image size is at least bigger than the SquashFS header
             if sqsh_header.image_size > MIN_SQUASHFS_HEADER_SIZE {
-                // Make sure the block size and block log fields agree
-                if squashfs_header["block_size"] > 0
-                    && squashfs_header["block_log"]
-                        == (squashfs_header["block_size"].ilog2() as usize)
+                let block_size = squashfs_header["block_size"];
+
+                /*
+                 * Vendor tools are known to use block sizes which disagree with the block log field. Since this is also
+                 * what random data looks like, only tolerate it if the superblock's table pointers are laid out the way
+                 * the SquashFS tools lay them out: in order, after the superblock, and inside the image.
+                 */
+                let block_log_agrees =
+                    block_size > 0 && squashfs_header["block_log"] == (block_size.ilog2() as usize);
+                let tables_agree = squashfs_header_size <= squashfs_header["inode_table_start"]
+                    && squashfs_header["inode_table_start"]
+                        < squashfs_header["directory_table_start"]
+                    && squashfs_header["directory_table_start"] <= squashfs_header["uid_start"]
+                    && squashfs_header["uid_start"] < sqsh_header.image_size;
+
+                // Vendor-modified images may use out-of-spec block sizes, but the block size must still be sane
+                if block_size > 0
+                    && block_size <= MAX_BLOCK_SIZE
+                    && (block_log_agrees || tables_agree)
                 {
+                    /*
+                     * Nonstandard magic bytes, non-power-of-two block sizes, and undefined flags don't prevent sasquatch
+                     * from extracting the image, so they are tolerated, but reported.
+                     */
+                    if !STANDARD_MAGICS.contains(&&sqsh_data[0..4]) {
+                        sqsh_header.quirks.push(format!(
+                            "vendor magic '{}'",
+                            String::from_utf8_lossy(&sqsh_data[0..4])
+                        ));
+                    }
+
+                    if !block_size.is_power_of_two() {
+                        sqsh_header
+                            .quirks
+                            .push("non-power-of-two block size".to_string());
+                    }
+
+                    if !block_log_agrees {
+                        sqsh_header
+                            .quirks
+                            .push("block size and block log mismatch".to_string());
+                    }
+
+                    if !(MIN_SPEC_BLOCK_SIZE..=MAX_SPEC_BLOCK_SIZE).contains(&block_size) {
+                        sqsh_header
+                            .quirks
+                            .push("out-of-spec block size".to_string());
+                    }
+
+                    if squashfs_version == 4
+                        && squashfs_header["flags"] & !SQUASHFS_V4_FLAGS_MASK != 0
+                    {
+                        sqsh_header
+                            .quirks
+                            .push(format!("undefined flags {:#06X}", squashfs_header["flags"]));
+                    }
+
                     // Report relevant squashfs fields
                     sqsh_header.timestamp = squashfs_header["modification_time"];
-                    sqsh_header.block_size = squashfs_header["block_size"];
+                    sqsh_header.block_size = block_size;
                     sqsh_header.header_size = squashfs_header_size;
                     sqsh_header.inode_count = squashfs_header["inode_count"];
                     sqsh_header.major_version = squashfs_header["major_version"];
@@ -169,6 +247,17 @@ pub fn parse_squashfs_header(sqsh_data: &[u8]) -> Result<SquashFSHeader, Structu
                         sqsh_header.compression = squashfs_header["compression_id"];
                     }
 
+                    // Table locations, used by the internal SquashFSv4 extractor
+                    sqsh_header.root_inode = squashfs_header["root_inode_ref"];
+                    sqsh_header.inode_table_start = squashfs_header["inode_table_start"];
+                    sqsh_header.directory_table_start = squashfs_header["directory_table_start"];
+                    sqsh_header.fragment_table_start = squashfs_header["fragment_table_start"];
+
+                    if squashfs_version == 4 {
+                        sqsh_header.fragment_count = squashfs_header["fragment_count"];
+                        sqsh_header.id_count = squashfs_header["id_count"];
+                    }
+
                     return Ok(sqsh_header);
                 }
             }
@@ -200,3 +289,339 @@ pub fn parse_squashfs_uid_entry(
         }
     }
 }
+
+/// Maximum size of a SquashFSv4 metadata block, once uncompressed
+pub const SQUASHFS_METADATA_SIZE: usize = 8192;
+
+/// Fragment index of SquashFSv4 files whose tail end is not stored in a fragment
+pub const SQUASHFS_NO_FRAGMENT: usize = 0xFFFFFFFF;
+
+// SquashFSv4 inode types; each extended inode type is the basic inode type plus 7
+pub const SQUASHFS_DIRECTORY: usize = 1;
+pub const SQUASHFS_REGULAR_FILE: usize = 2;
+pub const SQUASHFS_SYMLINK: usize = 3;
+pub const SQUASHFS_BLOCK_DEVICE: usize = 4;
+pub const SQUASHFS_CHARACTER_DEVICE: usize = 5;
+pub const SQUASHFS_FIFO: usize = 6;
+pub const SQUASHFS_SOCKET: usize = 7;
+
+/// Stores SquashFSv4 metadata block header info
+#[derive(Debug, Default, Clone)]
+pub struct SquashFSMetadataHeader {
+    pub data_size: usize,
+    pub compressed: bool,
+    pub header_size: usize,
+}
+
+/// Parse the two byte header that precedes each SquashFSv4 metadata block
+pub fn parse_squashfs_metadata_header(
+    metadata_data: &[u8],
+    endianness: &str,
+) -> Result<SquashFSMetadataHeader, StructureError> {
+    const UNCOMPRESSED_FLAG: usize = 0x8000;
+
+    let metadata_header_structure = vec![("size", "u16")];
+
+    let metadata_header = common::parse(metadata_data, &metadata_header_structure, endianness)?;
+    let data_size = metadata_header["size"] & !UNCOMPRESSED_FLAG;
+
+    if data_size == 0 || data_size > SQUASHFS_METADATA_SIZE {
+        return Err(StructureError);
+    }
+
+    Ok(SquashFSMetadataHeader {
+        data_size,
+        compressed: metadata_header["size"] & UNCOMPRESSED_FLAG == 0,
+        header_size: common::size(&metadata_header_structure),
+    })
+}
+
+/// Stores SquashFSv4 inode info; fields that don't apply to the inode's type are left at 0
+#[derive(Debug, Default, Clone)]
+pub struct SquashFSInode {
+    /// One of the basic inode types, regardless of whether the inode is a basic or extended inode
+    pub file_type: usize,
+    pub permissions: usize,
+    pub uid_index: usize,
+    pub gid_index: usize,
+    pub mtime: usize,
+    pub inode_number: usize,
+    /// Offset of the directory's metadata block from the start of the directory table
+    pub directory_block: usize,
+    /// Offset of the directory's listing inside of the uncompressed metadata block
+    pub directory_offset: usize,
+    /// Size of the directory listing
+    pub directory_size: usize,
+    /// Offset of the file's first data block from the start of the image
+    pub blocks_start: usize,
+    pub file_size: usize,
+    pub fragment_index: usize,
+    /// Offset of the tail end of the file inside of the uncompressed fragment block
+    pub fragment_offset: usize,
+    /// Size words of each of the file's data blocks
+    pub block_sizes: Vec<usize>,
+    pub symlink_target: Vec<u8>,
+    /// Encoded device number of block and character devices
+    pub device: usize,
+}
+
+/// Parse a SquashFSv4 inode, basic or extended. The inode data must contain the entire inode, including any trailing
+/// data block sizes or symlink target.
+pub fn parse_squashfs_inode(
+    inode_data: &[u8],
+    block_size: usize,
+    endianness: &str,
+) -> Result<SquashFSInode, StructureError> {
+    const EXTENDED_INODE_TYPE_OFFSET: usize = 7;
+
+    let inode_header_structure = vec![
+        ("inode_type", "u16"),
+        ("permissions", "u16"),
+        ("uid_index", "u16"),
+        ("gid_index", "u16"),
+        ("mtime", "u32"),
+        ("inode_number", "u32"),
+    ];
+
+    let directory_structure = vec![
+        ("block_index", "u32"),
+        ("link_count", "u32"),
+        ("file_size", "u16"),
+        ("block_offset", "u16"),
+        ("parent_inode", "u32"),
+    ];
+
+    let extended_directory_structure = vec![
+        ("link_count", "u32"),
+        ("file_size", "u32"),
+        ("block_index", "u32"),
+        ("parent_inode", "u32"),
+        ("index_count", "u16"),
+        ("block_offset", "u16"),
+        ("xattr_index", "u32"),
+    ];
+
+    let file_structure = vec![
+        ("blocks_start", "u32"),
+        ("fragment_index", "u32"),
+        ("block_offset", "u32"),
+        ("file_size", "u32"),
+    ];
+
+    let extended_file_structure = vec![
+        ("blocks_start", "u64"),
+        ("file_size", "u64"),
+        ("sparse", "u64"),
+        ("link_count", "u32"),
+        ("fragment_index", "u32"),
+        ("block_offset", "u32"),
+        ("xattr_index", "u32"),
+    ];
+
+    // Symlinks and device files have the same layout, regardless of whether they are basic or extended inodes
+    let symlink_structure = vec![("link_count", "u32"), ("target_size", "u32")];
+    let device_structure = vec![("link_count", "u32"), ("device", "u32")];
+    let ipc_structure = vec![("link_count", "u32")];
+
+    let inode_header = common::parse(inode_data, &inode_header_structure, endianness)?;
+    let inode_body = &inode_data[common::size(&inode_header_structure)..];
+
+    let mut inode = SquashFSInode {
+        file_type: inode_header["inode_type"],
+        permissions: inode_header["permissions"],
+        uid_index: inode_header["uid_index"],
+        gid_index: inode_header["gid_index"],
+        mtime: inode_header["mtime"],
+        inode_number: inode_header["inode_number"],
+        ..Default::default()
+    };
+
+    let extended = inode.file_type > EXTENDED_INODE_TYPE_OFFSET;
+
+    if extended {
+        inode.file_type -= EXTENDED_INODE_TYPE_OFFSET;
+    }
+
+    match inode.file_type {
+        SQUASHFS_DIRECTORY => {
+            let directory = match extended {
+                false => common::parse(inode_body, &directory_structure, endianness)?,
+                true => common::parse(inode_body, &extended_directory_structure, endianness)?,
+            };
+
+            inode.directory_block = directory["block_index"];
+            inode.directory_offset = directory["block_offset"];
+            inode.directory_size = directory["file_size"];
+        }
+        SQUASHFS_REGULAR_FILE => {
+            let (file, file_structure_size) = match extended {
+                false => (
+                    common::parse(inode_body, &file_structure, endianness)?,
+                    common::size(&file_structure),
+                ),
+                true => (
+                    common::parse(inode_body, &extended_file_structure, endianness)?,
+                    common::size(&extended_file_structure),
+                ),
+            };
+
+            inode.blocks_start = file["blocks_start"];
+            inode.file_size = file["file_size"];
+            inode.fragment_index = file["fragment_index"];
+            inode.fragment_offset = file["block_offset"];
+
+            // The tail end of the file is stored in a fragment, if it isn't stored in a block of its own
+            let block_count = match inode.fragment_index {
+                SQUASHFS_NO_FRAGMENT => inode.file_size.div_ceil(block_size),
+                _ => inode.file_size / block_size,
+            };
+
+            let block_size_structure = vec![("size", "u32")];
+            let block_size_size = common::size(&block_size_structure);
+
+            // Each block size must be present in the inode data; this also limits the size of the block list
+            let block_list = inode_body
+                .get(file_structure_size..)
+                .and_then(|block_list| block_list.get(..block_count.checked_mul(block_size_size)?))
+                .ok_or(StructureError)?;
+
+            for block_size_data in block_list.chunks_exact(block_size_size) {
+                inode.block_sizes.push(
+                    common::parse(block_size_data, &block_size_structure, endianness)?["size"],
+                );
+            }
+        }
+        SQUASHFS_SYMLINK => {
+            let symlink = common::parse(inode_body, &symlink_structure, endianness)?;
+            let target_start = common::size(&symlink_structure);
+
+            inode.symlink_target = inode_body
+                .get(target_start..target_start + symlink["target_size"])
+                .ok_or(StructureError)?
+                .to_vec();
+        }
+        SQUASHFS_BLOCK_DEVICE | SQUASHFS_CHARACTER_DEVICE => {
+            inode.device = common::parse(inode_body, &device_structure, endianness)?["device"];
+        }
+        SQUASHFS_FIFO | SQUASHFS_SOCKET => {
+            common::parse(inode_body, &ipc_structure, endianness)?;
+        }
+        _ => {
+            return Err(StructureError);
+        }
+    }
+
+    Ok(inode)
+}
+
+/// Stores SquashFSv4 directory entry info
+#[derive(Debug, Default, Clone)]
+pub struct SquashFSDirectoryEntry {
+    pub name: Vec<u8>,
+    /// Offset of the entry's inode metadata block from the start of the inode table
+    pub inode_block: usize,
+    /// Offset of the entry's inode inside of the uncompressed metadata block
+    pub inode_offset: usize,
+}
+
+/// Parse a SquashFSv4 directory listing, which consists of one or more headers, each followed by the entries whose
+/// inodes are stored in the same inode metadata block
+pub fn parse_squashfs_directory(
+    directory_data: &[u8],
+    endianness: &str,
+) -> Result<Vec<SquashFSDirectoryEntry>, StructureError> {
+    // Directory headers can not describe more than 256 entries
+    const MAX_HEADER_ENTRIES: usize = 256;
+
+    let directory_header_structure =
+        vec![("count", "u32"), ("start", "u32"), ("inode_number", "u32")];
+
+    let directory_entry_structure = vec![
+        ("offset", "u16"),
+        ("inode_number_delta", "u16"),
+        ("type", "u16"),
+        ("name_size", "u16"),
+    ];
+
+    let mut entries: Vec<SquashFSDirectoryEntry> = vec![];
+    let mut offset: usize = 0;
+
+    while offset < directory_data.len() {
+        let directory_header = common::parse(
+            &directory_data[offset..],
+            &directory_header_structure,
+            endianness,
+        )?;
+        offset += common::size(&directory_header_structure);
+
+        // Counts are stored minus one
+        let entry_count = directory_header["count"] + 1;
+
+        if entry_count > MAX_HEADER_ENTRIES {
+            return Err(StructureError);
+        }
+
+        for _ in 0..entry_count {
+            let directory_entry = common::parse(
+                directory_data.get(offset..).ok_or(StructureError)?,
+                &directory_entry_structure,
+                endianness,
+            )?;
+            offset += common::size(&directory_entry_structure);
+
+            // Names are stored without a NULL terminator, and their size is stored minus one
+            let name_size = directory_entry["name_size"] + 1;
+            let name = directory_data
+                .get(offset..offset + name_size)
+                .ok_or(StructureError)?;
+            offset += name_size;
+
+            entries.push(SquashFSDirectoryEntry {
+                name: name.to_vec(),
+                inode_block: directory_header["start"],
+                inode_offset: directory_entry["offset"],
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Stores SquashFSv4 fragment table entry info
+#[derive(Debug, Default, Clone)]
+pub struct SquashFSFragment {
+    /// Offset of the fragment block from the start of the image
+    pub start: usize,
+    /// Size word of the fragment block, in the same format as a data block size word
+    pub size: usize,
+}
+
+/// Parse a SquashFSv4 fragment table entry
+pub fn parse_squashfs_fragment(
+    fragment_data: &[u8],
+    endianness: &str,
+) -> Result<SquashFSFragment, StructureError> {
+    let fragment_structure = vec![("start", "u64"), ("size", "u32"), ("unused", "u32")];
+
+    let fragment = common::parse(fragment_data, &fragment_structure, endianness)?;
+
+    Ok(SquashFSFragment {
+        start: fragment["start"],
+        size: fragment["size"],
+    })
+}
+
+/// Parse a SquashFSv4 table pointer, which locates a metadata block in the fragment, ID or export tables
+pub fn parse_squashfs_table_pointer(
+    pointer_data: &[u8],
+    endianness: &str,
+) -> Result<usize, StructureError> {
+    let pointer_structure = vec![("pointer", "u64")];
+    Ok(common::parse(pointer_data, &pointer_structure, endianness)?["pointer"])
+}
+
+/// Parse a SquashFSv4 ID table entry, which is a UID or GID
+pub fn parse_squashfs_id(id_data: &[u8], endianness: &str) -> Result<usize, StructureError> {
+    let id_structure = vec![("id", "u32")];
+    Ok(common::parse(id_data, &id_structure, endianness)?["id"])
+}
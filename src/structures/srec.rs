@@ -0,0 +1,60 @@
+use crate::structures::common::StructureError;
+use crate::structures::ihex::{MemoryImage, record_line};
+
+/// Parses Motorola S-records up to, and including, the termination record; every record's checksum is validated
+pub fn parse_srec(srec_data: &[u8]) -> Result<MemoryImage, StructureError> {
+    // Size of the byte count field that precedes the address
+    const BYTE_COUNT_SIZE: usize = 1;
+    const CHECKSUM_SIZE: usize = 1;
+
+    let mut image = MemoryImage::default();
+    let mut offset: usize = 0;
+
+    while let Some((line, next_offset)) = record_line(srec_data, offset) {
+        let (record_type, record_hex) = match line {
+            [b'S', record_type, record_hex @ ..] => (*record_type, record_hex),
+            _ => return Err(StructureError),
+        };
+
+        let record = hex::decode(record_hex).map_err(|_| StructureError)?;
+
+        // The size of the address field depends on the record type; S4 records are reserved
+        let address_size: usize = match record_type {
+            b'0' | b'1' | b'5' | b'9' => 2,
+            b'2' | b'6' | b'8' => 3,
+            b'3' | b'7' => 4,
+            _ => return Err(StructureError),
+        };
+
+        if record.len() < BYTE_COUNT_SIZE + address_size + CHECKSUM_SIZE
+            || record.len() != BYTE_COUNT_SIZE + record[0] as usize
+        {
+            return Err(StructureError);
+        }
+
+        // The one's complement checksum makes the sum of all record bytes 0xFF
+        if record
+            .iter()
+            .fold(0_u8, |sum, byte| sum.wrapping_add(*byte))
+            != 0xFF
+        {
+            return Err(StructureError);
+        }
+
+        let address = record[BYTE_COUNT_SIZE..BYTE_COUNT_SIZE + address_size]
+            .iter()
+            .fold(0, |address, byte| (address << 8) | *byte as usize);
+        let data = &record[BYTE_COUNT_SIZE + address_size..record.len() - CHECKSUM_SIZE];
+
+        match record_type {
+            b'1' | b'2' | b'3' => image.records.push((address, data.to_vec())),
+            b'7' | b'8' | b'9' => return image.finish(next_offset),
+            // Header and record count records do not affect the image contents
+            _ => (),
+        }
+
+        offset = next_offset;
+    }
+
+    Err(StructureError)
+}
@@ -0,0 +1,90 @@
+use crate::structures::common::{self, StructureError};
+
+/// Size of the header common to all SWF files; the body of uncompressed and zlib compressed SWF files immediately
+/// follows it
+pub const SWF_HEADER_SIZE: usize = 8;
+
+/// Compression applied to the body of an SWF file
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SWFCompression {
+    #[default]
+    None,
+    Zlib,
+    Lzma,
+}
+
+/// Struct to store SWF header info
+#[derive(Debug, Default, Clone)]
+pub struct SWFHeader {
+    pub compression: SWFCompression,
+    pub version: u8,
+    /// Size of the uncompressed SWF file, including the SWF header
+    pub file_length: usize,
+    /// Offset of the (possibly compressed) body, relative to the start of the SWF header
+    pub body_offset: usize,
+    /// Size of the LZMA compressed body, not including the LZMA properties; only present in LZMA compressed SWF files
+    pub compressed_size: Option<usize>,
+    /// LZMA properties of LZMA compressed SWF files, empty otherwise
+    pub lzma_properties: Vec<u8>,
+}
+
+/// Parses an SWF header
+pub fn parse_swf_header(swf_data: &[u8]) -> Result<SWFHeader, StructureError> {
+    // LZMA compression was introduced in SWF version 13
+    const MIN_LZMA_VERSION: u8 = 13;
+    // Sanity limit on the SWF version
+    const MAX_VERSION: u8 = 64;
+    // Size of the LZMA properties that precede the LZMA compressed body
+    const LZMA_PROPERTIES_SIZE: usize = 5;
+
+    let swf_structure = vec![
+        ("magic_p1", "u16"),
+        ("magic_p2", "u8"),
+        ("version", "u8"),
+        ("file_length", "u32"),
+    ];
+
+    let lzma_structure = vec![("compressed_size", "u32")];
+
+    let swf_header = common::parse(swf_data, &swf_structure, "little")?;
+
+    let mut header = SWFHeader {
+        compression: match &swf_data[..3] {
+            b"FWS" => SWFCompression::None,
+            b"CWS" => SWFCompression::Zlib,
+            b"ZWS" => SWFCompression::Lzma,
+            _ => return Err(StructureError),
+        },
+        version: swf_header["version"] as u8,
+        file_length: swf_header["file_length"],
+        body_offset: SWF_HEADER_SIZE,
+        ..Default::default()
+    };
+
+    if header.version == 0 || header.version > MAX_VERSION || header.file_length <= SWF_HEADER_SIZE
+    {
+        return Err(StructureError);
+    }
+
+    if header.compression == SWFCompression::Lzma {
+        if header.version < MIN_LZMA_VERSION {
+            return Err(StructureError);
+        }
+
+        let lzma_header = common::parse(
+            swf_data.get(SWF_HEADER_SIZE..).ok_or(StructureError)?,
+            &lzma_structure,
+            "little",
+        )?;
+
+        let properties_offset = SWF_HEADER_SIZE + common::size(&lzma_structure);
+        header.body_offset = properties_offset + LZMA_PROPERTIES_SIZE;
+        header.compressed_size = Some(lzma_header["compressed_size"]);
+        header.lzma_properties = swf_data
+            .get(properties_offset..header.body_offset)
+            .ok_or(StructureError)?
+            .to_vec();
+    }
+
+    Ok(header)
+}
@@ -1,19 +1,36 @@
+use crate::common::guid_to_string;
 use crate::structures::common::{self, StructureError};
 
+/// Erased flash bytes are 0xFF, rather than 0x00, if this volume attribute is set
+pub const UEFI_VOLUME_ERASE_POLARITY: usize = 0x800;
+
 /// Stores info about a UEFI volume header
 #[derive(Debug, Default, Clone)]
 pub struct UEFIVolumeHeader {
     pub header_crc: usize,
     pub header_size: usize,
     pub volume_size: usize,
+    pub attributes: usize,
+    /// File system GUID, e.g. "8C8CE578-8A3D-4F1C-9935-896185C32DD3" for FFSv2 volumes
+    pub file_system: String,
+    /// Offset of the first FFS file, relative to the start of the volume; this follows the extended header, if any
+    pub files_offset: usize,
+    /// True if the 16-bit header checksum is valid
+    pub checksum_valid: bool,
 }
 
-/// Parse a UEFI volume header
+/// Parse a UEFI volume header, starting at the beginning of the volume (not at the "_FVH" magic bytes)
 pub fn parse_uefi_volume_header(uefi_data: &[u8]) -> Result<UEFIVolumeHeader, StructureError> {
     // The revision field must be 1 or 2
     let valid_revisions: Vec<usize> = vec![1, 2];
+    // FFS files are 8-byte aligned
+    const FILE_ALIGNMENT: usize = 8;
 
     let uefi_pi_header_structure = vec![
+        ("zero_vector_p1", "u64"),
+        ("zero_vector_p2", "u64"),
+        ("file_system_guid_p1", "u64"),
+        ("file_system_guid_p2", "u64"),
         ("volume_size", "u64"),
         ("magic", "u32"),
         ("attributes", "u32"),
@@ -24,26 +41,231 @@ pub fn parse_uefi_volume_header(uefi_data: &[u8]) -> Result<UEFIVolumeHeader, St
         ("revision", "u8"),
     ];
 
+    let extended_header_structure = vec![
+        ("volume_name_p1", "u64"),
+        ("volume_name_p2", "u64"),
+        ("extended_header_size", "u32"),
+    ];
+
     // Parse the volume header
-    if let Ok(uefi_volume_header) = common::parse(uefi_data, &uefi_pi_header_structure, "little") {
-        // Make sure the header size is sane (must be smaller than the total volume size)
-        if uefi_volume_header["header_size"] < uefi_volume_header["volume_size"] {
-            // The reserved field *must* be 0
-            if uefi_volume_header["reserved"] == 0 {
-                // The revision number must be 1 or 2
-                if valid_revisions.contains(&uefi_volume_header["revision"]) {
-                    return Ok(UEFIVolumeHeader {
-                        // TODO: Validate UEFI header CRC
-                        header_crc: uefi_volume_header["header_crc"],
-                        header_size: uefi_volume_header["header_size"],
-                        volume_size: uefi_volume_header["volume_size"],
-                    });
-                }
-            }
-        }
+    let uefi_volume_header = common::parse(uefi_data, &uefi_pi_header_structure, "little")?;
+
+    let header_size = uefi_volume_header["header_size"];
+    let volume_size = uefi_volume_header["volume_size"];
+
+    // Make sure the header size is sane (must be smaller than the total volume size), the reserved field *must* be 0,
+    // and the revision number must be 1 or 2
+    if header_size < common::size(&uefi_pi_header_structure)
+        || header_size >= volume_size
+        || uefi_volume_header["reserved"] != 0
+        || !valid_revisions.contains(&uefi_volume_header["revision"])
+    {
+        return Err(StructureError);
     }
 
-    Err(StructureError)
+    // The header checksum is a 16-bit sum of the entire header, including the block map, which must be zero
+    let checksum_valid = uefi_data.get(..header_size).is_some_and(|header_data| {
+        header_data.chunks_exact(2).fold(0u16, |sum, word| {
+            sum.wrapping_add(u16::from_le_bytes([word[0], word[1]]))
+        }) == 0
+    });
+
+    // Files start after the header, or after the extended header, if there is one
+    let mut files_offset = header_size;
+
+    let extended_header_offset = uefi_volume_header["extended_header_offset"];
+
+    if extended_header_offset != 0 {
+        let extended_header = common::parse(
+            uefi_data
+                .get(extended_header_offset..)
+                .ok_or(StructureError)?,
+            &extended_header_structure,
+            "little",
+        )?;
+
+        files_offset =
+            extended_header_offset.saturating_add(extended_header["extended_header_size"]);
+    }
+
+    files_offset = files_offset.next_multiple_of(FILE_ALIGNMENT);
+
+    if files_offset > volume_size {
+        return Err(StructureError);
+    }
+
+    Ok(UEFIVolumeHeader {
+        header_crc: uefi_volume_header["header_crc"],
+        header_size,
+        volume_size,
+        attributes: uefi_volume_header["attributes"],
+        file_system: guid_to_string(&uefi_data[16..32]),
+        files_offset,
+        checksum_valid,
+    })
+}
+
+/// FFS file types; all other file types, except pad files, are made up of sections
+pub const UEFI_FILE_TYPE_RAW: usize = 0x01;
+pub const UEFI_FILE_TYPE_PAD: usize = 0xF0;
+
+/// Stores info about a UEFI firmware file system (FFS) file header
+#[derive(Debug, Default, Clone)]
+pub struct UEFIFileHeader {
+    /// The file name GUID
+    pub name: String,
+    pub file_type: usize,
+    pub state: usize,
+    pub header_size: usize,
+    /// Total size of the file, including the header
+    pub size: usize,
+}
+
+/// Parse a UEFI FFS file header
+pub fn parse_uefi_file_header(file_data: &[u8]) -> Result<UEFIFileHeader, StructureError> {
+    // Files larger than 16MB have an additional 64-bit size field
+    const FFS_ATTRIB_LARGE_FILE: usize = 0x01;
+
+    let file_header_structure = vec![
+        ("name_p1", "u64"),
+        ("name_p2", "u64"),
+        ("header_checksum", "u8"),
+        ("file_checksum", "u8"),
+        ("file_type", "u8"),
+        ("attributes", "u8"),
+        ("size", "u24"),
+        ("state", "u8"),
+    ];
+
+    let large_file_structure = vec![("extended_size", "u64")];
+
+    let file_header = common::parse(file_data, &file_header_structure, "little")?;
+
+    let mut header_size = common::size(&file_header_structure);
+    let mut size = file_header["size"];
+
+    if file_header["attributes"] & FFS_ATTRIB_LARGE_FILE != 0 {
+        let extended_header =
+            common::parse(&file_data[header_size..], &large_file_structure, "little")?;
+        header_size += common::size(&large_file_structure);
+        size = extended_header["extended_size"];
+    }
+
+    if size < header_size {
+        return Err(StructureError);
+    }
+
+    Ok(UEFIFileHeader {
+        name: guid_to_string(&file_data[0..16]),
+        file_type: file_header["file_type"],
+        state: file_header["state"],
+        header_size,
+        size,
+    })
+}
+
+/// Section types
+pub const UEFI_SECTION_COMPRESSION: usize = 0x01;
+pub const UEFI_SECTION_GUID_DEFINED: usize = 0x02;
+pub const UEFI_SECTION_USER_INTERFACE: usize = 0x15;
+pub const UEFI_SECTION_FIRMWARE_VOLUME_IMAGE: usize = 0x17;
+
+/// Stores info about a UEFI FFS section header
+#[derive(Debug, Default, Clone)]
+pub struct UEFISectionHeader {
+    pub section_type: usize,
+    pub header_size: usize,
+    /// Total size of the section, including the header
+    pub size: usize,
+}
+
+/// Parse a UEFI FFS section header
+pub fn parse_uefi_section_header(section_data: &[u8]) -> Result<UEFISectionHeader, StructureError> {
+    // A size of 0xFFFFFF indicates that the section size is stored in an additional 32-bit size field
+    const EXTENDED_SIZE: usize = 0xFFFFFF;
+
+    let section_header_structure = vec![("size", "u24"), ("section_type", "u8")];
+    let extended_size_structure = vec![("extended_size", "u32")];
+
+    let section_header = common::parse(section_data, &section_header_structure, "little")?;
+
+    let mut header_size = common::size(&section_header_structure);
+    let mut size = section_header["size"];
+
+    if size == EXTENDED_SIZE {
+        let extended_header = common::parse(
+            &section_data[header_size..],
+            &extended_size_structure,
+            "little",
+        )?;
+        header_size += common::size(&extended_size_structure);
+        size = extended_header["extended_size"];
+    }
+
+    if size < header_size {
+        return Err(StructureError);
+    }
+
+    Ok(UEFISectionHeader {
+        section_type: section_header["section_type"],
+        header_size,
+        size,
+    })
+}
+
+/// Stores info about the header of a UEFI compression section
+#[derive(Debug, Default, Clone)]
+pub struct UEFICompressionSection {
+    pub uncompressed_size: usize,
+    pub compression_type: usize,
+    /// Offset of the compressed data, relative to the end of the common section header
+    pub data_offset: usize,
+}
+
+/// Parse the header of a UEFI compression section, which follows the common section header
+pub fn parse_uefi_compression_section(
+    section_data: &[u8],
+) -> Result<UEFICompressionSection, StructureError> {
+    let compression_section_structure =
+        vec![("uncompressed_size", "u32"), ("compression_type", "u8")];
+
+    let compression_header = common::parse(section_data, &compression_section_structure, "little")?;
+
+    Ok(UEFICompressionSection {
+        uncompressed_size: compression_header["uncompressed_size"],
+        compression_type: compression_header["compression_type"],
+        data_offset: common::size(&compression_section_structure),
+    })
+}
+
+/// Stores info about the header of a UEFI GUID defined section
+#[derive(Debug, Default, Clone)]
+pub struct UEFIGuidDefinedSection {
+    /// GUID identifying the section format, e.g. "EE4E5898-3914-4259-9D6E-DC7BD79403CF" for LZMA compressed sections
+    pub guid: String,
+    /// Offset of the section data, relative to the start of the section
+    pub data_offset: usize,
+    pub attributes: usize,
+}
+
+/// Parse the header of a UEFI GUID defined section, which follows the common section header
+pub fn parse_uefi_guid_defined_section(
+    section_data: &[u8],
+) -> Result<UEFIGuidDefinedSection, StructureError> {
+    let guid_defined_section_structure = vec![
+        ("guid_p1", "u64"),
+        ("guid_p2", "u64"),
+        ("data_offset", "u16"),
+        ("attributes", "u16"),
+    ];
+
+    let guid_header = common::parse(section_data, &guid_defined_section_structure, "little")?;
+
+    Ok(UEFIGuidDefinedSection {
+        guid: guid_to_string(&section_data[0..16]),
+        data_offset: guid_header["data_offset"],
+        attributes: guid_header["attributes"],
+    })
 }
 
 /// Stores info about a UEFI capsule header
@@ -0,0 +1,72 @@
+mod common;
+
+use binwalk::extractors::ar::extract_ar_archive;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "ar";
+    const INPUT_FILE_NAME: &str = "ar_gnu.bin";
+    common::integration_test(SIGNATURE_TYPE, INPUT_FILE_NAME);
+}
+
+#[test]
+fn bsd_integration_test() {
+    const SIGNATURE_TYPE: &str = "ar";
+    const INPUT_FILE_NAME: &str = "ar_bsd.bin";
+    common::integration_test(SIGNATURE_TYPE, INPUT_FILE_NAME);
+}
+
+#[test]
+fn long_member_names() {
+    // (input file, expected member files and their contents)
+    let test_cases = vec![
+        (
+            "ar_gnu.bin",
+            vec![
+                ("short.o", b"hello world\n".to_vec()),
+                (
+                    "a_very_long_object_file_name.o",
+                    b"long member one\n".repeat(8),
+                ),
+                ("another_long_member_name.txt", b"long member two".to_vec()),
+            ],
+        ),
+        (
+            "ar_bsd.bin",
+            vec![
+                (
+                    "a_much_longer_bsd_member_name.o",
+                    b"bsd member data\n".to_vec(),
+                ),
+                ("short.o", b"short!".to_vec()),
+            ],
+        ),
+    ];
+
+    for (file_name, expected_members) in test_cases {
+        let file_data = common::read_input_file(file_name);
+
+        let output_directory = common::output_directory("ar", file_name);
+
+        let result =
+            extract_ar_archive(&file_data, 0, Some(&output_directory.display().to_string()));
+
+        assert!(result.success);
+        assert_eq!(result.size, Some(file_data.len()));
+
+        // Symbol and name tables must not be extracted
+        assert_eq!(
+            std::fs::read_dir(&output_directory).unwrap().count(),
+            expected_members.len()
+        );
+
+        for (member_name, member_data) in expected_members {
+            assert_eq!(
+                std::fs::read(output_directory.join(member_name)).unwrap(),
+                member_data
+            );
+        }
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
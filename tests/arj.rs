@@ -1,4 +1,5 @@
 use crate::common::assert_results_ok;
+use binwalk::extractors::arj::extract_arj_archive;
 
 mod common;
 
@@ -7,14 +8,54 @@ fn integration_test_valid_arj() {
     const SIGNATURE_TYPE: &str = "arj";
     const INPUT_FILE_NAME: &str = "arj.bin";
 
-    let expected_signature_offsets: Vec<usize> = vec![0xD, 0x46];
+    // The local file header at 0x46 is part of the archive, and is not reported separately
+    let expected_signature_offsets: Vec<usize> = vec![0xD];
     let expected_extraction_offsets: Vec<usize> = vec![0xD];
 
     let results = common::run_binwalk(SIGNATURE_TYPE, INPUT_FILE_NAME);
 
+    assert_eq!(results.file_map[0].size, 0xB7);
+
     assert_results_ok(
         results,
         expected_signature_offsets,
         expected_extraction_offsets,
     )
 }
+
+#[test]
+fn extract_members() {
+    const ARCHIVE_SIZE: usize = 1215;
+
+    let file_data = common::read_input_file("arj_members.bin");
+
+    let output_directory = common::output_directory("arj", "extract_members");
+
+    let result = extract_arj_archive(&file_data, 0, Some(&output_directory.display().to_string()));
+
+    assert!(result.success);
+    assert_eq!(result.size, Some(ARCHIVE_SIZE));
+
+    let read = |path: &str| std::fs::read(output_directory.join(path)).unwrap();
+
+    // Stored, method 1 and method 4 compressed members
+    assert_eq!(read("stored.txt"), b"stored file\n");
+
+    let text: Vec<u8> = (0..200)
+        .flat_map(|i| format!("line {i:04} of an arj compressed text file\n").into_bytes())
+        .collect();
+    assert_eq!(read("method1.txt"), text);
+
+    let binary: Vec<u8> = (0..3)
+        .flat_map(|_| (0..1000).map(|i| ((i * 7) % 256) as u8))
+        .collect();
+    assert_eq!(read("dir/method4.bin"), binary);
+
+    // Parent directories are created for members that have no directory entry
+    assert_eq!(read("nodir/nested.txt"), b"nested\n");
+
+    // Members with a bad CRC are skipped
+    assert!(!output_directory.join("corrupt.txt").exists());
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
@@ -6,3 +6,28 @@ fn integration_test() {
     const INPUT_FILE_NAME: &str = "bzip2.bin";
     common::integration_test(SIGNATURE_TYPE, INPUT_FILE_NAME);
 }
+
+#[test]
+fn decompress_input() {
+    const COMPRESSED_SIZE: usize = 8125;
+
+    let file_data = std::fs::read("tests/inputs/bzip2.bin").expect("Failed to read input file");
+
+    // The bzip2 stream is followed by other data, so the file is not a single compressed stream
+    assert!(binwalk::common::decompress_input(&file_data).is_none());
+
+    let mut stream_data = file_data[..COMPRESSED_SIZE].to_vec();
+    let decompressed_data =
+        binwalk::common::decompress_input(&stream_data).expect("Failed to decompress input data");
+
+    // Zero padding after the compressed stream is ignored
+    stream_data.extend_from_slice(&[0; 64]);
+    assert_eq!(
+        binwalk::common::decompress_input(&stream_data),
+        Some(decompressed_data)
+    );
+
+    // Truncated streams are not decompressed
+    stream_data.truncate(COMPRESSED_SIZE / 2);
+    assert!(binwalk::common::decompress_input(&stream_data).is_none());
+}
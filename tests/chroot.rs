@@ -0,0 +1,523 @@
+use binwalk::extractors::common::{AbsoluteSymlinkPolicy, Chroot};
+use std::path::MAIN_SEPARATOR_STR;
+
+/// Convert a '/' delimited path into a path using the native path separator
+fn native(path: &str) -> String {
+    path.replace('/', MAIN_SEPARATOR_STR)
+}
+
+/// Returns a Chroot instance, and its chroot directory, rooted in a unique temporary directory
+fn test_chroot(name: &str) -> (Chroot, String) {
+    let chroot_dir = std::env::temp_dir()
+        .join("binwalk_chroot_tests")
+        .join(name)
+        .display()
+        .to_string();
+
+    let _ = std::fs::remove_dir_all(&chroot_dir);
+
+    (Chroot::new(Some(&chroot_dir)), chroot_dir)
+}
+
+#[test]
+fn sanitize_path() {
+    // (input path, preserve root path separator, expected sanitized path)
+    let test_cases: Vec<(&str, bool, &str)> = vec![
+        // Plain paths
+        ("etc/passwd", true, "/etc/passwd"),
+        ("/etc/passwd", true, "/etc/passwd"),
+        ("/etc/passwd", false, "/etc/passwd"),
+        // Directory traversal
+        ("../etc/passwd", true, "/etc/passwd"),
+        ("../../../../etc/passwd", true, "/etc/passwd"),
+        ("/../../etc/passwd", true, "/etc/passwd"),
+        ("etc/../passwd", true, "/passwd"),
+        ("etc/init.d/../../passwd", true, "/passwd"),
+        ("a/b/../../../../c", true, "/c"),
+        ("a/b/c/../../d", true, "/a/d"),
+        ("a/..", true, ""),
+        ("..", true, ""),
+        // Repeated path separators
+        ("etc//passwd", true, "/etc/passwd"),
+        ("//etc///passwd", true, "/etc/passwd"),
+        ("////", true, "/"),
+        // Trailing path separators
+        ("etc/", true, "/etc"),
+        ("/etc/init.d//", true, "/etc/init.d"),
+        // Mixed
+        ("//../etc//../../bin/./busybox/", true, "/bin/./busybox"),
+        (
+            "usr/../../..//lib/../lib64//libc.so",
+            true,
+            "/lib64/libc.so",
+        ),
+        // Windows path separators
+        ("..\\..\\etc\\passwd", true, "/etc/passwd"),
+        ("\\etc\\passwd", true, "/etc/passwd"),
+        ("\\etc\\passwd", false, "/etc/passwd"),
+        ("etc\\..\\..\\..\\passwd", true, "/passwd"),
+        ("etc\\\\init.d\\", true, "/etc/init.d"),
+        ("a/b\\../..\\../c/\\d", true, "/c/d"),
+        // Control characters
+        ("etc/pass\x00wd", true, "/etc/pass_wd"),
+        ("etc\n/passwd\r\n", true, "/etc_/passwd__"),
+        ("\x1b[31m/../passwd", true, "/passwd"),
+        ("\x7f\x01/\t", true, "/__/_"),
+        ("...", true, "/..."),
+        ("..foo/bar..", true, "/..foo/bar.."),
+    ];
+
+    let chroot = Chroot::default();
+
+    for (input, preserve_root, expected) in test_cases {
+        assert_eq!(
+            chroot.sanitize_path(&native(input), preserve_root),
+            native(expected),
+            "sanitize_path({input:?}, {preserve_root})"
+        );
+    }
+}
+
+#[test]
+fn safe_path_join() {
+    // (first path, second path, expected path relative to the chroot directory)
+    let test_cases: Vec<(&str, &str, &str)> = vec![
+        ("etc", "passwd", "/etc/passwd"),
+        ("/etc", "passwd", "/etc/passwd"),
+        ("/etc", "/passwd", "/etc/passwd"),
+        ("etc/", "passwd/", "/etc/passwd"),
+        ("/etc", "../../../passwd", "/passwd"),
+        ("../../../etc", "/passwd", "/etc/passwd"),
+        ("etc//init.d", "..//rc.local", "/etc/rc.local"),
+        ("", "", "/"),
+        ("..", "..", "/"),
+        ("\\etc", "..\\..\\passwd", "/passwd"),
+    ];
+
+    let (chroot, chroot_dir) = test_chroot("safe_path_join");
+
+    for (path1, path2, expected) in test_cases {
+        assert_eq!(
+            chroot.safe_path_join(native(path1), native(path2)),
+            format!("{}{}", chroot_dir, native(expected)),
+            "safe_path_join({path1:?}, {path2:?})"
+        );
+    }
+
+    // Paths that already begin with the chroot directory must not be chrooted twice
+    assert_eq!(
+        chroot.safe_path_join(&chroot_dir, native("etc/passwd")),
+        format!("{}{}", chroot_dir, native("/etc/passwd"))
+    );
+
+    let _ = std::fs::remove_dir_all(&chroot_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_multi_hop_escape() {
+    let (mut chroot, chroot_dir) = test_chroot("symlink_multi_hop_escape");
+    let chroot_path = std::path::Path::new(&chroot_dir);
+
+    chroot.verify_symlink_targets = true;
+
+    // A chain of links that stays inside the chroot directory is created as-is
+    assert!(chroot.create_directory("bin").is_ok());
+    assert!(chroot.create_file("bin/busybox", b"busybox").is_ok());
+    assert!(chroot.create_directory("usr/bin").is_ok());
+    assert!(chroot.create_symlink("sh", "/bin/busybox").is_ok());
+    assert!(chroot.create_symlink("usr/bin/sh", "../../sh").is_ok());
+    assert!(
+        std::fs::symlink_metadata(chroot_path.join("usr/bin/sh"))
+            .unwrap()
+            .is_symlink()
+    );
+    assert_eq!(
+        std::fs::read_to_string(chroot_path.join("usr/bin/sh")).unwrap(),
+        "busybox"
+    );
+
+    // x/y -> / is safe on its own, but causes x/y/z to physically reside in the chroot directory's root,
+    // which makes the chrooted relative target path for x/y/z traverse outside of the chroot directory.
+    assert!(chroot.create_directory("x").is_ok());
+    assert!(chroot.create_symlink("x/y", "/").is_ok());
+    assert!(
+        std::fs::symlink_metadata(chroot_path.join("x/y"))
+            .unwrap()
+            .is_symlink()
+    );
+    assert!(chroot.create_symlink("x/y/z", "/etc/passwd").is_ok());
+
+    let placeholder = chroot_path.join("z");
+    assert!(
+        !std::fs::symlink_metadata(&placeholder)
+            .unwrap()
+            .is_symlink()
+    );
+    assert_eq!(
+        std::fs::read_to_string(&placeholder).unwrap(),
+        "symlink -> /etc/passwd"
+    );
+
+    let _ = std::fs::remove_dir_all(&chroot_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_multi_hop_escape_unverified() {
+    let (chroot, chroot_dir) = test_chroot("symlink_multi_hop_escape_unverified");
+    let chroot_path = std::path::Path::new(&chroot_dir);
+
+    // Without target verification, the same chain of symlinks escapes the chroot directory
+    assert!(chroot.create_directory("x").is_ok());
+    assert!(chroot.create_symlink("x/y", "/").is_ok());
+    assert!(chroot.create_symlink("x/y/z", "/etc/passwd").is_ok());
+    assert!(
+        std::fs::symlink_metadata(chroot_path.join("z"))
+            .unwrap()
+            .is_symlink()
+    );
+
+    // Sweeping the chroot directory replaces the escaping symlink, but leaves the safe one alone
+    assert_eq!(chroot.neutralize_escaping_symlinks(), 1);
+    assert!(
+        std::fs::symlink_metadata(chroot_path.join("x/y"))
+            .unwrap()
+            .is_symlink()
+    );
+    assert!(
+        !std::fs::symlink_metadata(chroot_path.join("z"))
+            .unwrap()
+            .is_symlink()
+    );
+
+    let _ = std::fs::remove_dir_all(&chroot_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn neutralize_escaping_symlink_chains() {
+    let (chroot, chroot_dir) = test_chroot("neutralize_escaping_symlink_chains");
+    let chroot_path = std::path::Path::new(&chroot_dir);
+    let chroot_root = std::fs::canonicalize(&chroot_dir).unwrap();
+
+    // Simulate symlinks created by an external extractor: a -> b -> c -> ../../../../../../etc, plus loops
+    std::os::unix::fs::symlink("b", chroot_path.join("a")).unwrap();
+    std::os::unix::fs::symlink("c", chroot_path.join("b")).unwrap();
+    std::os::unix::fs::symlink("../../../../../../etc", chroot_path.join("c")).unwrap();
+    std::os::unix::fs::symlink("loop2", chroot_path.join("loop1")).unwrap();
+    std::os::unix::fs::symlink("loop1", chroot_path.join("loop2")).unwrap();
+    std::os::unix::fs::symlink("does/not/exist", chroot_path.join("dangling")).unwrap();
+
+    // Symlinks are checked in file name order: a, b and c all escape when they are checked, and loop1 can't be
+    // resolved; once loop1 has been replaced, loop2 resolves to it
+    assert_eq!(chroot.neutralize_escaping_symlinks(), 4);
+
+    for symlink in ["a", "b", "c", "loop1"] {
+        assert!(
+            !std::fs::symlink_metadata(chroot_path.join(symlink))
+                .unwrap()
+                .is_symlink()
+        );
+    }
+
+    // Dangling links that stay inside the chroot directory are left alone
+    assert!(
+        std::fs::symlink_metadata(chroot_path.join("dangling"))
+            .unwrap()
+            .is_symlink()
+    );
+
+    // No remaining symlinks may resolve outside of the chroot directory
+    for entry in std::fs::read_dir(&chroot_dir).unwrap().flatten() {
+        if let Ok(resolved) = std::fs::canonicalize(entry.path()) {
+            assert!(resolved.starts_with(&chroot_root), "{:?}", entry.path());
+        }
+    }
+
+    let _ = std::fs::remove_dir_all(&chroot_dir);
+}
+
+#[test]
+fn control_characters_in_file_names() {
+    let (chroot, chroot_dir) = test_chroot("control_characters_in_file_names");
+    let chroot_path = std::path::Path::new(&chroot_dir);
+
+    assert!(chroot.create_directory("dir\x00name").is_ok());
+    assert!(
+        chroot
+            .create_file("dir\x00name/evil\nfile\x00.txt", b"data")
+            .is_ok()
+    );
+
+    assert_eq!(
+        std::fs::read_to_string(chroot_path.join("dir_name").join("evil_file_.txt")).unwrap(),
+        "data"
+    );
+
+    // The only file system entries should be the sanitized ones
+    let entries: Vec<String> = walkdir::WalkDir::new(&chroot_dir)
+        .min_depth(1)
+        .into_iter()
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .collect();
+
+    assert_eq!(entries.len(), 2);
+    assert!(entries.contains(&"dir_name".to_string()));
+    assert!(entries.contains(&"evil_file_.txt".to_string()));
+
+    let _ = std::fs::remove_dir_all(&chroot_dir);
+}
+
+#[test]
+fn chroot_errors() {
+    use std::io::ErrorKind;
+
+    let (chroot, chroot_dir) = test_chroot("chroot_errors");
+    let expected_path = std::path::Path::new(&chroot_dir)
+        .join("file.txt")
+        .display()
+        .to_string();
+
+    assert!(chroot.create_file("file.txt", b"data").is_ok());
+
+    // (operation result, expected error kind)
+    let test_cases = vec![
+        (
+            chroot.create_file("file.txt", b"data"),
+            ErrorKind::AlreadyExists,
+        ),
+        (
+            chroot.carve_file("file.txt", b"data", 2, 100),
+            ErrorKind::InvalidInput,
+        ),
+        (chroot.create_fifo("file.txt"), ErrorKind::AlreadyExists),
+    ];
+
+    for (result, expected_kind) in test_cases {
+        let error = result.expect_err("operation should have failed");
+        assert_eq!(error.kind, expected_kind);
+        assert_eq!(error.path, expected_path);
+    }
+
+    // Underlying I/O errors are passed through
+    let error = chroot
+        .create_directory("file.txt/subdir")
+        .expect_err("creating a directory inside a regular file should fail");
+    assert_ne!(error.kind, ErrorKind::AlreadyExists);
+    assert!(error.to_string().starts_with(&chroot_dir));
+
+    let _ = std::fs::remove_dir_all(&chroot_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn quarantine() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (mut chroot, chroot_dir) = test_chroot("quarantine");
+    chroot.quarantine = true;
+
+    let chroot_path = std::path::Path::new(&chroot_dir);
+
+    assert!(chroot.create_file("busybox", b"AAAA").is_ok());
+    assert!(chroot.make_executable("busybox").is_ok());
+    assert!(chroot.create_directory("bin").is_ok());
+    assert!(chroot.create_symlink("bin/sh", "/busybox").is_ok());
+
+    // Quarantined files are not made executable
+    let permissions = std::fs::metadata(chroot_path.join("busybox"))
+        .unwrap()
+        .permissions();
+    assert_eq!(permissions.mode() & 0o111, 0);
+
+    // Quarantined symlinks are written as placeholder files
+    let symlink_path = chroot_path.join("bin").join("sh");
+    assert!(
+        !std::fs::symlink_metadata(&symlink_path)
+            .unwrap()
+            .file_type()
+            .is_symlink()
+    );
+    assert_eq!(
+        std::fs::read_to_string(&symlink_path).unwrap(),
+        "symlink -> /busybox"
+    );
+
+    let _ = std::fs::remove_dir_all(&chroot_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn absolute_symlink_policies() {
+    // (policy, expected symlink target, or None if the symlink should be replaced with a placeholder file)
+    let test_cases = vec![
+        (
+            AbsoluteSymlinkPolicy::ChrootAbsolute,
+            Some("../../bin/busybox"),
+        ),
+        (
+            AbsoluteSymlinkPolicy::RelativizeOnly,
+            Some("../../usr/sbin/bin/busybox"),
+        ),
+        (AbsoluteSymlinkPolicy::RejectAbsolute, None),
+    ];
+
+    for (policy, expected_target) in test_cases {
+        let (mut chroot, chroot_dir) = test_chroot(&format!("absolute_symlinks_{policy:?}"));
+        chroot.absolute_symlinks = policy;
+
+        let symlink_path = std::path::Path::new(&chroot_dir).join("usr/sbin/ls");
+
+        assert!(chroot.create_directory("usr/sbin").is_ok());
+        assert!(chroot.create_symlink("usr/sbin/ls", "/bin/busybox").is_ok());
+
+        match expected_target {
+            Some(target) => assert_eq!(
+                std::fs::read_link(&symlink_path).unwrap(),
+                std::path::Path::new(target)
+            ),
+            None => assert_eq!(
+                std::fs::read_to_string(&symlink_path).unwrap(),
+                "symlink -> /bin/busybox"
+            ),
+        }
+
+        // Relative targets are not affected by the policy
+        assert!(chroot.create_symlink("usr/sbin/ps", "ls").is_ok());
+        assert_eq!(
+            std::fs::read_link(std::path::Path::new(&chroot_dir).join("usr/sbin/ps")).unwrap(),
+            std::path::Path::new("../../usr/sbin/ls")
+        );
+
+        // Symlinks created outside of the Chroot API are re-created according to the policy
+        std::fs::remove_file(&symlink_path).unwrap();
+        std::os::unix::fs::symlink("/bin/busybox", &symlink_path).unwrap();
+
+        assert_eq!(chroot.apply_absolute_symlink_policy(), 1);
+        assert_eq!(
+            std::fs::symlink_metadata(&symlink_path)
+                .unwrap()
+                .file_type()
+                .is_symlink(),
+            expected_target.is_some()
+        );
+
+        let _ = std::fs::remove_dir_all(&chroot_dir);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_allowlist() {
+    // (symlink path, target path, expect a symlink to be created)
+    let test_cases = vec![
+        ("usr/bin/sh", "/bin/busybox", true),
+        ("usr/bin/libc.so", "../../lib/libc.so.6", true),
+        ("usr/bin/ls", "/bin", true),
+        ("usr/bin/passwd", "/etc/passwd", false),
+        ("usr/bin/shadow", "../../etc/shadow", false),
+        ("usr/bin/tricky", "/binaries/busybox", false),
+        ("usr/bin/escape", "../../../../../../bin/busybox", true),
+    ];
+
+    let (mut chroot, chroot_dir) = test_chroot("symlink_allowlist");
+    chroot.symlink_allowlist = vec!["/bin".to_string(), "lib".to_string()];
+
+    assert!(chroot.create_directory("usr/bin").is_ok());
+
+    for (symlink, target, expect_symlink) in &test_cases {
+        assert!(chroot.create_symlink(*symlink, *target).is_ok());
+
+        let symlink_path = std::path::Path::new(&chroot_dir).join(symlink);
+        assert_eq!(
+            std::fs::symlink_metadata(&symlink_path)
+                .unwrap()
+                .file_type()
+                .is_symlink(),
+            *expect_symlink
+        );
+
+        if !expect_symlink {
+            assert_eq!(
+                std::fs::read_to_string(&symlink_path).unwrap(),
+                format!("symlink -> {target}")
+            );
+        }
+    }
+
+    // Symlinks created outside of the Chroot API are checked against the allowlist as well
+    for (symlink, target, _) in &test_cases {
+        let symlink_path = std::path::Path::new(&chroot_dir).join(symlink);
+        std::fs::remove_file(&symlink_path).unwrap();
+        std::os::unix::fs::symlink(target, &symlink_path).unwrap();
+    }
+
+    assert_eq!(chroot.enforce_symlink_allowlist(), 3);
+
+    let _ = std::fs::remove_dir_all(&chroot_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn create_hardlink() {
+    use std::os::unix::fs::MetadataExt;
+
+    let (chroot, chroot_dir) = test_chroot("create_hardlink");
+    let chroot_path = std::path::Path::new(&chroot_dir);
+
+    assert!(chroot.create_directory("bin").is_ok());
+    assert!(chroot.create_file("bin/busybox", b"AAAA").is_ok());
+    assert!(chroot.create_hardlink("sh", "../../bin/busybox").is_ok());
+
+    let target_metadata = std::fs::metadata(chroot_path.join("bin").join("busybox")).unwrap();
+    let link_metadata = std::fs::metadata(chroot_path.join("sh")).unwrap();
+
+    // Both paths refer to the same inode
+    assert_eq!(target_metadata.ino(), link_metadata.ino());
+    assert_eq!(link_metadata.nlink(), 2);
+
+    // Existing files are not overwritten, and missing targets are reported
+    assert!(chroot.create_hardlink("sh", "bin/busybox").is_err());
+    assert!(chroot.create_hardlink("ls", "bin/ls").is_err());
+
+    let _ = std::fs::remove_dir_all(&chroot_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn create_sparse_file() {
+    use std::os::unix::fs::MetadataExt;
+
+    const ZERO_RUN_SIZE: usize = 4 * 1024 * 1024;
+
+    let (chroot, chroot_dir) = test_chroot("create_sparse_file");
+    let file_path = std::path::Path::new(&chroot_dir).join("disk.img");
+
+    // Leading, embedded and trailing runs of zeros
+    let file_data: Vec<u8> = [
+        vec![0; ZERO_RUN_SIZE],
+        b"AAAA".to_vec(),
+        vec![0; ZERO_RUN_SIZE],
+        b"BBBB".to_vec(),
+        vec![0; ZERO_RUN_SIZE],
+    ]
+    .concat();
+
+    assert!(chroot.create_sparse_file("disk.img", &file_data).is_ok());
+
+    let metadata = std::fs::metadata(&file_path).unwrap();
+
+    // The logical file size and contents are unaffected
+    assert_eq!(metadata.len(), file_data.len() as u64);
+    assert_eq!(std::fs::read(&file_path).unwrap(), file_data);
+
+    // Physical allocation never exceeds the logical size; on file systems that support sparse files, it is much smaller
+    assert!(metadata.blocks() * 512 <= metadata.len() + 4096);
+
+    // Existing files are not overwritten
+    assert!(chroot.create_sparse_file("disk.img", b"AAAA").is_err());
+
+    let _ = std::fs::remove_dir_all(&chroot_dir);
+}
@@ -1,3 +1,4 @@
+use binwalk::extractors::common::ExtractorType;
 use binwalk::{AnalysisResults, Binwalk};
 
 /// Convenience function for running an integration test against the specified file, with the provided signature filter.
@@ -37,14 +38,80 @@ pub fn assert_results_ok(
     }
 }
 
+/// Convenience function for testing that the specified signature, located at offset 0 in the specified file, reports
+/// a size equal to the number of compressed bytes consumed, rather than the size of the decompressed data.
+/// The compressed data must be immediately followed by a second, distinct, signature, which must still be found.
+#[allow(dead_code)]
+pub fn consumed_size_test(
+    signature_filter: &str,
+    trailing_signature_filter: &str,
+    file_name: &str,
+    compressed_size: usize,
+) {
+    let (binwalker, results) =
+        run_binwalk_with_filters(&[signature_filter, trailing_signature_filter], file_name);
+
+    // Both signatures must be found and extracted, and the compressed data must end where the next signature begins
+    assert_eq!(results.file_map.len(), 2);
+    assert_eq!(results.file_map[0].name, signature_filter);
+    assert_eq!(results.file_map[0].offset, 0);
+    assert_eq!(results.file_map[0].size, compressed_size);
+    assert_eq!(results.file_map[1].name, trailing_signature_filter);
+    assert_eq!(results.file_map[1].offset, compressed_size);
+
+    for signature_result in &results.file_map {
+        assert!(results.extractions[&signature_result.id].success);
+    }
+
+    // Internal extractors must report the number of bytes consumed, not the size of the decompressed data
+    if let Some(Some(extractor)) = binwalker.extractor_lookup_table.get(signature_filter)
+        && let ExtractorType::Internal(func) = extractor.utility
+    {
+        let file_data = read_input_file(file_name);
+        let dry_run = func(&file_data, 0, None);
+
+        assert!(dry_run.success);
+        assert_eq!(dry_run.size, Some(compressed_size));
+    }
+}
+
 /// Run Binwalk, with extraction, against the specified file, with the provided signature filter
 pub fn run_binwalk(signature_filter: &str, file_name: &str) -> AnalysisResults {
-    // Build the path to the input file
-    let file_path = std::path::Path::new("tests")
+    let (_, results) = run_binwalk_with_filters(&[signature_filter], file_name);
+    results
+}
+
+/// Returns the path to the specified file in the test inputs directory
+pub fn input_file_path(file_name: &str) -> String {
+    std::path::Path::new("tests")
         .join("inputs")
         .join(file_name)
         .display()
-        .to_string();
+        .to_string()
+}
+
+/// Reads the specified file from the test inputs directory
+pub fn read_input_file(file_name: &str) -> Vec<u8> {
+    std::fs::read(input_file_path(file_name)).expect("Failed to read input file")
+}
+
+/// Returns the path to an empty output directory for the specified test, under the system's temporary directory
+#[allow(dead_code)]
+pub fn output_directory(test_group: &str, test_name: &str) -> std::path::PathBuf {
+    let output_directory = std::env::temp_dir()
+        .join(format!("binwalk_{test_group}_tests"))
+        .join(test_name);
+    let _ = std::fs::remove_dir_all(&output_directory);
+    output_directory
+}
+
+/// Run Binwalk, with extraction, against the specified file, with the provided signature filters
+fn run_binwalk_with_filters(
+    signature_filters: &[&str],
+    file_name: &str,
+) -> (Binwalk, AnalysisResults) {
+    // Build the path to the input file
+    let file_path = input_file_path(file_name);
 
     // Build the path to the output directory
     let output_directory = std::path::Path::new("tests")
@@ -59,7 +126,12 @@ pub fn run_binwalk(signature_filter: &str, file_name: &str) -> AnalysisResults {
     let binwalker = Binwalk::configure(
         Some(file_path),
         Some(output_directory.clone()),
-        Some(vec![signature_filter.to_string()]),
+        Some(
+            signature_filters
+                .iter()
+                .map(|filter| filter.to_string())
+                .collect(),
+        ),
         None,
         None,
         false,
@@ -72,5 +144,5 @@ pub fn run_binwalk(signature_filter: &str, file_name: &str) -> AnalysisResults {
     // Clean up the output directory
     let _ = std::fs::remove_dir_all(output_directory);
 
-    results
+    (binwalker, results)
 }
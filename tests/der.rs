@@ -0,0 +1,65 @@
+mod common;
+
+use binwalk::extractors::der::der_certificate_carver;
+
+#[test]
+fn der_certificate() {
+    const CERTIFICATE_OFFSET: usize = 64;
+    const CERTIFICATE_SIZE: usize = 841;
+
+    let results = common::run_binwalk("der_certificate", "der.bin");
+
+    assert_eq!(results.file_map.len(), 1);
+    assert_eq!(results.file_map[0].offset, CERTIFICATE_OFFSET);
+    assert_eq!(results.file_map[0].size, CERTIFICATE_SIZE);
+    assert!(results.file_map[0].description.contains("CN=binwalk.test"));
+    assert!(results.extractions[&results.file_map[0].id].success);
+}
+
+#[test]
+fn der_keys() {
+    // (offset, size, description); without the certificate signature, the certificate's public key is also found
+    let expected_keys = vec![
+        (250, 294, "DER key, RSA public"),
+        (937, 1192, "DER key, RSA private"),
+        (2161, 294, "DER key, RSA public"),
+    ];
+
+    let results = common::run_binwalk("der_key", "der.bin");
+
+    assert_eq!(results.file_map.len(), expected_keys.len());
+
+    for (signature_result, (offset, size, description)) in
+        results.file_map.iter().zip(expected_keys)
+    {
+        assert_eq!(signature_result.offset, offset);
+        assert_eq!(signature_result.size, size);
+        assert!(signature_result.description.starts_with(description));
+        assert!(results.extractions[&signature_result.id].success);
+    }
+}
+
+#[test]
+fn carve_certificate() {
+    const CERTIFICATE_OFFSET: usize = 64;
+    const CERTIFICATE_SIZE: usize = 841;
+
+    let file_data = common::read_input_file("der.bin");
+
+    let output_directory = common::output_directory("der", "carve_certificate");
+
+    let result = der_certificate_carver(
+        &file_data,
+        CERTIFICATE_OFFSET,
+        Some(&output_directory.display().to_string()),
+    );
+
+    assert!(result.success);
+    assert_eq!(result.size, Some(CERTIFICATE_SIZE));
+    assert_eq!(
+        std::fs::read(output_directory.join("certificate.der")).unwrap(),
+        file_data[CERTIFICATE_OFFSET..CERTIFICATE_OFFSET + CERTIFICATE_SIZE]
+    );
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
@@ -0,0 +1,124 @@
+use crate::common::assert_results_ok;
+use binwalk::extractors::dmg::extract_dmg;
+
+mod common;
+
+/// Offset of the DMG in the test files
+const DMG_OFFSET: usize = 32;
+const DMG_SECTOR_SIZE: usize = 512;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "dmg";
+    const INPUT_FILE_NAME: &str = "dmg.bin";
+    const DMG_SIZE: usize = 5229;
+
+    let results = common::run_binwalk(SIGNATURE_TYPE, INPUT_FILE_NAME);
+
+    assert_eq!(results.file_map[0].size, DMG_SIZE);
+
+    assert_results_ok(results, vec![DMG_OFFSET], vec![DMG_OFFSET]);
+}
+
+#[test]
+fn resource_fork_integration_test() {
+    const SIGNATURE_TYPE: &str = "dmg";
+    const INPUT_FILE_NAME: &str = "dmg_rsrc.bin";
+    const DMG_SIZE: usize = 4366;
+
+    let results = common::run_binwalk(SIGNATURE_TYPE, INPUT_FILE_NAME);
+
+    assert_eq!(results.file_map[0].size, DMG_SIZE);
+
+    assert_results_ok(results, vec![DMG_OFFSET], vec![DMG_OFFSET]);
+}
+
+#[test]
+fn extract_disk_image() {
+    for input_file_name in ["dmg.bin", "dmg_rsrc.bin"] {
+        let file_data = common::read_input_file(input_file_name);
+
+        let output_directory = common::output_directory("dmg", input_file_name);
+
+        let result = extract_dmg(
+            &file_data,
+            DMG_OFFSET,
+            Some(&output_directory.display().to_string()),
+        );
+
+        assert!(result.success);
+
+        let image = std::fs::read(output_directory.join("disk.img")).unwrap();
+        let sectors = |start: usize, count: usize| {
+            &image[start * DMG_SECTOR_SIZE..(start + count) * DMG_SECTOR_SIZE]
+        };
+
+        let text: Vec<u8> = (0..400)
+            .flat_map(|i| format!("DMG test sector data line {i:05}\n").into_bytes())
+            .collect();
+        let raw: Vec<u8> = (0..2 * DMG_SECTOR_SIZE)
+            .map(|i| ((i * 31 + 7) % 251) as u8)
+            .collect();
+        let adc: Vec<u8> = b"ADC pattern 0123456789abcdefghijklmnopqrstuvwxyz!"
+            .repeat(30)
+            .into_iter()
+            .take(2 * DMG_SECTOR_SIZE)
+            .collect();
+
+        assert_eq!(image.len(), 26 * DMG_SECTOR_SIZE);
+
+        // zlib, raw, zero fill, bzip2 and ADC chunks in the first partition
+        assert_eq!(sectors(0, 8), &text[..8 * DMG_SECTOR_SIZE]);
+        assert_eq!(sectors(8, 2), raw);
+        assert!(sectors(10, 4).iter().all(|byte| *byte == 0));
+        assert_eq!(sectors(14, 2), &text[100..100 + 2 * DMG_SECTOR_SIZE]);
+        assert_eq!(sectors(16, 2), adc);
+
+        // The gap before the second partition is filled with zeros
+        assert!(sectors(18, 6).iter().all(|byte| *byte == 0));
+
+        // LZMA chunk in the second partition
+        assert_eq!(sectors(24, 2), &text[..2 * DMG_SECTOR_SIZE]);
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
+
+#[test]
+fn corrupt_block_tables() {
+    // Offsets of the two block tables in dmg_rsrc.bin, and of fields within a block table
+    const FIRST_BLOCK_TABLE: usize = 0xBC4;
+    const SECOND_BLOCK_TABLE: usize = 0xDAC;
+    const BLOCK_TABLE_SECTOR_NUMBER: usize = 8;
+    const FIRST_CHUNK_SECTOR_COUNT: usize = 220;
+
+    let file_data = common::read_input_file("dmg_rsrc.bin");
+
+    let output_directory = common::output_directory("dmg", "corrupt");
+
+    for (field_offset, value) in [
+        // Sector number which overflows when converted to a byte offset
+        (FIRST_BLOCK_TABLE + BLOCK_TABLE_SECTOR_NUMBER, u64::MAX / 2),
+        // Chunk which would decompress to a terabyte
+        (FIRST_BLOCK_TABLE + FIRST_CHUNK_SECTOR_COUNT, 1 << 31),
+        // Terabyte gap between the two partitions
+        (SECOND_BLOCK_TABLE + BLOCK_TABLE_SECTOR_NUMBER, 1 << 31),
+    ] {
+        let mut corrupt_data = file_data.clone();
+        corrupt_data[field_offset..field_offset + 8].copy_from_slice(&value.to_be_bytes());
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+
+        assert!(!extract_dmg(&corrupt_data, DMG_OFFSET, None).success);
+        assert!(
+            !extract_dmg(
+                &corrupt_data,
+                DMG_OFFSET,
+                Some(&output_directory.display().to_string()),
+            )
+            .success
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
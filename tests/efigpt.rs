@@ -0,0 +1,111 @@
+use binwalk::extractors::efigpt::extract_efigpt_partitions;
+use binwalk::extractors::mbr::extract_mbr_partitions;
+use binwalk::structures::efigpt::parse_efigpt_header;
+use std::path::{Path, PathBuf};
+
+mod common;
+
+const GPT_BLOCK_SIZE: usize = 512;
+
+/// Names of the partition files carved from the test image, and the LBAs at which the partitions start
+const PARTITIONS: [(&str, usize); 2] = [
+    ("EFI_System_EFI_partition.0", 34),
+    ("Linux_filesystem_rootfs_partition.1", 42),
+];
+
+fn read_input() -> Vec<u8> {
+    common::read_input_file("efigpt.bin")
+}
+
+fn output_directory(test_name: &str) -> PathBuf {
+    common::output_directory("efigpt", test_name)
+}
+
+/// Asserts that each partition was carved from the disk image to the output directory
+fn assert_partitions_carved(file_data: &[u8], output_directory: &Path) {
+    for (file_name, start_lba) in PARTITIONS {
+        let partition = std::fs::read(output_directory.join(file_name)).unwrap();
+        let start = start_lba * GPT_BLOCK_SIZE;
+        assert_eq!(partition, file_data[start..start + partition.len()]);
+    }
+}
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "efigpt";
+    const INPUT_FILE_NAME: &str = "efigpt.bin";
+    common::integration_test(SIGNATURE_TYPE, INPUT_FILE_NAME);
+}
+
+#[test]
+fn extract_partitions() {
+    let file_data = read_input();
+    let output_directory = output_directory("extract_partitions");
+
+    let result =
+        extract_efigpt_partitions(&file_data, 0, Some(&output_directory.display().to_string()));
+
+    assert!(result.success);
+    assert_eq!(result.size, Some(file_data.len()));
+    assert_partitions_carved(&file_data, &output_directory);
+
+    let gpt_header = parse_efigpt_header(&file_data).unwrap();
+    assert_eq!(
+        gpt_header.partitions[0].type_guid,
+        "C12A7328-F81F-11D2-BA4B-00A0C93EC93B"
+    );
+    assert_eq!(gpt_header.partitions[1].size, 16 * GPT_BLOCK_SIZE);
+    assert!(gpt_header.primary_crc_valid);
+    assert_eq!(gpt_header.backup_crc_valid, Some(true));
+}
+
+#[test]
+fn corrupt_primary_gpt() {
+    // Offset of the first partition entry's starting LBA in the primary GPT
+    const PRIMARY_ENTRY_START_LBA: usize = 2 * GPT_BLOCK_SIZE + 32;
+
+    let mut file_data = read_input();
+    file_data[PRIMARY_ENTRY_START_LBA] ^= 0xFF;
+
+    let gpt_header = parse_efigpt_header(&file_data).unwrap();
+    assert!(!gpt_header.primary_crc_valid);
+    assert!(gpt_header.used_backup);
+
+    // The partitions are read from the backup GPT
+    let output_directory = output_directory("corrupt_primary_gpt");
+    let result =
+        extract_efigpt_partitions(&file_data, 0, Some(&output_directory.display().to_string()));
+
+    assert!(result.success);
+    assert_partitions_carved(&file_data, &output_directory);
+}
+
+#[test]
+fn protective_mbr() {
+    let file_data = read_input();
+    let output_directory = output_directory("protective_mbr");
+
+    // The MBR extractor extracts the GPT partitions rather than the protective partition
+    let result =
+        extract_mbr_partitions(&file_data, 0, Some(&output_directory.display().to_string()));
+
+    assert!(result.success);
+    assert_partitions_carved(&file_data, &output_directory);
+}
+
+#[test]
+fn oversized_partition() {
+    use binwalk::signatures::efigpt::efigpt_parser;
+
+    // Offset of the first partition entry's ending LBA in the primary GPT
+    const PRIMARY_ENTRY_END_LBA: usize = 2 * GPT_BLOCK_SIZE + 40;
+
+    // A partition that ends at the last possible LBA must not overflow the partition size calculation, or panic
+    let mut file_data = read_input();
+    file_data[PRIMARY_ENTRY_END_LBA..PRIMARY_ENTRY_END_LBA + 8]
+        .copy_from_slice(&u64::MAX.to_le_bytes());
+
+    let _ = parse_efigpt_header(&file_data);
+    let _ = efigpt_parser(&file_data, 0);
+    let _ = extract_efigpt_partitions(&file_data, 0, None);
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use binwalk::extractors::elf::extract_elf_sections;
+
+#[test]
+fn elf_size() {
+    // (input file, size of the ELF image, excluding trailing data)
+    let test_cases = vec![("elf.bin", 2344), ("elf_be32.bin", 244)];
+
+    for (file_name, elf_size) in test_cases {
+        let results = common::run_binwalk("elf", file_name);
+
+        assert_eq!(results.file_map.len(), 1);
+        assert_eq!(results.file_map[0].offset, 0);
+        assert_eq!(results.file_map[0].size, elf_size);
+
+        // ELF files are not extracted by default
+        assert!(results.extractions.is_empty());
+    }
+}
+
+#[test]
+fn carve_sections() {
+    // Size of .debug_info after decompression
+    const DEBUG_INFO_SIZE: usize = 162;
+
+    let test_cases = vec![
+        ("elf.bin", "rodata", b"binwalk elf test rodata\0".to_vec()),
+        ("elf_be32.bin", "rodata", b"big endian rodata\0".to_vec()),
+    ];
+
+    for (file_name, section_file, section_data) in test_cases {
+        let file_data = common::read_input_file(file_name);
+
+        let output_directory = common::output_directory("elf", file_name);
+
+        let result =
+            extract_elf_sections(&file_data, 0, Some(&output_directory.display().to_string()));
+
+        assert!(result.success);
+        assert_eq!(
+            std::fs::read(output_directory.join(section_file)).unwrap(),
+            section_data
+        );
+
+        // The compressed debug info section must be decompressed
+        if file_name == "elf.bin" {
+            let debug_info = std::fs::read(output_directory.join("debug_info")).unwrap();
+            assert_eq!(debug_info.len(), DEBUG_INFO_SIZE);
+        }
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::common::assert_results_ok;
+use binwalk::extractors::encoded::{extract_base64, extract_hex};
+
+mod common;
+
+fn read_input(file_name: &str) -> Vec<u8> {
+    common::read_input_file(file_name)
+}
+
+#[test]
+fn base64_integration() {
+    const SIGNATURE_TYPE: &str = "base64";
+    const INPUT_FILE_NAME: &str = "base64.bin";
+    // Offset and size of the line wrapped base64 encoded gzip file; the short run at the end of the file is ignored
+    const ENCODED_OFFSET: usize = 59;
+    const ENCODED_SIZE: usize = 149;
+
+    let results = common::run_binwalk(SIGNATURE_TYPE, INPUT_FILE_NAME);
+
+    assert_eq!(results.file_map[0].size, ENCODED_SIZE);
+    assert_results_ok(results, vec![ENCODED_OFFSET], vec![ENCODED_OFFSET]);
+}
+
+#[test]
+fn hex_integration() {
+    const SIGNATURE_TYPE: &str = "hex";
+    const INPUT_FILE_NAME: &str = "hex.bin";
+    // Offset and size of the hex encoded gzip file; the short run at the end of the file is ignored
+    const ENCODED_OFFSET: usize = 32;
+    const ENCODED_SIZE: usize = 218;
+
+    let results = common::run_binwalk(SIGNATURE_TYPE, INPUT_FILE_NAME);
+
+    assert_eq!(results.file_map[0].size, ENCODED_SIZE);
+    assert_results_ok(results, vec![ENCODED_OFFSET], vec![ENCODED_OFFSET]);
+}
+
+#[test]
+fn decoded_data() {
+    let gzip_data = read_input("gzip.bin");
+
+    let output_directory = common::output_directory("encoded", "decoded_data");
+
+    for (extractor_name, file_name, offset) in
+        [("base64", "base64.bin", 59), ("hex", "hex.bin", 32)]
+    {
+        let file_data = read_input(file_name);
+        let extractor_output = output_directory.join(extractor_name);
+        let extractor_output_path = extractor_output.display().to_string();
+
+        let result = match extractor_name {
+            "base64" => extract_base64(&file_data, offset, Some(&extractor_output_path)),
+            _ => extract_hex(&file_data, offset, Some(&extractor_output_path)),
+        };
+
+        assert!(result.success);
+        assert_eq!(
+            std::fs::read(extractor_output.join("decoded.bin")).unwrap(),
+            gzip_data
+        );
+    }
+}
+
+#[test]
+fn invalid_encoding() {
+    // 96 decoded bytes, followed by incorrect padding
+    let base64_data = format!("{}QQ=", "QUFB".repeat(32));
+    assert!(!extract_base64(base64_data.as_bytes(), 0, None).success);
+
+    // Decodes to fewer than the minimum number of bytes
+    let base64_data = "QUFB".repeat(16);
+    assert!(!extract_base64(base64_data.as_bytes(), 0, None).success);
+
+    // An odd number of hex digits
+    let hex_data = format!("{}f", "1f8b0800".repeat(32));
+    assert!(!extract_hex(hex_data.as_bytes(), 0, None).success);
+}
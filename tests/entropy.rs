@@ -0,0 +1,109 @@
+use binwalk::entropy::{EntropyClass, block_entropy, classify_entropy};
+
+#[test]
+fn compressed_entropy_classification() {
+    const BLOCK_SIZE: usize = 32768;
+
+    let file_data = std::fs::read("tests/inputs/zip.bin").expect("Failed to read test input");
+    let blocks = classify_entropy(&file_data, BLOCK_SIZE, None).unwrap();
+
+    let classifications: Vec<_> = blocks
+        .iter()
+        .filter_map(|block| block.classification.as_ref())
+        .collect();
+
+    assert!(!classifications.is_empty());
+
+    for classification in classifications {
+        assert_eq!(classification.class, EntropyClass::Compressed);
+        assert!(classification.confidence > 0.5);
+    }
+}
+
+#[test]
+fn parallel_entropy() {
+    use entropy::shannon_entropy;
+
+    const BLOCK_SIZE: usize = 4096;
+    const DATA_SIZE: usize = 8 * 1024 * 1024;
+
+    // Large enough to be processed in parallel; alternates between low and high entropy regions
+    let mut state: u64 = 0x2545F4914F6CDD1D;
+    let data: Vec<u8> = (0..DATA_SIZE)
+        .map(|i| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            if (i / 65536) % 2 == 0 {
+                (state >> 32) as u8
+            } else {
+                (i % 7) as u8
+            }
+        })
+        .collect();
+
+    let blocks = classify_entropy(&data, BLOCK_SIZE, None).unwrap();
+
+    // Blocks are returned in order, and match the single threaded calculation
+    assert_eq!(blocks.len(), DATA_SIZE / BLOCK_SIZE);
+
+    for (i, block) in blocks.iter().enumerate() {
+        assert_eq!(block.start, i * BLOCK_SIZE);
+        assert_eq!(block.end, block.start + BLOCK_SIZE);
+        assert_eq!(
+            block.entropy,
+            shannon_entropy(&data[block.start..block.end])
+        );
+        assert_eq!(
+            block.classification.is_some(),
+            (block.start / 65536) % 2 == 0
+        );
+    }
+
+    // Public block entropy calculations are split across threads the same way
+    assert_eq!(
+        block_entropy(&data, BLOCK_SIZE),
+        blocks
+            .iter()
+            .map(|block| block.entropy as f64)
+            .collect::<Vec<f64>>()
+    );
+}
+
+#[test]
+fn region_classification() {
+    use binwalk::entropy::{RegionClass, byte_frequency, classify_regions};
+
+    const BLOCK_SIZE: usize = 4096;
+
+    let compressed_data = std::fs::read("tests/inputs/zip.bin").expect("Failed to read test input");
+    let text = "binwalk region classification test\n".repeat(BLOCK_SIZE);
+
+    // Block aligned padding, text and compressed data
+    let data: Vec<u8> = [
+        vec![0; BLOCK_SIZE * 2],
+        text.as_bytes()[..BLOCK_SIZE * 2].to_vec(),
+        compressed_data.clone(),
+    ]
+    .concat();
+
+    let regions = classify_regions(&data, BLOCK_SIZE).unwrap();
+    let classes: Vec<RegionClass> = regions.iter().map(|region| region.class).collect();
+
+    assert_eq!(&classes[..2], &[RegionClass::Padding, RegionClass::Text]);
+    assert!(classes.contains(&RegionClass::Compressed));
+
+    // Regions are contiguous
+    assert_eq!(regions[0].start, 0);
+    assert_eq!(regions.last().unwrap().end, data.len());
+    assert!(regions.windows(2).all(|pair| pair[0].end == pair[1].start));
+
+    // Compressed data uses nearly all byte values, and is not text
+    let frequency = byte_frequency(&compressed_data);
+    assert!(frequency.distinct > 250);
+    assert!(!frequency.text_like);
+    assert_eq!(
+        frequency.histogram.iter().sum::<u64>(),
+        compressed_data.len() as u64
+    );
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use binwalk::extractors::exfat::extract_exfat_filesystem;
+use std::path::PathBuf;
+
+/// Size of the test volume, and of its clusters
+const VOLUME_SIZE: usize = 256 * 512;
+const CLUSTER_SIZE: usize = 1024;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "exfat";
+    const INPUT_FILE_NAME: &str = "exfat.bin";
+    common::integration_test(SIGNATURE_TYPE, INPUT_FILE_NAME);
+}
+
+#[test]
+fn consumed_size() {
+    // The reported size is the volume length from the boot sector, so that trailing data is found
+    common::consumed_size_test("exfat", "gzip", "exfat_consumed_size.bin", VOLUME_SIZE);
+}
+
+/// Reads the test file and returns its data, along with an empty output directory
+fn test_file(test_name: &str) -> (Vec<u8>, PathBuf) {
+    let file_data = common::read_input_file("exfat.bin");
+
+    let output_directory = common::output_directory("exfat", test_name);
+
+    (file_data, output_directory)
+}
+
+#[test]
+fn extract_file_tree() {
+    let (file_data, output_directory) = test_file("file_tree");
+
+    let result =
+        extract_exfat_filesystem(&file_data, 0, Some(&output_directory.display().to_string()));
+
+    assert!(result.success);
+    assert_eq!(result.size, Some(VOLUME_SIZE));
+
+    let read = |path: &str| std::fs::read(output_directory.join(path)).unwrap();
+
+    assert_eq!(read("hello.txt"), b"hello\n");
+    assert_eq!(read("empty"), b"");
+    assert_eq!(read("Sub Directory/nested.txt"), b"nested file\n");
+
+    // The file name spans several file name entries
+    let long_file: Vec<u8> = (0..40)
+        .flat_map(|i| format!("line {i:04} of a long file\n").into_bytes())
+        .take(CLUSTER_SIZE)
+        .collect();
+    assert_eq!(
+        read("A long file name with more than fifteen characters.txt"),
+        long_file
+    );
+
+    // big.bin is stored in contiguous clusters, which are not recorded in the FAT
+    let big_file: Vec<u8> = (0..(3 * CLUSTER_SIZE) + 100)
+        .map(|i| (i % 256) as u8)
+        .collect();
+    assert_eq!(read("big.bin"), big_file);
+
+    // fragmented.bin has a cluster chain which is neither contiguous nor in order
+    let mut fragmented_file = [vec![b'A'; CLUSTER_SIZE], vec![b'B'; CLUSTER_SIZE]].concat();
+    fragmented_file.extend_from_slice(&[b'C'; 10]);
+    assert_eq!(read("fragmented.bin"), fragmented_file);
+
+    // Data beyond the valid data length reads as zeros
+    let mut valid_file = vec![0; CLUSTER_SIZE + 30];
+    valid_file[..10].copy_from_slice(b"valid data");
+    assert_eq!(read("valid.bin"), valid_file);
+
+    // The root directory spans two clusters
+    assert_eq!(read("file2.dat"), b"file 2\n");
+
+    // Deleted entry sets are not extracted
+    assert!(!output_directory.join("deleted.txt").exists());
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn entry_set_checksum_mismatch() {
+    // Offset of the first name character of hello.txt: the file entry set follows three entries in the root directory,
+    // which is cluster 4; the cluster heap starts at sector 32
+    const HELLO_NAME_OFFSET: usize = (32 * 512) + (2 * CLUSTER_SIZE) + (5 * 32) + 2;
+
+    let (mut file_data, output_directory) = test_file("checksum_mismatch");
+    assert_eq!(file_data[HELLO_NAME_OFFSET], b'h');
+    file_data[HELLO_NAME_OFFSET] = b'j';
+
+    let result =
+        extract_exfat_filesystem(&file_data, 0, Some(&output_directory.display().to_string()));
+
+    // Entry sets that fail checksum validation are skipped, but everything else is still extracted
+    assert!(result.success);
+    assert!(!output_directory.join("hello.txt").exists());
+    assert!(!output_directory.join("jello.txt").exists());
+    assert!(output_directory.join("empty").exists());
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use binwalk::extractors::ext::extract_ext_filesystem;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "ext";
+
+    for input_file_name in ["ext2.bin", "ext4.bin"] {
+        common::integration_test(SIGNATURE_TYPE, input_file_name);
+    }
+}
+
+#[test]
+fn extract_file_tree() {
+    // Size of the test file systems
+    const IMAGE_SIZE: usize = 256 * 1024;
+
+    // Modification time and ownership of big.bin
+    const BIG_FILE_MTIME: u64 = 1577934245;
+    const BIG_FILE_UID: u32 = 1000;
+    const BIG_FILE_GID: u32 = 100;
+
+    // ext2 maps file data with block maps, ext4 with extent trees
+    for file_name in ["ext2.bin", "ext4.bin"] {
+        let file_data = common::read_input_file(file_name);
+
+        let output_directory = common::output_directory("ext", file_name);
+
+        let result =
+            extract_ext_filesystem(&file_data, 0, Some(&output_directory.display().to_string()));
+
+        assert!(result.success);
+        assert_eq!(result.size, Some(IMAGE_SIZE));
+
+        let read = |path: &str| std::fs::read(output_directory.join(path)).unwrap();
+
+        assert_eq!(read("hello.txt"), b"hello\n");
+        assert_eq!(read("dir/hardlink.txt"), b"hello\n");
+        assert_eq!(read("dir/sub/nested.txt"), b"nested file\n");
+
+        // big.bin spans the direct and single indirect blocks of a block map
+        let big_file: Vec<u8> = (0..51200).map(|i| (i % 256) as u8).collect();
+        assert_eq!(read("big.bin"), big_file);
+
+        // Unallocated blocks read as zeros
+        let mut sparse_file = vec![0; 100003];
+        sparse_file[100000..].copy_from_slice(b"end");
+        assert_eq!(read("sparse.bin"), sparse_file);
+
+        // fragmented.bin has enough extents to require an extent tree index node
+        let fragmented_file = read("fragmented.bin");
+        assert_eq!(fragmented_file.len(), 14346);
+        for (i, chunk) in fragmented_file.chunks(2048).enumerate() {
+            assert_eq!(&chunk[..10], &[b'A' + i as u8; 10]);
+            assert!(chunk[10..].iter().all(|byte| *byte == 0));
+        }
+
+        // Fast symlinks are stored in the inode, slow symlinks in a data block
+        assert_eq!(read("fast_link"), b"hello\n");
+        assert!(
+            std::fs::read_link(output_directory.join("slow_link"))
+                .unwrap()
+                .ends_with("d/d/d/target")
+        );
+
+        // Device files are extracted as placeholder files
+        assert!(output_directory.join("null").exists());
+        assert!(output_directory.join("blk").exists());
+        assert!(output_directory.join("fifo").exists());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+            let mode = |path: &str| {
+                std::fs::metadata(output_directory.join(path))
+                    .unwrap()
+                    .permissions()
+                    .mode()
+                    & 0o777
+            };
+
+            assert_eq!(mode("hello.txt"), 0o600);
+            assert_eq!(mode("dir"), 0o750);
+
+            let big_file_metadata = std::fs::metadata(output_directory.join("big.bin")).unwrap();
+            assert_eq!(big_file_metadata.mtime() as u64, BIG_FILE_MTIME);
+
+            // Ownership can only be restored when running as root; the output directory is owned by the current user
+            let current_uid = std::fs::metadata(&output_directory).unwrap().uid();
+            if current_uid == 0 {
+                assert_eq!(big_file_metadata.uid(), BIG_FILE_UID);
+                assert_eq!(big_file_metadata.gid(), BIG_FILE_GID);
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
@@ -0,0 +1,2343 @@
+use binwalk::extractors::common::{
+    CarveAdjustment, Chroot, DigestAlgorithm, ExistingOutputPolicy, ExtractionConfig,
+    ExtractionError, ExtractionManifest, ExtractionPredicate, ExtractionResult,
+    ExtractionStatistics, Extractor, ExtractorFilter, ExtractorType, MANIFEST_FILE_NAME,
+    NestedSignaturePolicy, OFFSET_PLACEHOLDER, OUTPUT_DIRECTORY_PLACEHOLDER, OutputDirectoryNaming,
+    ProcessLimiter, SOURCE_FILE_PLACEHOLDER, carve, check_extractor_dependencies, execute,
+};
+use binwalk::extractors::elf::extract_elf_sections;
+use binwalk::extractors::tarball::{tarball_extractor, tarball_xattrs};
+use binwalk::extractors::trx::trx_extractor;
+use binwalk::signatures::common::{CONFIDENCE_HIGH, SignatureResult};
+use binwalk::{AnalysisResults, Binwalk};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Input data for extractor tests; the signature returned by `test_signature(4, 4)` matches the B's
+const TEST_INPUT: &[u8] = b"AAAABBBBCCCC";
+
+/// Configure a Binwalk instance to extract the specified input file to a unique output directory
+fn configure_binwalk(test_name: &str, file_name: &str) -> (Binwalk, String) {
+    let file_path = Path::new("tests")
+        .join("inputs")
+        .join(file_name)
+        .display()
+        .to_string();
+
+    let output_directory = std::env::temp_dir()
+        .join("binwalk_extraction_config_tests")
+        .join(test_name)
+        .display()
+        .to_string();
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+
+    let binwalker = Binwalk::configure(
+        Some(file_path),
+        Some(output_directory.clone()),
+        None,
+        None,
+        None,
+        false,
+    )
+    .expect("Binwalk initialization failed");
+
+    (binwalker, output_directory)
+}
+
+/// Run analysis and extraction with the provided Binwalk instance
+fn run_binwalk(binwalker: &Binwalk) -> AnalysisResults {
+    binwalker.analyze(&binwalker.base_target_file, true)
+}
+
+/// Create an empty, unique directory for the specified test
+fn test_directory(test_name: &str) -> PathBuf {
+    let test_directory = std::env::temp_dir()
+        .join("binwalk_extraction_config_tests")
+        .join(test_name);
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+    std::fs::create_dir_all(&test_directory).unwrap();
+
+    test_directory
+}
+
+/// A signature named "test", matching `size` bytes of data at `offset`
+fn test_signature(offset: usize, size: usize) -> SignatureResult {
+    SignatureResult {
+        name: "test".to_string(),
+        offset,
+        size,
+        ..Default::default()
+    }
+}
+
+/// An external extractor that runs the provided shell script
+fn sh_extractor(script: &str) -> Extractor {
+    Extractor {
+        utility: ExtractorType::External("sh".to_string()),
+        arguments: vec!["-c".to_string(), script.to_string()],
+        ..Default::default()
+    }
+}
+
+/// Run an extractor against file data, as if it had been read from the input.bin file in the test directory
+fn run_extractor(
+    test_directory: &Path,
+    file_data: &[u8],
+    signature: &SignatureResult,
+    extractor: &Extractor,
+    config: &ExtractionConfig,
+) -> ExtractionResult {
+    let file_path = test_directory.join("input.bin").display().to_string();
+    execute(
+        file_data,
+        &file_path,
+        signature,
+        &Some(extractor.clone()),
+        config,
+    )
+}
+
+/// Run a shell script extractor against the test signature in `TEST_INPUT`
+fn run_sh_extractor(
+    test_directory: &Path,
+    script: &str,
+    config: &ExtractionConfig,
+) -> ExtractionResult {
+    run_extractor(
+        test_directory,
+        TEST_INPUT,
+        &test_signature(4, 4),
+        &sh_extractor(script),
+        config,
+    )
+}
+
+#[test]
+fn output_directory_naming() {
+    // (naming scheme, expected output directory name for the gzip signature at offset 0)
+    let test_cases = vec![
+        (OutputDirectoryNaming::Hex, "0"),
+        (OutputDirectoryNaming::Decimal, "0000000000"),
+        (OutputDirectoryNaming::NameAndHex, "gzip_0"),
+    ];
+
+    for (naming, expected_directory_name) in test_cases {
+        let (mut binwalker, output_directory) =
+            configure_binwalk(&format!("naming_{naming:?}"), "gzip.bin");
+        binwalker.extraction_config.output_directory_naming = naming;
+
+        let results = run_binwalk(&binwalker);
+
+        assert_eq!(results.extractions.len(), 1);
+
+        let extraction = results.extractions.values().next().unwrap();
+        let expected_path = Path::new(&output_directory)
+            .join("gzip.bin.extracted")
+            .join(expected_directory_name);
+
+        assert!(extraction.success);
+        assert_eq!(
+            std::fs::canonicalize(&extraction.output_directory).unwrap(),
+            std::fs::canonicalize(&expected_path).unwrap()
+        );
+        assert!(expected_path.join("decompressed.bin").exists());
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
+
+#[test]
+fn existing_output() {
+    for policy in [
+        ExistingOutputPolicy::Overwrite,
+        ExistingOutputPolicy::Skip,
+        ExistingOutputPolicy::Fail,
+    ] {
+        let (mut binwalker, output_directory) =
+            configure_binwalk(&format!("existing_{policy:?}"), "gzip.bin");
+
+        // The first run creates the output directory
+        let results = run_binwalk(&binwalker);
+        let extraction = results.extractions.values().next().unwrap();
+        assert!(extraction.success);
+
+        // Simulate output left behind by the previous run
+        let extraction_directory = extraction.output_directory.clone();
+        let previous_output = Path::new(&extraction_directory).join("previous.txt");
+        std::fs::write(&previous_output, "previous run").unwrap();
+
+        binwalker.extraction_config.existing_output = policy;
+
+        let results = run_binwalk(&binwalker);
+        assert_eq!(results.extractions.len(), 1);
+        let extraction = results.extractions.values().next().unwrap();
+
+        match policy {
+            ExistingOutputPolicy::Overwrite => {
+                assert!(extraction.success);
+                assert!(!extraction.skipped);
+                assert!(!previous_output.exists());
+            }
+            ExistingOutputPolicy::Skip => {
+                assert!(extraction.success);
+                assert!(extraction.skipped);
+                assert!(previous_output.exists());
+            }
+            ExistingOutputPolicy::Fail => {
+                assert!(!extraction.success);
+                assert!(matches!(extraction.error, Some(ExtractionError::Io(_))));
+                assert!(previous_output.exists());
+            }
+        }
+
+        // The existing output is left in place, or replaced with the same extracted data
+        assert!(
+            Path::new(&extraction_directory)
+                .join("decompressed.bin")
+                .exists()
+        );
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn keep_failed() {
+    let test_directory = test_directory("keep_failed");
+
+    // An external extractor that writes some partial output, then exits with an error
+    let script = "echo partial > partial.txt; exit 1";
+
+    // By default, the output of a failed extraction is deleted
+    let result = run_sh_extractor(&test_directory, script, &ExtractionConfig::default());
+    assert!(!result.success);
+    assert_eq!(result.carved_file, None);
+    assert!(!Path::new(&result.output_directory).exists());
+
+    // With keep_failed, both the partial output and the carved file are preserved
+    let config = ExtractionConfig {
+        keep_failed: true,
+        ..Default::default()
+    };
+    let result = run_sh_extractor(&test_directory, script, &config);
+    assert!(!result.success);
+    assert_eq!(
+        std::fs::read_to_string(Path::new(&result.output_directory).join("partial.txt")).unwrap(),
+        "partial\n"
+    );
+    assert_eq!(
+        std::fs::read(result.carved_file.expect("carved file was not kept")).unwrap(),
+        b"BBBB"
+    );
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[test]
+fn carved_sha256() {
+    let (binwalker, output_directory) = configure_binwalk("carved_sha256", "gzip.bin");
+    let file_data = std::fs::read(&binwalker.base_target_file).unwrap();
+
+    let results = run_binwalk(&binwalker);
+
+    assert_eq!(results.file_map.len(), 1);
+    let signature = &results.file_map[0];
+    let extraction = &results.extractions[&signature.id];
+    let expected_sha256 = hex::encode(Sha256::digest(
+        &file_data[signature.offset..signature.offset + signature.size],
+    ));
+
+    assert!(extraction.success);
+    assert_eq!(extraction.carved_sha256, Some(expected_sha256));
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn carved_digest() {
+    for (algorithm, expected_digest) in [
+        (DigestAlgorithm::Md5, "877b0d63398d81502a9e947bf77b4517"),
+        (
+            DigestAlgorithm::Sha1,
+            "4c47f1ed482a402059401728e920048f9368d307",
+        ),
+    ] {
+        let (mut binwalker, output_directory) = configure_binwalk("carved_digest", "gzip.bin");
+        binwalker.extraction_config.digest_algorithm = Some(algorithm);
+
+        let results = run_binwalk(&binwalker);
+
+        assert_eq!(results.file_map.len(), 1);
+        let extraction = &results.extractions[&results.file_map[0].id];
+
+        // The SHA-256 hash is reported regardless of the configured digest algorithm
+        assert!(extraction.success);
+        assert!(extraction.carved_sha256.is_some());
+        assert_eq!(extraction.carved_digest, Some(expected_digest.to_string()));
+        assert_eq!(extraction.carved_digest_algorithm, Some(algorithm));
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
+
+#[test]
+fn dedupe() {
+    let test_directory = test_directory("dedupe");
+
+    // Two identical copies of the same gzip data
+    let gzip_data = std::fs::read(Path::new("tests").join("inputs").join("gzip.bin")).unwrap();
+    let input_file = test_directory.join("duplicates.bin");
+    std::fs::write(&input_file, [gzip_data.clone(), gzip_data.clone()].concat()).unwrap();
+
+    let mut binwalker = Binwalk::configure(
+        Some(input_file.display().to_string()),
+        Some(test_directory.join("output").display().to_string()),
+        None,
+        None,
+        None,
+        false,
+    )
+    .expect("Binwalk initialization failed");
+    binwalker.extraction_config.dedupe = true;
+
+    let results = run_binwalk(&binwalker);
+
+    assert_eq!(results.file_map.len(), 2);
+
+    let first = &results.extractions[&results.file_map[0].id];
+    let second = &results.extractions[&results.file_map[1].id];
+
+    assert!(first.success);
+    assert!(second.success);
+    assert_eq!(first.carved_sha256, second.carved_sha256);
+    assert_eq!(first.duplicate_of, None);
+    assert_eq!(second.duplicate_of, Some(first.output_directory.clone()));
+    assert!(second.do_not_recurse);
+
+    // The duplicate's output directory is a symlink to the first extraction's output directory
+    assert!(
+        std::fs::symlink_metadata(&second.output_directory)
+            .unwrap()
+            .is_symlink()
+    );
+    assert_eq!(
+        std::fs::canonicalize(&second.output_directory).unwrap(),
+        std::fs::canonicalize(&first.output_directory).unwrap()
+    );
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn use_stdin() {
+    let test_directory = test_directory("use_stdin");
+    let signature = test_signature(4, 4);
+
+    // An external extractor that copies its stdin to a file
+    let extractor = Extractor {
+        use_stdin: true,
+        ..sh_extractor("cat > stdin.bin")
+    };
+
+    let result = run_extractor(
+        &test_directory,
+        TEST_INPUT,
+        &signature,
+        &extractor,
+        &ExtractionConfig::default(),
+    );
+    assert!(result.success);
+
+    // The only file in the output directory should be the one written by the extractor
+    let output_files: Vec<_> = std::fs::read_dir(&result.output_directory)
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.file_name())
+        .collect();
+    assert_eq!(output_files, vec!["stdin.bin"]);
+    assert_eq!(
+        std::fs::read(Path::new(&result.output_directory).join("stdin.bin")).unwrap(),
+        b"BBBB"
+    );
+
+    // Data that extends beyond the end of the file is rejected before anything is written to stdin
+    let truncated_signature = SignatureResult {
+        offset: TEST_INPUT.len(),
+        id: "truncated".to_string(),
+        ..signature
+    };
+
+    let result = run_extractor(
+        &test_directory,
+        TEST_INPUT,
+        &truncated_signature,
+        &extractor,
+        &ExtractionConfig::default(),
+    );
+    assert!(!result.success);
+    assert!(matches!(
+        result.error,
+        Some(ExtractionError::InvalidCarveRange { offset, .. }) if offset == TEST_INPUT.len()
+    ));
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn capture_output() {
+    let test_directory = test_directory("capture_output");
+
+    // (extractor shell script, expected stdout, expected stderr)
+    let test_cases = vec![
+        // Output of failed extractors is recorded
+        (
+            "echo to stdout; echo to stderr >&2; exit 1",
+            Some("to stdout\n".to_string()),
+            Some("to stderr\n".to_string()),
+        ),
+        // Only the tail end of large outputs is recorded
+        (
+            "head -c 200000 /dev/zero | tr '\\0' 'A'; echo END; exit 1",
+            Some(format!("{}END\n", "A".repeat(64 * 1024 - 4))),
+            None,
+        ),
+        // Output of successful extractors is not recorded
+        ("echo to stdout; echo data > out.bin", None, None),
+    ];
+
+    let config = ExtractionConfig {
+        capture_output: true,
+        ..Default::default()
+    };
+
+    for (script, expected_stdout, expected_stderr) in test_cases {
+        let result = run_sh_extractor(&test_directory, script, &config);
+
+        assert_eq!(result.extractor_stdout, expected_stdout, "{script}");
+        assert_eq!(result.extractor_stderr, expected_stderr, "{script}");
+    }
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn extractor_environment() {
+    let test_directory = test_directory("extractor_environment");
+    let expected_output_directory = test_directory.join("input.bin.extracted").join("4");
+
+    // (run in output directory, expected working directory of the extractor)
+    let test_cases = vec![
+        (true, expected_output_directory.clone()),
+        (false, std::env::current_dir().unwrap()),
+    ];
+
+    for (run_in_output_dir, expected_cwd) in test_cases {
+        let extractor = Extractor {
+            env: vec![
+                ("TEST_VALUE".to_string(), "hello".to_string()),
+                (
+                    "TEST_OUTPUT".to_string(),
+                    expected_output_directory.display().to_string(),
+                ),
+            ],
+            run_in_output_dir,
+            ..sh_extractor(
+                "echo \"$TEST_VALUE\" > \"$TEST_OUTPUT/env.txt\"; pwd -P > \"$TEST_OUTPUT/cwd.txt\"",
+            )
+        };
+
+        let result = run_extractor(
+            &test_directory,
+            TEST_INPUT,
+            &test_signature(4, 4),
+            &extractor,
+            &ExtractionConfig::default(),
+        );
+        assert!(result.success);
+
+        let output_directory = Path::new(&result.output_directory);
+        assert_eq!(
+            std::fs::read_to_string(output_directory.join("env.txt")).unwrap(),
+            "hello\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(output_directory.join("cwd.txt"))
+                .unwrap()
+                .trim_end(),
+            std::fs::canonicalize(&expected_cwd)
+                .unwrap()
+                .display()
+                .to_string()
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn command_placeholders() {
+    let test_directory = test_directory("command_placeholders");
+
+    // Copy the carved file (%e) to <output directory (%o)>/copy_<offset (%O)>.bin, from outside of the output directory
+    let extractor = Extractor {
+        utility: ExtractorType::External("sh".to_string()),
+        extension: "bin".to_string(),
+        arguments: vec![
+            "-c".to_string(),
+            "cp \"$1\" \"$2/copy_$3.bin\"".to_string(),
+            "sh".to_string(),
+            SOURCE_FILE_PLACEHOLDER.to_string(),
+            OUTPUT_DIRECTORY_PLACEHOLDER.to_string(),
+            OFFSET_PLACEHOLDER.to_string(),
+        ],
+        run_in_output_dir: false,
+        ..Default::default()
+    };
+
+    let result = run_extractor(
+        &test_directory,
+        TEST_INPUT,
+        &test_signature(4, 4),
+        &extractor,
+        &ExtractionConfig::default(),
+    );
+    assert!(result.success);
+    assert_eq!(
+        std::fs::read(Path::new(&result.output_directory).join("copy_4.bin")).unwrap(),
+        b"BBBB"
+    );
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn embedded_command_placeholders() {
+    let test_directory = test_directory("embedded_command_placeholders");
+
+    // Placeholders embedded in arguments, e.g. --out=%o/sub, are replaced in place; unknown sequences are left as-is
+    let extractor = Extractor {
+        utility: ExtractorType::External("sh".to_string()),
+        extension: "bin".to_string(),
+        arguments: vec![
+            "-c".to_string(),
+            "mkdir \"${2#--out=}\" && cp \"${1#--in=}\" \"${2#--out=}/copy.bin\" && echo \"$3\" > \"${2#--out=}/args.txt\""
+                .to_string(),
+            "sh".to_string(),
+            "--in=%e".to_string(),
+            "--out=%o/sub_%O".to_string(),
+            "%O-%O%%x%".to_string(),
+        ],
+        run_in_output_dir: false,
+        ..Default::default()
+    };
+
+    let result = run_extractor(
+        &test_directory,
+        TEST_INPUT,
+        &test_signature(4, 4),
+        &extractor,
+        &ExtractionConfig::default(),
+    );
+    assert!(result.success);
+
+    let sub_directory = Path::new(&result.output_directory).join("sub_4");
+    assert_eq!(
+        std::fs::read(sub_directory.join("copy.bin")).unwrap(),
+        b"BBBB"
+    );
+    assert_eq!(
+        std::fs::read_to_string(sub_directory.join("args.txt")).unwrap(),
+        "4-4%%x%\n"
+    );
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[test]
+fn extracted_components() {
+    let test_directory = test_directory("extracted_components");
+
+    // Build a TRXv1 image with two partitions
+    const HEADER_SIZE: u32 = 28;
+    let kernel = b"kernel partition".to_vec();
+    let rootfs = b"root file system partition".to_vec();
+    let total_size = HEADER_SIZE + (kernel.len() + rootfs.len()) as u32;
+
+    let mut trx_data: Vec<u8> = b"HDR0".to_vec();
+    trx_data.extend_from_slice(&total_size.to_le_bytes());
+    trx_data.extend_from_slice(&0_u32.to_le_bytes());
+    trx_data.extend_from_slice(&0_u16.to_le_bytes());
+    trx_data.extend_from_slice(&1_u16.to_le_bytes());
+    trx_data.extend_from_slice(&HEADER_SIZE.to_le_bytes());
+    trx_data.extend_from_slice(&(HEADER_SIZE + kernel.len() as u32).to_le_bytes());
+    trx_data.extend_from_slice(&0_u32.to_le_bytes());
+    trx_data.extend_from_slice(&kernel);
+    trx_data.extend_from_slice(&rootfs);
+
+    let crc = binwalk::common::crc32(&trx_data[12..]) ^ 0xFFFFFFFF;
+    trx_data[8..12].copy_from_slice(&crc.to_le_bytes());
+
+    let signature = SignatureResult {
+        name: "trx".to_string(),
+        size: trx_data.len(),
+        ..Default::default()
+    };
+
+    let result = run_extractor(
+        &test_directory,
+        &trx_data,
+        &signature,
+        &trx_extractor(),
+        &ExtractionConfig::default(),
+    );
+    assert!(result.success);
+
+    // (component name, component data)
+    let expected_components = vec![("partition_0", &kernel), ("partition_1", &rootfs)];
+
+    assert_eq!(result.components.len(), expected_components.len());
+
+    for (component, (expected_name, expected_data)) in
+        result.components.iter().zip(expected_components)
+    {
+        assert_eq!(component.name, expected_name);
+        assert_eq!(component.size, expected_data.len());
+        assert_eq!(
+            component.sha256,
+            Some(hex::encode(Sha256::digest(expected_data)))
+        );
+        assert!(Path::new(&component.path).starts_with(&result.output_directory));
+        assert_eq!(&std::fs::read(&component.path).unwrap(), expected_data);
+    }
+
+    // Each component is recursed into independently
+    let recursion_targets = result.recursion_targets();
+    assert_eq!(recursion_targets.len(), 2);
+    assert_eq!(recursion_targets[0], result.components[0].path);
+    assert_eq!(recursion_targets[1], result.components[1].path);
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn extraction_validator() {
+    fn has_expected_output(output_directory: &str) -> bool {
+        Path::new(output_directory).join("expected.bin").exists()
+    }
+
+    let test_directory = test_directory("extraction_validator");
+
+    // (extractor output file, expected success)
+    let test_cases = vec![("expected.bin", true), ("garbage.bin", false)];
+
+    for (output_file, expected_success) in test_cases {
+        let extractor = Extractor {
+            validator: Some(has_expected_output),
+            ..sh_extractor(&format!("echo data > {output_file}"))
+        };
+
+        let result = run_extractor(
+            &test_directory,
+            TEST_INPUT,
+            &test_signature(4, 4),
+            &extractor,
+            &ExtractionConfig::default(),
+        );
+
+        assert_eq!(result.success, expected_success, "{output_file}");
+        assert_eq!(
+            result.error,
+            (!expected_success).then_some(ExtractionError::ValidationFailed),
+            "{output_file}"
+        );
+        // Output directories of failed extractions are cleaned up
+        assert_eq!(
+            Path::new(&result.output_directory).exists(),
+            expected_success,
+            "{output_file}"
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn ignored_outputs() {
+    let test_directory = test_directory("ignored_outputs");
+
+    // (extractor shell script, expected success, expected output size)
+    let test_cases = vec![
+        // Only an ignored log file was created, nothing was extracted
+        ("echo 'extraction failed' > extraction.log", false, 0),
+        (
+            "mkdir logs; echo 'extraction failed' > logs/status.txt",
+            false,
+            0,
+        ),
+        // Real data was extracted alongside the log file
+        (
+            "echo 'extraction ok' > extraction.log; echo data > data.bin",
+            true,
+            5,
+        ),
+    ];
+
+    for (script, expected_success, expected_output_size) in test_cases {
+        let extractor = Extractor {
+            ignored_outputs: vec![
+                "*.log".to_string(),
+                "logs".to_string(),
+                "logs/*".to_string(),
+            ],
+            ..sh_extractor(script)
+        };
+
+        let result = run_extractor(
+            &test_directory,
+            TEST_INPUT,
+            &test_signature(4, 4),
+            &extractor,
+            &ExtractionConfig::default(),
+        );
+
+        assert_eq!(result.success, expected_success, "{script}");
+        // Ignored outputs don't count towards the output size
+        assert_eq!(result.output_size, Some(expected_output_size), "{script}");
+    }
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn extraction_errors() {
+    let test_directory = test_directory("extraction_errors");
+
+    // (extractor command, arguments, expected error)
+    let test_cases = vec![
+        ("sh", vec!["-c", "echo data > out.bin"], None),
+        (
+            "sh",
+            vec!["-c", "exit 3"],
+            Some(ExtractionError::NonZeroExit(3)),
+        ),
+        (
+            "this-extractor-does-not-exist",
+            vec![],
+            Some(ExtractionError::Spawn),
+        ),
+        // The error describes what was found in the output directory
+        (
+            "sh",
+            vec!["-c", "touch empty1 empty2"],
+            Some(ExtractionError::NothingExtracted(
+                "0 non-empty files, 2 empty files, 0 directories, 0 symlinks (0 dangling), 0 special files, 0 unreadable entries".to_string(),
+            )),
+        ),
+    ];
+
+    for (command, arguments, expected_error) in test_cases {
+        let extractor = Extractor {
+            utility: ExtractorType::External(command.to_string()),
+            arguments: arguments.iter().map(|arg| arg.to_string()).collect(),
+            ..Default::default()
+        };
+
+        let result = run_extractor(
+            &test_directory,
+            TEST_INPUT,
+            &test_signature(4, 4),
+            &extractor,
+            &ExtractionConfig::default(),
+        );
+
+        assert_eq!(result.success, expected_error.is_none(), "{command}");
+        assert_eq!(result.error, expected_error, "{command}");
+    }
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn invalid_carve_range() {
+    let test_directory = test_directory("invalid_carve_range");
+    let extractor = sh_extractor("echo data > out.bin");
+
+    // (offset, size)
+    let test_cases = vec![(4, 0), (4, 100), (100, 1), (4, usize::MAX)];
+
+    for (offset, size) in test_cases {
+        let result = run_extractor(
+            &test_directory,
+            TEST_INPUT,
+            &test_signature(offset, size),
+            &extractor,
+            &ExtractionConfig::default(),
+        );
+
+        assert!(!result.success);
+        assert_eq!(
+            result.error,
+            Some(ExtractionError::InvalidCarveRange {
+                offset,
+                size,
+                available: TEST_INPUT.len(),
+            })
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn extractor_dependencies() {
+    let external = |command: &str| Extractor {
+        utility: ExtractorType::External(command.to_string()),
+        ..Default::default()
+    };
+
+    let extractors = vec![
+        external("sh"),
+        external("this-extractor-does-not-exist"),
+        external("this-extractor-does-not-exist"),
+        Extractor::default(),
+    ];
+
+    let missing = check_extractor_dependencies(&extractors);
+
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].command, "this-extractor-does-not-exist");
+}
+
+#[cfg(unix)]
+#[test]
+fn extractor_fallback() {
+    let test_directory = test_directory("extractor_fallback");
+
+    let missing_extractor = Extractor {
+        utility: ExtractorType::External("this-extractor-does-not-exist".to_string()),
+        ..Default::default()
+    };
+
+    let working_extractor = sh_extractor("echo data > output.bin");
+
+    // A missing preferred extractor falls back to the default extractor
+    let signature = SignatureResult {
+        preferred_extractor: Some(missing_extractor.clone()),
+        ..test_signature(4, 4)
+    };
+
+    let result = run_extractor(
+        &test_directory,
+        TEST_INPUT,
+        &signature,
+        &working_extractor,
+        &ExtractionConfig::default(),
+    );
+
+    assert!(result.success);
+    assert_eq!(result.extractor, "sh");
+
+    // A missing extractor falls back to its declared alternatives
+    let extractor = Extractor {
+        alternatives: vec![working_extractor],
+        ..missing_extractor
+    };
+
+    let result = run_extractor(
+        &test_directory,
+        TEST_INPUT,
+        &test_signature(4, 4),
+        &extractor,
+        &ExtractionConfig::default(),
+    );
+
+    assert!(result.success);
+    assert_eq!(result.extractor, "sh");
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[test]
+fn carve_only() {
+    let (mut binwalker, output_directory) = configure_binwalk("carve_only", "gzip.bin");
+    binwalker.extraction_config.carve_only = true;
+
+    let results = run_binwalk(&binwalker);
+
+    assert_eq!(results.extractions.len(), 1);
+
+    let extraction = results.extractions.values().next().unwrap();
+    let expected_file = Path::new(&output_directory)
+        .join("gzip.bin.extracted")
+        .join("0.gzip");
+
+    assert!(extraction.success);
+    assert_eq!(extraction.extractor, "carve");
+    assert!(extraction.recursion_targets().is_empty());
+    assert_eq!(
+        std::fs::read(&expected_file).unwrap(),
+        std::fs::read("tests/inputs/gzip.bin").unwrap()
+    );
+
+    // No extractor was run
+    assert!(
+        !Path::new(&output_directory)
+            .join("gzip.bin.extracted")
+            .join("0")
+            .exists()
+    );
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn carve_alignment() {
+    let (mut binwalker, output_directory) = configure_binwalk("carve_alignment", "gzip.bin");
+    binwalker.extraction_config.carve_only = true;
+    binwalker.extraction_config.carve_alignment = Some(64);
+    binwalker.extraction_config.carve_padding = Some(512);
+
+    // The gzip data starts at an unaligned offset
+    let gzip_data = std::fs::read(&binwalker.base_target_file).unwrap();
+    let file_data = [vec![0xFF; 100], gzip_data.clone()].concat();
+
+    let file_path = Path::new(&output_directory)
+        .join("unaligned.bin")
+        .display()
+        .to_string();
+    std::fs::write(&file_path, &file_data).unwrap();
+
+    let results = binwalker.analyze_buf(&file_data, &file_path, true);
+
+    assert_eq!(results.file_map.len(), 1);
+    let extraction = &results.extractions[&results.file_map[0].id];
+
+    // The carved file starts at offset 64 and is padded to 512 bytes, but the reported size is still the gzip size
+    assert!(extraction.success);
+    assert_eq!(extraction.size, Some(gzip_data.len()));
+    assert_eq!(
+        extraction.carve_adjustment,
+        Some(CarveAdjustment {
+            offset: 64,
+            leading_bytes: 36,
+            padding_bytes: 512 - 36 - gzip_data.len(),
+        })
+    );
+
+    let carved_data = std::fs::read(extraction.carved_file.as_ref().unwrap()).unwrap();
+    assert_eq!(carved_data.len(), 512);
+    assert_eq!(carved_data[..36 + gzip_data.len()], file_data[64..]);
+    assert!(carved_data[36 + gzip_data.len()..].iter().all(|b| *b == 0));
+    assert_eq!(extraction.components[0].size, 512);
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+
+    // Adjustments that would overflow are skipped; such signatures can't be carved anyway
+    let test_directory = test_directory("carve_alignment_overflow");
+    let file_path = test_directory.join("input.bin").display().to_string();
+    let result = carve(
+        TEST_INPUT,
+        &file_path,
+        &test_signature(4, usize::MAX - 4),
+        &binwalker.extraction_config,
+    );
+
+    assert!(!result.success);
+    assert_eq!(result.carve_adjustment, None);
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[test]
+fn extraction_predicate() {
+    let test_directory = test_directory("extraction_predicate");
+
+    // Two copies of the same gzip data
+    let gzip_data = std::fs::read(Path::new("tests").join("inputs").join("gzip.bin")).unwrap();
+    let input_file = test_directory.join("gzips.bin");
+    std::fs::write(&input_file, [gzip_data.clone(), gzip_data.clone()].concat()).unwrap();
+
+    let mut binwalker = Binwalk::configure(
+        Some(input_file.display().to_string()),
+        Some(test_directory.join("output").display().to_string()),
+        None,
+        None,
+        None,
+        false,
+    )
+    .expect("Binwalk initialization failed");
+
+    // Only extract data after the first copy
+    let first_size = gzip_data.len();
+    binwalker.extraction_config.extraction_predicate =
+        Some(ExtractionPredicate::new(move |signature| {
+            signature.offset >= first_size
+        }));
+
+    let results = run_binwalk(&binwalker);
+
+    // Both copies are reported, but only the second one is extracted
+    assert_eq!(results.file_map.len(), 2);
+
+    let first = &results.extractions[&results.file_map[0].id];
+    let second = &results.extractions[&results.file_map[1].id];
+
+    assert!(first.skipped);
+    assert!(!first.success);
+    assert!(first.output_directory.is_empty());
+    assert!(!second.skipped);
+    assert!(second.success);
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[test]
+fn no_recurse() {
+    // (signatures not to recurse into, expect recursion targets)
+    let test_cases = vec![(vec![], true), (vec!["gzip".to_string()], false)];
+
+    for (no_recurse, expect_recursion) in test_cases {
+        let (mut binwalker, output_directory) =
+            configure_binwalk(&format!("no_recurse_{expect_recursion}"), "gzip.bin");
+        binwalker.extraction_config.no_recurse = no_recurse;
+
+        let results = run_binwalk(&binwalker);
+        let extraction = results.extractions.values().next().unwrap();
+
+        assert!(extraction.success);
+        assert_eq!(extraction.do_not_recurse, !expect_recursion);
+        assert_eq!(extraction.recursion_targets().is_empty(), !expect_recursion);
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
+
+#[test]
+fn extractor_filter() {
+    let names = |names: &[&str]| -> Vec<String> { names.iter().map(|n| n.to_string()).collect() };
+
+    // (included extractors, excluded extractors, expect extraction)
+    let test_cases = vec![
+        (names(&[]), names(&[]), true),
+        (names(&["gzip"]), names(&[]), true),
+        (names(&["xz"]), names(&[]), false),
+        (names(&[]), names(&["gzip"]), false),
+        (names(&["gzip"]), names(&["gzip"]), false),
+        (names(&["gzip", "xz"]), names(&["xz"]), true),
+    ];
+
+    for (i, (include, exclude, expect_extraction)) in test_cases.into_iter().enumerate() {
+        let (mut binwalker, output_directory) =
+            configure_binwalk(&format!("extractor_filter_{i}"), "gzip.bin");
+        binwalker.extraction_config.extractor_filter = ExtractorFilter { include, exclude };
+
+        let results = run_binwalk(&binwalker);
+
+        // The signature is reported regardless of whether or not it was extracted
+        assert_eq!(results.file_map.len(), 1, "test case {i}");
+        assert_eq!(results.extractions.len(), 1, "test case {i}");
+
+        let extraction = results.extractions.values().next().unwrap();
+
+        assert_eq!(extraction.success, expect_extraction, "test case {i}");
+        assert_eq!(extraction.skipped, !expect_extraction, "test case {i}");
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn quarantine() {
+    let test_directory = test_directory("quarantine");
+
+    let config = ExtractionConfig {
+        quarantine: true,
+        ..Default::default()
+    };
+
+    // External extractors do not use the Chroot API, and may create executables, symlinks and FIFOs
+    let result = run_sh_extractor(
+        &test_directory,
+        "echo data > run.sh && chmod 755 run.sh && ln -s /etc/passwd passwd && mkfifo fifo",
+        &config,
+    );
+    let output_directory = Path::new(&result.output_directory);
+
+    assert!(result.success);
+
+    let permissions = std::fs::metadata(output_directory.join("run.sh"))
+        .unwrap()
+        .permissions();
+    assert_eq!(permissions.mode() & 0o111, 0);
+
+    assert_eq!(
+        std::fs::read_to_string(output_directory.join("passwd")).unwrap(),
+        "symlink -> /etc/passwd"
+    );
+    assert_eq!(
+        std::fs::read_to_string(output_directory.join("fifo")).unwrap(),
+        "fifo"
+    );
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn extractor_command_paths() {
+    let test_directory = test_directory("extractor_command_paths");
+
+    // An extractor that is not in PATH
+    let script_path = test_directory.join("unpack-test");
+    std::fs::write(&script_path, "#!/bin/sh\necho unpacked > unpacked.txt\n").unwrap();
+    std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let command_paths =
+        HashMap::from([("unpack-test".to_string(), script_path.display().to_string())]);
+
+    // (command, command path mappings)
+    let test_cases = vec![
+        // Commands specified by absolute path are run directly
+        (script_path.display().to_string(), HashMap::new()),
+        // Commands may be mapped to absolute paths by name
+        ("unpack-test".to_string(), command_paths),
+    ];
+
+    for (command, command_paths) in test_cases {
+        let extractor = Extractor {
+            utility: ExtractorType::External(command),
+            ..Default::default()
+        };
+
+        let config = ExtractionConfig {
+            command_paths,
+            ..Default::default()
+        };
+
+        let result = run_extractor(
+            &test_directory,
+            TEST_INPUT,
+            &test_signature(4, 4),
+            &extractor,
+            &config,
+        );
+
+        assert!(result.success);
+        assert_eq!(
+            std::fs::read_to_string(Path::new(&result.output_directory).join("unpacked.txt"))
+                .unwrap(),
+            "unpacked\n"
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn carved_extensions() {
+    let test_directory = test_directory("carved_extensions");
+
+    // Records the name of the carved file that it was passed
+    let extractor = Extractor {
+        utility: ExtractorType::External("sh".to_string()),
+        extension: "bin".to_string(),
+        arguments: vec![
+            "-c".to_string(),
+            "basename \"$0\" > carved_file_name.txt".to_string(),
+            SOURCE_FILE_PLACEHOLDER.to_string(),
+        ],
+        ..Default::default()
+    };
+
+    // (carved file extension overrides, expected carved file name)
+    let test_cases = vec![
+        // Without an override, the extractor's extension is used
+        (HashMap::new(), "test_4.bin"),
+        // Overrides only apply to the signature that they name
+        (
+            HashMap::from([("other".to_string(), "other".to_string())]),
+            "test_4.bin",
+        ),
+        (
+            HashMap::from([("test".to_string(), "custom".to_string())]),
+            "test_4.custom",
+        ),
+        // A leading period is optional
+        (
+            HashMap::from([("test".to_string(), ".custom".to_string())]),
+            "test_4.custom",
+        ),
+    ];
+
+    for (carved_extensions, expected_file_name) in test_cases {
+        let config = ExtractionConfig {
+            carved_extensions,
+            ..Default::default()
+        };
+
+        let result = run_extractor(
+            &test_directory,
+            TEST_INPUT,
+            &test_signature(4, 4),
+            &extractor,
+            &config,
+        );
+
+        assert!(result.success);
+        assert_eq!(
+            std::fs::read_to_string(
+                Path::new(&result.output_directory).join("carved_file_name.txt")
+            )
+            .unwrap()
+            .trim(),
+            expected_file_name
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn extractor_retries() {
+    let test_directory = test_directory("extractor_retries");
+
+    let attempts_path = test_directory.join("attempts");
+
+    // Killed by a signal on the first two attempts, then succeeds
+    let flaky_script = test_directory.join("flaky");
+    std::fs::write(
+        &flaky_script,
+        format!(
+            "#!/bin/sh\necho x >> {0}\n[ $(wc -l < {0}) -lt 3 ] && kill -9 $$\necho unpacked > unpacked.txt\n",
+            attempts_path.display()
+        ),
+    )
+    .unwrap();
+
+    // Always exits with an unexpected exit code
+    let unsupported_script = test_directory.join("unsupported");
+    std::fs::write(
+        &unsupported_script,
+        format!("#!/bin/sh\necho x >> {}\nexit 1\n", attempts_path.display()),
+    )
+    .unwrap();
+
+    for script in [&flaky_script, &unsupported_script] {
+        std::fs::set_permissions(script, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    // (script, retries, expected success, expected number of attempts)
+    let test_cases = vec![
+        (&flaky_script, 0, false, 1),
+        (&flaky_script, 1, false, 2),
+        (&flaky_script, 3, true, 3),
+        // Unexpected exit codes are not retried
+        (&unsupported_script, 3, false, 1),
+    ];
+
+    for (script, retries, success, attempts) in test_cases {
+        let _ = std::fs::remove_file(&attempts_path);
+
+        let extractor = Extractor {
+            utility: ExtractorType::External(script.display().to_string()),
+            retries,
+            ..Default::default()
+        };
+
+        let result = run_extractor(
+            &test_directory,
+            TEST_INPUT,
+            &test_signature(4, 4),
+            &extractor,
+            &ExtractionConfig::default(),
+        );
+
+        assert_eq!(result.success, success);
+        assert_eq!(
+            std::fs::read_to_string(&attempts_path)
+                .unwrap()
+                .lines()
+                .count(),
+            attempts
+        );
+
+        if success {
+            assert!(
+                Path::new(&result.output_directory)
+                    .join("unpacked.txt")
+                    .exists()
+            );
+        } else if script == &unsupported_script {
+            assert!(matches!(
+                result.error,
+                Some(ExtractionError::NonZeroExit(_))
+            ));
+        }
+    }
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[test]
+fn extraction_manifest() {
+    let (mut binwalker, output_directory) = configure_binwalk("extraction_manifest", "gzip.bin");
+    binwalker.extraction_config.write_manifest = true;
+
+    // Run twice; the manifest from the first run is replaced
+    run_binwalk(&binwalker);
+    let results = run_binwalk(&binwalker);
+
+    let manifest_path = Path::new(&output_directory)
+        .join("gzip.bin.extracted")
+        .join(MANIFEST_FILE_NAME);
+    let manifest: ExtractionManifest =
+        serde_json::from_slice(&std::fs::read(&manifest_path).unwrap()).unwrap();
+
+    let extraction = results.extractions.values().next().unwrap();
+
+    assert_eq!(manifest.extractions.len(), 1);
+    assert_eq!(manifest.extractions[0].offset, 0);
+    assert_eq!(manifest.extractions[0].signature, "gzip");
+    assert_eq!(manifest.extractions[0].extractor, extraction.extractor);
+    assert!(manifest.extractions[0].success);
+    assert_eq!(manifest.extractions[0].output_directory, "0");
+    assert_eq!(manifest.extractions[0].sha256, extraction.carved_sha256);
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn resume_extraction() {
+    let (mut binwalker, output_directory) = configure_binwalk("resume_extraction", "gzip.bin");
+    binwalker.extraction_config.write_manifest = true;
+    run_binwalk(&binwalker);
+
+    let extraction_directory = Path::new(&output_directory)
+        .join("gzip.bin.extracted")
+        .join("0");
+    let marker_path = extraction_directory.join("resume_marker");
+    std::fs::write(&marker_path, b"").unwrap();
+
+    // A resumed run skips the completed extraction, leaving its output untouched
+    binwalker.extraction_config.resume = true;
+    let results = run_binwalk(&binwalker);
+    let extraction = results.extractions.values().next().unwrap();
+
+    assert!(extraction.success);
+    assert_eq!(
+        extraction.output_directory,
+        extraction_directory.display().to_string()
+    );
+    assert!(marker_path.exists());
+
+    // Extractions whose output is missing are run again
+    std::fs::remove_dir_all(&extraction_directory).unwrap();
+    let results = run_binwalk(&binwalker);
+
+    assert!(results.extractions.values().next().unwrap().success);
+    assert!(extraction_directory.join("decompressed.bin").exists());
+    assert!(!marker_path.exists());
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn min_confidence() {
+    let (mut binwalker, output_directory) = configure_binwalk("min_confidence", "gzip.bin");
+
+    // High confidence signatures meet the threshold
+    binwalker.extraction_config.min_confidence = CONFIDENCE_HIGH;
+    let results = run_binwalk(&binwalker);
+    assert_eq!(results.file_map[0].confidence, CONFIDENCE_HIGH);
+    assert_eq!(results.extractions.len(), 1);
+
+    // Signatures below the threshold are reported, but not extracted
+    binwalker.extraction_config.min_confidence = CONFIDENCE_HIGH + 1;
+    let results = run_binwalk(&binwalker);
+    assert_eq!(results.file_map.len(), 1);
+    assert!(results.extractions.is_empty());
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn archive_extractions() {
+    let (binwalker, output_directory) = configure_binwalk("archive_extractions", "gzip.bin");
+    let results = run_binwalk(&binwalker);
+
+    let archive_path = binwalk::extractors::common::archive_extractions(
+        &binwalker.base_target_file,
+        &results.extractions,
+        &binwalker.extraction_config,
+    )
+    .expect("Failed to archive extractions");
+
+    // The loose extraction directory is replaced by the archive
+    assert_eq!(archive_path, format!("{}.tar", binwalker.base_target_file));
+    assert!(!Path::new(&format!("{}.extracted", binwalker.base_target_file)).exists());
+
+    let mut archive = tar::Archive::new(std::fs::File::open(&archive_path).unwrap());
+    let archived_files: Vec<String> = archive
+        .entries()
+        .unwrap()
+        .map(|entry| entry.unwrap().path().unwrap().display().to_string())
+        .collect();
+
+    assert!(archived_files.contains(&"0/decompressed.bin".to_string()));
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn overlapping_output_directories() {
+    let (binwalker, output_directory) =
+        configure_binwalk("overlapping_output_directories", "gzip.bin");
+
+    let file_data = std::fs::read(&binwalker.base_target_file).unwrap();
+
+    // Two signatures at the same offset
+    let mut file_map = binwalker.scan(&file_data);
+    let mut overlapping_signature = file_map[0].clone();
+    overlapping_signature.id = "overlapping".to_string();
+    file_map.push(overlapping_signature);
+
+    let extractions = binwalker.extract(&file_data, &binwalker.base_target_file, &file_map);
+
+    let first = &extractions[&file_map[0].id];
+    let second = &extractions[&file_map[1].id];
+
+    assert!(first.success);
+    assert!(second.success);
+    assert_ne!(first.output_directory, second.output_directory);
+    assert!(first.output_directory.ends_with("0"));
+    assert!(second.output_directory.ends_with("0_gzip"));
+    assert!(
+        Path::new(&first.output_directory)
+            .join("decompressed.bin")
+            .exists()
+    );
+    assert!(
+        Path::new(&second.output_directory)
+            .join("decompressed.bin")
+            .exists()
+    );
+
+    // Output directories are only unique per extraction; extracting again re-uses the same directories
+    let extractions = binwalker.extract(&file_data, &binwalker.base_target_file, &file_map);
+    assert_eq!(
+        extractions[&file_map[1].id].output_directory,
+        second.output_directory
+    );
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn preserve_xattrs() {
+    let test_directory = test_directory("preserve_xattrs");
+
+    // Build a tarball with a file that has an extended attribute
+    let mut tarball = tar::Builder::new(vec![]);
+    tarball
+        .append_pax_extensions([("SCHILY.xattr.user.binwalk", b"test".as_slice())])
+        .unwrap();
+    let mut header = tar::Header::new_ustar();
+    header.set_size(4);
+    header.set_mode(0o644);
+    header.set_cksum();
+    tarball
+        .append_data(&mut header, "file.txt", b"AAAA".as_slice())
+        .unwrap();
+    let file_data = tarball.into_inner().unwrap();
+
+    assert_eq!(
+        tarball_xattrs(&file_data),
+        vec![(
+            "file.txt".to_string(),
+            vec![("user.binwalk".to_string(), b"test".to_vec())]
+        )]
+    );
+
+    let file_path = test_directory.join("input.bin");
+    std::fs::write(&file_path, &file_data).unwrap();
+
+    // Not all file systems support extended attributes
+    let xattrs_supported = xattr::set(&file_path, "user.binwalk", b"test").is_ok();
+
+    let signature = SignatureResult {
+        name: "tarball".to_string(),
+        size: file_data.len(),
+        ..Default::default()
+    };
+
+    for preserve_xattrs in [false, true] {
+        let config = ExtractionConfig {
+            preserve_xattrs,
+            ..Default::default()
+        };
+
+        let result = run_extractor(
+            &test_directory,
+            &file_data,
+            &signature,
+            &tarball_extractor(),
+            &config,
+        );
+        assert!(result.success);
+
+        let extracted_file = Path::new(&result.output_directory).join("file.txt");
+        let xattr_value = xattr::get(&extracted_file, "user.binwalk").unwrap_or_default();
+
+        if preserve_xattrs && xattrs_supported {
+            assert_eq!(xattr_value, Some(b"test".to_vec()));
+        } else {
+            assert_eq!(xattr_value, None);
+        }
+    }
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn process_limiter() {
+    const MAX_PROCESSES: usize = 2;
+    const EXTRACTION_COUNT: usize = 6;
+
+    let test_directory = test_directory("process_limiter");
+
+    let config = ExtractionConfig {
+        process_limiter: ProcessLimiter::new(MAX_PROCESSES),
+        ..Default::default()
+    };
+
+    let extractor = sh_extractor("sleep 0.1; echo data > out.bin");
+
+    // Keep track of the maximum number of concurrently running processes
+    let done = Arc::new(AtomicBool::new(false));
+    let max_running = Arc::new(AtomicUsize::new(0));
+    let monitor = {
+        let limiter = config.process_limiter.clone();
+        let done = done.clone();
+        let max_running = max_running.clone();
+        std::thread::spawn(move || {
+            while !done.load(Ordering::SeqCst) {
+                max_running.fetch_max(limiter.running(), Ordering::SeqCst);
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        })
+    };
+
+    let extractions: Vec<_> = (0..EXTRACTION_COUNT)
+        .map(|i| {
+            let config = config.clone();
+            let extractor = extractor.clone();
+            // Each extraction needs its own output directory
+            let extraction_directory = test_directory.join(i.to_string());
+            std::thread::spawn(move || {
+                run_extractor(
+                    &extraction_directory,
+                    TEST_INPUT,
+                    &test_signature(4, 4),
+                    &extractor,
+                    &config,
+                )
+            })
+        })
+        .collect();
+
+    for extraction in extractions {
+        assert!(extraction.join().unwrap().success);
+    }
+
+    done.store(true, Ordering::SeqCst);
+    monitor.join().unwrap();
+
+    assert!(max_running.load(Ordering::SeqCst) <= MAX_PROCESSES);
+    assert!(max_running.load(Ordering::SeqCst) > 0);
+    assert_eq!(config.process_limiter.running(), 0);
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[test]
+fn registered_extractors() {
+    const MARKER_FILE_NAME: &str = "registered.txt";
+
+    fn registered_extractor(
+        _file_data: &[u8],
+        _offset: usize,
+        output_directory: Option<&str>,
+    ) -> ExtractionResult {
+        ExtractionResult {
+            success: Chroot::new(output_directory)
+                .create_file(MARKER_FILE_NAME, b"registered")
+                .is_ok(),
+            ..Default::default()
+        }
+    }
+
+    // Registered extractors override the compiled in extractor
+    let (binwalker, output_directory) = configure_binwalk("registered_override", "gzip.bin");
+    binwalker.register_extractor(
+        "gzip",
+        Extractor {
+            utility: ExtractorType::Internal(registered_extractor),
+            ..Default::default()
+        },
+    );
+
+    let results = run_binwalk(&binwalker);
+    let extraction = results.extractions.values().next().unwrap();
+
+    assert!(extraction.success);
+    assert!(
+        Path::new(&extraction.output_directory)
+            .join(MARKER_FILE_NAME)
+            .exists()
+    );
+    assert_eq!(
+        std::fs::read_dir(&extraction.output_directory)
+            .unwrap()
+            .count(),
+        1
+    );
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+
+    // Extractors may also be registered for signatures with no compiled in extractor
+    let (binwalker, output_directory) = configure_binwalk("registered_new", "elf.bin");
+    assert!(run_binwalk(&binwalker).extractions.is_empty());
+
+    binwalker.register_extractor(
+        "elf",
+        Extractor {
+            utility: ExtractorType::Internal(extract_elf_sections),
+            ..Default::default()
+        },
+    );
+
+    let results = run_binwalk(&binwalker);
+
+    assert_eq!(results.extractions.len(), 1);
+    assert!(results.extractions.values().next().unwrap().success);
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn output_failure() {
+    let (binwalker, output_directory) = configure_binwalk("output_failure", "gzip.bin");
+
+    // As if a previous extraction had filled up the output file system
+    binwalker
+        .extraction_config
+        .output_failure
+        .record(&ExtractionError::OutputUnavailable(
+            "No space left on device".to_string(),
+        ));
+
+    let results = run_binwalk(&binwalker);
+
+    assert_eq!(results.extractions.len(), 1);
+
+    let extraction = results.extractions.values().next().unwrap();
+
+    assert!(!extraction.success);
+    assert!(matches!(
+        extraction.error,
+        Some(ExtractionError::OutputUnavailable(_))
+    ));
+
+    // No further extractions are attempted
+    assert!(
+        !Path::new(&output_directory)
+            .join("gzip.bin.extracted")
+            .exists()
+    );
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn output_failure_on_write_error() {
+    // Writes to /dev/full fail with ENOSPC, just as they would on a full output file system
+    let write_error = Chroot::new(None)
+        .append_to_file("/dev/full", b"data")
+        .unwrap_err();
+    assert_eq!(write_error.kind, std::io::ErrorKind::StorageFull);
+
+    let extraction_error = ExtractionError::from(write_error);
+    assert!(matches!(
+        extraction_error,
+        ExtractionError::OutputUnavailable(_)
+    ));
+
+    // Other write errors only fail the extraction at hand
+    let missing_directory = std::env::temp_dir()
+        .join("binwalk_extraction_config_tests")
+        .join("output_failure_on_write_error")
+        .join("missing")
+        .join("file.bin");
+    let write_error = Chroot::new(None)
+        .append_to_file(missing_directory.display().to_string(), b"data")
+        .unwrap_err();
+    assert!(matches!(
+        ExtractionError::from(write_error),
+        ExtractionError::Io(_)
+    ));
+
+    let (binwalker, output_directory) =
+        configure_binwalk("output_failure_on_write_error", "gzip.bin");
+    binwalker
+        .extraction_config
+        .output_failure
+        .record(&extraction_error);
+
+    // Once the output file system is full, extraction is aborted without attempting to write anything else
+    let results = run_binwalk(&binwalker);
+    let extraction = results.extractions.values().next().unwrap();
+
+    assert!(!extraction.success);
+    assert_eq!(extraction.error, Some(extraction_error));
+    assert!(
+        !Path::new(&output_directory)
+            .join("gzip.bin.extracted")
+            .exists()
+    );
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn output_base() {
+    let (mut binwalker, output_directory) = configure_binwalk("output_base", "gzip.bin");
+    let output_base = Path::new(&output_directory).join("output_base");
+    binwalker.extraction_config.output_base = Some(output_base.clone());
+
+    // Extract the input file in place, rather than the symlink to it in the output directory
+    let file_path = Path::new("tests")
+        .join("inputs")
+        .join("gzip.bin")
+        .display()
+        .to_string();
+    let file_data = std::fs::read(&file_path).unwrap();
+    let results = binwalker.extract(&file_data, &file_path, &binwalker.scan(&file_data));
+
+    assert_eq!(results.len(), 1);
+
+    let extraction = results.values().next().unwrap();
+    let expected_directory = std::path::absolute(&output_base)
+        .unwrap()
+        .join("gzip.bin.extracted")
+        .join("0");
+
+    assert!(extraction.success);
+    assert_eq!(Path::new(&extraction.output_directory), expected_directory);
+    assert!(expected_directory.join("decompressed.bin").exists());
+    assert!(!Path::new(&format!("{file_path}.extracted")).exists());
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn extract_at_offsets() {
+    const PADDING: &[u8] = b"not gzip data";
+
+    let (binwalker, output_directory) = configure_binwalk("extract_at", "gzip.bin");
+
+    // Prefix the gzip data with some padding, so that it is not at the start of the file
+    let gzip_data = std::fs::read(&binwalker.base_target_file).unwrap();
+    let file_data = [PADDING, &gzip_data].concat();
+
+    let results = binwalker
+        .extract_at(
+            &file_data,
+            &binwalker.base_target_file,
+            &[(PADDING.len(), gzip_data.len(), "gzip")],
+        )
+        .expect("Extraction at a known offset failed");
+
+    assert_eq!(results.file_map.len(), 1);
+    assert_eq!(results.file_map[0].offset, PADDING.len());
+    assert_eq!(results.file_map[0].name, "gzip");
+
+    let extraction = &results.extractions[&results.file_map[0].id];
+    assert!(extraction.success);
+    assert!(
+        Path::new(&output_directory)
+            .join("gzip.bin.extracted")
+            .join(format!("{:X}", PADDING.len()))
+            .join("decompressed.bin")
+            .exists()
+    );
+
+    // Locations outside of the file data, and unknown signatures, are rejected
+    assert!(
+        binwalker
+            .extract_at(&file_data, "", &[(file_data.len(), 0, "gzip")])
+            .is_err()
+    );
+    assert!(
+        binwalker
+            .extract_at(&file_data, "", &[(0, file_data.len() + 1, "gzip")])
+            .is_err()
+    );
+    assert!(
+        binwalker
+            .extract_at(&file_data, "", &[(0, 0, "not_a_signature")])
+            .is_err()
+    );
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn extraction_statistics() {
+    let (binwalker, output_directory) = configure_binwalk("extraction_statistics", "gzip.bin");
+    let results = run_binwalk(&binwalker);
+
+    let mut statistics = ExtractionStatistics::default();
+    for signature in &results.file_map {
+        let extraction = &results.extractions[&signature.id];
+        assert!(extraction.duration_ms.is_some());
+        statistics.add(signature, extraction);
+    }
+
+    let extractor_statistics = statistics.by_duration();
+    assert_eq!(extractor_statistics.len(), 1);
+    assert_eq!(extractor_statistics[0].extractions, 1);
+    assert_eq!(
+        extractor_statistics[0].input_size,
+        std::fs::metadata(&binwalker.base_target_file)
+            .unwrap()
+            .len()
+    );
+    assert!(extractor_statistics[0].output_size > 0);
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn carve_gaps() {
+    let (mut binwalker, output_directory) = configure_binwalk("carve_gaps", "gzip.bin");
+    binwalker.extraction_config.gap_min_size = Some(16);
+
+    // Unidentified data before the gzip data is carved; the trailing data is below the minimum gap size
+    let mut file_data = vec![0xFF; 64];
+    file_data.extend(std::fs::read(&binwalker.base_target_file).unwrap());
+    file_data.extend(vec![0xFF; 8]);
+
+    let file_path = Path::new(&output_directory)
+        .join("padded.bin")
+        .display()
+        .to_string();
+    std::fs::write(&file_path, &file_data).unwrap();
+
+    let results = binwalker.analyze_buf(&file_data, &file_path, true);
+
+    assert_eq!(results.file_map.len(), 1);
+    assert_eq!(results.gaps.len(), 1);
+    assert_eq!(results.gaps[0].name, "gap");
+    assert_eq!(results.gaps[0].offset, 0);
+    assert_eq!(results.gaps[0].size, 64);
+
+    let gap_extraction = &results.extractions[&results.gaps[0].id];
+    let expected_file = Path::new(&output_directory)
+        .join("padded.bin.extracted")
+        .join("gap_0_40.bin");
+
+    assert!(gap_extraction.success);
+    assert_eq!(
+        gap_extraction.carved_file,
+        Some(expected_file.display().to_string())
+    );
+    assert_eq!(std::fs::read(&expected_file).unwrap(), vec![0xFF; 64]);
+
+    // The gzip data is still extracted as usual
+    assert!(results.extractions[&results.file_map[0].id].success);
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn file_type_lookup() {
+    const SQLITE_MIME_TYPE: &str = "application/vnd.sqlite3";
+
+    let (mut binwalker, output_directory) = configure_binwalk("file_type_lookup", "gzip.bin");
+    binwalker.extraction_config.gap_min_size = Some(16);
+
+    // binwalk has no signature for SQLite databases
+    let mut sqlite_data = b"SQLite format 3\x00".to_vec();
+    sqlite_data.extend(vec![0; 64]);
+
+    let results = binwalker.analyze_buf(&sqlite_data, "sqlite.db", false);
+    assert!(results.file_map.is_empty());
+    assert_eq!(results.file_type, None);
+
+    binwalker.file_type_lookup = true;
+
+    let results = binwalker.analyze_buf(&sqlite_data, "sqlite.db", false);
+    assert_eq!(results.file_type.as_deref(), Some(SQLITE_MIME_TYPE));
+
+    // Files with signatures are not typed, but their unidentified gaps are
+    let mut file_data = sqlite_data.clone();
+    file_data.extend(std::fs::read(&binwalker.base_target_file).unwrap());
+
+    let results = binwalker.analyze_buf(&file_data, "padded.bin", false);
+    assert_eq!(results.file_map.len(), 1);
+    assert_eq!(results.file_type, None);
+    assert_eq!(results.gaps.len(), 1);
+    assert_eq!(
+        results.gaps[0].description,
+        format!("Unidentified data, 80 bytes, file type: {SQLITE_MIME_TYPE}")
+    );
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn terminal_types() {
+    let (binwalker, output_directory) = configure_binwalk("terminal_types", "swf.bin");
+    let results = run_binwalk(&binwalker);
+
+    let decompressed_swf = results.extractions[&results.file_map[0].id].recursion_targets();
+    assert_eq!(decompressed_swf.len(), 1);
+    assert!(decompressed_swf[0].ends_with("decompressed.swf"));
+
+    // (terminal types, expect the decompressed SWF file to be terminal)
+    let test_cases = vec![
+        (vec![], false),
+        (vec!["SWF"], true),
+        (vec!["bin"], false),
+        (vec!["application/x-shockwave-flash"], true),
+        (vec!["application/*"], true),
+        (vec!["image/*", "txt"], false),
+    ];
+
+    for (terminal_types, expected) in test_cases {
+        let config = ExtractionConfig {
+            terminal_types: terminal_types.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.is_terminal_file(&decompressed_swf[0]),
+            expected,
+            "{terminal_types:?}"
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn nested_signatures() {
+    // (policy, expected number of signatures, expect ar extraction, expect gzip extraction)
+    let test_cases = vec![
+        (NestedSignaturePolicy::Discard, 1, true, false),
+        (NestedSignaturePolicy::Outermost, 2, true, false),
+        (NestedSignaturePolicy::Innermost, 2, false, true),
+        (NestedSignaturePolicy::Both, 2, true, true),
+    ];
+
+    for (policy, signature_count, ar_extracted, gzip_extracted) in test_cases {
+        let (mut binwalker, output_directory) =
+            configure_binwalk(&format!("nested_{policy:?}"), "gzip.bin");
+        binwalker.extraction_config.nested_signatures = policy;
+
+        // An ar archive with a single, gzip compressed, member
+        let gzip_data = std::fs::read(&binwalker.base_target_file).unwrap();
+        let mut file_data = b"!<arch>\n".to_vec();
+        file_data.extend(
+            format!(
+                "{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n",
+                "lsb-release.gz/",
+                0,
+                0,
+                0,
+                644,
+                gzip_data.len()
+            )
+            .into_bytes(),
+        );
+        file_data.extend(&gzip_data);
+        file_data.resize(file_data.len().next_multiple_of(2), b'\n');
+
+        std::fs::create_dir_all(&output_directory).unwrap();
+        let file_path = Path::new(&output_directory)
+            .join("nested.ar")
+            .display()
+            .to_string();
+        std::fs::write(&file_path, &file_data).unwrap();
+
+        let results = binwalker.analyze_buf(&file_data, &file_path, true);
+
+        assert_eq!(results.file_map.len(), signature_count, "{policy:?}");
+        assert_eq!(results.file_map[0].name, "ar");
+        assert_eq!(results.file_map[0].parent, None);
+
+        if signature_count > 1 {
+            assert_eq!(results.file_map[1].name, "gzip");
+            assert_eq!(results.file_map[1].offset, 68);
+            assert_eq!(
+                results.file_map[1].parent.as_ref(),
+                Some(&results.file_map[0].id)
+            );
+        }
+
+        let extracted = |index: usize| {
+            results
+                .file_map
+                .get(index)
+                .and_then(|signature| results.extractions.get(&signature.id))
+                .is_some_and(|extraction| extraction.success)
+        };
+
+        assert_eq!(extracted(0), ar_extracted, "{policy:?}");
+        assert_eq!(extracted(1), gzip_extracted, "{policy:?}");
+
+        // The gzip data is found again when recursing into the extracted ar archive member
+        if ar_extracted && gzip_extracted {
+            assert!(results.extractions[&results.file_map[1].id].do_not_recurse);
+        }
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
+
+#[test]
+fn extraction_plan() {
+    let (mut binwalker, output_directory) = configure_binwalk("extraction_plan", "gzip.bin");
+    binwalker.extraction_config.nested_signatures = NestedSignaturePolicy::Both;
+
+    // An ar archive with a single, gzip compressed, member, followed by a second copy of the gzip data
+    let gzip_data = std::fs::read(&binwalker.base_target_file).unwrap();
+    let mut file_data = b"!<arch>\n".to_vec();
+    file_data.extend(
+        format!(
+            "{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n",
+            "lsb-release.gz/",
+            0,
+            0,
+            0,
+            644,
+            gzip_data.len()
+        )
+        .into_bytes(),
+    );
+    file_data.extend(&gzip_data);
+    file_data.resize(file_data.len().next_multiple_of(2), b'\n');
+    let second_gzip_offset = file_data.len();
+    file_data.extend(&gzip_data);
+
+    let file_path = Path::new(&output_directory)
+        .join("plan.ar")
+        .display()
+        .to_string();
+    std::fs::write(&file_path, &file_data).unwrap();
+
+    // The second copy of the gzip data is not to be extracted
+    binwalker.extraction_config.extraction_predicate =
+        Some(ExtractionPredicate::new(move |signature| {
+            signature.offset != second_gzip_offset
+        }));
+
+    let file_map = binwalker.scan(&file_data);
+    let plan = binwalker.plan(&file_data, &file_path, &file_map);
+
+    assert_eq!(plan.len(), 3);
+    assert_eq!(
+        plan.iter()
+            .map(|planned| (planned.signature.name.as_str(), planned.depth))
+            .collect::<Vec<_>>(),
+        vec![("ar", 0), ("gzip", 1), ("gzip", 0)]
+    );
+
+    // The nested gzip data is found again when recursing into the extracted ar archive member
+    assert!(plan[0].recurse);
+    assert!(!plan[1].recurse);
+
+    let gzip_extraction = plan[1].extraction.as_ref().unwrap();
+    assert_eq!(gzip_extraction.extractor, "gzip_built_in");
+    assert_eq!(gzip_extraction.dry_run_success, Some(true));
+    assert_eq!(gzip_extraction.size, Some(gzip_data.len()));
+
+    assert!(plan[2].extraction.is_none());
+    assert_eq!(
+        plan[2].skip_reason.as_deref(),
+        Some("vetoed by the extraction predicate")
+    );
+
+    // Planning doesn't create any output directories
+    assert!(!Path::new(&format!("{file_path}.extracted")).exists());
+
+    // The plan matches what is actually extracted
+    let extractions = binwalker.extract(&file_data, &file_path, &file_map);
+
+    for planned in &plan[..2] {
+        let extraction = &extractions[&planned.signature.id];
+        let planned_extraction = planned.extraction.as_ref().unwrap();
+
+        assert!(extraction.success);
+        assert_eq!(extraction.extractor, planned_extraction.extractor);
+        assert_eq!(extraction.output_directory, planned_extraction.output_path);
+    }
+
+    assert!(extractions[&plan[2].signature.id].skipped);
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn extractor_config_file() {
+    let (binwalker, output_directory) = configure_binwalk("extractor_config_file", "gzip.bin");
+    std::fs::create_dir_all(&output_directory).unwrap();
+
+    // Replace the built-in gzip extractor with an external command
+    let config_path = Path::new(&output_directory).join("extractors.json");
+    std::fs::write(
+        &config_path,
+        r#"{"gzip": {"command": "sh", "extension": "gz", "arguments": ["-c", "gzip -dc < \"$0\" > configured.bin", "%e"], "timeout": 30}}"#,
+    )
+    .unwrap();
+
+    let registered = binwalker
+        .extraction_config
+        .extractor_registry
+        .register_from_file(&config_path)
+        .unwrap();
+    assert_eq!(registered, 1);
+
+    let results = run_binwalk(&binwalker);
+    let extraction = results.extractions.values().next().unwrap();
+
+    assert!(extraction.success);
+    assert!(
+        Path::new(&extraction.output_directory)
+            .join("configured.bin")
+            .exists()
+    );
+
+    // Unknown fields and missing commands are rejected, and nothing is registered
+    for invalid_config in [
+        r#"{"xz": {"command": "unxz", "unknown": true}}"#,
+        r#"{"xz": {"command": ""}}"#,
+        r#"{"xz": {"extension": "xz"}}"#,
+    ] {
+        std::fs::write(&config_path, invalid_config).unwrap();
+        let error = binwalker
+            .extraction_config
+            .extractor_registry
+            .register_from_file(&config_path)
+            .unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(
+            binwalker
+                .extraction_config
+                .extractor_registry
+                .get("xz")
+                .is_none()
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn extractor_timeout() {
+    let test_directory = test_directory("extractor_timeout");
+
+    let extractor = Extractor {
+        utility: ExtractorType::External("sleep".to_string()),
+        arguments: vec!["10".to_string()],
+        timeout: Some(Duration::from_millis(200)),
+        ..Default::default()
+    };
+
+    let start = Instant::now();
+    let result = run_extractor(
+        &test_directory,
+        TEST_INPUT,
+        &test_signature(4, 4),
+        &extractor,
+        &ExtractionConfig::default(),
+    );
+
+    assert!(!result.success);
+    assert_eq!(result.error, Some(ExtractionError::Timeout));
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[test]
+fn run_deadline() {
+    let (mut binwalker, output_directory) = configure_binwalk("run_deadline", "gzip.bin");
+    binwalker.extraction_config.run_deadline = Some(Instant::now());
+
+    // Signatures are still reported, but no extractions are started once the deadline has passed
+    let results = run_binwalk(&binwalker);
+    assert_eq!(results.file_map.len(), 1);
+    assert!(results.extractions.is_empty());
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn kill_at_deadline() {
+    let test_directory = test_directory("kill_at_deadline");
+
+    // The extractor itself has no timeout
+    let extractor = Extractor {
+        utility: ExtractorType::External("sleep".to_string()),
+        arguments: vec!["10".to_string()],
+        ..Default::default()
+    };
+
+    let start = Instant::now();
+    let config = ExtractionConfig {
+        run_deadline: Some(start + Duration::from_millis(200)),
+        kill_at_deadline: true,
+        ..Default::default()
+    };
+
+    let result = run_extractor(
+        &test_directory,
+        TEST_INPUT,
+        &test_signature(4, 4),
+        &extractor,
+        &config,
+    );
+
+    assert!(!result.success);
+    assert_eq!(result.error, Some(ExtractionError::Timeout));
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
+
+#[cfg(unix)]
+#[test]
+fn stdout_to_file() {
+    const STDOUT_FILE_NAME: &str = "decompressed.bin";
+
+    // (command, arguments, expect success)
+    let test_cases = vec![
+        ("gzip", vec!["-dc", "%e"], true),
+        // Commands that write nothing to stdout fail, and leave no output file behind
+        ("true", vec![], false),
+    ];
+
+    for (command, arguments, expect_success) in test_cases {
+        let (binwalker, output_directory) =
+            configure_binwalk(&format!("stdout_to_file_{command}"), "gzip.bin");
+
+        binwalker.register_extractor(
+            "gzip",
+            Extractor {
+                utility: ExtractorType::External(command.to_string()),
+                extension: "gz".to_string(),
+                arguments: arguments.iter().map(|arg| arg.to_string()).collect(),
+                stdout_to_file: Some(STDOUT_FILE_NAME.to_string()),
+                ..Default::default()
+            },
+        );
+
+        let results = run_binwalk(&binwalker);
+        let extraction = results.extractions.values().next().unwrap();
+        let stdout_file = Path::new(&extraction.output_directory).join(STDOUT_FILE_NAME);
+
+        assert_eq!(extraction.success, expect_success);
+
+        if expect_success {
+            assert!(
+                std::fs::read_to_string(&stdout_file)
+                    .unwrap()
+                    .contains("DISTRIB_ID")
+            );
+        } else {
+            assert!(matches!(extraction.error, Some(ExtractionError::Io(_))));
+            assert!(!stdout_file.exists());
+        }
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn carve_directory() {
+    let test_directory = test_directory("carve_directory");
+    let carve_directory = test_directory.join("scratch");
+    std::fs::create_dir_all(&carve_directory).unwrap();
+
+    // Signature names are not trusted, the carved file must not escape the carve directory
+    let signature = SignatureResult {
+        name: "../../test".to_string(),
+        ..test_signature(4, 4)
+    };
+
+    // Record the path of the carved file, and copy it to the output directory
+    let extractor = Extractor {
+        utility: ExtractorType::External("sh".to_string()),
+        extension: "bin".to_string(),
+        arguments: vec![
+            "-c".to_string(),
+            "echo \"$1\" > carved_path.txt && cp \"$1\" copy.bin".to_string(),
+            "sh".to_string(),
+            SOURCE_FILE_PLACEHOLDER.to_string(),
+        ],
+        ..Default::default()
+    };
+
+    let config = ExtractionConfig {
+        carve_directory: Some(carve_directory.clone()),
+        ..Default::default()
+    };
+
+    let result = run_extractor(&test_directory, TEST_INPUT, &signature, &extractor, &config);
+    assert!(result.success);
+
+    let output_directory = Path::new(&result.output_directory);
+    assert_eq!(
+        std::fs::read(output_directory.join("copy.bin")).unwrap(),
+        b"BBBB"
+    );
+
+    let carved_path = std::fs::read_to_string(output_directory.join("carved_path.txt")).unwrap();
+    assert!(
+        Path::new(carved_path.trim()).starts_with(std::fs::canonicalize(&carve_directory).unwrap())
+    );
+
+    // Only the extractor's output is written to the output directory, and the carved file is cleaned up afterwards
+    assert_eq!(std::fs::read_dir(output_directory).unwrap().count(), 2);
+    assert_eq!(std::fs::read_dir(&carve_directory).unwrap().count(), 0);
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
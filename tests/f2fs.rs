@@ -0,0 +1,88 @@
+use binwalk::Binwalk;
+
+/// Builds an empty F2FS image whose superblock describes a file system of the given number of blocks
+fn f2fs_image(block_count: u64, nat_address: u32) -> Vec<u8> {
+    const BLOCK_SIZE: usize = 4096;
+    const SEGMENT0_ADDRESS: u32 = 2;
+    const SEGMENT_COUNT: u32 = 5;
+
+    let mut superblock: Vec<u8> = vec![];
+
+    // magic, major version, minor version
+    superblock.extend(0xF2F52010_u32.to_le_bytes());
+    superblock.extend(1_u16.to_le_bytes());
+    superblock.extend(16_u16.to_le_bytes());
+
+    // log sector size, log sectors per block, log block size, log blocks per segment, segments per section,
+    // sections per zone, checksum offset
+    for value in [9_u32, 3, 12, 9, 1, 1, 0] {
+        superblock.extend(value.to_le_bytes());
+    }
+
+    superblock.extend(block_count.to_le_bytes());
+
+    // section count, segment count, then one segment each for the checkpoint, SIT, NAT, SSA and main areas
+    for value in [1_u32, SEGMENT_COUNT, 1, 1, 1, 1, 1] {
+        superblock.extend(value.to_le_bytes());
+    }
+
+    // segment 0, checkpoint, SIT, NAT, SSA and main area block addresses
+    for value in [
+        SEGMENT0_ADDRESS,
+        SEGMENT0_ADDRESS,
+        SEGMENT0_ADDRESS + 512,
+        nat_address,
+        SEGMENT0_ADDRESS + 512 * 3,
+        SEGMENT0_ADDRESS + 512 * 4,
+    ] {
+        superblock.extend(value.to_le_bytes());
+    }
+
+    // root, node and meta inode numbers, UUID
+    for value in [3_u32, 1, 2] {
+        superblock.extend(value.to_le_bytes());
+    }
+    superblock.extend([0xAB; 16]);
+
+    // Volume name
+    for code_unit in "data".encode_utf16() {
+        superblock.extend(code_unit.to_le_bytes());
+    }
+
+    let mut image = vec![0; (SEGMENT0_ADDRESS + 512 * SEGMENT_COUNT) as usize * BLOCK_SIZE];
+    image[0x400..0x400 + superblock.len()].copy_from_slice(&superblock);
+    image
+}
+
+#[test]
+fn f2fs_size() {
+    const BLOCK_COUNT: u64 = 2 + 512 * 5;
+    const IMAGE_SIZE: usize = BLOCK_COUNT as usize * 4096;
+
+    let gzip_data = std::fs::read("tests/inputs/gzip.bin").unwrap();
+
+    // An F2FS image immediately followed by gzip data
+    let mut file_data = f2fs_image(BLOCK_COUNT, 2 + 512 * 2);
+    file_data.extend(&gzip_data);
+
+    let results = Binwalk::new().scan(&file_data);
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].name, "f2fs");
+    assert_eq!(results[0].offset, 0);
+    assert_eq!(results[0].size, IMAGE_SIZE);
+    assert!(results[0].description.contains("volume name: \"data\""));
+    assert_eq!(results[1].name, "gzip");
+    assert_eq!(results[1].offset, IMAGE_SIZE);
+}
+
+#[test]
+fn f2fs_invalid_layout() {
+    // The NAT area does not follow the SIT area
+    let file_data = f2fs_image(2 + 512 * 5, 2 + 512 * 3);
+    assert!(Binwalk::new().scan(&file_data).is_empty());
+
+    // The segments extend beyond the end of the file system
+    let file_data = f2fs_image(512 * 5, 2 + 512 * 2);
+    assert!(Binwalk::new().scan(&file_data).is_empty());
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use binwalk::extractors::fat::{extract_fat_filesystem, extract_fat_filesystem_with_deleted_files};
+use std::path::PathBuf;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "fat";
+
+    for input_file_name in ["fat12.bin", "fat32.bin"] {
+        common::integration_test(SIGNATURE_TYPE, input_file_name);
+    }
+}
+
+/// Reads the specified test file and returns its data, along with an empty output directory
+fn test_file(file_name: &str, test_name: &str) -> (Vec<u8>, PathBuf) {
+    let file_data = common::read_input_file(file_name);
+
+    let output_directory = common::output_directory("fat", &format!("{test_name}_{file_name}"));
+
+    (file_data, output_directory)
+}
+
+#[test]
+fn extract_file_tree() {
+    // (input file, file system size)
+    let test_cases = vec![("fat12.bin", 400 * 512), ("fat32.bin", 2048 * 512)];
+
+    for (file_name, image_size) in test_cases {
+        let (file_data, output_directory) = test_file(file_name, "file_tree");
+
+        let result =
+            extract_fat_filesystem(&file_data, 0, Some(&output_directory.display().to_string()));
+
+        assert!(result.success);
+        assert_eq!(result.size, Some(image_size));
+
+        let read = |path: &str| std::fs::read(output_directory.join(path)).unwrap();
+
+        // Short names are displayed in lowercase if flagged as such
+        assert_eq!(read("hello.txt"), b"hello\n");
+        assert_eq!(read("EMPTY"), b"");
+
+        // A long file name with a fragmented cluster chain
+        let long_file: Vec<u8> = (0..100)
+            .flat_map(|i| format!("line {i:04} of a fragmented file\n").into_bytes())
+            .collect();
+        assert_eq!(read("A Long File Name With Spaces.txt"), long_file);
+
+        assert_eq!(read("Sub Directory/nested.txt"), b"nested file\n");
+
+        let deep_file: Vec<u8> = (0..768).map(|i| (i % 256) as u8).collect();
+        assert_eq!(read("Sub Directory/deep/file.bin"), deep_file);
+
+        // The FAT32 root directory spans multiple clusters
+        assert_eq!(read("FILE7.DAT"), b"file 7\n");
+
+        // Deleted files are not recovered by default
+        assert!(!output_directory.join("deleted file.txt").exists());
+        assert!(!output_directory.join("_ONE.TXT").exists());
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
+
+#[test]
+fn recover_deleted_files() {
+    for file_name in ["fat12.bin", "fat32.bin"] {
+        let (file_data, output_directory) = test_file(file_name, "deleted_files");
+
+        let result = extract_fat_filesystem_with_deleted_files(
+            &file_data,
+            0,
+            Some(&output_directory.display().to_string()),
+        );
+
+        assert!(result.success);
+        assert_eq!(
+            std::fs::read(output_directory.join("deleted file.txt")).unwrap(),
+            b"deleted data\n".repeat(50)
+        );
+
+        // Deleted files without a long name lose the first character of their short name
+        assert!(output_directory.join("_ONE.TXT").exists());
+        assert_eq!(
+            std::fs::read(output_directory.join("hello.txt")).unwrap(),
+            b"hello\n"
+        );
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
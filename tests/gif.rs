@@ -0,0 +1,15 @@
+mod common;
+
+#[test]
+fn consumed_size() {
+    const SIGNATURE_TYPE: &str = "gif";
+    const TRAILING_SIGNATURE_TYPE: &str = "gzip";
+    const INPUT_FILE_NAME: &str = "gif_consumed_size.bin";
+    const IMAGE_SIZE: usize = 35;
+    common::consumed_size_test(
+        SIGNATURE_TYPE,
+        TRAILING_SIGNATURE_TYPE,
+        INPUT_FILE_NAME,
+        IMAGE_SIZE,
+    );
+}
@@ -6,3 +6,41 @@ fn integration_test() {
     const INPUT_FILE_NAME: &str = "gzip.bin";
     common::integration_test(SIGNATURE_TYPE, INPUT_FILE_NAME);
 }
+
+#[test]
+fn consumed_size() {
+    const SIGNATURE_TYPE: &str = "gzip";
+    const TRAILING_SIGNATURE_TYPE: &str = "xz";
+    const INPUT_FILE_NAME: &str = "gzip_consumed_size.bin";
+    const COMPRESSED_SIZE: usize = 130578;
+    common::consumed_size_test(
+        SIGNATURE_TYPE,
+        TRAILING_SIGNATURE_TYPE,
+        INPUT_FILE_NAME,
+        COMPRESSED_SIZE,
+    );
+}
+
+#[test]
+fn preview() {
+    let mut binwalker = binwalk::Binwalk::configure(
+        None,
+        None,
+        Some(vec!["gzip".to_string()]),
+        None,
+        None,
+        false,
+    )
+    .expect("Binwalk initialization failed");
+
+    let file_data = std::fs::read("tests/inputs/gzip.bin").expect("Failed to read input file");
+
+    // Previews are disabled by default
+    assert_eq!(binwalker.scan(&file_data)[0].preview, None);
+
+    binwalker.preview_size = 4;
+    assert_eq!(
+        binwalker.scan(&file_data)[0].preview.as_deref(),
+        Some("1f 8b 08 08  |....|")
+    );
+}
@@ -0,0 +1,75 @@
+use crate::common::assert_results_ok;
+use binwalk::extractors::ihex::extract_ihex;
+
+mod common;
+
+/// Offsets and sizes of the two Intel HEX images in the test file
+const FIRST_IMAGE_OFFSET: usize = 16;
+const FIRST_IMAGE_SIZE: usize = 215;
+const SECOND_IMAGE_OFFSET: usize = 231;
+const SECOND_IMAGE_SIZE: usize = 113;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "ihex";
+    const INPUT_FILE_NAME: &str = "ihex.bin";
+
+    let results = common::run_binwalk(SIGNATURE_TYPE, INPUT_FILE_NAME);
+
+    assert_eq!(results.file_map[0].size, FIRST_IMAGE_SIZE);
+    assert!(
+        results.file_map[0]
+            .description
+            .contains("address span: 0x8000000-0x8000048")
+    );
+    assert_eq!(results.file_map[1].size, SECOND_IMAGE_SIZE);
+    assert!(
+        results.file_map[1]
+            .description
+            .contains("address span: 0x1000-0x1012")
+    );
+
+    assert_results_ok(
+        results,
+        vec![FIRST_IMAGE_OFFSET, SECOND_IMAGE_OFFSET],
+        vec![FIRST_IMAGE_OFFSET, SECOND_IMAGE_OFFSET],
+    );
+}
+
+#[test]
+fn extract_image() {
+    let file_data = common::read_input_file("ihex.bin");
+
+    let output_directory = common::output_directory("ihex", "extract_image");
+
+    let result = extract_ihex(
+        &file_data,
+        FIRST_IMAGE_OFFSET,
+        Some(&output_directory.display().to_string()),
+    );
+
+    assert!(result.success);
+    assert_eq!(result.size, Some(FIRST_IMAGE_SIZE));
+
+    // The gap between the two data regions is filled with 0xFF
+    let mut expected: Vec<u8> = (0..48).collect();
+    expected.extend([0xFF; 16]);
+    expected.extend([0xAA; 8]);
+
+    assert_eq!(
+        std::fs::read(output_directory.join("ihex.bin")).unwrap(),
+        expected
+    );
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+#[test]
+fn bad_checksum() {
+    let mut file_data = common::read_input_file("ihex.bin");
+
+    // Corrupt a data byte in the first data record
+    file_data[FIRST_IMAGE_OFFSET + 27] = b'F';
+
+    assert!(!extract_ihex(&file_data, FIRST_IMAGE_OFFSET, None).success);
+}
@@ -1,5 +1,10 @@
+use binwalk::extractors::jpeg::extract_jpeg_image;
+
 mod common;
 
+/// Size of the JPEG image at the start of jpeg_consumed_size.bin
+const IMAGE_SIZE: usize = 14573;
+
 #[test]
 fn integration_test() {
     const SIGNATURE_TYPE: &str = "jpeg";
@@ -15,3 +20,31 @@ fn integration_test() {
         expected_extraction_offsets,
     );
 }
+
+#[test]
+fn consumed_size() {
+    const SIGNATURE_TYPE: &str = "jpeg";
+    const TRAILING_SIGNATURE_TYPE: &str = "gzip";
+    const INPUT_FILE_NAME: &str = "jpeg_consumed_size.bin";
+    common::consumed_size_test(
+        SIGNATURE_TYPE,
+        TRAILING_SIGNATURE_TYPE,
+        INPUT_FILE_NAME,
+        IMAGE_SIZE,
+    );
+}
+
+#[test]
+fn fill_bytes() {
+    // Offset of the first marker following the start of image marker
+    const FIRST_MARKER_OFFSET: usize = 2;
+    const FILL_BYTES: usize = 3;
+
+    let mut file_data = common::read_input_file("jpeg_consumed_size.bin");
+    file_data.splice(FIRST_MARKER_OFFSET..FIRST_MARKER_OFFSET, [0xFF; FILL_BYTES]);
+
+    // Fill bytes preceding a marker are part of the image
+    let dry_run = extract_jpeg_image(&file_data, 0, None);
+    assert!(dry_run.success);
+    assert_eq!(dry_run.size, Some(IMAGE_SIZE + FILL_BYTES));
+}
@@ -0,0 +1,116 @@
+use crate::common::assert_results_ok;
+use binwalk::common::crc16;
+use binwalk::extractors::lha::extract_lha_archive;
+
+mod common;
+
+/// Offset of the archive in the test file
+const ARCHIVE_OFFSET: usize = 16;
+/// Size of the archive, including the end of archive marker
+const ARCHIVE_SIZE: usize = 2916;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "lha";
+    const INPUT_FILE_NAME: &str = "lha.bin";
+
+    let results = common::run_binwalk(SIGNATURE_TYPE, INPUT_FILE_NAME);
+
+    assert_eq!(results.file_map[0].size, ARCHIVE_SIZE);
+
+    assert_results_ok(results, vec![ARCHIVE_OFFSET], vec![ARCHIVE_OFFSET]);
+}
+
+#[test]
+fn extract_members() {
+    let file_data = common::read_input_file("lha.bin");
+
+    let output_directory = common::output_directory("lha", "extract_members");
+
+    let result = extract_lha_archive(
+        &file_data,
+        ARCHIVE_OFFSET,
+        Some(&output_directory.display().to_string()),
+    );
+
+    assert!(result.success);
+    assert_eq!(result.size, Some(ARCHIVE_SIZE));
+
+    let read = |path: &str| std::fs::read(output_directory.join(path)).unwrap();
+
+    let text: Vec<u8> = (0..300)
+        .flat_map(|i| format!("line {i:04} of an lha compressed text file\n").into_bytes())
+        .collect();
+    let binary: Vec<u8> = (0..6000)
+        .map(|i: usize| ((i * 13 + (i >> 8)) % 256) as u8)
+        .collect();
+
+    // Level 0 headers, stored and -lh5- members, with an MS-DOS path separator
+    assert_eq!(read("stored.txt"), b"stored file\n");
+    assert_eq!(read("dos/path.txt"), text);
+
+    // Level 1 headers, -lh1- and -lh6- members, with a directory name extended header
+    assert_eq!(read("lh1.txt"), text);
+    assert_eq!(read("level1/dir/nested.bin"), binary);
+
+    // Level 2 headers, a directory entry and a -lh7- member
+    assert!(output_directory.join("empty").is_dir());
+    assert_eq!(read("level2/lh7.txt"), text);
+
+    // Members with a bad CRC are skipped
+    assert!(!output_directory.join("corrupt.txt").exists());
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
+
+/// Builds a level 0 LHA member, consisting of its header and the compressed data
+fn lha_member(method: &str, name: &str, compressed_data: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut header: Vec<u8> = method.as_bytes().to_vec();
+    header.extend((compressed_data.len() as u32).to_le_bytes());
+    header.extend((data.len() as u32).to_le_bytes());
+    // Timestamp, attributes and header level
+    header.extend([0, 0, 0, 0, 0x20, 0]);
+    header.push(name.len() as u8);
+    header.extend(name.as_bytes());
+    header.extend(crc16(data).to_le_bytes());
+
+    let checksum = header
+        .iter()
+        .fold(0_u8, |sum, byte| sum.wrapping_add(*byte));
+
+    [
+        vec![header.len() as u8, checksum],
+        header,
+        compressed_data.to_vec(),
+    ]
+    .concat()
+}
+
+#[test]
+fn unsupported_methods() {
+    const STORED_DATA: &[u8] = b"stored file\n";
+
+    // The experimental -lh2- and -lh3- methods are not supported. Their members hold uncompressed data with a valid
+    // CRC, so that they are only skipped because of their compression method.
+    let mut file_data: Vec<u8> = vec![];
+    file_data.extend(lha_member("-lh2-", "lh2.txt", STORED_DATA, STORED_DATA));
+    file_data.extend(lha_member("-lh3-", "lh3.txt", STORED_DATA, STORED_DATA));
+    file_data.extend(lha_member("-lh0-", "stored.txt", STORED_DATA, STORED_DATA));
+    file_data.push(0);
+
+    let output_directory = common::output_directory("lha", "unsupported_methods");
+
+    let result = extract_lha_archive(&file_data, 0, Some(&output_directory.display().to_string()));
+
+    // Unsupported members are skipped, without affecting the rest of the archive
+    assert!(result.success);
+    assert_eq!(result.size, Some(file_data.len()));
+    assert!(!output_directory.join("lh2.txt").exists());
+    assert!(!output_directory.join("lh3.txt").exists());
+    assert_eq!(
+        std::fs::read(output_directory.join("stored.txt")).unwrap(),
+        STORED_DATA
+    );
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
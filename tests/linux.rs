@@ -0,0 +1,43 @@
+use crate::common::assert_results_ok;
+use binwalk::extractors::linux::extract_vmlinuz;
+
+mod common;
+
+/// Size of the bzImage in the test file, as reported by its setup header
+const IMAGE_SIZE: usize = 1600;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "linux_boot_image";
+    const INPUT_FILE_NAME: &str = "linux_boot_image.bin";
+
+    let results = common::run_binwalk(SIGNATURE_TYPE, INPUT_FILE_NAME);
+
+    assert_eq!(results.file_map[0].size, IMAGE_SIZE);
+
+    assert_results_ok(results, vec![0], vec![0]);
+}
+
+#[test]
+fn extract_vmlinux() {
+    let file_data = common::read_input_file("linux_boot_image.bin");
+
+    let output_directory = common::output_directory("linux", "extract_vmlinux");
+
+    let result = extract_vmlinuz(&file_data, 0, Some(&output_directory.display().to_string()));
+
+    assert!(result.success);
+    assert_eq!(result.size, Some(IMAGE_SIZE));
+
+    let kernel: Vec<u8> = (0..200)
+        .flat_map(|i| format!("vmlinux test kernel line {i:04}\n").into_bytes())
+        .collect();
+
+    assert_eq!(
+        std::fs::read(output_directory.join("vmlinux")).unwrap(),
+        kernel
+    );
+    assert!(!output_directory.join("decompressed.bin").exists());
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
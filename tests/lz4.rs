@@ -0,0 +1,60 @@
+mod common;
+
+#[test]
+fn consumed_size() {
+    const SIGNATURE_TYPE: &str = "lz4";
+    const TRAILING_SIGNATURE_TYPE: &str = "gzip";
+    const INPUT_FILE_NAME: &str = "lz4_consumed_size.bin";
+    const COMPRESSED_SIZE: usize = 250304;
+    common::consumed_size_test(
+        SIGNATURE_TYPE,
+        TRAILING_SIGNATURE_TYPE,
+        INPUT_FILE_NAME,
+        COMPRESSED_SIZE,
+    );
+}
+
+#[test]
+fn extract_from_stdin() {
+    use binwalk::extractors::common::{ExtractionConfig, execute};
+    use binwalk::extractors::lz4::lz4_extractor;
+    use binwalk::signatures::common::SignatureResult;
+
+    const COMPRESSED_SIZE: usize = 250304;
+
+    let test_directory = common::output_directory("lz4", "extract_from_stdin");
+    std::fs::create_dir_all(&test_directory).unwrap();
+
+    let file_data = common::read_input_file("lz4_consumed_size.bin");
+    let file_path = test_directory.join("lz4.bin").display().to_string();
+
+    let signature = SignatureResult {
+        name: "lz4".to_string(),
+        offset: 0,
+        size: COMPRESSED_SIZE,
+        ..Default::default()
+    };
+
+    // The compressed data is streamed to lz4, rather than carved to disk
+    let extractor = lz4_extractor();
+    assert!(extractor.use_stdin);
+
+    let result = execute(
+        &file_data,
+        &file_path,
+        &signature,
+        &Some(extractor),
+        &ExtractionConfig::default(),
+    );
+    assert!(result.success);
+    assert!(result.carved_file.is_none());
+
+    let output_files: Vec<_> = std::fs::read_dir(&result.output_directory)
+        .unwrap()
+        .flatten()
+        .map(|entry| entry.file_name())
+        .collect();
+    assert_eq!(output_files, vec!["decompressed.bin"]);
+
+    let _ = std::fs::remove_dir_all(&test_directory);
+}
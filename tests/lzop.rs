@@ -0,0 +1,23 @@
+mod common;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "lzop";
+    const INPUT_FILE_NAME: &str = "lzop.bin";
+    common::integration_test(SIGNATURE_TYPE, INPUT_FILE_NAME);
+}
+
+#[test]
+fn corrupted_block() {
+    use binwalk::extractors::lzop::lzop_decompress;
+
+    // Somewhere in the compressed data of the first block
+    const CORRUPTED_BYTE_OFFSET: usize = 1024;
+
+    let mut file_data = common::read_input_file("lzop.bin");
+
+    assert!(lzop_decompress(&file_data, 0, None).success);
+
+    file_data[CORRUPTED_BYTE_OFFSET] ^= 0xFF;
+    assert!(!lzop_decompress(&file_data, 0, None).success);
+}
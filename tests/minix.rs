@@ -0,0 +1,101 @@
+mod common;
+
+use binwalk::extractors::minix::extract_minix_filesystem;
+
+/// Size of the test file systems
+const IMAGE_SIZE: usize = 64 * 1024;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "minix";
+
+    for input_file_name in ["minix_v1.bin", "minix_v2.bin", "minix_v3.bin"] {
+        common::integration_test(SIGNATURE_TYPE, input_file_name);
+    }
+}
+
+#[test]
+fn consumed_size() {
+    // The file system size is calculated from the zone count, so that trailing data is found
+    common::consumed_size_test("minix", "gzip", "minix_consumed_size.bin", IMAGE_SIZE);
+}
+
+#[test]
+fn extract_file_tree() {
+    // Modification time and ownership of big.bin
+    const BIG_FILE_MTIME: u64 = 1577934245;
+    const BIG_FILE_UID: u32 = 1000;
+    const BIG_FILE_GID: u32 = 100;
+
+    // v1 uses 16 bit zone pointers and 14 character names, v2 uses 32 bit zone pointers and 30 character names,
+    // and v3 uses 32 bit inode numbers and 60 character names; the v3 file system is big endian
+    for (file_name, pointer_size) in [
+        ("minix_v1.bin", 2),
+        ("minix_v2.bin", 4),
+        ("minix_v3.bin", 4),
+    ] {
+        let file_data = common::read_input_file(file_name);
+
+        let output_directory = common::output_directory("minix", file_name);
+
+        let result =
+            extract_minix_filesystem(&file_data, 0, Some(&output_directory.display().to_string()));
+
+        assert!(result.success);
+        assert_eq!(result.size, Some(IMAGE_SIZE));
+
+        let read = |path: &str| std::fs::read(output_directory.join(path)).unwrap();
+
+        assert_eq!(read("hello.txt"), b"hello\n");
+        assert_eq!(read("dir/hardlink.txt"), b"hello\n");
+        assert_eq!(read("dir/sub/nested.txt"), b"nested file\n");
+
+        // big.bin spans the direct and single indirect zones
+        let big_file: Vec<u8> = (0..10240).map(|i| (i % 256) as u8).collect();
+        assert_eq!(read("big.bin"), big_file);
+
+        // The only data zone of sparse.bin is mapped by the double indirect zone; unmapped zones read as zeros
+        let sparse_blocks = 7 + (1024 / pointer_size) + 1;
+        let mut sparse_file = vec![0; sparse_blocks * 1024 + 3];
+        sparse_file[sparse_blocks * 1024..].copy_from_slice(b"end");
+        assert_eq!(read("sparse.bin"), sparse_file);
+
+        assert!(
+            std::fs::read_link(output_directory.join("link"))
+                .unwrap()
+                .ends_with("dir/sub/nested.txt")
+        );
+
+        // Device files are extracted as placeholder files
+        assert!(output_directory.join("null").exists());
+        assert!(output_directory.join("fifo").exists());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+            let mode = |path: &str| {
+                std::fs::metadata(output_directory.join(path))
+                    .unwrap()
+                    .permissions()
+                    .mode()
+                    & 0o777
+            };
+
+            assert_eq!(mode("hello.txt"), 0o600);
+            assert_eq!(mode("dir"), 0o750);
+
+            let big_file_metadata = std::fs::metadata(output_directory.join("big.bin")).unwrap();
+            assert_eq!(big_file_metadata.mtime() as u64, BIG_FILE_MTIME);
+
+            // Ownership can only be restored when running as root; the output directory is owned by the current user
+            let current_uid = std::fs::metadata(&output_directory).unwrap().uid();
+            if current_uid == 0 {
+                assert_eq!(big_file_metadata.uid(), BIG_FILE_UID);
+                assert_eq!(big_file_metadata.gid(), BIG_FILE_GID);
+            }
+        }
+
+        let _ = std::fs::remove_dir_all(&output_directory);
+    }
+}
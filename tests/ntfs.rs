@@ -0,0 +1,101 @@
+mod common;
+
+use binwalk::extractors::ntfs::extract_ntfs_partition;
+
+/// Offset and size of the NTFS partition in the test file
+const PARTITION_OFFSET: usize = 0x200;
+const PARTITION_SIZE: usize = 256 * 512;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "ntfs";
+    const INPUT_FILE_NAME: &str = "ntfs.bin";
+
+    let expected_signature_offsets: Vec<usize> = vec![PARTITION_OFFSET];
+    let expected_extraction_offsets: Vec<usize> = vec![PARTITION_OFFSET];
+
+    let results = common::run_binwalk(SIGNATURE_TYPE, INPUT_FILE_NAME);
+    assert_eq!(results.file_map[0].size, PARTITION_SIZE);
+
+    common::assert_results_ok(
+        results,
+        expected_signature_offsets,
+        expected_extraction_offsets,
+    );
+}
+
+#[test]
+fn extract_files() {
+    let file_data = common::read_input_file("ntfs.bin");
+
+    let output_directory = common::output_directory("ntfs", "extract_files");
+
+    let result = extract_ntfs_partition(
+        &file_data,
+        PARTITION_OFFSET,
+        Some(&output_directory.display().to_string()),
+    );
+
+    assert!(result.success);
+    assert_eq!(result.size, Some(PARTITION_SIZE));
+
+    let read = |path: &str| std::fs::read(output_directory.join(path)).unwrap();
+
+    // Resident data, and a named alternate data stream
+    assert_eq!(read("hello.txt"), b"hello\n");
+    assert_eq!(read("hello.txt:secret"), b"hidden stream\n");
+
+    // Non-resident data in fragmented runs
+    let big: Vec<u8> = (0..3000).map(|i| ((i * 7) % 256) as u8).collect();
+    assert_eq!(read("Sub Directory/big.bin"), big);
+
+    // Sparse runs read as zeros
+    assert_eq!(
+        read("sparse.bin"),
+        [vec![b'A'; 1024], vec![0; 2048], vec![b'B'; 300]].concat()
+    );
+
+    // LZNT1 compressed units, an unallocated unit, and a unit stored uncompressed
+    let text: Vec<u8> = (0..1000)
+        .flat_map(|i| format!("line {i:04} of a compressed file\n").into_bytes())
+        .collect();
+    let compressed_file = read("compressed.txt");
+    assert_eq!(compressed_file.len(), (3 * 8192) + 1000);
+    assert_eq!(compressed_file[..8192], text[..8192]);
+    assert!(compressed_file[8192..16384].iter().all(|byte| *byte == 0));
+    let stored_unit_offset = PARTITION_OFFSET + (120 * 512);
+    assert_eq!(
+        compressed_file[16384..24576],
+        file_data[stored_unit_offset..stored_unit_offset + 8192]
+    );
+    assert_eq!(compressed_file[24576..], text[..1000]);
+
+    // Long names are preferred over DOS names
+    assert_eq!(read("Sub Directory/long file name.txt"), b"long name\n");
+    assert!(!output_directory.join("Sub Directory/LONGFI~1.TXT").exists());
+
+    // Attributes stored in extension records
+    assert_eq!(read("extended.txt"), b"extension record data\n");
+
+    // Data beyond the initialized size reads as zeros
+    assert_eq!(
+        read("uninitialized.bin"),
+        [vec![b'I'; 500], vec![0; 500]].concat()
+    );
+
+    // Deleted files and metadata files are not extracted
+    assert!(!output_directory.join("deleted.txt").exists());
+    assert!(!output_directory.join("$MFT").exists());
+    assert!(!output_directory.join("$Meta1").exists());
+
+    // Modification times are restored
+    let modified = std::fs::metadata(output_directory.join("hello.txt"))
+        .unwrap()
+        .modified()
+        .unwrap()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap();
+    assert_eq!(modified.as_secs(), 1577934245);
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
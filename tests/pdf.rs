@@ -1,17 +1,58 @@
 mod common;
 
+use binwalk::extractors::pdf::extract_pdf_streams;
+
 #[test]
 fn integration_test() {
     const SIGNATURE_TYPE: &str = "pdf";
     const INPUT_FILE_NAME: &str = "pdf.bin";
+    common::integration_test(SIGNATURE_TYPE, INPUT_FILE_NAME);
+}
+
+#[test]
+fn decoded_streams() {
+    // Size of the PDF, including its incremental update, but not the trailing data
+    const PDF_SIZE: usize = 1231;
+
+    let file_data = common::read_input_file("pdf_streams.bin");
 
-    let expected_signature_offsets: Vec<usize> = vec![0];
-    let expected_extraction_offsets: Vec<usize> = vec![];
+    let output_directory = common::output_directory("pdf", "decoded_streams");
 
-    let results = common::run_binwalk(SIGNATURE_TYPE, INPUT_FILE_NAME);
-    common::assert_results_ok(
-        results,
-        expected_signature_offsets,
-        expected_extraction_offsets,
+    let result = extract_pdf_streams(&file_data, 0, Some(&output_directory.display().to_string()));
+
+    assert!(result.success);
+    assert_eq!(result.size, Some(PDF_SIZE));
+
+    // (output file name, expected decoded stream data)
+    let expected_files = vec![
+        // Embedded file, Flate encoded, named by its file specification
+        ("firmware.bin", b"Embedded firmware blob\n".repeat(20)),
+        // ASCIIHex encoded
+        ("object_5_0.bin", b"hex encoded stream data".to_vec()),
+        // LZW encoded
+        ("object_6_0.bin", b"TOBEORNOTTOBEORTOBEORNOT#".repeat(10)),
+        // Flate encoded, with a PNG predictor
+        (
+            "object_7_0.bin",
+            (0..5u8)
+                .flat_map(|row| (0..4u8).map(move |column| row * 3 + column))
+                .collect(),
+        ),
+        // Defined in an incremental update
+        ("object_8_0.bin", b"AHx stream, updated".to_vec()),
+    ];
+
+    assert_eq!(
+        std::fs::read_dir(&output_directory).unwrap().count(),
+        expected_files.len()
     );
+
+    for (file_name, file_data) in expected_files {
+        assert_eq!(
+            std::fs::read(output_directory.join(file_name)).unwrap(),
+            file_data
+        );
+    }
+
+    let _ = std::fs::remove_dir_all(&output_directory);
 }
@@ -0,0 +1,55 @@
+mod common;
+
+use binwalk::extractors::pe::extract_pe_resources;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "pe";
+    const INPUT_FILE_NAME: &str = "pe.bin";
+    common::integration_test(SIGNATURE_TYPE, INPUT_FILE_NAME);
+}
+
+#[test]
+fn resources_and_overlay() {
+    // Size of the PE image, excluding overlay data
+    const IMAGE_SIZE: usize = 1024;
+
+    let test_cases = vec![
+        (
+            "resources/RT_MANIFEST/1_1033.xml",
+            b"<?xml version=\"1.0\"?><assembly manifestVersion=\"1.0\"/>".to_vec(),
+        ),
+        (
+            "resources/RT_RCDATA/101_1033.bin",
+            b"RCDATA blob stored in the resource directory".to_vec(),
+        ),
+        (
+            "resources/CUSTOM/BLOB_0.bin",
+            b"custom named resource".to_vec(),
+        ),
+    ];
+
+    let file_data = common::read_input_file("pe.bin");
+
+    let output_directory = common::output_directory("pe", "resources_and_overlay");
+
+    let result = extract_pe_resources(&file_data, 0, Some(&output_directory.display().to_string()));
+
+    // The reported size includes the overlay, so that the overlay is not scanned as a separate file
+    assert!(result.success);
+    assert_eq!(result.size, Some(file_data.len()));
+
+    for (resource_file, resource_data) in test_cases {
+        assert_eq!(
+            std::fs::read(output_directory.join(resource_file)).unwrap(),
+            resource_data
+        );
+    }
+
+    assert_eq!(
+        std::fs::read(output_directory.join("overlay.bin")).unwrap(),
+        file_data[IMAGE_SIZE..]
+    );
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
@@ -15,3 +15,17 @@ fn integration_test() {
         expected_extraction_offsets,
     );
 }
+
+#[test]
+fn consumed_size() {
+    const SIGNATURE_TYPE: &str = "png";
+    const TRAILING_SIGNATURE_TYPE: &str = "gzip";
+    const INPUT_FILE_NAME: &str = "png_consumed_size.bin";
+    const IMAGE_SIZE: usize = 67;
+    common::consumed_size_test(
+        SIGNATURE_TYPE,
+        TRAILING_SIGNATURE_TYPE,
+        INPUT_FILE_NAME,
+        IMAGE_SIZE,
+    );
+}
@@ -1,8 +1,173 @@
+use binwalk::extractors::squashfs::extract_squashfs_v4;
+use binwalk::signatures::squashfs::squashfs_parser;
+
 mod common;
 
+/// Offsets of the block size, block log, and flags fields in the SquashFSv4 superblock
+const BLOCK_SIZE_OFFSET: usize = 12;
+const BLOCK_LOG_OFFSET: usize = 22;
+const FLAGS_OFFSET: usize = 24;
+/// Offset of the inode table pointer in the SquashFSv4 superblock
+const INODE_TABLE_OFFSET: usize = 64;
+
+fn read_input() -> Vec<u8> {
+    common::read_input_file("squashfs.bin")
+}
+
+/// Extracts a SquashFS image from the tests/inputs directory with the internal extractor, returning the path to the
+/// extracted squashfs-root directory
+fn extract_input(input_file_name: &str) -> std::path::PathBuf {
+    let file_data = common::read_input_file(input_file_name);
+
+    let output_directory = common::output_directory("squashfs", input_file_name);
+
+    let result = extract_squashfs_v4(&file_data, 0, Some(&output_directory.display().to_string()));
+
+    assert!(result.success);
+    assert_eq!(
+        result.size,
+        Some(squashfs_parser(&file_data, 0).unwrap().size)
+    );
+
+    output_directory.join("squashfs-root")
+}
+
 #[test]
 fn integration_test() {
     const SIGNATURE_TYPE: &str = "squashfs";
     const INPUT_FILE_NAME: &str = "squashfs.bin";
     common::integration_test(SIGNATURE_TYPE, INPUT_FILE_NAME);
 }
+
+#[test]
+fn vendor_magic() {
+    const SIGNATURE_TYPE: &str = "squashfs";
+
+    // Identical to squashfs.bin, but with the DD-WRT magic bytes
+    common::integration_test(SIGNATURE_TYPE, "squashfs_ddwrt.bin");
+    // Identical to squashfs.bin, but with the nonstandard magic bytes written by Broadcom SDK builds
+    common::integration_test(SIGNATURE_TYPE, "squashfs_broadcom.bin");
+
+    for input_file_name in [
+        "squashfs.bin",
+        "squashfs_ddwrt.bin",
+        "squashfs_broadcom.bin",
+    ] {
+        let squashfs_root = extract_input(input_file_name);
+        let lsb_release = std::fs::read_to_string(squashfs_root.join("lsb-release")).unwrap();
+        assert!(lsb_release.starts_with("DISTRIB_ID=Ubuntu\n"));
+    }
+}
+
+#[test]
+fn vendor_block_size() {
+    // XZ compressed images with a 3000 byte block size, a block log of 17, and undefined flags, in both byte orders
+    for input_file_name in ["squashfs_nonstandard.bin", "squashfs_nonstandard_be.bin"] {
+        let file_data = common::read_input_file(input_file_name);
+        let result = squashfs_parser(&file_data, 0).unwrap();
+        assert!(result.description.contains("compression: xz"));
+        assert!(result.description.ends_with(
+            "vendor quirks: non-power-of-two block size, block size and block log mismatch, out-of-spec block size, undefined flags 0x10C0"
+        ));
+
+        let squashfs_root = extract_input(input_file_name);
+
+        // busybox spans a compressed block, a sparse block, an uncompressed block, and a fragment
+        let busybox = std::fs::read(squashfs_root.join("bin").join("busybox")).unwrap();
+        let text: Vec<u8> = (0..120)
+            .flat_map(|i| format!("vendor firmware line {i:05}\n").into_bytes())
+            .take(3000)
+            .collect();
+        assert_eq!(busybox.len(), 9996);
+        assert_eq!(&busybox[..3000], text);
+        assert!(busybox[3000..6000].iter().all(|byte| *byte == 0));
+        assert!(busybox.ends_with(b"tail of busybox\nEND\n"));
+
+        assert!(
+            std::fs::symlink_metadata(squashfs_root.join("bin").join("sh"))
+                .unwrap()
+                .is_symlink()
+        );
+
+        // hostname.bak is a hard link to hostname, and shares its fragment with the tail end of busybox
+        for name in ["hostname", "hostname.bak"] {
+            assert_eq!(
+                std::fs::read(squashfs_root.join("etc").join(name)).unwrap(),
+                b"vendor-router\n"
+            );
+        }
+    }
+}
+
+#[test]
+fn corrupt_metadata() {
+    let mut file_data = read_input();
+    file_data[INODE_TABLE_OFFSET] += 1;
+    assert!(!extract_squashfs_v4(&file_data, 0, None).success);
+}
+
+#[test]
+fn standard_image() {
+    let result = squashfs_parser(&read_input(), 0).unwrap();
+    assert!(!result.description.contains("vendor quirks"));
+}
+
+#[test]
+fn vendor_quirks() {
+    let file_data = common::read_input_file("squashfs_ddwrt.bin");
+    let result = squashfs_parser(&file_data, 0).unwrap();
+    assert!(
+        result
+            .description
+            .ends_with("vendor quirks: vendor magic 'hsqt'")
+    );
+
+    let file_data = common::read_input_file("squashfs_broadcom.bin");
+    let result = squashfs_parser(&file_data, 0).unwrap();
+    assert!(
+        result
+            .description
+            .ends_with("vendor quirks: vendor magic 'shsq'")
+    );
+
+    // Block log which disagrees with the block size, but the superblock table pointers are sane
+    let mut file_data = read_input();
+    file_data[BLOCK_LOG_OFFSET] = 16;
+    let result = squashfs_parser(&file_data, 0).unwrap();
+    assert!(
+        result
+            .description
+            .ends_with("vendor quirks: block size and block log mismatch")
+    );
+
+    // Block log which disagrees with the block size, and nothing else to vouch for the header
+    file_data[INODE_TABLE_OFFSET..INODE_TABLE_OFFSET + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(squashfs_parser(&file_data, 0).is_err());
+
+    // Non-power-of-two block size, which is also smaller than the specification permits
+    let mut file_data = read_input();
+    file_data[BLOCK_SIZE_OFFSET..BLOCK_SIZE_OFFSET + 4].copy_from_slice(&3000_u32.to_le_bytes());
+    file_data[BLOCK_LOG_OFFSET] = 11;
+    let result = squashfs_parser(&file_data, 0).unwrap();
+    assert!(result.description.contains("block size: 3000"));
+    assert!(
+        result
+            .description
+            .ends_with("vendor quirks: non-power-of-two block size, out-of-spec block size")
+    );
+
+    // Flags undefined by the specification
+    let mut file_data = read_input();
+    file_data[FLAGS_OFFSET + 1] |= 0x80;
+    let result = squashfs_parser(&file_data, 0).unwrap();
+    assert!(
+        result
+            .description
+            .ends_with("vendor quirks: undefined flags 0x80C0")
+    );
+
+    // Insane block sizes are still rejected
+    let mut file_data = read_input();
+    file_data[BLOCK_SIZE_OFFSET..BLOCK_SIZE_OFFSET + 4].copy_from_slice(&0_u32.to_le_bytes());
+    assert!(squashfs_parser(&file_data, 0).is_err());
+}
@@ -0,0 +1,36 @@
+use binwalk::extractors::srec::extract_srec;
+
+mod common;
+
+/// Size of the S-records in the test file, up to and including the termination record
+const SREC_SIZE: usize = 191;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "srecord";
+    const INPUT_FILE_NAME: &str = "srec.bin";
+    common::integration_test(SIGNATURE_TYPE, INPUT_FILE_NAME);
+}
+
+#[test]
+fn extract_image() {
+    let file_data = common::read_input_file("srec.bin");
+
+    let output_directory = common::output_directory("srec", "extract_image");
+
+    let result = extract_srec(&file_data, 0, Some(&output_directory.display().to_string()));
+
+    assert!(result.success);
+    assert_eq!(result.size, Some(SREC_SIZE));
+
+    let mut expected: Vec<u8> = (0..40).map(|i: u8| i.wrapping_mul(7)).collect();
+    expected.resize(0x100, 0xFF);
+    expected.extend([0x55; 4]);
+
+    assert_eq!(
+        std::fs::read(output_directory.join("s-record.bin")).unwrap(),
+        expected
+    );
+
+    let _ = std::fs::remove_dir_all(&output_directory);
+}
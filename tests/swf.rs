@@ -0,0 +1,75 @@
+use crate::common::assert_results_ok;
+use binwalk::extractors::swf::swf_decompress;
+
+mod common;
+
+/// Offsets and sizes of the zlib and LZMA compressed SWF files in the test file
+const CWS_FILE: (usize, usize) = (0, 80);
+const ZWS_FILE: (usize, usize) = (96, 89);
+/// Uncompressed size of both SWF files
+const FILE_LENGTH: usize = 237;
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "swf";
+    const INPUT_FILE_NAME: &str = "swf.bin";
+
+    let results = common::run_binwalk(SIGNATURE_TYPE, INPUT_FILE_NAME);
+
+    // Reported sizes are the sizes of the compressed files
+    assert_eq!(results.file_map[0].size, CWS_FILE.1);
+    assert_eq!(results.file_map[1].size, ZWS_FILE.1);
+
+    assert_results_ok(
+        results,
+        vec![CWS_FILE.0, ZWS_FILE.0],
+        vec![CWS_FILE.0, ZWS_FILE.0],
+    );
+}
+
+#[test]
+fn decompress_files() {
+    let file_data = common::read_input_file("swf.bin");
+
+    let mut decompressed_files = vec![];
+
+    for (offset, size) in [CWS_FILE, ZWS_FILE] {
+        let output_directory = common::output_directory("swf", &format!("decompress_{offset}"));
+
+        let result = swf_decompress(
+            &file_data,
+            offset,
+            Some(&output_directory.display().to_string()),
+        );
+
+        assert!(result.success);
+        assert_eq!(result.size, Some(size));
+
+        let decompressed_file = std::fs::read(output_directory.join("decompressed.swf")).unwrap();
+
+        // Decompressed files have an uncompressed SWF header, with the compressed file's version and file length
+        assert_eq!(&decompressed_file[..3], b"FWS");
+        assert_eq!(decompressed_file[3], file_data[offset + 3]);
+        assert_eq!(decompressed_file.len(), FILE_LENGTH);
+        assert_eq!(
+            &decompressed_file[4..8],
+            (FILE_LENGTH as u32).to_le_bytes().as_slice()
+        );
+
+        decompressed_files.push(decompressed_file[4..].to_vec());
+    }
+
+    // Both files have the same contents
+    assert_eq!(decompressed_files[0], decompressed_files[1]);
+}
+
+#[test]
+fn length_mismatch() {
+    let mut file_data = common::read_input_file("swf.bin");
+
+    // The body must decompress to exactly the file length in the SWF header
+    for (offset, _) in [CWS_FILE, ZWS_FILE] {
+        file_data[offset + 4] += 1;
+        assert!(!swf_decompress(&file_data, offset, None).success);
+    }
+}
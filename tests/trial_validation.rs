@@ -0,0 +1,72 @@
+use binwalk::Binwalk;
+use binwalk::extractors::common::Extractor;
+use binwalk::extractors::zlib::{zlib_decompress, zlib_trial};
+use binwalk::signatures::common::{CONFIDENCE_HIGH, Signature, SignatureError, SignatureResult};
+
+/// Accepts every magic match
+fn accept_all_parser(_file_data: &[u8], offset: usize) -> Result<SignatureResult, SignatureError> {
+    Ok(SignatureResult {
+        offset,
+        size: 4,
+        confidence: CONFIDENCE_HIGH,
+        description: "Test data".to_string(),
+        ..Default::default()
+    })
+}
+
+/// Rejects every trial decode
+fn reject_all_trial(_file_data: &[u8], _offset: usize, _trial_size: usize) -> bool {
+    false
+}
+
+#[test]
+fn trial_validation_policy() {
+    let signature = Signature {
+        name: "trial_test".to_string(),
+        short: false,
+        magic_offset: 0,
+        always_display: false,
+        magic: vec![b"TRIAL".to_vec()],
+        parser: accept_all_parser,
+        description: "Test data".to_string(),
+        extractor: Some(Extractor {
+            trial_decoder: Some(reject_all_trial),
+            ..Default::default()
+        }),
+        trial_validation: true,
+    };
+
+    let mut binwalker = Binwalk::configure(
+        None,
+        None,
+        Some(vec!["trial_test".to_string()]),
+        None,
+        Some(vec![signature]),
+        false,
+    )
+    .expect("Binwalk initialization failed");
+
+    let file_data = b"\x00\x00TRIAL\x00\x00";
+
+    // Matches that fail the trial decode are never passed to the signature parser
+    assert!(binwalker.scan(file_data).is_empty());
+
+    // A trial size of 0 disables trial validation
+    binwalker.trial_size = 0;
+    assert_eq!(binwalker.scan(file_data).len(), 1);
+}
+
+#[test]
+fn zlib_trial_decode() {
+    let file_data = std::fs::read("tests/inputs/zlib.bin").expect("Failed to read input file");
+    let zlib_data = &file_data[64..64 + 183];
+
+    assert!(zlib_trial(zlib_data, 0, binwalk::DEFAULT_TRIAL_SIZE));
+    assert!(!zlib_trial(b"\x78\x9c\xff\xff\xff\xff\xff\xff", 0, 16));
+
+    // Only the first trial_size bytes are decoded, so a truncated stream passes the trial, but not the full decode
+    let truncated_data = &zlib_data[..100];
+    assert!(zlib_trial(truncated_data, 0, 16));
+    assert!(!zlib_trial(truncated_data, 0, binwalk::DEFAULT_TRIAL_SIZE));
+    assert!(!zlib_decompress(truncated_data, 0, None).success);
+}
@@ -0,0 +1,140 @@
+use crate::common::assert_results_ok;
+use binwalk::extractors::uefi::extract_uefi_volume;
+use binwalk::structures::uefi::parse_uefi_volume_header;
+use std::path::PathBuf;
+
+mod common;
+
+/// Offsets and sizes of the two concatenated volumes in the test file
+const FIRST_VOLUME: (usize, usize) = (0x100, 0x1000);
+const SECOND_VOLUME: (usize, usize) = (0x1100, 0x200);
+
+fn read_input() -> Vec<u8> {
+    common::read_input_file("uefi_volume.bin")
+}
+
+fn output_directory(test_name: &str) -> PathBuf {
+    common::output_directory("uefi", test_name)
+}
+
+#[test]
+fn integration_test() {
+    const SIGNATURE_TYPE: &str = "uefi_pi_volume";
+    const INPUT_FILE_NAME: &str = "uefi_volume.bin";
+
+    let results = common::run_binwalk(SIGNATURE_TYPE, INPUT_FILE_NAME);
+
+    // Each volume is reported separately, with the size from its header
+    assert_eq!(results.file_map[0].size, FIRST_VOLUME.1);
+    assert_eq!(results.file_map[1].size, SECOND_VOLUME.1);
+
+    assert_results_ok(
+        results,
+        vec![FIRST_VOLUME.0, SECOND_VOLUME.0],
+        vec![FIRST_VOLUME.0, SECOND_VOLUME.0],
+    );
+}
+
+#[test]
+fn extract_files() {
+    let file_data = read_input();
+    let output_directory = output_directory("extract_files");
+
+    let result = extract_uefi_volume(
+        &file_data,
+        FIRST_VOLUME.0,
+        Some(&output_directory.display().to_string()),
+    );
+
+    assert!(result.success);
+    assert_eq!(result.size, Some(FIRST_VOLUME.1));
+
+    let read = |path: &str| std::fs::read(output_directory.join(path)).unwrap();
+    let pe32 = |tag: &str| [b"MZ".to_vec(), tag.repeat(20).into_bytes()].concat();
+
+    // Uncompressed, EFI compressed, and LZMA compressed drivers; the Tiano compressed file contains a TE image
+    for (file_number, name, tag) in [
+        (1, "PlainDriver", "A"),
+        (2, "CompressedDriver", "C"),
+        (3, "LzmaDriver", "L"),
+    ] {
+        let file_directory = format!("AAAAAAAA-0000-0000-0000-00000000000{file_number}");
+        assert_eq!(read(&format!("{file_directory}/section_0.pe32")), pe32(tag));
+        assert_eq!(
+            read(&format!("{file_directory}/section_1.ui")),
+            name.as_bytes()
+        );
+    }
+
+    assert_eq!(
+        read("AAAAAAAA-0000-0000-0000-000000000004/section_0.te"),
+        [b"VZ".to_vec(), "T".repeat(20).into_bytes()].concat()
+    );
+    assert_eq!(
+        read("AAAAAAAA-0000-0000-0000-000000000006/raw.bin"),
+        b"raw file contents"
+    );
+
+    // Files in nested volumes are extracted inside the directory of the file that contains the nested volume
+    assert_eq!(
+        read(
+            "AAAAAAAA-0000-0000-0000-000000000007/11111111-2222-3333-4444-555555555555/section_0.pe32"
+        ),
+        pe32("N")
+    );
+
+    // Pad files and deleted files are not extracted
+    assert!(
+        !output_directory
+            .join("AAAAAAAA-0000-0000-0000-000000000005")
+            .exists()
+    );
+    assert!(
+        !output_directory
+            .join("AAAAAAAA-0000-0000-0000-000000000008")
+            .exists()
+    );
+}
+
+#[test]
+fn extended_header() {
+    let file_data = read_input();
+
+    // The second volume has an extended header, which the first file follows
+    let volume_header = parse_uefi_volume_header(&file_data[SECOND_VOLUME.0..]).unwrap();
+    assert!(volume_header.checksum_valid);
+    assert_eq!(volume_header.files_offset, 96);
+
+    let output_directory = output_directory("extended_header");
+    let result = extract_uefi_volume(
+        &file_data,
+        SECOND_VOLUME.0,
+        Some(&output_directory.display().to_string()),
+    );
+
+    assert!(result.success);
+    assert_eq!(result.size, Some(SECOND_VOLUME.1));
+    assert!(
+        output_directory
+            .join("BBBBBBBB-0000-0000-0000-000000000001")
+            .join("section_0.pe32")
+            .exists()
+    );
+}
+
+#[test]
+fn checksum_mismatch() {
+    // Offset of the volume attributes field
+    const ATTRIBUTES_OFFSET: usize = 44;
+
+    let mut file_data = read_input();
+    file_data[FIRST_VOLUME.0 + ATTRIBUTES_OFFSET] ^= 0x01;
+
+    let volume_header = parse_uefi_volume_header(&file_data[FIRST_VOLUME.0..]).unwrap();
+    assert!(!volume_header.checksum_valid);
+
+    // Checksum mismatches are reported, but the volume is still extracted
+    let result = extract_uefi_volume(&file_data, FIRST_VOLUME.0, None);
+    assert!(result.success);
+    assert_eq!(result.size, Some(FIRST_VOLUME.1));
+}
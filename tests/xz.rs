@@ -0,0 +1,27 @@
+mod common;
+
+#[test]
+fn consumed_size() {
+    const SIGNATURE_TYPE: &str = "xz";
+    const TRAILING_SIGNATURE_TYPE: &str = "gzip";
+    const INPUT_FILE_NAME: &str = "xz_consumed_size.bin";
+    const COMPRESSED_SIZE: usize = 11464;
+    common::consumed_size_test(
+        SIGNATURE_TYPE,
+        TRAILING_SIGNATURE_TYPE,
+        INPUT_FILE_NAME,
+        COMPRESSED_SIZE,
+    );
+}
+
+#[test]
+fn decompress_input() {
+    const COMPRESSED_SIZE: usize = 11464;
+
+    let file_data =
+        std::fs::read("tests/inputs/xz_consumed_size.bin").expect("Failed to read input file");
+
+    // The XZ stream is followed by gzip data, so the file is not a single compressed stream
+    assert!(binwalk::common::decompress_input(&file_data).is_none());
+    assert!(binwalk::common::decompress_input(&file_data[..COMPRESSED_SIZE]).is_some());
+}
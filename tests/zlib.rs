@@ -0,0 +1,52 @@
+mod common;
+
+use binwalk::extractors::zlib::zlib_decompress;
+
+#[test]
+fn embedded_stream() {
+    const SIGNATURE_TYPE: &str = "zlib";
+    const INPUT_FILE_NAME: &str = "zlib.bin";
+    const ZLIB_OFFSET: usize = 64;
+    const COMPRESSED_SIZE: usize = 183;
+
+    let results = common::run_binwalk(SIGNATURE_TYPE, INPUT_FILE_NAME);
+
+    assert_eq!(results.file_map.len(), 1);
+    assert_eq!(results.file_map[0].size, COMPRESSED_SIZE);
+
+    common::assert_results_ok(results, vec![ZLIB_OFFSET], vec![ZLIB_OFFSET]);
+}
+
+#[test]
+fn consumed_size() {
+    const SIGNATURE_TYPE: &str = "zlib";
+    const TRAILING_SIGNATURE_TYPE: &str = "gzip";
+    const INPUT_FILE_NAME: &str = "zlib_consumed_size.bin";
+    const COMPRESSED_SIZE: usize = 183;
+    common::consumed_size_test(
+        SIGNATURE_TYPE,
+        TRAILING_SIGNATURE_TYPE,
+        INPUT_FILE_NAME,
+        COMPRESSED_SIZE,
+    );
+}
+
+#[test]
+fn false_positives() {
+    let file_data = std::fs::read("tests/inputs/zlib.bin").expect("Failed to read input file");
+    let zlib_data = &file_data[64..64 + 183];
+
+    assert!(zlib_decompress(zlib_data, 0, None).success);
+
+    // Streams with a corrupt checksum are rejected
+    let mut corrupt_checksum = zlib_data.to_vec();
+    *corrupt_checksum.last_mut().unwrap() ^= 1;
+    assert!(!zlib_decompress(&corrupt_checksum, 0, None).success);
+
+    // Truncated streams are rejected
+    assert!(!zlib_decompress(&zlib_data[..zlib_data.len() - 8], 0, None).success);
+
+    // Valid streams that decompress to too little data are indistinguishable from chance matches, and are rejected
+    let too_short = b"\x78\x9c\x4b\x4c\x4a\x06\x00\x02\x4d\x01\x27";
+    assert!(!zlib_decompress(too_short, 0, None).success);
+}
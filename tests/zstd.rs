@@ -0,0 +1,15 @@
+mod common;
+
+#[test]
+fn consumed_size() {
+    const SIGNATURE_TYPE: &str = "zstd";
+    const TRAILING_SIGNATURE_TYPE: &str = "gzip";
+    const INPUT_FILE_NAME: &str = "zstd_consumed_size.bin";
+    const COMPRESSED_SIZE: usize = 50371;
+    common::consumed_size_test(
+        SIGNATURE_TYPE,
+        TRAILING_SIGNATURE_TYPE,
+        INPUT_FILE_NAME,
+        COMPRESSED_SIZE,
+    );
+}